@@ -143,6 +143,32 @@ def load_properties(f, interestingprops):
         props[prop].append((d_lo, d_hi))
     return props
 
+def load_special_casing(f):
+    fetch(f)
+    lowerspecial = {}
+    upperspecial = {}
+
+    for line in fileinput.input(f):
+        line = line.split("#")[0]
+        fields = [x.strip() for x in line.split(";")]
+        # Skip blank lines and the language- or context-sensitive mappings,
+        # which carry a condition list as a fifth field.
+        if len(fields) < 4 or (len(fields) > 4 and fields[4] != ""):
+            continue
+        [code, lower, title, upper] = fields[:4]
+        code = int(code, 16)
+        lower = [int(c, 16) for c in lower.split()]
+        upper = [int(c, 16) for c in upper.split()]
+
+        # Only the mappings that expand to several characters are kept, the
+        # others are already covered by the simple mappings in UnicodeData.
+        if len(lower) > 1:
+            lowerspecial[code] = lower
+        if len(upper) > 1:
+            upperspecial[code] = upper
+
+    return (lowerspecial, upperspecial)
+
 def escape_char(c):
     if c <= 0xff:
         return "'\\x%2.2x'" % c
@@ -196,7 +222,7 @@ def emit_property_module(f, mod, tbl):
     f.write("}\n\n")
 
 
-def emit_conversions_module(f, lowerupper, upperlower):
+def emit_conversions_module(f, lowerupper, upperlower, lowerspecial, upperspecial):
     f.write("pub mod conversions {")
     f.write("""
     use cmp::{Equal, Less, Greater};
@@ -218,7 +244,23 @@ def emit_conversions_module(f, lowerupper, upperlower):
         }
     }
 
-    fn bsearch_case_table(c: char, table: &'static [(char, char)]) -> Option<uint> {
+    /// Returns the full lowercase mapping of `c`, padded with `'\\0'`.
+    pub fn to_lower_full(c: char) -> [char, ..3] {
+        match bsearch_case_table(c, LuLl_special_table) {
+            None        => [to_lower(c), '\\0', '\\0'],
+            Some(index) => { let (_, chars) = LuLl_special_table[index]; chars }
+        }
+    }
+
+    /// Returns the full uppercase mapping of `c`, padded with `'\\0'`.
+    pub fn to_upper_full(c: char) -> [char, ..3] {
+        match bsearch_case_table(c, LlLu_special_table) {
+            None        => [to_upper(c), '\\0', '\\0'],
+            Some(index) => { let (_, chars) = LlLu_special_table[index]; chars }
+        }
+    }
+
+    fn bsearch_case_table<T>(c: char, table: &'static [(char, T)]) -> Option<uint> {
         table.bsearch(|&(key, _)| {
             if c == key { Equal }
             else if key < c { Less }
@@ -229,6 +271,8 @@ def emit_conversions_module(f, lowerupper, upperlower):
 """);
     emit_caseconversion_table(f, "LuLl", upperlower)
     emit_caseconversion_table(f, "LlLu", lowerupper)
+    emit_specialcasing_table(f, "LuLl", lowerspecial)
+    emit_specialcasing_table(f, "LlLu", upperspecial)
     f.write("}\n")
 
 def emit_caseconversion_table(f, name, table):
@@ -241,6 +285,18 @@ def emit_caseconversion_table(f, name, table):
         ix += 1
     f.write("\n    ];\n\n")
 
+def emit_specialcasing_table(f, name, table):
+    f.write("    static %s_special_table : &'static [(char, [char, ..3])] = &[\n" % name)
+    sorted_table = sorted(table.iteritems(), key=operator.itemgetter(0))
+    ix = 0
+    for key, value in sorted_table:
+        value = value + [0] * (3 - len(value))
+        f.write(ch_prefix(ix))
+        f.write("(%s, [%s])" % (escape_char(key),
+                                 ", ".join(escape_char(c) for c in value)))
+        ix += 1
+    f.write("\n    ];\n\n")
+
 def format_table_content(f, content, indent):
     line = " "*indent
     first = True
@@ -476,7 +532,9 @@ def gen_core_unicode():
 
         props = load_properties("PropList.txt", ["White_Space"])
         emit_property_module(rf, "property", props)
-        emit_conversions_module(rf, lowerupper, upperlower)
+        (lowerspecial, upperspecial) = load_special_casing("SpecialCasing.txt")
+        emit_conversions_module(rf, lowerupper, upperlower,
+                                lowerspecial, upperspecial)
 
 def gen_std_unicode():
     r = "std_unicode.rs"
@@ -22,6 +22,7 @@ use core::ops::{Drop, Deref};
 use core::option::{Some, None, Option};
 use core::ptr;
 use core::ptr::RawPtr;
+use core::uint;
 use heap::deallocate;
 
 /// An atomically reference counted wrapper for shared state.
@@ -106,9 +107,75 @@ impl<T: Share + Send> Arc<T> {
     /// to the underlying data have been dropped, the data itself will be
     /// destroyed.
     pub fn downgrade(&self) -> Weak<T> {
-        // See the clone() impl for why this is relaxed
-        self.inner().weak.fetch_add(1, atomics::Relaxed);
-        Weak { _ptr: self._ptr }
+        // The weak count is briefly locked by `is_unique` by storing
+        // `uint::MAX` in it, in which case we have to wait for it to be
+        // unlocked. Otherwise this could be relaxed, see the clone() impl.
+        let inner = self.inner();
+        loop {
+            let n = inner.weak.load(atomics::Relaxed);
+            if n == uint::MAX { continue }
+            let old = inner.weak.compare_and_swap(n, n + 1, atomics::Acquire);
+            if old == n { return Weak { _ptr: self._ptr } }
+        }
+    }
+
+    /// Returns a mutable pointer to the inner contents if this is the only
+    /// `Arc` pointing to them and there are no `Weak` pointers to them.
+    ///
+    /// Returns `None` if the contents are shared, as they can't be safely
+    /// mutated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate sync;
+    ///
+    /// use sync::Arc;
+    ///
+    /// fn main() {
+    ///     let mut x = Arc::new(3u);
+    ///     *Arc::get_mut(&mut x).unwrap() = 4;
+    ///     assert_eq!(*x, 4);
+    ///
+    ///     let _y = x.clone();
+    ///     assert!(Arc::get_mut(&mut x).is_none());
+    /// }
+    /// ```
+    #[inline]
+    #[experimental]
+    pub fn get_mut<'a>(this: &'a mut Arc<T>) -> Option<&'a mut T> {
+        if Arc::is_unique(this) {
+            // This unsafety is ok because there are no other strong or weak
+            // pointers, and we require the Arc itself to be `mut`, so this is
+            // the only possible reference to the inner data.
+            let inner = unsafe { &mut *this._ptr };
+            Some(&mut inner.data)
+        } else {
+            None
+        }
+    }
+
+    // Determines whether `this` is the only reference (strong or weak) to
+    // the inner data.
+    //
+    // Checking the two counts one after the other isn't enough: a weak
+    // pointer may be upgraded and then dropped in between the loads, or
+    // another strong pointer may be downgraded and then dropped. To rule this
+    // out, the weak count is locked for the duration of the check by
+    // swapping it from 1 (no weak pointers) to `uint::MAX`. While it's locked
+    // no weak pointers exist and `downgrade` spins, so no new strong pointers
+    // can be created either unless another strong pointer already exists.
+    fn is_unique(this: &mut Arc<T>) -> bool {
+        let inner = this.inner();
+        if inner.weak.compare_and_swap(1, uint::MAX, atomics::Acquire) != 1 {
+            return false
+        }
+        // The acquire load synchronizes with the release decrements of other
+        // strong pointers being dropped, so any of their accesses to the data
+        // happen before our mutation.
+        let unique = inner.strong.load(atomics::Acquire) == 1;
+        inner.weak.store(1, atomics::Release);
+        unique
     }
 }
 
@@ -152,15 +219,44 @@ impl<T: Send + Share + Clone> Arc<T> {
     #[inline]
     #[experimental]
     pub fn make_unique<'a>(&'a mut self) -> &'a mut T {
-        if self.inner().strong.load(atomics::SeqCst) != 1 {
-            *self = Arc::new(self.deref().clone())
+        Arc::make_mut(self)
+    }
+
+    /// Returns a mutable pointer to the inner contents, cloning them into a
+    /// new `Arc` first if they are shared.
+    ///
+    /// The contents are shared if there are any other `Arc` or `Weak` pointers
+    /// to them. After cloning, `this` no longer points to the same data as
+    /// the other pointers, so any `Weak` pointers which only had `this` left
+    /// to upgrade to will fail to upgrade from then on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate sync;
+    ///
+    /// use sync::Arc;
+    ///
+    /// fn main() {
+    ///     let mut x = Arc::new(3u);
+    ///     let y = x.clone();
+    ///     *Arc::make_mut(&mut x) += 1;
+    ///     assert_eq!(*x, 4);
+    ///     assert_eq!(*y, 3);
+    /// }
+    /// ```
+    #[inline]
+    #[experimental]
+    pub fn make_mut<'a>(this: &'a mut Arc<T>) -> &'a mut T {
+        if !Arc::is_unique(this) {
+            *this = Arc::new(this.deref().clone())
         }
         // This unsafety is ok because we're guaranteed that the pointer
-        // returned is the *only* pointer that will ever be returned to T. Our
-        // reference count is guaranteed to be 1 at this point, and we required
+        // returned is the *only* pointer that will ever be returned to T. There
+        // are no other strong or weak pointers at this point, and we required
         // the Arc itself to be `mut`, so we're returning the only possible
         // reference to the inner data.
-        let inner = unsafe { &mut *self._ptr };
+        let inner = unsafe { &mut *this._ptr };
         &mut inner.data
     }
 }
@@ -265,6 +361,7 @@ impl<T: Share + Send> Drop for Weak<T> {
 mod tests {
     use std::clone::Clone;
     use std::comm::channel;
+    use std::iter::{range, Iterator};
     use std::mem::drop;
     use std::ops::Drop;
     use std::option::{Option, Some, None};
@@ -297,7 +394,7 @@ mod tests {
         let (tx, rx) = channel();
 
         task::spawn(proc() {
-            let arc_v: Arc<Vec<int>> = rx.recv();
+            let arc_v: Arc<Vec<int>> = rx.recv_unwrap();
             assert_eq!(*arc_v.get(3), 4);
         });
 
@@ -402,4 +499,110 @@ mod tests {
         assert!(canary.load(atomics::Acquire) == 1);
         drop(arc_weak);
     }
+
+    #[test]
+    fn get_mut() {
+        let mut x = Arc::new(3u);
+        *Arc::get_mut(&mut x).unwrap() = 4;
+        assert_eq!(*x, 4);
+        let y = x.clone();
+        assert!(Arc::get_mut(&mut x).is_none());
+        drop(y);
+        assert!(Arc::get_mut(&mut x).is_some());
+        let _w = x.downgrade();
+        assert!(Arc::get_mut(&mut x).is_none());
+    }
+
+    #[test]
+    fn get_mut_after_weak_dropped() {
+        let mut x = Arc::new(3u);
+        let w = x.downgrade();
+        let w2 = w.clone();
+        assert!(Arc::get_mut(&mut x).is_none());
+        drop(w);
+        assert!(Arc::get_mut(&mut x).is_none());
+        drop(w2);
+        *Arc::get_mut(&mut x).unwrap() = 5;
+        // the weak count was unlocked again by get_mut
+        let w = x.downgrade();
+        assert_eq!(*w.upgrade().unwrap(), 5);
+    }
+
+    #[test]
+    fn make_mut() {
+        let mut x = Arc::new(3u);
+        *Arc::make_mut(&mut x) = 4;
+        let mut y = x.clone();
+        *Arc::make_mut(&mut y) = 5;
+        assert_eq!(*x, 4);
+        assert_eq!(*y, 5);
+
+        // no clone is made once the data is unique
+        let p = &*x as *uint;
+        assert!(Arc::make_mut(&mut x) as *mut uint as *uint == p);
+    }
+
+    #[test]
+    fn make_mut_with_weak() {
+        let mut x = Arc::new(3u);
+        let w = x.downgrade();
+        *Arc::make_mut(&mut x) = 4;
+        // the data was cloned out from under the weak pointer, whose data was
+        // destroyed along with the only strong pointer to it
+        assert!(w.upgrade().is_none());
+        assert_eq!(*x, 4);
+        assert!(Arc::get_mut(&mut x).is_some());
+    }
+
+    #[test]
+    fn make_unique_with_weak() {
+        let mut x = Arc::new(3u);
+        let w = x.downgrade();
+        *x.make_unique() = 4;
+        assert!(w.upgrade().is_none());
+        assert_eq!(*x, 4);
+    }
+
+    #[test]
+    fn get_mut_racing_weak() {
+        // Tasks repeatedly downgrade and upgrade their own strong pointers
+        // while the main task keeps trying to get a mutable pointer. Each task
+        // marks itself as done before dropping its last pointer, so if
+        // `get_mut` ever succeeds then every task must have finished.
+        static N: uint = 8;
+        let done = Arc::new(atomics::AtomicUint::new(0));
+        let mut x = Arc::new(0u);
+        let (tx, rx) = channel();
+        for _ in range(0, N) {
+            let (x, done, tx) = (x.clone(), done.clone(), tx.clone());
+            task::spawn(proc() {
+                for _ in range(0, 100u) {
+                    let w = x.downgrade();
+                    let w2 = w.clone();
+                    drop(w);
+                    let y = w2.upgrade().unwrap();
+                    task::deschedule();
+                    drop(y);
+                    assert_eq!(*w2.upgrade().unwrap(), 0);
+                }
+                done.fetch_add(1, atomics::SeqCst);
+                drop(x);
+                tx.send(());
+            });
+        }
+        drop(tx);
+
+        loop {
+            match Arc::get_mut(&mut x) {
+                Some(n) => {
+                    assert_eq!(done.load(atomics::SeqCst), N);
+                    *n += 1;
+                    break
+                }
+                None => task::deschedule(),
+            }
+        }
+        for _ in rx.iter() {}
+        assert_eq!(*x, 1);
+    }
 }
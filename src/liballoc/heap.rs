@@ -11,7 +11,7 @@
 // FIXME: #13994: port to the sized deallocation API when available
 // FIXME: #13996: mark the `allocate` and `reallocate` return value as `noalias` and `nonnull`
 
-use core::intrinsics::{abort, cttz32};
+use core::intrinsics::cttz32;
 use core::option::{None, Option};
 use core::ptr::{RawPtr, mut_null, null};
 use libc::{c_char, c_int, c_void, size_t};
@@ -48,7 +48,7 @@ fn mallocx_align(a: uint) -> c_int { unsafe { cttz32(a as u32) as c_int } }
 pub unsafe fn allocate(size: uint, align: uint) -> *mut u8 {
     let ptr = je_mallocx(size as size_t, mallocx_align(align)) as *mut u8;
     if ptr.is_null() {
-        abort()
+        ::oom(size)
     }
     ptr
 }
@@ -66,7 +66,7 @@ pub unsafe fn allocate(size: uint, align: uint) -> *mut u8 {
 pub unsafe fn reallocate(ptr: *mut u8, size: uint, align: uint, old_size: uint) -> *mut u8 {
     let ptr = je_rallocx(ptr as *mut c_void, size as size_t, mallocx_align(align)) as *mut u8;
     if ptr.is_null() {
-        abort()
+        ::oom(size)
     }
     ptr
 }
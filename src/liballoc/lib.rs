@@ -96,6 +96,31 @@ pub mod owned;
 pub mod arc;
 pub mod rc;
 
+/// Common out-of-memory routine
+///
+/// This invokes the handler defined by the `oom` lang item with the size of
+/// the allocation which could not be satisfied. When linking to libstd, the
+/// handler prints a diagnostic and aborts the process; a `#![no_std]` crate
+/// can define its own handler with the signature
+/// `extern fn oom(size: uint) -> !`.
+#[cold]
+#[inline(never)]
+pub fn oom(size: uint) -> ! {
+    #[allow(ctypes)]
+    #[cfg(stage0)]
+    extern {
+        #[link_name = "rust_oom"]
+        fn oom(size: uint) -> !;
+    }
+    #[allow(ctypes)]
+    #[cfg(not(stage0))]
+    extern {
+        #[lang = "oom"]
+        fn oom(size: uint) -> !;
+    }
+    unsafe { oom(size) }
+}
+
 #[cfg(not(test))]
 mod std {
     pub use core::fmt;
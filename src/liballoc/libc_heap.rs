@@ -13,9 +13,8 @@
 
 use libc::{c_void, size_t, free, malloc, realloc};
 use core::ptr::{RawPtr, mut_null};
-use core::intrinsics::abort;
 
-/// A wrapper around libc::malloc, aborting on out-of-memory
+/// A wrapper around libc::malloc, invoking the `oom` lang item on out-of-memory
 #[inline]
 pub unsafe fn malloc_raw(size: uint) -> *mut u8 {
     // `malloc(0)` may allocate, but it may also return a null pointer
@@ -25,14 +24,13 @@ pub unsafe fn malloc_raw(size: uint) -> *mut u8 {
     } else {
         let p = malloc(size as size_t);
         if p.is_null() {
-            // we need a non-allocating way to print an error here
-            abort();
+            ::oom(size)
         }
         p as *mut u8
     }
 }
 
-/// A wrapper around libc::realloc, aborting on out-of-memory
+/// A wrapper around libc::realloc, invoking the `oom` lang item on out-of-memory
 #[inline]
 pub unsafe fn realloc_raw(ptr: *mut u8, size: uint) -> *mut u8 {
     // `realloc(ptr, 0)` may allocate, but it may also return a null pointer
@@ -43,8 +41,7 @@ pub unsafe fn realloc_raw(ptr: *mut u8, size: uint) -> *mut u8 {
     } else {
         let p = realloc(ptr as *mut c_void, size as size_t);
         if p.is_null() {
-            // we need a non-allocating way to print an error here
-            abort();
+            ::oom(size)
         }
         p as *mut u8
     }
@@ -69,6 +69,11 @@ impl SmallBitv {
         self.bits_op(s.bits, nbits, |u1, u2| u1 & !u2)
     }
 
+    #[inline]
+    pub fn symmetric_difference(&mut self, s: &SmallBitv, nbits: uint) -> bool {
+        self.bits_op(s.bits, nbits, |u1, u2| u1 ^ u2)
+    }
+
     #[inline]
     pub fn get(&self, i: uint) -> bool {
         (self.bits & (1 << i)) != 0
@@ -190,6 +195,11 @@ impl BigBitv {
         self.process(b, nbits, |w1, w2| w1 & !w2)
     }
 
+    #[inline]
+    pub fn symmetric_difference(&mut self, b: &BigBitv, nbits: uint) -> bool {
+        self.process(b, nbits, |w1, w2| w1 ^ w2)
+    }
+
     #[inline]
     pub fn get(&self, i: uint) -> bool {
         let w = i / uint::BITS;
@@ -222,7 +232,7 @@ impl BigBitv {
 #[deriving(Clone)]
 enum BitvVariant { Big(BigBitv), Small(SmallBitv) }
 
-enum Op {Union, Intersect, Assign, Difference}
+enum Op {Union, Intersect, Assign, Difference, SymmetricDifference}
 
 /// The bitvector type
 ///
@@ -275,7 +285,8 @@ impl Bitv {
               Union      => s.union(s1,      self.nbits),
               Intersect  => s.intersect(s1,  self.nbits),
               Assign     => s.become(s1,     self.nbits),
-              Difference => s.difference(s1, self.nbits)
+              Difference => s.difference(s1, self.nbits),
+              SymmetricDifference => s.symmetric_difference(s1, self.nbits)
             },
             Big(_) => die()
           },
@@ -285,7 +296,8 @@ impl Bitv {
               Union      => s.union(s1,      self.nbits),
               Intersect  => s.intersect(s1,  self.nbits),
               Assign     => s.become(s1,     self.nbits),
-              Difference => s.difference(s1, self.nbits)
+              Difference => s.difference(s1, self.nbits),
+              SymmetricDifference => s.symmetric_difference(s1, self.nbits)
             }
           }
         }
@@ -437,6 +449,18 @@ impl Bitv {
         self.do_op(Difference, v)
     }
 
+    /**
+     * Calculates the symmetric difference of two bitvectors
+     *
+     * Sets each element of `self` to `true` if it differs from the element
+     * of `v` at the same index. Both bitvectors must be the same length.
+     * Returns `true` if `self` changed.
+     */
+    #[inline]
+    pub fn symmetric_difference(&mut self, v: &Bitv) -> bool {
+        self.do_op(SymmetricDifference, v)
+    }
+
     /// Returns `true` if all bits are 1
     #[inline]
     pub fn all(&self) -> bool {
@@ -564,6 +588,32 @@ impl Bitv {
         range(0u, self.nbits).advance(|i| !self.get(i) || f(i))
     }
 
+    /**
+     * Returns the number of bits set to 1 before index `i` (the rank of `i`).
+     *
+     * `i` must be no greater than the length of the bitvector.
+     */
+    pub fn count_ones_before(&self, i: uint) -> uint {
+        assert!(i <= self.nbits);
+        match self.rep {
+            Small(ref s) => count_ones_before([s.bits], i),
+            Big(ref b) => count_ones_before(b.storage.as_slice(), i),
+        }
+    }
+
+    /**
+     * Returns the index of the `n`th bit set to 1, counting from zero (the
+     * select of `n`), or `None` if fewer than `n + 1` bits are set.
+     */
+    pub fn nth_set_bit(&self, n: uint) -> Option<uint> {
+        // Bits past the end may be set, but they all come after the valid ones
+        let bit = match self.rep {
+            Small(ref s) => nth_set_bit([s.bits], n),
+            Big(ref b) => nth_set_bit(b.storage.as_slice(), n),
+        };
+        bit.filtered(|&i| i < self.nbits)
+    }
+
 }
 
 /**
@@ -604,6 +654,33 @@ impl ops::Index<uint,bool> for Bitv {
     }
 }
 
+// The number of ones among the first `i` bits of `words`
+fn count_ones_before(words: &[uint], i: uint) -> uint {
+    let (full, rmd) = (i / uint::BITS, i % uint::BITS);
+    let ones = |words: &[uint]| words.iter().fold(0, |n, w| n + w.count_ones());
+    if full >= words.len() {
+        return ones(words);
+    }
+    ones(words.slice_to(full)) + (words[full] & ((1 << rmd) - 1)).count_ones()
+}
+
+// The index of the `n`th one among all of the bits of `words`
+fn nth_set_bit(words: &[uint], mut n: uint) -> Option<uint> {
+    for (i, &w) in words.iter().enumerate() {
+        let ones = w.count_ones();
+        if n < ones {
+            // Clear the lowest `n` ones, leaving the one we're after lowest
+            let mut w = w;
+            for _ in range(0, n) {
+                w &= w - 1;
+            }
+            return Some(i * uint::BITS + w.trailing_zeros());
+        }
+        n -= ones;
+    }
+    None
+}
+
 #[inline]
 fn iterate_bits(base: uint, bits: uint, f: |uint| -> bool) -> bool {
     if bits == 0 {
@@ -724,25 +801,18 @@ impl BitvSet {
 
     #[inline]
     fn other_op(&mut self, other: &BitvSet, f: |uint, uint| -> uint) {
-        fn nbits(mut w: uint) -> uint {
-            let mut bits = 0;
-            for _ in range(0u, uint::BITS) {
-                if w == 0 {
-                    break;
-                }
-                bits += w & 1;
-                w >>= 1;
-            }
-            return bits;
-        }
-        if self.capacity() < other.capacity() {
-            self.bitv.storage.grow(other.capacity() / uint::BITS, &0);
+        let olen = other.bitv.storage.len();
+        if self.bitv.storage.len() < olen {
+            let grow = olen - self.bitv.storage.len();
+            self.bitv.storage.grow(grow, &0);
         }
-        for (i, &w) in other.bitv.storage.iter().enumerate() {
-            let old = *self.bitv.storage.get(i);
-            let new = f(old, w);
-            *self.bitv.storage.get_mut(i) = new;
-            self.size += nbits(new) - nbits(old);
+        // Words past the end of `other` are combined with zero words, so that
+        // an intersection clears them
+        for (i, old) in self.bitv.storage.mut_iter().enumerate() {
+            let w = if i < olen { *other.bitv.storage.get(i) } else { 0 };
+            let new = f(*old, w);
+            self.size = self.size - old.count_ones() + new.count_ones();
+            *old = new;
         }
     }
 
@@ -770,6 +840,18 @@ impl BitvSet {
         BitPositions {set: self, next_idx: 0}
     }
 
+    /// Returns the number of elements of the set which are less than `i`
+    /// (the rank of `i`).
+    pub fn count_ones_before(&self, i: uint) -> uint {
+        count_ones_before(self.bitv.storage.as_slice(), i)
+    }
+
+    /// Returns the `n`th smallest element of the set, counting from zero (the
+    /// select of `n`), or `None` if the set has no more than `n` elements.
+    pub fn nth_set_bit(&self, n: uint) -> Option<uint> {
+        nth_set_bit(self.bitv.storage.as_slice(), n)
+    }
+
     pub fn difference(&self, other: &BitvSet, f: |&uint| -> bool) -> bool {
         for (i, w1, w2) in self.commons(other) {
             if !iterate_bits(i, w1 & !w2, |b| f(&b)) {
@@ -1413,6 +1495,82 @@ mod tests {
         assert!(!b1[80]);
     }
 
+    #[test]
+    fn test_small_symmetric_difference() {
+        let mut b1 = Bitv::new(3, false);
+        let mut b2 = Bitv::new(3, false);
+        b1.set(0, true);
+        b1.set(1, true);
+        b2.set(1, true);
+        b2.set(2, true);
+        assert!(b1.symmetric_difference(&b2));
+        assert!(b1[0]);
+        assert!(!b1[1]);
+        assert!(b1[2]);
+        assert!(!b1.symmetric_difference(&Bitv::new(3, false)));
+    }
+
+    #[test]
+    fn test_big_symmetric_difference() {
+        let mut b1 = Bitv::new(100, false);
+        let mut b2 = Bitv::new(100, false);
+        b1.set(0, true);
+        b1.set(40, true);
+        b2.set(40, true);
+        b2.set(80, true);
+        assert!(b1.symmetric_difference(&b2));
+        assert!(b1[0]);
+        assert!(!b1[40]);
+        assert!(b1[80]);
+    }
+
+    #[test]
+    fn test_count_ones_before() {
+        for &nbits in [0u, 10, uint::BITS, 100, 3 * uint::BITS].iter() {
+            let bitv = from_fn(nbits, |i| i % 3 == 0);
+            let mut ones = 0;
+            for i in range(0, nbits) {
+                assert_eq!(bitv.count_ones_before(i), ones);
+                if bitv[i] { ones += 1; }
+            }
+            assert_eq!(bitv.count_ones_before(nbits), ones);
+        }
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_count_ones_before_out_of_bounds() {
+        Bitv::new(10, true).count_ones_before(11);
+    }
+
+    #[test]
+    fn test_nth_set_bit() {
+        for &nbits in [0u, 10, uint::BITS, 100, 3 * uint::BITS].iter() {
+            let bitv = from_fn(nbits, |i| i % 3 == 0);
+            let ones: Vec<uint> = range(0, nbits).filter(|i| *i % 3 == 0).collect();
+            for (n, &i) in ones.iter().enumerate() {
+                assert_eq!(bitv.nth_set_bit(n), Some(i));
+                assert_eq!(bitv.count_ones_before(i), n);
+            }
+            assert_eq!(bitv.nth_set_bit(ones.len()), None);
+        }
+    }
+
+    #[test]
+    fn test_nth_set_bit_negated() {
+        // Negating sets the unused bits past the end as well
+        let mut small = Bitv::new(10, false);
+        small.negate();
+        assert_eq!(small.nth_set_bit(9), Some(9));
+        assert_eq!(small.nth_set_bit(10), None);
+
+        let mut big = Bitv::new(100, false);
+        big.negate();
+        assert_eq!(big.nth_set_bit(99), Some(99));
+        assert_eq!(big.nth_set_bit(100), None);
+        assert_eq!(big.count_ones_before(100), 100);
+    }
+
     #[test]
     fn test_small_clear() {
         let mut b = Bitv::new(14, true);
@@ -1550,6 +1708,58 @@ mod tests {
         assert_eq!(i, expected.len());
     }
 
+    #[test]
+    fn test_bitv_set_in_place() {
+        let mut a = BitvSet::new();
+        let mut b = BitvSet::new();
+        for &x in [1u, 3, 5, 200, 500].iter() { assert!(a.insert(x)); }
+        for &x in [3u, 5, 200, 220].iter() { assert!(b.insert(x)); }
+
+        let mut c = a.clone();
+        c.union_with(&b);
+        assert_eq!(c.iter().collect::<Vec<uint>>(), vec!(1, 3, 5, 200, 220, 500));
+        assert_eq!(c.len(), 6);
+
+        let mut c = a.clone();
+        c.intersect_with(&b);
+        assert_eq!(c.iter().collect::<Vec<uint>>(), vec!(3, 5, 200));
+        assert_eq!(c.len(), 3);
+
+        // The words of `b` past the end of `a` don't matter
+        let mut c = b.clone();
+        c.intersect_with(&a);
+        assert_eq!(c.iter().collect::<Vec<uint>>(), vec!(3, 5, 200));
+        assert_eq!(c.len(), 3);
+
+        let mut c = a.clone();
+        c.difference_with(&b);
+        assert_eq!(c.iter().collect::<Vec<uint>>(), vec!(1, 500));
+        assert_eq!(c.len(), 2);
+
+        let mut c = a.clone();
+        c.symmetric_difference_with(&b);
+        assert_eq!(c.iter().collect::<Vec<uint>>(), vec!(1, 220, 500));
+        assert_eq!(c.len(), 3);
+    }
+
+    #[test]
+    fn test_bitv_set_rank_select() {
+        let mut a = BitvSet::new();
+        for &x in [1u, 3, 5, 200, 500].iter() { assert!(a.insert(x)); }
+
+        assert_eq!(a.count_ones_before(0), 0);
+        assert_eq!(a.count_ones_before(1), 0);
+        assert_eq!(a.count_ones_before(2), 1);
+        assert_eq!(a.count_ones_before(200), 3);
+        assert_eq!(a.count_ones_before(201), 4);
+        assert_eq!(a.count_ones_before(10000), 5);
+
+        for (n, x) in a.iter().enumerate() {
+            assert_eq!(a.nth_set_bit(n), Some(x));
+        }
+        assert_eq!(a.nth_set_bit(5), None);
+    }
+
     #[test]
     fn test_bitv_remove() {
         let mut a = BitvSet::new();
@@ -1725,6 +1935,28 @@ mod tests {
         })
     }
 
+    #[bench]
+    fn bench_bitv_set_big_union_with(b: &mut Bencher) {
+        let mut r = rng();
+        let mut s1 = BitvSet::new();
+        let mut s2 = BitvSet::new();
+        for _ in range(0u, 100) {
+            s1.insert((r.next_u32() as uint) % BENCH_BITS);
+            s2.insert((r.next_u32() as uint) % BENCH_BITS);
+        }
+        b.iter(|| {
+            s1.union_with(&s2);
+        })
+    }
+
+    #[bench]
+    fn bench_bitv_big_nth_set_bit(b: &mut Bencher) {
+        let bitv = from_fn(BENCH_BITS, |i| i % 3 == 0);
+        b.iter(|| {
+            bitv.nth_set_bit(BENCH_BITS / 6)
+        })
+    }
+
     #[bench]
     fn bench_btv_small_iter(b: &mut Bencher) {
         let bitv = Bitv::new(uint::BITS, false);
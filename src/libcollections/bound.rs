@@ -0,0 +1,22 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bounds of ranges of keys in ordered collections
+
+/// One end of a range of keys.
+#[deriving(Clone, Eq, Show)]
+pub enum Bound<T> {
+    /// The range includes this key
+    Included(T),
+    /// The range stops just short of this key
+    Excluded(T),
+    /// The range reaches all the way to this end of the collection
+    Unbounded,
+}
@@ -10,7 +10,9 @@
 
 //! A doubly-linked list with owned nodes.
 //!
-//! The DList allows pushing and popping elements at either end.
+//! The DList allows pushing and popping elements at either end, and a cursor
+//! (see `cursor` and `mut_cursor`) can walk the list to insert or remove
+//! elements or splice in another list anywhere in constant time.
 //!
 //! DList implements the trait Deque. It should be imported with `use
 //! collections::deque::Deque`.
@@ -63,6 +65,29 @@ pub struct MutItems<'a, T> {
     nelem: uint,
 }
 
+/// A cursor over a DList.
+///
+/// A cursor points either at an element of the list or at a "ghost"
+/// position between the back and the front of the list, and can be moved in
+/// either direction. Moving past either end of the list stops at the ghost,
+/// and moving past the ghost wraps around to the other end.
+pub struct Cursor<'a, T> {
+    list: &'a DList<T>,
+    current: Option<&'a Node<T>>,
+    index: uint,
+}
+
+/// A cursor over a DList which can also change the list.
+///
+/// See `Cursor` for how the cursor moves. Elements and other lists can be
+/// inserted on either side of the current position, and the current element
+/// can be removed, all in constant time.
+pub struct CursorMut<'a, T> {
+    list: &'a mut DList<T>,
+    current: Rawlink<Node<T>>,
+    index: uint,
+}
+
 /// DList consuming iterator
 #[deriving(Clone)]
 pub struct MoveItems<T> {
@@ -202,6 +227,59 @@ impl<T> DList<T> {
             }
         })
     }
+
+    /// Move all of the Nodes of `other` in just after `prev`, or first in the
+    /// list if `prev` is none
+    fn splice_after_node(&mut self, mut prev: Rawlink<Node<T>>,
+                         mut other: DList<T>) {
+        // Carefully empty `other`.
+        let o_tail = other.list_tail.take();
+        let o_length = other.length;
+        let o_head = match other.list_head.take() {
+            None => return,
+            Some(node) => node,
+        };
+        other.length = 0;
+
+        let next = match prev.resolve() {
+            None => self.list_head.take(),
+            Some(prev) => prev.next.take(),
+        };
+        match next {
+            None => self.list_tail = o_tail,
+            Some(next) => {
+                let mut tail = o_tail;
+                let tail = tail.resolve().unwrap();
+                tail.next = link_with_prev(next, o_tail);
+            }
+        }
+        match prev.resolve() {
+            None => self.list_head = link_with_prev(o_head, Rawlink::none()),
+            Some(prev) => prev.next = link_with_prev(o_head, Rawlink::some(prev)),
+        }
+        self.length += o_length;
+    }
+
+    /// Remove `node` from the list and return it
+    fn unlink_node(&mut self, mut node: Rawlink<Node<T>>) -> Box<Node<T>> {
+        let (mut prev, next) = {
+            let node = node.resolve().unwrap();
+            (node.prev.take(), node.next.take())
+        };
+        let node = match prev.resolve() {
+            None => self.list_head.take(),
+            Some(prev) => prev.next.take(),
+        };
+        match next {
+            None => self.list_tail = prev,
+            Some(next) => match prev.resolve() {
+                None => self.list_head = link_with_prev(next, Rawlink::none()),
+                Some(prev) => prev.next = link_with_prev(next, Rawlink::some(prev)),
+            }
+        }
+        self.length -= 1;
+        node.unwrap()
+    }
 }
 
 impl<T> Deque<T> for DList<T> {
@@ -381,6 +459,24 @@ impl<T> DList<T> {
     }
 
 
+    /// Provide a cursor pointing at the front element, or at the ghost
+    /// position if the list is empty
+    #[inline]
+    pub fn cursor<'a>(&'a self) -> Cursor<'a, T> {
+        Cursor{list: self, current: self.list_head.as_ref().map(|h| &**h), index: 0}
+    }
+
+    /// Provide a cursor which can change the list, pointing at the front
+    /// element, or at the ghost position if the list is empty
+    #[inline]
+    pub fn mut_cursor<'a>(&'a mut self) -> CursorMut<'a, T> {
+        let head_raw = match self.list_head {
+            Some(ref mut h) => Rawlink::some(*h),
+            None => Rawlink::none(),
+        };
+        CursorMut{list: self, current: head_raw, index: 0}
+    }
+
     /// Consume the list into an iterator yielding elements by value
     #[inline]
     pub fn move_iter(self) -> MoveItems<T> {
@@ -543,6 +639,213 @@ impl<'a, A> ListInsertion<A> for MutItems<'a, A> {
     }
 }
 
+impl<'a, A> Cursor<'a, A> {
+    /// Provide a reference to the current element, or None if the cursor is
+    /// at the ghost position
+    #[inline]
+    pub fn current(&self) -> Option<&'a A> {
+        self.current.map(|node| &node.value)
+    }
+
+    /// The index of the current element, or None if the cursor is at the
+    /// ghost position
+    #[inline]
+    pub fn index(&self) -> Option<uint> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Move to the next element, or to the ghost position from the back of
+    /// the list, or to the front of the list from the ghost position
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.list_head.as_ref().map(|h| &**h);
+                self.index = 0;
+            }
+            Some(node) => {
+                self.current = node.next.as_ref().map(|n| &**n);
+                self.index += 1;
+            }
+        }
+    }
+
+    /// Move to the previous element, or to the ghost position from the front
+    /// of the list, or to the back of the list from the ghost position
+    pub fn move_prev(&mut self) {
+        let (prev, from_ghost) = match self.current {
+            None => (self.list.list_tail.resolve_immut(), true),
+            Some(node) => (node.prev.resolve_immut(), false),
+        };
+        self.current = prev;
+        self.index = match prev {
+            None => self.list.len(),
+            Some(_) if from_ghost => self.list.len() - 1,
+            Some(_) => self.index - 1,
+        };
+    }
+
+    /// Provide a reference to the element the cursor would move to with
+    /// `move_next`, without moving it
+    pub fn peek_next(&self) -> Option<&'a A> {
+        let next = match self.current {
+            None => self.list.list_head.as_ref(),
+            Some(node) => node.next.as_ref(),
+        };
+        next.map(|node| &node.value)
+    }
+
+    /// Provide a reference to the element the cursor would move to with
+    /// `move_prev`, without moving it
+    pub fn peek_prev(&self) -> Option<&'a A> {
+        let prev = match self.current {
+            None => self.list.list_tail.resolve_immut(),
+            Some(node) => node.prev.resolve_immut(),
+        };
+        prev.map(|node| &node.value)
+    }
+}
+
+impl<'a, A> CursorMut<'a, A> {
+    /// Provide a mutable reference to the current element, or None if the
+    /// cursor is at the ghost position
+    #[inline]
+    pub fn current<'b>(&'b mut self) -> Option<&'b mut A> {
+        self.current.resolve().map(|node| &mut node.value)
+    }
+
+    /// The index of the current element, or None if the cursor is at the
+    /// ghost position
+    #[inline]
+    pub fn index(&self) -> Option<uint> {
+        if self.current.p.is_null() { None } else { Some(self.index) }
+    }
+
+    /// Move to the next element, or to the ghost position from the back of
+    /// the list, or to the front of the list from the ghost position
+    pub fn move_next(&mut self) {
+        let next = match self.current.resolve() {
+            None => {
+                self.index = 0;
+                match self.list.list_head {
+                    Some(ref mut h) => Rawlink::some(&mut **h),
+                    None => Rawlink::none(),
+                }
+            }
+            Some(node) => {
+                self.index += 1;
+                match node.next {
+                    Some(ref mut n) => Rawlink::some(&mut **n),
+                    None => Rawlink::none(),
+                }
+            }
+        };
+        self.current = next;
+    }
+
+    /// Move to the previous element, or to the ghost position from the front
+    /// of the list, or to the back of the list from the ghost position
+    pub fn move_prev(&mut self) {
+        let (prev, from_ghost) = match self.current.resolve() {
+            None => (self.list.list_tail, true),
+            Some(node) => (node.prev, false),
+        };
+        self.current = prev;
+        self.index = if prev.p.is_null() {
+            self.list.len()
+        } else if from_ghost {
+            self.list.len() - 1
+        } else {
+            self.index - 1
+        };
+    }
+
+    /// Provide a mutable reference to the element the cursor would move to
+    /// with `move_next`, without moving it
+    pub fn peek_next<'b>(&'b mut self) -> Option<&'b mut A> {
+        let next = match self.current.resolve() {
+            None => self.list.list_head.as_mut(),
+            Some(node) => node.next.as_mut(),
+        };
+        next.map(|node| &mut node.value)
+    }
+
+    /// Provide a mutable reference to the element the cursor would move to
+    /// with `move_prev`, without moving it
+    pub fn peek_prev<'b>(&'b mut self) -> Option<&'b mut A> {
+        let prev = match self.current.resolve() {
+            None => self.list.list_tail,
+            Some(node) => node.prev,
+        };
+        if prev.p.is_null() {
+            None
+        } else {
+            Some(unsafe { &mut (*prev.p).value })
+        }
+    }
+
+    /// Insert `elt` just after the current element, or at the front of the
+    /// list if the cursor is at the ghost position
+    ///
+    /// O(1)
+    pub fn insert_after(&mut self, elt: A) {
+        let mut other = DList::new();
+        other.push_back(elt);
+        self.splice_after(other);
+    }
+
+    /// Insert `elt` just before the current element, or at the back of the
+    /// list if the cursor is at the ghost position
+    ///
+    /// O(1)
+    pub fn insert_before(&mut self, elt: A) {
+        let mut other = DList::new();
+        other.push_back(elt);
+        self.splice_before(other);
+    }
+
+    /// Move all elements of `other` in just after the current element, or at
+    /// the front of the list if the cursor is at the ghost position
+    ///
+    /// O(1)
+    pub fn splice_after(&mut self, other: DList<A>) {
+        if self.current.p.is_null() {
+            self.index += other.len();
+        }
+        self.list.splice_after_node(self.current, other);
+    }
+
+    /// Move all elements of `other` in just before the current element, or at
+    /// the back of the list if the cursor is at the ghost position
+    ///
+    /// O(1)
+    pub fn splice_before(&mut self, other: DList<A>) {
+        let prev = match self.current.resolve() {
+            None => self.list.list_tail,
+            Some(node) => node.prev,
+        };
+        self.index += other.len();
+        self.list.splice_after_node(prev, other);
+    }
+
+    /// Remove the current element and return it, moving the cursor to the
+    /// next element. Return None and do nothing if the cursor is at the ghost
+    /// position.
+    ///
+    /// O(1)
+    pub fn remove_current(&mut self) -> Option<A> {
+        let next = match self.current.resolve() {
+            None => return None,
+            Some(node) => match node.next {
+                Some(ref mut n) => Rawlink::some(&mut **n),
+                None => Rawlink::none(),
+            },
+        };
+        let box Node{value, ..} = self.list.unlink_node(self.current);
+        self.current = next;
+        Some(value)
+    }
+}
+
 impl<A> Iterator<A> for MoveItems<A> {
     #[inline]
     fn next(&mut self) -> Option<A> { self.list.pop_front() }
@@ -905,6 +1208,158 @@ mod tests {
         assert_eq!(m.move_iter().collect::<Vec<int>>(), vec![-2,0,1,2,3,4,5,6,7,8,9,0,1]);
     }
 
+    #[test]
+    fn test_cursor_move() {
+        let m = list_from([1, 2, 3]);
+        let mut c = m.cursor();
+        assert_eq!(c.current(), Some(&1));
+        assert_eq!(c.index(), Some(0));
+        assert_eq!(c.peek_prev(), None);
+        assert_eq!(c.peek_next(), Some(&2));
+        c.move_next();
+        c.move_next();
+        assert_eq!(c.current(), Some(&3));
+        assert_eq!(c.index(), Some(2));
+        c.move_next();
+        assert_eq!(c.current(), None);
+        assert_eq!(c.index(), None);
+        assert_eq!(c.peek_next(), Some(&1));
+        assert_eq!(c.peek_prev(), Some(&3));
+        c.move_next();
+        assert_eq!(c.current(), Some(&1));
+        c.move_prev();
+        c.move_prev();
+        assert_eq!(c.current(), Some(&3));
+        assert_eq!(c.index(), Some(2));
+        c.move_prev();
+        assert_eq!(c.current(), Some(&2));
+        assert_eq!(c.index(), Some(1));
+
+        let n: DList<int> = DList::new();
+        let mut c = n.cursor();
+        assert_eq!(c.current(), None);
+        c.move_prev();
+        assert_eq!(c.current(), None);
+        c.move_next();
+        assert_eq!(c.current(), None);
+        assert_eq!(c.index(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert() {
+        let mut m = list_from([1, 3, 5]);
+        {
+            let mut c = m.mut_cursor();
+            c.insert_before(0);
+            assert_eq!(c.index(), Some(1));
+            c.move_next();
+            c.insert_before(2);
+            c.insert_after(4);
+            assert_eq!(*c.current().unwrap(), 3);
+            assert_eq!(c.index(), Some(3));
+            *c.peek_next().unwrap() *= 10;
+            *c.peek_prev().unwrap() *= 10;
+
+            // At the ghost position insert_after goes to the front and
+            // insert_before to the back
+            c.move_next();
+            c.move_next();
+            c.move_next();
+            assert!(c.current().is_none());
+            c.insert_after(-1);
+            c.insert_before(6);
+            assert_eq!(c.index(), None);
+            c.move_prev();
+            assert_eq!(c.index(), Some(7));
+        }
+        check_links(&m);
+        assert_eq!(m.move_iter().collect::<Vec<int>>(),
+                   vec![-1, 0, 1, 20, 3, 40, 5, 6]);
+    }
+
+    #[test]
+    fn test_cursor_mut_remove() {
+        let mut m = generate_test();
+        {
+            let mut c = m.mut_cursor();
+            assert_eq!(c.remove_current(), Some(0));
+            assert_eq!(c.index(), Some(0));
+            c.move_next();
+            assert_eq!(c.remove_current(), Some(2));
+            assert_eq!(*c.current().unwrap(), 3);
+            c.move_prev();
+            c.move_prev();
+            assert!(c.current().is_none());
+            assert_eq!(c.remove_current(), None);
+            c.move_prev();
+            assert_eq!(c.remove_current(), Some(6));
+            assert!(c.current().is_none());
+            c.move_prev();
+            assert_eq!(c.index(), Some(3));
+            assert_eq!(*c.current().unwrap(), 5);
+        }
+        check_links(&m);
+        assert_eq!(m.len(), 4);
+        assert_eq!(m.move_iter().collect::<Vec<int>>(), vec![1, 3, 4, 5]);
+
+        let mut n = list_from([1]);
+        assert_eq!(n.mut_cursor().remove_current(), Some(1));
+        check_links(&n);
+        assert!(n.is_empty());
+        assert_eq!(n.back(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_splice() {
+        let mut m = list_from([1, 5]);
+        {
+            let mut c = m.mut_cursor();
+            c.splice_after(list_from([2, 3]));
+            c.splice_after(DList::new());
+            assert_eq!(c.index(), Some(0));
+            c.move_next();
+            c.move_next();
+            c.move_next();
+            c.splice_before(list_from([4]));
+            assert_eq!(*c.current().unwrap(), 5);
+            assert_eq!(c.index(), Some(4));
+            c.move_next();
+            c.splice_after(list_from([-1, 0]));
+            c.splice_before(list_from([6, 7]));
+            assert_eq!(c.index(), None);
+            c.move_next();
+            assert_eq!(*c.current().unwrap(), -1);
+        }
+        check_links(&m);
+        assert_eq!(m.len(), 9);
+        assert_eq!(m.move_iter().collect::<Vec<int>>(),
+                   vec![-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let mut n = DList::new();
+        n.mut_cursor().splice_before(list_from([1, 2]));
+        check_links(&n);
+        assert_eq!(n.move_iter().collect::<Vec<int>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_cursor_mut_move_to_front() {
+        // Moving an element to the front, the way an LRU cache would
+        let mut m = generate_test();
+        {
+            let mut c = m.mut_cursor();
+            while *c.current().unwrap() != 4 {
+                c.move_next();
+            }
+            let x = c.remove_current().unwrap();
+            while c.index().is_some() {
+                c.move_next();
+            }
+            c.insert_after(x);
+        }
+        check_links(&m);
+        assert_eq!(m.move_iter().collect::<Vec<int>>(), vec![4, 0, 1, 2, 3, 5, 6]);
+    }
+
     #[test]
     fn test_merge() {
         let mut m = list_from([0, 1, 3, 5, 6, 7, 2]);
@@ -529,6 +529,34 @@ mod table {
                 new_ht
             }
         }
+
+        fn clone_from(&mut self, source: &RawTable<K, V>) {
+            if self.capacity() != source.capacity() {
+                *self = source.clone();
+                return;
+            }
+
+            // Both tables have the same layout, so every bucket of `source`
+            // lines up with one of ours. Clone into the keys and values we
+            // already hold wherever both buckets are full.
+            for i in range(0, source.capacity()) {
+                match (self.peek(i), source.peek(i)) {
+                    (Full(to), Full(from)) => {
+                        let (hash, k, v) = self.read_all_mut(&to);
+                        let (from_k, from_v) = source.read(&from);
+                        *hash = from.hash();
+                        k.clone_from(from_k);
+                        v.clone_from(from_v);
+                    }
+                    (Full(to), Empty(_)) => { self.take(to); }
+                    (Empty(to), Full(from)) => {
+                        let (k, v) = source.read(&from);
+                        self.put(to, from.hash(), (*k).clone(), (*v).clone());
+                    }
+                    (Empty(_), Empty(_)) => {}
+                }
+            }
+        }
     }
 
     #[unsafe_destructor]
@@ -710,7 +738,6 @@ static INITIAL_LOAD_FACTOR: Fraction = (9, 10);
 ///     println!("{}: \"{}\"", *book, *review);
 /// }
 /// ```
-#[deriving(Clone)]
 pub struct HashMap<K, V, H = sip::SipHasher> {
     // All hashes are keyed on these values, to prevent hash collision attacks.
     hasher: H,
@@ -1323,6 +1350,35 @@ impl<K: TotalEq + Hash<S>, V, S, H: Hasher<S>> HashMap<K, V, H> {
         }
     }
 
+    /// Gets the entry for the given key, through which its value can be
+    /// inspected, replaced or removed, or filled in if there isn't one yet,
+    /// without hashing or searching for the key again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use collections::HashMap;
+    /// use collections::hashmap::{Occupied, Vacant};
+    ///
+    /// let mut counts = HashMap::new();
+    /// for word in "the cat saw the dog".words() {
+    ///     match counts.entry(word) {
+    ///         Occupied(mut entry) => { *entry.get_mut() += 1; }
+    ///         Vacant(entry) => { entry.set(1u); }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(counts.get(&"the"), &2);
+    /// assert_eq!(counts.get(&"cat"), &1);
+    /// ```
+    pub fn entry<'a>(&'a mut self, key: K) -> Entry<'a, K, V, H> {
+        let hash = self.make_hash(&key);
+        match self.search_hashed(&hash, &key) {
+            Some(index) => Occupied(OccupiedEntry { map: self, index: index }),
+            None => Vacant(VacantEntry { map: self, hash: hash, key: key }),
+        }
+    }
+
     /// Retrieves a value for the given key, failing if the key is not present.
     pub fn get<'a>(&'a self, k: &K) -> &'a V {
         match self.find(k) {
@@ -1402,6 +1458,26 @@ impl<K: TotalEq + Hash<S>, V: Clone, S, H: Hasher<S>> HashMap<K, V, H> {
     }
 }
 
+impl<K: Clone, V: Clone, H: Clone> Clone for HashMap<K, V, H> {
+    fn clone(&self) -> HashMap<K, V, H> {
+        HashMap {
+            hasher: self.hasher.clone(),
+            grow_at: self.grow_at,
+            minimum_capacity: self.minimum_capacity,
+            table: self.table.clone(),
+            load_factor: self.load_factor,
+        }
+    }
+
+    fn clone_from(&mut self, source: &HashMap<K, V, H>) {
+        self.hasher.clone_from(&source.hasher);
+        self.grow_at = source.grow_at;
+        self.minimum_capacity = source.minimum_capacity;
+        self.table.clone_from(&source.table);
+        self.load_factor = source.load_factor;
+    }
+}
+
 impl<K: TotalEq + Hash<S>, V: Eq, S, H: Hasher<S>> Eq for HashMap<K, V, H> {
     fn eq(&self, other: &HashMap<K, V, H>) -> bool {
         if self.len() != other.len() { return false; }
@@ -1453,6 +1529,100 @@ pub type Keys<'a, K, V> =
 pub type Values<'a, K, V> =
     iter::Map<'static, (&'a K, &'a V), &'a V, Entries<'a, K, V>>;
 
+/// A view into a single key of a HashMap, as returned by `entry`
+pub enum Entry<'a, K, V, H> {
+    /// The key has a value in the map
+    Occupied(OccupiedEntry<'a, K, V, H>),
+    /// The key has no value in the map
+    Vacant(VacantEntry<'a, K, V, H>),
+}
+
+/// An entry for a key which has a value in a HashMap
+pub struct OccupiedEntry<'a, K, V, H> {
+    map: &'a mut HashMap<K, V, H>,
+    index: table::FullIndex,
+}
+
+/// An entry for a key which has no value in a HashMap
+pub struct VacantEntry<'a, K, V, H> {
+    map: &'a mut HashMap<K, V, H>,
+    hash: table::SafeHash,
+    key: K,
+}
+
+impl<'a, K: TotalEq + Hash<S>, V, S, H: Hasher<S>> Entry<'a, K, V, H> {
+    /// Returns the value of an occupied entry, or fills a vacant entry in with
+    /// the value returned by `f` and returns that.
+    pub fn or_insert_with(self, f: |&K| -> V) -> &'a mut V {
+        match self {
+            Occupied(entry) => entry.into_mut(),
+            Vacant(entry) => {
+                let value = f(entry.key());
+                entry.set(value)
+            }
+        }
+    }
+}
+
+impl<'a, K: TotalEq + Hash<S>, V, S, H: Hasher<S>> OccupiedEntry<'a, K, V, H> {
+    /// Returns the key of this entry.
+    pub fn key<'b>(&'b self) -> &'b K {
+        let (k, _) = self.map.table.read(&self.index);
+        k
+    }
+
+    /// Returns the value of this entry.
+    pub fn get<'b>(&'b self) -> &'b V {
+        let (_, v) = self.map.table.read(&self.index);
+        v
+    }
+
+    /// Returns the value of this entry mutably.
+    pub fn get_mut<'b>(&'b mut self) -> &'b mut V {
+        let (_, v) = self.map.table.read_mut(&self.index);
+        v
+    }
+
+    /// Converts this entry into a mutable reference to its value which lives
+    /// as long as the borrow of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        let OccupiedEntry { map, index } = self;
+        let (_, v) = map.table.read_mut(&index);
+        v
+    }
+
+    /// Replaces the value of this entry, returning the old value.
+    pub fn set(&mut self, value: V) -> V {
+        replace(self.get_mut(), value)
+    }
+
+    /// Removes this entry from the map, returning its value.
+    pub fn take(self) -> V {
+        let OccupiedEntry { map, index } = self;
+        let value = map.pop_internal(index).unwrap();
+
+        // `pop` makes room before searching for its key, but shrinking the
+        // table would have moved this entry, so do it afterwards instead.
+        let size = map.table.size();
+        map.make_some_room(size);
+        value
+    }
+}
+
+impl<'a, K: TotalEq + Hash<S>, V, S, H: Hasher<S>> VacantEntry<'a, K, V, H> {
+    /// Returns the key of this entry.
+    pub fn key<'b>(&'b self) -> &'b K {
+        &self.key
+    }
+
+    /// Fills this entry in with `value`, returning a reference to it which
+    /// lives as long as the borrow of the map.
+    pub fn set(self, value: V) -> &'a mut V {
+        let VacantEntry { map, hash, key } = self;
+        map.insert_hashed(hash, key, value)
+    }
+}
+
 impl<K: TotalEq + Hash<S>, V, S, H: Hasher<S> + Default> FromIterator<(K, V)> for HashMap<K, V, H> {
     fn from_iter<T: Iterator<(K, V)>>(iter: T) -> HashMap<K, V, H> {
         let (lower, _) = iter.size_hint();
@@ -1481,11 +1651,20 @@ pub type SetMoveItems<K> =
 /// An implementation of a hash set using the underlying representation of a
 /// HashMap where the value is (). As with the `HashMap` type, a `HashSet`
 /// requires that the elements implement the `Eq` and `Hash` traits.
-#[deriving(Clone)]
 pub struct HashSet<T, H = sip::SipHasher> {
     map: HashMap<T, (), H>
 }
 
+impl<T: Clone, H: Clone> Clone for HashSet<T, H> {
+    fn clone(&self) -> HashSet<T, H> {
+        HashSet { map: self.map.clone() }
+    }
+
+    fn clone_from(&mut self, source: &HashSet<T, H>) {
+        self.map.clone_from(&source.map)
+    }
+}
+
 impl<T: TotalEq + Hash<S>, S, H: Hasher<S>> Eq for HashSet<T, H> {
     fn eq(&self, other: &HashSet<T, H>) -> bool {
         if self.len() != other.len() { return false; }
@@ -1646,7 +1825,7 @@ pub type SetAlgebraItems<'a, T, H> =
 
 #[cfg(test)]
 mod test_map {
-    use super::HashMap;
+    use super::{HashMap, Occupied, Vacant};
     use std::cmp::Equiv;
     use std::hash::Hash;
     use std::iter::{Iterator,range_inclusive,range_step_inclusive};
@@ -1903,6 +2082,84 @@ mod test_map {
         assert_eq!(m.pop(&1), None);
     }
 
+    #[test]
+    fn test_fnv_hasher() {
+        use std::hash::fnv::FnvHasher;
+
+        let mut m = HashMap::with_hasher(FnvHasher);
+        for i in range(0u, 1000) {
+            assert!(m.insert(i, i * 2));
+        }
+        for i in range(0u, 1000) {
+            assert_eq!(m.find(&i), Some(&(i * 2)));
+        }
+        let m2: HashMap<uint, uint, FnvHasher> = m.clone().move_iter().collect();
+        assert!(m == m2);
+    }
+
+    #[test]
+    fn test_entry() {
+        let mut m = HashMap::new();
+        m.insert(1, 10);
+
+        match m.entry(1) {
+            Occupied(mut entry) => {
+                assert_eq!(*entry.key(), 1);
+                assert_eq!(*entry.get(), 10);
+                assert_eq!(entry.set(100), 10);
+            }
+            Vacant(_) => fail!(),
+        }
+        assert_eq!(m.find(&1), Some(&100));
+
+        match m.entry(2) {
+            Occupied(_) => fail!(),
+            Vacant(entry) => {
+                assert_eq!(*entry.key(), 2);
+                *entry.set(20) += 1;
+            }
+        }
+        assert_eq!(m.find(&2), Some(&21));
+
+        match m.entry(1) {
+            Occupied(entry) => assert_eq!(entry.take(), 100),
+            Vacant(_) => fail!(),
+        }
+        assert_eq!(m.find(&1), None);
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_entry_or_insert_with() {
+        let mut m = HashMap::new();
+        for i in range(0, 100) {
+            m.entry(i % 10).or_insert_with(|_| Vec::new()).push(i);
+        }
+        assert_eq!(m.len(), 10);
+        for (k, v) in m.iter() {
+            assert_eq!(v.len(), 10);
+            assert!(v.iter().all(|i| i % 10 == *k));
+        }
+    }
+
+    #[test]
+    fn test_entry_take_shrinks() {
+        let mut m = HashMap::new();
+        for i in range(0, 1000) {
+            m.insert(i, i);
+        }
+        for i in range(0, 1000) {
+            match m.entry(i) {
+                Occupied(entry) => assert_eq!(entry.take(), i),
+                Vacant(_) => fail!(),
+            }
+            for j in range(i + 1, 1000) {
+                assert_eq!(m.find(&j), Some(&j));
+            }
+        }
+        assert!(m.is_empty());
+    }
+
     #[test]
     #[allow(experimental)]
     fn test_pop_equiv() {
@@ -1985,6 +2242,29 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_clone_from() {
+        let mut m1 = HashMap::with_capacity(64);
+        let mut m2 = HashMap::with_capacity(64);
+        for i in range(0, 20) {
+            m1.insert(i, i.to_str());
+        }
+        for i in range(10, 40) {
+            m2.insert(i, (i * 2).to_str());
+        }
+
+        let cap = m1.table.capacity();
+        m1.clone_from(&m2);
+        assert_eq!(m1.table.capacity(), cap);
+        assert_eq!(m1, m2);
+
+        // A table of a different capacity can't be reused.
+        let m3 = HashMap::with_capacity(1024);
+        m1.clone_from(&m3);
+        assert_eq!(m1.table.capacity(), m3.table.capacity());
+        assert!(m1.is_empty());
+    }
+
     #[test]
     fn test_eq() {
         let mut m1 = HashMap::new();
@@ -2450,4 +2730,67 @@ mod bench {
             k += 1;
         })
     }
+
+    #[bench]
+    fn find_or_insert_with_existing(b: &mut Bencher) {
+        use super::HashMap;
+
+        let mut m = HashMap::new();
+
+        for i in range_inclusive(1, 1000) {
+            m.insert(i, i);
+        }
+
+        b.iter(|| {
+            for i in range_inclusive(1, 1000) {
+                *m.find_or_insert_with(i, |&k| k) += 1;
+            }
+        });
+    }
+
+    #[bench]
+    fn entry_existing(b: &mut Bencher) {
+        use super::HashMap;
+
+        let mut m = HashMap::new();
+
+        for i in range_inclusive(1, 1000) {
+            m.insert(i, i);
+        }
+
+        b.iter(|| {
+            for i in range_inclusive(1, 1000) {
+                *m.entry(i).or_insert_with(|&k| k) += 1;
+            }
+        });
+    }
+
+    #[bench]
+    fn find_then_insert_nonexisting(b: &mut Bencher) {
+        use super::HashMap;
+
+        b.iter(|| {
+            let mut m = HashMap::new();
+            for i in range_inclusive(1, 1000) {
+                if m.find(&i).is_none() {
+                    m.insert(i, i);
+                }
+            }
+        });
+    }
+
+    #[bench]
+    fn entry_nonexisting(b: &mut Bencher) {
+        use super::{HashMap, Occupied, Vacant};
+
+        b.iter(|| {
+            let mut m = HashMap::new();
+            for i in range_inclusive(1, 1000) {
+                match m.entry(i) {
+                    Occupied(_) => {}
+                    Vacant(entry) => { entry.set(i); }
+                }
+            }
+        });
+    }
 }
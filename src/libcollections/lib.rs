@@ -31,6 +31,7 @@ extern crate debug;
 #[cfg(test)] #[phase(syntax, link)] extern crate log;
 
 pub use bitv::Bitv;
+pub use bound::Bound;
 pub use btree::BTree;
 pub use deque::Deque;
 pub use dlist::DList;
@@ -44,6 +45,7 @@ pub use treemap::{TreeMap, TreeSet};
 pub use trie::{TrieMap, TrieSet};
 
 pub mod bitv;
+pub mod bound;
 pub mod btree;
 pub mod deque;
 pub mod dlist;
@@ -35,11 +35,15 @@
 //!
 //! cache.change_capacity(1);
 //! assert!(cache.get(&2).is_none());
+//!
+//! assert_eq!(cache.pop_lru(), Some((6, 60)));
+//! assert!(cache.is_empty());
 //! ```
 
 use std::container::Container;
 use std::hash::Hash;
 use std::fmt;
+use std::kinds::marker;
 use std::mem;
 use std::ptr;
 
@@ -61,6 +65,15 @@ pub struct LruCache<K, V> {
     head: *mut LruEntry<K, V>,
 }
 
+/// An iterator over the key-value pairs of a cache, from most-recently used
+/// to least-recently used.
+pub struct Entries<'a, K, V> {
+    front: *LruEntry<K, V>,
+    back: *LruEntry<K, V>,
+    remaining: uint,
+    marker: marker::ContravariantLifetime<'a>,
+}
+
 impl<S, K: Hash<S>> Hash<S> for KeyRef<K> {
     fn hash(&self, state: &mut S) {
         unsafe { (*self.k).hash(state) }
@@ -126,7 +139,7 @@ impl<K: Hash + TotalEq, V> LruCache<K, V> {
                 self.map.swap(KeyRef{k: keyref}, node);
                 self.attach(node_ptr);
                 if self.len() > self.capacity() {
-                    self.remove_lru();
+                    self.pop_lru();
                 }
             }
         }
@@ -155,7 +168,35 @@ impl<K: Hash + TotalEq, V> LruCache<K, V> {
     pub fn pop(&mut self, k: &K) -> Option<V> {
         match self.map.pop(&KeyRef{k: k}) {
             None => None,
-            Some(lru_entry) => Some(lru_entry.value)
+            Some(mut lru_entry) => {
+                self.detach(&mut *lru_entry);
+                Some(lru_entry.value)
+            }
+        }
+    }
+
+    /// Remove and return the least-recently-used key-value pair, or `None` if
+    /// the cache is empty.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        if self.len() == 0 { return None }
+        let lru = unsafe { (*self.head).prev };
+        self.detach(lru);
+        let node = self.map.pop(&KeyRef{k: unsafe { &(*lru).key }}).unwrap();
+        let box LruEntry { key: k, value: v, .. } = node;
+        Some((k, v))
+    }
+
+    /// Return an iterator over the key-value pairs of the cache, from
+    /// most-recently used to least-recently used. Iterating doesn't count as
+    /// using the pairs.
+    pub fn iter<'a>(&'a self) -> Entries<'a, K, V> {
+        unsafe {
+            Entries {
+                front: (*self.head).next as *LruEntry<K, V>,
+                back: (*self.head).prev as *LruEntry<K, V>,
+                remaining: self.len(),
+                marker: marker::ContravariantLifetime::<'a>,
+            }
         }
     }
 
@@ -168,20 +209,11 @@ impl<K: Hash + TotalEq, V> LruCache<K, V> {
     /// least-recently-used key-value pairs if necessary.
     pub fn change_capacity(&mut self, capacity: uint) {
         for _ in range(capacity, self.len()) {
-            self.remove_lru();
+            self.pop_lru();
         }
         self.max_size = capacity;
     }
 
-    #[inline]
-    fn remove_lru(&mut self) {
-        if self.len() > 0 {
-            let lru = unsafe { (*self.head).prev };
-            self.detach(lru);
-            self.map.pop(&KeyRef{k: unsafe { &(*lru).key }});
-        }
-    }
-
     #[inline]
     fn detach(&mut self, node: *mut LruEntry<K, V>) {
         unsafe {
@@ -206,17 +238,9 @@ impl<A: fmt::Show + Hash + TotalEq, B: fmt::Show> fmt::Show for LruCache<A, B> {
     /// used to least-recently used.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         try!(write!(f, r"\{"));
-        let mut cur = self.head;
-        for i in range(0, self.len()) {
+        for (i, (k, v)) in self.iter().enumerate() {
             if i > 0 { try!(write!(f, ", ")) }
-            unsafe {
-                cur = (*cur).next;
-                try!(write!(f, "{}", (*cur).key));
-            }
-            try!(write!(f, ": "));
-            unsafe {
-                try!(write!(f, "{}", (*cur).value));
-            }
+            try!(write!(f, "{}: {}", *k, *v));
         }
         write!(f, r"\}")
     }
@@ -233,9 +257,43 @@ impl<K: Hash + TotalEq, V> Mutable for LruCache<K, V> {
     /// Clear the cache of all key-value pairs.
     fn clear(&mut self) {
         self.map.clear();
+        unsafe {
+            (*self.head).next = self.head;
+            (*self.head).prev = self.head;
+        }
     }
 }
 
+impl<'a, K, V> Iterator<(&'a K, &'a V)> for Entries<'a, K, V> {
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        if self.remaining == 0 { return None }
+        self.remaining -= 1;
+        unsafe {
+            let node = self.front;
+            self.front = (*node).next as *LruEntry<K, V>;
+            Some((&(*node).key, &(*node).value))
+        }
+    }
+
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator<(&'a K, &'a V)> for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        if self.remaining == 0 { return None }
+        self.remaining -= 1;
+        unsafe {
+            let node = self.back;
+            self.back = (*node).prev as *LruEntry<K, V>;
+            Some((&(*node).key, &(*node).value))
+        }
+    }
+}
+
+impl<'a, K, V> ExactSize<(&'a K, &'a V)> for Entries<'a, K, V> {}
+
 #[unsafe_destructor]
 impl<K, V> Drop for LruCache<K, V> {
     fn drop(&mut self) {
@@ -300,6 +358,55 @@ mod tests {
         assert_eq!(opt1.unwrap(), 10);
         assert!(cache.get(&1).is_none());
         assert_eq!(cache.len(), 1);
+
+        // The popped pair is no longer in the recency order
+        cache.put(3, 30);
+        cache.put(4, 40);
+        assert!(cache.get(&2).is_none());
+        assert_eq!(cache.to_str(), "{4: 40, 3: 30}".to_string());
+    }
+
+    #[test]
+    fn test_pop_lru() {
+        let mut cache: LruCache<int, int> = LruCache::new(3);
+        assert_eq!(cache.pop_lru(), None);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        cache.get(&1);
+        assert_eq!(cache.pop_lru(), Some((2, 20)));
+        assert_eq!(cache.pop_lru(), Some((3, 30)));
+        assert_eq!(cache.len(), 1);
+        cache.put(4, 40);
+        assert_eq!(cache.pop_lru(), Some((1, 10)));
+        assert_eq!(cache.pop_lru(), Some((4, 40)));
+        assert_eq!(cache.pop_lru(), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut cache: LruCache<int, int> = LruCache::new(3);
+        assert_eq!(cache.iter().next(), None);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        cache.get(&1);
+        let pairs: Vec<(int, int)> = cache.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(pairs, vec![(1, 10), (3, 30), (2, 20)]);
+        let keys: Vec<int> = cache.iter().rev().map(|(&k, _)| k).collect();
+        assert_eq!(keys, vec![2, 3, 1]);
+
+        let mut it = cache.iter();
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Some((&1, &10)));
+        assert_eq!(it.next_back(), Some((&2, &20)));
+        assert_eq!(it.next(), Some((&3, &30)));
+        assert_eq!(it.next_back(), None);
+        assert_eq!(it.next(), None);
+
+        // Iterating doesn't change the order
+        assert_eq!(cache.pop_lru(), Some((2, 20)));
     }
 
     #[test]
@@ -311,6 +418,15 @@ mod tests {
         cache.change_capacity(1);
         assert!(cache.get(&1).is_none());
         assert_eq!(cache.capacity(), 1);
+
+        cache.change_capacity(3);
+        cache.put(3, 30);
+        cache.put(4, 40);
+        assert_eq!(cache.len(), 3);
+        cache.change_capacity(0);
+        assert!(cache.is_empty());
+        cache.put(5, 50);
+        assert!(cache.is_empty());
     }
 
     #[test]
@@ -339,5 +455,7 @@ mod tests {
         assert!(cache.get(&1).is_none());
         assert!(cache.get(&2).is_none());
         assert_eq!(cache.to_str(), "{}".to_string());
+        cache.put(3, 30);
+        assert_eq!(cache.to_str(), "{3: 30}".to_string());
     }
 }
@@ -13,8 +13,9 @@
 #![allow(missing_doc)]
 
 use std::clone::Clone;
-use std::mem::{overwrite, zeroed, replace, swap};
+use std::mem::{overwrite, zeroed, replace, swap, take};
 use std::slice;
+use std::uint;
 
 /// A priority queue implemented with a binary heap
 #[deriving(Clone)]
@@ -44,6 +45,29 @@ impl<T: TotalOrd> PriorityQueue<T> {
         if self.is_empty() { None } else { Some(self.data.get(0)) }
     }
 
+    /// Returns a mutable reference to the greatest item in a queue, or `None`
+    /// if it is empty.
+    ///
+    /// The item may be changed through the returned `PeekMut`, and the queue
+    /// is reordered once it goes out of scope, which is cheaper than a `pop`
+    /// followed by a `push`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use collections::priority_queue::PriorityQueue;
+    ///
+    /// let mut pq = PriorityQueue::from_vec(vec!(3, 5, 1));
+    /// {
+    ///     let mut top = pq.peek_mut().unwrap();
+    ///     *top = 2;
+    /// }
+    /// assert_eq!(pq.top(), Some(&3));
+    /// ```
+    pub fn peek_mut<'a>(&'a mut self) -> Option<PeekMut<'a, T>> {
+        if self.is_empty() { None } else { Some(PeekMut { queue: self }) }
+    }
+
     #[deprecated="renamed to `top`"]
     pub fn maybe_top<'a>(&'a self) -> Option<&'a T> { self.top() }
 
@@ -107,6 +131,30 @@ impl<T: TotalOrd> PriorityQueue<T> {
         }
     }
 
+    /// Moves all of the items of `other` into `self`, leaving `other` empty
+    pub fn append(&mut self, other: &mut PriorityQueue<T>) {
+        if self.len() < other.len() {
+            swap(self, other);
+        }
+        let (len, other_len) = (self.len(), other.len());
+        if other_len == 0 {
+            return
+        }
+
+        // Pushing each item costs about `other_len * log2(len)` while
+        // rebuilding the heap costs about `2 * (len + other_len)`
+        let log2 = uint::BITS - len.leading_zeros();
+        let items = take(&mut other.data);
+        if 2 * (len + other_len) < other_len * log2 {
+            self.data.push_all_move(items);
+            self.rebuild();
+        } else {
+            for item in items.move_iter() {
+                self.push(item);
+            }
+        }
+    }
+
     #[allow(dead_code)]
     #[deprecated="renamed to `into_vec`"]
     fn to_vec(self) -> Vec<T> { self.into_vec() }
@@ -142,12 +190,16 @@ impl<T: TotalOrd> PriorityQueue<T> {
     /// Create a PriorityQueue from a vector (heapify)
     pub fn from_vec(xs: Vec<T>) -> PriorityQueue<T> {
         let mut q = PriorityQueue{data: xs,};
-        let mut n = q.len() / 2;
+        q.rebuild();
+        q
+    }
+
+    fn rebuild(&mut self) {
+        let mut n = self.len() / 2;
         while n > 0 {
             n -= 1;
-            q.siftdown(n)
+            self.siftdown(n)
         }
-        q
     }
 
     // The implementations of siftup and siftdown use unsafe blocks in
@@ -201,6 +253,29 @@ impl<T: TotalOrd> PriorityQueue<T> {
     }
 }
 
+/// A mutable reference to the greatest item of a `PriorityQueue`, which
+/// moves the item back into place once it is dropped.
+///
+/// This is created by the `peek_mut` method on `PriorityQueue`.
+pub struct PeekMut<'a, T> {
+    queue: &'a mut PriorityQueue<T>,
+}
+
+#[unsafe_destructor]
+impl<'a, T: TotalOrd> Drop for PeekMut<'a, T> {
+    fn drop(&mut self) {
+        self.queue.siftdown(0);
+    }
+}
+
+impl<'a, T: TotalOrd> Deref<T> for PeekMut<'a, T> {
+    fn deref<'b>(&'b self) -> &'b T { self.queue.data.get(0) }
+}
+
+impl<'a, T: TotalOrd> DerefMut<T> for PeekMut<'a, T> {
+    fn deref_mut<'b>(&'b mut self) -> &'b mut T { self.queue.data.get_mut(0) }
+}
+
 /// PriorityQueue iterator
 pub struct Items <'a, T> {
     iter: slice::Items<'a, T>,
@@ -362,6 +437,57 @@ mod tests {
         check_to_vec(vec!(5, 4, 3, 2, 1, 5, 4, 3, 2, 1, 5, 4, 3, 2, 1));
     }
 
+    #[test]
+    fn test_peek_mut() {
+        let data = vec!(2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1);
+        let mut heap = PriorityQueue::from_vec(data);
+        {
+            let mut top = heap.peek_mut().unwrap();
+            assert_eq!(*top, 10);
+            *top = 4;
+        }
+        assert_eq!(*heap.top().unwrap(), 9);
+        {
+            // Leaving the top alone keeps it on top
+            let top = heap.peek_mut().unwrap();
+            assert_eq!(*top, 9);
+        }
+        assert_eq!(*heap.top().unwrap(), 9);
+        *heap.peek_mut().unwrap() = 11;
+        assert_eq!(heap.into_sorted_vec(),
+                   vec!(0, 1, 1, 2, 2, 3, 4, 4, 5, 6, 7, 8, 11));
+    }
+
+    #[test]
+    fn test_empty_peek_mut() {
+        let mut empty: PriorityQueue<int> = PriorityQueue::new();
+        assert!(empty.peek_mut().is_none());
+    }
+
+    fn check_append(a: Vec<int>, b: Vec<int>) {
+        let mut expected = a.clone().append(b.as_slice());
+        expected.sort();
+
+        let mut q1 = PriorityQueue::from_vec(a);
+        let mut q2 = PriorityQueue::from_vec(b);
+        q1.append(&mut q2);
+        assert!(q2.is_empty());
+        assert_eq!(q1.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_append() {
+        check_append(vec!(), vec!());
+        check_append(vec!(3, 1), vec!());
+        check_append(vec!(), vec!(3, 1));
+        check_append(vec!(5, 1, 2), vec!(4, 9, 0));
+        check_append(vec!(1, 3, 5, 7, 9, 2, 4, 6, 8, 0), vec!(10, -1));
+        check_append(vec!(10, -1), vec!(1, 3, 5, 7, 9, 2, 4, 6, 8, 0));
+        check_append(Vec::from_fn(100, |i| i as int), vec!(7, 3));
+        check_append(Vec::from_fn(100, |i| (i * 7 % 100) as int),
+                     Vec::from_fn(90, |i| (i * 3 % 50) as int));
+    }
+
     #[test]
     fn test_empty_pop() {
         let mut heap: PriorityQueue<int> = PriorityQueue::new();
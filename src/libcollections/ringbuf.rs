@@ -15,6 +15,10 @@
 
 use std::cmp;
 use std::iter::RandomAccessIterator;
+use std::mem;
+use std::num;
+use std::ptr;
+use std::raw::Slice;
 
 use deque::Deque;
 
@@ -22,11 +26,14 @@ static INITIAL_CAPACITY: uint = 8u; // 2^3
 static MINIMUM_CAPACITY: uint = 2u;
 
 /// RingBuf is a circular buffer that implements Deque.
-#[deriving(Clone)]
 pub struct RingBuf<T> {
     nelts: uint,
     lo: uint,
-    elts: Vec<Option<T>>
+    // The number of slots in the ring, which are the start of the buffer of
+    // `elts`. Only the slots holding one of the `nelts` elements are
+    // initialized, so `elts` itself is always kept empty.
+    cap: uint,
+    elts: Vec<T>
 }
 
 impl<T> Container for RingBuf<T> {
@@ -37,8 +44,7 @@ impl<T> Container for RingBuf<T> {
 impl<T> Mutable for RingBuf<T> {
     /// Clear the RingBuf, removing all values.
     fn clear(&mut self) {
-        for x in self.elts.mut_iter() { *x = None }
-        self.nelts = 0;
+        while self.pop_front().is_some() {}
         self.lo = 0;
     }
 }
@@ -66,12 +72,14 @@ impl<T> Deque<T> for RingBuf<T> {
 
     /// Remove and return the first element in the RingBuf, or None if it is empty
     fn pop_front(&mut self) -> Option<T> {
-        let result = self.elts.get_mut(self.lo).take();
-        if result.is_some() {
-            self.lo = (self.lo + 1u) % self.elts.len();
+        if self.nelts > 0 {
+            let result = unsafe { ptr::read(self.slot(self.lo) as *T) };
+            self.lo = (self.lo + 1u) % self.cap;
             self.nelts -= 1u;
+            Some(result)
+        } else {
+            None
         }
-        result
     }
 
     /// Remove and return the last element in the RingBuf, or None if it is empty
@@ -79,7 +87,7 @@ impl<T> Deque<T> for RingBuf<T> {
         if self.nelts > 0 {
             self.nelts -= 1;
             let hi = self.raw_index(self.nelts);
-            self.elts.get_mut(hi).take()
+            Some(unsafe { ptr::read(self.slot(hi) as *T) })
         } else {
             None
         }
@@ -87,23 +95,23 @@ impl<T> Deque<T> for RingBuf<T> {
 
     /// Prepend an element to the RingBuf
     fn push_front(&mut self, t: T) {
-        if self.nelts == self.elts.len() {
-            grow(self.nelts, &mut self.lo, &mut self.elts);
+        if self.nelts == self.cap {
+            self.grow();
         }
         if self.lo == 0u {
-            self.lo = self.elts.len() - 1u;
+            self.lo = self.cap - 1u;
         } else { self.lo -= 1u; }
-        *self.elts.get_mut(self.lo) = Some(t);
+        unsafe { mem::overwrite(self.slot(self.lo), t); }
         self.nelts += 1u;
     }
 
     /// Append an element to the RingBuf
     fn push_back(&mut self, t: T) {
-        if self.nelts == self.elts.len() {
-            grow(self.nelts, &mut self.lo, &mut self.elts);
+        if self.nelts == self.cap {
+            self.grow();
         }
         let hi = self.raw_index(self.nelts);
-        *self.elts.get_mut(hi) = Some(t);
+        unsafe { mem::overwrite(self.slot(hi), t); }
         self.nelts += 1u;
     }
 }
@@ -116,30 +124,26 @@ impl<T> RingBuf<T> {
 
     /// Create an empty RingBuf with space for at least `n` elements.
     pub fn with_capacity(n: uint) -> RingBuf<T> {
-        RingBuf{nelts: 0, lo: 0,
-              elts: Vec::from_fn(cmp::max(MINIMUM_CAPACITY, n), |_| None)}
+        let cap = cmp::max(MINIMUM_CAPACITY, n);
+        RingBuf{nelts: 0, lo: 0, cap: cap, elts: Vec::with_capacity(cap)}
     }
 
     /// Retrieve an element in the RingBuf by index
     ///
     /// Fails if there is no element with the given index
     pub fn get<'a>(&'a self, i: uint) -> &'a T {
+        assert!(i < self.len());
         let idx = self.raw_index(i);
-        match *self.elts.get(idx) {
-            None => fail!(),
-            Some(ref v) => v
-        }
+        unsafe { &*self.slot(idx) }
     }
 
     /// Retrieve an element in the RingBuf by index
     ///
     /// Fails if there is no element with the given index
     pub fn get_mut<'a>(&'a mut self, i: uint) -> &'a mut T {
+        assert!(i < self.len());
         let idx = self.raw_index(i);
-        match *self.elts.get_mut(idx) {
-            None => fail!(),
-            Some(ref mut v) => v
-        }
+        unsafe { &mut *self.slot(idx) }
     }
 
     /// Swap elements at indices `i` and `j`
@@ -152,12 +156,31 @@ impl<T> RingBuf<T> {
         assert!(j < self.len());
         let ri = self.raw_index(i);
         let rj = self.raw_index(j);
-        self.elts.as_mut_slice().swap(ri, rj);
+        unsafe { self.mut_buffer().swap(ri, rj); }
     }
 
     /// Return index in underlying vec for a given logical element index
     fn raw_index(&self, idx: uint) -> uint {
-        raw_index(self.lo, self.elts.len(), idx)
+        raw_index(self.lo, self.cap, idx)
+    }
+
+    /// Return a pointer to the slot with the given index in the underlying
+    /// vec, which may not hold an element.
+    #[inline]
+    fn slot(&self, idx: uint) -> *mut T {
+        unsafe { self.elts.as_ptr().offset(idx as int) as *mut T }
+    }
+
+    /// Return all of the slots of the ring as a slice, including any which
+    /// don't hold an element.
+    unsafe fn buffer<'a>(&'a self) -> &'a [T] {
+        mem::transmute(Slice { data: self.elts.as_ptr(), len: self.cap })
+    }
+
+    /// Return all of the slots of the ring as a mutable slice, including any
+    /// which don't hold an element.
+    unsafe fn mut_buffer<'a>(&'a mut self) -> &'a mut [T] {
+        mem::transmute(Slice { data: self.elts.as_ptr(), len: self.cap })
     }
 
     /// Reserve capacity for exactly `n` elements in the given RingBuf,
@@ -168,7 +191,9 @@ impl<T> RingBuf<T> {
     ///
     /// * n - The number of elements to reserve space for
     pub fn reserve_exact(&mut self, n: uint) {
-        self.elts.reserve_exact(n);
+        if n > self.cap {
+            self.resize(n);
+        }
     }
 
     /// Reserve capacity for at least `n` elements in the given RingBuf,
@@ -182,47 +207,212 @@ impl<T> RingBuf<T> {
     ///
     /// * n - The number of elements to reserve space for
     pub fn reserve(&mut self, n: uint) {
-        self.elts.reserve(n);
+        if n > self.cap {
+            self.resize(num::next_power_of_two(n));
+        }
+    }
+
+    /// Grow is only called on a full ring, so nelts is also cap, unlike
+    /// elsewhere.
+    fn grow(&mut self) {
+        assert_eq!(self.nelts, self.cap);
+        let newlen = self.nelts * 2;
+        self.resize(newlen);
+    }
+
+    /// Grow the ring to `newlen` slots, keeping the elements in order.
+    fn resize(&mut self, newlen: uint) {
+        let oldlen = self.cap;
+        assert!(newlen >= oldlen);
+        self.elts.reserve_exact(newlen);
+        self.cap = newlen;
+
+        // Nothing needs to move unless the elements wrapped around the end
+        // of the old ring.
+        if self.lo + self.nelts <= oldlen { return }
+
+        /*
+          Move the shortest part into the newly reserved area.
+          lo ---->|
+          nelts ----------->|
+            [o o o|o o o o o]
+          A [. . .|o o o o o o o o|. . . . .]
+          B [o o o|. . . . . . . .|o o o o o]
+         */
+
+        let head = oldlen - self.lo;
+        let tail = self.nelts - head;
+        unsafe {
+            if tail <= head && tail <= newlen - oldlen { // A
+                ptr::copy_nonoverlapping_memory(self.slot(oldlen),
+                                                self.slot(0) as *T, tail);
+            } else {                                     // B
+                ptr::copy_memory(self.slot(newlen - head),
+                                 self.slot(self.lo) as *T, head);
+                self.lo = newlen - head;
+            }
+        }
+    }
+
+    /// Return the elements of the RingBuf from front to back as a pair of
+    /// slices.
+    ///
+    /// The first slice starts with the front element, and the second one is
+    /// empty unless the elements wrap around the end of the underlying
+    /// buffer, in which case it ends with the back element. See
+    /// `make_contiguous` for getting all of the elements as one slice.
+    pub fn as_slices<'a>(&'a self) -> (&'a [T], &'a [T]) {
+        let buf = unsafe { self.buffer() };
+        if self.lo + self.nelts <= self.cap {
+            (buf.slice(self.lo, self.lo + self.nelts), buf.slice(0, 0))
+        } else {
+            (buf.slice_from(self.lo), buf.slice_to(self.lo + self.nelts - self.cap))
+        }
+    }
+
+    /// Return the elements of the RingBuf from front to back as a pair of
+    /// mutable slices, just like `as_slices`.
+    pub fn as_mut_slices<'a>(&'a mut self) -> (&'a mut [T], &'a mut [T]) {
+        let (lo, nelts, cap) = (self.lo, self.nelts, self.cap);
+        let buf = unsafe { self.mut_buffer() };
+        if lo + nelts <= cap {
+            let (empty, elts) = buf.mut_split_at(0);
+            (elts.mut_slice(lo, lo + nelts), empty)
+        } else {
+            let (back, front) = buf.mut_split_at(lo);
+            (front, back.mut_slice_to(lo + nelts - cap))
+        }
+    }
+
+    /// Rearrange the elements of the RingBuf in place so that they don't
+    /// wrap around the end of the underlying buffer, and return them as a
+    /// single slice from front to back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use collections::{Deque, RingBuf};
+    ///
+    /// let mut buf = RingBuf::with_capacity(4);
+    /// buf.push_back(2);
+    /// buf.push_back(3);
+    /// buf.push_front(1);
+    /// {
+    ///     let (front, back) = buf.as_slices();
+    ///     assert_eq!(front, &[1]);
+    ///     assert_eq!(back, &[2, 3]);
+    /// }
+    ///
+    /// assert_eq!(buf.make_contiguous().len(), 3);
+    /// let (front, back) = buf.as_slices();
+    /// assert_eq!(front, &[1, 2, 3]);
+    /// assert!(back.is_empty());
+    /// ```
+    pub fn make_contiguous<'a>(&'a mut self) -> &'a mut [T] {
+        if self.lo + self.nelts > self.cap {
+            // Rotate the whole buffer so that the front element ends up in
+            // the first slot. This moves the empty slots around as well, but
+            // it never drops or duplicates anything.
+            let lo = self.lo;
+            unsafe {
+                self.mut_buffer().mut_slice_to(lo).reverse();
+                self.mut_buffer().mut_slice_from(lo).reverse();
+                self.mut_buffer().reverse();
+            }
+            self.lo = 0;
+        }
+        let (lo, nelts) = (self.lo, self.nelts);
+        unsafe { self.mut_buffer().mut_slice(lo, lo + nelts) }
+    }
+
+    /// Rotate the elements of the RingBuf `n` places to the left, so that
+    /// the element at index `n` becomes the front element.
+    ///
+    /// Fails if `n` is greater than the number of elements.
+    pub fn rotate_left(&mut self, n: uint) {
+        assert!(n <= self.len());
+        if self.nelts == self.cap {
+            // There's no room for anything to move, but there's no need
+            // for it to either: the back wraps around to the front.
+            self.lo = raw_index(self.lo, self.cap, n);
+        } else if n <= self.nelts - n {
+            for _ in range(0, n) {
+                let elt = self.pop_front().unwrap();
+                self.push_back(elt);
+            }
+        } else {
+            for _ in range(0, self.nelts - n) {
+                let elt = self.pop_back().unwrap();
+                self.push_front(elt);
+            }
+        }
+    }
+
+    /// Rotate the elements of the RingBuf `n` places to the right, so that
+    /// the element at index `len - n` becomes the front element.
+    ///
+    /// Fails if `n` is greater than the number of elements.
+    pub fn rotate_right(&mut self, n: uint) {
+        assert!(n <= self.len());
+        let n = self.nelts - n;
+        self.rotate_left(n);
     }
 
     /// Front-to-back iterator.
     pub fn iter<'a>(&'a self) -> Items<'a, T> {
-        Items{index: 0, rindex: self.nelts, lo: self.lo, elts: self.elts.as_slice()}
+        Items{index: 0, rindex: self.nelts, lo: self.lo, elts: unsafe { self.buffer() }}
     }
 
     /// Front-to-back iterator which returns mutable values.
     pub fn mut_iter<'a>(&'a mut self) -> MutItems<'a, T> {
-        let start_index = raw_index(self.lo, self.elts.len(), 0);
-        let end_index = raw_index(self.lo, self.elts.len(), self.nelts);
+        let start_index = raw_index(self.lo, self.cap, 0);
+        let end_index = raw_index(self.lo, self.cap, self.nelts);
+        let nelts = self.nelts;
+        let elts = unsafe { self.mut_buffer() };
 
         // Divide up the array
         if end_index <= start_index {
             // Items to iterate goes from:
-            //    start_index to self.elts.len()
+            //    start_index to self.cap
             // and then
             //    0 to end_index
-            let (temp, remaining1) = self.elts.mut_split_at(start_index);
+            let (temp, remaining1) = elts.mut_split_at(start_index);
             let (remaining2, _) = temp.mut_split_at(end_index);
             MutItems { remaining1: remaining1,
                                  remaining2: remaining2,
-                                 nelts: self.nelts }
+                                 nelts: nelts }
         } else {
             // Items to iterate goes from start_index to end_index:
-            let (empty, elts) = self.elts.mut_split_at(0);
+            let (empty, elts) = elts.mut_split_at(0);
             let remaining1 = elts.mut_slice(start_index, end_index);
             MutItems { remaining1: remaining1,
                                  remaining2: empty,
-                                 nelts: self.nelts }
+                                 nelts: nelts }
         }
     }
 }
 
+#[unsafe_destructor]
+impl<T> Drop for RingBuf<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone> Clone for RingBuf<T> {
+    fn clone(&self) -> RingBuf<T> {
+        let mut ret = RingBuf::with_capacity(self.cap);
+        ret.extend(self.iter().map(|elt| elt.clone()));
+        ret
+    }
+}
+
 /// RingBuf iterator
 pub struct Items<'a, T> {
     lo: uint,
     index: uint,
     rindex: uint,
-    elts: &'a [Option<T>],
+    elts: &'a [T],
 }
 
 impl<'a, T> Iterator<&'a T> for Items<'a, T> {
@@ -233,7 +423,7 @@ impl<'a, T> Iterator<&'a T> for Items<'a, T> {
         }
         let raw_index = raw_index(self.lo, self.elts.len(), self.index);
         self.index += 1;
-        Some(self.elts[raw_index].get_ref())
+        Some(&self.elts[raw_index])
     }
 
     #[inline]
@@ -251,7 +441,7 @@ impl<'a, T> DoubleEndedIterator<&'a T> for Items<'a, T> {
         }
         self.rindex -= 1;
         let raw_index = raw_index(self.lo, self.elts.len(), self.rindex);
-        Some(self.elts[raw_index].get_ref())
+        Some(&self.elts[raw_index])
     }
 }
 
@@ -267,15 +457,15 @@ impl<'a, T> RandomAccessIterator<&'a T> for Items<'a, T> {
             None
         } else {
             let raw_index = raw_index(self.lo, self.elts.len(), self.index + j);
-            Some(self.elts[raw_index].get_ref())
+            Some(&self.elts[raw_index])
         }
     }
 }
 
 /// RingBuf mutable iterator
 pub struct MutItems<'a, T> {
-    remaining1: &'a mut [Option<T>],
-    remaining2: &'a mut [Option<T>],
+    remaining1: &'a mut [T],
+    remaining2: &'a mut [T],
     nelts: uint,
 }
 
@@ -292,7 +482,7 @@ impl<'a, T> Iterator<&'a mut T> for MutItems<'a, T> {
             &mut self.remaining2
         };
         self.nelts -= 1;
-        Some(r.mut_shift_ref().unwrap().get_mut_ref())
+        r.mut_shift_ref()
     }
 
     #[inline]
@@ -314,47 +504,12 @@ impl<'a, T> DoubleEndedIterator<&'a mut T> for MutItems<'a, T> {
             &mut self.remaining1
         };
         self.nelts -= 1;
-        Some(r.mut_pop_ref().unwrap().get_mut_ref())
+        r.mut_pop_ref()
     }
 }
 
 impl<'a, T> ExactSize<&'a mut T> for MutItems<'a, T> {}
 
-/// Grow is only called on full elts, so nelts is also len(elts), unlike
-/// elsewhere.
-fn grow<T>(nelts: uint, loptr: &mut uint, elts: &mut Vec<Option<T>>) {
-    assert_eq!(nelts, elts.len());
-    let lo = *loptr;
-    let newlen = nelts * 2;
-    elts.reserve(newlen);
-
-    /* fill with None */
-    for _ in range(elts.len(), elts.capacity()) {
-        elts.push(None);
-    }
-
-    /*
-      Move the shortest half into the newly reserved area.
-      lo ---->|
-      nelts ----------->|
-        [o o o|o o o o o]
-      A [. . .|o o o o o o o o|. . . . .]
-      B [o o o|. . . . . . . .|o o o o o]
-     */
-
-    assert!(newlen - nelts/2 >= nelts);
-    if lo <= (nelts - lo) { // A
-        for i in range(0u, lo) {
-            elts.as_mut_slice().swap(i, nelts + i);
-        }
-    } else {                // B
-        for i in range(lo, nelts) {
-            elts.as_mut_slice().swap(i, newlen - nelts + i);
-        }
-        *loptr += newlen - nelts;
-    }
-}
-
 /// Return index in underlying vec for a given logical element index
 fn raw_index(lo: uint, len: uint, index: uint) -> uint {
     if lo >= len - index {
@@ -655,6 +810,136 @@ mod tests {
         assert_eq!(d.elts.capacity(), 64);
     }
 
+    // A RingBuf of capacity 8 holding 0..8 whose elements wrap around the
+    // end of the buffer after the first `n`.
+    fn wrapped(n: uint) -> RingBuf<uint> {
+        let mut d = RingBuf::with_capacity(8);
+        for i in range(n, 8u) {
+            d.push_back(i);
+        }
+        for i in range(0, n).rev() {
+            d.push_front(i);
+        }
+        assert_eq!(d.cap, 8);
+        d
+    }
+
+    #[test]
+    fn test_as_slices() {
+        let d: RingBuf<uint> = RingBuf::new();
+        let (a, b) = d.as_slices();
+        assert!(a.is_empty() && b.is_empty());
+
+        for n in range(0u, 9) {
+            let mut d = wrapped(n);
+            let (a, b) = d.as_slices();
+            assert_eq!(a.len() + b.len(), 8);
+            assert_eq!(a.iter().chain(b.iter()).map(|&x| x).collect::<Vec<uint>>(),
+                       range(0u, 8).collect::<Vec<uint>>());
+            if n > 0 && n < 8 {
+                assert_eq!(a, range(0, n).collect::<Vec<uint>>().as_slice());
+            }
+
+            {
+                let (a, b) = d.as_mut_slices();
+                for x in a.mut_iter().chain(b.mut_iter()) {
+                    *x *= 2;
+                }
+            }
+            assert_eq!(d.iter().map(|&x| x).collect::<Vec<uint>>(),
+                       range(0u, 8).map(|x| x * 2).collect::<Vec<uint>>());
+        }
+
+        // Make room at the back, so that the elements stop wrapping
+        let mut d = wrapped(3);
+        for _ in range(0, 3) {
+            d.pop_front();
+        }
+        let (a, b) = d.as_slices();
+        assert_eq!(a, &[3u, 4, 5, 6, 7]);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        for n in range(0u, 9) {
+            let mut d = wrapped(n);
+            assert_eq!(d.make_contiguous().iter().map(|&x| x).collect::<Vec<uint>>(),
+                       range(0u, 8).collect::<Vec<uint>>());
+            let (_, b) = d.as_slices();
+            assert!(b.is_empty());
+            d.push_back(8);
+            d.push_front(9);
+            assert_eq!(d.iter().map(|&x| x).collect::<Vec<uint>>(),
+                       vec!(9, 0, 1, 2, 3, 4, 5, 6, 7, 8));
+        }
+
+        let mut d = RingBuf::with_capacity(8);
+        for i in range(0u, 3) {
+            d.push_front(i);
+        }
+        assert_eq!(d.make_contiguous().iter().map(|&x| x).collect::<Vec<uint>>(),
+                   vec!(2, 1, 0));
+        assert_eq!(d.pop_back(), Some(0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        for len in range(0u, 10) {
+            // Leave some slots free for the elements to be moved into, or not
+            let mut d = RingBuf::with_capacity(8);
+            for i in range(0u, len) {
+                d.push_back(i);
+            }
+            for n in range(0, len + 1) {
+                let mut d = d.clone();
+                d.rotate_left(n);
+                let expected: Vec<uint> = range(n, len).chain(range(0, n)).collect();
+                assert_eq!(d.iter().map(|&x| x).collect::<Vec<uint>>(), expected);
+                d.rotate_right(n);
+                assert_eq!(d.iter().map(|&x| x).collect::<Vec<uint>>(),
+                           range(0, len).collect::<Vec<uint>>());
+            }
+        }
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_rotate_too_far() {
+        let mut d: RingBuf<int> = range(0, 5).collect();
+        d.rotate_left(6);
+    }
+
+    #[test]
+    fn test_drops() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Counted(Rc<Cell<uint>>);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                let Counted(ref drops) = *self;
+                drops.set(drops.get() + 1);
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0u));
+        {
+            let mut d = RingBuf::with_capacity(2);
+            for _ in range(0, 10) {
+                d.push_front(Counted(drops.clone()));
+                d.push_back(Counted(drops.clone()));
+            }
+            drop(d.pop_front());
+            drop(d.pop_back());
+            assert_eq!(drops.get(), 2);
+            d.rotate_left(5);
+            d.make_contiguous();
+            assert_eq!(drops.get(), 2);
+        }
+        assert_eq!(drops.get(), 20);
+    }
+
     #[test]
     fn test_swap() {
         let mut d: RingBuf<int> = range(0, 5).collect();
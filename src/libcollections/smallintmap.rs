@@ -15,8 +15,9 @@
 
 #![allow(missing_doc)]
 
+use std::cmp;
 use std::iter::{Enumerate, FilterMap};
-use std::mem::replace;
+use std::mem::{replace, take};
 use std::{vec, slice};
 
 #[allow(missing_doc)]
@@ -117,6 +118,25 @@ impl<V> SmallIntMap<V> {
         SmallIntMap { v: Vec::with_capacity(capacity) }
     }
 
+    /// Returns the number of keys the map can hold values for without
+    /// reallocating, all keys below it.
+    pub fn capacity(&self) -> uint { self.v.capacity() }
+
+    /// Reserves capacity so that values for all keys below `len` can be
+    /// inserted without reallocating. Does nothing if the capacity is
+    /// already sufficient.
+    pub fn reserve_len(&mut self, len: uint) { self.v.reserve(len) }
+
+    /// Shrinks the capacity of the map down to just above its largest key.
+    pub fn shrink_to_fit(&mut self) {
+        let len = match self.v.iter().rposition(|elt| elt.is_some()) {
+            Some(i) => i + 1,
+            None => 0,
+        };
+        self.v.truncate(len);
+        self.v.shrink_to_fit();
+    }
+
     pub fn get<'a>(&'a self, key: &uint) -> &'a V {
         self.find(key).expect("key not present")
     }
@@ -142,12 +162,52 @@ impl<V> SmallIntMap<V> {
         }
     }
 
+    /// An iterator visiting the key-value pairs with keys in the range
+    /// [`start`, `end`) in ascending order by the keys, without going over
+    /// the keys outside of it.
+    /// Iterator element type is (uint, &'r V)
+    pub fn range<'r>(&'r self, start: uint, end: uint) -> Entries<'r, V> {
+        let end = cmp::min(end, self.v.len());
+        let start = cmp::min(start, end);
+        Entries {
+            front: start,
+            back: end,
+            iter: self.v.slice(start, end).iter()
+        }
+    }
+
+    /// An iterator visiting the key-value pairs with keys in the range
+    /// [`start`, `end`) in ascending order by the keys, with mutable
+    /// references to the values
+    /// Iterator element type is (uint, &'r mut V)
+    pub fn mut_range<'r>(&'r mut self, start: uint, end: uint)
+                         -> MutEntries<'r, V> {
+        let end = cmp::min(end, self.v.len());
+        let start = cmp::min(start, end);
+        MutEntries {
+            front: start,
+            back: end,
+            iter: self.v.mut_slice(start, end).mut_iter()
+        }
+    }
+
+    /// An iterator moving all key-value pairs out of the map in ascending
+    /// order by the keys. Unlike `move_iter`, the map keeps its capacity.
+    ///
+    /// The map is empty once the iterator is dropped, even if it wasn't
+    /// run to completion.
+    /// Iterator element type is (uint, V)
+    pub fn drain<'r>(&'r mut self) -> Drain<'r, V> {
+        let len = self.v.len();
+        Drain { map: self, front: 0, back: len }
+    }
+
     /// Empties the hash map, moving all values into the specified closure
     pub fn move_iter(&mut self)
         -> FilterMap<(uint, Option<V>), (uint, V),
                 Enumerate<vec::MoveItems<Option<V>>>>
     {
-        let values = replace(&mut self.v, vec!());
+        let values = take(&mut self.v);
         values.move_iter().enumerate().filter_map(|(i, v)| {
             v.map(|v| (i, v))
         })
@@ -243,6 +303,53 @@ pub struct MutEntries<'a, T> {
 iterator!(impl MutEntries -> (uint, &'a mut T), get_mut_ref)
 double_ended_iterator!(impl MutEntries -> (uint, &'a mut T), get_mut_ref)
 
+pub struct Drain<'a, T> {
+    map: &'a mut SmallIntMap<T>,
+    front: uint,
+    back: uint,
+}
+
+impl<'a, T> Iterator<(uint, T)> for Drain<'a, T> {
+    #[inline]
+    fn next(&mut self) -> Option<(uint, T)> {
+        while self.front < self.back {
+            let index = self.front;
+            self.front += 1;
+            match self.map.v.get_mut(index).take() {
+                Some(elem) => return Some((index, elem)),
+                None => ()
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        (0, Some(self.back - self.front))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator<(uint, T)> for Drain<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(uint, T)> {
+        while self.front < self.back {
+            self.back -= 1;
+            match self.map.v.get_mut(self.back).take() {
+                Some(elem) => return Some((self.back, elem)),
+                None => ()
+            }
+        }
+        None
+    }
+}
+
+#[unsafe_destructor]
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        self.map.v.clear();
+    }
+}
+
 #[cfg(test)]
 mod test_map {
 
@@ -442,6 +549,84 @@ mod test_map {
         assert!(it.next().is_none());
     }
 
+    #[test]
+    fn test_capacity() {
+        let mut m: SmallIntMap<int> = SmallIntMap::new();
+        m.reserve_len(100);
+        assert!(m.capacity() >= 100);
+        let cap = m.capacity();
+        for i in range(0u, 100) {
+            m.insert(i, i as int);
+        }
+        assert_eq!(m.capacity(), cap);
+
+        for i in range(10u, 100) {
+            m.remove(&i);
+        }
+        m.shrink_to_fit();
+        assert_eq!(m.capacity(), 10);
+        assert_eq!(m.len(), 10);
+        assert_eq!(m.find(&9), Some(&9));
+
+        m.clear();
+        m.shrink_to_fit();
+        assert_eq!(m.capacity(), 0);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut m = SmallIntMap::new();
+        for &k in [0u, 1, 3, 5, 6, 10].iter() {
+            m.insert(k, k * 10);
+        }
+
+        let r: Vec<(uint, &uint)> = m.range(1, 6).collect();
+        assert_eq!(r, vec!((1, &10), (3, &30), (5, &50)));
+        let r: Vec<(uint, &uint)> = m.range(2, 100).rev().collect();
+        assert_eq!(r, vec!((10, &100), (6, &60), (5, &50), (3, &30)));
+        assert!(m.range(7, 10).next().is_none());
+        assert!(m.range(6, 3).next().is_none());
+        assert!(m.range(20, 30).next().is_none());
+        let mut it = m.range(6, 11);
+        assert_eq!(it.size_hint(), (0, Some(5)));
+        assert_eq!(it.next().unwrap(), (6, &60));
+        assert_eq!(it.next_back().unwrap(), (10, &100));
+        assert!(it.next().is_none());
+
+        for (k, v) in m.mut_range(3, 6) {
+            assert_eq!(*v, k * 10);
+            *v += 1;
+        }
+        let r: Vec<(uint, &uint)> = m.iter().collect();
+        assert_eq!(r, vec!((0, &0), (1, &10), (3, &31), (5, &51), (6, &60),
+                           (10, &100)));
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut m = SmallIntMap::new();
+        for &k in [1u, 3, 5].iter() {
+            m.insert(k, box k);
+        }
+        let cap = m.capacity();
+        let r: Vec<(uint, Box<uint>)> = m.drain().collect();
+        assert_eq!(r, vec!((1, box 1), (3, box 3), (5, box 5)));
+        assert!(m.is_empty());
+        assert_eq!(m.capacity(), cap);
+
+        for &k in [1u, 3, 5].iter() {
+            m.insert(k, box k);
+        }
+        {
+            let mut it = m.drain();
+            assert_eq!(it.next_back(), Some((5, box 5)));
+        }
+        assert!(m.is_empty());
+        assert!(m.find(&1).is_none());
+        m.insert(2, box 2);
+        assert_eq!(m.len(), 1);
+    }
+
     #[test]
     fn test_move_iter() {
         let mut m = SmallIntMap::new();
@@ -20,6 +20,8 @@ use std::iter;
 use std::mem::{replace, swap};
 use std::ptr;
 
+use bound::{Bound, Included, Excluded, Unbounded};
+
 // This is implemented as an AA tree, which is a simplified variation of
 // a red-black tree where red (horizontal) nodes can only be added
 // as a right child. The time complexity is the same, and re-balancing
@@ -27,8 +29,6 @@ use std::ptr;
 
 // Future improvements:
 
-// range search - O(log n) retrieval of an iterator from some key
-
 // (possibly) implement the overloads Python does for sets:
 //   * intersection: &
 //   * difference: -
@@ -215,6 +215,42 @@ macro_rules! bound_setup {
     }
 }
 
+macro_rules! range_setup {
+    // initialiser of the iterator to manipulate
+    ($iter:expr,
+     // the bound to start from, and the bound at the far end
+     $start:expr, $far:expr,
+     // whether we are iterating in ascending order
+     $forward:expr,
+     // the key past the far end which iteration stops at
+     $end:expr) => {
+        {
+            let mut iter = $iter;
+            while !iter.node.is_null() {
+                let keep = {
+                    let node_k = unsafe {&(*iter.node).key};
+                    within(node_k, $start, $forward)
+                };
+                if keep {
+                    iter.traverse_keep($forward)
+                } else {
+                    iter.traverse_skip($forward)
+                }
+            }
+
+            // If the range is empty then the first key of the iteration is
+            // already past its far end.
+            let end = match iter.stack.last() {
+                Some(node) if !within(&node.key, $far, !$forward) => {
+                    &node.key as *K
+                }
+                _ => $end
+            };
+            (iter, end)
+        }
+    }
+}
+
 
 impl<K: TotalOrd, V> TreeMap<K, V> {
     /// Get a lazy iterator that should be initialized using
@@ -268,6 +304,88 @@ impl<K: TotalOrd, V> TreeMap<K, V> {
     pub fn mut_upper_bound<'a>(&'a mut self, k: &K) -> MutEntries<'a, K, V> {
         bound_setup!(self.mut_iter_for_traversal(), false)
     }
+
+    /// Return a lazy iterator over the key-value pairs whose keys lie
+    /// between `min` and `max`, in ascending order of their keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use collections::TreeMap;
+    /// use collections::bound::{Included, Excluded};
+    ///
+    /// let map: TreeMap<int, &str> = range(0, 10).map(|i| (i, "x")).collect();
+    /// let keys: Vec<int> = map.range(Included(&3), Excluded(&7))
+    ///                         .map(|(&k, _)| k).collect();
+    /// assert_eq!(keys, vec![3, 4, 5, 6]);
+    /// ```
+    pub fn range<'a>(&'a self, min: Bound<&K>, max: Bound<&K>)
+                     -> Range<'a, K, V> {
+        let end = range_end(deref(&self.root), &max, true);
+        let (iter, end) = range_setup!(self.iter_for_traversal(),
+                                       &min, &max, true, end);
+        Range { iter: iter, end: end }
+    }
+
+    /// Return a lazy iterator over the key-value pairs whose keys lie
+    /// between `min` and `max`, in descending order of their keys.
+    pub fn rev_range<'a>(&'a self, min: Bound<&K>, max: Bound<&K>)
+                         -> RevRange<'a, K, V> {
+        let end = range_end(deref(&self.root), &min, false);
+        let (iter, end) = range_setup!(self.iter_for_traversal(),
+                                       &max, &min, false, end);
+        RevRange { iter: iter, end: end }
+    }
+
+    /// Return a lazy iterator over the key-value pairs (with the values
+    /// being mutable) whose keys lie between `min` and `max`, in ascending
+    /// order of their keys.
+    pub fn mut_range<'a>(&'a mut self, min: Bound<&K>, max: Bound<&K>)
+                         -> MutRange<'a, K, V> {
+        let end = range_end(deref(&self.root), &max, true);
+        let (iter, end) = range_setup!(self.mut_iter_for_traversal(),
+                                       &min, &max, true, end);
+        MutRange { iter: iter, end: end }
+    }
+
+    /// Return a lazy iterator over the key-value pairs (with the values
+    /// being mutable) whose keys lie between `min` and `max`, in descending
+    /// order of their keys.
+    pub fn mut_rev_range<'a>(&'a mut self, min: Bound<&K>, max: Bound<&K>)
+                             -> RevMutRange<'a, K, V> {
+        let end = range_end(deref(&self.root), &min, false);
+        let (iter, end) = range_setup!(self.mut_iter_for_traversal(),
+                                       &max, &min, false, end);
+        RevMutRange { iter: iter, end: end }
+    }
+}
+
+// Whether `key` lies on the inner side of `bound`, which is the lower bound
+// of a range if `lower` is set and its upper bound otherwise.
+fn within<K: TotalOrd>(key: &K, bound: &Bound<&K>, lower: bool) -> bool {
+    let (outside, inside) = if lower { (Less, Greater) } else { (Greater, Less) };
+    match *bound {
+        Included(k) => key.cmp(k) != outside,
+        Excluded(k) => key.cmp(k) == inside,
+        Unbounded => true,
+    }
+}
+
+// Find the key of the first node past `bound`, the far end of a range, in the
+// direction of iteration, or null if there is no such node.
+fn range_end<K: TotalOrd, V>(mut node: *TreeNode<K, V>, bound: &Bound<&K>,
+                              forward: bool) -> *K {
+    let mut end = ptr::null();
+    while !node.is_null() {
+        let n = unsafe {&*node};
+        if within(&n.key, bound, !forward) {
+            node = deref(if forward { &n.right } else { &n.left });
+        } else {
+            end = &n.key as *K;
+            node = deref(if forward { &n.left } else { &n.right });
+        }
+    }
+    end
 }
 
 /// Lazy forward iterator over a map
@@ -319,6 +437,33 @@ pub struct RevMutEntries<'a, K, V> {
     iter: MutEntries<'a, K, V>,
 }
 
+/// Lazy forward iterator over the part of a map within a range of keys
+pub struct Range<'a, K, V> {
+    iter: Entries<'a, K, V>,
+    // The key of the first node past the range, or null if there is none.
+    end: *K,
+}
+
+/// Lazy backward iterator over the part of a map within a range of keys
+pub struct RevRange<'a, K, V> {
+    iter: Entries<'a, K, V>,
+    end: *K,
+}
+
+/// Lazy forward iterator over the part of a map within a range of keys, that
+/// allows for the mutation of the values.
+pub struct MutRange<'a, K, V> {
+    iter: MutEntries<'a, K, V>,
+    end: *K,
+}
+
+/// Lazy backward iterator over the part of a map within a range of keys, that
+/// allows for the mutation of the values.
+pub struct RevMutRange<'a, K, V> {
+    iter: MutEntries<'a, K, V>,
+    end: *K,
+}
+
 
 // FIXME #5846 we want to be able to choose between &x and &mut x
 // (with many different `x`) below, so we need to optionally pass mut
@@ -401,6 +546,26 @@ macro_rules! define_iterator {
                 self.node = $deref(addr!(& $($addr_mut)* node.right));
             }
 
+            /// traverse_keep and traverse_skip work like traverse_left and
+            /// traverse_right for either direction of iteration: the former
+            /// keeps the current node around to be yielded after everything
+            /// before it, and the latter skips over the current node along
+            /// with everything before it.
+            #[inline]
+            fn traverse_keep(&mut self, forward: bool) {
+                if forward { return self.traverse_left() }
+                let node = unsafe {addr!(& $($addr_mut)* *self.node)};
+                self.node = $deref(addr!(& $($addr_mut)* node.right));
+                self.stack.push(node);
+            }
+
+            #[inline]
+            fn traverse_skip(&mut self, forward: bool) {
+                if forward { return self.traverse_right() }
+                let node = unsafe {addr!(& $($addr_mut)* *self.node)};
+                self.node = $deref(addr!(& $($addr_mut)* node.left));
+            }
+
             #[inline]
             fn traverse_complete(&mut self) {
                 if !self.node.is_null() {
@@ -457,6 +622,40 @@ define_iterator! {
     addr_mut = mut
 }
 
+macro_rules! define_range_iterator {
+    ($name:ident,
+     // whether this iterates in ascending order
+     forward = $forward:expr,
+
+     // see `define_iterator`
+     addr_mut = $($addr_mut:tt)*
+     ) => {
+        item!(impl<'a, K, V> Iterator<(&'a K, &'a $($addr_mut)* V)> for $name<'a, K, V> {
+            fn next(&mut self) -> Option<(&'a K, &'a $($addr_mut)* V)> {
+                match self.iter.next_($forward) {
+                    Some((k, _)) if k as *K == self.end => {
+                        // Nothing further along is in the range either.
+                        self.iter.stack.clear();
+                        self.iter.node = ptr::RawPtr::null();
+                        None
+                    }
+                    next => next
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (uint, Option<uint>) {
+                self.iter.size_hint()
+            }
+        })
+    }
+}
+
+define_range_iterator! { Range, forward = true, addr_mut = }
+define_range_iterator! { RevRange, forward = false, addr_mut = }
+define_range_iterator! { MutRange, forward = true, addr_mut = mut }
+define_range_iterator! { RevMutRange, forward = false, addr_mut = mut }
+
 fn deref<'a, K, V>(node: &'a Option<Box<TreeNode<K, V>>>) -> *TreeNode<K, V> {
     match *node {
         Some(ref n) => {
@@ -544,6 +743,20 @@ impl<'a, T> Iterator<&'a T> for RevSetItems<'a, T> {
     }
 }
 
+impl<'a, T> Iterator<&'a T> for SetRange<'a, T> {
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|(value, _)| value)
+    }
+}
+
+impl<'a, T> Iterator<&'a T> for RevSetRange<'a, T> {
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|(value, _)| value)
+    }
+}
+
 /// A implementation of the `Set` trait on top of the `TreeMap` container. The
 /// only requirement is that the type of the elements contained ascribes to the
 /// `TotalOrd` trait.
@@ -667,6 +880,21 @@ impl<T: TotalOrd> TreeSet<T> {
         SetItems{iter: self.map.upper_bound(v)}
     }
 
+    /// Get a lazy iterator over the values between `min` and `max`, in
+    /// ascending order.
+    #[inline]
+    pub fn range<'a>(&'a self, min: Bound<&T>, max: Bound<&T>) -> SetRange<'a, T> {
+        SetRange{iter: self.map.range(min, max)}
+    }
+
+    /// Get a lazy iterator over the values between `min` and `max`, in
+    /// descending order.
+    #[inline]
+    pub fn rev_range<'a>(&'a self, min: Bound<&T>, max: Bound<&T>)
+                         -> RevSetRange<'a, T> {
+        RevSetRange{iter: self.map.rev_range(min, max)}
+    }
+
     /// Visit the values (in-order) representing the difference
     pub fn difference<'a>(&'a self, other: &'a TreeSet<T>) -> DifferenceItems<'a, T> {
         DifferenceItems{a: self.iter().peekable(), b: other.iter().peekable()}
@@ -700,6 +928,16 @@ pub struct RevSetItems<'a, T> {
     iter: RevEntries<'a, T, ()>
 }
 
+/// Lazy forward iterator over the values of a set within a range
+pub struct SetRange<'a, T> {
+    iter: Range<'a, T, ()>
+}
+
+/// Lazy backward iterator over the values of a set within a range
+pub struct RevSetRange<'a, T> {
+    iter: RevRange<'a, T, ()>
+}
+
 /// Lazy forward iterator over a set that consumes the set while iterating
 pub type MoveSetItems<T> = iter::Map<'static, (T, ()), T, MoveEntries<T, ()>>;
 
@@ -999,6 +1237,7 @@ impl<T: TotalOrd> Extendable<T> for TreeSet<T> {
 #[cfg(test)]
 mod test_treemap {
     use super::{TreeMap, TreeNode};
+    use bound::{Bound, Included, Excluded, Unbounded};
 
     use rand::Rng;
     use rand;
@@ -1225,6 +1464,81 @@ mod test_treemap {
         assert_eq!(end_it.next(), None);
     }
 
+    fn bounds(i: &int) -> Vec<Bound<&int>> {
+        vec!(Included(i), Excluded(i), Unbounded)
+    }
+
+    fn contains(bound: &Bound<&int>, k: int, lower: bool) -> bool {
+        match *bound {
+            Included(&b) => if lower { k >= b } else { k <= b },
+            Excluded(&b) => if lower { k > b } else { k < b },
+            Unbounded => true,
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let mut m = TreeMap::new();
+        for i in range(1, 50) {
+            assert!(m.insert(i * 2, i * 4));
+        }
+
+        for lo in range(0, 101) {
+            for hi in range(lo - 3, 101) {
+                for min in bounds(&lo).move_iter() {
+                    for max in bounds(&hi).move_iter() {
+                        let expected: Vec<(int, int)> = m.iter().filter(|&(&k, _)| {
+                            contains(&min, k, true) && contains(&max, k, false)
+                        }).map(|(&k, &v)| (k, v)).collect();
+
+                        let forward: Vec<(int, int)> = m.range(min, max)
+                            .map(|(&k, &v)| (k, v)).collect();
+                        assert_eq!(forward, expected);
+
+                        let mut backward: Vec<(int, int)> = m.rev_range(min, max)
+                            .map(|(&k, &v)| (k, v)).collect();
+                        backward.reverse();
+                        assert_eq!(backward, expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_empty_map() {
+        let m: TreeMap<int, int> = TreeMap::new();
+        assert_eq!(m.range(Unbounded, Unbounded).next(), None);
+        assert_eq!(m.rev_range(Included(&1), Excluded(&5)).next(), None);
+    }
+
+    #[test]
+    fn test_mut_range() {
+        let mut m = TreeMap::new();
+        for i in range(0, 20) {
+            assert!(m.insert(i, i));
+        }
+
+        for (_, v) in m.mut_range(Included(&5), Excluded(&10)) {
+            *v += 100;
+        }
+        for (i, (&k, v)) in m.mut_rev_range(Excluded(&14), Unbounded).enumerate() {
+            assert_eq!(k, 19 - i as int);
+            *v += 1000;
+        }
+
+        for (&k, &v) in m.iter() {
+            let expected = if k >= 5 && k < 10 {
+                k + 100
+            } else if k > 14 {
+                k + 1000
+            } else {
+                k
+            };
+            assert_eq!(v, expected);
+        }
+    }
+
     #[test]
     fn test_rev_iter() {
         let mut m = TreeMap::new();
@@ -1577,6 +1891,20 @@ mod test_set {
         }
     }
 
+    #[test]
+    fn test_range() {
+        use bound::{Included, Excluded};
+
+        let set: TreeSet<int> = range(0, 10).collect();
+        let values: Vec<int> = set.range(Excluded(&2), Included(&6))
+                                  .map(|&v| v).collect();
+        assert_eq!(values, vec!(3, 4, 5, 6));
+        let values: Vec<int> = set.rev_range(Excluded(&2), Included(&6))
+                                  .map(|&v| v).collect();
+        assert_eq!(values, vec!(6, 5, 4, 3));
+        assert_eq!(set.range(Included(&6), Excluded(&6)).next(), None);
+    }
+
     #[test]
     fn test_rev_iter() {
         let mut m = TreeSet::new();
@@ -16,6 +16,8 @@ use std::slice::{Items, MutItems};
 use std::slice;
 use std::uint;
 
+use bound::{Bound, Included, Excluded, Unbounded};
+
 // FIXME: #5244: need to manually update the TrieNode constructor
 static SHIFT: uint = 4;
 static SIZE: uint = 1 << SHIFT;
@@ -258,6 +260,86 @@ impl<T> TrieMap<T> {
     pub fn mut_upper_bound<'a>(&'a mut self, key: uint) -> MutEntries<'a, T> {
         self.mut_bound(key, true)
     }
+
+    /// Get an iterator over the key-value pairs whose keys lie between `min`
+    /// and `max`, in ascending order of their keys.
+    ///
+    /// The iterator starts out like `lower_bound` or `upper_bound`, and stops
+    /// at the first key past `max` without looking at the rest of the map.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use collections::TrieMap;
+    /// use collections::bound::{Included, Excluded};
+    ///
+    /// let map: TrieMap<&str> = vec!((1, "a"), (3, "b"), (5, "c")).move_iter().collect();
+    /// let keys: Vec<uint> = map.range(Excluded(1), Included(5)).map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec!(3, 5));
+    /// ```
+    pub fn range<'a>(&'a self, min: Bound<uint>, max: Bound<uint>) -> Range<'a, T> {
+        let iter = match min {
+            Included(key) => self.lower_bound(key),
+            Excluded(key) => self.upper_bound(key),
+            Unbounded => self.iter(),
+        };
+        Range { iter: iter, max: max }
+    }
+
+    /// Get an iterator over the key-value pairs whose keys lie between `min`
+    /// and `max`, in ascending order of their keys, with the ability to
+    /// mutate the values.
+    pub fn mut_range<'a>(&'a mut self, min: Bound<uint>, max: Bound<uint>)
+                         -> MutRange<'a, T> {
+        let iter = match min {
+            Included(key) => self.mut_lower_bound(key),
+            Excluded(key) => self.mut_upper_bound(key),
+            Unbounded => self.mut_iter(),
+        };
+        MutRange { iter: iter, max: max }
+    }
+
+    /// Find the key-value pair whose key shares the longest prefix of bits
+    /// with `key`, which is `key` itself if it's in the map. Returns `None`
+    /// if the map is empty.
+    ///
+    /// This is the key which differs the least from `key` when they are
+    /// xor-ed together, so the length of the prefix they share is the number
+    /// of leading zeros of that.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use collections::TrieMap;
+    ///
+    /// let mut map = TrieMap::new();
+    /// map.insert(0b1000, "a");
+    /// map.insert(0b1011, "b");
+    /// map.insert(0b0111, "c");
+    /// assert_eq!(map.longest_common_prefix(0b1010), Some((0b1011, &"b")));
+    /// assert_eq!(map.longest_common_prefix(0b0000), Some((0b0111, &"c")));
+    /// ```
+    pub fn longest_common_prefix<'a>(&'a self, key: uint) -> Option<(uint, &'a T)> {
+        let mut node: &'a TrieNode<T> = &self.root;
+        let mut idx = 0;
+        loop {
+            // The children sharing the most leading bits with the chunk of
+            // `key` come first when ordered by their chunk xor-ed with it,
+            // and none of the lower chunks can make up for a higher one.
+            let want = chunk(key, idx);
+            let mut diff = 0;
+            loop {
+                // Only the root can be without children
+                if diff == SIZE { return None }
+                match node.children[want ^ diff] {
+                    Internal(ref x) => { node = &**x; break }
+                    External(stored, ref value) => return Some((stored, value)),
+                    Nothing => diff += 1
+                }
+            }
+            idx += 1;
+        }
+    }
 }
 
 impl<T> FromIterator<(uint, T)> for TrieMap<T> {
@@ -357,6 +439,18 @@ impl TrieSet {
     pub fn upper_bound<'a>(&'a self, val: uint) -> SetItems<'a> {
         SetItems{iter: self.map.upper_bound(val)}
     }
+
+    /// Get an iterator over the values between `min` and `max`, in ascending
+    /// order.
+    pub fn range<'a>(&'a self, min: Bound<uint>, max: Bound<uint>) -> SetRange<'a> {
+        SetRange{iter: self.map.range(min, max)}
+    }
+
+    /// Find the value which shares the longest prefix of bits with `val`, or
+    /// `None` if the set is empty. See `TrieMap::longest_common_prefix`.
+    pub fn longest_common_prefix(&self, val: uint) -> Option<uint> {
+        self.map.longest_common_prefix(val).map(|(key, _)| key)
+    }
 }
 
 impl FromIterator<uint> for TrieSet {
@@ -503,6 +597,20 @@ pub struct MutEntries<'a, T> {
     remaining_max: uint
 }
 
+/// Forward iterator over the key-value pairs of a map whose keys lie in a
+/// range
+pub struct Range<'a, T> {
+    iter: Entries<'a, T>,
+    max: Bound<uint>
+}
+
+/// Forward iterator over the key-value pairs of a map whose keys lie in a
+/// range, with the values being mutable.
+pub struct MutRange<'a, T> {
+    iter: MutEntries<'a, T>,
+    max: Bound<uint>
+}
+
 // FIXME #5846: see `addr!` above.
 macro_rules! item { ($i:item) => {$i}}
 
@@ -620,6 +728,41 @@ macro_rules! iterator_impl {
 iterator_impl! { Entries, iter = iter, mutability = }
 iterator_impl! { MutEntries, iter = mut_iter, mutability = mut }
 
+macro_rules! range_iterator_impl {
+    ($name:ident, mutability = $($mut_:tt)*) => {
+        item!(impl<'a, T> Iterator<(uint, &'a $($mut_)* T)> for $name<'a, T> {
+            fn next(&mut self) -> Option<(uint, &'a $($mut_)* T)> {
+                match self.iter.next() {
+                    Some((key, value)) => {
+                        let within = match self.max {
+                            Included(max) => key <= max,
+                            Excluded(max) => key < max,
+                            Unbounded => true,
+                        };
+                        if within { return Some((key, value)) }
+                    }
+                    None => {}
+                }
+                // The keys only go up from here, so empty the stack to stop
+                // for good.
+                self.iter.length = 0;
+                self.iter.remaining_min = 0;
+                self.iter.remaining_max = 0;
+                None
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (uint, Option<uint>) {
+                let (_, max) = self.iter.size_hint();
+                (0, max)
+            }
+        })
+    }
+}
+
+range_iterator_impl! { Range, mutability = }
+range_iterator_impl! { MutRange, mutability = mut }
+
 /// Forward iterator over a set
 pub struct SetItems<'a> {
     iter: Entries<'a, ()>
@@ -635,9 +778,25 @@ impl<'a> Iterator<uint> for SetItems<'a> {
     }
 }
 
+/// Forward iterator over the values of a set which lie in a range
+pub struct SetRange<'a> {
+    iter: Range<'a, ()>
+}
+
+impl<'a> Iterator<uint> for SetRange<'a> {
+    fn next(&mut self) -> Option<uint> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        self.iter.size_hint()
+    }
+}
+
 #[cfg(test)]
 mod test_map {
     use super::{TrieMap, TrieNode, Internal, External, Nothing};
+    use bound::{Bound, Included, Excluded, Unbounded};
     use std::iter::range_step;
     use std::uint;
 
@@ -909,6 +1068,86 @@ mod test_map {
         assert!(m_lower.iter().all(|(_, &x)| x == 0));
         assert!(m_upper.iter().all(|(_, &x)| x == 0));
     }
+
+    #[test]
+    fn test_range() {
+        let empty: TrieMap<uint> = TrieMap::new();
+        assert_eq!(empty.range(Unbounded, Unbounded).next(), None);
+        assert_eq!(empty.range(Included(0), Excluded(10)).next(), None);
+
+        let mut map = TrieMap::new();
+        for x in range_step(0u, 300, 3) {
+            map.insert(x, x * 2);
+        }
+        map.insert(uint::MAX, 0);
+
+        fn within(key: uint, bound: Bound<uint>, lower: bool) -> bool {
+            match bound {
+                Included(k) => if lower { key >= k } else { key <= k },
+                Excluded(k) => if lower { key > k } else { key < k },
+                Unbounded => true,
+            }
+        }
+
+        for lo in range(0u, 302) {
+            for hi in range_step(lo, 302, 7) {
+                for &min in [Included(lo), Excluded(lo), Unbounded].iter() {
+                    for &max in [Included(hi), Excluded(hi), Unbounded].iter() {
+                        let expected: Vec<(uint, &uint)> = map.iter().filter(|&(k, _)| {
+                            within(k, min, true) && within(k, max, false)
+                        }).collect();
+                        let actual: Vec<(uint, &uint)> = map.range(min, max).collect();
+                        assert_eq!(actual, expected);
+                    }
+                }
+            }
+        }
+
+        // Stopping is for good
+        let mut it = map.range(Included(3), Excluded(6));
+        assert_eq!(it.next(), Some((3, &6)));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_mut_range() {
+        let mut map = TrieMap::new();
+        for x in range(0u, 100) {
+            map.insert(x * 100, x);
+        }
+        for (k, v) in map.mut_range(Excluded(1000), Included(2000)) {
+            assert!(k > 1000 && k <= 2000);
+            *v = 0;
+        }
+        for (k, &v) in map.iter() {
+            assert_eq!(v == 0, k == 0 || (k > 1000 && k <= 2000));
+        }
+    }
+
+    #[test]
+    fn test_longest_common_prefix() {
+        let empty: TrieMap<uint> = TrieMap::new();
+        assert_eq!(empty.longest_common_prefix(0), None);
+
+        let keys = [0u, 1, 7, 16, 17, 255, 256, 1000, 1 << 40, uint::MAX - 3, uint::MAX];
+        let mut map = TrieMap::new();
+        for &k in keys.iter() {
+            map.insert(k, k);
+        }
+
+        let queries = [0u, 2, 6, 8, 15, 18, 100, 254, 257, 999, 1001, 1 << 39,
+                       (1 << 40) + 5, uint::MAX / 2, uint::MAX - 1, uint::MAX];
+        for &q in queries.iter().chain(keys.iter()) {
+            let best = *keys.iter().min_by(|&&k| k ^ q).unwrap();
+            assert_eq!(map.longest_common_prefix(q), Some((best, &best)));
+        }
+
+        let mut one = TrieMap::new();
+        one.insert(42u, ());
+        assert_eq!(one.longest_common_prefix(uint::MAX), Some((42, &())));
+    }
 }
 
 #[cfg(test)]
@@ -1022,6 +1261,7 @@ mod bench_map {
 #[cfg(test)]
 mod test_set {
     use super::TrieSet;
+    use bound::{Included, Excluded, Unbounded};
     use std::uint;
 
     #[test]
@@ -1053,4 +1293,25 @@ mod test_set {
             assert!(set.contains(x));
         }
     }
+
+    #[test]
+    fn test_range() {
+        let set: TrieSet = vec!(1u, 4, 9, 16, 25).move_iter().collect();
+        let r: Vec<uint> = set.range(Included(4), Excluded(25)).collect();
+        assert_eq!(r, vec!(4, 9, 16));
+        let r: Vec<uint> = set.range(Excluded(4), Unbounded).collect();
+        assert_eq!(r, vec!(9, 16, 25));
+        assert!(set.range(Excluded(25), Unbounded).next().is_none());
+    }
+
+    #[test]
+    fn test_longest_common_prefix() {
+        let mut set = TrieSet::new();
+        assert_eq!(set.longest_common_prefix(3), None);
+        set.insert(0b1100);
+        set.insert(0b0011);
+        assert_eq!(set.longest_common_prefix(0b1111), Some(0b1100));
+        assert_eq!(set.longest_common_prefix(0b0111), Some(0b0011));
+        assert_eq!(set.longest_common_prefix(0b0011), Some(0b0011));
+    }
 }
@@ -14,11 +14,12 @@
 //! of any type, through runtime reflection.
 //!
 //! `Any` itself can be used to get a `TypeId`, and has more features when used as a trait object.
-//! As `&Any` (a borrowed trait object), it has the `is` and `as_ref` methods, to test if the
-//! contained value is of a given type, and to get a reference to the inner value as a type. As
-//! `&mut Any`, there is also the `as_mut` method, for getting a mutable reference to the inner
-//! value. `Box<Any>` adds the `move` method, which will unwrap a `Box<T>` from the object.  See
-//! the extension traits (`*Ext`) for the full details.
+//! As `&Any` (a borrowed trait object), it has the `is` and `downcast_ref` methods, to test if
+//! the contained value is of a given type, and to get a reference to the inner value as a type.
+//! As `&mut Any`, there is also the `downcast_mut` method, for getting a mutable reference to the
+//! inner value. None of these need an allocator, so they are available to users of libcore
+//! alone. `Box<Any>` adds the `move` method in liballoc, which will unwrap a `Box<T>` from the
+//! object. See the extension traits (`*Ext`) for the full details.
 
 use mem::{transmute, transmute_copy};
 use option::{Option, Some, None};
@@ -58,6 +59,9 @@ pub trait AnyRefExt<'a> {
 
     /// Returns some reference to the boxed value if it is of type `T`, or
     /// `None` if it isn't.
+    fn downcast_ref<T: 'static>(self) -> Option<&'a T>;
+
+    #[deprecated="renamed to `downcast_ref`"]
     fn as_ref<T: 'static>(self) -> Option<&'a T>;
 }
 
@@ -75,7 +79,7 @@ impl<'a> AnyRefExt<'a> for &'a Any {
     }
 
     #[inline]
-    fn as_ref<T: 'static>(self) -> Option<&'a T> {
+    fn downcast_ref<T: 'static>(self) -> Option<&'a T> {
         if self.is::<T>() {
             unsafe {
                 // Get the raw representation of the trait object
@@ -88,18 +92,24 @@ impl<'a> AnyRefExt<'a> for &'a Any {
             None
         }
     }
+
+    #[inline]
+    fn as_ref<T: 'static>(self) -> Option<&'a T> { self.downcast_ref::<T>() }
 }
 
 /// Extension methods for a mutable referenced `Any` trait object
 pub trait AnyMutRefExt<'a> {
     /// Returns some mutable reference to the boxed value if it is of type `T`, or
     /// `None` if it isn't.
+    fn downcast_mut<T: 'static>(self) -> Option<&'a mut T>;
+
+    #[deprecated="renamed to `downcast_mut`"]
     fn as_mut<T: 'static>(self) -> Option<&'a mut T>;
 }
 
 impl<'a> AnyMutRefExt<'a> for &'a mut Any {
     #[inline]
-    fn as_mut<T: 'static>(self) -> Option<&'a mut T> {
+    fn downcast_mut<T: 'static>(self) -> Option<&'a mut T> {
         if self.is::<T>() {
             unsafe {
                 // Get the raw representation of the trait object
@@ -112,6 +122,9 @@ impl<'a> AnyMutRefExt<'a> for &'a mut Any {
             None
         }
     }
+
+    #[inline]
+    fn as_mut<T: 'static>(self) -> Option<&'a mut T> { self.downcast_mut::<T>() }
 }
 
 #[cfg(test)]
@@ -161,22 +174,22 @@ mod tests {
     }
 
     #[test]
-    fn any_as_ref() {
+    fn any_downcast_ref() {
         let a = &5u as &Any;
 
-        match a.as_ref::<uint>() {
+        match a.downcast_ref::<uint>() {
             Some(&5) => {}
             x => fail!("Unexpected value {}", x)
         }
 
-        match a.as_ref::<Test>() {
+        match a.downcast_ref::<Test>() {
             None => {}
             x => fail!("Unexpected value {}", x)
         }
     }
 
     #[test]
-    fn any_as_mut() {
+    fn any_downcast_mut() {
         let mut a = 5u;
         let mut b = box 7u;
 
@@ -184,7 +197,7 @@ mod tests {
         let tmp: &mut uint = b;
         let b_r = tmp as &mut Any;
 
-        match a_r.as_mut::<uint>() {
+        match a_r.downcast_mut::<uint>() {
             Some(x) => {
                 assert_eq!(*x, 5u);
                 *x = 612;
@@ -192,7 +205,7 @@ mod tests {
             x => fail!("Unexpected value {}", x)
         }
 
-        match b_r.as_mut::<uint>() {
+        match b_r.downcast_mut::<uint>() {
             Some(x) => {
                 assert_eq!(*x, 7u);
                 *x = 413;
@@ -200,22 +213,22 @@ mod tests {
             x => fail!("Unexpected value {}", x)
         }
 
-        match a_r.as_mut::<Test>() {
+        match a_r.downcast_mut::<Test>() {
             None => (),
             x => fail!("Unexpected value {}", x)
         }
 
-        match b_r.as_mut::<Test>() {
+        match b_r.downcast_mut::<Test>() {
             None => (),
             x => fail!("Unexpected value {}", x)
         }
 
-        match a_r.as_mut::<uint>() {
+        match a_r.downcast_mut::<uint>() {
             Some(&612) => {}
             x => fail!("Unexpected value {}", x)
         }
 
-        match b_r.as_mut::<uint>() {
+        match b_r.downcast_mut::<uint>() {
             Some(&413) => {}
             x => fail!("Unexpected value {}", x)
         }
@@ -272,11 +285,11 @@ mod bench {
     use self::test::Bencher;
 
     #[bench]
-    fn bench_as_ref(b: &mut Bencher) {
+    fn bench_downcast_ref(b: &mut Bencher) {
         b.iter(|| {
             let mut x = 0; let mut y = &mut x as &mut Any;
             test::black_box(&mut y);
-            test::black_box(y.as_ref::<int>() == Some(&0));
+            test::black_box(y.downcast_ref::<int>() == Some(&0));
         });
     }
 }
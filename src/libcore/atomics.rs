@@ -9,10 +9,28 @@
 // except according to those terms.
 
 //! Core atomic primitives
+//!
+//! `AtomicI64` and `AtomicU64` are available on all targets. On 32-bit x86
+//! their operations compile to `lock cmpxchg8b`, and on ARM to
+//! `ldrexd`/`strexd` (or, for CPUs older than ARMv6K, to calls to libgcc's
+//! `__sync_*_8` routines, which use the kernel's 64-bit compare-and-swap
+//! helper). 32-bit MIPS has no 64-bit compare-and-swap at all, so there the
+//! 64-bit types are emulated with a small table of spinlocks, selected by the
+//! address of the atomic. They remain correct, but are not lock-free and are
+//! considerably slower than the word-sized types.
+//!
+//! On 32-bit targets `i64` and `u64` may only be 4-byte aligned, and a 64-bit
+//! atomic operation on a misaligned value is not atomic, so `AtomicI64` and
+//! `AtomicU64` are 8-byte aligned on all targets.
 
 use intrinsics;
+use option::{Option, Some, None};
+use result::{Result, Ok, Err};
 use std::kinds::marker;
 use ty::Unsafe;
+#[cfg(target_arch = "mips")] use cmp::{Eq, Ord};
+#[cfg(target_arch = "mips")] use kinds::Copy;
+#[cfg(target_arch = "mips")] use ops::{Add, Sub, BitAnd, BitOr, BitXor};
 
 /// An atomic boolean type.
 pub struct AtomicBool {
@@ -32,6 +50,28 @@ pub struct AtomicUint {
     nocopy: marker::NoCopy
 }
 
+/// A signed 64-bit atomic integer type, supporting basic atomic arithmetic
+/// operations
+pub struct AtomicI64 {
+    v: Unsafe<i64>,
+    nocopy: marker::NoCopy,
+    align: [Align64, ..0],
+}
+
+/// An unsigned 64-bit atomic integer type, supporting basic atomic arithmetic
+/// operations
+pub struct AtomicU64 {
+    v: Unsafe<u64>,
+    nocopy: marker::NoCopy,
+    align: [Align64, ..0],
+}
+
+// An 8-byte aligned type on all targets: LLVM aligns vectors to their size.
+// A zero-length array of it raises the alignment of the 64-bit atomics
+// without making them any bigger.
+#[simd]
+struct Align64(u32, u32);
+
 /// An unsafe atomic pointer. Only supports basic atomic operations
 pub struct AtomicPtr<T> {
     p: Unsafe<uint>,
@@ -80,6 +120,16 @@ pub static INIT_ATOMIC_INT  : AtomicInt  = AtomicInt  { v: Unsafe{value: 0,
 pub static INIT_ATOMIC_UINT : AtomicUint = AtomicUint { v: Unsafe{value: 0,
                                                                   marker1: marker::InvariantType},
                                                         nocopy: marker::NoCopy };
+/// An `AtomicI64` initialized to `0`
+pub static INIT_ATOMIC_I64  : AtomicI64  = AtomicI64  { v: Unsafe{value: 0,
+                                                                  marker1: marker::InvariantType},
+                                                        nocopy: marker::NoCopy,
+                                                        align: [] };
+/// An `AtomicU64` initialized to `0`
+pub static INIT_ATOMIC_U64  : AtomicU64  = AtomicU64  { v: Unsafe{value: 0,
+                                                                  marker1: marker::InvariantType},
+                                                        nocopy: marker::NoCopy,
+                                                        align: [] };
 
 // NB: Needs to be -1 (0b11111111...) to make fetch_nand work correctly
 static UINT_TRUE: uint = -1;
@@ -398,6 +448,43 @@ impl AtomicInt {
     pub fn fetch_xor(&self, val: int, order: Ordering) -> int {
         unsafe { atomic_xor(self.v.get(), val, order) }
     }
+
+    /// Store the maximum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_max(&self, val: int, order: Ordering) -> int {
+        unsafe { atomic_max(self.v.get(), val, order) }
+    }
+
+    /// Store the minimum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_min(&self, val: int, order: Ordering) -> int {
+        unsafe { atomic_min(self.v.get(), val, order) }
+    }
+
+    /// Update the value with the result of `f`, returning the previous value
+    ///
+    /// `f` is called with the current value, which is loaded with
+    /// `fetch_order`. If it returns `Some(new)`, `new` is stored with
+    /// `set_order` if the value has not changed in the meantime, and `f` is
+    /// called again with the new current value if it has. The result is
+    /// `Ok(previous)` once a value has been stored, or `Err(current)` if `f`
+    /// returned `None`.
+    #[inline]
+    pub fn fetch_update(&self, set_order: Ordering, fetch_order: Ordering,
+                        f: |int| -> Option<int>) -> Result<int, int> {
+        let mut prev = self.load(fetch_order);
+        loop {
+            let new = match f(prev) {
+                Some(new) => new,
+                None => return Err(prev),
+            };
+            let cur = self.compare_and_swap(prev, new, set_order);
+            if cur == prev { return Ok(prev) }
+            prev = cur;
+        }
+    }
 }
 
 impl AtomicUint {
@@ -510,6 +597,247 @@ impl AtomicUint {
     pub fn fetch_xor(&self, val: uint, order: Ordering) -> uint {
         unsafe { atomic_xor(self.v.get(), val, order) }
     }
+
+    /// Store the maximum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_max(&self, val: uint, order: Ordering) -> uint {
+        unsafe { atomic_umax(self.v.get(), val, order) }
+    }
+
+    /// Store the minimum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_min(&self, val: uint, order: Ordering) -> uint {
+        unsafe { atomic_umin(self.v.get(), val, order) }
+    }
+
+    /// Update the value with the result of `f`, returning the previous value
+    ///
+    /// `f` is called with the current value, which is loaded with
+    /// `fetch_order`. If it returns `Some(new)`, `new` is stored with
+    /// `set_order` if the value has not changed in the meantime, and `f` is
+    /// called again with the new current value if it has. The result is
+    /// `Ok(previous)` once a value has been stored, or `Err(current)` if `f`
+    /// returned `None`.
+    #[inline]
+    pub fn fetch_update(&self, set_order: Ordering, fetch_order: Ordering,
+                        f: |uint| -> Option<uint>) -> Result<uint, uint> {
+        let mut prev = self.load(fetch_order);
+        loop {
+            let new = match f(prev) {
+                Some(new) => new,
+                None => return Err(prev),
+            };
+            let cur = self.compare_and_swap(prev, new, set_order);
+            if cur == prev { return Ok(prev) }
+            prev = cur;
+        }
+    }
+}
+
+impl AtomicI64 {
+    /// Create a new `AtomicI64`
+    pub fn new(v: i64) -> AtomicI64 {
+        AtomicI64 { v: Unsafe::new(v), nocopy: marker::NoCopy, align: [] }
+    }
+
+    /// Load the value
+    #[inline]
+    pub fn load(&self, order: Ordering) -> i64 {
+        unsafe { atomic64_load(self.v.get() as *i64, order) }
+    }
+
+    /// Store the value
+    #[inline]
+    pub fn store(&self, val: i64, order: Ordering) {
+        unsafe { atomic64_store(self.v.get(), val, order); }
+    }
+
+    /// Store a value, returning the old value
+    #[inline]
+    pub fn swap(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_swap(self.v.get(), val, order) }
+    }
+
+    /// If the current value is the same as expected, store a new value
+    ///
+    /// Compare the current value with `old`; if they are the same then
+    /// replace the current value with `new`. Return the previous value.
+    /// If the return value is equal to `old` then the value was updated.
+    #[inline]
+    pub fn compare_and_swap(&self, old: i64, new: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_compare_and_swap(self.v.get(), old, new, order) }
+    }
+
+    /// Add to the current value, returning the previous
+    #[inline]
+    pub fn fetch_add(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_add(self.v.get(), val, order) }
+    }
+
+    /// Subtract from the current value, returning the previous
+    #[inline]
+    pub fn fetch_sub(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_sub(self.v.get(), val, order) }
+    }
+
+    /// Bitwise and with the current value, returning the previous
+    #[inline]
+    pub fn fetch_and(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_and(self.v.get(), val, order) }
+    }
+
+    /// Bitwise or with the current value, returning the previous
+    #[inline]
+    pub fn fetch_or(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_or(self.v.get(), val, order) }
+    }
+
+    /// Bitwise xor with the current value, returning the previous
+    #[inline]
+    pub fn fetch_xor(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_xor(self.v.get(), val, order) }
+    }
+
+    /// Store the maximum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_max(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_max(self.v.get(), val, order) }
+    }
+
+    /// Store the minimum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_min(&self, val: i64, order: Ordering) -> i64 {
+        unsafe { atomic64_min(self.v.get(), val, order) }
+    }
+
+    /// Update the value with the result of `f`, returning the previous value
+    ///
+    /// `f` is called with the current value, which is loaded with
+    /// `fetch_order`. If it returns `Some(new)`, `new` is stored with
+    /// `set_order` if the value has not changed in the meantime, and `f` is
+    /// called again with the new current value if it has. The result is
+    /// `Ok(previous)` once a value has been stored, or `Err(current)` if `f`
+    /// returned `None`.
+    #[inline]
+    pub fn fetch_update(&self, set_order: Ordering, fetch_order: Ordering,
+                        f: |i64| -> Option<i64>) -> Result<i64, i64> {
+        let mut prev = self.load(fetch_order);
+        loop {
+            let new = match f(prev) {
+                Some(new) => new,
+                None => return Err(prev),
+            };
+            let cur = self.compare_and_swap(prev, new, set_order);
+            if cur == prev { return Ok(prev) }
+            prev = cur;
+        }
+    }
+}
+
+impl AtomicU64 {
+    /// Create a new `AtomicU64`
+    pub fn new(v: u64) -> AtomicU64 {
+        AtomicU64 { v: Unsafe::new(v), nocopy: marker::NoCopy, align: [] }
+    }
+
+    /// Load the value
+    #[inline]
+    pub fn load(&self, order: Ordering) -> u64 {
+        unsafe { atomic64_load(self.v.get() as *u64, order) }
+    }
+
+    /// Store the value
+    #[inline]
+    pub fn store(&self, val: u64, order: Ordering) {
+        unsafe { atomic64_store(self.v.get(), val, order); }
+    }
+
+    /// Store a value, returning the old value
+    #[inline]
+    pub fn swap(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_swap(self.v.get(), val, order) }
+    }
+
+    /// If the current value is the same as expected, store a new value
+    ///
+    /// Compare the current value with `old`; if they are the same then
+    /// replace the current value with `new`. Return the previous value.
+    /// If the return value is equal to `old` then the value was updated.
+    #[inline]
+    pub fn compare_and_swap(&self, old: u64, new: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_compare_and_swap(self.v.get(), old, new, order) }
+    }
+
+    /// Add to the current value, returning the previous
+    #[inline]
+    pub fn fetch_add(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_add(self.v.get(), val, order) }
+    }
+
+    /// Subtract from the current value, returning the previous
+    #[inline]
+    pub fn fetch_sub(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_sub(self.v.get(), val, order) }
+    }
+
+    /// Bitwise and with the current value, returning the previous
+    #[inline]
+    pub fn fetch_and(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_and(self.v.get(), val, order) }
+    }
+
+    /// Bitwise or with the current value, returning the previous
+    #[inline]
+    pub fn fetch_or(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_or(self.v.get(), val, order) }
+    }
+
+    /// Bitwise xor with the current value, returning the previous
+    #[inline]
+    pub fn fetch_xor(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_xor(self.v.get(), val, order) }
+    }
+
+    /// Store the maximum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_max(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_umax(self.v.get(), val, order) }
+    }
+
+    /// Store the minimum of the current value and `val`, returning the
+    /// previous value
+    #[inline]
+    pub fn fetch_min(&self, val: u64, order: Ordering) -> u64 {
+        unsafe { atomic64_umin(self.v.get(), val, order) }
+    }
+
+    /// Update the value with the result of `f`, returning the previous value
+    ///
+    /// `f` is called with the current value, which is loaded with
+    /// `fetch_order`. If it returns `Some(new)`, `new` is stored with
+    /// `set_order` if the value has not changed in the meantime, and `f` is
+    /// called again with the new current value if it has. The result is
+    /// `Ok(previous)` once a value has been stored, or `Err(current)` if `f`
+    /// returned `None`.
+    #[inline]
+    pub fn fetch_update(&self, set_order: Ordering, fetch_order: Ordering,
+                        f: |u64| -> Option<u64>) -> Result<u64, u64> {
+        let mut prev = self.load(fetch_order);
+        loop {
+            let new = match f(prev) {
+                Some(new) => new,
+                None => return Err(prev),
+            };
+            let cur = self.compare_and_swap(prev, new, set_order);
+            if cur == prev { return Ok(prev) }
+            prev = cur;
+        }
+    }
 }
 
 impl<T> AtomicPtr<T> {
@@ -662,6 +990,193 @@ unsafe fn atomic_xor<T>(dst: *mut T, val: T, order: Ordering) -> T {
     }
 }
 
+#[inline]
+unsafe fn atomic_max<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    match order {
+        Acquire => intrinsics::atomic_max_acq(dst, val),
+        Release => intrinsics::atomic_max_rel(dst, val),
+        AcqRel  => intrinsics::atomic_max_acqrel(dst, val),
+        Relaxed => intrinsics::atomic_max_relaxed(dst, val),
+        _       => intrinsics::atomic_max(dst, val)
+    }
+}
+
+#[inline]
+unsafe fn atomic_min<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    match order {
+        Acquire => intrinsics::atomic_min_acq(dst, val),
+        Release => intrinsics::atomic_min_rel(dst, val),
+        AcqRel  => intrinsics::atomic_min_acqrel(dst, val),
+        Relaxed => intrinsics::atomic_min_relaxed(dst, val),
+        _       => intrinsics::atomic_min(dst, val)
+    }
+}
+
+#[inline]
+unsafe fn atomic_umax<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    match order {
+        Acquire => intrinsics::atomic_umax_acq(dst, val),
+        Release => intrinsics::atomic_umax_rel(dst, val),
+        AcqRel  => intrinsics::atomic_umax_acqrel(dst, val),
+        Relaxed => intrinsics::atomic_umax_relaxed(dst, val),
+        _       => intrinsics::atomic_umax(dst, val)
+    }
+}
+
+#[inline]
+unsafe fn atomic_umin<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    match order {
+        Acquire => intrinsics::atomic_umin_acq(dst, val),
+        Release => intrinsics::atomic_umin_rel(dst, val),
+        AcqRel  => intrinsics::atomic_umin_acqrel(dst, val),
+        Relaxed => intrinsics::atomic_umin_relaxed(dst, val),
+        _       => intrinsics::atomic_umin(dst, val)
+    }
+}
+
+
+// The 64-bit atomics go through the `atomic64_*` functions. Where the target
+// has a 64-bit compare-and-swap these are the usual intrinsics; otherwise each
+// operation is performed under one of the spinlocks in `LOCKS64`.
+
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_load<T>(dst: *T, order: Ordering) -> T {
+    atomic_load(dst, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_store<T>(dst: *mut T, val: T, order: Ordering) {
+    atomic_store(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_swap<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_swap(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_compare_and_swap<T>(dst: *mut T, old: T, new: T,
+                                       order: Ordering) -> T {
+    atomic_compare_and_swap(dst, old, new, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_add<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_add(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_sub<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_sub(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_and<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_and(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_or<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_or(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_xor<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_xor(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_max<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_max(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_min<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_min(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_umax<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_umax(dst, val, order)
+}
+#[cfg(not(target_arch = "mips"))] #[inline]
+unsafe fn atomic64_umin<T>(dst: *mut T, val: T, order: Ordering) -> T {
+    atomic_umin(dst, val, order)
+}
+
+#[cfg(target_arch = "mips")]
+static NLOCKS64: uint = 64;
+#[cfg(target_arch = "mips")]
+static mut LOCKS64: [uint, ..NLOCKS64] = [0, ..NLOCKS64];
+
+/// Runs `f` while holding the spinlock guarding the 64-bit atomic at `dst`.
+///
+/// The locks are taken and released with `SeqCst` ordering, which is at least
+/// as strong as any ordering requested of the emulated operation.
+#[cfg(target_arch = "mips")]
+unsafe fn with_lock64<T, U>(dst: *T, f: || -> U) -> U {
+    let lock = &mut LOCKS64[(dst as uint >> 3) % NLOCKS64] as *mut uint;
+    while atomic_compare_and_swap(lock, 0, 1, SeqCst) != 0 {}
+    let ret = f();
+    atomic_store(lock, 0, SeqCst);
+    ret
+}
+
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_load<T: Copy>(dst: *T, _order: Ordering) -> T {
+    with_lock64(dst, || *dst)
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_store<T: Copy>(dst: *mut T, val: T, _order: Ordering) {
+    with_lock64(dst as *T, || *dst = val)
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_swap<T: Copy>(dst: *mut T, val: T, _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; *dst = val; old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_compare_and_swap<T: Copy + Eq>(dst: *mut T, old: T, new: T,
+                                                  _order: Ordering) -> T {
+    with_lock64(dst as *T, || {
+        let cur = *dst;
+        if cur == old { *dst = new; }
+        cur
+    })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_add<T: Copy + Add<T, T>>(dst: *mut T, val: T,
+                                            _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; *dst = old + val; old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_sub<T: Copy + Sub<T, T>>(dst: *mut T, val: T,
+                                            _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; *dst = old - val; old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_and<T: Copy + BitAnd<T, T>>(dst: *mut T, val: T,
+                                               _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; *dst = old & val; old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_or<T: Copy + BitOr<T, T>>(dst: *mut T, val: T,
+                                             _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; *dst = old | val; old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_xor<T: Copy + BitXor<T, T>>(dst: *mut T, val: T,
+                                               _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; *dst = old ^ val; old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_max<T: Copy + Ord>(dst: *mut T, val: T,
+                                      _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; if val > old { *dst = val; } old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_min<T: Copy + Ord>(dst: *mut T, val: T,
+                                      _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; if val < old { *dst = val; } old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_umax<T: Copy + Ord>(dst: *mut T, val: T,
+                                       _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; if val > old { *dst = val; } old })
+}
+#[cfg(target_arch = "mips")]
+unsafe fn atomic64_umin<T: Copy + Ord>(dst: *mut T, val: T,
+                                       _order: Ordering) -> T {
+    with_lock64(dst as *T, || { let old = *dst; if val < old { *dst = val; } old })
+}
 
 /// An atomic fence.
 ///
@@ -699,6 +1214,21 @@ pub fn fence(order: Ordering) {
 #[cfg(test)]
 mod test {
     use super::*;
+    use mem;
+    use option::{Some, None};
+    use result::{Ok, Err};
+
+    #[test]
+    fn align64() {
+        assert_eq!(mem::min_align_of::<AtomicI64>(), 8);
+        assert_eq!(mem::min_align_of::<AtomicU64>(), 8);
+        assert_eq!(mem::size_of::<AtomicU64>(), 8);
+
+        // The alignment holds for atomics inside other structures too
+        struct Holder { _a: u8, b: AtomicU64 }
+        let p = Holder { _a: 0, b: AtomicU64::new(0) };
+        assert_eq!(&p.b as *AtomicU64 as uint % 8, 0);
+    }
 
     #[test]
     fn bool_() {
@@ -759,9 +1289,85 @@ mod test {
         assert_eq!(x.load(SeqCst), 0xf731 ^ 0x137f);
     }
 
+    #[test]
+    fn u64_arith() {
+        // Values above 2^32 make sure that both halves are updated together
+        let x = AtomicU64::new(0xffff_ffff);
+        assert_eq!(x.fetch_add(1, SeqCst), 0xffff_ffff);
+        assert_eq!(x.load(SeqCst), 0x1_0000_0000);
+        assert_eq!(x.fetch_sub(2, SeqCst), 0x1_0000_0000);
+        assert_eq!(x.load(SeqCst), 0xffff_fffe);
+        assert_eq!(x.swap(0x1234_5678_9abc_def0, SeqCst), 0xffff_fffe);
+        assert_eq!(x.compare_and_swap(0x1234_5678_9abc_def0, 3, SeqCst),
+                   0x1234_5678_9abc_def0);
+        assert_eq!(x.compare_and_swap(0x1234_5678_9abc_def0, 4, SeqCst), 3);
+        assert_eq!(x.load(SeqCst), 3);
+    }
+
+    #[test]
+    fn u64_bitwise() {
+        let x = AtomicU64::new(0xf731_0000_0000);
+        assert_eq!(x.fetch_and(0x137f_0000_0000, SeqCst), 0xf731_0000_0000);
+        assert_eq!(x.load(SeqCst), 0xf731_0000_0000 & 0x137f_0000_0000);
+        x.store(0xf731_0000_0000, SeqCst);
+        assert_eq!(x.fetch_or(0x137f, SeqCst), 0xf731_0000_0000);
+        assert_eq!(x.load(SeqCst), 0xf731_0000_137f);
+        assert_eq!(x.fetch_xor(0xf731_0000_137f, SeqCst), 0xf731_0000_137f);
+        assert_eq!(x.load(SeqCst), 0);
+    }
+
+    #[test]
+    fn i64_arith() {
+        let x = AtomicI64::new(-1);
+        assert_eq!(x.fetch_add(-0x1_0000_0000, SeqCst), -1);
+        assert_eq!(x.load(SeqCst), -0x1_0000_0001);
+        assert_eq!(x.fetch_sub(-0x1_0000_0002, SeqCst), -0x1_0000_0001);
+        assert_eq!(x.load(SeqCst), 1);
+    }
+
+    #[test]
+    fn int_min_max() {
+        let x = AtomicInt::new(5);
+        assert_eq!(x.fetch_max(-3, SeqCst), 5);
+        assert_eq!(x.fetch_max(7, SeqCst), 5);
+        assert_eq!(x.fetch_min(-3, SeqCst), 7);
+        assert_eq!(x.load(SeqCst), -3);
+    }
+
+    #[test]
+    fn uint_min_max() {
+        // -1 is the largest uint, so this checks that the comparison is unsigned
+        let x = AtomicUint::new(5);
+        assert_eq!(x.fetch_max(-1, SeqCst), 5);
+        assert_eq!(x.fetch_min(3, SeqCst), -1);
+        assert_eq!(x.load(SeqCst), 3);
+    }
+
+    #[test]
+    fn u64_min_max() {
+        let x = AtomicU64::new(0x1_0000_0000);
+        assert_eq!(x.fetch_max(0xffff_ffff, SeqCst), 0x1_0000_0000);
+        assert_eq!(x.fetch_min(0xffff_ffff, SeqCst), 0x1_0000_0000);
+        assert_eq!(x.load(SeqCst), 0xffff_ffff);
+
+        let y = AtomicI64::new(-0x1_0000_0000);
+        assert_eq!(y.fetch_max(1, SeqCst), -0x1_0000_0000);
+        assert_eq!(y.load(SeqCst), 1);
+    }
+
+    #[test]
+    fn uint_fetch_update() {
+        let x = AtomicUint::new(7);
+        assert_eq!(x.fetch_update(SeqCst, SeqCst, |_| None), Err(7));
+        assert_eq!(x.fetch_update(SeqCst, SeqCst, |x| Some(x * 2)), Ok(7));
+        assert_eq!(x.load(SeqCst), 14);
+    }
+
     static mut S_BOOL : AtomicBool = INIT_ATOMIC_BOOL;
     static mut S_INT  : AtomicInt  = INIT_ATOMIC_INT;
     static mut S_UINT : AtomicUint = INIT_ATOMIC_UINT;
+    static mut S_I64  : AtomicI64  = INIT_ATOMIC_I64;
+    static mut S_U64  : AtomicU64  = INIT_ATOMIC_U64;
 
     #[test]
     fn static_init() {
@@ -769,6 +1375,8 @@ mod test {
             assert!(!S_BOOL.load(SeqCst));
             assert!(S_INT.load(SeqCst) == 0);
             assert!(S_UINT.load(SeqCst) == 0);
+            assert!(S_I64.load(SeqCst) == 0);
+            assert!(S_U64.load(SeqCst) == 0);
         }
     }
 
@@ -227,34 +227,109 @@ pub fn to_digit(c: char, radix: uint) -> Option<uint> {
 
 /// Convert a char to its uppercase equivalent
 ///
-/// The case-folding performed is the common or simple mapping:
-/// it maps one unicode codepoint (one char in Rust) to its uppercase equivalent according
-/// to the Unicode database at ftp://ftp.unicode.org/Public/UNIDATA/UnicodeData.txt
-/// The additional SpecialCasing.txt is not considered here, as it expands to multiple
-/// codepoints in some cases.
+/// The case-folding performed is the full mapping: it maps one unicode
+/// codepoint (one char in Rust) to its uppercase equivalent according to the
+/// Unicode database at ftp://ftp.unicode.org/Public/UNIDATA/UnicodeData.txt,
+/// taking into account the unconditional mappings of the additional
+/// SpecialCasing.txt. Those can expand into several codepoints, for example
+/// 'ß' maps to "SS", which is why an iterator is returned. The language- and
+/// context-sensitive mappings of SpecialCasing.txt are not considered.
 ///
 /// A full reference can be found here
 /// http://www.unicode.org/versions/Unicode4.0.0/ch03.pdf#G33992
 ///
 /// # Return value
 ///
-/// Returns the char itself if no conversion was made
+/// Returns an iterator yielding the char itself if no conversion was made
 #[inline]
-pub fn to_uppercase(c: char) -> char {
-    conversions::to_upper(c)
+pub fn to_uppercase(c: char) -> ToUppercase {
+    ToUppercase(CaseMappingIter::new(conversions::to_upper_full(c)))
 }
 
 /// Convert a char to its lowercase equivalent
 ///
-/// The case-folding performed is the common or simple mapping
+/// The case-folding performed is the full mapping
 /// see `to_uppercase` for references and more information
 ///
 /// # Return value
 ///
-/// Returns the char itself if no conversion if possible
+/// Returns an iterator yielding the char itself if no conversion if possible
 #[inline]
-pub fn to_lowercase(c: char) -> char {
-    conversions::to_lower(c)
+pub fn to_lowercase(c: char) -> ToLowercase {
+    ToLowercase(CaseMappingIter::new(conversions::to_lower_full(c)))
+}
+
+/// An iterator over the characters of the uppercase equivalent of a char,
+/// see `to_uppercase`.
+pub struct ToUppercase(CaseMappingIter);
+
+impl Iterator<char> for ToUppercase {
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        let ToUppercase(ref mut iter) = *self;
+        iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let ToUppercase(ref iter) = *self;
+        iter.size_hint()
+    }
+}
+
+/// An iterator over the characters of the lowercase equivalent of a char,
+/// see `to_lowercase`.
+pub struct ToLowercase(CaseMappingIter);
+
+impl Iterator<char> for ToLowercase {
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        let ToLowercase(ref mut iter) = *self;
+        iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let ToLowercase(ref iter) = *self;
+        iter.size_hint()
+    }
+}
+
+// The case mapping tables pad mappings that are shorter than three chars
+// with '\0', which never appears in a mapping itself.
+struct CaseMappingIter {
+    chars: [char, ..3],
+    pos: uint,
+}
+
+impl CaseMappingIter {
+    fn new(chars: [char, ..3]) -> CaseMappingIter {
+        CaseMappingIter { chars: chars, pos: 0 }
+    }
+
+    fn len(&self) -> uint {
+        let mut len = 0;
+        while len < 3 && self.chars[len] != '\0' { len += 1 }
+        len - self.pos
+    }
+}
+
+impl Iterator<char> for CaseMappingIter {
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        if self.pos < 3 && self.chars[self.pos] != '\0' {
+            self.pos += 1;
+            Some(self.chars[self.pos - 1])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let len = self.len();
+        (len, Some(len))
+    }
 }
 
 ///
@@ -437,34 +512,35 @@ pub trait Char {
 
     /// Converts a character to its lowercase equivalent.
     ///
-    /// The case-folding performed is the common or simple mapping. See
-    /// `to_uppercase()` for references and more information.
+    /// The case-folding performed is the full mapping. See `to_uppercase()`
+    /// for references and more information.
     ///
     /// # Return value
     ///
-    /// Returns the lowercase equivalent of the character, or the character
-    /// itself if no conversion is possible.
-    fn to_lowercase(&self) -> char;
+    /// Returns an iterator over the lowercase equivalent of the character,
+    /// or over the character itself if no conversion is possible.
+    fn to_lowercase(&self) -> ToLowercase;
 
     /// Converts a character to its uppercase equivalent.
     ///
-    /// The case-folding performed is the common or simple mapping: it maps
-    /// one unicode codepoint (one character in Rust) to its uppercase
-    /// equivalent according to the Unicode database [1]. The additional
-    /// `SpecialCasing.txt` is not considered here, as it expands to multiple
-    /// codepoints in some cases.
+    /// The case-folding performed is the full mapping: it maps one unicode
+    /// codepoint (one character in Rust) to its uppercase equivalent
+    /// according to the Unicode database [1], including the unconditional
+    /// mappings of the additional `SpecialCasing.txt`. As these can expand
+    /// to multiple codepoints, for example 'ß' to "SS", an iterator is
+    /// returned.
     ///
     /// A full reference can be found here [2].
     ///
     /// # Return value
     ///
-    /// Returns the uppercase equivalent of the character, or the character
-    /// itself if no conversion was made.
+    /// Returns an iterator over the uppercase equivalent of the character,
+    /// or over the character itself if no conversion was made.
     ///
     /// [1]: ftp://ftp.unicode.org/Public/UNIDATA/UnicodeData.txt
     ///
     /// [2]: http://www.unicode.org/versions/Unicode4.0.0/ch03.pdf#G33992
-    fn to_uppercase(&self) -> char;
+    fn to_uppercase(&self) -> ToUppercase;
 
     /// Converts a number to the character representing it.
     ///
@@ -545,9 +621,9 @@ impl Char for char {
 
     fn to_digit(&self, radix: uint) -> Option<uint> { to_digit(*self, radix) }
 
-    fn to_lowercase(&self) -> char { to_lowercase(*self) }
+    fn to_lowercase(&self) -> ToLowercase { to_lowercase(*self) }
 
-    fn to_uppercase(&self) -> char { to_uppercase(*self) }
+    fn to_uppercase(&self) -> ToUppercase { to_uppercase(*self) }
 
     fn from_digit(num: uint, radix: uint) -> Option<char> { from_digit(num, radix) }
 
@@ -604,6 +680,7 @@ mod test {
     use super::{escape_unicode, escape_default};
 
     use char::Char;
+    use iter::Iterator;
     use slice::ImmutableVector;
     use option::{Some, None};
     use realstd::string::String;
@@ -654,37 +731,50 @@ mod test {
         assert_eq!('$'.to_digit(36u), None);
     }
 
+    fn check_case<I: Iterator<char>>(mut iter: I, expected: &[char]) {
+        assert_eq!(iter.size_hint(), (expected.len(), Some(expected.len())));
+        for &c in expected.iter() {
+            assert_eq!(iter.next(), Some(c));
+        }
+        assert_eq!(iter.next(), None);
+    }
+
     #[test]
     fn test_to_lowercase() {
-        assert_eq!('A'.to_lowercase(), 'a');
-        assert_eq!('Ö'.to_lowercase(), 'ö');
-        assert_eq!('ß'.to_lowercase(), 'ß');
-        assert_eq!('Ü'.to_lowercase(), 'ü');
-        assert_eq!('💩'.to_lowercase(), '💩');
-        assert_eq!('Σ'.to_lowercase(), 'σ');
-        assert_eq!('Τ'.to_lowercase(), 'τ');
-        assert_eq!('Ι'.to_lowercase(), 'ι');
-        assert_eq!('Γ'.to_lowercase(), 'γ');
-        assert_eq!('Μ'.to_lowercase(), 'μ');
-        assert_eq!('Α'.to_lowercase(), 'α');
-        assert_eq!('Σ'.to_lowercase(), 'σ');
+        check_case('A'.to_lowercase(), &['a']);
+        check_case('Ö'.to_lowercase(), &['ö']);
+        check_case('ß'.to_lowercase(), &['ß']);
+        check_case('Ü'.to_lowercase(), &['ü']);
+        check_case('💩'.to_lowercase(), &['💩']);
+        check_case('Σ'.to_lowercase(), &['σ']);
+        check_case('Τ'.to_lowercase(), &['τ']);
+        check_case('Ι'.to_lowercase(), &['ι']);
+        check_case('Γ'.to_lowercase(), &['γ']);
+        check_case('Μ'.to_lowercase(), &['μ']);
+        check_case('Α'.to_lowercase(), &['α']);
+        check_case('Σ'.to_lowercase(), &['σ']);
+        check_case('İ'.to_lowercase(), &['i', '\u0307']);
     }
 
     #[test]
     fn test_to_uppercase() {
-        assert_eq!('a'.to_uppercase(), 'A');
-        assert_eq!('ö'.to_uppercase(), 'Ö');
-        assert_eq!('ß'.to_uppercase(), 'ß'); // not ẞ: Latin capital letter sharp s
-        assert_eq!('ü'.to_uppercase(), 'Ü');
-        assert_eq!('💩'.to_uppercase(), '💩');
-
-        assert_eq!('σ'.to_uppercase(), 'Σ');
-        assert_eq!('τ'.to_uppercase(), 'Τ');
-        assert_eq!('ι'.to_uppercase(), 'Ι');
-        assert_eq!('γ'.to_uppercase(), 'Γ');
-        assert_eq!('μ'.to_uppercase(), 'Μ');
-        assert_eq!('α'.to_uppercase(), 'Α');
-        assert_eq!('ς'.to_uppercase(), 'Σ');
+        check_case('a'.to_uppercase(), &['A']);
+        check_case('ö'.to_uppercase(), &['Ö']);
+        check_case('ß'.to_uppercase(), &['S', 'S']); // not ẞ: Latin capital letter sharp s
+        check_case('ü'.to_uppercase(), &['Ü']);
+        check_case('💩'.to_uppercase(), &['💩']);
+
+        check_case('σ'.to_uppercase(), &['Σ']);
+        check_case('τ'.to_uppercase(), &['Τ']);
+        check_case('ι'.to_uppercase(), &['Ι']);
+        check_case('γ'.to_uppercase(), &['Γ']);
+        check_case('μ'.to_uppercase(), &['Μ']);
+        check_case('α'.to_uppercase(), &['Α']);
+        check_case('ς'.to_uppercase(), &['Σ']);
+
+        check_case('ﬃ'.to_uppercase(), &['F', 'F', 'I']);
+        check_case('ŉ'.to_uppercase(), &['ʼ', 'N']);
+        check_case('ᾳ'.to_uppercase(), &['Α', 'Ι']);
     }
 
     #[test]
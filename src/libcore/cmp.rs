@@ -40,6 +40,8 @@
 pub use PartialEq = cmp::Eq;
 pub use PartialOrd = cmp::Ord;
 
+use num::Float;
+
 /// Trait for values that can be compared for equality and inequality.
 ///
 /// This trait allows partial equality, where types can be unordered instead of
@@ -192,6 +194,59 @@ pub fn max<T: TotalOrd>(v1: T, v2: T) -> T {
     if v1 > v2 { v1 } else { v2 }
 }
 
+/// An `f32` which is totally ordered by `Float::total_cmp`, for use where `TotalOrd`
+/// is required, such as when sorting or as the key of a `TreeMap`.
+///
+/// Equality is also that of `total_cmp`: NaN is equal to itself, and -0.0 is
+/// not equal to +0.0.
+#[deriving(Clone, Show)]
+pub struct TotalF32(pub f32);
+
+/// An `f64` which is totally ordered by `Float::total_cmp`, for use where `TotalOrd`
+/// is required, such as when sorting or as the key of a `TreeMap`.
+///
+/// Equality is also that of `total_cmp`: NaN is equal to itself, and -0.0 is
+/// not equal to +0.0.
+///
+/// # Example
+///
+/// ```rust
+/// use std::cmp::TotalF64;
+///
+/// let mut v = vec![TotalF64(2.5), TotalF64(-1.0), TotalF64(0.0)];
+/// v.sort();
+/// assert!(v == vec![TotalF64(-1.0), TotalF64(0.0), TotalF64(2.5)]);
+/// ```
+#[deriving(Clone, Show)]
+pub struct TotalF64(pub f64);
+
+macro_rules! total_float_impl(
+    ($t:ident) => {
+        impl Eq for $t {
+            #[inline]
+            fn eq(&self, other: &$t) -> bool { self.cmp(other) == Equal }
+        }
+
+        impl TotalEq for $t {}
+
+        impl Ord for $t {
+            #[inline]
+            fn lt(&self, other: &$t) -> bool { self.cmp(other) == Less }
+        }
+
+        impl TotalOrd for $t {
+            #[inline]
+            fn cmp(&self, other: &$t) -> Ordering {
+                let ($t(a), $t(b)) = (*self, *other);
+                a.total_cmp(b)
+            }
+        }
+    }
+)
+
+total_float_impl!(TotalF32)
+total_float_impl!(TotalF64)
+
 // Implementation of Eq, TotalEq, Ord and TotalOrd for primitive types
 #[cfg(not(test))]
 mod impls {
@@ -15,33 +15,61 @@
 //! useful an upstream crate must define failure for libcore to use. The current
 //! interface for failure is:
 //!
-//!     fn begin_unwind(fmt: &fmt::Arguments, file: &str, line: uint) -> !;
+//!     fn begin_unwind(info: &FailureInfo) -> !;
 //!
-//! This definition allows for failing with any general message, but it does not
-//! allow for failing with a `~Any` value. The reason for this is that libcore
-//! is not allowed to allocate.
+//! The `FailureInfo` structure carries the failure message and the location at
+//! which failure was initiated. This definition allows for failing with any
+//! general message, but it does not allow for failing with a `~Any` value. The
+//! reason for this is that libcore is not allowed to allocate. Instead, the
+//! failures raised by the compiler's lang items carry a `&'static Any` class
+//! (such as `BoundsCheckFailure`) so that they can be told apart from one
+//! another without inspecting the message.
 //!
 //! This module contains a few other failure functions, but these are just the
 //! necessary lang items for the compiler. All failure is funneled through this
 //! one function. Currently, the actual symbol is declared in the standard
 //! library, but the location of this may change over time.
+//!
+//! # Defining failure
+//!
+//! The `begin_unwind` lang item is a "weak" lang item: libcore (and any other
+//! crate) may call it without defining it, but the final artifact (an
+//! executable, dylib, or staticlib) must contain exactly one definition. When
+//! linking to libstd, the definition is `std::rt::unwind::rust_begin_unwind`.
+//! A `#![no_std]` crate can instead provide its own by defining a function with
+//! the `begin_unwind` lang item and the following signature:
+//!
+//! ```ignore
+//! #[lang = "begin_unwind"]
+//! extern fn begin_unwind(info: &FailureInfo) -> ! {
+//!     // spin, reset the board, log over a serial port, ...
+//!     loop {}
+//! }
+//! ```
+//!
+//! The definition is always emitted with the symbol name `rust_begin_unwind`,
+//! regardless of what the function itself is called, and it must never return.
+//!
+//! Lines and columns are both 1-based. A column of 0 means that the column of
+//! the failure is not known, for example when failing through an entry point
+//! which only records the line.
 
 #![allow(dead_code, missing_doc)]
 
+use any::Any;
+use atomics::{AtomicUint, INIT_ATOMIC_UINT, Relaxed};
 use fmt;
 use intrinsics;
+use kinds::Send;
+use option::{Option, None, Some};
 #[cfg(not(test), stage0)]
 use str::raw::c_str_to_static_slice;
 
 #[cold] #[inline(never)] // this is the slow path, always
 #[lang="fail_"]
 #[cfg(not(test), not(stage0))]
-fn fail_(expr: &'static str, file: &'static str, line: uint) -> ! {
-    format_args!(|args| -> () {
-        begin_unwind(args, file, line);
-    }, "{}", expr);
-
-    unsafe { intrinsics::abort() }
+fn fail_(expr: &'static str, file: &'static str, line: uint, col: uint) -> ! {
+    begin_unwind_str(expr, file, line, col)
 }
 
 #[cold] #[inline(never)] // this is the slow path, always
@@ -52,7 +80,7 @@ fn fail_(expr: *u8, file: *u8, line: uint) -> ! {
         let expr = c_str_to_static_slice(expr as *i8);
         let file = c_str_to_static_slice(file as *i8);
         format_args!(|args| -> () {
-            begin_unwind(args, file, line);
+            begin_unwind(args, file, line, 0);
         }, "{}", expr);
 
         intrinsics::abort()
@@ -62,10 +90,11 @@ fn fail_(expr: *u8, file: *u8, line: uint) -> ! {
 #[cold]
 #[lang="fail_bounds_check"]
 #[cfg(not(test), not(stage0))]
-fn fail_bounds_check(file: &'static str, line: uint,
+fn fail_bounds_check(file: &'static str, line: uint, col: uint,
                      index: uint, len: uint) -> ! {
+    unsafe { BOUNDS_CHECK_FAILURES.fetch_add(1, Relaxed); }
     format_args!(|args| -> () {
-        begin_unwind(args, file, line);
+        begin_unwind_class(args, file, line, col, &BOUNDS_CHECK);
     }, "index out of bounds: the len is {} but the index is {}", len, index);
     unsafe { intrinsics::abort() }
 }
@@ -74,28 +103,190 @@ fn fail_bounds_check(file: &'static str, line: uint,
 #[lang="fail_bounds_check"]
 #[cfg(not(test), stage0)]
 fn fail_bounds_check(file: *u8, line: uint, index: uint, len: uint) -> ! {
+    unsafe { BOUNDS_CHECK_FAILURES.fetch_add(1, Relaxed); }
     let file = unsafe { c_str_to_static_slice(file as *i8) };
     format_args!(|args| -> () {
-        begin_unwind(args, file, line);
+        begin_unwind(args, file, line, 0);
     }, "index out of bounds: the len is {} but the index is {}", len, index);
     unsafe { intrinsics::abort() }
 }
 
-#[cold]
-pub fn begin_unwind(fmt: &fmt::Arguments, file: &'static str, line: uint) -> ! {
+#[cold] #[inline(never)]
+#[lang="fail_divide_by_zero"]
+#[cfg(not(test))]
+fn fail_divide_by_zero(file: &'static str, line: uint, col: uint) -> ! {
+    unsafe { ARITHMETIC_FAILURES.fetch_add(1, Relaxed); }
+    format_args!(|args| -> () {
+        begin_unwind_class(args, file, line, col, &DIVIDE_BY_ZERO);
+    }, "attempted to divide by zero");
+    unsafe { intrinsics::abort() }
+}
+
+#[cold] #[inline(never)]
+#[lang="fail_rem_by_zero"]
+#[cfg(not(test))]
+fn fail_rem_by_zero(file: &'static str, line: uint, col: uint) -> ! {
+    unsafe { ARITHMETIC_FAILURES.fetch_add(1, Relaxed); }
+    format_args!(|args| -> () {
+        begin_unwind_class(args, file, line, col, &DIVIDE_BY_ZERO);
+    }, "attempted remainder with a divisor of zero");
+    unsafe { intrinsics::abort() }
+}
+
+#[cold] #[inline(never)]
+#[lang="fail_overflow"]
+#[cfg(not(test))]
+fn fail_overflow(file: &'static str, line: uint, col: uint,
+                 op: &'static str, signed: bool, lhs: u64, rhs: u64) -> ! {
+    unsafe { ARITHMETIC_FAILURES.fetch_add(1, Relaxed); }
+    // The operands are passed widened to 64 bits; `signed` tells us how they
+    // should be read back.
+    let sym = match op {
+        "add" => "+",
+        "subtract" => "-",
+        "multiply" => "*",
+        "shift left" => "<<",
+        "shift right" => ">>",
+        _ => op,
+    };
+    if signed {
+        format_args!(|args| -> () {
+            begin_unwind_class(args, file, line, col, &OVERFLOW);
+        }, "attempt to {} with overflow: {} {} {}",
+           op, lhs as i64, sym, rhs as i64);
+    } else {
+        format_args!(|args| -> () {
+            begin_unwind_class(args, file, line, col, &OVERFLOW);
+        }, "attempt to {} with overflow: {} {} {}", op, lhs, sym, rhs);
+    }
+    unsafe { intrinsics::abort() }
+}
+
+/// The class of failures raised when an index is out of bounds.
+pub struct BoundsCheckFailure;
+
+/// The class of failures raised on division or remainder by zero.
+pub struct DivideByZeroFailure;
+
+/// The class of failures raised when an arithmetic operation overflows.
+pub struct OverflowFailure;
+
+static BOUNDS_CHECK: BoundsCheckFailure = BoundsCheckFailure;
+static DIVIDE_BY_ZERO: DivideByZeroFailure = DivideByZeroFailure;
+static OVERFLOW: OverflowFailure = OverflowFailure;
+
+static mut EXPLICIT_FAILURES: AtomicUint = INIT_ATOMIC_UINT;
+static mut BOUNDS_CHECK_FAILURES: AtomicUint = INIT_ATOMIC_UINT;
+static mut ARITHMETIC_FAILURES: AtomicUint = INIT_ATOMIC_UINT;
+
+/// The number of failures of each class which have begun in this process.
+///
+/// Bounds check and arithmetic failures are counted by libcore itself.
+/// Explicit failures are counted by the crate defining `begin_unwind`, through
+/// `record_explicit_failure`.
+#[deriving(Clone, Eq, Show)]
+pub struct FailureStats {
+    /// Failures from `fail!`, `assert!` and similar
+    pub explicit: uint,
+    /// Failures from an index being out of bounds
+    pub bounds_check: uint,
+    /// Failures from division by zero or arithmetic overflow
+    pub arithmetic: uint,
+}
+
+/// Returns the number of failures of each class so far.
+///
+/// The counters are only updated with relaxed atomic operations, so a
+/// snapshot taken while other tasks are failing may be slightly out of date.
+pub fn stats() -> FailureStats {
+    unsafe {
+        FailureStats {
+            explicit: EXPLICIT_FAILURES.load(Relaxed),
+            bounds_check: BOUNDS_CHECK_FAILURES.load(Relaxed),
+            arithmetic: ARITHMETIC_FAILURES.load(Relaxed),
+        }
+    }
+}
+
+/// Counts an explicit failure in the statistics returned by `stats`.
+pub fn record_explicit_failure() {
+    unsafe { EXPLICIT_FAILURES.fetch_add(1, Relaxed); }
+}
+
+/// A description of a failure, as handed to the `begin_unwind` lang item.
+pub struct FailureInfo<'a> {
+    /// The failure message
+    pub fmt: &'a fmt::Arguments<'a>,
+    /// The file in which failure was initiated
+    pub file: &'static str,
+    /// The (1-based) line at which failure was initiated
+    pub line: uint,
+    /// The (1-based) column at which failure was initiated, or 0 if unknown
+    pub col: uint,
+    /// An optional value describing the class of the failure, such as
+    /// `BoundsCheckFailure`. As libcore cannot allocate, this is always a
+    /// reference to a static.
+    pub payload: Option<&'static Any:Send>,
+}
+
+/// Fails with the given message and location and no payload.
+#[cold] #[rustc_begin_unwind]
+pub fn begin_unwind(fmt: &fmt::Arguments, file: &'static str, line: uint,
+                    col: uint) -> ! {
+    begin_unwind_info(&FailureInfo {
+        fmt: fmt,
+        file: file,
+        line: line,
+        col: col,
+        payload: None,
+    })
+}
+
+/// Fails with the message `fmt`, attaching `class` as the payload of the
+/// failure so it can be told apart from other failures without inspecting
+/// the message.
+#[cold] #[rustc_begin_unwind]
+pub fn begin_unwind_class(fmt: &fmt::Arguments, file: &'static str, line: uint,
+                          col: uint, class: &'static Any:Send) -> ! {
+    begin_unwind_info(&FailureInfo {
+        fmt: fmt,
+        file: file,
+        line: line,
+        col: col,
+        payload: Some(class),
+    })
+}
+
+/// Fails with a static message and no payload.
+///
+/// This is equivalent to failing with a `"{}"` format string, but the
+/// formatting is done here rather than at each call site, which keeps the code
+/// generated for `fail!("message")` as small as possible.
+#[cold] #[inline(never)] #[rustc_begin_unwind]
+pub fn begin_unwind_str(msg: &'static str, file: &'static str, line: uint,
+                        col: uint) -> ! {
+    format_args!(|args| -> () {
+        begin_unwind(args, file, line, col);
+    }, "{}", msg);
+    unsafe { intrinsics::abort() }
+}
+
+/// Invokes the failure handler defined by the `begin_unwind` lang item.
+///
+/// This is the single entry point through which all failure in libcore flows.
+#[cold] #[rustc_begin_unwind]
+pub fn begin_unwind_info(info: &FailureInfo) -> ! {
     #[allow(ctypes)]
     #[cfg(stage0)]
     extern {
         #[link_name = "rust_begin_unwind"]
-        fn begin_unwind(fmt: &fmt::Arguments, file: &'static str,
-                        line: uint) -> !;
+        fn begin_unwind(info: &FailureInfo) -> !;
     }
     #[allow(ctypes)]
     #[cfg(not(stage0))]
     extern {
         #[lang = "begin_unwind"]
-        fn begin_unwind(fmt: &fmt::Arguments, file: &'static str,
-                        line: uint) -> !;
+        fn begin_unwind(info: &FailureInfo) -> !;
     }
-    unsafe { begin_unwind(fmt, file, line) }
+    unsafe { begin_unwind(info) }
 }
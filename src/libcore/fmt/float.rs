@@ -14,7 +14,7 @@ use char;
 use container::Container;
 use fmt;
 use iter::{Iterator, range, DoubleEndedIterator};
-use num::{Float, FPNaN, FPInfinite, ToPrimitive, Primitive};
+use num::{Float, FPNaN, FPInfinite, FPZero, ToPrimitive, Primitive};
 use num::{Zero, One, cast};
 use option::{None, Some};
 use result::Ok;
@@ -22,47 +22,11 @@ use slice::{ImmutableVector, MutableVector};
 use slice;
 use str::StrSlice;
 
-/// A flag that specifies whether to use exponential (scientific) notation.
-pub enum ExponentFormat {
-    /// Do not use exponential notation.
-    ExpNone,
-    /// Use exponential notation with the exponent having a base of 10 and the
-    /// exponent sign being `e` or `E`. For example, 1000 would be printed
-    /// 1e3.
-    ExpDec,
-    /// Use exponential notation with the exponent having a base of 2 and the
-    /// exponent sign being `p` or `P`. For example, 8 would be printed 1p3.
-    ExpBin,
-}
-
-/// The number of digits used for emitting the fractional part of a number, if
-/// any.
-pub enum SignificantDigits {
-    /// All calculable digits will be printed.
-    ///
-    /// Note that bignums or fractions may cause a surprisingly large number
-    /// of digits to be printed.
-    DigAll,
-
-    /// At most the given number of digits will be printed, truncating any
-    /// trailing zeroes.
-    DigMax(uint),
-
-    /// Precisely the given number of digits will be printed.
-    DigExact(uint)
-}
-
-/// How to emit the sign of a number.
-pub enum SignFormat {
-    /// No sign will be printed. The exponent sign will also be emitted.
-    SignNone,
-    /// `-` will be printed for negative values, but no sign will be emitted
-    /// for positive numbers.
-    SignNeg,
-    /// `+` will be printed for positive values, and `-` will be printed for
-    /// negative values.
-    SignAll,
-}
+// The formatting flags are shared with the number parsing in
+// `num::strconv`, which is where they are defined.
+pub use num::strconv::{ExponentFormat, ExpNone, ExpDec, ExpBin};
+pub use num::strconv::{SignificantDigits, DigAll, DigMax, DigExact};
+pub use num::strconv::{SignFormat, SignNone, SignNeg, SignAll};
 
 static DIGIT_P_RADIX: uint = ('p' as uint) - ('a' as uint) + 11u;
 static DIGIT_E_RADIX: uint = ('e' as uint) - ('a' as uint) + 11u;
@@ -368,3 +332,378 @@ pub fn float_to_str_bytes_common<T: Primitive + Float, U>(
 
     f(buf.slice_to(end))
 }
+
+// Shortest round-trip formatting.
+//
+// When no precision is requested, floats are printed with the shortest
+// sequence of digits that reads back as the same value. The digits are
+// produced with Florian Loitsch's Grisu2 algorithm ("Printing Floating-Point
+// Numbers Quickly and Accurately with Integers", PLDI 2010), which works
+// entirely in 64-bit integer arithmetic on fixed-size buffers, so it is
+// usable without an allocator. The output always round-trips; in rare
+// cases (well under 0.1% of doubles) it is one digit longer than strictly
+// necessary.
+
+/// The maximum number of digits Grisu2 produces for an `f64`.
+static MAX_SHORTEST_DIGITS: uint = 17;
+
+/// A "do-it-yourself" floating point number, `f * 2^e`, with a full 64-bit
+/// significand.
+struct Fp {
+    f: u64,
+    e: int,
+}
+
+impl Fp {
+    /// Multiplies two numbers, rounding the 128-bit product of the
+    /// significands to its upper 64 bits.
+    fn mul(&self, other: &Fp) -> Fp {
+        static MASK: u64 = 0xffffffff;
+        let a = self.f >> 32;
+        let b = self.f & MASK;
+        let c = other.f >> 32;
+        let d = other.f & MASK;
+        let ac = a * c;
+        let bc = b * c;
+        let ad = a * d;
+        let bd = b * d;
+        let tmp = (bd >> 32) + (ad & MASK) + (bc & MASK) + (1 << 31);
+        Fp {
+            f: ac + (ad >> 32) + (bc >> 32) + (tmp >> 32),
+            e: self.e + other.e + 64,
+        }
+    }
+
+    /// Shifts the significand left until its top bit is set.
+    fn normalize(&self) -> Fp {
+        let mut f = self.f;
+        let mut e = self.e;
+        while f & (1 << 63) == 0 {
+            f <<= 1;
+            e -= 1;
+        }
+        Fp { f: f, e: e }
+    }
+}
+
+/// Normalized approximations of `10^k` as `(significand, binary exponent,
+/// k)`, for every eighth `k` from -348 to 340.
+static CACHED_POWERS: [(u64, i16, i16), ..87] = [
+    (0xfa8fd5a0081c0288, -1220, -348),
+    (0xbaaee17fa23ebf76, -1193, -340),
+    (0x8b16fb203055ac76, -1166, -332),
+    (0xcf42894a5dce35ea, -1140, -324),
+    (0x9a6bb0aa55653b2d, -1113, -316),
+    (0xe61acf033d1a45df, -1087, -308),
+    (0xab70fe17c79ac6ca, -1060, -300),
+    (0xff77b1fcbebcdc4f, -1034, -292),
+    (0xbe5691ef416bd60c, -1007, -284),
+    (0x8dd01fad907ffc3c, -980, -276),
+    (0xd3515c2831559a83, -954, -268),
+    (0x9d71ac8fada6c9b5, -927, -260),
+    (0xea9c227723ee8bcb, -901, -252),
+    (0xaecc49914078536d, -874, -244),
+    (0x823c12795db6ce57, -847, -236),
+    (0xc21094364dfb5637, -821, -228),
+    (0x9096ea6f3848984f, -794, -220),
+    (0xd77485cb25823ac7, -768, -212),
+    (0xa086cfcd97bf97f4, -741, -204),
+    (0xef340a98172aace5, -715, -196),
+    (0xb23867fb2a35b28e, -688, -188),
+    (0x84c8d4dfd2c63f3b, -661, -180),
+    (0xc5dd44271ad3cdba, -635, -172),
+    (0x936b9fcebb25c996, -608, -164),
+    (0xdbac6c247d62a584, -582, -156),
+    (0xa3ab66580d5fdaf6, -555, -148),
+    (0xf3e2f893dec3f126, -529, -140),
+    (0xb5b5ada8aaff80b8, -502, -132),
+    (0x87625f056c7c4a8b, -475, -124),
+    (0xc9bcff6034c13053, -449, -116),
+    (0x964e858c91ba2655, -422, -108),
+    (0xdff9772470297ebd, -396, -100),
+    (0xa6dfbd9fb8e5b88f, -369, -92),
+    (0xf8a95fcf88747d94, -343, -84),
+    (0xb94470938fa89bcf, -316, -76),
+    (0x8a08f0f8bf0f156b, -289, -68),
+    (0xcdb02555653131b6, -263, -60),
+    (0x993fe2c6d07b7fac, -236, -52),
+    (0xe45c10c42a2b3b06, -210, -44),
+    (0xaa242499697392d3, -183, -36),
+    (0xfd87b5f28300ca0e, -157, -28),
+    (0xbce5086492111aeb, -130, -20),
+    (0x8cbccc096f5088cc, -103, -12),
+    (0xd1b71758e219652c, -77, -4),
+    (0x9c40000000000000, -50, 4),
+    (0xe8d4a51000000000, -24, 12),
+    (0xad78ebc5ac620000, 3, 20),
+    (0x813f3978f8940984, 30, 28),
+    (0xc097ce7bc90715b3, 56, 36),
+    (0x8f7e32ce7bea5c70, 83, 44),
+    (0xd5d238a4abe98068, 109, 52),
+    (0x9f4f2726179a2245, 136, 60),
+    (0xed63a231d4c4fb27, 162, 68),
+    (0xb0de65388cc8ada8, 189, 76),
+    (0x83c7088e1aab65db, 216, 84),
+    (0xc45d1df942711d9a, 242, 92),
+    (0x924d692ca61be758, 269, 100),
+    (0xda01ee641a708dea, 295, 108),
+    (0xa26da3999aef774a, 322, 116),
+    (0xf209787bb47d6b85, 348, 124),
+    (0xb454e4a179dd1877, 375, 132),
+    (0x865b86925b9bc5c2, 402, 140),
+    (0xc83553c5c8965d3d, 428, 148),
+    (0x952ab45cfa97a0b3, 455, 156),
+    (0xde469fbd99a05fe3, 481, 164),
+    (0xa59bc234db398c25, 508, 172),
+    (0xf6c69a72a3989f5c, 534, 180),
+    (0xb7dcbf5354e9bece, 561, 188),
+    (0x88fcf317f22241e2, 588, 196),
+    (0xcc20ce9bd35c78a5, 614, 204),
+    (0x98165af37b2153df, 641, 212),
+    (0xe2a0b5dc971f303a, 667, 220),
+    (0xa8d9d1535ce3b396, 694, 228),
+    (0xfb9b7cd9a4a7443c, 720, 236),
+    (0xbb764c4ca7a44410, 747, 244),
+    (0x8bab8eefb6409c1a, 774, 252),
+    (0xd01fef10a657842c, 800, 260),
+    (0x9b10a4e5e9913129, 827, 268),
+    (0xe7109bfba19c0c9d, 853, 276),
+    (0xac2820d9623bf429, 880, 284),
+    (0x80444b5e7aa7cf85, 907, 292),
+    (0xbf21e44003acdd2d, 933, 300),
+    (0x8e679c2f5e44ff8f, 960, 308),
+    (0xd433179d9c8cb841, 986, 316),
+    (0x9e19db92b4e31ba9, 1013, 324),
+    (0xeb96bf6ebadf77d9, 1039, 332),
+    (0xaf87023b9bf0ee6b, 1066, 340),
+];
+
+/// Powers of ten that fit in a `u64`.
+static POW10: [u64, ..20] = [
+    1, 10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000,
+    1000000000, 10000000000, 100000000000, 1000000000000, 10000000000000,
+    100000000000000, 1000000000000000, 10000000000000000,
+    100000000000000000, 1000000000000000000, 10000000000000000000,
+];
+
+/// Returns a cached power of ten `c` such that multiplying a number with
+/// binary exponent `e` by it leaves the product's exponent in the range
+/// [-60, -32], along with the decimal exponent of `1 / c`.
+fn cached_power(e: int) -> (Fp, int) {
+    // ceil((-61 - e) * log10(2)), offset to index the table.
+    let dk = (-61 - e) as f64 * 0.30102999566398114 + 347.0;
+    let mut k = dk as int;
+    if dk - k as f64 > 0.0 {
+        k += 1;
+    }
+    let (f, e, k) = CACHED_POWERS[((k >> 3) + 1) as uint];
+    (Fp { f: f, e: e as int }, -(k as int))
+}
+
+/// Moves the last generated digit towards `w` for as long as it stays
+/// within the rounding interval and gets closer.
+fn grisu_round(buf: &mut [u8], len: uint, delta: u64, mut rest: u64,
+               ten_kappa: u64, wp_w: u64) {
+    while rest < wp_w && delta - rest >= ten_kappa &&
+          (rest + ten_kappa < wp_w || wp_w - rest > rest + ten_kappa - wp_w) {
+        buf[len - 1] -= 1;
+        rest += ten_kappa;
+    }
+}
+
+/// Generates the digits of `w`, stopping as soon as they identify a number
+/// within `delta` below the upper boundary `mp`.
+fn digit_gen(w: Fp, mp: Fp, mut delta: u64, buf: &mut [u8],
+             mut k: int) -> (uint, int) {
+    let shift = -mp.e as uint;
+    let one = 1u64 << shift;
+    let wp_w = mp.f - w.f;
+    let mut p1 = (mp.f >> shift) as u32;
+    let mut p2 = mp.f & (one - 1);
+    let mut len = 0u;
+
+    let mut kappa = 1u;
+    while kappa < 10 && p1 as u64 >= POW10[kappa] {
+        kappa += 1;
+    }
+
+    // Integral digits.
+    while kappa > 0 {
+        let pow = POW10[kappa - 1] as u32;
+        let d = p1 / pow;
+        p1 %= pow;
+        if d != 0 || len != 0 {
+            buf[len] = '0' as u8 + d as u8;
+            len += 1;
+        }
+        kappa -= 1;
+        let rest = (p1 as u64 << shift) + p2;
+        if rest <= delta {
+            k += kappa as int;
+            grisu_round(buf, len, delta, rest, POW10[kappa] << shift, wp_w);
+            return (len, k);
+        }
+    }
+
+    // Fractional digits.
+    let mut frac_digits = 0u;
+    loop {
+        p2 *= 10;
+        delta *= 10;
+        let d = p2 >> shift;
+        if d != 0 || len != 0 {
+            buf[len] = '0' as u8 + d as u8;
+            len += 1;
+        }
+        p2 &= one - 1;
+        frac_digits += 1;
+        if p2 < delta {
+            k -= frac_digits as int;
+            let scale = if frac_digits < POW10.len() { POW10[frac_digits] } else { 0 };
+            grisu_round(buf, len, delta, p2, one, wp_w * scale);
+            return (len, k);
+        }
+    }
+}
+
+/// Writes the shortest digits of the positive, finite number `f * 2^e`
+/// into `buf`, returning the number of digits and the decimal exponent `k`
+/// such that the number is `digits * 10^k`.
+///
+/// `lower_closer` must be true when the next representable number below is
+/// half as far away as the one above, i.e. for the powers of two that
+/// start a binade.
+fn grisu2(f: u64, e: int, lower_closer: bool, buf: &mut [u8]) -> (uint, int) {
+    let v = Fp { f: f, e: e }.normalize();
+
+    // The rounding interval is halfway to each neighbour.
+    let plus = Fp { f: (f << 1) + 1, e: e - 1 }.normalize();
+    let minus = if lower_closer {
+        Fp { f: (f << 2) - 1, e: e - 2 }
+    } else {
+        Fp { f: (f << 1) - 1, e: e - 1 }
+    };
+    let minus = Fp { f: minus.f << (minus.e - plus.e) as uint, e: plus.e };
+
+    let (c, k) = cached_power(plus.e);
+    let w = v.mul(&c);
+    let mut wp = plus.mul(&c);
+    let mut wm = minus.mul(&c);
+    // Account for the rounding error of the multiplications.
+    wm.f += 1;
+    wp.f -= 1;
+    let delta = wp.f - wm.f;
+    digit_gen(w, wp, delta, buf, k)
+}
+
+/**
+ * Converts a number to the shortest string of decimal digits that parses
+ * back to the same value, without allocating.
+ *
+ * This is the formatting used by `{}` and `{:e}` when no precision is
+ * given. No sign is emitted, so `num` is expected to be non-negative;
+ * `NaN` and infinities are printed as `NaN` and `inf`.
+ *
+ * # Arguments
+ * - `num`        - The number to convert.
+ * - `exp_format` - Whether to use decimal exponential notation. `ExpBin`
+ *                  is not supported.
+ * - `exp_upper`  - Whether to use `E` rather than `e` for the exponent.
+ * - `f`          - A closure to invoke with the bytes representing the
+ *                  float.
+ *
+ * # Failure
+ * - Fails if `exp_format` is `ExpBin`.
+ */
+pub fn float_to_shortest_str_bytes<T: Primitive + Float, U>(
+    num: T,
+    exp_format: ExponentFormat,
+    exp_upper: bool,
+    f: |&[u8]| -> U
+) -> U {
+    let exp_sep = match exp_format {
+        ExpNone => None,
+        ExpDec if exp_upper => Some('E' as u8),
+        ExpDec => Some('e' as u8),
+        ExpBin => fail!("float_to_shortest_str_bytes: binary exponents \
+                         are not supported"),
+    };
+
+    let mut digits = [0u8, ..MAX_SHORTEST_DIGITS];
+    let (ndigits, k) = match num.classify() {
+        FPNaN => return f("NaN".as_bytes()),
+        FPInfinite => return f("inf".as_bytes()),
+        FPZero => {
+            digits[0] = '0' as u8;
+            (1, 0)
+        }
+        _ => {
+            let (mant, exp, _) = num.integer_decode();
+            let (mant, exp) = (mant, exp as int);
+            let digits_bits = Float::mantissa_digits(None::<T>);
+            let min_normal_exp = Float::min_exp(None::<T>) - digits_bits as int;
+            // `integer_decode` doubles the mantissa of subnormals; undo that
+            // so the rounding interval is the real distance to the
+            // neighbours.
+            let (mant, exp) = if exp < min_normal_exp {
+                (mant >> 1, exp + 1)
+            } else {
+                (mant, exp)
+            };
+            let lower_closer = mant == 1 << (digits_bits - 1) &&
+                               exp > min_normal_exp;
+            grisu2(mant, exp, lower_closer, digits.as_mut_slice())
+        }
+    };
+    let digits = digits.slice_to(ndigits);
+
+    // Large enough for any f64 in positional notation: at most 309 integral
+    // digits, or "0." followed by 323 zeroes and 17 digits.
+    let mut buf = [0u8, ..350];
+    let mut end = 0;
+    {
+        let push = |b: u8| {
+            buf[end] = b;
+            end += 1;
+        };
+        // Position of the decimal point relative to the first digit.
+        let point = ndigits as int + k;
+        match exp_sep {
+            None if k >= 0 => {
+                for &d in digits.iter() { push(d) }
+                for _ in range(0, k) { push('0' as u8) }
+            }
+            None if point > 0 => {
+                for (i, &d) in digits.iter().enumerate() {
+                    if i as int == point { push('.' as u8) }
+                    push(d);
+                }
+            }
+            None => {
+                push('0' as u8);
+                push('.' as u8);
+                for _ in range(0, -point) { push('0' as u8) }
+                for &d in digits.iter() { push(d) }
+            }
+            Some(sep) => {
+                push(digits[0]);
+                if ndigits > 1 {
+                    push('.' as u8);
+                    for &d in digits.slice_from(1).iter() { push(d) }
+                }
+                push(sep);
+                let exp = point - 1;
+                if exp < 0 { push('-' as u8) }
+                let exp = (if exp < 0 { -exp } else { exp }) as uint;
+                let mut pow = 1;
+                while pow * 10 <= exp { pow *= 10 }
+                while pow > 0 {
+                    push('0' as u8 + (exp / pow % 10) as u8);
+                    pow /= 10;
+                }
+            }
+        }
+    }
+
+    f(buf.slice_to(end))
+}
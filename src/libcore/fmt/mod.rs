@@ -436,6 +436,10 @@ impl<'a> Formatter<'a> {
     ///
     /// This function will correctly account for the flags provided as well as
     /// the minimum width. It will not take precision into account.
+    ///
+    /// This is intended for implementations of the numeric formatting traits
+    /// on types outside of the standard library, so that they honor
+    /// specifications such as `{:+08x}` the same way the built-in integers do.
     pub fn pad_integral(&mut self, is_positive: bool, prefix: &str,
                         buf: &[u8]) -> Result {
         use fmt::rt::{FlagAlternate, FlagSignPlus, FlagSignAwareZeroPad};
@@ -545,7 +549,7 @@ impl<'a> Formatter<'a> {
     }
 
     /// Runs a callback, emitting the correct padding either before or
-    /// afterwards depending on whether right or left alingment is requested.
+    /// afterwards depending on whether right or left alignment is requested.
     fn with_padding(&mut self,
                     padding: uint,
                     default: rt::Alignment,
@@ -578,6 +582,38 @@ impl<'a> Formatter<'a> {
     pub fn write_fmt(&mut self, fmt: &Arguments) -> Result {
         write(self.buf, fmt)
     }
+
+    /// Returns the character used as 'fill' whenever there is alignment.
+    pub fn fill(&self) -> char { self.fill }
+
+    /// Returns the requested alignment, `AlignUnknown` if none was given.
+    pub fn align(&self) -> rt::Alignment { self.align }
+
+    /// Returns the requested minimum width of the output, if any.
+    pub fn width(&self) -> Option<uint> { self.width }
+
+    /// Returns the requested precision, if any.
+    pub fn precision(&self) -> Option<uint> { self.precision }
+
+    /// Tests whether the `+` flag was specified.
+    pub fn sign_plus(&self) -> bool {
+        self.flags & (1 << (rt::FlagSignPlus as uint)) != 0
+    }
+
+    /// Tests whether the `-` flag was specified.
+    pub fn sign_minus(&self) -> bool {
+        self.flags & (1 << (rt::FlagSignMinus as uint)) != 0
+    }
+
+    /// Tests whether the `#` flag was specified.
+    pub fn alternate(&self) -> bool {
+        self.flags & (1 << (rt::FlagAlternate as uint)) != 0
+    }
+
+    /// Tests whether the `0` flag was specified.
+    pub fn sign_aware_zero_pad(&self) -> bool {
+        self.flags & (1 << (rt::FlagSignAwareZeroPad as uint)) != 0
+    }
 }
 
 /// This is a function which calls are emitted to by the compiler itself to
@@ -693,20 +729,28 @@ macro_rules! floating(($ty:ident) => {
         fn fmt(&self, fmt: &mut Formatter) -> Result {
             use num::Signed;
 
-            let digits = match fmt.precision {
-                Some(i) => float::DigExact(i),
-                None => float::DigMax(6),
-            };
-            float::float_to_str_bytes_common(self.abs(),
-                                             10,
-                                             true,
-                                             float::SignNeg,
-                                             digits,
-                                             float::ExpNone,
-                                             false,
-                                             |bytes| {
-                fmt.pad_integral(*self >= 0.0, "", bytes)
-            })
+            match fmt.precision {
+                Some(i) => {
+                    float::float_to_str_bytes_common(self.abs(),
+                                                     10,
+                                                     true,
+                                                     float::SignNeg,
+                                                     float::DigExact(i),
+                                                     float::ExpNone,
+                                                     false,
+                                                     |bytes| {
+                        fmt.pad_integral(*self >= 0.0, "", bytes)
+                    })
+                }
+                None => {
+                    float::float_to_shortest_str_bytes(self.abs(),
+                                                       float::ExpNone,
+                                                       false,
+                                                       |bytes| {
+                        fmt.pad_integral(*self >= 0.0, "", bytes)
+                    })
+                }
+            }
         }
     }
 
@@ -714,20 +758,28 @@ macro_rules! floating(($ty:ident) => {
         fn fmt(&self, fmt: &mut Formatter) -> Result {
             use num::Signed;
 
-            let digits = match fmt.precision {
-                Some(i) => float::DigExact(i),
-                None => float::DigMax(6),
-            };
-            float::float_to_str_bytes_common(self.abs(),
-                                             10,
-                                             true,
-                                             float::SignNeg,
-                                             digits,
-                                             float::ExpDec,
-                                             false,
-                                             |bytes| {
-                fmt.pad_integral(*self >= 0.0, "", bytes)
-            })
+            match fmt.precision {
+                Some(i) => {
+                    float::float_to_str_bytes_common(self.abs(),
+                                                     10,
+                                                     true,
+                                                     float::SignNeg,
+                                                     float::DigExact(i),
+                                                     float::ExpDec,
+                                                     false,
+                                                     |bytes| {
+                        fmt.pad_integral(*self >= 0.0, "", bytes)
+                    })
+                }
+                None => {
+                    float::float_to_shortest_str_bytes(self.abs(),
+                                                       float::ExpDec,
+                                                       false,
+                                                       |bytes| {
+                        fmt.pad_integral(*self >= 0.0, "", bytes)
+                    })
+                }
+            }
         }
     }
 
@@ -735,20 +787,28 @@ macro_rules! floating(($ty:ident) => {
         fn fmt(&self, fmt: &mut Formatter) -> Result {
             use num::Signed;
 
-            let digits = match fmt.precision {
-                Some(i) => float::DigExact(i),
-                None => float::DigMax(6),
-            };
-            float::float_to_str_bytes_common(self.abs(),
-                                             10,
-                                             true,
-                                             float::SignNeg,
-                                             digits,
-                                             float::ExpDec,
-                                             true,
-                                             |bytes| {
-                fmt.pad_integral(*self >= 0.0, "", bytes)
-            })
+            match fmt.precision {
+                Some(i) => {
+                    float::float_to_str_bytes_common(self.abs(),
+                                                     10,
+                                                     true,
+                                                     float::SignNeg,
+                                                     float::DigExact(i),
+                                                     float::ExpDec,
+                                                     true,
+                                                     |bytes| {
+                        fmt.pad_integral(*self >= 0.0, "", bytes)
+                    })
+                }
+                None => {
+                    float::float_to_shortest_str_bytes(self.abs(),
+                                                       float::ExpDec,
+                                                       true,
+                                                       |bytes| {
+                        fmt.pad_integral(*self >= 0.0, "", bytes)
+                    })
+                }
+            }
         }
     }
 })
@@ -164,14 +164,30 @@ macro_rules! int_base {
         }
     }
 }
+// Signed integers print their two's complement bit pattern in the non-decimal
+// radixes, unless a sign was explicitly requested with `+` or `-`, in which
+// case the magnitude is printed after the sign.
+macro_rules! signed_int_base {
+    ($Trait:ident for $T:ident as $U:ident -> $Radix:ident) => {
+        impl fmt::$Trait for $T {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if f.sign_plus() || f.sign_minus() {
+                    $Radix.fmt_int(*self, f)
+                } else {
+                    $Radix.fmt_int(*self as $U, f)
+                }
+            }
+        }
+    }
+}
 macro_rules! integer {
     ($Int:ident, $Uint:ident) => {
         int_base!(Show     for $Int as $Int   -> Decimal)
         int_base!(Signed   for $Int as $Int   -> Decimal)
-        int_base!(Binary   for $Int as $Uint  -> Binary)
-        int_base!(Octal    for $Int as $Uint  -> Octal)
-        int_base!(LowerHex for $Int as $Uint  -> LowerHex)
-        int_base!(UpperHex for $Int as $Uint  -> UpperHex)
+        signed_int_base!(Binary   for $Int as $Uint -> Binary)
+        signed_int_base!(Octal    for $Int as $Uint -> Octal)
+        signed_int_base!(LowerHex for $Int as $Uint -> LowerHex)
+        signed_int_base!(UpperHex for $Int as $Uint -> UpperHex)
         radix_fmt!($Int as $Int, fmt_int)
 
         int_base!(Show     for $Uint as $Uint -> Decimal)
@@ -370,6 +386,27 @@ mod tests {
         assert!(format!("{:+05d}", -1).as_slice() == "-0001");
     }
 
+    #[test]
+    fn test_format_int_sign_radix() {
+        use {i8, i64};
+        assert!(format!("{:x}", -1i8).as_slice() == "ff");
+        assert!(format!("{:+x}", -1i8).as_slice() == "-1");
+        assert!(format!("{:-x}", -1i8).as_slice() == "-1");
+        assert!(format!("{:+x}", 10i).as_slice() == "+a");
+        assert!(format!("{:-x}", 10i).as_slice() == "a");
+        assert!(format!("{:+#x}", -255i).as_slice() == "-0xff");
+        assert!(format!("{:-#X}", -255i16).as_slice() == "-0xFF");
+        assert!(format!("{:-#o}", -8i32).as_slice() == "-0o10");
+        assert!(format!("{:-#t}", -5i64).as_slice() == "-0b101");
+        assert!(format!("{:+#08x}", -10i).as_slice() == "-0x0000a");
+        assert!(format!("{:+6x}", -10i).as_slice() == "    -a");
+        assert!(format!("{:-x}", i8::MIN).as_slice() == "-80");
+        assert!(format!("{:-t}", i64::MIN).as_slice() ==
+                "-1000000000000000000000000000000000000000000000000000000000000000");
+        // Unsigned integers are never negative.
+        assert!(format!("{:-x}", -1u8).as_slice() == "ff");
+    }
+
     #[test]
     fn test_format_int_twos_complement() {
         use {i8, i16, i32, i64};
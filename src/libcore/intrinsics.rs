@@ -303,6 +303,16 @@ extern "rust-intrinsic" {
     /// `forget` is unsafe because the caller is responsible for
     /// ensuring the argument is deallocated already.
     pub fn forget<T>(_: T) -> ();
+
+    /// Returns its argument unchanged, while preventing the optimizer from
+    /// seeing how the value is produced or used.
+    ///
+    /// This is meant for benchmarks, to keep the computation being measured
+    /// from being constant folded or removed as dead code. It has no other
+    /// effect, and in particular offers no synchronization guarantees.
+    #[cfg(not(stage0))]
+    pub fn black_box<T>(dummy: T) -> T;
+
     pub fn transmute<T,U>(e: T) -> U;
 
     /// Returns `true` if a type requires drop glue.
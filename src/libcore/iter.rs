@@ -306,6 +306,29 @@ pub trait Iterator<A> {
         Take{iter: self, n: n}
     }
 
+    /// Creates an iterator which yields the first element of this iterator,
+    /// and then every `step`th element after it.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `step` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [0, 1, 2, 3, 4, 5, 6];
+    /// let mut it = a.iter().step_by(3);
+    /// assert_eq!(it.next().unwrap(), &0);
+    /// assert_eq!(it.next().unwrap(), &3);
+    /// assert_eq!(it.next().unwrap(), &6);
+    /// assert!(it.next().is_none());
+    /// ```
+    #[inline]
+    fn step_by(self, step: uint) -> StepBy<Self> {
+        assert!(step != 0, "step_by: step must be non-zero");
+        StepBy{iter: self, step: step, first: true}
+    }
+
     /// Creates a new iterator which behaves in a similar fashion to fold.
     /// There is a state which is passed between each iteration and can be
     /// mutated as necessary. The yielded values from the closure are yielded
@@ -1036,6 +1059,76 @@ impl<A, T: Clone + Iterator<A>> CloneableIterator for T {
     }
 }
 
+/// A trait for iterators over cloneable elements.
+pub trait TupleWindowsIterator<A> {
+    /// Creates an iterator which yields each pair of adjacent elements of
+    /// this iterator, as overlapping windows of two. Every element except the
+    /// first and the last one is therefore yielded twice, once cloned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::iter::TupleWindowsIterator;
+    ///
+    /// let a = [1, 2, 3];
+    /// let mut it = a.iter().map(|&x| x).tuple_windows();
+    /// assert_eq!(it.next(), Some((1, 2)));
+    /// assert_eq!(it.next(), Some((2, 3)));
+    /// assert!(it.next().is_none());
+    /// ```
+    fn tuple_windows(self) -> TupleWindows<A, Self>;
+}
+
+impl<A: Clone, T: Iterator<A>> TupleWindowsIterator<A> for T {
+    #[inline]
+    fn tuple_windows(self) -> TupleWindows<A, T> {
+        TupleWindows{iter: self, last: None}
+    }
+}
+
+/// An iterator over the overlapping pairs of adjacent elements of `iter`
+#[deriving(Clone)]
+pub struct TupleWindows<A, T> {
+    iter: T,
+    last: Option<A>
+}
+
+impl<A: Clone, T: Iterator<A>> Iterator<(A, A)> for TupleWindows<A, T> {
+    #[inline]
+    fn next(&mut self) -> Option<(A, A)> {
+        let prev = match self.last.take() {
+            Some(x) => x,
+            None => match self.iter.next() {
+                Some(x) => x,
+                None => return None
+            }
+        };
+        match self.iter.next() {
+            Some(x) => {
+                self.last = Some(x.clone());
+                Some((prev, x))
+            }
+            None => None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let (lower, upper) = self.iter.size_hint();
+        if self.last.is_some() {
+            // the element held back from the previous window starts the
+            // next one, so each remaining element ends a window
+            (lower, upper)
+        } else {
+            let upper = match upper {
+                Some(x) => Some(x.saturating_sub(1)),
+                None => None
+            };
+            (lower.saturating_sub(1), upper)
+        }
+    }
+}
+
 /// An iterator that repeats endlessly
 #[deriving(Clone)]
 pub struct Cycle<T> {
@@ -1662,6 +1755,52 @@ impl<A, T: RandomAccessIterator<A>> RandomAccessIterator<A> for Take<T> {
     }
 }
 
+/// An iterator which yields every `step`th element of `iter`, starting with
+/// the first one.
+#[deriving(Clone)]
+pub struct StepBy<T> {
+    iter: T,
+    step: uint,
+    first: bool
+}
+
+impl<A, T: Iterator<A>> Iterator<A> for StepBy<T> {
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        if self.first {
+            self.first = false;
+            self.iter.next()
+        } else {
+            self.iter.nth(self.step - 1)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        // the number of elements yielded out of `n` remaining ones
+        let (first, step) = (self.first, self.step);
+        let steps = |n: uint| {
+            if !first {
+                n / step
+            } else if n == 0 {
+                0
+            } else {
+                1 + (n - 1) / step
+            }
+        };
+
+        let lower = steps(lower);
+        let upper = match upper {
+            Some(x) => Some(steps(x)),
+            None => None
+        };
+
+        (lower, upper)
+    }
+}
+
 
 /// An iterator to maintain state while iterating another iterator
 pub struct Scan<'a, A, B, T, St> {
@@ -2494,6 +2633,76 @@ mod tests {
         assert_eq!(i, ys.len());
     }
 
+    #[test]
+    fn test_iterator_step_by() {
+        let xs = [0u, 1, 2, 3, 5, 13, 15, 16, 17, 19];
+        let ys = [0u, 3, 15, 19];
+        let mut it = xs.iter().step_by(3);
+        let mut i = 0;
+        for &x in it {
+            assert_eq!(x, ys[i]);
+            i += 1;
+        }
+        assert_eq!(i, ys.len());
+
+        let it = range(0u, 5).step_by(1);
+        assert!(it.collect::<Vec<uint>>() == vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_iterator_step_by_zero() {
+        range(0u, 5).step_by(0);
+    }
+
+    #[test]
+    fn test_iterator_step_by_size_hint() {
+        let v = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut it = v.iter().step_by(3);
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        for n in range(0u, 4).rev() {
+            it.next();
+            assert_eq!(it.size_hint(), (n, Some(n)));
+        }
+        assert!(it.next().is_none());
+
+        assert_eq!(v.iter().step_by(20).size_hint(), (1, Some(1)));
+        assert_eq!(v.slice_to(0).iter().step_by(2).size_hint(), (0, Some(0)));
+        assert_eq!(count(0, 1).step_by(2).size_hint(), (uint::MAX / 2 + 1, None));
+        assert_eq!(v.iter().filter(|_| false).step_by(2).size_hint(), (0, Some(5)));
+    }
+
+    #[test]
+    fn test_iterator_tuple_windows() {
+        let xs = [1, 2, 3, 4];
+        let mut it = xs.iter().map(|&x| x).tuple_windows();
+        assert_eq!(it.next(), Some((1, 2)));
+        assert_eq!(it.next(), Some((2, 3)));
+        assert_eq!(it.next(), Some((3, 4)));
+        assert_eq!(it.next(), None);
+
+        assert!([1].iter().tuple_windows().next().is_none());
+        let empty: &[int] = &[];
+        assert!(empty.iter().tuple_windows().next().is_none());
+    }
+
+    #[test]
+    fn test_iterator_tuple_windows_size_hint() {
+        let v = &[0, 1, 2, 3, 4];
+        let mut it = v.iter().tuple_windows();
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        for n in range(0u, 4).rev() {
+            it.next();
+            assert_eq!(it.size_hint(), (n, Some(n)));
+        }
+        assert!(it.next().is_none());
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        assert_eq!(v.slice_to(1).iter().tuple_windows().size_hint(), (0, Some(0)));
+        assert_eq!(count(0, 1).tuple_windows().size_hint(), (uint::MAX - 1, None));
+        assert_eq!(v.iter().filter(|_| false).tuple_windows().size_hint(), (0, Some(4)));
+    }
+
     #[test]
     fn test_iterator_scan() {
         // test the type inference
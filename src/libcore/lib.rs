@@ -38,9 +38,10 @@
 //!   provided by `librlibc` which is distributed with the standard rust
 //!   distribution.
 //!
-//! * `rust_begin_unwind` - This function takes three arguments, a
-//!   `&fmt::Arguments`, a `&str`, and a `uint`. These three arguments dictate
-//!   the failure message, the file at which failure was invoked, and the line.
+//! * `rust_begin_unwind` - This function takes one argument, a
+//!   `&failure::FailureInfo`, which describes the failure message, the file,
+//!   line, and column at which failure was invoked, and an optional static
+//!   payload describing the class of the failure.
 //!   It is up to consumers of this core library to define this failure
 //!   function; it is only required to never return.
 //!
@@ -66,6 +67,7 @@
 #[cfg(test)] pub use kinds = realcore::kinds;
 #[cfg(test)] pub use ops = realcore::ops;
 #[cfg(test)] pub use ty = realcore::ty;
+#[cfg(test)] pub use nonzero = realcore::nonzero;
 
 mod macros;
 
@@ -106,6 +108,7 @@ pub mod ptr;
 #[cfg(not(test))] pub mod ops;
 #[cfg(not(test))] pub mod ty;
 #[cfg(not(test))] pub mod cmp;
+#[cfg(not(test))] pub mod nonzero;
 pub mod clone;
 pub mod default;
 pub mod container;
@@ -129,6 +132,7 @@ pub mod slice;
 pub mod str;
 pub mod tuple;
 pub mod fmt;
+pub mod from_str;
 
 // FIXME: this module should not exist. Once owned allocations are no longer a
 //        language type, this module can move outside to the owned allocation
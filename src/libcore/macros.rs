@@ -14,10 +14,12 @@
 #[macro_export]
 macro_rules! fail(
     () => (
-        fail!("{}", "explicit failure")
+        fail!("explicit failure")
     );
     ($msg:expr) => (
-        fail!("{}", $msg)
+        // A message without any interpolation doesn't need any of the
+        // formatting machinery at the call site.
+        ::core::failure::begin_unwind_str($msg, file!(), line!(), col!() + 1)
     );
     ($fmt:expr, $($arg:tt)*) => ({
         // a closure can't have return type !, so we need a full
@@ -33,7 +35,7 @@ macro_rules! fail(
         // up with the number of calls to fail!()
         #[inline(always)]
         fn run_fmt(fmt: &::std::fmt::Arguments) -> ! {
-            ::core::failure::begin_unwind(fmt, file!(), line!())
+            ::core::failure::begin_unwind(fmt, file!(), line!(), col!() + 1)
         }
         format_args!(run_fmt, $fmt, $($arg)*)
     });
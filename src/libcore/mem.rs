@@ -13,6 +13,7 @@
 //! This module contains functions for querying the size and alignment of
 //! types, initializing and manipulating memory.
 
+use default::Default;
 use ptr;
 use intrinsics;
 use intrinsics::{bswap16, bswap32, bswap64};
@@ -378,6 +379,28 @@ pub fn replace<T>(dest: &mut T, mut src: T) -> T {
     src
 }
 
+/**
+ * Replace the value at a mutable location with the default value of its type,
+ * returning the previous value.
+ *
+ * This is a shorthand for `replace(dest, Default::default())`, which is
+ * usually what is wanted when moving a collection out of a field:
+ *
+ * ```rust
+ * # struct Buffer<T> { buf: Vec<T> }
+ * impl<T> Buffer<T> {
+ *     fn get_and_reset(&mut self) -> Vec<T> {
+ *         use std::mem::take;
+ *         take(&mut self.buf)
+ *     }
+ * }
+ * ```
+ */
+#[inline]
+pub fn take<T: Default>(dest: &mut T) -> T {
+    replace(dest, Default::default())
+}
+
 /// Disposes of a value.
 ///
 /// This function can be used to destroy any value by allowing `drop` to take
@@ -558,6 +581,18 @@ mod tests {
         assert!(y.is_some());
     }
 
+    #[test]
+    fn test_take() {
+        let mut x = vec!(1i, 2, 3);
+        let y = take(&mut x);
+        assert!(x == Vec::new());
+        assert!(y == vec!(1i, 2, 3));
+
+        let mut z = Some(5i);
+        assert!(take(&mut z) == Some(5));
+        assert!(z.is_none());
+    }
+
     #[test]
     fn test_transmute_copy() {
         assert_eq!(1u, unsafe { ::mem::transmute_copy(&1) });
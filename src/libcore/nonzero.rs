@@ -0,0 +1,74 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Exposes the `NonZero` lang item, which lets the compiler use the zero
+//! value of a type to represent other values.
+//!
+//! An enum with two variants, one of which carries no data and the other of
+//! which contains a `NonZero<T>` (directly, or nested within structs), is
+//! represented without a discriminant. As with `Option<Box<T>>`, the dataless
+//! variant is represented by zeroing the wrapped value, so an
+//! `Option<MyPtr<T>>` takes up no more space than a `MyPtr<T>` does.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::mem;
+//! use std::nonzero::NonZero;
+//!
+//! struct MyPtr<T> {
+//!     ptr: NonZero<*mut T>,
+//! }
+//!
+//! assert_eq!(mem::size_of::<Option<MyPtr<int>>>(), mem::size_of::<*mut int>());
+//! ```
+
+use ops::Deref;
+
+/// Types which can be wrapped in a `NonZero`: raw pointers and the primitive
+/// integers, for which a zero value is all zero bits.
+pub trait Zeroable {}
+
+impl<T> Zeroable for *T {}
+impl<T> Zeroable for *mut T {}
+impl Zeroable for int {}
+impl Zeroable for uint {}
+impl Zeroable for i8 {}
+impl Zeroable for u8 {}
+impl Zeroable for i16 {}
+impl Zeroable for u16 {}
+impl Zeroable for i32 {}
+impl Zeroable for u32 {}
+impl Zeroable for i64 {}
+impl Zeroable for u64 {}
+
+/// A wrapper for a raw pointer or integer which is never null or zero.
+#[lang="non_zero"]
+#[deriving(Clone, Eq, TotalEq, Ord, TotalOrd, Show)]
+pub struct NonZero<T>(T);
+
+impl<T: Zeroable> NonZero<T> {
+    /// Creates a `NonZero` wrapping `inner`.
+    ///
+    /// This is unsafe because `inner` must not be null or zero: a zero value
+    /// would be read back as the dataless variant of any enclosing enum.
+    #[inline(always)]
+    pub unsafe fn new(inner: T) -> NonZero<T> {
+        NonZero(inner)
+    }
+}
+
+impl<T: Zeroable> Deref<T> for NonZero<T> {
+    #[inline]
+    fn deref<'a>(&'a self) -> &'a T {
+        let NonZero(ref inner) = *self;
+        inner
+    }
+}
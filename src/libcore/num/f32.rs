@@ -10,10 +10,13 @@
 
 //! Operations and constants for 32-bits floats (`f32` type)
 
+use cmp::{Ordering, TotalOrd};
+use from_str::FromStr;
 use intrinsics;
 use mem;
 use num::{FPNormal, FPCategory, FPZero, FPSubnormal, FPInfinite, FPNaN};
-use num::Float;
+use num::{Float, FromStrRadix};
+use num::strconv;
 use option::Option;
 
 pub static RADIX: uint = 2u;
@@ -191,6 +194,17 @@ impl Float for f32 {
         (mantissa as u64, exponent, sign)
     }
 
+    #[inline]
+    fn total_cmp(self, other: f32) -> Ordering {
+        // Flipping all but the sign bit of negative values makes the bits,
+        // read as a signed integer, order the same way as totalOrder.
+        fn key(x: f32) -> i32 {
+            let bits: i32 = unsafe { mem::transmute(x) };
+            bits ^ ((((bits >> 31) as u32) >> 1) as i32)
+        }
+        key(self).cmp(&key(other))
+    }
+
     /// Round half-way cases toward `NEG_INFINITY`
     #[inline]
     fn floor(self) -> f32 {
@@ -366,3 +380,61 @@ impl Float for f32 {
         self * (value / 180.0f32)
     }
 }
+
+impl FromStr for f32 {
+    /// Convert a string in base 10 to a float.
+    /// Accepts an optional decimal exponent.
+    ///
+    /// This function accepts strings such as
+    ///
+    /// * '3.14'
+    /// * '+3.14', equivalent to '3.14'
+    /// * '-3.14'
+    /// * '2.5E10', or equivalently, '2.5e10'
+    /// * '2.5E-10'
+    /// * '.' (understood as 0)
+    /// * '5.'
+    /// * '.5', or, equivalently,  '0.5'
+    /// * '+inf', 'inf', '-inf', 'NaN'
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Arguments
+    ///
+    /// * num - A string
+    ///
+    /// # Return value
+    ///
+    /// `None` if the string did not represent a valid number.  Otherwise,
+    /// `Some(n)` where `n` is the floating-point number represented by `num`.
+    #[inline]
+    fn from_str(val: &str) -> Option<f32> {
+        strconv::from_str_common(val, 10u, true, true, true,
+                                 strconv::ExpDec, false, false)
+    }
+}
+
+impl FromStrRadix for f32 {
+    /// Convert a string in a given base to a float.
+    ///
+    /// Due to possible conflicts, this function does **not** accept
+    /// the special values `inf`, `-inf`, `+inf` and `NaN`, **nor**
+    /// does it recognize exponents of any kind.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Arguments
+    ///
+    /// * num - A string
+    /// * radix - The base to use. Must lie in the range [2 .. 36]
+    ///
+    /// # Return value
+    ///
+    /// `None` if the string did not represent a valid number. Otherwise,
+    /// `Some(n)` where `n` is the floating-point number represented by `num`.
+    #[inline]
+    fn from_str_radix(val: &str, rdx: uint) -> Option<f32> {
+        strconv::from_str_common(val, rdx, true, true, false,
+                                 strconv::ExpNone, false, false)
+    }
+}
@@ -10,10 +10,13 @@
 
 //! Operations and constants for 64-bits floats (`f64` type)
 
+use cmp::{Ordering, TotalOrd};
+use from_str::FromStr;
 use intrinsics;
 use mem;
 use num::{FPNormal, FPCategory, FPZero, FPSubnormal, FPInfinite, FPNaN};
-use num::Float;
+use num::{Float, FromStrRadix};
+use num::strconv;
 use option::Option;
 
 // FIXME(#5527): These constants should be deprecated once associated
@@ -197,6 +200,17 @@ impl Float for f64 {
         (mantissa, exponent, sign)
     }
 
+    #[inline]
+    fn total_cmp(self, other: f64) -> Ordering {
+        // Flipping all but the sign bit of negative values makes the bits,
+        // read as a signed integer, order the same way as totalOrder.
+        fn key(x: f64) -> i64 {
+            let bits: i64 = unsafe { mem::transmute(x) };
+            bits ^ ((((bits >> 63) as u64) >> 1) as i64)
+        }
+        key(self).cmp(&key(other))
+    }
+
     /// Round half-way cases toward `NEG_INFINITY`
     #[inline]
     fn floor(self) -> f64 {
@@ -375,3 +389,60 @@ impl Float for f64 {
     }
 }
 
+impl FromStr for f64 {
+    /// Convert a string in base 10 to a float.
+    /// Accepts an optional decimal exponent.
+    ///
+    /// This function accepts strings such as
+    ///
+    /// * '3.14'
+    /// * '+3.14', equivalent to '3.14'
+    /// * '-3.14'
+    /// * '2.5E10', or equivalently, '2.5e10'
+    /// * '2.5E-10'
+    /// * '.' (understood as 0)
+    /// * '5.'
+    /// * '.5', or, equivalently,  '0.5'
+    /// * '+inf', 'inf', '-inf', 'NaN'
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Arguments
+    ///
+    /// * num - A string
+    ///
+    /// # Return value
+    ///
+    /// `none` if the string did not represent a valid number.  Otherwise,
+    /// `Some(n)` where `n` is the floating-point number represented by `num`.
+    #[inline]
+    fn from_str(val: &str) -> Option<f64> {
+        strconv::from_str_common(val, 10u, true, true, true,
+                                 strconv::ExpDec, false, false)
+    }
+}
+
+impl FromStrRadix for f64 {
+    /// Convert a string in a given base to a float.
+    ///
+    /// Due to possible conflicts, this function does **not** accept
+    /// the special values `inf`, `-inf`, `+inf` and `NaN`, **nor**
+    /// does it recognize exponents of any kind.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Arguments
+    ///
+    /// * num - A string
+    /// * radix - The base to use. Must lie in the range [2 .. 36]
+    ///
+    /// # Return value
+    ///
+    /// `None` if the string did not represent a valid number. Otherwise,
+    /// `Some(n)` where `n` is the floating-point number represented by `num`.
+    #[inline]
+    fn from_str_radix(val: &str, rdx: uint) -> Option<f64> {
+        strconv::from_str_common(val, rdx, true, true, false,
+                                 strconv::ExpNone, false, false)
+    }
+}
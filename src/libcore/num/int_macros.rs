@@ -28,6 +28,40 @@ pub static MIN: $T = (-1 as $T) << (BITS - 1);
 // calling the `Bounded::max_value` function.
 pub static MAX: $T = !MIN;
 
+// String conversion functions and impl str -> num
+
+/// Parse a byte slice as a number in the given base
+///
+/// Yields an `Option` because `buf` may or may not actually be parseable.
+///
+/// # Examples
+///
+/// ```
+/// let num = std::i64::parse_bytes([49,50,51,52,53,54,55,56,57], 10);
+/// assert!(num == Some(123456789));
+/// ```
+#[inline]
+pub fn parse_bytes(buf: &[u8], radix: uint) -> ::option::Option<$T> {
+    ::num::strconv::from_str_bytes_common(buf, radix, true, false, false,
+                                          ::num::strconv::ExpNone, false, false)
+}
+
+impl ::from_str::FromStr for $T {
+    #[inline]
+    fn from_str(s: &str) -> ::option::Option<$T> {
+        ::num::strconv::from_str_common(s, 10u, true, false, false,
+                                        ::num::strconv::ExpNone, false, false)
+    }
+}
+
+impl ::num::FromStrRadix for $T {
+    #[inline]
+    fn from_str_radix(s: &str, radix: uint) -> ::option::Option<$T> {
+        ::num::strconv::from_str_common(s, radix, true, false, false,
+                                        ::num::strconv::ExpNone, false, false)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use prelude::*;
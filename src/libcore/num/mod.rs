@@ -24,6 +24,8 @@ use ops::{Add, Sub, Mul, Div, Rem, Neg};
 use ops::{Not, BitAnd, BitOr, BitXor, Shl, Shr};
 use option::{Option, Some, None};
 
+pub mod strconv;
+
 /// The base trait for numeric types
 pub trait Num: Eq + Zero + One
              + Neg<Self>
@@ -506,7 +508,11 @@ pub trait Int: Primitive
              + CheckedAdd
              + CheckedSub
              + CheckedMul
-             + CheckedDiv {}
+             + CheckedDiv
+             + CheckedRem
+             + OverflowingAdd
+             + OverflowingSub
+             + OverflowingMul {}
 
 trait_impl!(Int for uint u8 u16 u32 u64 int i8 i16 i32 i64)
 
@@ -1032,6 +1038,16 @@ impl_num_cast!(int,   to_int)
 impl_num_cast!(f32,   to_f32)
 impl_num_cast!(f64,   to_f64)
 
+/// A generic trait for converting a string with a radix (base) to a value
+pub trait FromStrRadix {
+    fn from_str_radix(str: &str, radix: uint) -> Option<Self>;
+}
+
+/// A utility function that just calls FromStrRadix::from_str_radix.
+pub fn from_str_radix<T: FromStrRadix>(str: &str, radix: uint) -> Option<T> {
+    FromStrRadix::from_str_radix(str, radix)
+}
+
 /// Saturating math operations
 pub trait Saturating {
     /// Saturating addition operator.
@@ -1041,9 +1057,13 @@ pub trait Saturating {
     /// Saturating subtraction operator.
     /// Returns a-b, saturating at the numeric bounds instead of overflowing.
     fn saturating_sub(self, v: Self) -> Self;
+
+    /// Saturating multiplication operator.
+    /// Returns a*b, saturating at the numeric bounds instead of overflowing.
+    fn saturating_mul(self, v: Self) -> Self;
 }
 
-impl<T: CheckedAdd + CheckedSub + Zero + Ord + Bounded> Saturating for T {
+impl<T: CheckedAdd + CheckedSub + CheckedMul + Zero + Ord + Bounded> Saturating for T {
     #[inline]
     fn saturating_add(self, v: T) -> T {
         match self.checked_add(&v) {
@@ -1067,6 +1087,21 @@ impl<T: CheckedAdd + CheckedSub + Zero + Ord + Bounded> Saturating for T {
             }
         }
     }
+
+    #[inline]
+    fn saturating_mul(self, v: T) -> T {
+        match self.checked_mul(&v) {
+            Some(x) => x,
+            None => {
+                let zero: T = Zero::zero();
+                if (self < zero) == (v < zero) {
+                    Bounded::max_value()
+                } else {
+                    Bounded::min_value()
+                }
+            }
+        }
+    }
 }
 
 /// Performs addition that returns `None` instead of wrapping around on overflow.
@@ -1221,6 +1256,301 @@ macro_rules! checkeddiv_uint_impl(
 
 checkeddiv_uint_impl!(uint u8 u16 u32 u64)
 
+/// Performs a remainder operation that returns `None` instead of failing on a
+/// zero divisor or overflow.
+pub trait CheckedRem: Rem<Self, Self> {
+    /// Computes the remainder of dividing two numbers. If the divisor is zero
+    /// or the division would overflow, `None` is returned.
+    fn checked_rem(&self, v: &Self) -> Option<Self>;
+}
+
+macro_rules! checkedrem_int_impl(
+    ($t:ty, $min:expr) => {
+        impl CheckedRem for $t {
+            #[inline]
+            fn checked_rem(&self, v: &$t) -> Option<$t> {
+                if *v == 0 || (*self == $min && *v == -1) {
+                    None
+                } else {
+                    Some(self % *v)
+                }
+            }
+        }
+    }
+)
+
+checkedrem_int_impl!(int, int::MIN)
+checkedrem_int_impl!(i8, i8::MIN)
+checkedrem_int_impl!(i16, i16::MIN)
+checkedrem_int_impl!(i32, i32::MIN)
+checkedrem_int_impl!(i64, i64::MIN)
+
+macro_rules! checkedrem_uint_impl(
+    ($($t:ty)*) => ($(
+        impl CheckedRem for $t {
+            #[inline]
+            fn checked_rem(&self, v: &$t) -> Option<$t> {
+                if *v == 0 {
+                    None
+                } else {
+                    Some(self % *v)
+                }
+            }
+        }
+    )*)
+)
+
+checkedrem_uint_impl!(uint u8 u16 u32 u64)
+
+/// Performs addition that wraps around on overflow, reporting whether it did.
+pub trait OverflowingAdd: Add<Self, Self> {
+    /// Adds two numbers, returning the wrapped result along with `true` if
+    /// the addition overflowed.
+    fn overflowing_add(&self, v: &Self) -> (Self, bool);
+}
+
+macro_rules! overflowing_impl(
+    ($trait_name:ident, $method:ident, $t:ty, $op:path) => {
+        impl $trait_name for $t {
+            #[inline]
+            fn $method(&self, v: &$t) -> ($t, bool) {
+                unsafe { $op(*self, *v) }
+            }
+        }
+    }
+)
+macro_rules! overflowing_cast_impl(
+    ($trait_name:ident, $method:ident, $t:ty, $cast:ty, $op:path) => {
+        impl $trait_name for $t {
+            #[inline]
+            fn $method(&self, v: &$t) -> ($t, bool) {
+                unsafe {
+                    let (x, y) = $op(*self as $cast, *v as $cast);
+                    (x as $t, y)
+                }
+            }
+        }
+    }
+)
+
+#[cfg(target_word_size = "32")]
+overflowing_cast_impl!(OverflowingAdd, overflowing_add, uint, u32,
+                       intrinsics::u32_add_with_overflow)
+#[cfg(target_word_size = "64")]
+overflowing_cast_impl!(OverflowingAdd, overflowing_add, uint, u64,
+                       intrinsics::u64_add_with_overflow)
+
+overflowing_impl!(OverflowingAdd, overflowing_add, u8,  intrinsics::u8_add_with_overflow)
+overflowing_impl!(OverflowingAdd, overflowing_add, u16, intrinsics::u16_add_with_overflow)
+overflowing_impl!(OverflowingAdd, overflowing_add, u32, intrinsics::u32_add_with_overflow)
+overflowing_impl!(OverflowingAdd, overflowing_add, u64, intrinsics::u64_add_with_overflow)
+
+#[cfg(target_word_size = "32")]
+overflowing_cast_impl!(OverflowingAdd, overflowing_add, int, i32,
+                       intrinsics::i32_add_with_overflow)
+#[cfg(target_word_size = "64")]
+overflowing_cast_impl!(OverflowingAdd, overflowing_add, int, i64,
+                       intrinsics::i64_add_with_overflow)
+
+overflowing_impl!(OverflowingAdd, overflowing_add, i8,  intrinsics::i8_add_with_overflow)
+overflowing_impl!(OverflowingAdd, overflowing_add, i16, intrinsics::i16_add_with_overflow)
+overflowing_impl!(OverflowingAdd, overflowing_add, i32, intrinsics::i32_add_with_overflow)
+overflowing_impl!(OverflowingAdd, overflowing_add, i64, intrinsics::i64_add_with_overflow)
+
+/// Performs subtraction that wraps around on underflow, reporting whether it
+/// did.
+pub trait OverflowingSub: Sub<Self, Self> {
+    /// Subtracts two numbers, returning the wrapped result along with `true`
+    /// if the subtraction underflowed.
+    fn overflowing_sub(&self, v: &Self) -> (Self, bool);
+}
+
+#[cfg(target_word_size = "32")]
+overflowing_cast_impl!(OverflowingSub, overflowing_sub, uint, u32,
+                       intrinsics::u32_sub_with_overflow)
+#[cfg(target_word_size = "64")]
+overflowing_cast_impl!(OverflowingSub, overflowing_sub, uint, u64,
+                       intrinsics::u64_sub_with_overflow)
+
+overflowing_impl!(OverflowingSub, overflowing_sub, u8,  intrinsics::u8_sub_with_overflow)
+overflowing_impl!(OverflowingSub, overflowing_sub, u16, intrinsics::u16_sub_with_overflow)
+overflowing_impl!(OverflowingSub, overflowing_sub, u32, intrinsics::u32_sub_with_overflow)
+overflowing_impl!(OverflowingSub, overflowing_sub, u64, intrinsics::u64_sub_with_overflow)
+
+#[cfg(target_word_size = "32")]
+overflowing_cast_impl!(OverflowingSub, overflowing_sub, int, i32,
+                       intrinsics::i32_sub_with_overflow)
+#[cfg(target_word_size = "64")]
+overflowing_cast_impl!(OverflowingSub, overflowing_sub, int, i64,
+                       intrinsics::i64_sub_with_overflow)
+
+overflowing_impl!(OverflowingSub, overflowing_sub, i8,  intrinsics::i8_sub_with_overflow)
+overflowing_impl!(OverflowingSub, overflowing_sub, i16, intrinsics::i16_sub_with_overflow)
+overflowing_impl!(OverflowingSub, overflowing_sub, i32, intrinsics::i32_sub_with_overflow)
+overflowing_impl!(OverflowingSub, overflowing_sub, i64, intrinsics::i64_sub_with_overflow)
+
+/// Performs multiplication that wraps around on underflow or overflow,
+/// reporting whether it did.
+pub trait OverflowingMul: Mul<Self, Self> {
+    /// Multiplies two numbers, returning the wrapped result along with `true`
+    /// if the multiplication underflowed or overflowed.
+    fn overflowing_mul(&self, v: &Self) -> (Self, bool);
+}
+
+#[cfg(target_word_size = "32")]
+overflowing_cast_impl!(OverflowingMul, overflowing_mul, uint, u32,
+                       intrinsics::u32_mul_with_overflow)
+#[cfg(target_word_size = "64")]
+overflowing_cast_impl!(OverflowingMul, overflowing_mul, uint, u64,
+                       intrinsics::u64_mul_with_overflow)
+
+overflowing_impl!(OverflowingMul, overflowing_mul, u8,  intrinsics::u8_mul_with_overflow)
+overflowing_impl!(OverflowingMul, overflowing_mul, u16, intrinsics::u16_mul_with_overflow)
+overflowing_impl!(OverflowingMul, overflowing_mul, u32, intrinsics::u32_mul_with_overflow)
+overflowing_impl!(OverflowingMul, overflowing_mul, u64, intrinsics::u64_mul_with_overflow)
+
+#[cfg(target_word_size = "32")]
+overflowing_cast_impl!(OverflowingMul, overflowing_mul, int, i32,
+                       intrinsics::i32_mul_with_overflow)
+#[cfg(target_word_size = "64")]
+overflowing_cast_impl!(OverflowingMul, overflowing_mul, int, i64,
+                       intrinsics::i64_mul_with_overflow)
+
+overflowing_impl!(OverflowingMul, overflowing_mul, i8,  intrinsics::i8_mul_with_overflow)
+overflowing_impl!(OverflowingMul, overflowing_mul, i16, intrinsics::i16_mul_with_overflow)
+overflowing_impl!(OverflowingMul, overflowing_mul, i32, intrinsics::i32_mul_with_overflow)
+overflowing_impl!(OverflowingMul, overflowing_mul, i64, intrinsics::i64_mul_with_overflow)
+
+/// An integer whose arithmetic intentionally wraps around on overflow.
+///
+/// The arithmetic operators on `Wrapping<T>` compute their results modulo
+/// 2<sup>N</sup>, where N is the width of `T`, and never overflow. This makes
+/// code which relies on wraparound, such as hash functions and random number
+/// generators, explicit about it. Shift amounts are taken modulo N.
+///
+/// # Example
+///
+/// ```rust
+/// use std::num::Wrapping;
+///
+/// let Wrapping(x) = Wrapping(250u8) + Wrapping(10);
+/// assert_eq!(x, 4);
+/// ```
+#[deriving(Clone, Eq, TotalEq, Ord, TotalOrd, Show)]
+pub struct Wrapping<T>(pub T);
+
+impl<T: OverflowingAdd> Add<Wrapping<T>, Wrapping<T>> for Wrapping<T> {
+    #[inline]
+    fn add(&self, other: &Wrapping<T>) -> Wrapping<T> {
+        let (&Wrapping(ref a), &Wrapping(ref b)) = (self, other);
+        let (x, _) = a.overflowing_add(b);
+        Wrapping(x)
+    }
+}
+
+impl<T: OverflowingSub> Sub<Wrapping<T>, Wrapping<T>> for Wrapping<T> {
+    #[inline]
+    fn sub(&self, other: &Wrapping<T>) -> Wrapping<T> {
+        let (&Wrapping(ref a), &Wrapping(ref b)) = (self, other);
+        let (x, _) = a.overflowing_sub(b);
+        Wrapping(x)
+    }
+}
+
+impl<T: OverflowingMul> Mul<Wrapping<T>, Wrapping<T>> for Wrapping<T> {
+    #[inline]
+    fn mul(&self, other: &Wrapping<T>) -> Wrapping<T> {
+        let (&Wrapping(ref a), &Wrapping(ref b)) = (self, other);
+        let (x, _) = a.overflowing_mul(b);
+        Wrapping(x)
+    }
+}
+
+impl<T: Not<T>> Not<Wrapping<T>> for Wrapping<T> {
+    #[inline]
+    fn not(&self) -> Wrapping<T> {
+        let &Wrapping(ref a) = self;
+        Wrapping(!*a)
+    }
+}
+
+macro_rules! wrapping_bit_impl(
+    ($trait_name:ident, $method:ident) => {
+        impl<T: $trait_name<T, T>> $trait_name<Wrapping<T>, Wrapping<T>> for Wrapping<T> {
+            #[inline]
+            fn $method(&self, other: &Wrapping<T>) -> Wrapping<T> {
+                let (&Wrapping(ref a), &Wrapping(ref b)) = (self, other);
+                Wrapping(a.$method(b))
+            }
+        }
+    }
+)
+
+wrapping_bit_impl!(BitAnd, bitand)
+wrapping_bit_impl!(BitOr, bitor)
+wrapping_bit_impl!(BitXor, bitxor)
+
+macro_rules! wrapping_shift_impl(
+    ($($t:ty)*) => ($(
+        impl Shl<uint, Wrapping<$t>> for Wrapping<$t> {
+            #[inline]
+            fn shl(&self, other: &uint) -> Wrapping<$t> {
+                let &Wrapping(a) = self;
+                Wrapping(a << (*other & (size_of::<$t>() * 8 - 1)) as $t)
+            }
+        }
+
+        impl Shr<uint, Wrapping<$t>> for Wrapping<$t> {
+            #[inline]
+            fn shr(&self, other: &uint) -> Wrapping<$t> {
+                let &Wrapping(a) = self;
+                Wrapping(a >> (*other & (size_of::<$t>() * 8 - 1)) as $t)
+            }
+        }
+    )*)
+)
+
+wrapping_shift_impl!(uint u8 u16 u32 u64 int i8 i16 i32 i64)
+
+impl<T: Zero + OverflowingAdd> Zero for Wrapping<T> {
+    #[inline]
+    fn zero() -> Wrapping<T> { Wrapping(Zero::zero()) }
+    #[inline]
+    fn is_zero(&self) -> bool {
+        let &Wrapping(ref a) = self;
+        a.is_zero()
+    }
+}
+
+impl<T: One + OverflowingMul> One for Wrapping<T> {
+    #[inline]
+    fn one() -> Wrapping<T> { Wrapping(One::one()) }
+}
+
+impl<T: ToPrimitive> ToPrimitive for Wrapping<T> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        let &Wrapping(ref a) = self;
+        a.to_i64()
+    }
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        let &Wrapping(ref a) = self;
+        a.to_u64()
+    }
+}
+
+impl<T: FromPrimitive> FromPrimitive for Wrapping<T> {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Wrapping<T>> {
+        FromPrimitive::from_i64(n).map(|x| Wrapping(x))
+    }
+    #[inline]
+    fn from_u64(n: u64) -> Option<Wrapping<T>> {
+        FromPrimitive::from_u64(n).map(|x| Wrapping(x))
+    }
+}
+
 /// Helper function for testing numeric operations
 #[cfg(test)]
 pub fn test_num<T:Num + NumCast + ::std::fmt::Show>(ten: T, two: T) {
@@ -1302,6 +1632,14 @@ pub trait Float: Signed + Primitive {
     /// Returns the mantissa, exponent and sign as integers, respectively.
     fn integer_decode(self) -> (u64, i16, i8);
 
+    /// Compares two values according to the totalOrder predicate of
+    /// IEEE 754-2008 (section 5.10).
+    ///
+    /// Unlike the comparison operators, this orders every value, including
+    /// NaN: negative NaNs sort before negative infinity, positive NaNs after
+    /// positive infinity, and -0.0 before +0.0.
+    fn total_cmp(self, other: Self) -> Ordering;
+
     /// Return the largest integer less than or equal to a number.
     fn floor(self) -> Self;
     /// Return the smallest integer greater than or equal to a number.
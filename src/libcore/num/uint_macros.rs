@@ -19,6 +19,40 @@ pub static BYTES : uint = ($bits / 8);
 pub static MIN: $T = 0 as $T;
 pub static MAX: $T = 0 as $T - 1 as $T;
 
+// String conversion functions and impl str -> num
+
+/// Parse a byte slice as a number in the given base
+///
+/// Yields an `Option` because `buf` may or may not actually be parseable.
+///
+/// # Examples
+///
+/// ```
+/// let num = std::uint::parse_bytes([49,50,51,52,53,54,55,56,57], 10);
+/// assert!(num == Some(123456789));
+/// ```
+#[inline]
+pub fn parse_bytes(buf: &[u8], radix: uint) -> ::option::Option<$T> {
+    ::num::strconv::from_str_bytes_common(buf, radix, false, false, false,
+                                          ::num::strconv::ExpNone, false, false)
+}
+
+impl ::from_str::FromStr for $T {
+    #[inline]
+    fn from_str(s: &str) -> ::option::Option<$T> {
+        ::num::strconv::from_str_common(s, 10u, false, false, false,
+                                        ::num::strconv::ExpNone, false, false)
+    }
+}
+
+impl ::num::FromStrRadix for $T {
+    #[inline]
+    fn from_str_radix(s: &str, radix: uint) -> ::option::Option<$T> {
+        ::num::strconv::from_str_common(s, radix, false, false, false,
+                                        ::num::strconv::ExpNone, false, false)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use prelude::*;
@@ -653,6 +653,138 @@ pub trait Index<Index,Result> {
     fn index(&self, index: &Index) -> Result;
 }
 
+/**
+ *
+ * The `IndexMut` trait is used to specify the functionality of indexing
+ * operations like `arr[idx]`, when used in a mutable context.
+ *
+ * # Example
+ *
+ * A trivial implementation of `IndexMut`. When `Foo[Foo]` is assigned to,
+ * it ends up calling `index_mut`, and therefore, `main` prints `Indexing!`.
+ *
+ * ```
+ * struct Foo;
+ *
+ * impl IndexMut<Foo, Foo> for Foo {
+ *     fn index_mut<'a>(&'a mut self, _rhs: &Foo) -> &'a mut Foo {
+ *         println!("Indexing!");
+ *         self
+ *     }
+ * }
+ *
+ * fn main() {
+ *     let mut foo = Foo;
+ *     foo[Foo] = Foo;
+ * }
+ * ```
+ */
+#[lang="index_mut"]
+pub trait IndexMut<Index,Result> {
+    /// The method for the indexing (`Foo[Bar]`) operation in a mutable context
+    fn index_mut<'a>(&'a mut self, index: &Index) -> &'a mut Result;
+}
+
+/**
+ *
+ * The `Slice` trait is used to specify the functionality of slicing operations
+ * like `arr[from..to]` when used in an immutable context.
+ *
+ * # Example
+ *
+ * A trivial implementation of `Slice`. When `Foo[..Foo]` happens, it ends up
+ * calling `slice_to_or_fail`, and therefore, `main` prints `Slicing!`.
+ *
+ * ```
+ * struct Foo;
+ *
+ * impl<'a> Slice<'a, Foo, &'a Foo> for Foo {
+ *     fn as_slice_(&'a self) -> &'a Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ *     fn slice_from_or_fail(&'a self, _from: &Foo) -> &'a Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ *     fn slice_to_or_fail(&'a self, _to: &Foo) -> &'a Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ *     fn slice_or_fail(&'a self, _from: &Foo, _to: &Foo) -> &'a Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ * }
+ *
+ * fn main() {
+ *     Foo[..Foo];
+ * }
+ * ```
+ */
+#[lang="slice"]
+pub trait Slice<'a, Idx, Result> {
+    /// The method for the slicing operation foo[]
+    fn as_slice_(&'a self) -> Result;
+    /// The method for the slicing operation foo[from..]
+    fn slice_from_or_fail(&'a self, from: &Idx) -> Result;
+    /// The method for the slicing operation foo[..to]
+    fn slice_to_or_fail(&'a self, to: &Idx) -> Result;
+    /// The method for the slicing operation foo[from..to]
+    fn slice_or_fail(&'a self, from: &Idx, to: &Idx) -> Result;
+}
+
+/**
+ *
+ * The `SliceMut` trait is used to specify the functionality of slicing
+ * operations like `arr[mut from..to]`, which yield a mutable slice.
+ *
+ * # Example
+ *
+ * A trivial implementation of `SliceMut`. When `Foo[mut Foo..]` happens, it
+ * ends up calling `slice_from_or_fail_mut`, and therefore, `main` prints
+ * `Slicing!`.
+ *
+ * ```
+ * struct Foo;
+ *
+ * impl<'a> SliceMut<'a, Foo, &'a mut Foo> for Foo {
+ *     fn as_mut_slice_(&'a mut self) -> &'a mut Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ *     fn slice_from_or_fail_mut(&'a mut self, _from: &Foo) -> &'a mut Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ *     fn slice_to_or_fail_mut(&'a mut self, _to: &Foo) -> &'a mut Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ *     fn slice_or_fail_mut(&'a mut self, _from: &Foo, _to: &Foo) -> &'a mut Foo {
+ *         println!("Slicing!");
+ *         self
+ *     }
+ * }
+ *
+ * fn main() {
+ *     let mut foo = Foo;
+ *     foo[mut Foo..];
+ * }
+ * ```
+ */
+#[lang="slice_mut"]
+pub trait SliceMut<'a, Idx, Result> {
+    /// The method for the slicing operation foo[mut]
+    fn as_mut_slice_(&'a mut self) -> Result;
+    /// The method for the slicing operation foo[mut from..]
+    fn slice_from_or_fail_mut(&'a mut self, from: &Idx) -> Result;
+    /// The method for the slicing operation foo[mut ..to]
+    fn slice_to_or_fail_mut(&'a mut self, to: &Idx) -> Result;
+    /// The method for the slicing operation foo[mut from..to]
+    fn slice_or_fail_mut(&'a mut self, from: &Idx, to: &Idx) -> Result;
+}
+
 /**
  *
  * The `Deref` trait is used to specify the functionality of dereferencing
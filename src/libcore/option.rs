@@ -141,6 +141,7 @@
 //! }
 //! ```
 
+use clone::Clone;
 use cmp::{Eq, TotalEq, TotalOrd};
 use default::Default;
 use iter::{Iterator, DoubleEndedIterator, FromIterator, ExactSize};
@@ -148,7 +149,7 @@ use mem;
 use slice;
 
 /// The `Option`
-#[deriving(Clone, Eq, Ord, TotalEq, TotalOrd, Show)]
+#[deriving(Eq, Ord, TotalEq, TotalOrd, Show)]
 pub enum Option<T> {
     /// No value
     None,
@@ -507,6 +508,27 @@ impl<T: Default> Option<T> {
 // Trait implementations
 /////////////////////////////////////////////////////////////////////////////
 
+impl<T: Clone> Clone for Option<T> {
+    #[inline]
+    fn clone(&self) -> Option<T> {
+        match *self {
+            Some(ref x) => Some(x.clone()),
+            None => None
+        }
+    }
+
+    /// Clones `source` into `self`, reusing the resources of the contained
+    /// value when both are `Some`.
+    #[inline]
+    fn clone_from(&mut self, source: &Option<T>) {
+        match (self.as_mut(), source) {
+            (Some(to), &Some(ref from)) => { to.clone_from(from); return }
+            _ => {}
+        }
+        *self = source.clone();
+    }
+}
+
 impl<T> Default for Option<T> {
     #[inline]
     fn default() -> Option<T> { None }
@@ -33,11 +33,13 @@ pub use kinds::{Copy, Send, Sized, Share};
 pub use ops::{Add, Sub, Mul, Div, Rem, Neg, Not};
 pub use ops::{BitAnd, BitOr, BitXor};
 pub use ops::{Drop, Deref, DerefMut};
-pub use ops::{Shl, Shr, Index};
+pub use ops::{Shl, Shr, Index, IndexMut};
+pub use ops::{Slice, SliceMut};
 pub use option::{Option, Some, None};
 pub use result::{Result, Ok, Err};
 
 // Reexported functions
+pub use from_str::from_str;
 pub use iter::range;
 pub use mem::drop;
 
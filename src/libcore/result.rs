@@ -283,7 +283,7 @@ use option::{None, Option, Some};
 /// `Result` is a type that represents either success (`Ok`) or failure (`Err`).
 ///
 /// See the [`std::result`](index.html) module documentation for details.
-#[deriving(Clone, Eq, Ord, TotalEq, TotalOrd, Show)]
+#[deriving(Eq, Ord, TotalEq, TotalOrd, Show)]
 #[must_use]
 pub enum Result<T, E> {
     /// Contains the success value
@@ -559,6 +559,32 @@ impl<T: Show, E> Result<T, E> {
     }
 }
 
+/////////////////////////////////////////////////////////////////////////////
+// Trait implementations
+/////////////////////////////////////////////////////////////////////////////
+
+impl<T: Clone, E: Clone> Clone for Result<T, E> {
+    #[inline]
+    fn clone(&self) -> Result<T, E> {
+        match *self {
+            Ok(ref t) => Ok(t.clone()),
+            Err(ref e) => Err(e.clone())
+        }
+    }
+
+    /// Clones `source` into `self`, reusing the resources of the contained
+    /// value when both hold the same variant.
+    #[inline]
+    fn clone_from(&mut self, source: &Result<T, E>) {
+        match (self.as_mut(), source) {
+            (Ok(to), &Ok(ref from)) => { to.clone_from(from); return }
+            (Err(to), &Err(ref from)) => { to.clone_from(from); return }
+            _ => {}
+        }
+        *self = source.clone();
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // Free functions
 /////////////////////////////////////////////////////////////////////////////
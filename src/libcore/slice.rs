@@ -23,6 +23,7 @@ use num::{CheckedAdd, Saturating, div_rem};
 use option::{None, Option, Some};
 use ptr;
 use ptr::RawPtr;
+use result::{Result, Ok, Err};
 use mem;
 use mem::size_of;
 use kinds::marker;
@@ -252,6 +253,7 @@ pub mod traits {
     use cmp::{Eq, Ord, TotalEq, TotalOrd, Ordering, Equiv};
     use iter::{order, Iterator};
     use container::Container;
+    use ops::{Slice, SliceMut};
 
     impl<'a,T:Eq> Eq for &'a [T] {
         fn eq(&self, other: & &'a [T]) -> bool {
@@ -324,6 +326,50 @@ pub mod traits {
         #[inline]
         fn gt(&self, other: &~[T]) -> bool { self.as_slice() > other.as_slice() }
     }
+
+    impl<'a, 'b, T> Slice<'a, uint, &'b [T]> for &'b [T] {
+        #[inline]
+        fn as_slice_(&'a self) -> &'b [T] {
+            *self
+        }
+
+        #[inline]
+        fn slice_from_or_fail(&'a self, from: &uint) -> &'b [T] {
+            self.slice_from(*from)
+        }
+
+        #[inline]
+        fn slice_to_or_fail(&'a self, to: &uint) -> &'b [T] {
+            self.slice_to(*to)
+        }
+
+        #[inline]
+        fn slice_or_fail(&'a self, from: &uint, to: &uint) -> &'b [T] {
+            self.slice(*from, *to)
+        }
+    }
+
+    impl<'a, 'b, T> SliceMut<'a, uint, &'a mut [T]> for &'b mut [T] {
+        #[inline]
+        fn as_mut_slice_(&'a mut self) -> &'a mut [T] {
+            self.as_mut_slice()
+        }
+
+        #[inline]
+        fn slice_from_or_fail_mut(&'a mut self, from: &uint) -> &'a mut [T] {
+            self.mut_slice_from(*from)
+        }
+
+        #[inline]
+        fn slice_to_or_fail_mut(&'a mut self, to: &uint) -> &'a mut [T] {
+            self.mut_slice_to(*to)
+        }
+
+        #[inline]
+        fn slice_or_fail_mut(&'a mut self, from: &uint, to: &uint) -> &'a mut [T] {
+            self.mut_slice(*from, *to)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -451,6 +497,15 @@ pub trait ImmutableVector<'a, T> {
      */
     fn chunks(self, size: uint) -> Chunks<'a, T>;
 
+    /// Divides one slice into two at an index.
+    ///
+    /// The first will contain all indices from `[0, mid)` (excluding
+    /// the index `mid` itself) and the second will contain all
+    /// indices from `[mid, len)` (excluding the index `len` itself).
+    ///
+    /// Fails if `mid > len`.
+    fn split_at(self, mid: uint) -> (&'a [T], &'a [T]);
+
     /// Returns the element of a vector at the given index, or `None` if the
     /// index is out of bounds
     fn get(&self, index: uint) -> Option<&'a T>;
@@ -495,6 +550,26 @@ pub trait ImmutableVector<'a, T> {
      */
     fn bsearch(&self, f: |&T| -> Ordering) -> Option<uint>;
 
+    /**
+     * Binary search a sorted vector with a comparator function, reporting
+     * where the target belongs if it is not found.
+     *
+     * The comparator function behaves as for `bsearch`.
+     *
+     * Returns `Ok` with the index where the comparator returned `Equal`, or
+     * `Err` with the index at which an element matching the target could be
+     * inserted while keeping the vector sorted.
+     *
+     * # Example
+     *
+     * ```rust
+     * let v = [1, 3, 5, 7];
+     * assert_eq!(v.binary_search_by(|x| x.cmp(&5)), Ok(2));
+     * assert_eq!(v.binary_search_by(|x| x.cmp(&4)), Err(2));
+     * ```
+     */
+    fn binary_search_by(&self, f: |&T| -> Ordering) -> Result<uint, uint>;
+
     /**
      * Returns an immutable reference to the first element in this slice
      * and adjusts the slice in place so that it no longer contains
@@ -610,6 +685,11 @@ impl<'a,T> ImmutableVector<'a, T> for &'a [T] {
         Chunks { v: self, size: size }
     }
 
+    #[inline]
+    fn split_at(self, mid: uint) -> (&'a [T], &'a [T]) {
+        (self.slice(0, mid), self.slice(mid, self.len()))
+    }
+
     #[inline]
     fn get(&self, index: uint) -> Option<&'a T> {
         if index < self.len() { Some(&self[index]) } else { None }
@@ -653,13 +733,17 @@ impl<'a,T> ImmutableVector<'a, T> for &'a [T] {
 
 
     fn bsearch(&self, f: |&T| -> Ordering) -> Option<uint> {
+        self.binary_search_by(f).ok()
+    }
+
+    fn binary_search_by(&self, f: |&T| -> Ordering) -> Result<uint, uint> {
         let mut base : uint = 0;
         let mut lim : uint = self.len();
 
         while lim != 0 {
             let ix = base + (lim >> 1);
             match f(&self[ix]) {
-                Equal => return Some(ix),
+                Equal => return Ok(ix),
                 Less => {
                     base = ix + 1;
                     lim -= 1;
@@ -668,7 +752,7 @@ impl<'a,T> ImmutableVector<'a, T> for &'a [T] {
             }
             lim >>= 1;
         }
-        return None;
+        return Err(base);
     }
 
     fn shift_ref(&mut self) -> Option<&'a T> {
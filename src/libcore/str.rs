@@ -25,6 +25,7 @@ use iter::range;
 use num::Saturating;
 use option::{None, Option, Some};
 use raw::Repr;
+use ptr::RawPtr;
 use slice::ImmutableVector;
 use slice;
 use uint;
@@ -588,84 +589,106 @@ pub fn eq_slice(a: &str, b: &str) -> bool {
 Section: Misc
 */
 
-/// Walk through `iter` checking that it's a valid UTF-8 sequence,
-/// returning `true` in that case, or, if it is invalid, `false` with
-/// `iter` reset such that it is pointing at the first byte in the
-/// invalid sequence.
-#[inline(always)]
-fn run_utf8_validation_iterator(iter: &mut slice::Items<u8>) -> bool {
-    loop {
-        // save the current thing we're pointing at.
-        let old = *iter;
+// The number of bytes in a `uint`, and a mask of the high bit of each of them.
+static WORD_BYTES: uint = uint::BYTES;
+static NONASCII_MASK: uint = 0x80808080_80808080u64 as uint;
 
-        // restore the iterator we had at the start of this codepoint.
-        macro_rules! err ( () => { {*iter = old; return false} });
+/// Checks that `v` is a valid UTF-8 sequence.
+///
+/// Runs of ASCII are by far the most common input, so when the current byte is
+/// ASCII and suitably aligned the bytes are checked two words at a time, only
+/// falling back to decoding byte by byte around non-ASCII data and in the
+/// unaligned head and the tail of the vector.
+#[inline(always)]
+fn run_utf8_validation(v: &[u8]) -> bool {
+    let len = v.len();
+    let ptr = v.as_ptr();
+    let mut i = 0u;
+    while i < len {
+        macro_rules! err ( () => { return false });
         macro_rules! next ( () => {
-                match iter.next() {
-                    Some(a) => *a,
+                {
+                    i += 1;
                     // we needed data, but there was none: error!
-                    None => err!()
+                    if i >= len { err!() }
+                    v[i]
                 }
             });
 
-        let first = match iter.next() {
-            Some(&b) => b,
-            // we're at the end of the iterator and a codepoint
-            // boundary at the same time, so this string is valid.
-            None => return true
-        };
+        let first = v[i];
+
+        if first < 128 {
+            // Word at a time is only done from an aligned position, so that
+            // the loads never straddle the end of the vector.
+            if (ptr as uint + i) & (WORD_BYTES - 1) == 0 {
+                while i + 2 * WORD_BYTES <= len {
+                    let (a, b) = unsafe {
+                        let p = ptr.offset(i as int) as *uint;
+                        (*p, *p.offset(1))
+                    };
+                    if (a | b) & NONASCII_MASK != 0 { break }
+                    i += 2 * WORD_BYTES;
+                }
+                // Skip the remaining ASCII bytes, including those in the
+                // words which stopped the loop above.
+                while i < len && v[i] < 128 {
+                    i += 1;
+                }
+            } else {
+                i += 1;
+            }
+            continue;
+        }
 
-        // ASCII characters are always valid, so only large
-        // bytes need more examination.
-        if first >= 128 {
-            let w = utf8_char_width(first);
-            let second = next!();
-            // 2-byte encoding is for codepoints  \u0080 to  \u07ff
-            //        first  C2 80        last DF BF
-            // 3-byte encoding is for codepoints  \u0800 to  \uffff
-            //        first  E0 A0 80     last EF BF BF
-            //   excluding surrogates codepoints  \ud800 to  \udfff
-            //               ED A0 80 to       ED BF BF
-            // 4-byte encoding is for codepoints \u10000 to \u10ffff
-            //        first  F0 90 80 80  last F4 8F BF BF
-            //
-            // Use the UTF-8 syntax from the RFC
-            //
-            // https://tools.ietf.org/html/rfc3629
-            // UTF8-1      = %x00-7F
-            // UTF8-2      = %xC2-DF UTF8-tail
-            // UTF8-3      = %xE0 %xA0-BF UTF8-tail / %xE1-EC 2( UTF8-tail ) /
-            //               %xED %x80-9F UTF8-tail / %xEE-EF 2( UTF8-tail )
-            // UTF8-4      = %xF0 %x90-BF 2( UTF8-tail ) / %xF1-F3 3( UTF8-tail ) /
-            //               %xF4 %x80-8F 2( UTF8-tail )
-            match w {
-                2 => if second & 192 != TAG_CONT_U8 {err!()},
-                3 => {
-                    match (first, second, next!() & 192) {
-                        (0xE0        , 0xA0 .. 0xBF, TAG_CONT_U8) |
-                        (0xE1 .. 0xEC, 0x80 .. 0xBF, TAG_CONT_U8) |
-                        (0xED        , 0x80 .. 0x9F, TAG_CONT_U8) |
-                        (0xEE .. 0xEF, 0x80 .. 0xBF, TAG_CONT_U8) => {}
-                        _ => err!()
-                    }
+        let w = utf8_char_width(first);
+        let second = next!();
+        // 2-byte encoding is for codepoints  \u0080 to  \u07ff
+        //        first  C2 80        last DF BF
+        // 3-byte encoding is for codepoints  \u0800 to  \uffff
+        //        first  E0 A0 80     last EF BF BF
+        //   excluding surrogates codepoints  \ud800 to  \udfff
+        //               ED A0 80 to       ED BF BF
+        // 4-byte encoding is for codepoints \u10000 to \u10ffff
+        //        first  F0 90 80 80  last F4 8F BF BF
+        //
+        // Use the UTF-8 syntax from the RFC
+        //
+        // https://tools.ietf.org/html/rfc3629
+        // UTF8-1      = %x00-7F
+        // UTF8-2      = %xC2-DF UTF8-tail
+        // UTF8-3      = %xE0 %xA0-BF UTF8-tail / %xE1-EC 2( UTF8-tail ) /
+        //               %xED %x80-9F UTF8-tail / %xEE-EF 2( UTF8-tail )
+        // UTF8-4      = %xF0 %x90-BF 2( UTF8-tail ) / %xF1-F3 3( UTF8-tail ) /
+        //               %xF4 %x80-8F 2( UTF8-tail )
+        match w {
+            2 => if second & 192 != TAG_CONT_U8 {err!()},
+            3 => {
+                match (first, second, next!() & 192) {
+                    (0xE0        , 0xA0 .. 0xBF, TAG_CONT_U8) |
+                    (0xE1 .. 0xEC, 0x80 .. 0xBF, TAG_CONT_U8) |
+                    (0xED        , 0x80 .. 0x9F, TAG_CONT_U8) |
+                    (0xEE .. 0xEF, 0x80 .. 0xBF, TAG_CONT_U8) => {}
+                    _ => err!()
                 }
-                4 => {
-                    match (first, second, next!() & 192, next!() & 192) {
-                        (0xF0        , 0x90 .. 0xBF, TAG_CONT_U8, TAG_CONT_U8) |
-                        (0xF1 .. 0xF3, 0x80 .. 0xBF, TAG_CONT_U8, TAG_CONT_U8) |
-                        (0xF4        , 0x80 .. 0x8F, TAG_CONT_U8, TAG_CONT_U8) => {}
-                        _ => err!()
-                    }
+            }
+            4 => {
+                match (first, second, next!() & 192, next!() & 192) {
+                    (0xF0        , 0x90 .. 0xBF, TAG_CONT_U8, TAG_CONT_U8) |
+                    (0xF1 .. 0xF3, 0x80 .. 0xBF, TAG_CONT_U8, TAG_CONT_U8) |
+                    (0xF4        , 0x80 .. 0x8F, TAG_CONT_U8, TAG_CONT_U8) => {}
+                    _ => err!()
                 }
-                _ => err!()
             }
+            _ => err!()
         }
+        i += 1;
     }
+    true
 }
 
 /// Determines if a vector of bytes contains valid UTF-8.
 pub fn is_utf8(v: &[u8]) -> bool {
-    run_utf8_validation_iterator(&mut v.iter())
+    run_utf8_validation(v)
 }
 
 /// Determines if a vector of `u16` contains valid UTF-16
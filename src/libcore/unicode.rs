@@ -4052,7 +4052,23 @@ pub mod conversions {
         }
     }
 
-    fn bsearch_case_table(c: char, table: &'static [(char, char)]) -> Option<uint> {
+    /// Returns the full lowercase mapping of `c`, padded with `'\0'`.
+    pub fn to_lower_full(c: char) -> [char, ..3] {
+        match bsearch_case_table(c, LuLl_special_table) {
+            None        => [to_lower(c), '\0', '\0'],
+            Some(index) => { let (_, chars) = LuLl_special_table[index]; chars }
+        }
+    }
+
+    /// Returns the full uppercase mapping of `c`, padded with `'\0'`.
+    pub fn to_upper_full(c: char) -> [char, ..3] {
+        match bsearch_case_table(c, LlLu_special_table) {
+            None        => [to_upper(c), '\0', '\0'],
+            Some(index) => { let (_, chars) = LlLu_special_table[index]; chars }
+        }
+    }
+
+    fn bsearch_case_table<T>(c: char, table: &'static [(char, T)]) -> Option<uint> {
         table.bsearch(|&(key, _)| {
             if c == key { Equal }
             else if key < c { Less }
@@ -5053,4 +5069,62 @@ pub mod conversions {
         ('\U0001044e', '\U00010426'), ('\U0001044f', '\U00010427')
     ];
 
+    static LuLl_special_table : &'static [(char, [char, ..3])] = &[
+        ('\u0130', ['\x69', '\u0307', '\x00'])
+    ];
+
+    static LlLu_special_table : &'static [(char, [char, ..3])] = &[
+        ('\xdf', ['\x53', '\x53', '\x00']), ('\u0149', ['\u02bc', '\x4e', '\x00']),
+        ('\u01f0', ['\x4a', '\u030c', '\x00']), ('\u0390', ['\u0399', '\u0308', '\u0301']),
+        ('\u03b0', ['\u03a5', '\u0308', '\u0301']), ('\u0587', ['\u0535', '\u0552', '\x00']),
+        ('\u1e96', ['\x48', '\u0331', '\x00']), ('\u1e97', ['\x54', '\u0308', '\x00']),
+        ('\u1e98', ['\x57', '\u030a', '\x00']), ('\u1e99', ['\x59', '\u030a', '\x00']),
+        ('\u1e9a', ['\x41', '\u02be', '\x00']), ('\u1f50', ['\u03a5', '\u0313', '\x00']),
+        ('\u1f52', ['\u03a5', '\u0313', '\u0300']), ('\u1f54', ['\u03a5', '\u0313', '\u0301']),
+        ('\u1f56', ['\u03a5', '\u0313', '\u0342']), ('\u1f80', ['\u1f08', '\u0399', '\x00']),
+        ('\u1f81', ['\u1f09', '\u0399', '\x00']), ('\u1f82', ['\u1f0a', '\u0399', '\x00']),
+        ('\u1f83', ['\u1f0b', '\u0399', '\x00']), ('\u1f84', ['\u1f0c', '\u0399', '\x00']),
+        ('\u1f85', ['\u1f0d', '\u0399', '\x00']), ('\u1f86', ['\u1f0e', '\u0399', '\x00']),
+        ('\u1f87', ['\u1f0f', '\u0399', '\x00']), ('\u1f88', ['\u1f08', '\u0399', '\x00']),
+        ('\u1f89', ['\u1f09', '\u0399', '\x00']), ('\u1f8a', ['\u1f0a', '\u0399', '\x00']),
+        ('\u1f8b', ['\u1f0b', '\u0399', '\x00']), ('\u1f8c', ['\u1f0c', '\u0399', '\x00']),
+        ('\u1f8d', ['\u1f0d', '\u0399', '\x00']), ('\u1f8e', ['\u1f0e', '\u0399', '\x00']),
+        ('\u1f8f', ['\u1f0f', '\u0399', '\x00']), ('\u1f90', ['\u1f28', '\u0399', '\x00']),
+        ('\u1f91', ['\u1f29', '\u0399', '\x00']), ('\u1f92', ['\u1f2a', '\u0399', '\x00']),
+        ('\u1f93', ['\u1f2b', '\u0399', '\x00']), ('\u1f94', ['\u1f2c', '\u0399', '\x00']),
+        ('\u1f95', ['\u1f2d', '\u0399', '\x00']), ('\u1f96', ['\u1f2e', '\u0399', '\x00']),
+        ('\u1f97', ['\u1f2f', '\u0399', '\x00']), ('\u1f98', ['\u1f28', '\u0399', '\x00']),
+        ('\u1f99', ['\u1f29', '\u0399', '\x00']), ('\u1f9a', ['\u1f2a', '\u0399', '\x00']),
+        ('\u1f9b', ['\u1f2b', '\u0399', '\x00']), ('\u1f9c', ['\u1f2c', '\u0399', '\x00']),
+        ('\u1f9d', ['\u1f2d', '\u0399', '\x00']), ('\u1f9e', ['\u1f2e', '\u0399', '\x00']),
+        ('\u1f9f', ['\u1f2f', '\u0399', '\x00']), ('\u1fa0', ['\u1f68', '\u0399', '\x00']),
+        ('\u1fa1', ['\u1f69', '\u0399', '\x00']), ('\u1fa2', ['\u1f6a', '\u0399', '\x00']),
+        ('\u1fa3', ['\u1f6b', '\u0399', '\x00']), ('\u1fa4', ['\u1f6c', '\u0399', '\x00']),
+        ('\u1fa5', ['\u1f6d', '\u0399', '\x00']), ('\u1fa6', ['\u1f6e', '\u0399', '\x00']),
+        ('\u1fa7', ['\u1f6f', '\u0399', '\x00']), ('\u1fa8', ['\u1f68', '\u0399', '\x00']),
+        ('\u1fa9', ['\u1f69', '\u0399', '\x00']), ('\u1faa', ['\u1f6a', '\u0399', '\x00']),
+        ('\u1fab', ['\u1f6b', '\u0399', '\x00']), ('\u1fac', ['\u1f6c', '\u0399', '\x00']),
+        ('\u1fad', ['\u1f6d', '\u0399', '\x00']), ('\u1fae', ['\u1f6e', '\u0399', '\x00']),
+        ('\u1faf', ['\u1f6f', '\u0399', '\x00']), ('\u1fb2', ['\u1fba', '\u0399', '\x00']),
+        ('\u1fb3', ['\u0391', '\u0399', '\x00']), ('\u1fb4', ['\u0386', '\u0399', '\x00']),
+        ('\u1fb6', ['\u0391', '\u0342', '\x00']), ('\u1fb7', ['\u0391', '\u0342', '\u0399']),
+        ('\u1fbc', ['\u0391', '\u0399', '\x00']), ('\u1fc2', ['\u1fca', '\u0399', '\x00']),
+        ('\u1fc3', ['\u0397', '\u0399', '\x00']), ('\u1fc4', ['\u0389', '\u0399', '\x00']),
+        ('\u1fc6', ['\u0397', '\u0342', '\x00']), ('\u1fc7', ['\u0397', '\u0342', '\u0399']),
+        ('\u1fcc', ['\u0397', '\u0399', '\x00']), ('\u1fd2', ['\u0399', '\u0308', '\u0300']),
+        ('\u1fd3', ['\u0399', '\u0308', '\u0301']), ('\u1fd6', ['\u0399', '\u0342', '\x00']),
+        ('\u1fd7', ['\u0399', '\u0308', '\u0342']), ('\u1fe2', ['\u03a5', '\u0308', '\u0300']),
+        ('\u1fe3', ['\u03a5', '\u0308', '\u0301']), ('\u1fe4', ['\u03a1', '\u0313', '\x00']),
+        ('\u1fe6', ['\u03a5', '\u0342', '\x00']), ('\u1fe7', ['\u03a5', '\u0308', '\u0342']),
+        ('\u1ff2', ['\u1ffa', '\u0399', '\x00']), ('\u1ff3', ['\u03a9', '\u0399', '\x00']),
+        ('\u1ff4', ['\u038f', '\u0399', '\x00']), ('\u1ff6', ['\u03a9', '\u0342', '\x00']),
+        ('\u1ff7', ['\u03a9', '\u0342', '\u0399']), ('\u1ffc', ['\u03a9', '\u0399', '\x00']),
+        ('\ufb00', ['\x46', '\x46', '\x00']), ('\ufb01', ['\x46', '\x49', '\x00']),
+        ('\ufb02', ['\x46', '\x4c', '\x00']), ('\ufb03', ['\x46', '\x46', '\x49']),
+        ('\ufb04', ['\x46', '\x46', '\x4c']), ('\ufb05', ['\x53', '\x54', '\x00']),
+        ('\ufb06', ['\x53', '\x54', '\x00']), ('\ufb13', ['\u0544', '\u0546', '\x00']),
+        ('\ufb14', ['\u0544', '\u0535', '\x00']), ('\ufb15', ['\u0544', '\u053b', '\x00']),
+        ('\ufb16', ['\u054e', '\u0546', '\x00']), ('\ufb17', ['\u0544', '\u053d', '\x00'])
+    ];
+
 }
@@ -53,7 +53,7 @@ impl BasicLoop {
     /// Process everything in the work queue (continually)
     fn work(&mut self) {
         while self.work.len() > 0 {
-            for work in mem::replace(&mut self.work, vec![]).move_iter() {
+            for work in mem::take(&mut self.work).move_iter() {
                 work();
             }
         }
@@ -63,7 +63,7 @@ impl BasicLoop {
         let messages = unsafe {
             self.messages.with(|messages| {
                 if messages.len() > 0 {
-                    Some(mem::replace(messages, vec![]))
+                    Some(mem::take(messages))
                 } else {
                     None
                 }
@@ -162,6 +162,8 @@ impl EventLoop for BasicLoop {
     fn io<'a>(&'a mut self) -> Option<&'a mut IoFactory> { None }
 
     fn has_active_io(&self) -> bool { false }
+
+    fn has_pending_events(&self) -> bool { self.work.len() > 0 }
 }
 
 struct BasicRemote {
@@ -226,6 +228,7 @@ mod test {
         SchedPool::new(PoolConfig {
             threads: 1,
             event_loop_factory: basic::event_loop,
+            detect_deadlocks: false,
         })
     }
 
@@ -247,7 +250,7 @@ mod test {
             spawn(proc() {
                 tx.send(());
             });
-            rx.recv();
+            rx.recv_unwrap();
         });
     }
 
@@ -256,6 +259,7 @@ mod test {
         let mut pool = SchedPool::new(PoolConfig {
             threads: 2,
             event_loop_factory: basic::event_loop,
+            detect_deadlocks: false,
         });
 
         for _ in range(0, 20) {
@@ -264,7 +268,7 @@ mod test {
                 spawn(proc() {
                     tx.send(());
                 });
-                rx.recv();
+                rx.recv_unwrap();
             });
         }
 
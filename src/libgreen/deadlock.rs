@@ -0,0 +1,164 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deadlock detection for a pool of schedulers
+//!
+//! When a pool is configured to detect deadlocks, it keeps track of three
+//! things: which tasks are blocked on another task (on a channel or a mutex),
+//! how many schedulers are asleep with nothing pending on their event loop,
+//! and how many tasks have been sent to a scheduler which hasn't picked them up
+//! yet. Once every scheduler is asleep, nothing is in flight and every task in
+//! the pool is blocked, no task in the pool can ever make progress again.
+//!
+//! Each blocked task hands the monitor one of the handles it blocks with, so
+//! once a deadlock is detected the monitor claims every blocked task for
+//! itself and fails it with a message saying what it was deadlocked on. The
+//! primitive the task was blocked on is none the wiser: waking up its own
+//! handle later on just finds the task gone.
+//!
+//! A deadlocked task can't unwind, though, as the primitive may still point
+//! into its stack and the destructors on its stack would wait on the very
+//! primitives which are deadlocked. Its failure is reported to whoever is
+//! watching it (`try`, `future_result`, ...) right away, and then the task is
+//! leaked along with its stack and its task-local data. Anything the
+//! deadlocked tasks held on to, such as a locked mutex, stays that way.
+//!
+//! Note that a task which is waiting on a native thread or on a task in another
+//! pool looks exactly like a deadlocked task, which is why detection has to be
+//! asked for.
+
+use std::mem;
+use std::rt::task::{Task, BlockedTask};
+use std::unstable::sync::Exclusive;
+
+/// Bookkeeping shared among all the schedulers and tasks of a pool.
+#[deriving(Clone)]
+pub struct Monitor {
+    state: Exclusive<State>,
+}
+
+struct State {
+    // schedulers in the pool, and how many of them are asleep
+    scheds: uint,
+    asleep: uint,
+    // tasks sent to a scheduler in a message which hasn't been received yet
+    in_flight: uint,
+    // the blocked tasks
+    blocked: Vec<Blocked>,
+}
+
+struct Blocked {
+    id: uint,
+    // what the task is blocked on, and the whole line printed about it
+    what: &'static str,
+    desc: String,
+    // the handle through which a deadlocked task is claimed
+    handle: BlockedTask,
+}
+
+/// A task which is about to block, and is registered with the monitor once it
+/// has been descheduled.
+pub struct Waiter {
+    pub monitor: Monitor,
+    pub id: uint,
+    pub what: &'static str,
+    pub desc: String,
+}
+
+impl Waiter {
+    /// Registers the task as blocked, keeping one handle to it and returning
+    /// another one for the primitive to block with.
+    pub fn register(self, task: BlockedTask) -> BlockedTask {
+        let Waiter { monitor, id, what, desc } = self;
+        let mut handles = task.make_selectable(2);
+        let ours = handles.next().unwrap();
+        let theirs = handles.next().unwrap();
+        unsafe {
+            monitor.state.with(|s| {
+                s.blocked.push(Blocked {
+                    id: id, what: what, desc: desc, handle: ours,
+                })
+            })
+        }
+        theirs
+    }
+}
+
+impl Monitor {
+    pub fn new() -> Monitor {
+        Monitor {
+            state: Exclusive::new(State {
+                scheds: 0,
+                asleep: 0,
+                in_flight: 0,
+                blocked: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn add_sched(&self) {
+        unsafe { self.state.with(|s| s.scheds += 1) }
+    }
+
+    pub fn remove_sched(&self) {
+        unsafe { self.state.with(|s| s.scheds -= 1) }
+    }
+
+    /// Records that a scheduler has gone to sleep with nothing pending. If this
+    /// leaves the `tasks` tasks of the pool deadlocked, they're all claimed
+    /// and returned along with what they're blocked on, for the caller to
+    /// fail them.
+    pub fn sleep(&self, tasks: uint) -> Vec<(Box<Task>, &'static str)> {
+        unsafe {
+            self.state.with(|s| {
+                s.asleep += 1;
+                if s.asleep != s.scheds || s.in_flight != 0 || tasks == 0 ||
+                   s.blocked.len() != tasks {
+                    return Vec::new()
+                }
+                rterrln!("deadlock detected, all {} tasks in the pool \
+                          are blocked:", tasks);
+                for b in s.blocked.iter() {
+                    rterrln!("    {}", b.desc);
+                }
+                // A task which somebody else manages to wake up first isn't
+                // ours to fail.
+                let blocked = mem::take(&mut s.blocked);
+                blocked.move_iter().filter_map(|b| {
+                    let what = b.what;
+                    b.handle.wake().map(|t| (t, what))
+                }).collect()
+            })
+        }
+    }
+
+    pub fn wake(&self) {
+        unsafe { self.state.with(|s| s.asleep -= 1) }
+    }
+
+    pub fn task_sent(&self) {
+        unsafe { self.state.with(|s| s.in_flight += 1) }
+    }
+
+    pub fn task_received(&self) {
+        unsafe { self.state.with(|s| s.in_flight -= 1) }
+    }
+
+    pub fn unblock(&self, id: uint) {
+        unsafe {
+            self.state.with(|s| {
+                match s.blocked.iter().position(|b| b.id == id) {
+                    Some(i) => { s.blocked.swap_remove(i); }
+                    None => {}
+                }
+            })
+        }
+    }
+}
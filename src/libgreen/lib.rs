@@ -217,23 +217,31 @@ extern crate libc;
 extern crate alloc;
 
 use alloc::arc::Arc;
-use std::mem::replace;
+use std::mem::take;
 use std::os;
+use std::rt::local::Local;
 use std::rt::rtio;
+use std::rt::task::Task;
 use std::rt::thread::Thread;
 use std::rt;
 use std::sync::atomics::{SeqCst, AtomicUint, INIT_ATOMIC_UINT};
 use std::sync::deque;
-use std::task::TaskOpts;
+use std::task::{TaskOpts, failure_exit_status};
+use std::unstable::sync::Exclusive;
 
+use deadlock::Monitor;
 use sched::{Shutdown, Scheduler, SchedHandle, TaskFromFriend, NewNeighbor};
+use sched::SchedCounters;
 use sleeper_list::SleeperList;
 use stack::StackPool;
 use task::GreenTask;
 
+pub use sched::SchedulerStats;
+
 mod macros;
 mod simple;
 mod message_queue;
+mod deadlock;
 
 pub mod basic;
 pub mod context;
@@ -291,11 +299,39 @@ macro_rules! green_start( ($f:ident) => (
 pub fn start(argc: int, argv: **u8,
              event_loop_factory: fn() -> Box<rtio::EventLoop:Send>,
              main: proc():Send) -> int {
+    let config = PoolConfig::new().event_loop_factory(event_loop_factory);
+    start_with_config(argc, argv, config, main)
+}
+
+/// Like `start`, but configures the pool of M:N schedulers with `config`
+/// rather than according to the environment.
+///
+/// # Example
+///
+/// ```no_run
+/// extern crate green;
+/// extern crate rustuv;
+///
+/// #[start]
+/// fn start(argc: int, argv: **u8) -> int {
+///     let config = green::PoolConfig::new()
+///                      .threads(4)
+///                      .event_loop_factory(rustuv::event_loop);
+///     green::start_with_config(argc, argv, config, main)
+/// }
+///
+/// fn main() {
+///     // running in a pool of 4 schedulers
+/// }
+/// ```
+pub fn start_with_config(argc: int, argv: **u8, config: PoolConfig,
+                         main: proc():Send) -> int {
     rt::init(argc, argv);
     let mut main = Some(main);
+    let mut config = Some(config);
     let mut ret = None;
     simple::task().run(|| {
-        ret = Some(run(event_loop_factory, main.take_unwrap()));
+        ret = Some(run_with_config(config.take_unwrap(), main.take_unwrap()));
     });
     // unsafe is ok b/c we're sure that the runtime is gone
     unsafe { rt::cleanup() }
@@ -312,11 +348,16 @@ pub fn start(argc: int, argv: **u8,
 /// have returned.
 pub fn run(event_loop_factory: fn() -> Box<rtio::EventLoop:Send>,
            main: proc():Send) -> int {
+    run_with_config(PoolConfig::new().event_loop_factory(event_loop_factory),
+                    main)
+}
+
+/// Like `run`, but configures the pool of M:N schedulers with `config` rather
+/// than according to the environment.
+pub fn run_with_config(config: PoolConfig, main: proc():Send) -> int {
     // Create a scheduler pool and spawn the main task into this pool. We will
     // get notified over a channel when the main task exits.
-    let mut cfg = PoolConfig::new();
-    cfg.event_loop_factory = event_loop_factory;
-    let mut pool = SchedPool::new(cfg);
+    let mut pool = SchedPool::new(config);
     let (tx, rx) = channel();
     let mut opts = TaskOpts::new();
     opts.notify_chan = Some(tx);
@@ -325,8 +366,9 @@ pub fn run(event_loop_factory: fn() -> Box<rtio::EventLoop:Send>,
 
     // Wait for the main task to return, and set the process error code
     // appropriately.
-    if rx.recv().is_err() {
-        os::set_exit_status(rt::DEFAULT_ERROR_CODE);
+    match rx.recv_unwrap() {
+        Ok(()) => {}
+        Err(cause) => os::set_exit_status(failure_exit_status(&*cause)),
     }
 
     // Now that we're sure all tasks are dead, shut down the pool of schedulers,
@@ -335,6 +377,28 @@ pub fn run(event_loop_factory: fn() -> Box<rtio::EventLoop:Send>,
     os::get_exit_status()
 }
 
+/// Returns a snapshot of the statistics of each scheduler in the pool which the
+/// current task is running in, see `SchedPool::stats`.
+///
+/// Returns `None` if the current task isn't a green task.
+pub fn stats() -> Option<Vec<SchedulerStats>> {
+    let task: Option<Box<Task>> = Local::try_take();
+    let mut task = match task {
+        Some(task) => task,
+        None => return None,
+    };
+    let stats = match task.maybe_take_runtime::<GreenTask>() {
+        Some(green) => {
+            let stats = green.sched.get_ref().task_state.stats();
+            task.put_runtime(green);
+            Some(stats)
+        }
+        None => None,
+    };
+    Local::put(task);
+    stats
+}
+
 /// Configuration of how an M:N pool of schedulers is spawned.
 pub struct PoolConfig {
     /// The number of schedulers (OS threads) to spawn into this M:N pool.
@@ -342,6 +406,15 @@ pub struct PoolConfig {
     /// A factory function used to create new event loops. If this is not
     /// specified then the default event loop factory is used.
     pub event_loop_factory: fn() -> Box<rtio::EventLoop:Send>,
+    /// Whether to fail all of the tasks in the pool, listing what each task is
+    /// waiting on, once they're all blocked on one another. This
+    /// defaults to whether the `RUST_DETECT_DEADLOCKS` environment variable is
+    /// set.
+    ///
+    /// A task waiting on a native thread or on a task in another pool can't be
+    /// told apart from a deadlocked task, so this should only be turned on for
+    /// programs which stay within one pool.
+    pub detect_deadlocks: bool,
 }
 
 impl PoolConfig {
@@ -351,8 +424,31 @@ impl PoolConfig {
         PoolConfig {
             threads: rt::default_sched_threads(),
             event_loop_factory: basic::event_loop,
+            detect_deadlocks: os::getenv("RUST_DETECT_DEADLOCKS").is_some(),
         }
     }
+
+    /// Sets the number of schedulers to spawn into the pool.
+    pub fn threads(mut self, threads: uint) -> PoolConfig {
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the factory function used to create the event loop of each
+    /// scheduler.
+    pub fn event_loop_factory(mut self,
+                              factory: fn() -> Box<rtio::EventLoop:Send>)
+                              -> PoolConfig {
+        self.event_loop_factory = factory;
+        self
+    }
+
+    /// Sets whether the pool detects deadlocks, see the `detect_deadlocks`
+    /// field.
+    pub fn detect_deadlocks(mut self, detect: bool) -> PoolConfig {
+        self.detect_deadlocks = detect;
+        self
+    }
 }
 
 /// A structure representing a handle to a pool of schedulers. This handle is
@@ -373,11 +469,15 @@ pub struct SchedPool {
 
 /// This is an internal state shared among a pool of schedulers. This is used to
 /// keep track of how many tasks are currently running in the pool and then
-/// sending on a channel once the entire pool has been drained of all tasks.
+/// sending on a channel once the entire pool has been drained of all tasks. If
+/// the pool detects deadlocks, this also holds the deadlock monitor.
 #[deriving(Clone)]
 struct TaskState {
     cnt: Arc<AtomicUint>,
     done: Sender<()>,
+    monitor: Option<Monitor>,
+    scheds: Exclusive<Vec<(uint, Arc<SchedCounters>,
+                           deque::Stealer<Box<GreenTask>>)>>,
 }
 
 impl SchedPool {
@@ -390,12 +490,13 @@ impl SchedPool {
 
         let PoolConfig {
             threads: nscheds,
-            event_loop_factory: factory
+            event_loop_factory: factory,
+            detect_deadlocks: detect_deadlocks
         } = config;
         assert!(nscheds > 0);
 
         // The pool of schedulers that will be returned from this function
-        let (p, state) = TaskState::new();
+        let (p, state) = TaskState::new(detect_deadlocks);
         let mut pool = SchedPool {
             threads: vec![],
             handles: vec![],
@@ -425,7 +526,8 @@ impl SchedPool {
         // Now that we've got all our work queues, create one scheduler per
         // queue, spawn the scheduler into a thread, and be sure to keep a
         // handle to the scheduler and the thread to keep them alive.
-        for worker in workers.move_iter() {
+        for (worker, stealer) in workers.move_iter()
+                                         .zip(pool.stealers.clone().move_iter()) {
             rtdebug!("inserting a regular scheduler");
 
             let mut sched = box Scheduler::new(pool.id,
@@ -434,6 +536,7 @@ impl SchedPool {
                                             pool.stealers.clone(),
                                             pool.sleepers.clone(),
                                             pool.task_state.clone());
+            pool.task_state.register(&*sched, stealer);
             pool.handles.push(sched.make_handle());
             pool.threads.push(Thread::start(proc() { sched.bootstrap(); }));
         }
@@ -494,6 +597,7 @@ impl SchedPool {
                                         self.stealers.clone(),
                                         self.sleepers.clone(),
                                         self.task_state.clone());
+        self.task_state.register(&*sched, stealer);
         let ret = sched.make_handle();
         self.handles.push(sched.make_handle());
         self.threads.push(Thread::start(proc() { sched.bootstrap() }));
@@ -501,6 +605,15 @@ impl SchedPool {
         return ret;
     }
 
+    /// Returns a snapshot of the statistics of each scheduler in this pool,
+    /// including those spawned with `spawn_sched`.
+    ///
+    /// The statistics of all schedulers are not taken at the same instant, so
+    /// they may not be consistent with one another while the pool is busy.
+    pub fn stats(&self) -> Vec<SchedulerStats> {
+        self.task_state.stats()
+    }
+
     /// Consumes the pool of schedulers, waiting for all tasks to exit and all
     /// schedulers to shut down.
     ///
@@ -519,28 +632,50 @@ impl SchedPool {
         // message the current task count will be 0, so we just receive in a
         // loop until everything is totally dead.
         while self.task_state.active() {
-            self.tasks_done.recv();
+            self.tasks_done.recv_unwrap();
         }
 
         // Now that everyone's gone, tell everything to shut down.
-        for mut handle in replace(&mut self.handles, vec![]).move_iter() {
+        for mut handle in take(&mut self.handles).move_iter() {
             handle.send(Shutdown);
         }
-        for thread in replace(&mut self.threads, vec![]).move_iter() {
+        for thread in take(&mut self.threads).move_iter() {
             thread.join();
         }
     }
 }
 
 impl TaskState {
-    fn new() -> (Receiver<()>, TaskState) {
+    fn new(detect_deadlocks: bool) -> (Receiver<()>, TaskState) {
         let (tx, rx) = channel();
         (rx, TaskState {
             cnt: Arc::new(AtomicUint::new(0)),
             done: tx,
+            monitor: if detect_deadlocks { Some(Monitor::new()) } else { None },
+            scheds: Exclusive::new(Vec::new()),
         })
     }
 
+    fn register(&self, sched: &Scheduler,
+                queue: deque::Stealer<Box<GreenTask>>) {
+        let entry = (sched.sched_id(), sched.counters.clone(), queue);
+        unsafe { self.scheds.with(|scheds| scheds.push(entry)) }
+    }
+
+    fn stats(&self) -> Vec<SchedulerStats> {
+        unsafe {
+            self.scheds.with_imm(|scheds| {
+                scheds.iter().map(|&(id, ref counters, ref queue)| {
+                    counters.stats(id, queue)
+                }).collect()
+            })
+        }
+    }
+
+    fn count(&self) -> uint {
+        self.cnt.load(SeqCst)
+    }
+
     fn increment(&mut self) {
         self.cnt.fetch_add(1, SeqCst);
     }
@@ -8,11 +8,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use alloc::arc::Arc;
 use std::mem;
 use std::rt::local::Local;
 use std::rt::rtio::{RemoteCallback, PausableIdleCallback, Callback, EventLoop};
 use std::rt::task::BlockedTask;
 use std::rt::task::Task;
+use std::sync::atomics::{AtomicUint, SeqCst};
 use std::sync::deque;
 use std::unstable::mutex::NativeMutex;
 use std::raw;
@@ -22,6 +24,7 @@ use rand::{XorShiftRng, Rng, Rand};
 use TaskState;
 use context::Context;
 use coroutine::Coroutine;
+use deadlock::Monitor;
 use sleeper_list::SleeperList;
 use stack::StackPool;
 use task::{TypeSched, GreenTask, HomeSched, AnySched};
@@ -45,6 +48,8 @@ pub struct Scheduler {
     /// Bookkeeping for the number of tasks which are currently running around
     /// inside this pool of schedulers
     pub task_state: TaskState,
+    /// Statistics about this scheduler, shared with the pool
+    pub counters: Arc<SchedCounters>,
     /// There are N work queues, one per scheduler.
     work_queue: deque::Worker<Box<GreenTask>>,
     /// Work queues for the other schedulers. These are created by
@@ -69,6 +74,9 @@ pub struct Scheduler {
     /// A flag to indicate we've received the shutdown message and should
     /// no longer try to go to sleep, but exit instead.
     no_sleep: bool,
+    /// Indicates that the deadlock monitor of the pool (if any) counts this
+    /// scheduler as asleep with nothing pending on its event loop.
+    asleep: bool,
     /// The scheduler runs on a special task. When it is not running
     /// it is stored here instead of the work queue.
     sched_task: Option<Box<GreenTask>>,
@@ -153,6 +161,7 @@ impl Scheduler {
             message_producer: producer,
             sleepy: false,
             no_sleep: false,
+            asleep: false,
             event_loop: event_loop,
             work_queue: work_queue,
             work_queues: work_queues,
@@ -166,9 +175,14 @@ impl Scheduler {
             yield_check_count: 0,
             steal_for_yield: false,
             task_state: state,
+            counters: Arc::new(SchedCounters::new()),
         };
 
         sched.yield_check_count = reset_yield_check(&mut sched.rng);
+        match sched.task_state.monitor {
+            Some(ref monitor) => monitor.add_sched(),
+            None => {}
+        }
 
         return sched;
     }
@@ -219,6 +233,11 @@ impl Scheduler {
         let message = stask.sched.get_mut_ref().message_queue.pop();
         rtassert!(match message { msgq::Empty => true, _ => false });
 
+        match stask.sched.get_ref().task_state.monitor {
+            Some(ref monitor) => monitor.remove_sched(),
+            None => {}
+        }
+
         stask.task.get_mut_ref().destroyed = true;
     }
 
@@ -279,6 +298,7 @@ impl Scheduler {
         // Assume that we need to continue idling unless we reach the
         // end of this function without performing an action.
         self.idle_callback.get_mut_ref().resume();
+        self.wake_monitor();
 
         // First we check for scheduler messages, these are higher
         // priority than regular tasks.
@@ -340,6 +360,12 @@ impl Scheduler {
             sched.idle_callback.get_mut_ref().pause();
         }
 
+        // With nothing left to do, let the deadlock monitor know that we're
+        // asleep.
+        if !sched.no_sleep {
+            sched.sleep_monitor();
+        }
+
         // Finished a cycle without using the Scheduler. Place it back
         // in TLS.
         stask.put_with_sched(sched);
@@ -378,6 +404,17 @@ impl Scheduler {
             }
         };
 
+        match msg {
+            Some(PinnedTask(..)) | Some(TaskFromFriend(..)) |
+            Some(RunOnce(..)) => {
+                match self.task_state.monitor {
+                    Some(ref monitor) => monitor.task_received(),
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+
         match msg {
             Some(PinnedTask(task)) => {
                 let mut task = task;
@@ -469,7 +506,11 @@ impl Scheduler {
                 }
                 None => {
                     rtdebug!("scheduler trying to steal");
-                    return self.try_steals();
+                    let task = self.try_steals();
+                    if task.is_some() {
+                        self.counters.steals.fetch_add(1, SeqCst);
+                    }
+                    return task;
                 }
             }
         } else {
@@ -617,6 +658,27 @@ impl Scheduler {
                                -> Box<GreenTask> {
         let f_opaque = ClosureConverter::from_fn(f);
 
+        // Check that pinned tasks really do stay put
+        if cfg!(not(ndebug)) {
+            match next_task.pinned_to {
+                Some(id) if id != self.sched_id() => {
+                    rtabort!("pinned task migrated to another scheduler")
+                }
+                _ => {}
+            }
+        }
+
+        // If we were counted as asleep, then a task was woken up by our event
+        // loop. Once we're done running tasks, the idle callback needs to come
+        // around again to figure out whether we can go back to sleep.
+        if self.asleep {
+            self.wake_monitor();
+            match self.idle_callback {
+                Some(ref mut idle) => idle.resume(),
+                None => {}
+            }
+        }
+
         let current_task_dupe = &*current_task as *GreenTask;
 
         // The current task is placed inside an enum with the cleanup
@@ -807,6 +869,19 @@ impl Scheduler {
         fail!("should never return!");
     }
 
+    /// Called by a task which has been failed by the deadlock monitor to end
+    /// execution. Unlike `terminate_current_task`, the task and its stack are
+    /// leaked rather than recycled, as the primitive which the task was blocked
+    /// on may still point into its stack.
+    pub fn abandon_current_task(mut ~self, cur: Box<GreenTask>) -> ! {
+        let stask = self.sched_task.take_unwrap();
+        let _cur = self.change_task_context(cur, stask, |sched, dead_task| {
+            mem::forget(dead_task);
+            sched.task_state.decrement();
+        });
+        fail!("should never return!");
+    }
+
     pub fn run_task(~self, cur: Box<GreenTask>, next: Box<GreenTask>) {
         let (sched, task) =
             self.process_task(cur, next, Scheduler::switch_task);
@@ -831,6 +906,7 @@ impl Scheduler {
             assert!(self.sched_task.is_none());
             self.run_sched_once(cur);
         } else {
+            self.counters.yields.fetch_add(1, SeqCst);
             self.yield_check_count = reset_yield_check(&mut self.rng);
             // Tell the scheduler to start stealing on the next iteration
             self.steal_for_yield = true;
@@ -884,13 +960,79 @@ impl Scheduler {
         return SchedHandle {
             remote: remote,
             queue: self.message_producer.clone(),
+            monitor: self.task_state.monitor.clone(),
             sched_id: self.sched_id()
         }
     }
+
+    // * Deadlock detection
+
+    // Something pending on the event loop could still wake up a task, so we
+    // only count as asleep if there's nothing.
+    fn sleep_monitor(&mut self) {
+        if self.asleep { return }
+        match self.task_state.monitor {
+            Some(..) if self.event_loop.has_pending_events() => {}
+            Some(ref monitor) => {
+                self.asleep = true;
+                let deadlocked = monitor.sleep(self.task_state.count());
+                for (task, what) in deadlocked.move_iter() {
+                    GreenTask::convert(task).fail_deadlocked(what);
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn wake_monitor(&mut self) {
+        if !self.asleep { return }
+        self.asleep = false;
+        self.task_state.monitor.get_ref().wake();
+    }
 }
 
 // Supporting types
 
+/// A snapshot of the statistics of one scheduler in a pool, as returned by
+/// `SchedPool::stats` and `green::stats`.
+#[deriving(Clone, Eq, Show)]
+pub struct SchedulerStats {
+    /// The id of the scheduler, the same as the `sched_id` of its handles
+    pub sched_id: uint,
+    /// The number of tasks this scheduler has stolen from other schedulers
+    /// after finding its own work queue empty
+    pub steals: uint,
+    /// The number of times a task on this scheduler has yielded
+    pub yields: uint,
+    /// The number of tasks waiting in this scheduler's work queue
+    pub queue_depth: uint,
+}
+
+/// The counters behind `SchedulerStats`, which are only ever updated by the
+/// scheduler they belong to but may be read from anywhere.
+pub struct SchedCounters {
+    steals: AtomicUint,
+    yields: AtomicUint,
+}
+
+impl SchedCounters {
+    fn new() -> SchedCounters {
+        SchedCounters { steals: AtomicUint::new(0), yields: AtomicUint::new(0) }
+    }
+
+    /// Takes a snapshot of these counters, along with the current depth of the
+    /// scheduler's work queue.
+    pub fn stats(&self, sched_id: uint,
+                 queue: &deque::Stealer<Box<GreenTask>>) -> SchedulerStats {
+        SchedulerStats {
+            sched_id: sched_id,
+            steals: self.steals.load(SeqCst),
+            yields: self.yields.load(SeqCst),
+            queue_depth: queue.len(),
+        }
+    }
+}
+
 type SchedulingFn = fn(Box<Scheduler>, Box<GreenTask>, Box<GreenTask>)
                        -> (Box<Scheduler>, Box<GreenTask>);
 
@@ -906,11 +1048,18 @@ pub enum SchedMessage {
 pub struct SchedHandle {
     remote: Box<RemoteCallback:Send>,
     queue: msgq::Producer<SchedMessage>,
+    monitor: Option<Monitor>,
     pub sched_id: uint
 }
 
 impl SchedHandle {
     pub fn send(&mut self, msg: SchedMessage) {
+        match (&msg, &self.monitor) {
+            (&PinnedTask(..), &Some(ref monitor)) |
+            (&TaskFromFriend(..), &Some(ref monitor)) |
+            (&RunOnce(..), &Some(ref monitor)) => monitor.task_sent(),
+            _ => {}
+        }
         self.queue.push(msg);
         self.remote.fire();
     }
@@ -1034,6 +1183,7 @@ mod test {
         SchedPool::new(PoolConfig {
             threads: 1,
             event_loop_factory: basic::event_loop,
+            detect_deadlocks: false,
         })
     }
 
@@ -1116,7 +1266,7 @@ mod test {
             handle1.send(TaskFromFriend(pool.task(TaskOpts::new(), proc() {
                 tx.send(sched_id());
             })));
-            let sched1_id = rx.recv();
+            let sched1_id = rx.recv_unwrap();
 
             let mut task = pool.task(TaskOpts::new(), proc() {
                 assert_eq!(sched_id(), sched1_id);
@@ -1125,7 +1275,7 @@ mod test {
             task.give_home(HomeSched(handle1));
             handle2.send(TaskFromFriend(task));
         }
-        drx.recv();
+        drx.recv_unwrap();
 
         pool.shutdown();
     }
@@ -1146,7 +1296,7 @@ mod test {
             let (normal_worker, normal_stealer) = pool.deque();
             let (special_worker, special_stealer) = pool.deque();
             let queues = vec![normal_stealer, special_stealer];
-            let (_p, state) = TaskState::new();
+            let (_p, state) = TaskState::new(false);
 
             // Our normal scheduler
             let mut normal_sched = box Scheduler::new(
@@ -1236,7 +1386,7 @@ mod test {
             let normal_task = GreenTask::new(&mut normal_sched.stack_pool, None, proc() {
                 run(task2);
                 run(task4);
-                rx.recv();
+                rx.recv_unwrap();
                 let mut nh = normal_handle;
                 nh.send(Shutdown);
                 let mut sh = special_handle;
@@ -1278,6 +1428,7 @@ mod test {
         let mut pool = SchedPool::new(PoolConfig {
             threads: 2,
             event_loop_factory: rustuv::event_loop,
+            detect_deadlocks: false,
         });
 
         // This is a regression test that when there are no schedulable tasks in
@@ -1304,13 +1455,13 @@ mod test {
         pool1.spawn(TaskOpts::new(), proc() {
             let id = sched_id();
             tx1.send(());
-            rx2.recv();
+            rx2.recv_unwrap();
             assert_eq!(id, sched_id());
         });
 
         pool2.spawn(TaskOpts::new(), proc() {
             let id = sched_id();
-            rx1.recv();
+            rx1.recv_unwrap();
             assert_eq!(id, sched_id());
             tx2.send(());
         });
@@ -1345,7 +1496,7 @@ mod test {
 
             loop {
                 match rxs.pop() {
-                    Some(rx) => rx.recv(),
+                    Some(rx) => rx.recv_unwrap(),
                     None => break,
                 }
             }
@@ -1378,14 +1529,14 @@ mod test {
                 roundtrip(1, n_tasks, &rx, &tx1);
             });
 
-            end_rx.recv();
+            end_rx.recv_unwrap();
         });
 
         fn roundtrip(id: int, n_tasks: int,
                      rx: &Receiver<(int, Sender<()>)>,
                      tx: &Sender<(int, Sender<()>)>) {
             loop {
-                match rx.recv() {
+                match rx.recv_unwrap() {
                     (1, end_tx) => {
                         debug!("{}\n", id);
                         end_tx.send(());
@@ -1429,6 +1580,7 @@ mod test {
         let mut pool = SchedPool::new(PoolConfig {
             threads: 2, // this must be > 1
             event_loop_factory: basic::event_loop,
+            detect_deadlocks: false,
         });
         pool.spawn(TaskOpts::new(), proc() {
             let (tx, rx) = channel();
@@ -1501,12 +1653,12 @@ mod test {
             let mut handle = pool.spawn_sched();
             handle.send(PinnedTask(pool.task(TaskOpts::new(), proc() {
                 // Wait until the other task has its lock
-                start_rx.recv();
+                start_rx.recv_unwrap();
 
                 fn pingpong(po: &Receiver<int>, ch: &Sender<int>) {
                     let mut val = 20;
                     while val > 0 {
-                        val = po.recv();
+                        val = po.recv_unwrap();
                         let _ = ch.send_opt(val - 1);
                     }
                 }
@@ -1519,7 +1671,7 @@ mod test {
                     pingpong(&child_rx, &parent_tx);
                 });
 
-                let child_tx = setup_rx.recv();
+                let child_tx = setup_rx.recv_unwrap();
                 child_tx.send(20);
                 pingpong(&parent_rx, &child_tx);
                 unsafe {
@@ -1530,9 +1682,33 @@ mod test {
             })));
             drop(handle);
 
-            fin_rx.recv();
+            fin_rx.recv_unwrap();
             pool.shutdown();
         }
         unsafe { LOCK.destroy(); }
     }
+
+    #[test]
+    fn stats() {
+        let config = PoolConfig::new().threads(2)
+                                      .event_loop_factory(basic::event_loop);
+        let mut pool = SchedPool::new(config);
+        let stats = pool.stats();
+        assert_eq!(stats.len(), 2);
+        for s in stats.iter() {
+            assert_eq!((s.steals, s.yields, s.queue_depth), (0, 0, 0));
+        }
+
+        let (tx, rx) = channel();
+        pool.spawn(TaskOpts::new(), proc() {
+            for _ in range(0, 10) { ::std::task::deschedule(); }
+            tx.send(::stats().unwrap());
+        });
+        let stats = rx.recv_unwrap();
+        assert_eq!(stats.len(), 2);
+        assert!(stats.iter().fold(0, |n, s| n + s.yields) >= 10);
+        pool.shutdown();
+
+        assert!(::stats().is_none());
+    }
 }
@@ -80,6 +80,7 @@ impl Runtime for SimpleTask {
     }
     fn local_io<'a>(&'a mut self) -> Option<rtio::LocalIo<'a>> { None }
     fn stack_bounds(&self) -> (uint, uint) { fail!() }
+    fn stack_guard(&self) -> Option<(uint, uint)> { None }
     fn can_block(&self) -> bool { true }
     fn wrap(~self) -> Box<Any> { fail!() }
 }
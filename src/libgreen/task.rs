@@ -19,11 +19,14 @@
 //! values.
 
 use std::any::Any;
+use std::io::{IoError, IoUnavailable};
 use std::mem;
+use std::os;
 use std::raw;
 use std::rt::Runtime;
 use std::rt::env;
 use std::rt::local::Local;
+use std::rt::observer;
 use std::rt::rtio;
 use std::rt::stack;
 use std::rt::task::{Task, BlockedTask, SendMessage};
@@ -32,6 +35,7 @@ use std::unstable::mutex::NativeMutex;
 
 use context::Context;
 use coroutine::Coroutine;
+use deadlock::{Monitor, Waiter};
 use sched::{Scheduler, SchedHandle, RunOnce};
 use stack::StackPool;
 
@@ -68,6 +72,21 @@ pub struct GreenTask {
 
     // See the comments in the scheduler about why this is necessary
     pub nasty_deschedule_lock: NativeMutex,
+
+    // The deadlock monitor which this task is registered as blocked with, if
+    // it's blocked and its pool detects deadlocks
+    monitor: Option<Monitor>,
+
+    // Set by the deadlock monitor to what this task was deadlocked on when it
+    // wakes the task up to fail it
+    deadlocked: Option<&'static str>,
+
+    /// Whether this task is to be pinned to the scheduler which first runs it
+    pub pin_on_start: bool,
+
+    /// The id of the scheduler this task is pinned to, if any. It must never
+    /// run on any other scheduler.
+    pub pinned_to: Option<uint>,
 }
 
 pub enum TaskType {
@@ -106,12 +125,16 @@ extern fn bootstrap_green_task(task: uint, code: *(), env: *()) -> ! {
         sched.task_state.increment();
         sched.pool_id
     };
+    if task.pin_on_start {
+        task.pin();
+    }
 
     // Convert our green task to a libstd task and then execute the code
     // requested. This is the "try/catch" block for this green task and
     // is the wrapper for *all* code run in the task.
     let mut start = Some(start);
     let task = task.swap().run(|| start.take_unwrap()());
+    observer::died(&*task);
 
     // Once the function has exited, it's time to run the termination
     // routine. This means we need to context switch one more time but
@@ -124,6 +147,25 @@ extern fn bootstrap_green_task(task: uint, code: *(), env: *()) -> ! {
     GreenTask::convert(task).terminate()
 }
 
+/// Pins the current task to the scheduler thread it's running on, so that it
+/// never migrates to another thread of its pool. This is required for tasks
+/// which own thread-affine resources, such as OpenGL contexts.
+///
+/// Note that a pinned task can only perform I/O with objects belonging to its
+/// own scheduler. Native tasks never leave their thread, so this has no effect
+/// on them.
+pub fn pin_to_current_scheduler() {
+    let mut task: Box<Task> = Local::take();
+    match task.maybe_take_runtime::<GreenTask>() {
+        Some(mut green) => {
+            green.pin();
+            task.put_runtime(green);
+        }
+        None => {}
+    }
+    Local::put(task);
+}
+
 impl GreenTask {
     /// Creates a new green task which is not homed to any particular scheduler
     /// and will not have any contained Task structure.
@@ -166,6 +208,10 @@ impl GreenTask {
             sched: None,
             handle: None,
             nasty_deschedule_lock: unsafe { NativeMutex::new() },
+            monitor: None,
+            deadlocked: None,
+            pin_on_start: false,
+            pinned_to: None,
             task: Some(box Task::new()),
         }
     }
@@ -177,16 +223,42 @@ impl GreenTask {
                      opts: TaskOpts,
                      f: proc():Send) -> Box<GreenTask> {
         let TaskOpts {
-            notify_chan, name, stack_size,
-            stderr, stdout,
+            notify_chan, name, stack_size, priority, affinity, scheduled,
+            pinned, stderr, stdout, on_fail,
         } = opts;
 
+        // Green tasks migrate among the threads of their pool, so there's no
+        // thread to apply these options to.
+        let f = if priority.is_some() || affinity.is_some() {
+            match scheduled {
+                Some(tx) => {
+                    let _ = tx.send_opt(Err(IoError {
+                        kind: IoUnavailable,
+                        desc: "green tasks can't have a priority or CPU affinity",
+                        detail: None,
+                    }));
+                    proc() {}
+                }
+                None => {
+                    proc() fail!("green tasks can't have a priority or CPU affinity")
+                }
+            }
+        } else {
+            match scheduled {
+                Some(tx) => { let _ = tx.send_opt(Ok(())); }
+                None => {}
+            }
+            f
+        };
+
         let mut green = GreenTask::new(pool, stack_size, f);
+        green.pin_on_start = pinned;
         {
             let task = green.task.get_mut_ref();
             task.name = name;
             task.stderr = stderr;
             task.stdout = stdout;
+            task.on_fail = on_fail;
             match notify_chan {
                 Some(chan) => {
                     task.death.on_exit = Some(SendMessage(chan));
@@ -194,6 +266,7 @@ impl GreenTask {
                 None => {}
             }
         }
+        observer::spawned(green.task.get_ref());
         return green;
     }
 
@@ -303,6 +376,85 @@ impl GreenTask {
         sched.terminate_current_task(self)
     }
 
+    /// Pins this task to the scheduler it's currently running on. From now on
+    /// the task is sent back to this scheduler whenever another one picks it
+    /// up, and is only ever woken up on it.
+    pub fn pin(&mut self) {
+        let (id, home, handle) = {
+            let sched = self.sched.get_mut_ref();
+            (sched.sched_id(), sched.make_handle(), sched.make_handle())
+        };
+        self.pinned_to = Some(id);
+        self.give_home(HomeSched(home));
+        // Remote wakeups go through this handle, so it must lead home as well
+        self.handle = Some(handle);
+    }
+
+    // Prepares registering this task with the deadlock monitor of its pool as
+    // it's about to block on `what`. The registration itself has to wait until
+    // the task has been descheduled, as only then can the monitor be handed a
+    // way to wake it up.
+    fn block(&mut self, monitor: &Monitor, what: &'static str) -> Waiter {
+        let desc = match self.task.get_ref().name {
+            Some(ref name) => format!("task '{}' is blocked on {}", name, what),
+            None => format!("task <unnamed> is blocked on {}", what),
+        };
+        self.monitor = Some(monitor.clone());
+        Waiter {
+            monitor: monitor.clone(),
+            id: self.as_uint(),
+            what: what,
+            desc: desc,
+        }
+    }
+
+    // Called whenever this task is woken up, taking it back off the list of
+    // blocked tasks.
+    fn unblock(&mut self) {
+        observer::unblocked(self.task.get_ref());
+        match self.monitor.take() {
+            Some(monitor) => monitor.unblock(self.as_uint()),
+            None => {}
+        }
+    }
+
+    /// Wakes up this task, which the deadlock monitor has claimed while it was
+    /// blocked on `what`, in order for it to fail.
+    pub fn fail_deadlocked(mut ~self, what: &'static str) {
+        self.monitor = None;
+        self.deadlocked = Some(what);
+        self.reawaken_remotely();
+    }
+
+    // Fails this task without unwinding, once it has been woken up by the
+    // deadlock monitor. The failure is reported like any other, but then the
+    // task is leaked rather than destroyed, see the deadlock module for why.
+    fn die_deadlocked(mut ~self, what: &'static str) -> ! {
+        let msg = format!("deadlocked on {}", what);
+        {
+            let name = match self.task.get_ref().name {
+                Some(ref name) => name.as_slice(),
+                None => "<unnamed>",
+            };
+            rterrln!("task '{}' failed at '{}'", name, msg);
+        }
+
+        // Reporting the failure may run arbitrary code, so we must still have
+        // a local task in TLS while doing so.
+        self.put();
+        unsafe {
+            let me: *mut Task = Local::unsafe_borrow();
+            (*me).death.collect_failure(Err(box msg as Box<Any:Send>));
+        }
+        let mut task: Box<Task> = Local::take();
+        task.destroyed = true;
+        observer::died(&*task);
+
+        let mut me = GreenTask::convert(task);
+        let sched = me.sched.take_unwrap();
+        sched.abandon_current_task(me)
+    }
+
     // This function is used to remotely wakeup this green task back on to its
     // original pool of schedulers. In order to do so, each tasks arranges a
     // SchedHandle upon descheduling to be available for sending itself back to
@@ -345,11 +497,21 @@ impl Runtime for GreenTask {
         sched.maybe_yield(self);
     }
 
-    fn deschedule(mut ~self, times: uint, cur_task: Box<Task>,
+    fn deschedule(mut ~self, times: uint, mut cur_task: Box<Task>,
                   f: |BlockedTask| -> Result<(), BlockedTask>) {
+        observer::blocked(&*cur_task);
+        let blocked_on = cur_task.blocked_on.take();
         self.put_task(cur_task);
         let mut sched = self.sched.take_unwrap();
 
+        // Blocking on another task counts towards a deadlock, but descheduling
+        // for other reasons (such as moving to another scheduler) doesn't.
+        let mut waiter = match (blocked_on, &sched.task_state.monitor) {
+            (Some(what), &Some(ref monitor)) => Some(self.block(monitor, what)),
+            _ => None,
+        };
+        let monitored = waiter.is_some();
+
         // In order for this task to be reawoken in all possible contexts, we
         // may need a handle back in to the current scheduler. When we're woken
         // up in anything other than the local scheduler pool, this handle is
@@ -368,23 +530,35 @@ impl Runtime for GreenTask {
         // Task => GreenTask structure.
         if times == 1 {
             sched.deschedule_running_task_and_then(self, |sched, task| {
+                let task = match waiter.take() {
+                    Some(waiter) => waiter.register(task),
+                    None => task,
+                };
                 match f(task) {
                     Ok(()) => {}
                     Err(t) => {
                         t.wake().map(|t| {
-                            sched.enqueue_task(GreenTask::convert(t))
+                            let mut t = GreenTask::convert(t);
+                            t.unblock();
+                            sched.enqueue_task(t)
                         });
                     }
                 }
             });
         } else {
             sched.deschedule_running_task_and_then(self, |sched, task| {
+                let task = match waiter.take() {
+                    Some(waiter) => waiter.register(task),
+                    None => task,
+                };
                 for task in task.make_selectable(times) {
                     match f(task) {
                         Ok(()) => {},
                         Err(task) => {
                             task.wake().map(|t| {
-                                sched.enqueue_task(GreenTask::convert(t))
+                                let mut t = GreenTask::convert(t);
+                                t.unblock();
+                                sched.enqueue_task(t)
                             });
                             break
                         }
@@ -392,11 +566,22 @@ impl Runtime for GreenTask {
                 }
             });
         }
+
+        // The deadlock monitor may have woken us up just to fail us
+        if monitored {
+            let me = GreenTask::convert(Local::take());
+            let deadlocked = me.deadlocked;
+            match deadlocked {
+                Some(what) => me.die_deadlocked(what),
+                None => me.put(),
+            }
+        }
     }
 
     fn reawaken(mut ~self, to_wake: Box<Task>) {
         self.put_task(to_wake);
         assert!(self.sched.is_none());
+        self.unblock();
 
         // Optimistically look for a local task, but if one's not available to
         // inspect (in order to see if it's in the same sched pool as we are),
@@ -480,6 +665,20 @@ impl Runtime for GreenTask {
          c.current_stack_segment.end() as uint)
     }
 
+    fn stack_guard(&self) -> Option<(uint, uint)> {
+        // Scheduler coroutines run on the OS thread's stack and have a dummy
+        // stack segment starting at zero. For all others, the lowest page of
+        // the segment is the guard page (see `Stack::new`).
+        self.coroutine.as_ref().and_then(|c| {
+            let start = c.current_stack_segment.start();
+            if start.is_null() {
+                None
+            } else {
+                Some((start as uint, start as uint + os::page_size()))
+            }
+        })
+    }
+
     fn can_block(&self) -> bool { false }
 
     fn wrap(~self) -> Box<Any> { self as Box<Any> }
@@ -487,6 +686,7 @@ impl Runtime for GreenTask {
 
 #[cfg(test)]
 mod tests {
+    use std::io::IoUnavailable;
     use std::rt::local::Local;
     use std::rt::task::Task;
     use std::task;
@@ -499,6 +699,7 @@ mod tests {
         let mut pool = SchedPool::new(PoolConfig {
             threads: 1,
             event_loop_factory: ::rustuv::event_loop,
+            detect_deadlocks: false,
         });
         pool.spawn(opts, f);
         pool.shutdown();
@@ -510,7 +711,7 @@ mod tests {
         spawn_opts(TaskOpts::new(), proc() {
             tx.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 
     #[test]
@@ -531,7 +732,24 @@ mod tests {
         let (tx, rx) = channel();
         opts.notify_chan = Some(tx);
         spawn_opts(opts, proc() {});
-        assert!(rx.recv().is_ok());
+        assert!(rx.recv_unwrap().is_ok());
+    }
+
+    #[test]
+    fn smoke_opts_priority() {
+        let mut opts = TaskOpts::new();
+        opts.priority = Some(task::LowPriority);
+        let (tx, rx) = channel();
+        opts.notify_chan = Some(tx);
+        spawn_opts(opts, proc() {});
+        assert!(rx.recv_unwrap().is_err());
+
+        let mut opts = TaskOpts::new();
+        opts.priority = Some(task::LowPriority);
+        let (tx, rx) = channel();
+        opts.scheduled = Some(tx);
+        spawn_opts(opts, proc() fail!());
+        assert_eq!(rx.recv_unwrap().unwrap_err().kind, IoUnavailable);
     }
 
     #[test]
@@ -540,7 +758,7 @@ mod tests {
         let (tx, rx) = channel();
         opts.notify_chan = Some(tx);
         spawn_opts(opts, proc() { fail!() });
-        assert!(rx.recv().is_err());
+        assert!(rx.recv_unwrap().is_err());
     }
 
     #[test]
@@ -550,7 +768,7 @@ mod tests {
             for _ in range(0, 10) { task::deschedule(); }
             tx.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 
     #[test]
@@ -563,13 +781,13 @@ mod tests {
                 spawn(proc() {
                     tx3.send(());
                 });
-                rx.recv();
+                rx.recv_unwrap();
                 tx2.send(());
             });
-            rx.recv();
+            rx.recv_unwrap();
             tx1.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 
     #[test]
@@ -588,6 +806,74 @@ mod tests {
                 tx.send(());
             });
         });
-        rx.recv();
+        rx.recv_unwrap();
+    }
+
+    fn current_sched() -> uint {
+        let mut task: Box<Task> = Local::take();
+        let id = match task.maybe_take_runtime::<GreenTask>() {
+            Some(ops) => {
+                let id = ops.sched.get_ref().sched_id();
+                task.put_runtime(ops);
+                id
+            }
+            None => fail!(),
+        };
+        Local::put(task);
+        id
+    }
+
+    // Yields repeatedly, giving the other schedulers every chance to steal us
+    fn stays_put(tx: Sender<()>) {
+        let home = current_sched();
+        for _ in range(0, 100) {
+            task::deschedule();
+            assert_eq!(current_sched(), home);
+        }
+        tx.send(());
+    }
+
+    fn run_pinned(f: |&mut SchedPool, Sender<()>|) {
+        let config = PoolConfig::new().threads(4)
+                                      .event_loop_factory(::rustuv::event_loop);
+        let mut pool = SchedPool::new(config);
+        let (tx, rx) = channel();
+        for _ in range(0, 8) {
+            f(&mut pool, tx.clone());
+        }
+        drop(tx);
+        for _ in range(0, 8) {
+            rx.recv_unwrap();
+        }
+        pool.shutdown();
+    }
+
+    #[test]
+    fn pinned_opts() {
+        run_pinned(|pool, tx| {
+            let mut opts = TaskOpts::new();
+            opts.pinned = true;
+            pool.spawn(opts, proc() stays_put(tx));
+        });
+    }
+
+    #[test]
+    fn pinned_builder() {
+        run_pinned(|pool, tx| {
+            pool.spawn(TaskOpts::new(), proc() {
+                task::TaskBuilder::new().pinned().spawn(proc() stays_put(tx));
+            });
+        });
+    }
+
+    #[test]
+    fn pin_to_current_scheduler() {
+        run_pinned(|pool, tx| {
+            pool.spawn(TaskOpts::new(), proc() {
+                task::deschedule();
+                super::pin_to_current_scheduler();
+                stays_put(tx);
+            });
+        });
     }
 }
@@ -113,11 +113,13 @@ pub use consts::os::posix88::{S_IREAD, S_IRUSR, S_IRWXU, S_IWUSR};
 pub use consts::os::posix88::{STDERR_FILENO, STDIN_FILENO, S_IXUSR};
 pub use consts::os::posix88::{STDOUT_FILENO, W_OK, X_OK};
 pub use consts::os::bsd44::{AF_INET, AF_INET6, SOCK_STREAM, SOCK_DGRAM};
+pub use consts::os::bsd44::{SOCK_RAW, IPPROTO_UDP};
 pub use consts::os::bsd44::{IPPROTO_IP, IPPROTO_IPV6, IPPROTO_TCP, TCP_NODELAY};
 pub use consts::os::bsd44::{SOL_SOCKET, SO_KEEPALIVE, SO_ERROR};
 pub use consts::os::bsd44::{SO_REUSEADDR, SO_BROADCAST, SHUT_WR, IP_MULTICAST_LOOP};
 pub use consts::os::bsd44::{IP_ADD_MEMBERSHIP, IP_DROP_MEMBERSHIP};
 pub use consts::os::bsd44::{IPV6_ADD_MEMBERSHIP, IPV6_DROP_MEMBERSHIP};
+pub use consts::os::bsd44::{IPV6_UNICAST_HOPS, IPV6_MULTICAST_HOPS, IPV6_MULTICAST_LOOP};
 pub use consts::os::bsd44::{IP_MULTICAST_TTL, IP_TTL, SHUT_RD};
 
 pub use funcs::c95::ctype::{isalnum, isalpha, iscntrl, isdigit};
@@ -167,16 +169,19 @@ pub use funcs::bsd43::{shutdown};
 #[cfg(unix)] pub use consts::os::sysconf::{_SC_PAGESIZE};
 #[cfg(unix)] pub use consts::os::posix88::{PROT_READ, PROT_WRITE, PROT_EXEC};
 #[cfg(unix)] pub use consts::os::posix88::{MAP_FIXED, MAP_FILE, MAP_ANON, MAP_PRIVATE, MAP_FAILED};
+#[cfg(unix)] pub use consts::os::posix88::{MAP_SHARED, MS_SYNC};
 #[cfg(unix)] pub use consts::os::posix88::{EACCES, EBADF, EINVAL, ENODEV, ENOMEM};
 #[cfg(unix)] pub use consts::os::posix88::{ECONNREFUSED, ECONNRESET, EPERM, EPIPE};
 #[cfg(unix)] pub use consts::os::posix88::{ENOTCONN, ECONNABORTED, EADDRNOTAVAIL, EINTR};
 #[cfg(unix)] pub use consts::os::posix88::{EADDRINUSE, ENOENT, EISDIR, EAGAIN, EWOULDBLOCK};
 #[cfg(unix)] pub use consts::os::posix88::{ECANCELED, SIGINT, EINPROGRESS};
+#[cfg(unix)] pub use consts::os::posix88::{EEXIST};
 #[cfg(unix)] pub use consts::os::posix88::{SIGTERM, SIGKILL, SIGPIPE, PROT_NONE};
 #[cfg(unix)] pub use consts::os::posix01::{SIG_IGN};
 #[cfg(unix)] pub use consts::os::bsd44::{AF_UNIX};
+#[cfg(unix)] pub use consts::os::bsd44::{LOCK_SH, LOCK_EX, LOCK_NB, LOCK_UN};
 
-#[cfg(unix)] pub use types::os::common::posix01::{pthread_t, timespec, timezone};
+#[cfg(unix)] pub use types::os::common::posix01::{pthread_t, timespec, timezone, iovec};
 
 #[cfg(unix)] pub use types::os::arch::posix88::{uid_t, gid_t};
 #[cfg(unix)] pub use types::os::arch::posix01::{pthread_attr_t};
@@ -185,12 +190,15 @@ pub use funcs::bsd43::{shutdown};
 #[cfg(unix)] pub use funcs::posix88::unistd::{getgid, getuid};
 #[cfg(unix)] pub use funcs::posix88::unistd::{_PC_NAME_MAX, utime, nanosleep, pathconf, link};
 #[cfg(unix)] pub use funcs::posix88::unistd::{chown};
-#[cfg(unix)] pub use funcs::posix88::mman::{mmap, munmap, mprotect};
+#[cfg(unix)] pub use funcs::posix88::mman::{mmap, munmap, mprotect, msync};
 #[cfg(unix)] pub use funcs::posix88::dirent::{opendir, readdir_r, closedir};
 #[cfg(unix)] pub use funcs::posix88::fcntl::{fcntl};
 #[cfg(unix)] pub use funcs::posix01::stat_::{lstat};
 #[cfg(unix)] pub use funcs::posix01::unistd::{fsync, ftruncate};
 #[cfg(unix)] pub use funcs::posix01::unistd::{readlink, symlink};
+#[cfg(unix)] pub use funcs::posix01::uio::{readv, writev};
+#[cfg(unix)] pub use funcs::posix01::stdlib::{realpath};
+#[cfg(unix)] pub use funcs::bsd44::{flock};
 
 #[cfg(windows)] pub use consts::os::c95::{WSAECONNREFUSED, WSAECONNRESET, WSAEACCES};
 #[cfg(windows)] pub use consts::os::c95::{WSAEWOULDBLOCK, WSAENOTCONN, WSAECONNABORTED};
@@ -204,6 +212,7 @@ pub use funcs::bsd43::{shutdown};
 #[cfg(windows)] pub use consts::os::extra::{FILE_MAP_READ, FILE_MAP_WRITE, FILE_MAP_EXECUTE};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_ALREADY_EXISTS, ERROR_NO_DATA};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_FILE_NOT_FOUND, ERROR_INVALID_NAME};
+#[cfg(windows)] pub use consts::os::extra::{ERROR_FILE_EXISTS};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_BROKEN_PIPE, ERROR_INVALID_FUNCTION};
 #[cfg(windows)] pub use consts::os::extra::{TRUE, FALSE, INFINITE};
 #[cfg(windows)] pub use consts::os::extra::{PROCESS_TERMINATE, PROCESS_QUERY_INFORMATION};
@@ -227,6 +236,8 @@ pub use funcs::bsd43::{shutdown};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_PIPE_BUSY, ERROR_IO_PENDING};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_PIPE_CONNECTED, WAIT_OBJECT_0};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_NOT_FOUND};
+#[cfg(windows)] pub use consts::os::extra::{ERROR_LOCK_VIOLATION, LOCKFILE_FAIL_IMMEDIATELY};
+#[cfg(windows)] pub use consts::os::extra::{LOCKFILE_EXCLUSIVE_LOCK};
 #[cfg(windows)] pub use consts::os::extra::{ERROR_OPERATION_ABORTED};
 #[cfg(windows)] pub use types::os::common::bsd44::{SOCKET};
 #[cfg(windows)] pub use types::os::common::posix01::{stat, utimbuf};
@@ -244,7 +255,7 @@ pub use funcs::bsd43::{shutdown};
 #[cfg(windows)] pub use funcs::extra::kernel32::{GetModuleFileNameW, SetCurrentDirectoryW};
 #[cfg(windows)] pub use funcs::extra::kernel32::{GetSystemInfo, VirtualAlloc, VirtualFree};
 #[cfg(windows)] pub use funcs::extra::kernel32::{CreateFileMappingW, MapViewOfFile};
-#[cfg(windows)] pub use funcs::extra::kernel32::{UnmapViewOfFile, CloseHandle};
+#[cfg(windows)] pub use funcs::extra::kernel32::{UnmapViewOfFile, FlushViewOfFile, CloseHandle};
 #[cfg(windows)] pub use funcs::extra::kernel32::{WaitForSingleObject, GetSystemTimeAsFileTime};
 #[cfg(windows)] pub use funcs::extra::kernel32::{QueryPerformanceCounter};
 #[cfg(windows)] pub use funcs::extra::kernel32::{WaitForSingleObject, QueryPerformanceFrequency};
@@ -259,6 +270,7 @@ pub use funcs::bsd43::{shutdown};
 #[cfg(windows)] pub use funcs::extra::kernel32::{GetOverlappedResult, ConnectNamedPipe};
 #[cfg(windows)] pub use funcs::extra::kernel32::{DisconnectNamedPipe, OpenProcess};
 #[cfg(windows)] pub use funcs::extra::kernel32::{MoveFileExW, VirtualProtect};
+#[cfg(windows)] pub use funcs::extra::kernel32::{LockFileEx, UnlockFileEx};
 #[cfg(windows)] pub use funcs::extra::msvcrt::{get_osfhandle, open_osfhandle};
 
 #[cfg(target_os = "linux")] #[cfg(target_os = "android")] #[cfg(target_os = "freebsd")]
@@ -378,6 +390,11 @@ pub mod types {
                     pub tv_nsec: c_long,
                 }
 
+                pub struct iovec {
+                    pub iov_base: *mut c_void,
+                    pub iov_len: size_t,
+                }
+
                 pub enum timezone {}
 
                 pub type sighandler_t = size_t;
@@ -762,6 +779,11 @@ pub mod types {
                     pub tv_nsec: c_long,
                 }
 
+                pub struct iovec {
+                    pub iov_base: *mut c_void,
+                    pub iov_len: size_t,
+                }
+
                 pub enum timezone {}
 
                 pub type sighandler_t = size_t;
@@ -1318,6 +1340,11 @@ pub mod types {
                     pub tv_nsec: c_long,
                 }
 
+                pub struct iovec {
+                    pub iov_base: *mut c_void,
+                    pub iov_len: size_t,
+                }
+
                 pub enum timezone {}
 
                 pub type sighandler_t = size_t;
@@ -1710,7 +1737,9 @@ pub mod consts {
             pub static AF_INET6: c_int = 23;
             pub static SOCK_STREAM: c_int = 1;
             pub static SOCK_DGRAM: c_int = 2;
+            pub static SOCK_RAW: c_int = 3;
             pub static IPPROTO_TCP: c_int = 6;
+            pub static IPPROTO_UDP: c_int = 17;
             pub static IPPROTO_IP: c_int = 0;
             pub static IPPROTO_IPV6: c_int = 41;
             pub static IP_MULTICAST_TTL: c_int = 3;
@@ -1719,6 +1748,9 @@ pub mod consts {
             pub static IP_DROP_MEMBERSHIP: c_int = 6;
             pub static IPV6_ADD_MEMBERSHIP: c_int = 5;
             pub static IPV6_DROP_MEMBERSHIP: c_int = 6;
+            pub static IPV6_UNICAST_HOPS: c_int = 4;
+            pub static IPV6_MULTICAST_HOPS: c_int = 10;
+            pub static IPV6_MULTICAST_LOOP: c_int = 11;
             pub static IP_TTL: c_int = 4;
 
             pub static TCP_NODELAY: c_int = 0x0001;
@@ -1748,6 +1780,8 @@ pub mod consts {
             pub static ERROR_FILE_NOT_FOUND: c_int = 2;
             pub static ERROR_ACCESS_DENIED: c_int = 5;
             pub static ERROR_INVALID_HANDLE : c_int = 6;
+            pub static ERROR_LOCK_VIOLATION : c_int = 33;
+            pub static ERROR_FILE_EXISTS : c_int = 80;
             pub static ERROR_BROKEN_PIPE: c_int = 109;
             pub static ERROR_DISK_FULL : c_int = 112;
             pub static ERROR_CALL_NOT_IMPLEMENTED : c_int = 120;
@@ -1905,6 +1939,9 @@ pub mod consts {
             pub static FILE_FLAG_WRITE_THROUGH: DWORD = 0x80000000;
             pub static FILE_FLAG_FIRST_PIPE_INSTANCE: DWORD = 0x00080000;
 
+            pub static LOCKFILE_FAIL_IMMEDIATELY: DWORD = 0x00000001;
+            pub static LOCKFILE_EXCLUSIVE_LOCK: DWORD = 0x00000002;
+
             pub static FILE_NAME_NORMALIZED: DWORD = 0x0;
             pub static FILE_NAME_OPENED: DWORD = 0x8;
 
@@ -2504,7 +2541,9 @@ pub mod consts {
             pub static AF_INET6: c_int = 10;
             pub static SOCK_STREAM: c_int = 1;
             pub static SOCK_DGRAM: c_int = 2;
+            pub static SOCK_RAW: c_int = 3;
             pub static IPPROTO_TCP: c_int = 6;
+            pub static IPPROTO_UDP: c_int = 17;
             pub static IPPROTO_IP: c_int = 0;
             pub static IPPROTO_IPV6: c_int = 41;
             pub static IP_MULTICAST_TTL: c_int = 33;
@@ -2514,6 +2553,9 @@ pub mod consts {
             pub static IP_DROP_MEMBERSHIP: c_int = 36;
             pub static IPV6_ADD_MEMBERSHIP: c_int = 20;
             pub static IPV6_DROP_MEMBERSHIP: c_int = 21;
+            pub static IPV6_UNICAST_HOPS: c_int = 16;
+            pub static IPV6_MULTICAST_HOPS: c_int = 18;
+            pub static IPV6_MULTICAST_LOOP: c_int = 19;
 
             pub static TCP_NODELAY: c_int = 1;
             pub static SOL_SOCKET: c_int = 1;
@@ -2525,6 +2567,11 @@ pub mod consts {
             pub static SHUT_RD: c_int = 0;
             pub static SHUT_WR: c_int = 1;
             pub static SHUT_RDWR: c_int = 2;
+
+            pub static LOCK_SH: c_int = 1;
+            pub static LOCK_EX: c_int = 2;
+            pub static LOCK_NB: c_int = 4;
+            pub static LOCK_UN: c_int = 8;
         }
         #[cfg(target_arch = "mips")]
         pub mod bsd44 {
@@ -2547,7 +2594,9 @@ pub mod consts {
             pub static AF_INET6: c_int = 10;
             pub static SOCK_STREAM: c_int = 2;
             pub static SOCK_DGRAM: c_int = 1;
+            pub static SOCK_RAW: c_int = 3;
             pub static IPPROTO_TCP: c_int = 6;
+            pub static IPPROTO_UDP: c_int = 17;
             pub static IPPROTO_IP: c_int = 0;
             pub static IPPROTO_IPV6: c_int = 41;
             pub static IP_MULTICAST_TTL: c_int = 33;
@@ -2557,6 +2606,9 @@ pub mod consts {
             pub static IP_DROP_MEMBERSHIP: c_int = 36;
             pub static IPV6_ADD_MEMBERSHIP: c_int = 20;
             pub static IPV6_DROP_MEMBERSHIP: c_int = 21;
+            pub static IPV6_UNICAST_HOPS: c_int = 16;
+            pub static IPV6_MULTICAST_HOPS: c_int = 18;
+            pub static IPV6_MULTICAST_LOOP: c_int = 19;
 
             pub static TCP_NODELAY: c_int = 1;
             pub static SOL_SOCKET: c_int = 65535;
@@ -2568,6 +2620,11 @@ pub mod consts {
             pub static SHUT_RD: c_int = 0;
             pub static SHUT_WR: c_int = 1;
             pub static SHUT_RDWR: c_int = 2;
+
+            pub static LOCK_SH: c_int = 1;
+            pub static LOCK_EX: c_int = 2;
+            pub static LOCK_NB: c_int = 4;
+            pub static LOCK_UN: c_int = 8;
         }
         #[cfg(target_arch = "x86")]
         #[cfg(target_arch = "x86_64")]
@@ -3003,7 +3060,9 @@ pub mod consts {
             pub static AF_UNIX: c_int = 1;
             pub static SOCK_STREAM: c_int = 1;
             pub static SOCK_DGRAM: c_int = 2;
+            pub static SOCK_RAW: c_int = 3;
             pub static IPPROTO_TCP: c_int = 6;
+            pub static IPPROTO_UDP: c_int = 17;
             pub static IPPROTO_IP: c_int = 0;
             pub static IPPROTO_IPV6: c_int = 41;
             pub static IP_MULTICAST_TTL: c_int = 10;
@@ -3013,6 +3072,9 @@ pub mod consts {
             pub static IP_DROP_MEMBERSHIP: c_int = 13;
             pub static IPV6_ADD_MEMBERSHIP: c_int = 12;
             pub static IPV6_DROP_MEMBERSHIP: c_int = 13;
+            pub static IPV6_UNICAST_HOPS: c_int = 4;
+            pub static IPV6_MULTICAST_HOPS: c_int = 10;
+            pub static IPV6_MULTICAST_LOOP: c_int = 11;
 
             pub static TCP_NODELAY: c_int = 1;
             pub static TCP_KEEPIDLE: c_int = 256;
@@ -3025,6 +3087,11 @@ pub mod consts {
             pub static SHUT_RD: c_int = 0;
             pub static SHUT_WR: c_int = 1;
             pub static SHUT_RDWR: c_int = 2;
+
+            pub static LOCK_SH: c_int = 1;
+            pub static LOCK_EX: c_int = 2;
+            pub static LOCK_NB: c_int = 4;
+            pub static LOCK_UN: c_int = 8;
         }
         pub mod extra {
             use types::os::arch::c95::c_int;
@@ -3388,7 +3455,9 @@ pub mod consts {
             pub static AF_INET6: c_int = 30;
             pub static SOCK_STREAM: c_int = 1;
             pub static SOCK_DGRAM: c_int = 2;
+            pub static SOCK_RAW: c_int = 3;
             pub static IPPROTO_TCP: c_int = 6;
+            pub static IPPROTO_UDP: c_int = 17;
             pub static IPPROTO_IP: c_int = 0;
             pub static IPPROTO_IPV6: c_int = 41;
             pub static IP_MULTICAST_TTL: c_int = 10;
@@ -3398,6 +3467,9 @@ pub mod consts {
             pub static IP_DROP_MEMBERSHIP: c_int = 13;
             pub static IPV6_ADD_MEMBERSHIP: c_int = 12;
             pub static IPV6_DROP_MEMBERSHIP: c_int = 13;
+            pub static IPV6_UNICAST_HOPS: c_int = 4;
+            pub static IPV6_MULTICAST_HOPS: c_int = 10;
+            pub static IPV6_MULTICAST_LOOP: c_int = 11;
 
             pub static TCP_NODELAY: c_int = 0x01;
             pub static TCP_KEEPALIVE: c_int = 0x10;
@@ -3410,6 +3482,11 @@ pub mod consts {
             pub static SHUT_RD: c_int = 0;
             pub static SHUT_WR: c_int = 1;
             pub static SHUT_RDWR: c_int = 2;
+
+            pub static LOCK_SH: c_int = 1;
+            pub static LOCK_EX: c_int = 2;
+            pub static LOCK_NB: c_int = 4;
+            pub static LOCK_UN: c_int = 8;
         }
         pub mod extra {
             use types::os::arch::c95::c_int;
@@ -3964,6 +4041,15 @@ pub mod funcs {
     #[cfg(target_os = "macos")]
     #[cfg(target_os = "freebsd")]
     pub mod posix01 {
+        pub mod stdlib {
+            use types::os::arch::c95::c_char;
+
+            extern {
+                pub fn realpath(file_name: *c_char, resolved_name: *mut c_char)
+                                -> *c_char;
+            }
+        }
+
         pub mod stat_ {
             use types::os::arch::c95::{c_char, c_int};
             use types::os::arch::posix01::stat;
@@ -4038,6 +4124,17 @@ pub mod funcs {
             }
         }
 
+        pub mod uio {
+            use types::os::arch::c95::c_int;
+            use types::os::arch::posix88::ssize_t;
+            use types::os::common::posix01::iovec;
+
+            extern {
+                pub fn readv(fd: c_int, iov: *iovec, iovcnt: c_int) -> ssize_t;
+                pub fn writev(fd: c_int, iov: *iovec, iovcnt: c_int) -> ssize_t;
+            }
+        }
+
         pub mod mman {
             use types::common::c95::{c_void};
             use types::os::arch::c95::{c_int, size_t};
@@ -4179,6 +4276,7 @@ pub mod funcs {
                            -> c_int;
             pub fn mincore(addr: *c_void, len: size_t, vec: *c_uchar)
                            -> c_int;
+            pub fn flock(fd: c_int, operation: c_int) -> c_int;
         }
     }
 
@@ -4195,6 +4293,7 @@ pub mod funcs {
                            -> c_int;
             pub fn mincore(addr: *c_void, len: size_t, vec: *c_uchar)
                            -> c_int;
+            pub fn flock(fd: c_int, operation: c_int) -> c_int;
         }
     }
 
@@ -4345,6 +4444,8 @@ pub mod funcs {
                                      dwNumberOfBytesToMap: SIZE_T)
                                      -> LPVOID;
                 pub fn UnmapViewOfFile(lpBaseAddress: LPCVOID) -> BOOL;
+                pub fn FlushViewOfFile(lpBaseAddress: LPCVOID,
+                                       dwNumberOfBytesToFlush: SIZE_T) -> BOOL;
                 pub fn MoveFileExW(lpExistingFileName: LPCWSTR,
                                    lpNewFileName: LPCWSTR,
                                    dwFlags: DWORD) -> BOOL;
@@ -4375,6 +4476,17 @@ pub mod funcs {
                                         lpNewFilePointer: PLARGE_INTEGER,
                                         dwMoveMethod: DWORD) -> BOOL;
                 pub fn SetEndOfFile(hFile: HANDLE) -> BOOL;
+                pub fn LockFileEx(hFile: HANDLE,
+                                  dwFlags: DWORD,
+                                  dwReserved: DWORD,
+                                  nNumberOfBytesToLockLow: DWORD,
+                                  nNumberOfBytesToLockHigh: DWORD,
+                                  lpOverlapped: LPOVERLAPPED) -> BOOL;
+                pub fn UnlockFileEx(hFile: HANDLE,
+                                    dwReserved: DWORD,
+                                    nNumberOfBytesToUnlockLow: DWORD,
+                                    nNumberOfBytesToUnlockHigh: DWORD,
+                                    lpOverlapped: LPOVERLAPPED) -> BOOL;
 
                 pub fn GetSystemTimeAsFileTime(
                             lpSystemTimeAsFileTime: LPFILETIME);
@@ -13,10 +13,12 @@ use libc::{c_char, c_int};
 use libc;
 use std::c_str::CString;
 use std::io::IoError;
+use std::io::net::ip;
 use std::mem;
 use std::ptr::{null, mut_null};
+use std::str;
 
-use super::net::sockaddr_to_addr;
+use super::net::{addr_to_sockaddr, sockaddr_to_addr};
 
 pub struct GetAddrInfoRequest;
 
@@ -32,8 +34,8 @@ impl GetAddrInfoRequest {
             libc::addrinfo {
                 ai_flags: hint.flags as c_int,
                 ai_family: hint.family as c_int,
-                ai_socktype: 0,
-                ai_protocol: 0,
+                ai_socktype: hint.socktype.map_or(0, socktype_to_c),
+                ai_protocol: hint.protocol.map_or(0, protocol_to_c),
                 ai_addrlen: 0,
                 ai_canonname: null(),
                 ai_addr: null(),
@@ -69,8 +71,8 @@ impl GetAddrInfoRequest {
                 addrs.push(ai::Info {
                     address: addr,
                     family: (*rp).ai_family as uint,
-                    socktype: None,
-                    protocol: None,
+                    socktype: socktype_from_c((*rp).ai_socktype),
+                    protocol: protocol_from_c((*rp).ai_protocol),
                     flags: (*rp).ai_flags as uint
                 });
 
@@ -84,10 +86,69 @@ impl GetAddrInfoRequest {
     }
 }
 
+pub struct GetNameInfoRequest;
+
+impl GetNameInfoRequest {
+    pub fn run(addr: ip::IpAddr) -> Result<String, IoError> {
+        let addr = ip::SocketAddr { ip: addr, port: 0 };
+        let (storage, len) = addr_to_sockaddr(addr);
+        let mut host = [0 as c_char, ..NI_MAXHOST];
+        let s = unsafe {
+            getnameinfo(&storage as *_ as *libc::sockaddr,
+                        len as libc::socklen_t,
+                        host.as_mut_ptr(), host.len() as libc::socklen_t,
+                        mut_null(), 0, 0)
+        };
+        if s != 0 {
+            return Err(get_error(s));
+        }
+        let name = unsafe { CString::new(host.as_ptr(), false) };
+        Ok(str::from_utf8_lossy(name.as_bytes_no_nul()).to_string())
+    }
+}
+
+fn socktype_to_c(ty: ai::SocketType) -> c_int {
+    match ty {
+        ai::Stream => libc::SOCK_STREAM,
+        ai::Datagram => libc::SOCK_DGRAM,
+        ai::Raw => libc::SOCK_RAW,
+    }
+}
+
+fn socktype_from_c(ty: c_int) -> Option<ai::SocketType> {
+    match ty {
+        t if t == libc::SOCK_STREAM => Some(ai::Stream),
+        t if t == libc::SOCK_DGRAM => Some(ai::Datagram),
+        t if t == libc::SOCK_RAW => Some(ai::Raw),
+        _ => None,
+    }
+}
+
+fn protocol_to_c(proto: ai::Protocol) -> c_int {
+    match proto {
+        ai::TCP => libc::IPPROTO_TCP,
+        ai::UDP => libc::IPPROTO_UDP,
+    }
+}
+
+fn protocol_from_c(proto: c_int) -> Option<ai::Protocol> {
+    match proto {
+        p if p == libc::IPPROTO_TCP => Some(ai::TCP),
+        p if p == libc::IPPROTO_UDP => Some(ai::UDP),
+        _ => None,
+    }
+}
+
+static NI_MAXHOST: uint = 1025;
+
 extern "system" {
     fn getaddrinfo(node: *c_char, service: *c_char,
                    hints: *libc::addrinfo, res: *mut *mut libc::addrinfo) -> c_int;
     fn freeaddrinfo(res: *mut libc::addrinfo);
+    fn getnameinfo(sa: *libc::sockaddr, salen: libc::socklen_t,
+                   host: *mut c_char, hostlen: libc::socklen_t,
+                   serv: *mut c_char, servlen: libc::socklen_t,
+                   flags: c_int) -> c_int;
     #[cfg(not(windows))]
     fn gai_strerror(errcode: c_int) -> *c_char;
     #[cfg(windows)]
@@ -16,6 +16,9 @@ pub use self::select::fd_set;
 pub use self::signal::{sigaction, siginfo, sigset_t};
 pub use self::signal::{SA_ONSTACK, SA_RESTART, SA_RESETHAND, SA_NOCLDSTOP};
 pub use self::signal::{SA_NODEFER, SA_NOCLDWAIT, SA_SIGINFO, SIGCHLD};
+pub use self::termios::{termios, tcflag_t, tcgetattr, tcsetattr, TCSANOW};
+pub use self::termios::{BRKINT, ICRNL, INPCK, ISTRIP, IXON, CS8};
+pub use self::termios::{ECHO, ICANON, IEXTEN, ISIG};
 
 use libc;
 
@@ -44,8 +47,42 @@ pub static MSG_DONTWAIT: libc::c_int = 0x80;
 #[cfg(target_os = "android")]
 pub static MSG_DONTWAIT: libc::c_int = 0x40;
 
+#[cfg(target_os = "macos")]
+#[cfg(target_os = "freebsd")]
+#[cfg(target_os = "linux", target_arch = "mips")]
+pub static TIOCGWINSZ: libc::c_ulong = 0x40087468;
+#[cfg(target_os = "linux", not(target_arch = "mips"))]
+#[cfg(target_os = "android")]
+pub static TIOCGWINSZ: libc::c_ulong = 0x5413;
+
 pub static WNOHANG: libc::c_int = 1;
 
+pub static POLLIN: libc::c_short = 0x1;
+pub static POLLOUT: libc::c_short = 0x4;
+pub static POLLERR: libc::c_short = 0x8;
+pub static POLLHUP: libc::c_short = 0x10;
+pub static POLLNVAL: libc::c_short = 0x20;
+
+#[cfg(target_os = "linux")]
+#[cfg(target_os = "android")]
+pub type nfds_t = libc::c_ulong;
+#[cfg(target_os = "macos")]
+#[cfg(target_os = "freebsd")]
+pub type nfds_t = libc::c_uint;
+
+pub struct pollfd {
+    pub fd: libc::c_int,
+    pub events: libc::c_short,
+    pub revents: libc::c_short,
+}
+
+pub struct winsize {
+    pub ws_row: libc::c_ushort,
+    pub ws_col: libc::c_ushort,
+    pub ws_xpixel: libc::c_ushort,
+    pub ws_ypixel: libc::c_ushort,
+}
+
 extern {
     pub fn gettimeofday(timeval: *mut libc::timeval,
                         tzp: *libc::c_void) -> libc::c_int;
@@ -60,6 +97,8 @@ extern {
                       optval: *mut libc::c_void,
                       optlen: *mut libc::socklen_t) -> libc::c_int;
     pub fn ioctl(fd: libc::c_int, req: libc::c_ulong, ...) -> libc::c_int;
+    pub fn poll(fds: *mut pollfd, nfds: nfds_t,
+                timeout: libc::c_int) -> libc::c_int;
 
 
     pub fn waitpid(pid: libc::pid_t, status: *mut libc::c_int,
@@ -233,3 +272,113 @@ mod signal {
         pub sa_mask: sigset_t,
     }
 }
+
+#[cfg(target_os = "linux")]
+#[cfg(target_os = "android")]
+mod termios {
+    use libc;
+
+    pub type tcflag_t = libc::c_uint;
+
+    pub static BRKINT: tcflag_t = 0o2;
+    pub static INPCK: tcflag_t = 0o20;
+    pub static ISTRIP: tcflag_t = 0o40;
+    pub static ICRNL: tcflag_t = 0o400;
+    pub static IXON: tcflag_t = 0o2000;
+    pub static CS8: tcflag_t = 0o60;
+    pub static ISIG: tcflag_t = 0o1;
+    pub static ICANON: tcflag_t = 0o2;
+    pub static ECHO: tcflag_t = 0o10;
+    #[cfg(not(target_arch = "mips"))]
+    pub static IEXTEN: tcflag_t = 0o100000;
+    #[cfg(target_arch = "mips")]
+    pub static IEXTEN: tcflag_t = 0o400;
+
+    #[cfg(not(target_arch = "mips"))]
+    pub static TCSANOW: libc::c_int = 0;
+    #[cfg(target_arch = "mips")]
+    pub static TCSANOW: libc::c_int = 0x540e;
+
+    #[cfg(target_os = "linux")]
+    pub struct termios {
+        pub c_iflag: tcflag_t,
+        pub c_oflag: tcflag_t,
+        pub c_cflag: tcflag_t,
+        pub c_lflag: tcflag_t,
+        c_line: u8,
+        c_cc: [u8, ..32],
+        c_ispeed: libc::c_uint,
+        c_ospeed: libc::c_uint,
+    }
+
+    #[cfg(target_os = "linux")]
+    extern {
+        pub fn tcgetattr(fd: libc::c_int, termios: *mut termios) -> libc::c_int;
+        pub fn tcsetattr(fd: libc::c_int, actions: libc::c_int,
+                         termios: *termios) -> libc::c_int;
+    }
+
+    // bionic only provides these as inline functions, so call the ioctls they
+    // wrap directly. This is the kernel's definition of the structure.
+    #[cfg(target_os = "android")]
+    pub struct termios {
+        pub c_iflag: tcflag_t,
+        pub c_oflag: tcflag_t,
+        pub c_cflag: tcflag_t,
+        pub c_lflag: tcflag_t,
+        c_line: u8,
+        c_cc: [u8, ..19],
+    }
+
+    #[cfg(target_os = "android")]
+    pub unsafe fn tcgetattr(fd: libc::c_int,
+                            termios: *mut termios) -> libc::c_int {
+        super::ioctl(fd, 0x5401, termios)
+    }
+
+    #[cfg(target_os = "android")]
+    pub unsafe fn tcsetattr(fd: libc::c_int, _actions: libc::c_int,
+                            termios: *termios) -> libc::c_int {
+        super::ioctl(fd, 0x5402, termios)
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[cfg(target_os = "freebsd")]
+mod termios {
+    use libc;
+
+    #[cfg(target_os = "macos")]
+    pub type tcflag_t = libc::c_ulong;
+    #[cfg(target_os = "freebsd")]
+    pub type tcflag_t = libc::c_uint;
+
+    pub static BRKINT: tcflag_t = 0x2;
+    pub static INPCK: tcflag_t = 0x10;
+    pub static ISTRIP: tcflag_t = 0x20;
+    pub static ICRNL: tcflag_t = 0x100;
+    pub static IXON: tcflag_t = 0x200;
+    pub static CS8: tcflag_t = 0x300;
+    pub static ECHO: tcflag_t = 0x8;
+    pub static ISIG: tcflag_t = 0x80;
+    pub static ICANON: tcflag_t = 0x100;
+    pub static IEXTEN: tcflag_t = 0x400;
+
+    pub static TCSANOW: libc::c_int = 0;
+
+    pub struct termios {
+        pub c_iflag: tcflag_t,
+        pub c_oflag: tcflag_t,
+        pub c_cflag: tcflag_t,
+        pub c_lflag: tcflag_t,
+        c_cc: [u8, ..20],
+        c_ispeed: tcflag_t,
+        c_ospeed: tcflag_t,
+    }
+
+    extern {
+        pub fn tcgetattr(fd: libc::c_int, termios: *mut termios) -> libc::c_int;
+        pub fn tcsetattr(fd: libc::c_int, actions: libc::c_int,
+                         termios: *termios) -> libc::c_int;
+    }
+}
@@ -17,9 +17,12 @@ use std::c_str::CString;
 use std::io::IoError;
 use std::io;
 use std::mem;
+use std::ptr;
 use std::rt::rtio;
+use std::unstable::mutex::{StaticNativeMutex, NATIVE_MUTEX_INIT};
 
 use io::{IoResult, retry, keep_going};
+use io::c;
 
 pub type fd_t = libc::c_int;
 
@@ -101,6 +104,12 @@ impl rtio::RtioFileStream for FileDesc {
     fn write(&mut self, buf: &[u8]) -> Result<(), IoError> {
         self.inner_write(buf)
     }
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> Result<int, IoError> {
+        super::readv(self.fd(), bufs).map(|i| i as int)
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> Result<(), IoError> {
+        super::writev(self.fd(), bufs)
+    }
     fn pread(&mut self, buf: &mut [u8], offset: u64) -> Result<int, IoError> {
         match retry(|| unsafe {
             libc::pread(self.fd(), buf.as_ptr() as *libc::c_void,
@@ -170,6 +179,7 @@ impl rtio::RtioFileStream for FileDesc {
             _ => Err(super::last_error()),
         }
     }
+    fn fd(&self) -> c_int { self.inner.fd }
 }
 
 impl rtio::RtioPipe for FileDesc {
@@ -179,6 +189,12 @@ impl rtio::RtioPipe for FileDesc {
     fn write(&mut self, buf: &[u8]) -> Result<(), IoError> {
         self.inner_write(buf)
     }
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> Result<uint, IoError> {
+        super::readv(self.fd(), bufs)
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> Result<(), IoError> {
+        super::writev(self.fd(), bufs)
+    }
     fn clone(&self) -> Box<rtio::RtioPipe:Send> {
         box FileDesc { inner: self.inner.clone() } as Box<rtio::RtioPipe:Send>
     }
@@ -196,22 +212,139 @@ impl rtio::RtioPipe for FileDesc {
     fn set_timeout(&mut self, _t: Option<u64>) {}
     fn set_read_timeout(&mut self, _t: Option<u64>) {}
     fn set_write_timeout(&mut self, _t: Option<u64>) {}
+    fn poll_fd(&self) -> Option<c_int> { Some(self.fd()) }
+}
+
+/// A terminal device, which can be put into raw mode.
+///
+/// Any number of `Tty`s may be open on the same terminal, so the attributes it
+/// had before the first of them entered raw mode are saved once per
+/// descriptor, and only restored once the last one leaves raw mode. A `Tty`
+/// which is dropped while in raw mode leaves it first.
+pub struct Tty {
+    fd: FileDesc,
+    raw: bool,
+}
+
+// The original attributes of each terminal which some `Tty` has put into raw
+// mode, along with how many `Tty`s have done so.
+struct RawTerminal {
+    fd: fd_t,
+    orig: c::termios,
+    raw: uint,
+}
+
+static mut RAW_LOCK: StaticNativeMutex = NATIVE_MUTEX_INIT;
+static mut RAW_TERMINALS: *mut Vec<RawTerminal> = 0 as *mut Vec<RawTerminal>;
+
+impl Tty {
+    pub fn new(fd: fd_t) -> Tty {
+        Tty { fd: FileDesc::new(fd, true), raw: false }
+    }
+
+    fn attrs(&self) -> IoResult<c::termios> {
+        let mut attrs: c::termios = unsafe { mem::zeroed() };
+        match unsafe { c::tcgetattr(self.fd.fd(), &mut attrs) } {
+            0 => Ok(attrs),
+            _ => Err(super::last_error()),
+        }
+    }
+
+    fn set_attrs(&mut self, attrs: &c::termios) -> IoResult<()> {
+        super::mkerr_libc(retry(|| unsafe {
+            c::tcsetattr(self.fd.fd(), c::TCSANOW, attrs)
+        }))
+    }
+
+    fn enter_raw(&mut self) -> IoResult<()> {
+        unsafe {
+            let _guard = RAW_LOCK.lock();
+            if RAW_TERMINALS.is_null() {
+                RAW_TERMINALS = mem::transmute(box Vec::<RawTerminal>::new());
+            }
+            let terminals = &mut *RAW_TERMINALS;
+            let fd = self.fd.fd();
+            match terminals.mut_iter().find(|t| t.fd == fd) {
+                Some(t) => { t.raw += 1; return Ok(()) }
+                None => {}
+            }
+
+            // These are the same flags libuv uses for its raw mode
+            let orig = try!(self.attrs());
+            let mut attrs = orig;
+            attrs.c_iflag &= !(c::BRKINT | c::ICRNL | c::INPCK |
+                               c::ISTRIP | c::IXON);
+            attrs.c_cflag |= c::CS8;
+            attrs.c_lflag &= !(c::ECHO | c::ICANON | c::IEXTEN | c::ISIG);
+            try!(self.set_attrs(&attrs));
+            terminals.push(RawTerminal { fd: fd, orig: orig, raw: 1 });
+            Ok(())
+        }
+    }
+
+    fn leave_raw(&mut self) -> IoResult<()> {
+        unsafe {
+            let _guard = RAW_LOCK.lock();
+            let terminals = &mut *RAW_TERMINALS;
+            let fd = self.fd.fd();
+            let i = terminals.iter().position(|t| t.fd == fd).unwrap();
+            if terminals.get(i).raw > 1 {
+                terminals.get_mut(i).raw -= 1;
+                return Ok(())
+            }
+            let orig = terminals.get(i).orig;
+            try!(self.set_attrs(&orig));
+            terminals.swap_remove(i);
+            Ok(())
+        }
+    }
 }
 
-impl rtio::RtioTTY for FileDesc {
+impl rtio::RtioTTY for Tty {
     fn read(&mut self, buf: &mut [u8]) -> Result<uint, IoError> {
-        self.inner_read(buf)
+        self.fd.inner_read(buf)
     }
     fn write(&mut self, buf: &[u8]) -> Result<(), IoError> {
-        self.inner_write(buf)
+        self.fd.inner_write(buf)
     }
-    fn set_raw(&mut self, _raw: bool) -> Result<(), IoError> {
-        Err(super::unimpl())
+    fn set_raw(&mut self, raw: bool) -> Result<(), IoError> {
+        // already in the requested mode
+        if raw == self.raw { return Ok(()) }
+        if raw {
+            try!(self.enter_raw());
+        } else {
+            try!(self.leave_raw());
+        }
+        self.raw = raw;
+        Ok(())
+    }
+    fn set_echo(&mut self, echo: bool) -> Result<(), IoError> {
+        let mut attrs = try!(self.attrs());
+        if echo {
+            attrs.c_lflag |= c::ECHO;
+        } else {
+            attrs.c_lflag &= !c::ECHO;
+        }
+        self.set_attrs(&attrs)
     }
     fn get_winsize(&mut self) -> Result<(int, int), IoError> {
-        Err(super::unimpl())
+        let mut size: c::winsize = unsafe { mem::zeroed() };
+        match unsafe { c::ioctl(self.fd.fd(), c::TIOCGWINSZ, &mut size) } {
+            -1 => Err(super::last_error()),
+            _ => Ok((size.ws_col as int, size.ws_row as int)),
+        }
+    }
+    fn isatty(&self) -> bool {
+        unsafe { libc::isatty(self.fd.fd()) != 0 }
+    }
+}
+
+impl Drop for Tty {
+    fn drop(&mut self) {
+        if self.raw {
+            let _ = self.leave_raw();
+        }
     }
-    fn isatty(&self) -> bool { false }
 }
 
 impl Drop for Inner {
@@ -325,6 +458,7 @@ impl rtio::RtioFileStream for CFile {
     fn fstat(&mut self) -> IoResult<io::FileStat> {
         self.flush().and_then(|()| self.fd.fstat())
     }
+    fn fd(&self) -> c_int { self.fd.fd() }
 }
 
 impl Drop for CFile {
@@ -339,10 +473,13 @@ pub fn open(path: &CString, fm: io::FileMode, fa: io::FileAccess)
         io::Open => 0,
         io::Append => libc::O_APPEND,
         io::Truncate => libc::O_TRUNC,
+        io::CreateNew => libc::O_CREAT | libc::O_EXCL,
     };
-    // Opening with a write permission must silently create the file.
+    // Opening with a write permission must silently create the file. The mode
+    // is only used when the file is created, which CreateNew also does.
     let (flags, mode) = match fa {
-        io::Read => (flags | libc::O_RDONLY, 0),
+        io::Read => (flags | libc::O_RDONLY,
+                     libc::S_IRUSR | libc::S_IWUSR),
         io::Write => (flags | libc::O_WRONLY | libc::O_CREAT,
                       libc::S_IRUSR | libc::S_IWUSR),
         io::ReadWrite => (flags | libc::O_RDWR | libc::O_CREAT,
@@ -451,6 +588,17 @@ pub fn readlink(p: &CString) -> IoResult<Path> {
     }
 }
 
+pub fn realpath(p: &CString) -> IoResult<Path> {
+    // Passing a null buffer has realpath allocate one of the right size
+    let ret = unsafe { libc::realpath(p.with_ref(|p| p), ptr::mut_null()) };
+    if ret.is_null() { return Err(super::last_error()) }
+    unsafe {
+        let path = Path::new(CString::new(ret, false));
+        libc::free(ret as *mut c_void);
+        Ok(path)
+    }
+}
+
 pub fn symlink(src: &CString, dst: &CString) -> IoResult<()> {
     super::mkerr_libc(retry(|| unsafe {
         libc::symlink(src.with_ref(|p| p), dst.with_ref(|p| p))
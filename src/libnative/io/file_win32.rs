@@ -207,6 +207,7 @@ impl rtio::RtioFileStream for FileDesc {
             _ => Err(super::last_error()),
         }
     }
+    fn fd(&self) -> c_int { self.inner.fd }
 }
 
 impl rtio::RtioPipe for FileDesc {
@@ -245,6 +246,9 @@ impl rtio::RtioTTY for FileDesc {
     fn set_raw(&mut self, _raw: bool) -> Result<(), IoError> {
         Err(super::unimpl())
     }
+    fn set_echo(&mut self, _echo: bool) -> Result<(), IoError> {
+        Err(super::unimpl())
+    }
     fn get_winsize(&mut self) -> Result<(int, int), IoError> {
         Err(super::unimpl())
     }
@@ -275,6 +279,7 @@ pub fn open(path: &CString, fm: io::FileMode, fa: io::FileAccess)
         io::Open => 0,
         io::Append => libc::O_APPEND,
         io::Truncate => libc::O_TRUNC,
+        io::CreateNew => 0,
     };
     let flags = match fa {
         io::Read => flags | libc::O_RDONLY,
@@ -297,6 +302,7 @@ pub fn open(path: &CString, fm: io::FileMode, fa: io::FileAccess)
     let dwCreationDisposition = match (fm, fa) {
         (io::Truncate, io::Read) => libc::TRUNCATE_EXISTING,
         (io::Truncate, _) => libc::CREATE_ALWAYS,
+        (io::CreateNew, _) => libc::CREATE_NEW,
         (io::Open, io::Read) => libc::OPEN_EXISTING,
         (io::Open, _) => libc::OPEN_ALWAYS,
         (io::Append, io::Read) => {
@@ -466,6 +472,47 @@ pub fn readlink(p: &CString) -> IoResult<Path> {
     return ret;
 }
 
+pub fn realpath(p: &CString) -> IoResult<Path> {
+    use io::c::compat::kernel32::GetFinalPathNameByHandleW;
+    // Opening the file follows every symlink on the way to it, and backup
+    // semantics are needed to open directories as well.
+    let handle = unsafe {
+        as_utf16_p(p.as_str().unwrap(), |p| {
+            libc::CreateFileW(p,
+                              0,
+                              libc::FILE_SHARE_READ | libc::FILE_SHARE_WRITE |
+                                  libc::FILE_SHARE_DELETE,
+                              ptr::mut_null(),
+                              libc::OPEN_EXISTING,
+                              libc::FILE_FLAG_BACKUP_SEMANTICS,
+                              ptr::mut_null())
+        })
+    };
+    if handle as int == libc::INVALID_HANDLE_VALUE as int {
+        return Err(super::last_error())
+    }
+    let ret = fill_utf16_buf_and_decode(|buf, sz| unsafe {
+        GetFinalPathNameByHandleW(handle,
+                                  buf as *u16,
+                                  sz - 1,
+                                  libc::VOLUME_NAME_DOS)
+    });
+    // The final path is always in the verbatim form, which is turned back
+    // into the usual form for drives and UNC shares alike.
+    let ret = match ret {
+        Some(ref s) if s.as_slice().starts_with(r"\\?\UNC\") => {
+            Ok(Path::new(format!(r"\\{}", s.as_slice().slice_from(8))))
+        }
+        Some(ref s) if s.as_slice().starts_with(r"\\?\") => {
+            Ok(Path::new(s.as_slice().slice_from(4)))
+        }
+        Some(s) => Ok(Path::new(s)),
+        None => Err(super::last_error()),
+    };
+    assert!(unsafe { libc::CloseHandle(handle) } != 0);
+    return ret;
+}
+
 pub fn symlink(src: &CString, dst: &CString) -> IoResult<()> {
     use io::c::compat::kernel32::CreateSymbolicLinkW;
     super::mkerr_winbool(as_utf16_p(src.as_str().unwrap(), |src| {
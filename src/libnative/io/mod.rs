@@ -24,14 +24,16 @@
 use libc::c_int;
 use libc;
 use std::c_str::CString;
+use std::cmp;
 use std::io;
 use std::io::IoError;
-use std::io::net::ip::SocketAddr;
+use std::io::net::ip::{IpAddr, SocketAddr};
 use std::io::signal::Signum;
 use std::os;
 use std::rt::rtio;
 use std::rt::rtio::{RtioTcpStream, RtioTcpListener, RtioUdpSocket};
 use std::rt::rtio::{RtioUnixListener, RtioPipe, RtioFileStream, RtioProcess};
+use std::rt::rtio::RtioUnixDatagram;
 use std::rt::rtio::{RtioSignal, RtioTTY, CloseBehavior, RtioTimer, ProcessConfig};
 use ai = std::io::net::addrinfo;
 
@@ -69,6 +71,10 @@ pub mod timer;
 #[path = "pipe_unix.rs"]
 pub mod pipe;
 
+#[cfg(unix)]
+#[path = "signal_unix.rs"]
+pub mod signal;
+
 #[cfg(windows)]
 #[path = "pipe_win32.rs"]
 pub mod pipe;
@@ -90,6 +96,16 @@ fn last_error() -> IoError {
     IoError::last_error()
 }
 
+#[cfg(unix)]
+fn tty(fd: c_int) -> Box<RtioTTY:Send> {
+    box file::Tty::new(fd) as Box<RtioTTY:Send>
+}
+
+#[cfg(windows)]
+fn tty(fd: c_int) -> Box<RtioTTY:Send> {
+    box file::FileDesc::new(fd, true) as Box<RtioTTY:Send>
+}
+
 // unix has nonzero values as errors
 fn mkerr_libc(ret: libc::c_int) -> IoResult<()> {
     if ret != 0 {
@@ -149,6 +165,79 @@ fn keep_going(data: &[u8], f: |*u8, uint| -> i64) -> i64 {
     return (origamt - amt) as i64;
 }
 
+// The smallest IOV_MAX of the supported platforms; no more than this many
+// buffers are handed to a single readv/writev call.
+#[cfg(unix)]
+static IOV_MAX: uint = 1024;
+
+#[cfg(unix)]
+fn iovec(ptr: *u8, len: uint) -> libc::iovec {
+    libc::iovec { iov_base: ptr as *mut libc::c_void, iov_len: len as libc::size_t }
+}
+
+/// Reads into as many of `bufs` as a single `readv` call will fill, in order.
+#[cfg(unix)]
+fn readv(fd: c_int, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+    let iovs: Vec<libc::iovec> = bufs.iter().take(IOV_MAX).map(|buf| {
+        iovec(buf.as_ptr(), buf.len())
+    }).collect();
+    let ret = retry(|| unsafe {
+        libc::readv(fd, iovs.as_ptr(), iovs.len() as c_int) as c_int
+    });
+    if ret == 0 && iovs.iter().any(|iov| iov.iov_len > 0) {
+        Err(io::standard_error(io::EndOfFile))
+    } else if ret < 0 {
+        Err(last_error())
+    } else {
+        Ok(ret as uint)
+    }
+}
+
+/// Writes the entirety of each of `bufs`, in order, with as few `writev` calls
+/// as possible.
+#[cfg(unix)]
+fn writev(fd: c_int, bufs: &[&[u8]]) -> IoResult<()> {
+    let mut iovs: Vec<libc::iovec> = bufs.iter().filter(|buf| {
+        !buf.is_empty()
+    }).map(|buf| iovec(buf.as_ptr(), buf.len())).collect();
+
+    // `start` is the first iovec which still has data left to be written
+    let mut start = 0;
+    let mut written = 0;
+    while start < iovs.len() {
+        let cnt = cmp::min(iovs.len() - start, IOV_MAX);
+        let ret = retry(|| unsafe {
+            libc::writev(fd, iovs.slice_from(start).as_ptr(),
+                         cnt as c_int) as c_int
+        });
+        if ret < 0 {
+            return Err(last_error())
+        } else if ret == 0 {
+            return Err(io::standard_error(io::ShortWrite(written)))
+        }
+        written += ret as uint;
+
+        // skip past everything that was written, which may well end partway
+        // through an iovec
+        let mut amt = ret as uint;
+        while amt > 0 {
+            let iov = iovs.get_mut(start);
+            let len = iov.iov_len as uint;
+            if amt >= len {
+                amt -= len;
+                start += 1;
+            } else {
+                iov.iov_base = unsafe {
+                    (iov.iov_base as *u8).offset(amt as int) as *mut libc::c_void
+                };
+                iov.iov_len = (len - amt) as libc::size_t;
+                amt = 0;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Implementation of rt::rtio's IoFactory trait to generate handles to the
 /// native I/O functionality.
 pub struct IoFactory {
@@ -170,9 +259,9 @@ impl rtio::IoFactory for IoFactory {
             box s as Box<RtioTcpStream:Send>
         })
     }
-    fn tcp_bind(&mut self, addr: SocketAddr)
+    fn tcp_bind(&mut self, addr: SocketAddr, reuse_addr: bool)
                 -> IoResult<Box<RtioTcpListener:Send>> {
-        net::TcpListener::bind(addr).map(|s| {
+        net::TcpListener::bind(addr, reuse_addr).map(|s| {
             box s as Box<RtioTcpListener:Send>
         })
     }
@@ -192,10 +281,25 @@ impl rtio::IoFactory for IoFactory {
             box s as Box<RtioPipe:Send>
         })
     }
+    #[cfg(unix)]
+    fn unix_datagram(&mut self, path: Option<&CString>)
+                     -> IoResult<Box<RtioUnixDatagram:Send>> {
+        pipe::UnixDatagram::bind(path).map(|s| {
+            box s as Box<RtioUnixDatagram:Send>
+        })
+    }
+    #[cfg(windows)]
+    fn unix_datagram(&mut self, _path: Option<&CString>)
+                     -> IoResult<Box<RtioUnixDatagram:Send>> {
+        Err(unimpl())
+    }
     fn get_host_addresses(&mut self, host: Option<&str>, servname: Option<&str>,
                           hint: Option<ai::Hint>) -> IoResult<Vec<ai::Info>> {
         addrinfo::GetAddrInfoRequest::run(host, servname, hint)
     }
+    fn get_address_name(&mut self, addr: IpAddr) -> IoResult<String> {
+        addrinfo::GetNameInfoRequest::run(addr)
+    }
 
     // filesystem operations
     fn fs_from_raw_fd(&mut self, fd: c_int, close: CloseBehavior)
@@ -242,6 +346,9 @@ impl rtio::IoFactory for IoFactory {
     fn fs_readlink(&mut self, path: &CString) -> IoResult<Path> {
         file::readlink(path)
     }
+    fn fs_realpath(&mut self, path: &CString) -> IoResult<Path> {
+        file::realpath(path)
+    }
     fn fs_symlink(&mut self, src: &CString, dst: &CString) -> IoResult<()> {
         file::symlink(src, dst)
     }
@@ -276,7 +383,7 @@ impl rtio::IoFactory for IoFactory {
     fn tty_open(&mut self, fd: c_int, _readable: bool)
                 -> IoResult<Box<RtioTTY:Send>> {
         if unsafe { libc::isatty(fd) } != 0 {
-            Ok(box file::FileDesc::new(fd, true) as Box<RtioTTY:Send>)
+            Ok(tty(fd))
         } else {
             Err(IoError {
                 kind: io::MismatchedFileTypeForOperation,
@@ -285,8 +392,21 @@ impl rtio::IoFactory for IoFactory {
             })
         }
     }
+    #[cfg(unix)]
+    fn signal(&mut self, signum: Signum, channel: Sender<Signum>)
+              -> IoResult<Box<RtioSignal:Send>> {
+        signal::Signal::new(signum, channel).map(|s| {
+            box s as Box<RtioSignal:Send>
+        })
+    }
+    #[cfg(windows)]
     fn signal(&mut self, _signal: Signum, _channel: Sender<Signum>)
               -> IoResult<Box<RtioSignal:Send>> {
         Err(unimpl())
     }
+    #[cfg(unix)]
+    fn poll(&mut self, fds: &mut [rtio::PollFd], timeout_ms: Option<u64>)
+            -> IoResult<uint> {
+        util::poll(fds, timeout_ms)
+    }
 }
@@ -14,6 +14,7 @@ use std::io::net::ip;
 use std::io;
 use std::mem;
 use std::rt::rtio;
+use std::rt::rtio::RtioTcpStream;
 use std::unstable::mutex;
 
 use super::{IoResult, retry, keep_going};
@@ -67,7 +68,7 @@ fn ip_to_inaddr(ip: ip::IpAddr) -> InAddr {
     }
 }
 
-fn addr_to_sockaddr(addr: ip::SocketAddr) -> (libc::sockaddr_storage, uint) {
+pub fn addr_to_sockaddr(addr: ip::SocketAddr) -> (libc::sockaddr_storage, uint) {
     unsafe {
         let storage: libc::sockaddr_storage = mem::zeroed();
         let len = match ip_to_inaddr(addr.ip) {
@@ -118,6 +119,18 @@ fn setsockopt<T>(fd: sock_t, opt: libc::c_int, val: libc::c_int,
     }
 }
 
+fn set_ttl(fd: sock_t, ttl: int) -> IoResult<()> {
+    match try!(sockname(fd, libc::getsockname)).ip {
+        ip::Ipv4Addr(..) => {
+            setsockopt(fd, libc::IPPROTO_IP, libc::IP_TTL, ttl as libc::c_int)
+        }
+        ip::Ipv6Addr(..) => {
+            setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS,
+                       ttl as libc::c_int)
+        }
+    }
+}
+
 pub fn getsockopt<T: Copy>(fd: sock_t, opt: libc::c_int,
                            val: libc::c_int) -> IoResult<T> {
     unsafe {
@@ -366,6 +379,18 @@ impl rtio::RtioTcpStream for TcpStream {
             Err(e) => Err(e)
         }
     }
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        match readv(self.fd(), self.read_deadline, bufs) {
+            Some(ret) => ret,
+            None => rtio::readv_first(bufs, |buf| self.read(buf)),
+        }
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        match writev(self.fd(), self.write_deadline, bufs) {
+            Some(ret) => ret,
+            None => rtio::writev_each(bufs, |buf| self.write(buf)),
+        }
+    }
     fn peer_name(&mut self) -> IoResult<ip::SocketAddr> {
         sockname(self.fd(), libc::getpeername)
     }
@@ -381,6 +406,9 @@ impl rtio::RtioTcpStream for TcpStream {
     fn letdie(&mut self) -> IoResult<()> {
         self.set_keepalive(None)
     }
+    fn time_to_live(&mut self, ttl: int) -> IoResult<()> {
+        set_ttl(self.fd(), ttl)
+    }
 
     fn clone(&self) -> Box<rtio::RtioTcpStream:Send> {
         box TcpStream {
@@ -408,6 +436,8 @@ impl rtio::RtioTcpStream for TcpStream {
     fn set_write_timeout(&mut self, timeout: Option<u64>) {
         self.write_deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
     }
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<libc::c_int> { Some(self.fd()) }
 }
 
 impl rtio::RtioSocket for TcpStream {
@@ -436,7 +466,8 @@ pub struct TcpListener {
 }
 
 impl TcpListener {
-    pub fn bind(addr: ip::SocketAddr) -> IoResult<TcpListener> {
+    pub fn bind(addr: ip::SocketAddr,
+                reuse_addr: bool) -> IoResult<TcpListener> {
         let fd = try!(socket(addr, libc::SOCK_STREAM));
         let ret = TcpListener { inner: Inner::new(fd) };
 
@@ -447,7 +478,7 @@ impl TcpListener {
         // On platforms with Berkeley-derived sockets, this allows
         // to quickly rebind a socket, without needing to wait for
         // the OS to clean up the previous one.
-        if cfg!(unix) {
+        if reuse_addr {
             try!(setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR,
                             1 as libc::c_int));
         }
@@ -527,6 +558,8 @@ impl rtio::RtioTcpAcceptor for TcpAcceptor {
     fn set_timeout(&mut self, timeout: Option<u64>) {
         self.deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
     }
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<libc::c_int> { Some(self.fd()) }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -566,8 +599,16 @@ impl UdpSocket {
     }
 
     pub fn set_multicast_loop(&mut self, on: bool) -> IoResult<()> {
-        setsockopt(self.fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_LOOP,
-                   on as libc::c_int)
+        match try!(sockname(self.fd(), libc::getsockname)).ip {
+            ip::Ipv4Addr(..) => {
+                setsockopt(self.fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_LOOP,
+                           on as libc::c_int)
+            }
+            ip::Ipv6Addr(..) => {
+                setsockopt(self.fd(), libc::IPPROTO_IPV6,
+                           libc::IPV6_MULTICAST_LOOP, on as libc::c_int)
+            }
+        }
     }
 
     pub fn set_membership(&mut self, addr: ip::IpAddr,
@@ -696,11 +737,19 @@ impl rtio::RtioUdpSocket for UdpSocket {
     }
 
     fn multicast_time_to_live(&mut self, ttl: int) -> IoResult<()> {
-        setsockopt(self.fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_TTL,
-                   ttl as libc::c_int)
+        match try!(sockname(self.fd(), libc::getsockname)).ip {
+            ip::Ipv4Addr(..) => {
+                setsockopt(self.fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_TTL,
+                           ttl as libc::c_int)
+            }
+            ip::Ipv6Addr(..) => {
+                setsockopt(self.fd(), libc::IPPROTO_IPV6,
+                           libc::IPV6_MULTICAST_HOPS, ttl as libc::c_int)
+            }
+        }
     }
     fn time_to_live(&mut self, ttl: int) -> IoResult<()> {
-        setsockopt(self.fd(), libc::IPPROTO_IP, libc::IP_TTL, ttl as libc::c_int)
+        set_ttl(self.fd(), ttl)
     }
 
     fn hear_broadcasts(&mut self) -> IoResult<()> {
@@ -889,3 +938,35 @@ pub fn write<T>(fd: sock_t,
         Ok(written)
     }
 }
+
+// Vectored reads and writes have no equivalent of MSG_DONTWAIT, so they are
+// only ever issued for sockets which will be read or written in blocking mode:
+// those without a deadline, and only on linux (where, as explained above,
+// sockets are never switched into nonblocking mode). Otherwise `None` is
+// returned, and the caller should fall back to plain reads and writes.
+
+#[cfg(target_os = "linux")]
+pub fn readv(fd: sock_t, deadline: u64,
+             bufs: &mut [&mut [u8]]) -> Option<IoResult<uint>> {
+    if deadline != 0 { return None }
+    Some(super::readv(fd, bufs))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn readv(_fd: sock_t, _deadline: u64,
+             _bufs: &mut [&mut [u8]]) -> Option<IoResult<uint>> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub fn writev(fd: sock_t, deadline: u64,
+              bufs: &[&[u8]]) -> Option<IoResult<()>> {
+    if deadline != 0 { return None }
+    Some(super::writev(fd, bufs))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn writev(_fd: sock_t, _deadline: u64,
+              _bufs: &[&[u8]]) -> Option<IoResult<()>> {
+    None
+}
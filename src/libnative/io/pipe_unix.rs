@@ -10,11 +10,13 @@
 
 use alloc::arc::Arc;
 use libc;
-use std::c_str::CString;
+use std::c_str::{CString, ToCStr};
+use std::cmp;
 use std::intrinsics;
 use std::io;
 use std::mem;
 use std::rt::rtio;
+use std::rt::rtio::RtioPipe;
 use std::unstable::mutex;
 
 use super::{IoResult, retry};
@@ -50,11 +52,54 @@ fn addr_to_sockaddr_un(addr: &CString) -> IoResult<(libc::sockaddr_storage, uint
         *slot = value;
     }
 
+    // An abstract address is named by the bytes following its leading null,
+    // and its length does not include a null terminator.
+    if is_abstract(addr) {
+        s.sun_path[0] = 0;
+        return Ok((storage, mem::size_of::<libc::sa_family_t>() + len));
+    }
+
     // count the null terminator
     let len = mem::size_of::<libc::sa_family_t>() + len + 1;
     return Ok((storage, len));
 }
 
+fn sockaddr_to_addr(storage: &libc::sockaddr_storage,
+                    len: uint) -> Option<CString> {
+    let s: &libc::sockaddr_un = unsafe { mem::transmute(storage) };
+    let offset = mem::size_of::<libc::sockaddr_un>() - s.sun_path.len();
+
+    // unnamed sockets (such as unbound datagram sockets) have no path at all
+    if len <= offset { return None }
+    let path = s.sun_path.slice_to(cmp::min(len - offset, s.sun_path.len()));
+    let bytes: Vec<u8> = if path[0] == 0 {
+        // An abstract name is all of the bytes up to the length of the
+        // address, nulls included. A CString can't hold those, so like
+        // /proc/net/unix they're shown as '@', just as the leading one is.
+        path.iter().map(|&b| if b == 0 { '@' as u8 } else { b as u8 }).collect()
+    } else {
+        path.iter().take_while(|b| **b != 0).map(|&b| b as u8).collect()
+    };
+    Some(bytes.as_slice().to_c_str())
+}
+
+// On Linux, a path beginning with '@' names a socket in the abstract
+// namespace, which has no presence in the filesystem. The '@' stands in for
+// the leading null byte that `sun_path` actually contains.
+#[cfg(target_os = "linux")]
+fn is_abstract(addr: &CString) -> bool {
+    addr.as_bytes_no_nul().head() == Some(&('@' as u8))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_abstract(_addr: &CString) -> bool { false }
+
+fn unlink(addr: &CString) {
+    if !is_abstract(addr) {
+        unsafe { let _ = libc::unlink(addr.with_ref(|p| p)); }
+    }
+}
+
 struct Inner {
     fd: fd_t,
     lock: mutex::NativeMutex,
@@ -175,6 +220,20 @@ impl rtio::RtioPipe for UnixStream {
         }
     }
 
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        match net::readv(self.fd(), self.read_deadline, bufs) {
+            Some(ret) => ret,
+            None => rtio::readv_first(bufs, |buf| self.read(buf)),
+        }
+    }
+
+    fn writev(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        match net::writev(self.fd(), self.write_deadline, bufs) {
+            Some(ret) => ret,
+            None => rtio::writev_each(bufs, |buf| self.write(buf)),
+        }
+    }
+
     fn clone(&self) -> Box<rtio::RtioPipe:Send> {
         box UnixStream::new(self.inner.clone()) as Box<rtio::RtioPipe:Send>
     }
@@ -196,6 +255,7 @@ impl rtio::RtioPipe for UnixStream {
     fn set_write_timeout(&mut self, timeout: Option<u64>) {
         self.write_deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
     }
+    fn poll_fd(&self) -> Option<libc::c_int> { Some(self.fd()) }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -266,6 +326,7 @@ impl rtio::RtioUnixAcceptor for UnixAcceptor {
     fn set_timeout(&mut self, timeout: Option<u64>) {
         self.deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
     }
+    fn poll_fd(&self) -> Option<libc::c_int> { Some(self.fd()) }
 }
 
 impl Drop for UnixListener {
@@ -273,8 +334,137 @@ impl Drop for UnixListener {
         // Unlink the path to the socket to ensure that it doesn't linger. We're
         // careful to unlink the path before we close the file descriptor to
         // prevent races where we unlink someone else's path.
-        unsafe {
-            let _ = libc::unlink(self.path.with_ref(|p| p));
+        unlink(&self.path);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Unix Datagrams
+////////////////////////////////////////////////////////////////////////////////
+
+struct DatagramInner {
+    sock: Inner,
+    path: Option<CString>,
+}
+
+impl Drop for DatagramInner {
+    fn drop(&mut self) {
+        // As with listeners, unlink the bound path before the descriptor is
+        // closed.
+        match self.path {
+            Some(ref path) => unlink(path),
+            None => {}
         }
     }
 }
+
+pub struct UnixDatagram {
+    inner: Arc<DatagramInner>,
+    read_deadline: u64,
+    write_deadline: u64,
+}
+
+impl UnixDatagram {
+    pub fn bind(addr: Option<&CString>) -> IoResult<UnixDatagram> {
+        let sock = match addr {
+            Some(addr) => try!(bind(addr, libc::SOCK_DGRAM)),
+            None => Inner::new(try!(unix_socket(libc::SOCK_DGRAM))),
+        };
+        let inner = DatagramInner { sock: sock, path: addr.map(|a| a.clone()) };
+        Ok(UnixDatagram::new(Arc::new(inner)))
+    }
+
+    fn new(inner: Arc<DatagramInner>) -> UnixDatagram {
+        UnixDatagram {
+            inner: inner,
+            read_deadline: 0,
+            write_deadline: 0,
+        }
+    }
+
+    fn fd(&self) -> fd_t { self.inner.sock.fd }
+
+    #[cfg(target_os = "linux")]
+    fn lock_nonblocking(&self) {}
+
+    #[cfg(not(target_os = "linux"))]
+    fn lock_nonblocking<'a>(&'a self) -> net::Guard<'a> {
+        let ret = net::Guard {
+            fd: self.fd(),
+            guard: unsafe { self.inner.sock.lock.lock() },
+        };
+        assert!(util::set_nonblocking(self.fd(), true).is_ok());
+        ret
+    }
+}
+
+impl rtio::RtioUnixDatagram for UnixDatagram {
+    fn recvfrom(&mut self,
+                buf: &mut [u8]) -> IoResult<(uint, Option<CString>)> {
+        let fd = self.fd();
+        let mut storage: libc::sockaddr_storage = unsafe { intrinsics::init() };
+        let storagep = &mut storage as *mut _ as *mut libc::sockaddr;
+        let mut addrlen: libc::socklen_t =
+                mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        let dolock = || self.lock_nonblocking();
+        let doread = |nb| unsafe {
+            let flags = if nb {c::MSG_DONTWAIT} else {0};
+            libc::recvfrom(fd,
+                           buf.as_mut_ptr() as *mut libc::c_void,
+                           buf.len() as libc::size_t,
+                           flags,
+                           storagep,
+                           &mut addrlen) as libc::c_int
+        };
+        let n = try!(net::read(fd, self.read_deadline, dolock, doread));
+        Ok((n, sockaddr_to_addr(&storage, addrlen as uint)))
+    }
+
+    fn sendto(&mut self, buf: &[u8], dst: &CString) -> IoResult<()> {
+        let (dst, dstlen) = try!(addr_to_sockaddr_un(dst));
+        let dstp = &dst as *_ as *libc::sockaddr;
+        let dstlen = dstlen as libc::socklen_t;
+
+        let fd = self.fd();
+        let dolock = || self.lock_nonblocking();
+        let dowrite = |nb, buf: *u8, len: uint| unsafe {
+            let flags = if nb {c::MSG_DONTWAIT} else {0};
+            libc::sendto(fd,
+                         buf as *libc::c_void,
+                         len as libc::size_t,
+                         flags,
+                         dstp,
+                         dstlen) as i64
+        };
+
+        let n = try!(net::write(fd, self.write_deadline, buf, false,
+                                dolock, dowrite));
+        if n != buf.len() {
+            Err(io::IoError {
+                kind: io::ShortWrite(n),
+                desc: "couldn't send entire packet at once",
+                detail: None,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn clone(&self) -> Box<rtio::RtioUnixDatagram:Send> {
+        box UnixDatagram::new(self.inner.clone())
+            as Box<rtio::RtioUnixDatagram:Send>
+    }
+
+    fn set_timeout(&mut self, timeout: Option<u64>) {
+        let deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
+        self.read_deadline = deadline;
+        self.write_deadline = deadline;
+    }
+    fn set_read_timeout(&mut self, timeout: Option<u64>) {
+        self.read_deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
+    }
+    fn set_write_timeout(&mut self, timeout: Option<u64>) {
+        self.write_deadline = timeout.map(|a| ::io::timer::now() + a).unwrap_or(0);
+    }
+}
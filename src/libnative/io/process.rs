@@ -74,15 +74,20 @@ impl Process {
             return Err(super::unimpl());
         }
 
-        fn get_io(io: p::StdioContainer, ret: &mut Vec<Option<file::FileDesc>>)
+        // `fd` is the stdio file descriptor being configured, which is also the
+        // descriptor inherited for `Inherit`
+        fn get_io(io: p::Stdio, fd: c_int,
+                  ret: &mut Vec<Option<file::FileDesc>>)
             -> (Option<os::Pipe>, c_int)
         {
             match io {
-                p::Ignored => { ret.push(None); (None, -1) }
-                p::InheritFd(fd) => { ret.push(None); (None, fd) }
-                p::CreatePipe(readable, _writable) => {
+                p::Inherit => { ret.push(None); (None, fd) }
+                p::Null => { ret.push(None); (None, -1) }
+                p::Fd(fd) => { ret.push(None); (None, fd) }
+                p::Piped => {
                     let pipe = os::pipe();
-                    let (theirs, ours) = if readable {
+                    // only stdin is read by the child
+                    let (theirs, ours) = if fd == 0 {
                         (pipe.input, pipe.out)
                     } else {
                         (pipe.out, pipe.input)
@@ -94,9 +99,14 @@ impl Process {
         }
 
         let mut ret_io = Vec::new();
-        let (in_pipe, in_fd) = get_io(cfg.stdin, &mut ret_io);
-        let (out_pipe, out_fd) = get_io(cfg.stdout, &mut ret_io);
-        let (err_pipe, err_fd) = get_io(cfg.stderr, &mut ret_io);
+        let (in_pipe, in_fd) = get_io(cfg.stdin, 0, &mut ret_io);
+        let (out_pipe, out_fd) = get_io(cfg.stdout, 1, &mut ret_io);
+        let (err_pipe, err_fd) = if cfg.stderr_to_stdout {
+            ret_io.push(None);
+            (None, out_fd)
+        } else {
+            get_io(cfg.stderr, 2, &mut ret_io)
+        };
 
         let res = spawn_process_os(cfg, in_fd, out_fd, err_fd);
 
@@ -724,6 +734,10 @@ fn with_envp<T>(env: Option<&[(CString, CString)]>, cb: |*mut c_void| -> T) -> T
                 blk.push(0);
             }
 
+            // an empty block still needs both of its terminating nulls
+            if env.is_empty() {
+                blk.push(0);
+            }
             blk.push(0);
 
             cb(blk.as_mut_ptr() as *mut c_void)
@@ -853,11 +867,6 @@ fn waitpid(pid: pid_t, deadline: u64) -> IoResult<p::ProcessExit> {
 
 #[cfg(unix)]
 fn waitpid(pid: pid_t, deadline: u64) -> IoResult<p::ProcessExit> {
-    use std::cmp;
-    use std::comm;
-
-    static mut WRITE_FD: libc::c_int = 0;
-
     let mut status = 0 as c_int;
     if deadline == 0 {
         return match retry(|| unsafe { c::waitpid(pid, &mut status, 0) }) {
@@ -904,7 +913,7 @@ fn waitpid(pid: pid_t, deadline: u64) -> IoResult<p::ProcessExit> {
     // The actual communication between the helper thread and this thread is
     // quite simple, just a channel moving data around.
 
-    unsafe { HELPER.boot(register_sigchld, waitpid_helper) }
+    boot_sigchld_helper();
 
     match waitpid_nowait(pid) {
         Some(ret) => return Ok(ret),
@@ -917,179 +926,202 @@ fn waitpid(pid: pid_t, deadline: u64) -> IoResult<p::ProcessExit> {
         Ok(e) => Ok(e),
         Err(()) => Err(util::timeout("wait timed out")),
     };
+}
 
-    // Register a new SIGCHLD handler, returning the reading half of the
-    // self-pipe plus the old handler registered (return value of sigaction).
-    fn register_sigchld() -> (libc::c_int, c::sigaction) {
-        unsafe {
-            let mut old: c::sigaction = mem::zeroed();
-            let mut new: c::sigaction = mem::zeroed();
-            new.sa_handler = sigchld_handler;
-            new.sa_flags = c::SA_NOCLDSTOP;
-            assert_eq!(c::sigaction(c::SIGCHLD, &new, &mut old), 0);
-
-            let mut pipes = [0, ..2];
-            assert_eq!(libc::pipe(pipes.as_mut_ptr()), 0);
-            util::set_nonblocking(pipes[0], true).unwrap();
-            util::set_nonblocking(pipes[1], true).unwrap();
-            WRITE_FD = pipes[1];
-            (pipes[0], old)
-        }
+#[cfg(unix)]
+static mut WRITE_FD: libc::c_int = 0;
+
+/// Boots the helper thread which owns the SIGCHLD handler of the process, if
+/// it isn't running yet. The handler stays installed for as long as the
+/// helper thread runs, which is until the runtime shuts down, and it also
+/// delivers SIGCHLD to the signal module's registrations for it.
+#[cfg(unix)]
+pub fn boot_sigchld_helper() {
+    unsafe { HELPER.boot(register_sigchld, waitpid_helper) }
+}
+
+// Register a new SIGCHLD handler, returning the reading half of the
+// self-pipe plus the old handler registered (return value of sigaction).
+#[cfg(unix)]
+fn register_sigchld() -> (libc::c_int, c::sigaction) {
+    unsafe {
+        let mut old: c::sigaction = mem::zeroed();
+        let mut new: c::sigaction = mem::zeroed();
+        new.sa_handler = sigchld_handler;
+        new.sa_flags = c::SA_NOCLDSTOP;
+        assert_eq!(c::sigaction(c::SIGCHLD, &new, &mut old), 0);
+
+        let mut pipes = [0, ..2];
+        assert_eq!(libc::pipe(pipes.as_mut_ptr()), 0);
+        util::set_nonblocking(pipes[0], true).unwrap();
+        util::set_nonblocking(pipes[1], true).unwrap();
+        WRITE_FD = pipes[1];
+        (pipes[0], old)
     }
+}
 
-    // Helper thread for processing SIGCHLD messages
-    fn waitpid_helper(input: libc::c_int,
-                      messages: Receiver<Req>,
-                      (read_fd, old): (libc::c_int, c::sigaction)) {
-        util::set_nonblocking(input, true).unwrap();
-        let mut set: c::fd_set = unsafe { mem::zeroed() };
-        let mut tv: libc::timeval;
-        let mut active = Vec::<(libc::pid_t, Sender<p::ProcessExit>, u64)>::new();
-        let max = cmp::max(input, read_fd) + 1;
-
-        'outer: loop {
-            // Figure out the timeout of our syscall-to-happen. If we're waiting
-            // for some processes, then they'll have a timeout, otherwise we
-            // wait indefinitely for a message to arrive.
-            //
-            // FIXME: sure would be nice to not have to scan the entire array
-            let min = active.iter().map(|a| *a.ref2()).enumerate().min_by(|p| {
-                p.val1()
-            });
-            let (p, idx) = match min {
-                Some((idx, deadline)) => {
-                    let now = ::io::timer::now();
-                    let ms = if now < deadline {deadline - now} else {0};
-                    tv = util::ms_to_timeval(ms);
-                    (&tv as *_, idx)
-                }
-                None => (ptr::null(), -1),
-            };
+// Helper thread for processing SIGCHLD messages
+#[cfg(unix)]
+fn waitpid_helper(input: libc::c_int,
+                  messages: Receiver<Req>,
+                  (read_fd, old): (libc::c_int, c::sigaction)) {
+    use std::cmp;
+    use std::comm;
 
-            // Wait for something to happen
-            c::fd_set(&mut set, input);
-            c::fd_set(&mut set, read_fd);
-            match unsafe { c::select(max, &set, ptr::null(), ptr::null(), p) } {
-                // interrupted, retry
-                -1 if os::errno() == libc::EINTR as int => continue,
+    util::set_nonblocking(input, true).unwrap();
+    let mut set: c::fd_set = unsafe { mem::zeroed() };
+    let mut tv: libc::timeval;
+    let mut active = Vec::<(libc::pid_t, Sender<p::ProcessExit>, u64)>::new();
+    let max = cmp::max(input, read_fd) + 1;
+
+    'outer: loop {
+        // Figure out the timeout of our syscall-to-happen. If we're waiting
+        // for some processes, then they'll have a timeout, otherwise we
+        // wait indefinitely for a message to arrive.
+        //
+        // FIXME: sure would be nice to not have to scan the entire array
+        let min = active.iter().map(|a| *a.ref2()).enumerate().min_by(|p| {
+            p.val1()
+        });
+        let (p, idx) = match min {
+            Some((idx, deadline)) => {
+                let now = ::io::timer::now();
+                let ms = if now < deadline {deadline - now} else {0};
+                tv = util::ms_to_timeval(ms);
+                (&tv as *_, idx)
+            }
+            None => (ptr::null(), -1),
+        };
 
-                // We read something, break out and process
-                1 | 2 => {}
+        // Wait for something to happen
+        c::fd_set(&mut set, input);
+        c::fd_set(&mut set, read_fd);
+        match unsafe { c::select(max, &set, ptr::null(), ptr::null(), p) } {
+            // interrupted, retry
+            -1 if os::errno() == libc::EINTR as int => continue,
 
-                // Timeout, the pending request is removed
-                0 => {
-                    drop(active.remove(idx));
-                    continue
-                }
+            // We read something, break out and process
+            1 | 2 => {}
 
-                n => fail!("error in select {} ({})", os::errno(), n),
+            // Timeout, the pending request is removed
+            0 => {
+                drop(active.remove(idx));
+                continue
             }
 
-            // Process any pending messages
-            if drain(input) {
-                loop {
-                    match messages.try_recv() {
-                        Ok(NewChild(pid, tx, deadline)) => {
-                            active.push((pid, tx, deadline));
-                        }
-                        Err(comm::Disconnected) => {
-                            assert!(active.len() == 0);
-                            break 'outer;
-                        }
-                        Err(comm::Empty) => break,
+            n => fail!("error in select {} ({})", os::errno(), n),
+        }
+
+        // Process any pending messages
+        if drain(input) {
+            loop {
+                match messages.try_recv() {
+                    Ok(NewChild(pid, tx, deadline)) => {
+                        active.push((pid, tx, deadline));
+                    }
+                    Err(comm::Disconnected) => {
+                        assert!(active.len() == 0);
+                        break 'outer;
                     }
+                    Err(comm::Empty) => break,
                 }
             }
+        }
 
-            // If a child exited (somehow received SIGCHLD), then poll all
-            // children to see if any of them exited.
-            //
-            // We also attempt to be responsible netizens when dealing with
-            // SIGCHLD by invoking any previous SIGCHLD handler instead of just
-            // ignoring any previous SIGCHLD handler. Note that we don't provide
-            // a 1:1 mapping of our handler invocations to the previous handler
-            // invocations because we drain the `read_fd` entirely. This is
-            // probably OK because the kernel is already allowed to coalesce
-            // simultaneous signals, we're just doing some extra coalescing.
-            //
-            // Another point of note is that this likely runs the signal handler
-            // on a different thread than the one that received the signal. I
-            // *think* this is ok at this time.
-            //
-            // The main reason for doing this is to allow stdtest to run native
-            // tests as well. Both libgreen and libnative are running around
-            // with process timeouts, but libgreen should get there first
-            // (currently libuv doesn't handle old signal handlers).
-            if drain(read_fd) {
-                let i: uint = unsafe { mem::transmute(old.sa_handler) };
-                if i != 0 {
-                    assert!(old.sa_flags & c::SA_SIGINFO == 0);
-                    (old.sa_handler)(c::SIGCHLD);
-                }
-
-                // FIXME: sure would be nice to not have to scan the entire
-                //        array...
-                active.retain(|&(pid, ref tx, _)| {
-                    match waitpid_nowait(pid) {
-                        Some(msg) => { tx.send(msg); false }
-                        None => true,
-                    }
-                });
+        // If a child exited (somehow received SIGCHLD), then poll all
+        // children to see if any of them exited.
+        //
+        // We also attempt to be responsible netizens when dealing with
+        // SIGCHLD by invoking any previous SIGCHLD handler instead of just
+        // ignoring any previous SIGCHLD handler. Note that we don't provide
+        // a 1:1 mapping of our handler invocations to the previous handler
+        // invocations because we drain the `read_fd` entirely. This is
+        // probably OK because the kernel is already allowed to coalesce
+        // simultaneous signals, we're just doing some extra coalescing.
+        //
+        // Another point of note is that this likely runs the signal handler
+        // on a different thread than the one that received the signal. I
+        // *think* this is ok at this time.
+        //
+        // The main reason for doing this is to allow stdtest to run native
+        // tests as well. Both libgreen and libnative are running around
+        // with process timeouts, but libgreen should get there first
+        // (currently libuv doesn't handle old signal handlers).
+        if drain(read_fd) {
+            let i: uint = unsafe { mem::transmute(old.sa_handler) };
+            if i != 0 {
+                assert!(old.sa_flags & c::SA_SIGINFO == 0);
+                (old.sa_handler)(c::SIGCHLD);
             }
-        }
 
-        // Once this helper thread is done, we re-register the old sigchld
-        // handler and close our intermediate file descriptors.
-        unsafe {
-            assert_eq!(c::sigaction(c::SIGCHLD, &old, ptr::mut_null()), 0);
-            let _ = libc::close(read_fd);
-            let _ = libc::close(WRITE_FD);
-            WRITE_FD = -1;
+            // FIXME: sure would be nice to not have to scan the entire
+            //        array...
+            active.retain(|&(pid, ref tx, _)| {
+                match waitpid_nowait(pid) {
+                    Some(msg) => { tx.send(msg); false }
+                    None => true,
+                }
+            });
         }
     }
 
-    // Drain all pending data from the file descriptor, returning if any data
-    // could be drained. This requires that the file descriptor is in
-    // nonblocking mode.
-    fn drain(fd: libc::c_int) -> bool {
-        let mut ret = false;
-        loop {
-            let mut buf = [0u8, ..1];
-            match unsafe {
-                libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void,
-                           buf.len() as libc::size_t)
-            } {
-                n if n > 0 => { ret = true; }
-                0 => return true,
-                -1 if util::wouldblock() => return ret,
-                n => fail!("bad read {} ({})", os::last_os_error(), n),
-            }
-        }
+    // Once this helper thread is done, we re-register the old sigchld
+    // handler and close our intermediate file descriptors.
+    unsafe {
+        assert_eq!(c::sigaction(c::SIGCHLD, &old, ptr::mut_null()), 0);
+        let _ = libc::close(read_fd);
+        let _ = libc::close(WRITE_FD);
+        WRITE_FD = -1;
     }
+}
 
-    // Signal handler for SIGCHLD signals, must be async-signal-safe!
-    //
-    // This function will write to the writing half of the "self pipe" to wake
-    // up the helper thread if it's waiting. Note that this write must be
-    // nonblocking because if it blocks and the reader is the thread we
-    // interrupted, then we'll deadlock.
-    //
-    // When writing, if the write returns EWOULDBLOCK then we choose to ignore
-    // it. At that point we're guaranteed that there's something in the pipe
-    // which will wake up the other end at some point, so we just allow this
-    // signal to be coalesced with the pending signals on the pipe.
-    extern fn sigchld_handler(_signum: libc::c_int) {
-        let mut msg = 1;
+// Drain all pending data from the file descriptor, returning if any data
+// could be drained. This requires that the file descriptor is in
+// nonblocking mode.
+#[cfg(unix)]
+fn drain(fd: libc::c_int) -> bool {
+    let mut ret = false;
+    loop {
+        let mut buf = [0u8, ..1];
         match unsafe {
-            libc::write(WRITE_FD, &mut msg as *mut _ as *libc::c_void, 1)
+            libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void,
+                       buf.len() as libc::size_t)
         } {
-            1 => {}
-            -1 if util::wouldblock() => {} // see above comments
-            n => fail!("bad error on write fd: {} {}", n, os::errno()),
+            n if n > 0 => { ret = true; }
+            0 => return true,
+            -1 if util::wouldblock() => return ret,
+            n => fail!("bad read {} ({})", os::last_os_error(), n),
         }
     }
 }
 
+// Signal handler for SIGCHLD signals, must be async-signal-safe!
+//
+// This function will write to the writing half of the "self pipe" to wake
+// up the helper thread if it's waiting. Note that this write must be
+// nonblocking because if it blocks and the reader is the thread we
+// interrupted, then we'll deadlock.
+//
+// When writing, if the write returns EWOULDBLOCK then we choose to ignore
+// it. At that point we're guaranteed that there's something in the pipe
+// which will wake up the other end at some point, so we just allow this
+// signal to be coalesced with the pending signals on the pipe.
+//
+// This is the only handler for SIGCHLD in the process, so it also passes the
+// signal on to the signal module for any `Child` registrations.
+#[cfg(unix)]
+extern fn sigchld_handler(_signum: libc::c_int) {
+    let mut msg = 1;
+    match unsafe {
+        libc::write(WRITE_FD, &mut msg as *mut _ as *libc::c_void, 1)
+    } {
+        1 => {}
+        -1 if util::wouldblock() => {} // see above comments
+        n => fail!("bad error on write fd: {} {}", n, os::errno()),
+    }
+    super::signal::forward_sigchld();
+}
+
 fn waitpid_nowait(pid: pid_t) -> Option<p::ProcessExit> {
     return waitpid_os(pid);
 
@@ -0,0 +1,302 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signal handling for unix platforms
+//!
+//! Like the SIGCHLD handling in the process module, this uses the self-pipe
+//! trick: the signal handler itself only writes the number of the signal to a
+//! nonblocking pipe, and a helper thread reads the other end of this pipe and
+//! sends the signal to every channel registered for it.
+//!
+//! The helper thread also owns the installed signal handlers. A handler is
+//! installed the first time a signal is registered and the previous handler is
+//! restored once the last registration for it is dropped. Registration waits
+//! for the helper thread to install the handler so that a signal raised right
+//! after registering is not lost.
+//!
+//! SIGCHLD is the exception: its handler belongs to the process module, which
+//! needs it for as long as the runtime is up to reap children. Rather than
+//! installing a handler of its own, registering SIGCHLD boots the process
+//! module's helper thread, whose handler passes SIGCHLD on to this module.
+
+use libc;
+use std::comm;
+use std::io;
+use std::io::IoError;
+use std::io::signal::Signum;
+use std::mem;
+use std::os;
+use std::ptr;
+use std::rt::rtio;
+use std::sync::atomics;
+
+use io::IoResult;
+use io::c;
+use io::util;
+use io::helper_thread::Helper;
+
+helper_init!(static mut HELPER: Helper<Req>)
+
+static mut WRITE_FD: libc::c_int = -1;
+
+pub struct Signal {
+    id: uint,
+}
+
+struct Registration {
+    id: uint,
+    signum: libc::c_int,
+    signal: Signum,
+    tx: Sender<Signum>,
+    masked: bool,
+    pending: bool,
+}
+
+#[allow(visible_private_types)]
+pub enum Req {
+    // Register a new channel for a signal, installing the signal handler if
+    // necessary and acknowledging once it has been installed
+    NewSignal(Registration, Sender<IoResult<()>>),
+
+    // Change whether the signals for a registration are held back
+    MaskSignal(uint, bool),
+
+    // Remove a registration based on its id
+    RemoveSignal(uint),
+}
+
+impl Signal {
+    pub fn new(signal: Signum, tx: Sender<Signum>) -> IoResult<Signal> {
+        let signum = match signal.os_signum() {
+            Some(signum) => signum,
+            None => return Err(IoError {
+                kind: io::InvalidInput,
+                desc: "signal not available on this platform",
+                detail: None,
+            }),
+        };
+        unsafe { HELPER.boot(register_pipe, helper); }
+        if signum == c::SIGCHLD {
+            super::process::boot_sigchld_helper();
+        }
+
+        static mut ID: atomics::AtomicUint = atomics::INIT_ATOMIC_UINT;
+        let id = unsafe { ID.fetch_add(1, atomics::Relaxed) };
+        let registration = Registration {
+            id: id,
+            signum: signum,
+            signal: signal,
+            tx: tx,
+            masked: false,
+            pending: false,
+        };
+        let (ack, rx) = channel();
+        unsafe { HELPER.send(NewSignal(registration, ack)); }
+        try!(rx.recv_unwrap());
+        Ok(Signal { id: id })
+    }
+}
+
+impl rtio::RtioSignal for Signal {
+    fn set_masked(&mut self, masked: bool) {
+        unsafe { HELPER.send(MaskSignal(self.id, masked)); }
+    }
+}
+
+impl Drop for Signal {
+    fn drop(&mut self) {
+        unsafe { HELPER.send(RemoveSignal(self.id)); }
+    }
+}
+
+// Create the self-pipe, returning its reading half
+fn register_pipe() -> libc::c_int {
+    unsafe {
+        let mut pipes = [0, ..2];
+        assert_eq!(libc::pipe(pipes.as_mut_ptr()), 0);
+        util::set_nonblocking(pipes[0], true).unwrap();
+        util::set_nonblocking(pipes[1], true).unwrap();
+        WRITE_FD = pipes[1];
+        pipes[0]
+    }
+}
+
+fn helper(input: libc::c_int, messages: Receiver<Req>, read_fd: libc::c_int) {
+    util::set_nonblocking(input, true).unwrap();
+    let mut set: c::fd_set = unsafe { mem::zeroed() };
+    let mut active: Vec<Registration> = Vec::new();
+    // the signals we have installed a handler for, along with the handler
+    // which was previously installed
+    let mut installed: Vec<(libc::c_int, c::sigaction)> = Vec::new();
+    let max = ::std::cmp::max(input, read_fd) + 1;
+
+    'outer: loop {
+        c::fd_set(&mut set, input);
+        c::fd_set(&mut set, read_fd);
+        match unsafe {
+            c::select(max, &set, ptr::null(), ptr::null(), ptr::null())
+        } {
+            -1 if os::errno() == libc::EINTR as int => continue,
+            1 | 2 => {}
+            n => fail!("error in select {} ({})", os::errno(), n),
+        }
+
+        if read(input).len() > 0 {
+            loop {
+                match messages.try_recv() {
+                    Ok(NewSignal(registration, ack)) => {
+                        let signum = registration.signum;
+                        if signum == c::SIGCHLD ||
+                           installed.iter().any(|&(s, _)| s == signum) {
+                            active.push(registration);
+                            ack.send(Ok(()));
+                            continue
+                        }
+                        match install(signum) {
+                            Ok(old) => {
+                                installed.push((signum, old));
+                                active.push(registration);
+                                ack.send(Ok(()));
+                            }
+                            Err(e) => ack.send(Err(e)),
+                        }
+                    }
+                    Ok(MaskSignal(id, masked)) => {
+                        match active.mut_iter().find(|r| r.id == id) {
+                            Some(r) => {
+                                r.masked = masked;
+                                if !masked && r.pending {
+                                    r.pending = false;
+                                    let _ = r.tx.send_opt(r.signal);
+                                }
+                            }
+                            None => {}
+                        }
+                    }
+                    Ok(RemoveSignal(id)) => {
+                        let i = active.iter().position(|r| r.id == id);
+                        let i = i.expect("no signal registration found");
+                        let signum = active.remove(i).unwrap().signum;
+                        if !active.iter().any(|r| r.signum == signum) {
+                            match installed.iter().position(|&(s, _)| {
+                                s == signum
+                            }) {
+                                Some(i) => {
+                                    let (_, old) = installed.remove(i).unwrap();
+                                    restore(signum, &old);
+                                }
+                                // SIGCHLD, whose handler isn't ours
+                                None => {}
+                            }
+                        }
+                    }
+                    Err(comm::Disconnected) => break 'outer,
+                    Err(comm::Empty) => break,
+                }
+            }
+        }
+
+        for &signum in read(read_fd).iter() {
+            let signum = signum as libc::c_int;
+
+            // As with SIGCHLD, be a responsible netizen and invoke any
+            // previous handler for the signal (if it was a plain handler
+            // rather than SIG_DFL or SIG_IGN).
+            match installed.iter().find(|&&(s, _)| s == signum) {
+                Some(&(_, ref old)) => {
+                    let i: uint = unsafe { mem::transmute(old.sa_handler) };
+                    if i > 1 && old.sa_flags & c::SA_SIGINFO == 0 {
+                        (old.sa_handler)(signum);
+                    }
+                }
+                None => {}
+            }
+
+            for r in active.mut_iter().filter(|r| r.signum == signum) {
+                if r.masked {
+                    r.pending = true;
+                } else {
+                    let _ = r.tx.send_opt(r.signal);
+                }
+            }
+        }
+    }
+
+    // Once this helper thread is done, restore any handlers which are still
+    // installed and close our intermediate file descriptors.
+    for &(signum, ref old) in installed.iter() {
+        restore(signum, old);
+    }
+    unsafe {
+        let _ = libc::close(read_fd);
+        let _ = libc::close(WRITE_FD);
+        WRITE_FD = -1;
+    }
+
+    fn install(signum: libc::c_int) -> IoResult<c::sigaction> {
+        unsafe {
+            let mut old: c::sigaction = mem::zeroed();
+            let mut new: c::sigaction = mem::zeroed();
+            new.sa_handler = signal_handler;
+            new.sa_flags = c::SA_RESTART;
+            match c::sigaction(signum, &new, &mut old) {
+                0 => Ok(old),
+                _ => Err(super::last_error()),
+            }
+        }
+    }
+
+    fn restore(signum: libc::c_int, old: &c::sigaction) {
+        unsafe {
+            assert_eq!(c::sigaction(signum, old, ptr::mut_null()), 0);
+        }
+    }
+}
+
+// Read all pending bytes from the nonblocking file descriptor.
+fn read(fd: libc::c_int) -> Vec<u8> {
+    let mut ret = Vec::new();
+    loop {
+        let mut buf = [0u8, ..64];
+        match unsafe {
+            libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void,
+                       buf.len() as libc::size_t)
+        } {
+            n if n > 0 => ret.push_all(buf.slice_to(n as uint)),
+            0 => return ret,
+            -1 if util::wouldblock() => return ret,
+            n => fail!("bad read {} ({})", os::last_os_error(), n),
+        }
+    }
+}
+
+// Called by the process module's SIGCHLD handler to deliver SIGCHLD, must be
+// async-signal-safe!
+pub fn forward_sigchld() {
+    if unsafe { WRITE_FD } != -1 {
+        signal_handler(c::SIGCHLD);
+    }
+}
+
+// Signal handler for all registered signals, must be async-signal-safe!
+//
+// The number of the signal is written to the self-pipe to wake up the helper
+// thread. As with SIGCHLD, if the pipe is full this signal is dropped; the
+// kernel is allowed to coalesce pending signals anyway.
+extern fn signal_handler(signum: libc::c_int) {
+    let mut msg = signum as u8;
+    match unsafe {
+        libc::write(WRITE_FD, &mut msg as *mut _ as *libc::c_void, 1)
+    } {
+        1 => {}
+        -1 if util::wouldblock() => {}
+        n => fail!("bad error on write fd: {} {}", n, os::errno()),
+    }
+}
@@ -236,7 +236,7 @@ impl Timer {
             None => {
                 let (tx, rx) = channel();
                 unsafe { HELPER.send(RemoveTimer(self.id, tx)); }
-                rx.recv()
+                rx.recv_unwrap()
             }
         }
     }
@@ -127,7 +127,7 @@ impl Timer {
 
         let (tx, rx) = channel();
         unsafe { HELPER.send(RemoveTimer(self.obj, tx)) }
-        rx.recv();
+        rx.recv_unwrap();
 
         self.on_worker = false;
     }
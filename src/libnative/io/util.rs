@@ -14,6 +14,7 @@ use std::io;
 use std::mem;
 use std::os;
 use std::ptr;
+#[cfg(unix)] use std::rt::rtio;
 
 use super::c;
 use super::net;
@@ -166,3 +167,44 @@ pub fn await(fd: net::sock_t, deadline: Option<u64>,
         _ => Ok(()),
     }
 }
+
+/// Waits for any of `fds` to become ready, filling in their readiness and
+/// returning how many are ready. Returns 0 if the timeout expired first.
+#[cfg(unix)]
+pub fn poll(fds: &mut [rtio::PollFd],
+            timeout_ms: Option<u64>) -> IoResult<uint> {
+    use std::cmp;
+    use std::i32;
+
+    let mut pfds = fds.iter().map(|fd| {
+        let mut events = 0;
+        if fd.read { events |= c::POLLIN; }
+        if fd.write { events |= c::POLLOUT; }
+        c::pollfd { fd: fd.fd, events: events, revents: 0 }
+    }).collect::<Vec<c::pollfd>>();
+    let deadline = timeout_ms.map(|ms| ::io::timer::now() + ms);
+
+    let n = retry(|| {
+        // Recompute the timeout on each attempt so an EINTR doesn't extend
+        // the total time spent waiting.
+        let timeout = match deadline {
+            None => -1,
+            Some(deadline) => {
+                let now = ::io::timer::now();
+                let ms = if deadline < now {0} else {deadline - now};
+                cmp::min(ms, i32::MAX as u64) as libc::c_int
+            }
+        };
+        unsafe {
+            c::poll(pfds.as_mut_ptr(), pfds.len() as c::nfds_t, timeout)
+        }
+    });
+    if n == -1 { return Err(last_error()) }
+
+    let done = c::POLLERR | c::POLLHUP | c::POLLNVAL;
+    for (fd, pfd) in fds.mut_iter().zip(pfds.iter()) {
+        fd.readable = pfd.revents & (c::POLLIN | done) != 0;
+        fd.writable = pfd.revents & c::POLLOUT != 0;
+    }
+    Ok(n as uint)
+}
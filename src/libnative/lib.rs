@@ -63,7 +63,10 @@ extern crate libc;
 
 use std::os;
 use std::rt;
+use std::rt::observer;
+use std::rt::task::SendMessage;
 use std::str;
+use std::task::failure_exit_status;
 
 pub mod io;
 pub mod task;
@@ -126,16 +129,28 @@ pub fn start(argc: int, argv: **u8, main: proc()) -> int {
     let mut main = Some(main);
     let mut task = task::new((my_stack_bottom, my_stack_top));
     task.name = Some(str::Slice("<main>"));
+    let (tx, rx) = channel();
+    task.death.on_exit = Some(SendMessage(tx));
+    observer::spawned(&*task);
     let t = task.run(|| {
         unsafe {
             rt::stack::record_stack_bounds(my_stack_bottom, my_stack_top);
         }
         exit_code = Some(run(main.take_unwrap()));
     });
+    observer::died(&*t);
     drop(t);
+
+    // If the exit code wasn't set, then the task block must have failed, and
+    // the failure may have requested a particular exit status.
+    let exit_code = exit_code.unwrap_or_else(|| {
+        match rx.try_recv() {
+            Ok(Err(cause)) => failure_exit_status(&*cause),
+            _ => rt::DEFAULT_ERROR_CODE,
+        }
+    });
     unsafe { rt::cleanup(); }
-    // If the exit code wasn't set, then the task block must have failed.
-    return exit_code.unwrap_or(rt::DEFAULT_ERROR_CODE);
+    return exit_code;
 }
 
 /// Executes a procedure on the current thread in a Rust task context.
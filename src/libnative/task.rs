@@ -19,15 +19,19 @@ use std::mem;
 use std::rt::bookkeeping;
 use std::rt::env;
 use std::rt::local::Local;
+use std::rt::observer;
 use std::rt::rtio;
 use std::rt::stack;
+use std::rt::stack_overflow;
 use std::rt::task::{Task, BlockedTask, SendMessage};
 use std::rt::thread::Thread;
 use std::rt;
-use std::task::TaskOpts;
+use std::sync::atomics;
+use std::task::{TaskOpts, Priority};
 use std::unstable::mutex::NativeMutex;
 
 use io;
+use io::IoResult;
 use task;
 
 /// Creates a new Task which is ready to execute as a 1:1 task.
@@ -35,6 +39,7 @@ pub fn new(stack_bounds: (uint, uint)) -> Box<Task> {
     let mut task = box Task::new();
     let mut ops = ops();
     ops.stack_bounds = stack_bounds;
+    ops.stack_guard = unsafe { stack_overflow::current_guard_page() };
     task.put_runtime(ops);
     return task;
 }
@@ -46,9 +51,45 @@ fn ops() -> Box<Ops> {
         io: io::IoFactory::new(),
         // these *should* get overwritten
         stack_bounds: (0, 0),
+        stack_guard: None,
     }
 }
 
+// The defaults set through `set_default_stack_size` and
+// `set_default_guard_size`, zero if they haven't been set.
+static mut DEFAULT_STACK_SIZE: atomics::AtomicUint = atomics::INIT_ATOMIC_UINT;
+static mut DEFAULT_GUARD_SIZE: atomics::AtomicUint = atomics::INIT_ATOMIC_UINT;
+
+/// Sets the stack size, in bytes, of native tasks spawned without an explicit
+/// stack size from now on.
+///
+/// This is meant for programs which recurse deeply, such as parsers. A stack
+/// size given in the `RUST_MIN_STACK` environment variable takes precedence
+/// over this default. Setting the size to zero restores the runtime's default.
+pub fn set_default_stack_size(bytes: uint) {
+    unsafe { DEFAULT_STACK_SIZE.store(bytes, atomics::SeqCst) }
+}
+
+/// Returns the stack size of native tasks spawned without an explicit stack
+/// size.
+pub fn default_stack_size() -> uint {
+    match unsafe { DEFAULT_STACK_SIZE.load(atomics::SeqCst) } {
+        n if n > 0 && !env::min_stack_from_env() => n,
+        _ => env::min_stack(),
+    }
+}
+
+/// Sets the size, in bytes, of the guard area below the stack of native
+/// tasks spawned from now on. Overflowing the stack into the guard area is
+/// reported as a stack overflow, so programs with very large stack frames may
+/// want a guard bigger than the platform's default of a page.
+///
+/// Setting the size to zero restores the platform's default. The guard size
+/// is ignored on platforms which can't configure it.
+pub fn set_default_guard_size(bytes: uint) {
+    unsafe { DEFAULT_GUARD_SIZE.store(bytes, atomics::SeqCst) }
+}
+
 /// Spawns a function with the default configuration
 pub fn spawn(f: proc():Send) {
     spawn_opts(TaskOpts::new(), f)
@@ -57,21 +98,25 @@ pub fn spawn(f: proc():Send) {
 /// Spawns a new task given the configuration options and a procedure to run
 /// inside the task.
 pub fn spawn_opts(opts: TaskOpts, f: proc():Send) {
+    // Native tasks never leave their thread, so they're always pinned
     let TaskOpts {
-        notify_chan, name, stack_size,
-        stderr, stdout,
+        notify_chan, name, stack_size, priority, affinity, scheduled,
+        pinned: _, stderr, stdout, on_fail,
     } = opts;
 
     let mut task = box Task::new();
     task.name = name;
     task.stderr = stderr;
     task.stdout = stdout;
+    task.on_fail = on_fail;
     match notify_chan {
         Some(chan) => { task.death.on_exit = Some(SendMessage(chan)); }
         None => {}
     }
+    observer::spawned(&*task);
 
-    let stack = stack_size.unwrap_or(env::min_stack());
+    let stack = stack_size.unwrap_or_else(default_stack_size);
+    let guard = unsafe { DEFAULT_GUARD_SIZE.load(atomics::SeqCst) };
     let task = task;
     let ops = ops();
 
@@ -86,7 +131,7 @@ pub fn spawn_opts(opts: TaskOpts, f: proc():Send) {
     // by the time that this function is executing we've already consumed at
     // least a little bit of stack (we don't know the exact byte address at
     // which our stack started).
-    Thread::spawn_stack(stack, proc() {
+    Thread::spawn_stack_guard(stack, guard, proc() {
         let something_around_the_top_of_the_stack = 1;
         let addr = &something_around_the_top_of_the_stack as *int;
         let my_stack = addr as uint;
@@ -95,11 +140,34 @@ pub fn spawn_opts(opts: TaskOpts, f: proc():Send) {
         }
         let mut ops = ops;
         ops.stack_bounds = (my_stack - stack + 1024, my_stack);
+        ops.stack_guard = unsafe { stack_overflow::current_guard_page() };
+
+        // Name the thread after the task so that it's not anonymous in
+        // debuggers.
+        match task.name {
+            Some(ref name) => Thread::set_name(name.as_slice()),
+            None => {}
+        }
 
         let mut f = Some(f);
+        let mut affinity = affinity;
+        let mut scheduled = scheduled;
         let mut task = task;
         task.put_runtime(ops);
-        let t = task.run(|| { f.take_unwrap()() });
+        let t = task.run(|| {
+            match (configure_thread(priority, affinity.take()), scheduled.take()) {
+                (Ok(()), tx) => {
+                    match tx {
+                        Some(tx) => { let _ = tx.send_opt(Ok(())); }
+                        None => {}
+                    }
+                    f.take_unwrap()()
+                }
+                (Err(e), Some(tx)) => { let _ = tx.send_opt(Err(e)); }
+                (Err(e), None) => fail!("failed to schedule the task: {}", e),
+            }
+        });
+        observer::died(&*t);
         drop(t);
         bookkeeping::decrement();
     })
@@ -116,6 +184,10 @@ struct Ops {
     // native tasks necessarily know their precise bounds, hence this is
     // optional.
     stack_bounds: (uint, uint),
+
+    // The (lo, hi) edges of the guard area below this task's stack, used to
+    // tell stack overflows apart from other segmentation faults.
+    stack_guard: Option<(uint, uint)>,
 }
 
 impl rt::Runtime for Ops {
@@ -139,6 +211,8 @@ impl rt::Runtime for Ops {
 
     fn stack_bounds(&self) -> (uint, uint) { self.stack_bounds }
 
+    fn stack_guard(&self) -> Option<(uint, uint)> { self.stack_guard }
+
     fn can_block(&self) -> bool { true }
 
     // This function gets a little interesting. There are a few safety and
@@ -183,6 +257,8 @@ impl rt::Runtime for Ops {
     fn deschedule(mut ~self, times: uint, mut cur_task: Box<Task>,
                   f: |BlockedTask| -> Result<(), BlockedTask>) {
         let me = &mut *self as *mut Ops;
+        observer::blocked(&*cur_task);
+        cur_task.blocked_on = None;
         cur_task.put_runtime(self);
 
         unsafe {
@@ -231,6 +307,7 @@ impl rt::Runtime for Ops {
             // re-acquire ownership of the task
             cur_task = mem::transmute(cur_task_dupe);
         }
+        observer::unblocked(&*cur_task);
 
         // put the task back in TLS, and everything is as it once was.
         Local::put(cur_task);
@@ -264,13 +341,163 @@ impl rt::Runtime for Ops {
     }
 }
 
+// Applies the scheduling options of a task to the current thread.
+fn configure_thread(priority: Option<Priority>,
+                    affinity: Option<Vec<uint>>) -> IoResult<()> {
+    match priority {
+        Some(priority) => try!(imp::set_priority(priority)),
+        None => {}
+    }
+    match affinity {
+        Some(cpus) => try!(imp::set_affinity(cpus.as_slice())),
+        None => {}
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[cfg(target_os = "android")]
+mod imp {
+    use libc;
+    use std::io::{IoError, InvalidInput};
+    use std::mem;
+    use std::task::{Priority, LowPriority, NormalPriority, HighPriority};
+    use std::uint;
+
+    use io::IoResult;
+
+    static PRIO_PROCESS: libc::c_int = 0;
+    // The number of CPUs in a cpu_set_t
+    static CPU_SETSIZE: uint = 1024;
+    // A cpu_set_t is an array of c_ulong, which is as big as a uint here
+    static CPU_WORD_BITS: uint = uint::BITS;
+
+    pub fn set_priority(priority: Priority) -> IoResult<()> {
+        // Each thread has its own nice value on linux, and a `who` of 0 is the
+        // calling thread.
+        let nice = match priority {
+            LowPriority => 10,
+            NormalPriority => 0,
+            HighPriority => -10,
+        };
+        match unsafe { setpriority(PRIO_PROCESS, 0, nice) } {
+            0 => Ok(()),
+            _ => Err(IoError::last_error()),
+        }
+    }
+
+    pub fn set_affinity(cpus: &[uint]) -> IoResult<()> {
+        let mut set = [0 as libc::c_ulong, ..CPU_SETSIZE / CPU_WORD_BITS];
+        for &cpu in cpus.iter() {
+            if cpu >= CPU_SETSIZE {
+                return Err(IoError {
+                    kind: InvalidInput,
+                    desc: "CPU index out of range",
+                    detail: None,
+                })
+            }
+            set[cpu / CPU_WORD_BITS] |= 1 << (cpu % CPU_WORD_BITS);
+        }
+        let size = mem::size_of_val(&set) as libc::size_t;
+        match unsafe { sched_setaffinity(0, size, set.as_ptr()) } {
+            0 => Ok(()),
+            _ => Err(IoError::last_error()),
+        }
+    }
+
+    extern {
+        fn setpriority(which: libc::c_int, who: libc::c_uint,
+                       prio: libc::c_int) -> libc::c_int;
+        fn sched_setaffinity(pid: libc::pid_t, cpusetsize: libc::size_t,
+                             mask: *libc::c_ulong) -> libc::c_int;
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use libc;
+    use std::io::{IoError, InvalidInput};
+    use std::task::{Priority, LowPriority, NormalPriority, HighPriority};
+    use std::uint;
+
+    use io::IoResult;
+
+    static THREAD_PRIORITY_BELOW_NORMAL: libc::c_int = -1;
+    static THREAD_PRIORITY_NORMAL: libc::c_int = 0;
+    static THREAD_PRIORITY_ABOVE_NORMAL: libc::c_int = 1;
+
+    pub fn set_priority(priority: Priority) -> IoResult<()> {
+        let priority = match priority {
+            LowPriority => THREAD_PRIORITY_BELOW_NORMAL,
+            NormalPriority => THREAD_PRIORITY_NORMAL,
+            HighPriority => THREAD_PRIORITY_ABOVE_NORMAL,
+        };
+        match unsafe { SetThreadPriority(GetCurrentThread(), priority) } {
+            0 => Err(IoError::last_error()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn set_affinity(cpus: &[uint]) -> IoResult<()> {
+        let mut mask = 0u;
+        for &cpu in cpus.iter() {
+            if cpu >= uint::BITS {
+                return Err(IoError {
+                    kind: InvalidInput,
+                    desc: "CPU index out of range",
+                    detail: None,
+                })
+            }
+            mask |= 1 << cpu;
+        }
+        match unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } {
+            0 => Err(IoError::last_error()),
+            _ => Ok(()),
+        }
+    }
+
+    extern "system" {
+        fn GetCurrentThread() -> libc::HANDLE;
+        fn SetThreadPriority(thread: libc::HANDLE,
+                             priority: libc::c_int) -> libc::BOOL;
+        fn SetThreadAffinityMask(thread: libc::HANDLE, mask: uint) -> uint;
+    }
+}
+
+#[cfg(not(target_os = "linux"), not(target_os = "android"), not(windows))]
+mod imp {
+    use std::io::{IoError, IoUnavailable};
+    use std::task::Priority;
+
+    use io::IoResult;
+
+    pub fn set_priority(_priority: Priority) -> IoResult<()> {
+        Err(unsupported())
+    }
+
+    pub fn set_affinity(_cpus: &[uint]) -> IoResult<()> {
+        Err(unsupported())
+    }
+
+    fn unsupported() -> IoError {
+        IoError {
+            kind: IoUnavailable,
+            desc: "not supported on this platform",
+            detail: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::rt::local::Local;
     use std::rt::task::Task;
     use std::task;
     use std::task::TaskOpts;
+    use libc;
     use super::{spawn, spawn_opts, Ops};
+    use super::{set_default_stack_size, default_stack_size};
+    use super::set_default_guard_size;
 
     #[test]
     fn smoke() {
@@ -278,7 +505,7 @@ mod tests {
         spawn(proc() {
             tx.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 
     #[test]
@@ -299,7 +526,67 @@ mod tests {
         let (tx, rx) = channel();
         opts.notify_chan = Some(tx);
         spawn_opts(opts, proc() {});
-        assert!(rx.recv().is_ok());
+        assert!(rx.recv_unwrap().is_ok());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn named_thread() {
+        extern {
+            fn prctl(option: libc::c_int, arg2: libc::c_ulong, ...) -> libc::c_int;
+        }
+        static PR_GET_NAME: libc::c_int = 16;
+
+        let mut opts = TaskOpts::new();
+        opts.name = Some("resolver-worker-3".into_maybe_owned());
+        let (tx, rx) = channel();
+        spawn_opts(opts, proc() {
+            let mut buf = [0u8, ..16];
+            unsafe {
+                assert_eq!(prctl(PR_GET_NAME, buf.as_mut_ptr() as libc::c_ulong), 0);
+            }
+            let len = buf.iter().position(|&b| b == 0).unwrap();
+            tx.send(Vec::from_slice(buf.slice_to(len)));
+        });
+        assert_eq!(rx.recv_unwrap().as_slice(), "resolver-worker".as_bytes());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    #[cfg(windows)]
+    fn smoke_opts_scheduling() {
+        let mut opts = TaskOpts::new();
+        opts.priority = Some(task::LowPriority);
+        opts.affinity = Some(vec!(0));
+        let (tx, rx) = channel();
+        opts.notify_chan = Some(tx);
+        spawn_opts(opts, proc() {});
+        assert!(rx.recv_unwrap().is_ok());
+    }
+
+    #[test]
+    fn smoke_opts_bad_affinity() {
+        let mut opts = TaskOpts::new();
+        opts.affinity = Some(vec!(1 << 20));
+        let (tx, rx) = channel();
+        opts.notify_chan = Some(tx);
+        spawn_opts(opts, proc() {});
+        assert!(rx.recv_unwrap().is_err());
+    }
+
+    #[test]
+    fn smoke_opts_bad_affinity_scheduled() {
+        let mut opts = TaskOpts::new();
+        opts.affinity = Some(vec!(1 << 20));
+        let (tx, rx) = channel();
+        opts.scheduled = Some(tx);
+        let (tx, rx2) = channel();
+        opts.notify_chan = Some(tx);
+        let (tx, rx3) = channel();
+        spawn_opts(opts, proc() tx.send(()));
+        assert!(rx.recv_unwrap().is_err());
+        assert!(rx2.recv_unwrap().is_ok());
+        assert!(rx3.recv().is_err());
     }
 
     #[test]
@@ -308,7 +595,7 @@ mod tests {
         let (tx, rx) = channel();
         opts.notify_chan = Some(tx);
         spawn_opts(opts, proc() { fail!() });
-        assert!(rx.recv().is_err());
+        assert!(rx.recv_unwrap().is_err());
     }
 
     #[test]
@@ -318,7 +605,7 @@ mod tests {
             for _ in range(0, 10) { task::deschedule(); }
             tx.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 
     #[test]
@@ -331,13 +618,13 @@ mod tests {
                 spawn(proc() {
                     tx3.send(());
                 });
-                rx.recv();
+                rx.recv_unwrap();
                 tx2.send(());
             });
-            rx.recv();
+            rx.recv_unwrap();
             tx1.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 
     #[test]
@@ -356,6 +643,29 @@ mod tests {
                 tx.send(());
             });
         });
-        rx.recv();
+        rx.recv_unwrap();
+    }
+
+    fn stack_size() -> uint {
+        let task: Box<Task> = Local::take();
+        let (lo, hi) = task.stack_bounds();
+        Local::put(task);
+        hi - lo
+    }
+
+    #[test]
+    fn default_sizes() {
+        // Only ever grow the defaults while other tests are running
+        // concurrently, and put them back afterwards. RUST_MIN_STACK taking
+        // precedence is tested by run-pass/native-min-stack-env.rs.
+        let size = 16 * 1024 * 1024;
+        set_default_stack_size(size);
+        set_default_guard_size(64 * 1024);
+        assert!(default_stack_size() >= size);
+        let (tx, rx) = channel();
+        spawn(proc() tx.send(stack_size()));
+        assert!(rx.recv_unwrap() >= size - 4096);
+        set_default_stack_size(0);
+        set_default_guard_size(0);
     }
 }
@@ -213,7 +213,7 @@ mod test {
             txs.push(tx);
             task::spawn(proc() {
                 // wait until all the tasks are ready to go.
-                rx.recv();
+                rx.recv_unwrap();
 
                 // deschedule to attempt to interleave things as much
                 // as possible (XXX: is this a good test?)
@@ -983,10 +983,10 @@ fn combine_ranges(unordered: Vec<(char, char)>) -> Vec<(char, char)> {
 // Constructs a Unicode friendly Perl character class from \d, \s or \w
 // (or any of their negated forms). Note that this does not handle negation.
 fn perl_unicode_class(which: char) -> Vec<(char, char)> {
-    match which.to_lowercase() {
-        'd' => Vec::from_slice(PERLD),
-        's' => Vec::from_slice(PERLS),
-        'w' => Vec::from_slice(PERLW),
+    match which {
+        'd' | 'D' => Vec::from_slice(PERLD),
+        's' | 'S' => Vec::from_slice(PERLS),
+        'w' | 'W' => Vec::from_slice(PERLW),
         _ => unreachable!(),
     }
 }
@@ -326,7 +326,7 @@ impl<'r, 't> Nfa<'r, 't> {
             None => false,
             Some(textc) => {
                 regc == textc
-                    || (casei && regc.to_uppercase() == textc.to_uppercase())
+                    || (casei && simple_uppercase(regc) == simple_uppercase(textc))
             }
         }
     }
@@ -543,9 +543,9 @@ fn class_cmp(casei: bool, mut textc: char,
         //    parser or the compiler.
         // FIXME: This is too simplistic for correct Unicode support.
         //        See also: char_eq
-        textc = textc.to_uppercase();
-        start = start.to_uppercase();
-        end = end.to_uppercase();
+        textc = simple_uppercase(textc);
+        start = simple_uppercase(start);
+        end = simple_uppercase(end);
     }
     if textc >= start && textc <= end {
         Equal
@@ -556,6 +556,20 @@ fn class_cmp(casei: bool, mut textc: char,
     }
 }
 
+/// Returns the uppercase equivalent of `c` when it is a single character, or
+/// `c` itself when it uppercases to several characters (like 'ß' does).
+///
+/// Case insensitive matching is done one character at a time, so the
+/// multi-character mappings can't be used.
+#[inline]
+pub fn simple_uppercase(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
 /// Returns the starting location of `needle` in `haystack`.
 /// If `needle` is not in `haystack`, then `None` is returned.
 ///
@@ -43,6 +43,7 @@ use regex::native::{
     Match, EmptyBegin, EmptyEnd, EmptyWordBoundary,
     Program, Dynamic, Native,
     FLAG_NOCASE, FLAG_MULTI, FLAG_DOTNL, FLAG_NEGATED,
+    simple_uppercase,
 };
 
 /// For the `regex!` syntax extension. Do not use.
@@ -150,7 +151,7 @@ fn exec<'t>(which: ::regex::native::MatchKind, input: &'t str,
     use regex::native::{
         MatchKind, Exists, Location, Submatches,
         StepState, StepMatchEarlyReturn, StepMatch, StepContinue,
-        CharReader, find_prefix,
+        CharReader, find_prefix, simple_uppercase,
     };
 
     return Nfa {
@@ -450,9 +451,9 @@ fn exec<'t>(which: ::regex::native::MatchKind, input: &'t str,
                 }
                 OneChar(c, flags) => {
                     if flags & FLAG_NOCASE > 0 {
-                        let upc = c.to_uppercase();
+                        let upc = simple_uppercase(c);
                         quote_expr!(self.cx, {
-                            let upc = self.chars.prev.map(|c| c.to_uppercase());
+                            let upc = self.chars.prev.map(simple_uppercase);
                             if upc == Some($upc) {
                                 self.add(nlist, $nextpc, caps);
                             }
@@ -470,7 +471,7 @@ fn exec<'t>(which: ::regex::native::MatchKind, input: &'t str,
                     let casei = flags & FLAG_NOCASE > 0;
                     let get_char =
                         if casei {
-                            quote_expr!(self.cx, self.chars.prev.unwrap().to_uppercase())
+                            quote_expr!(self.cx, simple_uppercase(self.chars.prev.unwrap()))
                         } else {
                             quote_expr!(self.cx, self.chars.prev.unwrap())
                         };
@@ -520,8 +521,8 @@ fn exec<'t>(which: ::regex::native::MatchKind, input: &'t str,
 
         let mut arms = ranges.iter().map(|&(mut start, mut end)| {
             if casei {
-                start = start.to_uppercase();
-                end = end.to_uppercase();
+                start = simple_uppercase(start);
+                end = simple_uppercase(end);
             }
             let pat = self.cx.pat(self.sp, ast::PatRange(quote_expr!(self.cx, $start),
                                                          quote_expr!(self.cx, $end)));
@@ -240,6 +240,7 @@ mod svh_visitor {
         SawExprAssign,
         SawExprAssignOp(ast::BinOp),
         SawExprIndex,
+        SawExprSlice(ast::Mutability),
         SawExprPath,
         SawExprAddrOf(ast::Mutability),
         SawExprRet,
@@ -272,6 +273,7 @@ mod svh_visitor {
             ExprAssignOp(op, _, _)   => SawExprAssignOp(op),
             ExprField(_, id, _)      => SawExprField(content(id)),
             ExprIndex(..)            => SawExprIndex,
+            ExprSlice(_, _, _, m)    => SawExprSlice(m),
             ExprPath(..)             => SawExprPath,
             ExprAddrOf(m, _)         => SawExprAddrOf(m),
             ExprBreak(id)            => SawExprBreak(id.map(content)),
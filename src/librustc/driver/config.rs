@@ -172,7 +172,9 @@ debugging_opts!(
         LTO,
         AST_JSON,
         AST_JSON_NOEXPAND,
-        LS
+        LS,
+        ABORT_ON_FAIL,
+        OVERFLOW_CHECKS
     ]
     0
 )
@@ -206,7 +208,11 @@ pub fn debugging_opts_map() -> Vec<(&'static str, &'static str, u64)> {
      ("lto", "Perform LLVM link-time optimizations", LTO),
      ("ast-json", "Print the AST as JSON and halt", AST_JSON),
      ("ast-json-noexpand", "Print the pre-expansion AST as JSON and halt", AST_JSON_NOEXPAND),
-     ("ls", "List the symbols defined by a library crate", LS))
+     ("ls", "List the symbols defined by a library crate", LS),
+     ("abort-on-fail", "Abort instead of unwinding on failure, omitting landing pads",
+      ABORT_ON_FAIL),
+     ("overflow-checks", "Fail on overflow in integer addition, subtraction and \
+                          multiplication", OVERFLOW_CHECKS))
 }
 
 /// Declare a macro that will define all CodegenOptions fields and parsers all
@@ -313,6 +319,8 @@ cgoptions!(
         "use an external assembler rather than LLVM's integrated one"),
     relocation_model: String = ("pic".to_string(), parse_string,
          "choose the relocation model to use (llc -relocation-model for details)"),
+    no_stack_check: bool = (false, parse_bool,
+        "leave out the stack exhaustion checks of every function (see #[no_split_stack])"),
 )
 
 pub fn build_codegen_options(matches: &getopts::Matches) -> CodegenOptions
@@ -167,7 +167,13 @@ impl Session {
         self.debugging_opt(config::LTO)
     }
     pub fn no_landing_pads(&self) -> bool {
-        self.debugging_opt(config::NO_LANDING_PADS)
+        self.debugging_opt(config::NO_LANDING_PADS) || self.abort_on_fail()
+    }
+    pub fn abort_on_fail(&self) -> bool {
+        self.debugging_opt(config::ABORT_ON_FAIL)
+    }
+    pub fn overflow_checks(&self) -> bool {
+        self.debugging_opt(config::OVERFLOW_CHECKS)
     }
     pub fn show_span(&self) -> bool {
         self.debugging_opt(config::SHOW_SPAN)
@@ -85,7 +85,7 @@ fn warn_if_multiple_versions(diag: &SpanHandler, cstore: &CStore) {
     cstore.iter_crate_data(|cnum, data| {
         let crateid = data.crate_id();
         let key = (crateid.name.clone(), crateid.path.clone());
-        map.find_or_insert_with(key, |_| Vec::new()).push(cnum);
+        map.entry(key).or_insert_with(|_| Vec::new()).push(cnum);
     });
 
     for ((name, _), dupes) in map.move_iter() {
@@ -206,7 +206,7 @@ impl<'a> Context<'a> {
                 match self.try_match(file, rlib_prefix.as_slice(), ".rlib") {
                     Some(hash) => {
                         info!("rlib accepted, hash: {}", hash);
-                        let slot = candidates.find_or_insert_with(hash, |_| {
+                        let slot = candidates.entry(hash).or_insert_with(|_| {
                             (HashSet::new(), HashSet::new())
                         });
                         let (ref mut rlibs, _) = *slot;
@@ -226,7 +226,7 @@ impl<'a> Context<'a> {
                                      dysuffix) {
                     Some(hash) => {
                         info!("dylib accepted, hash: {}", hash);
-                        let slot = candidates.find_or_insert_with(hash, |_| {
+                        let slot = candidates.entry(hash).or_insert_with(|_| {
                             (HashSet::new(), HashSet::new())
                         });
                         let (_, ref mut dylibs) = *slot;
@@ -851,7 +851,7 @@ fn check_loans_in_expr<'a>(this: &mut CheckLoanCtxt<'a>,
       if method_map.contains_key(&MethodCall::expr(expr.id)) => {
         this.check_call(expr, None, expr.span, [rval]);
       }
-      ast::ExprUnary(_, _) | ast::ExprIndex(_, _)
+      ast::ExprUnary(_, _) | ast::ExprIndex(_, _) | ast::ExprSlice(..)
       if method_map.contains_key(&MethodCall::expr(expr.id)) => {
         this.check_call(expr, None, expr.span, []);
       }
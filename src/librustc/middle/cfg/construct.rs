@@ -386,6 +386,12 @@ impl<'a> CFGBuilder<'a> {
                 self.call(expr, pred, e, [])
             }
 
+            ast::ExprSlice(base, start, end, _) => {
+                let args: Vec<@ast::Expr> =
+                    start.iter().chain(end.iter()).map(|&e| e).collect();
+                self.call(expr, pred, base, args.as_slice())
+            }
+
             ast::ExprTup(ref exprs) => {
                 self.straightline(expr, pred, exprs.as_slice())
             }
@@ -203,7 +203,7 @@ impl<'a, O:DataFlowOperator> DataFlowContext<'a, O> {
     fn compute_id_range(&mut self, id: ast::NodeId) -> (uint, uint) {
         let mut expanded = false;
         let len = self.nodeid_to_bitset.len();
-        let n = self.nodeid_to_bitset.find_or_insert_with(id, |_| {
+        let n = self.nodeid_to_bitset.entry(id).or_insert_with(|_| {
             expanded = true;
             len
         });
@@ -569,6 +569,14 @@ impl<'a, 'b, O:DataFlowOperator> PropagationContext<'a, 'b, O> {
                 self.walk_call(expr.id, [e], in_out, loop_scopes);
             }
 
+            ast::ExprSlice(e, e1, e2, _) => {
+                let mut args = vec!(e);
+                for &e in e1.iter().chain(e2.iter()) {
+                    args.push(e);
+                }
+                self.walk_call(expr.id, args.as_slice(), in_out, loop_scopes);
+            }
+
             ast::ExprTup(ref exprs) => {
                 self.walk_exprs(exprs.as_slice(), in_out, loop_scopes);
             }
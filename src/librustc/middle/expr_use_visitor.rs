@@ -254,6 +254,13 @@ impl<'d,'t,TYPER:mc::Typer> ExprUseVisitor<'d,'t,TYPER> {
                 }
             }
 
+            ast::ExprSlice(base, start, end, _) => { // base[start..end]
+                let args: Vec<@ast::Expr> =
+                    start.iter().chain(end.iter()).map(|&e| e).collect();
+                assert!(self.walk_overloaded_operator(expr, base,
+                                                      args.as_slice()));
+            }
+
             ast::ExprCall(callee, ref args) => {    // callee(args)
                 self.walk_callee(expr, callee);
                 self.consume_exprs(args);
@@ -234,6 +234,9 @@ lets_do_this! {
     ShlTraitLangItem,                "shl",                     shl_trait;
     ShrTraitLangItem,                "shr",                     shr_trait;
     IndexTraitLangItem,              "index",                   index_trait;
+    IndexMutTraitLangItem,           "index_mut",               index_mut_trait;
+    SliceTraitLangItem,              "slice",                   slice_trait;
+    SliceMutTraitLangItem,           "slice_mut",               slice_mut_trait;
 
     UnsafeTypeLangItem,              "unsafe",                  unsafe_type;
 
@@ -247,8 +250,10 @@ lets_do_this! {
     UniqStrEqFnLangItem,             "uniq_str_eq",             uniq_str_eq_fn;
 
     // A number of failure-related lang items. The `fail_` item corresponds to
-    // divide-by-zero and various failure cases with `match`. The
-    // `fail_bounds_check` item is for indexing arrays.
+    // various failure cases with `match`. The `fail_bounds_check` item is for
+    // indexing arrays, `fail_divide_by_zero` and `fail_rem_by_zero` are for
+    // integer division, and `fail_overflow` is for arithmetic which has been
+    // checked for overflow.
     //
     // The `begin_unwind` lang item has a predefined symbol name and is sort of
     // a "weak lang item" in the sense that a crate is not required to have it
@@ -257,8 +262,15 @@ lets_do_this! {
     // lang item, but do not have it defined.
     FailFnLangItem,                  "fail_",                   fail_fn;
     FailBoundsCheckFnLangItem,       "fail_bounds_check",       fail_bounds_check_fn;
+    FailOverflowFnLangItem,          "fail_overflow",           fail_overflow_fn;
+    FailDivideByZeroFnLangItem,      "fail_divide_by_zero",     fail_divide_by_zero_fn;
+    FailRemByZeroFnLangItem,         "fail_rem_by_zero",        fail_rem_by_zero_fn;
     BeginUnwindLangItem,             "begin_unwind",            begin_unwind;
 
+    // Like `begin_unwind`, the `oom` lang item is a weak lang item which is
+    // invoked by liballoc when an allocation fails.
+    OomLangItem,                     "oom",                     oom;
+
     ExchangeMallocFnLangItem,        "exchange_malloc",         exchange_malloc_fn;
     ClosureExchangeMallocFnLangItem, "closure_exchange_malloc", closure_exchange_malloc_fn;
     ExchangeFreeFnLangItem,          "exchange_free",           exchange_free_fn;
@@ -281,6 +293,8 @@ lets_do_this! {
     GcLangItem,                      "gc",                      gc;
     OwnedBoxLangItem,                "owned_box",               owned_box;
 
+    NonZeroItem,                     "non_zero",                non_zero;
+
     CovariantTypeItem,               "covariant_type",          covariant_type;
     ContravariantTypeItem,           "contravariant_type",      contravariant_type;
     InvariantTypeItem,               "invariant_type",          invariant_type;
@@ -123,6 +123,8 @@ pub enum Lint {
     Warnings,
 
     RawPointerDeriving,
+
+    MissingNoUnwind,
 }
 
 pub fn level_to_str(lv: Level) -> &'static str {
@@ -443,6 +445,13 @@ static lint_table: &'static [(&'static str, LintSpec)] = &[
         desc: "uses of #[deriving] with raw pointers are rarely correct",
         default: Warn,
     }),
+
+    ("missing_no_unwind",
+     LintSpec {
+        lint: MissingNoUnwind,
+        desc: "detects extern functions which may unwind into foreign code",
+        default: Allow,
+    }),
 ];
 
 /*
@@ -1118,12 +1127,46 @@ static other_attrs: &'static [&'static str] = &[
 
     // fn-level
     "test", "bench", "should_fail", "ignore", "inline", "lang", "main", "start",
-    "no_split_stack", "cold", "macro_registrar", "linkage",
+    "no_split_stack", "cold", "macro_registrar", "linkage", "rustc_no_unwind",
+    "rustc_begin_unwind",
 
     // internal attribute: bypass privacy inside items
     "!resolve_unexported",
 ];
 
+fn check_missing_no_unwind(cx: &Context, it: &ast::Item) {
+    // Failure unwinding out of a function with a foreign ABI unwinds into the
+    // foreign code which called it, which is undefined behavior.
+    match it.node {
+        ast::ItemFn(_, _, abi, _, _) if abi != abi::Rust => {
+            if !attr::contains_name(it.attrs.as_slice(), "rustc_no_unwind") {
+                cx.span_lint(MissingNoUnwind, it.span,
+                             "extern function is not marked \
+                              #[rustc_no_unwind], failure will unwind \
+                              into foreign code");
+            }
+        }
+        _ => {}
+    }
+}
+
+// Only functions have a stack check which can be left out, so the attribute
+// does nothing anywhere else.
+fn check_no_split_stack_usage(cx: &Context, attrs: &[ast::Attribute],
+                              crate_level: bool) {
+    for attr in attrs.iter() {
+        if !attr.name().equiv(&("no_split_stack")) { continue }
+        let msg = if crate_level {
+            "`no_split_stack` has no effect on a crate, compile with \
+             `-C no-stack-check` to leave out the stack checks of every \
+             function"
+        } else {
+            "`no_split_stack` only applies to functions"
+        };
+        cx.span_lint(AttributeUsage, attr.span, msg);
+    }
+}
+
 fn check_crate_attrs_usage(cx: &Context, attrs: &[ast::Attribute]) {
 
     for attr in attrs.iter() {
@@ -1190,6 +1233,7 @@ fn check_unused_attribute(cx: &Context, attrs: &[ast::Attribute]) {
         "no_mangle",
         "no_split_stack",
         "packed",
+        "rustc_no_unwind",
         "static_assert",
         "thread_local",
 
@@ -1492,9 +1536,8 @@ fn check_unused_mut_pat(cx: &Context, pats: &[@ast::Pat]) {
                     }
                     let ident = path.segments.get(0).identifier;
                     if !token::get_ident(ident).get().starts_with("_") {
-                        mutables.insert_or_update_with(ident.name as uint, vec!(id), |_, old| {
-                            old.push(id);
-                        });
+                        mutables.entry(ident.name as uint)
+                                .or_insert_with(|_| Vec::new()).push(id);
                     }
                 }
                 _ => {
@@ -1793,6 +1836,11 @@ impl<'a> Visitor<()> for Context<'a> {
             check_attrs_usage(cx, it.attrs.as_slice());
             check_unused_attribute(cx, it.attrs.as_slice());
             check_raw_ptr_deriving(cx, it);
+            check_missing_no_unwind(cx, it);
+            match it.node {
+                ast::ItemFn(..) => {}
+                _ => check_no_split_stack_usage(cx, it.attrs.as_slice(), false),
+            }
 
             cx.visit_ids(|v| v.visit_item(it, ()));
 
@@ -1803,6 +1851,7 @@ impl<'a> Visitor<()> for Context<'a> {
     fn visit_foreign_item(&mut self, it: &ast::ForeignItem, _: ()) {
         self.with_lint_attrs(it.attrs.as_slice(), |cx| {
             check_attrs_usage(cx, it.attrs.as_slice());
+            check_no_split_stack_usage(cx, it.attrs.as_slice(), false);
             visit::walk_foreign_item(cx, it, ());
         })
     }
@@ -1997,6 +2046,7 @@ pub fn check_crate(tcx: &ty::ctxt,
         });
 
         check_crate_attrs_usage(cx, krate.attrs.as_slice());
+        check_no_split_stack_usage(cx, krate.attrs.as_slice(), true);
         // since the root module isn't visited as an item (because it isn't an item), warn for it
         // here.
         check_unused_attribute(cx, krate.attrs.as_slice());
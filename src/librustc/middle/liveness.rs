@@ -525,7 +525,7 @@ fn visit_expr(ir: &mut IrMaps, expr: &Expr) {
       }
 
       // otherwise, live nodes are not required:
-      ExprIndex(..) | ExprField(..) | ExprVstore(..) | ExprVec(..) |
+      ExprIndex(..) | ExprSlice(..) | ExprField(..) | ExprVstore(..) | ExprVec(..) |
       ExprCall(..) | ExprMethodCall(..) | ExprTup(..) |
       ExprBinary(..) | ExprAddrOf(..) |
       ExprCast(..) | ExprUnary(..) | ExprBreak(_) |
@@ -1183,6 +1183,12 @@ impl<'a> Liveness<'a> {
             self.propagate_through_exprs([l, r], succ)
           }
 
+          ExprSlice(e, e1, e2, _) => {
+            let succ = self.propagate_through_opt_expr(e2, succ);
+            let succ = self.propagate_through_opt_expr(e1, succ);
+            self.propagate_through_expr(e, succ)
+          }
+
           ExprAddrOf(_, e) |
           ExprCast(e, _) |
           ExprUnary(_, e) |
@@ -1437,7 +1443,7 @@ fn check_expr(this: &mut Liveness, expr: &Expr) {
 
       // no correctness conditions related to liveness
       ExprCall(..) | ExprMethodCall(..) | ExprIf(..) | ExprMatch(..) |
-      ExprWhile(..) | ExprLoop(..) | ExprIndex(..) | ExprField(..) |
+      ExprWhile(..) | ExprLoop(..) | ExprIndex(..) | ExprSlice(..) | ExprField(..) |
       ExprVstore(..) | ExprVec(..) | ExprTup(..) |
       ExprBinary(..) |
       ExprCast(..) | ExprUnary(..) | ExprRet(..) | ExprBreak(..) |
@@ -272,6 +272,8 @@ pub trait Typer {
     fn tcx<'a>(&'a self) -> &'a ty::ctxt;
     fn node_ty(&self, id: ast::NodeId) -> McResult<ty::t>;
     fn node_method_ty(&self, method_call: typeck::MethodCall) -> Option<ty::t>;
+    fn node_method_origin(&self, method_call: typeck::MethodCall)
+                          -> Option<typeck::MethodOrigin>;
     fn adjustments<'a>(&'a self) -> &'a RefCell<NodeMap<ty::AutoAdjustment>>;
     fn is_method_call(&self, id: ast::NodeId) -> bool;
     fn temporary_scope(&self, rvalue_id: ast::NodeId) -> Option<ast::NodeId>;
@@ -452,8 +454,18 @@ impl<'t,TYPER:Typer> MemCategorizationContext<'t,TYPER> {
           }
 
           ast::ExprIndex(base, _) => {
-            if self.typer.is_method_call(expr.id) {
-                return Ok(self.cat_rvalue_node(expr.id(), expr.span(), expr_ty));
+            let method_call = typeck::MethodCall::expr(expr.id());
+            match self.typer.node_method_origin(method_call) {
+                Some(ref origin) if ty::method_origin_is_index_mut(self.tcx(), origin) => {
+                    // `index_mut` returns a `&mut` pointer to the element,
+                    // which is then dereferenced just like an overloaded `*`.
+                    let base_cmt = if_ok!(self.cat_expr(base));
+                    return Ok(self.cat_deref(expr, base_cmt, 0));
+                }
+                Some(_) => {
+                    return Ok(self.cat_rvalue_node(expr.id(), expr.span(), expr_ty));
+                }
+                None => {}
             }
 
             let base_cmt = if_ok!(self.cat_expr(base));
@@ -473,7 +485,8 @@ impl<'t,TYPER:Typer> MemCategorizationContext<'t,TYPER> {
           ast::ExprAssign(..) | ast::ExprAssignOp(..) |
           ast::ExprFnBlock(..) | ast::ExprProc(..) | ast::ExprRet(..) |
           ast::ExprUnary(..) |
-          ast::ExprMethodCall(..) | ast::ExprCast(..) | ast::ExprVstore(..) |
+          ast::ExprMethodCall(..) | ast::ExprSlice(..) |
+          ast::ExprCast(..) | ast::ExprVstore(..) |
           ast::ExprVec(..) | ast::ExprTup(..) | ast::ExprIf(..) |
           ast::ExprBinary(..) | ast::ExprWhile(..) |
           ast::ExprBlock(..) | ast::ExprLoop(..) | ast::ExprMatch(..) |
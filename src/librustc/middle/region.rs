@@ -517,7 +517,7 @@ fn resolve_expr(visitor: &mut RegionResolutionVisitor,
             new_cx.var_parent = Some(expr.id);
         }
 
-        ast::ExprAssignOp(..) | ast::ExprIndex(..) |
+        ast::ExprAssignOp(..) | ast::ExprIndex(..) | ast::ExprSlice(..) |
         ast::ExprUnary(..) | ast::ExprCall(..) | ast::ExprMethodCall(..) => {
             // FIXME(#6268) Nested method calls
             //
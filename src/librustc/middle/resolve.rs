@@ -32,6 +32,7 @@ use syntax::visit;
 use syntax::visit::Visitor;
 
 use collections::{HashMap, HashSet};
+use collections::hashmap::{Occupied, Vacant};
 use std::cell::{Cell, RefCell};
 use std::mem::replace;
 use std::rc::{Rc, Weak};
@@ -2590,7 +2591,7 @@ impl<'a> Resolver<'a> {
                                name: Name,
                                name_bindings: Rc<NameBindings>) {
         let mut import_resolutions = module_.import_resolutions.borrow_mut();
-        let dest_import_resolution = import_resolutions.find_or_insert_with(name, |_| {
+        let dest_import_resolution = import_resolutions.entry(name).or_insert_with(|_| {
             // Create a new import resolution from this child.
             ImportResolution::new(id, is_public)
         });
@@ -5358,19 +5359,20 @@ impl<'a> Resolver<'a> {
         assert!(match lp {LastImport{..} => false, _ => true},
                 "Import should only be used for `use` directives");
         self.last_private.insert(node_id, lp);
-        self.def_map.borrow_mut().insert_or_update_with(node_id, def, |_, old_value| {
+        match self.def_map.borrow_mut().entry(node_id) {
             // Resolve appears to "resolve" the same ID multiple
             // times, so here is a sanity check it at least comes to
             // the same conclusion! - nmatsakis
-            if def != *old_value {
+            Occupied(entry) => if def != *entry.get() {
                 self.session
                     .bug(format!("node_id {:?} resolved first to {:?} and \
                                   then {:?}",
                                  node_id,
-                                 *old_value,
+                                 *entry.get(),
                                  def).as_slice());
-            }
-        });
+            },
+            Vacant(entry) => { entry.set(def); }
+        }
     }
 
     fn enforce_default_binding_mode(&mut self,
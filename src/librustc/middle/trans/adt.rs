@@ -99,10 +99,14 @@ pub enum Repr {
     },
     /**
      * Two cases distinguished by a nullable pointer: the case with discriminant
-     * `nndiscr` is represented by the struct `nonnull`, where the `ptrfield`th
-     * field is known to be nonnull due to its type; if that field is null, then
-     * it represents the other case, which is inhabited by at most one value
-     * (and all other fields are undefined/unused).
+     * `nndiscr` is represented by the struct `nonnull`, where the field found
+     * by following the `ptrfield` path is known to be nonnull due to its type;
+     * if that field is null, then it represents the other case, which is
+     * inhabited by at most one value (and all other fields are
+     * undefined/unused).
+     *
+     * The path usually has a single element, the index of a pointer field,
+     * but it leads into nested structs to reach a pointer or a `NonZero`.
      *
      * For example, `std::option::Option` instantiated at a safe pointer type
      * is represented such that `None` is a null pointer and `Some` is the
@@ -111,11 +115,15 @@ pub enum Repr {
     StructWrappedNullablePointer {
         pub nonnull: Struct,
         pub nndiscr: Disr,
-        pub ptrfield: uint,
+        pub ptrfield: DiscrField,
         pub nullfields: Vec<ty::t>,
     }
 }
 
+/// The path of field indices, starting from the fields of an enum variant, to
+/// the field whose nullness encodes the discriminant.
+pub type DiscrField = Vec<uint>;
+
 /// For structs, and struct-like parts of anything fancier.
 pub struct Struct {
     pub size: u64,
@@ -211,12 +219,12 @@ fn represent_type_uncached(cx: &CrateContext, t: ty::t) -> Repr {
                 let mut discr = 0;
                 while discr < 2 {
                     if cases.get(1 - discr).is_zerolen(cx) {
-                        match cases.get(discr).find_ptr() {
+                        match cases.get(discr).find_ptr(cx.tcx()) {
                             Some(ptrfield) => {
                                 let st = mk_struct(cx, cases.get(discr).tys.as_slice(),
                                                    false);
 
-                                return if st.fields.len() == 1 {
+                                return if st.fields.len() == 1 && ptrfield.len() == 1 {
                                     RawNullablePointer {
                                         nndiscr: discr as Disr,
                                         nnty: *st.fields.get(0),
@@ -288,19 +296,55 @@ impl Case {
     fn is_zerolen(&self, cx: &CrateContext) -> bool {
         mk_struct(cx, self.tys.as_slice(), false).size == 0
     }
-    fn find_ptr(&self) -> Option<uint> {
-        self.tys.iter().position(|&ty| {
-            match ty::get(ty).sty {
-                ty::ty_rptr(_, mt) => match ty::get(mt.ty).sty {
-                    ty::ty_vec(_, None) | ty::ty_str => false,
-                    _ => true,
-                },
-                ty::ty_uniq(..) | ty::ty_box(..) |
-                ty::ty_bare_fn(..) => true,
-                // Is that everything?  Would closures or slices qualify?
-                _ => false
+    fn find_ptr(&self, tcx: &ty::ctxt) -> Option<DiscrField> {
+        for (i, &ty) in self.tys.iter().enumerate() {
+            match find_nonnull_field(tcx, ty) {
+                Some(mut path) => {
+                    path.push(i);
+                    path.reverse();
+                    return Some(path);
+                }
+                None => {}
+            }
+        }
+        None
+    }
+}
+
+/// Finds a field within a value of type `ty` which can never be null, returning
+/// the path to it in reverse order (innermost field first). The path is empty
+/// if the value itself is a non-null pointer.
+fn find_nonnull_field(tcx: &ty::ctxt, ty: ty::t) -> Option<DiscrField> {
+    match ty::get(ty).sty {
+        ty::ty_rptr(_, mt) => match ty::get(mt.ty).sty {
+            ty::ty_vec(_, None) | ty::ty_str => None,
+            _ => Some(Vec::new()),
+        },
+        ty::ty_uniq(..) | ty::ty_box(..) |
+        ty::ty_bare_fn(..) => Some(Vec::new()),
+
+        // The value wrapped by a `NonZero` is never zero.
+        ty::ty_struct(did, _) if Some(did) == tcx.lang_items.non_zero() => {
+            Some(vec!(0))
+        }
+
+        ty::ty_struct(did, ref substs) => {
+            let fields = ty::lookup_struct_fields(tcx, did);
+            for (j, field) in fields.iter().enumerate() {
+                let field_ty = ty::lookup_field_type(tcx, did, field.id, substs);
+                match find_nonnull_field(tcx, field_ty) {
+                    Some(mut path) => {
+                        path.push(j);
+                        return Some(path);
+                    }
+                    None => {}
+                }
             }
-        })
+            None
+        }
+
+        // Is that everything?  Would closures or slices qualify?
+        _ => None
     }
 }
 
@@ -552,8 +596,8 @@ pub fn trans_get_discr(bcx: &Block, r: &Repr, scrutinee: ValueRef, cast_to: Opti
             val = ICmp(bcx, cmp, Load(bcx, scrutinee), C_null(llptrty));
             signed = false;
         }
-        StructWrappedNullablePointer { nonnull: ref nonnull, nndiscr, ptrfield, .. } => {
-            val = struct_wrapped_nullable_bitdiscr(bcx, nonnull, nndiscr, ptrfield, scrutinee);
+        StructWrappedNullablePointer { nndiscr, ref ptrfield, .. } => {
+            val = struct_wrapped_nullable_bitdiscr(bcx, nndiscr, ptrfield, scrutinee);
             signed = false;
         }
     }
@@ -563,12 +607,19 @@ pub fn trans_get_discr(bcx: &Block, r: &Repr, scrutinee: ValueRef, cast_to: Opti
     }
 }
 
-fn struct_wrapped_nullable_bitdiscr(bcx: &Block, nonnull: &Struct, nndiscr: Disr, ptrfield: uint,
+fn struct_wrapped_nullable_bitdiscr(bcx: &Block, nndiscr: Disr, ptrfield: &DiscrField,
                                     scrutinee: ValueRef) -> ValueRef {
-    let llptr = Load(bcx, GEPi(bcx, scrutinee, [0, ptrfield]));
+    let llptr = Load(bcx, struct_wrapped_nullable_field_ptr(bcx, ptrfield, scrutinee));
     let cmp = if nndiscr == 0 { IntEQ } else { IntNE };
-    let llptrty = type_of::type_of(bcx.ccx(), *nonnull.fields.get(ptrfield));
-    ICmp(bcx, cmp, llptr, C_null(llptrty))
+    ICmp(bcx, cmp, llptr, C_null(val_ty(llptr)))
+}
+
+/// Access the field which encodes the discriminant of a
+/// `StructWrappedNullablePointer`.
+fn struct_wrapped_nullable_field_ptr(bcx: &Block, ptrfield: &DiscrField,
+                                     val: ValueRef) -> ValueRef {
+    let path = vec!(0u).append(ptrfield.as_slice());
+    GEPi(bcx, val, path.as_slice())
 }
 
 /// Helper for cases where the discriminant is simply loaded.
@@ -652,11 +703,10 @@ pub fn trans_start_init(bcx: &Block, r: &Repr, val: ValueRef, discr: Disr) {
                 Store(bcx, C_null(llptrty), val)
             }
         }
-        StructWrappedNullablePointer { nonnull: ref nonnull, nndiscr, ptrfield, .. } => {
+        StructWrappedNullablePointer { nndiscr, ref ptrfield, .. } => {
             if discr != nndiscr {
-                let llptrptr = GEPi(bcx, val, [0, ptrfield]);
-                let llptrty = type_of::type_of(bcx.ccx(),
-                                               *nonnull.fields.get(ptrfield));
+                let llptrptr = struct_wrapped_nullable_field_ptr(bcx, ptrfield, val);
+                let llptrty = val_ty(llptrptr).element_type();
                 Store(bcx, C_null(llptrty), llptrptr)
             }
         }
@@ -923,8 +973,9 @@ pub fn const_get_discrim(ccx: &CrateContext, r: &Repr, val: ValueRef)
                 nndiscr
             }
         }
-        StructWrappedNullablePointer { nndiscr, ptrfield, .. } => {
-            if is_null(const_struct_field(ccx, val, ptrfield)) {
+        StructWrappedNullablePointer { nndiscr, ref ptrfield, .. } => {
+            let field = ptrfield.iter().fold(val, |v, &ix| const_struct_field(ccx, v, ix));
+            if is_null(field) {
                 /* subtraction as uint is ok because nndiscr is either 0 or 1 */
                 (1 - nndiscr) as Disr
             } else {
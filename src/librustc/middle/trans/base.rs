@@ -39,6 +39,7 @@ use metadata::{csearch, encoder};
 use middle::lint;
 use middle::astencode;
 use middle::lang_items::{LangItem, ExchangeMallocFnLangItem, StartFnLangItem};
+use middle::lang_items::{FailDivideByZeroFnLangItem, FailRemByZeroFnLangItem};
 use middle::weak_lang_items;
 use middle::trans::_match;
 use middle::trans::adt;
@@ -83,7 +84,7 @@ use syntax::ast_util::{local_def, is_local};
 use syntax::attr::AttrMetaMethods;
 use syntax::attr;
 use syntax::codemap::Span;
-use syntax::parse::token::InternedString;
+use syntax::parse::token;
 use syntax::visit::Visitor;
 use syntax::visit;
 use syntax::{ast, ast_util, ast_map};
@@ -783,10 +784,10 @@ pub fn fail_if_zero<'a>(
                     rhs: ValueRef,
                     rhs_t: ty::t)
                     -> &'a Block<'a> {
-    let text = if divrem == ast::BiDiv {
-        "attempted to divide by zero"
+    let lang_item = if divrem == ast::BiDiv {
+        FailDivideByZeroFnLangItem
     } else {
-        "attempted remainder with a divisor of zero"
+        FailRemByZeroFnLangItem
     };
     let is_zero = match ty::get(rhs_t).sty {
       ty::ty_int(t) => {
@@ -803,10 +804,74 @@ pub fn fail_if_zero<'a>(
       }
     };
     with_cond(cx, is_zero, |bcx| {
-        controlflow::trans_fail(bcx, span, InternedString::new(text))
+        controlflow::trans_fail_at(bcx, span, lang_item)
     })
 }
 
+// Adds, subtracts or multiplies two integers, failing if the result
+// overflows. Only used when overflow checks have been asked for.
+pub fn trans_overflow_checked_binop<'a>(
+                                    cx: &'a Block<'a>,
+                                    span: Span,
+                                    op: ast::BinOp,
+                                    lhs: ValueRef,
+                                    rhs: ValueRef,
+                                    t: ty::t)
+                                    -> (&'a Block<'a>, ValueRef) {
+    let signed = match ty::get(t).sty {
+        ty::ty_int(..) => true,
+        ty::ty_uint(..) => false,
+        _ => {
+            cx.sess().bug(format!("overflow check on unexpected type: {}",
+                                  ty_to_str(cx.tcx(), t)).as_slice());
+        }
+    };
+    let bits = machine::llbitsize_of_real(cx.ccx(), val_ty(lhs));
+    let name = match (op, signed, bits) {
+        (ast::BiAdd, true, 8) => "llvm.sadd.with.overflow.i8",
+        (ast::BiAdd, true, 16) => "llvm.sadd.with.overflow.i16",
+        (ast::BiAdd, true, 32) => "llvm.sadd.with.overflow.i32",
+        (ast::BiAdd, true, 64) => "llvm.sadd.with.overflow.i64",
+        (ast::BiAdd, false, 8) => "llvm.uadd.with.overflow.i8",
+        (ast::BiAdd, false, 16) => "llvm.uadd.with.overflow.i16",
+        (ast::BiAdd, false, 32) => "llvm.uadd.with.overflow.i32",
+        (ast::BiAdd, false, 64) => "llvm.uadd.with.overflow.i64",
+        (ast::BiSub, true, 8) => "llvm.ssub.with.overflow.i8",
+        (ast::BiSub, true, 16) => "llvm.ssub.with.overflow.i16",
+        (ast::BiSub, true, 32) => "llvm.ssub.with.overflow.i32",
+        (ast::BiSub, true, 64) => "llvm.ssub.with.overflow.i64",
+        (ast::BiSub, false, 8) => "llvm.usub.with.overflow.i8",
+        (ast::BiSub, false, 16) => "llvm.usub.with.overflow.i16",
+        (ast::BiSub, false, 32) => "llvm.usub.with.overflow.i32",
+        (ast::BiSub, false, 64) => "llvm.usub.with.overflow.i64",
+        (ast::BiMul, true, 8) => "llvm.smul.with.overflow.i8",
+        (ast::BiMul, true, 16) => "llvm.smul.with.overflow.i16",
+        (ast::BiMul, true, 32) => "llvm.smul.with.overflow.i32",
+        (ast::BiMul, true, 64) => "llvm.smul.with.overflow.i64",
+        (ast::BiMul, false, 8) => "llvm.umul.with.overflow.i8",
+        (ast::BiMul, false, 16) => "llvm.umul.with.overflow.i16",
+        (ast::BiMul, false, 32) => "llvm.umul.with.overflow.i32",
+        (ast::BiMul, false, 64) => "llvm.umul.with.overflow.i64",
+        _ => cx.sess().bug("unexpected overflow-checked binop"),
+    };
+    let opname = match op {
+        ast::BiAdd => "add",
+        ast::BiSub => "subtract",
+        _ => "multiply",
+    };
+
+    let llfn = cx.ccx().get_intrinsic(&name);
+    let res = Call(cx, llfn, [lhs, rhs], []);
+    let val = ExtractValue(cx, res, 0);
+    let overflowed = ExtractValue(cx, res, 1);
+    let bcx = with_cond(cx, overflowed, |bcx| {
+        controlflow::trans_fail_overflow(bcx, span,
+                                         token::intern_and_get_ident(opname),
+                                         signed, lhs, rhs)
+    });
+    (bcx, val)
+}
+
 pub fn trans_external_path(ccx: &CrateContext, did: ast::DefId, t: ty::t) -> ValueRef {
     let name = csearch::get_symbol(&ccx.sess().cstore, did);
     match ty::get(t).sty {
@@ -1304,7 +1369,7 @@ pub fn trans_closure(ccx: &CrateContext,
                      llfndecl: ValueRef,
                      param_substs: Option<&param_substs>,
                      id: ast::NodeId,
-                     _attributes: &[ast::Attribute],
+                     attributes: &[ast::Attribute],
                      output_type: ty::t,
                      maybe_load_env: <'a> |&'a Block<'a>| -> &'a Block<'a>) {
     ccx.stats.n_closures.set(ccx.stats.n_closures.get() + 1);
@@ -1334,6 +1399,14 @@ pub fn trans_closure(ccx: &CrateContext,
     // cleanup scope for the incoming arguments
     let arg_scope = fcx.push_custom_cleanup_scope();
 
+    // A function which must not unwind converts any failure escaping its body
+    // into an abort, once the cleanups of the body have been run. Being
+    // scheduled first, this is the last cleanup to run on the way out.
+    if attr::contains_name(attributes, "rustc_no_unwind") {
+        fcx.schedule_clean(cleanup::CustomScope(arg_scope),
+                           box cleanup::AbortOnUnwind as Box<cleanup::Cleanup>);
+    }
+
     // Create the first basic block in the function and keep a handle on it to
     //  pass to finish_fn later.
     let bcx_top = fcx.entry_bcx.borrow().clone().unwrap();
@@ -2287,6 +2360,19 @@ pub fn trans_crate(krate: ast::Crate,
     }
 
     glue::emit_tydescs(&ccx);
+
+    // Functions are declared with stack checks, which `-C no-stack-check`
+    // leaves out of everything translated into this crate.
+    if ccx.sess().opts.cg.no_stack_check {
+        unsafe {
+            let mut llfn = llvm::LLVMGetFirstFunction(ccx.llmod);
+            while llfn.is_not_null() {
+                unset_split_stack(llfn);
+                llfn = llvm::LLVMGetNextFunction(llfn);
+            }
+        }
+    }
+
     if ccx.sess().opts.debuginfo != NoDebugInfo {
         debuginfo::finalize(&ccx);
     }
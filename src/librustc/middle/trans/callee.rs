@@ -29,6 +29,7 @@ use middle::trans::cleanup;
 use middle::trans::cleanup::CleanupMethods;
 use middle::trans::common;
 use middle::trans::common::*;
+use middle::trans::controlflow;
 use middle::trans::datum::*;
 use middle::trans::datum::Datum;
 use middle::trans::expr;
@@ -456,6 +457,13 @@ pub fn trans_call<'a>(
                   dest: expr::Dest)
                   -> &'a Block<'a> {
     let _icx = push_ctxt("trans_call");
+
+    // With -Z abort-on-fail, failing explicitly (with `fail!` and friends)
+    // traps just like the failures emitted by the compiler itself.
+    if in_cx.sess().abort_on_fail() && calls_begin_unwind(in_cx, f) {
+        return controlflow::trans_fail_abort(in_cx);
+    }
+
     trans_call_inner(in_cx,
                      Some(common::expr_info(call_ex)),
                      expr_ty(in_cx, f),
@@ -464,6 +472,17 @@ pub fn trans_call<'a>(
                      Some(dest)).bcx
 }
 
+// Whether `f` names one of the functions through which the runtime begins
+// unwinding, which are marked `#[rustc_begin_unwind]`
+fn calls_begin_unwind(bcx: &Block, f: &ast::Expr) -> bool {
+    match bcx.tcx().def_map.borrow().find(&f.id) {
+        Some(&ast::DefFn(did, _)) => {
+            ty::has_attr(bcx.tcx(), did, "rustc_begin_unwind")
+        }
+        _ => false
+    }
+}
+
 pub fn trans_method_call<'a>(
                          bcx: &'a Block<'a>,
                          call_ex: &ast::Expr,
@@ -699,10 +718,10 @@ pub enum CallArgs<'a> {
     // value.
     ArgVals(&'a [ValueRef]),
 
-    // For overloaded operators: `(lhs, Option(rhs, rhs_id))`. `lhs`
+    // For overloaded operators: `(lhs, Vec(rhs, rhs_id))`. `lhs`
     // is the left-hand-side and `rhs/rhs_id` is the datum/expr-id of
-    // the right-hand-side (if any).
-    ArgOverloadedOp(Datum<Expr>, Option<(Datum<Expr>, ast::NodeId)>),
+    // each of the remaining operands (if any).
+    ArgOverloadedOp(Datum<Expr>, Vec<(Datum<Expr>, ast::NodeId)>),
 }
 
 fn trans_args<'a>(cx: &'a Block<'a>,
@@ -752,17 +771,13 @@ fn trans_args<'a>(cx: &'a Block<'a>,
                                 DontAutorefArg)
             }));
 
-            match rhs {
-                Some((rhs, rhs_id)) => {
-                    assert_eq!(arg_tys.len(), 2);
-
-                    llargs.push(unpack_result!(bcx, {
-                        trans_arg_datum(bcx, *arg_tys.get(1), rhs,
-                                        arg_cleanup_scope,
-                                        DoAutorefArg(rhs_id))
-                    }));
-                }
-                None => assert_eq!(arg_tys.len(), 1)
+            assert_eq!(arg_tys.len(), 1 + rhs.len());
+            for (i, (rhs, rhs_id)) in rhs.move_iter().enumerate() {
+                llargs.push(unpack_result!(bcx, {
+                    trans_arg_datum(bcx, *arg_tys.get(i + 1), rhs,
+                                    arg_cleanup_scope,
+                                    DoAutorefArg(rhs_id))
+                }));
             }
         }
         ArgVals(vs) => {
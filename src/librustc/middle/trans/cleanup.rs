@@ -69,6 +69,7 @@ pub struct CachedEarlyExit {
 
 pub trait Cleanup {
     fn clean_on_unwind(&self) -> bool;
+    fn clean_on_normal_exit(&self) -> bool;
     fn trans<'a>(&self, bcx: &'a Block<'a>) -> &'a Block<'a>;
 }
 
@@ -437,7 +438,9 @@ impl<'a> CleanupHelperMethods<'a> for FunctionContext<'a> {
         let mut bcx = bcx;
         if !bcx.unreachable.get() {
             for cleanup in scope.cleanups.iter().rev() {
-                bcx = cleanup.trans(bcx);
+                if cleanup.clean_on_normal_exit() {
+                    bcx = cleanup.trans(bcx);
+                }
             }
         }
         bcx
@@ -831,6 +834,10 @@ impl Cleanup for DropValue {
         self.on_unwind
     }
 
+    fn clean_on_normal_exit(&self) -> bool {
+        true
+    }
+
     fn trans<'a>(&self, bcx: &'a Block<'a>) -> &'a Block<'a> {
         if self.is_immediate {
             glue::drop_ty_immediate(bcx, self.val, self.ty)
@@ -856,6 +863,10 @@ impl Cleanup for FreeValue {
         true
     }
 
+    fn clean_on_normal_exit(&self) -> bool {
+        true
+    }
+
     fn trans<'a>(&self, bcx: &'a Block<'a>) -> &'a Block<'a> {
         match self.heap {
             HeapManaged => {
@@ -868,6 +879,28 @@ impl Cleanup for FreeValue {
     }
 }
 
+/// Aborts the process if unwinding reaches the scope this cleanup belongs to.
+/// Used for functions marked `#[rustc_no_unwind]`, which must never unwind
+/// into their caller.
+pub struct AbortOnUnwind;
+
+impl Cleanup for AbortOnUnwind {
+    fn clean_on_unwind(&self) -> bool {
+        true
+    }
+
+    fn clean_on_normal_exit(&self) -> bool {
+        false
+    }
+
+    fn trans<'a>(&self, bcx: &'a Block<'a>) -> &'a Block<'a> {
+        let llfn = bcx.ccx().get_intrinsic(&("llvm.trap"));
+        build::Call(bcx, llfn, [], []);
+        build::Unreachable(bcx);
+        bcx
+    }
+}
+
 pub fn temporary_scope(tcx: &ty::ctxt,
                        id: ast::NodeId)
                        -> ScopeId {
@@ -894,7 +927,11 @@ pub fn var_scope(tcx: &ty::ctxt,
 
 fn cleanup_is_suitable_for(c: &Cleanup,
                            label: EarlyExitLabel) -> bool {
-    !label.is_unwind() || c.clean_on_unwind()
+    if label.is_unwind() {
+        c.clean_on_unwind()
+    } else {
+        c.clean_on_normal_exit()
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -11,6 +11,7 @@
 use lib::llvm::*;
 use driver::config::FullDebugInfo;
 use middle::lang_items::{FailFnLangItem, FailBoundsCheckFnLangItem};
+use middle::lang_items::{FailOverflowFnLangItem, LangItem};
 use middle::trans::base::*;
 use middle::trans::build::*;
 use middle::trans::callee;
@@ -19,6 +20,7 @@ use middle::trans::cleanup;
 use middle::trans::common::*;
 use middle::trans::debuginfo;
 use middle::trans::expr;
+use middle::trans::type_::Type;
 use middle::trans::type_of;
 use middle::ty;
 use util::ppaux::Repr;
@@ -26,7 +28,7 @@ use util::ppaux::Repr;
 use syntax::ast;
 use syntax::ast::Ident;
 use syntax::ast_util;
-use syntax::codemap::Span;
+use syntax::codemap::{Pos, Span};
 use syntax::parse::token::InternedString;
 use syntax::parse::token;
 use syntax::visit::Visitor;
@@ -348,6 +350,15 @@ fn str_slice_arg<'a>(bcx: &'a Block<'a>, s: InternedString) -> ValueRef {
     BitCast(bcx, slot, type_of::type_of(ccx, t).ptr_to())
 }
 
+pub fn trans_fail_abort<'a>(bcx: &'a Block<'a>) -> &'a Block<'a> {
+    // With -Z abort-on-fail there is nothing to unwind, so failure is
+    // translated directly into a trap rather than a call into the runtime.
+    let llfn = bcx.ccx().get_intrinsic(&("llvm.trap"));
+    Call(bcx, llfn, [], []);
+    Unreachable(bcx);
+    return bcx;
+}
+
 pub fn trans_fail<'a>(
                   bcx: &'a Block<'a>,
                   sp: Span,
@@ -356,12 +367,17 @@ pub fn trans_fail<'a>(
     let ccx = bcx.ccx();
     let _icx = push_ctxt("trans_fail_value");
 
+    if bcx.sess().abort_on_fail() {
+        return trans_fail_abort(bcx);
+    }
+
     let v_str = str_slice_arg(bcx, fail_str);
     let loc = bcx.sess().codemap().lookup_char_pos(sp.lo);
     let filename = token::intern_and_get_ident(loc.file.name.as_slice());
     let v_filename = str_slice_arg(bcx, filename);
     let v_line = loc.line as int;
-    let args = vec!(v_str, v_filename, C_int(ccx, v_line));
+    let v_col = loc.col.to_uint() as int + 1;
+    let args = vec!(v_str, v_filename, C_int(ccx, v_line), C_int(ccx, v_col));
     let did = langcall(bcx, Some(sp), "", FailFnLangItem);
     let bcx = callee::trans_lang_call(bcx,
                                       did,
@@ -371,6 +387,39 @@ pub fn trans_fail<'a>(
     return bcx;
 }
 
+/// Invokes a failure lang item which only takes the location of the failure
+/// as its arguments, such as `fail_divide_by_zero`. Keeping the message in
+/// the lang item itself keeps these (potentially very common) call sites
+/// small.
+pub fn trans_fail_at<'a>(
+                     bcx: &'a Block<'a>,
+                     sp: Span,
+                     lang_item: LangItem)
+                     -> &'a Block<'a> {
+    let _icx = push_ctxt("trans_fail_at");
+
+    if bcx.sess().abort_on_fail() {
+        return trans_fail_abort(bcx);
+    }
+
+    // Extract the file/line/column from the span
+    let loc = bcx.sess().codemap().lookup_char_pos(sp.lo);
+    let filename = token::intern_and_get_ident(loc.file.name.as_slice());
+
+    // Invoke the lang item
+    let filename = str_slice_arg(bcx, filename);
+    let line = C_int(bcx.ccx(), loc.line as int);
+    let col = C_int(bcx.ccx(), loc.col.to_uint() as int + 1);
+    let args = vec!(filename, line, col);
+    let did = langcall(bcx, Some(sp), "", lang_item);
+    let bcx = callee::trans_lang_call(bcx,
+                                      did,
+                                      args.as_slice(),
+                                      Some(expr::Ignore)).bcx;
+    Unreachable(bcx);
+    return bcx;
+}
+
 pub fn trans_fail_bounds_check<'a>(
                                bcx: &'a Block<'a>,
                                sp: Span,
@@ -379,14 +428,19 @@ pub fn trans_fail_bounds_check<'a>(
                                -> &'a Block<'a> {
     let _icx = push_ctxt("trans_fail_bounds_check");
 
-    // Extract the file/line from the span
+    if bcx.sess().abort_on_fail() {
+        return trans_fail_abort(bcx);
+    }
+
+    // Extract the file/line/column from the span
     let loc = bcx.sess().codemap().lookup_char_pos(sp.lo);
     let filename = token::intern_and_get_ident(loc.file.name.as_slice());
 
     // Invoke the lang item
     let filename = str_slice_arg(bcx, filename);
     let line = C_int(bcx.ccx(), loc.line as int);
-    let args = vec!(filename, line, index, len);
+    let col = C_int(bcx.ccx(), loc.col.to_uint() as int + 1);
+    let args = vec!(filename, line, col, index, len);
     let did = langcall(bcx, Some(sp), "", FailBoundsCheckFnLangItem);
     let bcx = callee::trans_lang_call(bcx,
                                       did,
@@ -395,3 +449,49 @@ pub fn trans_fail_bounds_check<'a>(
     Unreachable(bcx);
     return bcx;
 }
+
+pub fn trans_fail_overflow<'a>(
+                           bcx: &'a Block<'a>,
+                           sp: Span,
+                           op: InternedString,
+                           signed: bool,
+                           lhs: ValueRef,
+                           rhs: ValueRef)
+                           -> &'a Block<'a> {
+    let ccx = bcx.ccx();
+    let _icx = push_ctxt("trans_fail_overflow");
+
+    if bcx.sess().abort_on_fail() {
+        return trans_fail_abort(bcx);
+    }
+
+    // Extract the file/line/column from the span
+    let loc = bcx.sess().codemap().lookup_char_pos(sp.lo);
+    let filename = token::intern_and_get_ident(loc.file.name.as_slice());
+
+    // The lang item takes both operands as 64-bit integers, which also tells
+    // it how to print them back out.
+    let widen = |v: ValueRef| {
+        if signed {
+            SExtOrBitCast(bcx, v, Type::i64(ccx))
+        } else {
+            ZExtOrBitCast(bcx, v, Type::i64(ccx))
+        }
+    };
+    let lhs = widen(lhs);
+    let rhs = widen(rhs);
+
+    // Invoke the lang item
+    let filename = str_slice_arg(bcx, filename);
+    let line = C_int(ccx, loc.line as int);
+    let col = C_int(ccx, loc.col.to_uint() as int + 1);
+    let op = str_slice_arg(bcx, op);
+    let args = vec!(filename, line, col, op, C_bool(ccx, signed), lhs, rhs);
+    let did = langcall(bcx, Some(sp), "", FailOverflowFnLangItem);
+    let bcx = callee::trans_lang_call(bcx,
+                                      did,
+                                      args.as_slice(),
+                                      Some(expr::Ignore)).bcx;
+    Unreachable(bcx);
+    return bcx;
+}
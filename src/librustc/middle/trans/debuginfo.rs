@@ -1584,13 +1584,15 @@ impl EnumMemberDescriptionFactory {
                     }
                 ]
             },
-            adt::StructWrappedNullablePointer { nonnull: ref struct_def, nndiscr, ptrfield, ..} => {
+            adt::StructWrappedNullablePointer { nonnull: ref struct_def,
+                                                nndiscr,
+                                                ref ptrfield, ..} => {
                 // Create a description of the non-null variant
                 let (variant_type_metadata, variant_llvm_type, member_description_factory) =
                     describe_enum_variant(cx,
                                           struct_def,
                                           &**self.variants.get(nndiscr as uint),
-                                          OptimizedDiscriminant(ptrfield),
+                                          OptimizedDiscriminant(ptrfield.clone()),
                                           self.containing_scope,
                                           self.file_metadata,
                                           self.span);
@@ -1609,8 +1611,12 @@ impl EnumMemberDescriptionFactory {
                 let null_variant_index = (1 - nndiscr) as uint;
                 let null_variant_ident = self.variants.get(null_variant_index).name;
                 let null_variant_name = token::get_ident(null_variant_ident);
+                // The path to the discriminant field is encoded as its indices
+                // separated by `$`.
+                let discrfield = ptrfield.iter().map(|i| i.to_str())
+                                         .collect::<Vec<String>>().connect("$");
                 let union_member_name = format!("RUST$ENCODED$ENUM${}${}",
-                                                ptrfield,
+                                                discrfield,
                                                 null_variant_name);
 
                 // Create the (singleton) list of descriptions of union members
@@ -1653,7 +1659,7 @@ impl VariantMemberDescriptionFactory {
 
 enum EnumDiscriminantInfo {
     RegularDiscriminant(DIType),
-    OptimizedDiscriminant(uint),
+    OptimizedDiscriminant(adt::DiscrField),
     NoDiscriminant
 }
 
@@ -2831,6 +2837,13 @@ fn populate_scope_map(cx: &CrateContext,
                 walk_expr(cx, rhs, scope_stack, scope_map);
             }
 
+            ast::ExprSlice(base, start, end, _) => {
+                walk_expr(cx, base, scope_stack, scope_map);
+                for &sub_exp in start.iter().chain(end.iter()) {
+                    walk_expr(cx, sub_exp, scope_stack, scope_map);
+                }
+            }
+
             ast::ExprVec(ref init_expressions) |
             ast::ExprTup(ref init_expressions) => {
                 for ie in init_expressions.iter() {
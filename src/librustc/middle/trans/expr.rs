@@ -458,6 +458,36 @@ fn trans_index<'a>(bcx: &'a Block<'a>,
     let ccx = bcx.ccx();
     let mut bcx = bcx;
 
+    // Check for an overloaded `index_mut`. (An overloaded `index` yields
+    // its result by value and is translated in `trans_rvalue_dps_unadjusted`.)
+    let method_call = MethodCall::expr(index_expr.id);
+    let method_ty = ccx.tcx.method_map.borrow()
+                       .find(&method_call).map(|method| method.ty);
+    match method_ty {
+        Some(method_ty) => {
+            // Evaluate `trans_overloaded_op`, which invokes the user's
+            // index_mut() method to obtain a `&mut T` pointer to the
+            // element. As with an overloaded deref, that pointer is then
+            // used directly as the address of the resulting lvalue.
+            let base_datum = unpack_datum!(bcx, trans(bcx, base));
+            let ix_datum = unpack_datum!(bcx, trans(bcx, idx));
+            let val = unpack_result!(bcx, trans_overloaded_op(bcx, index_expr, method_call,
+                                                              base_datum,
+                                                              vec!((ix_datum, idx.id)),
+                                                              None));
+            let ref_ty = ty::ty_fn_ret(monomorphize_type(bcx, method_ty));
+            let elt_ty = match ty::deref(ref_ty, true) {
+                Some(mt) => mt.ty,
+                None => {
+                    bcx.tcx().sess.span_bug(index_expr.span,
+                                            "index_mut() did not return a pointer")
+                }
+            };
+            return DatumBlock(bcx, Datum(val, elt_ty, LvalueExpr));
+        }
+        None => {}
+    }
+
     let base_datum = unpack_datum!(bcx, trans_to_lvalue(bcx, base, "index"));
 
     // Translate index expression and cast to a suitable LLVM integer.
@@ -490,7 +520,9 @@ fn trans_index<'a>(bcx: &'a Block<'a>,
     let expect = ccx.get_intrinsic(&("llvm.expect.i1"));
     let expected = Call(bcx, expect, [bounds_check, C_i1(ccx, false)], []);
     let bcx = with_cond(bcx, expected, |bcx| {
-            controlflow::trans_fail_bounds_check(bcx, index_expr.span, ix_val, len)
+            // Blame the index operand itself so that chained indexing such as
+            // `a[i][j]` reports the column of the index which is out of bounds.
+            controlflow::trans_fail_bounds_check(bcx, idx.span, ix_val, len)
         });
     let elt = InBoundsGEP(bcx, base, [ix_val]);
     let elt = PointerCast(bcx, elt, vt.llunit_ty.ptr_to());
@@ -726,20 +758,31 @@ fn trans_rvalue_dps_unadjusted<'a>(bcx: &'a Block<'a>,
             let lhs = unpack_datum!(bcx, trans(bcx, lhs));
             let rhs_datum = unpack_datum!(bcx, trans(bcx, rhs));
             trans_overloaded_op(bcx, expr, MethodCall::expr(expr.id), lhs,
-                                Some((rhs_datum, rhs.id)), Some(dest)).bcx
+                                vec!((rhs_datum, rhs.id)), Some(dest)).bcx
         }
         ast::ExprUnary(_, subexpr) => {
             // if not overloaded, would be RvalueDatumExpr
             let arg = unpack_datum!(bcx, trans(bcx, subexpr));
             trans_overloaded_op(bcx, expr, MethodCall::expr(expr.id),
-                                arg, None, Some(dest)).bcx
+                                arg, Vec::new(), Some(dest)).bcx
         }
         ast::ExprIndex(base, idx) => {
             // if not overloaded, would be RvalueDatumExpr
             let base = unpack_datum!(bcx, trans(bcx, base));
             let idx_datum = unpack_datum!(bcx, trans(bcx, idx));
             trans_overloaded_op(bcx, expr, MethodCall::expr(expr.id), base,
-                                Some((idx_datum, idx.id)), Some(dest)).bcx
+                                vec!((idx_datum, idx.id)), Some(dest)).bcx
+        }
+        ast::ExprSlice(base, start, end, _) => {
+            // slicing is always overloaded
+            let base = unpack_datum!(bcx, trans(bcx, base));
+            let mut args = Vec::new();
+            for &e in start.iter().chain(end.iter()) {
+                let datum = unpack_datum!(bcx, trans(bcx, e));
+                args.push((datum, e.id));
+            }
+            trans_overloaded_op(bcx, expr, MethodCall::expr(expr.id), base,
+                                args, Some(dest)).bcx
         }
         ast::ExprCast(val, _) => {
             // DPS output mode means this is a trait cast:
@@ -1278,7 +1321,15 @@ fn trans_eager_binop<'a>(
     let rhs = base::cast_shift_expr_rhs(bcx, op, lhs, rhs);
 
     let mut bcx = bcx;
+    let check_overflow = bcx.sess().overflow_checks() && !is_float &&
+                         !is_simd && ty::type_is_integral(intype);
     let val = match op {
+      ast::BiAdd | ast::BiSub | ast::BiMul if check_overflow => {
+        let (cx, val) = base::trans_overflow_checked_binop(
+            bcx, binop_expr.span, op, lhs, rhs, intype);
+        bcx = cx;
+        val
+      }
       ast::BiAdd => {
         if is_float { FAdd(bcx, lhs, rhs) }
         else { Add(bcx, lhs, rhs) }
@@ -1443,7 +1494,7 @@ fn trans_overloaded_op<'a, 'b>(
                        expr: &ast::Expr,
                        method_call: MethodCall,
                        lhs: Datum<Expr>,
-                       rhs: Option<(Datum<Expr>, ast::NodeId)>,
+                       rhs: Vec<(Datum<Expr>, ast::NodeId)>,
                        dest: Option<Dest>)
                        -> Result<'a> {
     let method_ty = bcx.tcx().method_map.borrow().get(&method_call).ty;
@@ -1715,7 +1766,7 @@ fn deref_once<'a>(bcx: &'a Block<'a>,
                 unpack_datum!(bcx, auto_ref(bcx, datum, expr))
             };
             let val = unpack_result!(bcx, trans_overloaded_op(bcx, expr, method_call,
-                                                              datum, None, None));
+                                                              datum, Vec::new(), None));
             let ref_ty = ty::ty_fn_ret(monomorphize_type(bcx, method_ty));
             Datum(val, ref_ty, RvalueExpr(Rvalue(ByValue)))
         }
@@ -25,6 +25,7 @@ use middle::trans::machine;
 use middle::trans::machine::llsize_of;
 use middle::trans::type_::Type;
 use middle::ty;
+use std::c_str::ToCStr;
 use syntax::ast;
 use syntax::ast_map;
 use syntax::parse::token;
@@ -376,6 +377,39 @@ pub fn trans_intrinsic(ccx: &CrateContext,
         "forget" => {
             RetVoid(bcx);
         }
+        "black_box" => {
+            // Move the value to memory and hand its address to an empty,
+            // volatile `asm` statement that clobbers memory. LLVM has to
+            // assume the statement reads and rewrites the value, so it can
+            // neither compute the value lazily nor drop code whose only
+            // effect is producing it.
+            let tp_ty = *substs.substs.tps.get(0);
+            if return_type_is_void(ccx, tp_ty) {
+                RetVoid(bcx);
+            } else {
+                let lltp_ty = type_of::type_of(ccx, tp_ty);
+                let mode = appropriate_rvalue_mode(ccx, tp_ty);
+                let src = Datum {val: get_param(decl, first_real_arg),
+                                 ty: tp_ty,
+                                 kind: Rvalue(mode)};
+                let dst = match bcx.fcx.llretptr.get() {
+                    Some(ptr) => ptr,
+                    None => alloca(bcx, lltp_ty, "black_box"),
+                };
+                bcx = src.store_to(bcx, dst);
+                let llptr = PointerCast(bcx, dst, Type::i8p(ccx));
+                "".with_c_str(|asm| {
+                    "r,~{memory}".with_c_str(|cons| {
+                        InlineAsmCall(bcx, asm, cons, [llptr], Type::void(ccx),
+                                      true, false, lib::llvm::AD_ATT)
+                    })
+                });
+                match bcx.fcx.llretptr.get() {
+                    Some(_) => RetVoid(bcx),
+                    None => Ret(bcx, Load(bcx, dst)),
+                }
+            }
+        }
         "transmute" => {
             let (in_type, out_type) = (*substs.substs.tps.get(0),
                                        *substs.substs.tps.get(1));
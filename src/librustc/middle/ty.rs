@@ -3042,6 +3042,11 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
             // the deref method invoked for `*a` always yields an `&T`
             ast::ExprUnary(ast::UnDeref, _) => LvalueExpr,
 
+            // likewise, `index_mut` yields an `&mut T` for `a[b]`
+            ast::ExprIndex(..) if is_overloaded_index_mut(tcx, expr.id) => {
+                LvalueExpr
+            }
+
             // in the general case, result could be any type, use DPS
             _ => RvalueDpsExpr
         };
@@ -3098,6 +3103,7 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
 
         ast::ExprCall(..) |
         ast::ExprMethodCall(..) |
+        ast::ExprSlice(..) |
         ast::ExprStruct(..) |
         ast::ExprTup(..) |
         ast::ExprIf(..) |
@@ -4449,6 +4455,29 @@ pub fn trait_of_method(tcx: &ctxt, def_id: ast::DefId)
     }
 }
 
+/// Returns true if the given method origin resolves to a method of the
+/// `IndexMut` trait. Unlike `Index::index`, the result of such a call is a
+/// `&mut` pointer which is implicitly dereferenced to form an lvalue.
+pub fn method_origin_is_index_mut(tcx: &ctxt, origin: &typeck::MethodOrigin)
+                                  -> bool {
+    let trait_did = match *origin {
+        typeck::MethodStatic(did) => trait_of_method(tcx, did),
+        typeck::MethodParam(ref mp) => Some(mp.trait_id),
+        typeck::MethodObject(ref mo) => Some(mo.trait_id),
+    };
+    trait_did.is_some() && trait_did == tcx.lang_items.index_mut_trait()
+}
+
+/// Returns true if the expression with the given ID is an indexing
+/// expression that was resolved to a call to `IndexMut::index_mut`.
+pub fn is_overloaded_index_mut(tcx: &ctxt, id: ast::NodeId) -> bool {
+    let method_call = typeck::MethodCall::expr(id);
+    match tcx.method_map.borrow().find(&method_call) {
+        Some(method) => method_origin_is_index_mut(tcx, &method.origin),
+        None => false
+    }
+}
+
 /// If the given def ID describes a method belonging to a trait, (either a
 /// default method or an implementation of a trait method), return the ID of
 /// the method inside trait definition (this means that if the given def ID
@@ -4752,6 +4781,11 @@ impl mc::Typer for ty::ctxt {
         self.method_map.borrow().find(&method_call).map(|method| method.ty)
     }
 
+    fn node_method_origin(&self, method_call: typeck::MethodCall)
+                          -> Option<typeck::MethodOrigin> {
+        self.method_map.borrow().find(&method_call).map(|method| method.origin.clone())
+    }
+
     fn adjustments<'a>(&'a self) -> &'a RefCell<NodeMap<ty::AutoAdjustment>> {
         &self.adjustments
     }
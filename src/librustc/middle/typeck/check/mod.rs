@@ -2108,6 +2108,47 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
         }
     }
 
+    fn try_overloaded_index_mut(fcx: &FnCtxt,
+                                expr: &ast::Expr,
+                                base_ty: ty::t,
+                                base: @ast::Expr,
+                                idx: @ast::Expr,
+                                lvalue_pref: LvaluePreference)
+                                -> Option<ty::t> {
+        /*!
+         * Resolves `base[idx]` to a call to `IndexMut::index_mut` if a
+         * mutable lvalue is wanted and `base_ty` implements the trait.
+         * Returns the type of the element, which is an lvalue reached by
+         * dereferencing the `&mut` pointer returned by `index_mut`.
+         */
+
+        let trait_did = match (lvalue_pref, fcx.tcx().lang_items.index_mut_trait()) {
+            (PreferMutLvalue, Some(trait_did)) => trait_did,
+            _ => return None
+        };
+
+        let method = method::lookup_in_trait(fcx, expr.span, Some(&*base),
+                                             token::intern("index_mut"), trait_did,
+                                             base_ty, [], AutoderefReceiver,
+                                             IgnoreStaticMethods);
+        match method {
+            Some(method) => {
+                let method_ty = method.ty;
+                // HACK(eddyb) Fully qualified path to work around a resolve bug.
+                let method_call = ::middle::typeck::MethodCall::expr(expr.id);
+                fcx.inh.method_map.borrow_mut().insert(method_call, method);
+                let ref_ty = check_method_argument_types(fcx, expr.span,
+                                                         method_ty, expr,
+                                                         [base, idx], DoDerefArgs);
+                match ty::deref(ref_ty, true) {
+                    Some(mt) => Some(mt.ty),
+                    None => Some(ty::mk_err())
+                }
+            }
+            None => None
+        }
+    }
+
     // could be either an expr_binop or an expr_assign_binop
     fn check_binop(fcx: &FnCtxt,
                    expr: &ast::Expr,
@@ -3313,28 +3354,90 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                       let resolved = structurally_resolved_type(fcx,
                                                                 expr.span,
                                                                 raw_base_t);
-                      let ret_ty = lookup_op_method(fcx,
-                                                    expr,
-                                                    resolved,
-                                                    token::intern("index"),
-                                                    tcx.lang_items.index_trait(),
-                                                    [base, idx],
-                                                    AutoderefReceiver,
-                                                    || {
-                        fcx.type_error_message(expr.span,
-                                               |actual| {
-                                                    format!("cannot index a \
-                                                             value of type \
-                                                             `{}`", actual)
-                                               },
-                                               base_t,
-                                               None);
-                      });
+                      let ret_ty = match try_overloaded_index_mut(fcx, expr,
+                                                                  resolved, base,
+                                                                  idx, lvalue_pref) {
+                          Some(elt_ty) => elt_ty,
+                          None => {
+                              lookup_op_method(fcx, expr, resolved,
+                                               token::intern("index"),
+                                               tcx.lang_items.index_trait(),
+                                               [base, idx], AutoderefReceiver, || {
+                                  fcx.type_error_message(expr.span, |actual| {
+                                      format!("cannot index a value of type `{}`",
+                                              actual)
+                                  }, base_t, None);
+                              })
+                          }
+                      };
                       fcx.write_ty(id, ret_ty);
                   }
               }
           }
        }
+      ast::ExprSlice(base, start, end, mutbl) => {
+          let lvalue_pref = match mutbl {
+              ast::MutMutable => PreferMutLvalue,
+              ast::MutImmutable => NoPreference
+          };
+          check_expr_with_lvalue_pref(fcx, base, lvalue_pref);
+          let raw_base_t = fcx.expr_ty(base);
+          if ty::type_is_error(raw_base_t) || ty::type_is_bot(raw_base_t) {
+              for &e in start.iter().chain(end.iter()) {
+                  check_expr(fcx, e);
+              }
+              fcx.write_ty(id, raw_base_t);
+          } else {
+              let resolved = structurally_resolved_type(fcx,
+                                                        expr.span,
+                                                        raw_base_t);
+              let (method_name, trait_did) = match mutbl {
+                  ast::MutImmutable => {
+                      (match (start, end) {
+                          (None, None) => "as_slice_",
+                          (Some(_), None) => "slice_from_or_fail",
+                          (None, Some(_)) => "slice_to_or_fail",
+                          (Some(_), Some(_)) => "slice_or_fail",
+                      }, tcx.lang_items.slice_trait())
+                  }
+                  ast::MutMutable => {
+                      (match (start, end) {
+                          (None, None) => "as_mut_slice_",
+                          (Some(_), None) => "slice_from_or_fail_mut",
+                          (None, Some(_)) => "slice_to_or_fail_mut",
+                          (Some(_), Some(_)) => "slice_or_fail_mut",
+                      }, tcx.lang_items.slice_mut_trait())
+                  }
+              };
+              let mut args = vec!(base);
+              for &e in start.iter().chain(end.iter()) {
+                  args.push(e);
+              }
+              let ret_ty = lookup_op_method(fcx,
+                                            expr,
+                                            resolved,
+                                            token::intern(method_name),
+                                            trait_did,
+                                            args.as_slice(),
+                                            AutoderefReceiver,
+                                            || {
+                fcx.type_error_message(expr.span,
+                                       |actual| {
+                                            format!("cannot take a {}slice \
+                                                     of a value of type \
+                                                     `{}`",
+                                                    match mutbl {
+                                                        ast::MutMutable => "mutable ",
+                                                        ast::MutImmutable => ""
+                                                    },
+                                                    actual)
+                                       },
+                                       resolved,
+                                       None);
+              });
+              fcx.write_ty(id, ret_ty);
+          }
+      }
     }
 
     debug!("type of expr({}) {} is...", expr.id,
@@ -4255,6 +4358,7 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
             "init" => (1u, Vec::new(), param(ccx, 0u)),
             "uninit" => (1u, Vec::new(), param(ccx, 0u)),
             "forget" => (1u, vec!( param(ccx, 0) ), ty::mk_nil()),
+            "black_box" => (1u, vec!( param(ccx, 0) ), param(ccx, 0)),
             "transmute" => (2, vec!( param(ccx, 0) ), param(ccx, 1)),
             "move_val_init" => {
                 (1u,
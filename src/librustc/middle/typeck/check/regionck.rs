@@ -129,7 +129,7 @@ use middle::typeck::check::regionmanip::relate_nested_regions;
 use middle::typeck::infer::resolve_and_force_all_but_regions;
 use middle::typeck::infer::resolve_type;
 use middle::typeck::infer;
-use middle::typeck::MethodCall;
+use middle::typeck::{MethodCall, MethodOrigin};
 use middle::pat_util;
 use util::nodemap::NodeMap;
 use util::ppaux::{ty_to_str, region_to_str, Repr};
@@ -275,6 +275,11 @@ impl<'fcx> mc::Typer for Rcx<'fcx> {
         self.resolve_method_type(method_call)
     }
 
+    fn node_method_origin(&self, method_call: MethodCall)
+                          -> Option<MethodOrigin> {
+        self.fcx.inh.method_map.borrow().find(&method_call).map(|method| method.origin.clone())
+    }
+
     fn adjustments<'a>(&'a self) -> &'a RefCell<NodeMap<ty::AutoAdjustment>> {
         &self.fcx.inh.adjustments
     }
@@ -476,7 +481,43 @@ fn visit_expr(rcx: &mut Rcx, expr: &ast::Expr) {
             visit::walk_expr(rcx, expr, ());
         }
 
-        ast::ExprIndex(lhs, rhs) |
+        ast::ExprIndex(lhs, rhs) if has_method_map => {
+            // As for `ExprBinary` below.
+            constrain_call(rcx, None, expr, Some(lhs), [rhs], true);
+
+            // `index_mut` returns a `&mut` that is dereferenced in place,
+            // so, as for an overloaded `*a`, its lifetime must enclose the
+            // indexing expression.
+            let method_call = MethodCall::expr(expr.id);
+            let ref_ty = match rcx.fcx.inh.method_map.borrow().find(&method_call) {
+                Some(method) if ty::method_origin_is_index_mut(rcx.tcx(),
+                                                               &method.origin) => {
+                    Some(ty::ty_fn_ret(method.ty))
+                }
+                _ => None
+            };
+            for &ref_ty in ref_ty.iter() {
+                match ty::get(ref_ty).sty {
+                    ty::ty_rptr(r_ptr, _) => {
+                        mk_subregion_due_to_dereference(rcx, expr.span,
+                                                        ty::ReScope(expr.id), r_ptr);
+                    }
+                    _ => {}
+                }
+            }
+
+            visit::walk_expr(rcx, expr, ());
+        }
+
+        ast::ExprSlice(base, start, end, _) => {
+            // Slicing is always an overloaded call.
+            let args: Vec<@ast::Expr> =
+                start.iter().chain(end.iter()).map(|&e| e).collect();
+            constrain_call(rcx, None, expr, Some(base), args.as_slice(), true);
+
+            visit::walk_expr(rcx, expr, ());
+        }
+
         ast::ExprBinary(_, lhs, rhs) if has_method_map => {
             // As `expr_method_call`, but the call is via an
             // overloaded op.  Note that we (sadly) currently use an
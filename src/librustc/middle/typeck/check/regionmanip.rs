@@ -33,7 +33,7 @@ pub fn replace_late_bound_regions_in_fn_sig(
             debug!("region r={}", r.to_str());
             match r {
                 ty::ReLateBound(s, br) if s == fn_sig.binder_id => {
-                    *map.find_or_insert_with(br, |_| mapf(br))
+                    *map.entry(br).or_insert_with(|_| mapf(br))
                 }
                 _ => r
             }
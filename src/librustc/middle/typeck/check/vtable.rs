@@ -654,6 +654,7 @@ pub fn early_resolve_expr(ex: &ast::Expr, fcx: &FnCtxt, is_early: bool) {
       ast::ExprUnary(_, _) |
       ast::ExprAssignOp(_, _, _) |
       ast::ExprIndex(_, _) |
+      ast::ExprSlice(..) |
       ast::ExprMethodCall(_, _, _) => {
         match fcx.inh.method_map.borrow().find(&MethodCall::expr(ex.id)) {
           Some(method) => {
@@ -119,6 +119,7 @@ impl<'a> Visitor<()> for Context<'a> {
 
 weak_lang_items!(
     begin_unwind,       BeginUnwindLangItem,        rust_begin_unwind;
+    oom,                OomLangItem,                rust_oom;
     stack_exhausted,    StackExhaustedLangItem,     rust_stack_exhausted;
     eh_personality,     EhPersonalityLangItem,      rust_eh_personality;
 )
@@ -11,10 +11,13 @@
 //! An efficient hash map for node IDs
 
 use collections::{HashMap, HashSet};
-use std::hash::{Hasher, Hash};
-use std::io;
 use syntax::ast;
 
+// The hashmap in libcollections by default uses SipHash which isn't quite as
+// speedy as we want. In the compiler we're not really worried about DOS
+// attempts, so we just default to a non-cryptographic hash.
+pub use std::hash::fnv::{FnvHasher, FnvState};
+
 pub type FnvHashMap<K, V> = HashMap<K, V, FnvHasher>;
 pub type FnvHashSet<V> = HashSet<V, FnvHasher>;
 
@@ -59,36 +62,3 @@ pub mod DefIdSet {
         super::FnvHashSet::new()
     }
 }
-
-/// A speedy hash algorithm for node ids and def ids. The hashmap in
-/// libcollections by default uses SipHash which isn't quite as speedy as we
-/// want. In the compiler we're not really worried about DOS attempts, so we
-/// just default to a non-cryptographic hash.
-///
-/// This uses FNV hashing, as described here:
-/// http://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
-#[deriving(Clone)]
-pub struct FnvHasher;
-
-pub struct FnvState(u64);
-
-impl Hasher<FnvState> for FnvHasher {
-    fn hash<T: Hash<FnvState>>(&self, t: &T) -> u64 {
-        let mut state = FnvState(0xcbf29ce484222325);
-        t.hash(&mut state);
-        let FnvState(ret) = state;
-        return ret;
-    }
-}
-
-impl Writer for FnvState {
-    fn write(&mut self, bytes: &[u8]) -> io::IoResult<()> {
-        let FnvState(mut hash) = *self;
-        for byte in bytes.iter() {
-            hash = hash ^ (*byte as u64);
-            hash = hash * 0x100000001b3;
-        }
-        *self = FnvState(hash);
-        Ok(())
-    }
-}
@@ -715,7 +715,7 @@ impl DocFolder for Cache {
             clean::ImplItem(ref i) => {
                 match i.trait_ {
                     Some(clean::ResolvedPath{ did, .. }) => {
-                        let v = self.implementors.find_or_insert_with(did, |_| {
+                        let v = self.implementors.entry(did).or_insert_with(|_| {
                             Vec::new()
                         });
                         match i.for_ {
@@ -854,7 +854,7 @@ impl DocFolder for Cache {
                     clean::Item{ attrs, inner: clean::ImplItem(i), .. } => {
                         match i.for_ {
                             clean::ResolvedPath { did, .. } => {
-                                let v = self.impls.find_or_insert_with(did, |_| {
+                                let v = self.impls.entry(did).or_insert_with(|_| {
                                     Vec::new()
                                 });
                                 // extract relevant documentation for this impl
@@ -1916,7 +1916,7 @@ fn build_sidebar(m: &clean::Module) -> HashMap<String, Vec<String>> {
             None => continue,
             Some(ref s) => s.to_string(),
         };
-        let v = map.find_or_insert_with(short.to_string(), |_| Vec::new());
+        let v = map.entry(short.to_string()).or_insert_with(|_| Vec::new());
         v.push(myname);
     }
 
@@ -60,8 +60,17 @@ impl GetAddrInfoRequest {
                     flags |= cval as i32;
                 }
             });
-            let socktype = 0;
-            let protocol = 0;
+            let socktype = match hint.socktype {
+                Some(ai::Stream) => libc::SOCK_STREAM,
+                Some(ai::Datagram) => libc::SOCK_DGRAM,
+                Some(ai::Raw) => libc::SOCK_RAW,
+                None => 0,
+            };
+            let protocol = match hint.protocol {
+                Some(ai::TCP) => libc::IPPROTO_TCP,
+                Some(ai::UDP) => libc::IPPROTO_UDP,
+                None => 0,
+            };
 
             libc::addrinfo {
                 ai_flags: flags,
@@ -150,21 +159,17 @@ pub fn accum_addrinfo(addr: &Addrinfo) -> Vec<ai::Info> {
                 }
             });
 
-            /* FIXME: do we really want to support these
             let protocol = match (*addr).ai_protocol {
-                p if p == uvll::rust_IPPROTO_UDP() => Some(ai::UDP),
-                p if p == uvll::rust_IPPROTO_TCP() => Some(ai::TCP),
+                p if p == libc::IPPROTO_UDP => Some(ai::UDP),
+                p if p == libc::IPPROTO_TCP => Some(ai::TCP),
                 _ => None,
             };
             let socktype = match (*addr).ai_socktype {
-                p if p == uvll::rust_SOCK_STREAM() => Some(ai::Stream),
-                p if p == uvll::rust_SOCK_DGRAM() => Some(ai::Datagram),
-                p if p == uvll::rust_SOCK_RAW() => Some(ai::Raw),
+                p if p == libc::SOCK_STREAM => Some(ai::Stream),
+                p if p == libc::SOCK_DGRAM => Some(ai::Datagram),
+                p if p == libc::SOCK_RAW => Some(ai::Raw),
                 _ => None,
             };
-            */
-            let protocol = None;
-            let socktype = None;
 
             addrs.push(ai::Info {
                 address: rustaddr,
@@ -154,7 +154,7 @@ mod test_remote {
             watcher.fire();
         });
 
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
         thread.join();
     }
 }
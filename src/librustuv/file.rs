@@ -467,6 +467,7 @@ impl rtio::RtioFileStream for FileWatcher {
         let _m = self.fire_homing_missile();
         FsRequest::fstat(&self.loop_, self.fd).map_err(uv_error_to_io_error)
     }
+    fn fd(&self) -> c_int { self.fd }
 }
 
 #[cfg(test)]
@@ -592,4 +593,39 @@ mod test {
         let result = FsRequest::rmdir(l(), path);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn file_io_deschedules_task() {
+        use green::{SchedPool, PoolConfig};
+        use std::io::{File, Truncate, ReadWrite};
+        use std::io::fs;
+        use std::task::TaskOpts;
+
+        // A spawned task is run immediately, and it should only hand its
+        // scheduler back to us once it's waiting on libuv to finish its file
+        // IO, not once it's done with all of it.
+        let config = PoolConfig::new().threads(1).event_loop_factory(::event_loop);
+        let mut pool = SchedPool::new(config);
+        let (tx, rx) = channel();
+        pool.spawn(TaskOpts::new(), proc() {
+            let (done_tx, done_rx) = channel();
+            spawn(proc() {
+                let path = Path::new("./tmp/file_io_deschedules_task");
+                let data = Vec::from_elem(1 << 20, 0u8);
+                {
+                    let mut file = File::open_mode(&path, Truncate,
+                                                   ReadWrite).unwrap();
+                    file.write(data.as_slice()).unwrap();
+                    assert_eq!(file.stat().unwrap().size, 1 << 20);
+                }
+                assert_eq!(File::open(&path).read_to_end().unwrap(), data);
+                fs::unlink(&path).unwrap();
+                done_tx.send(());
+            });
+            tx.send(done_rx.try_recv().is_err());
+            done_rx.recv_unwrap();
+        });
+        assert!(rx.recv_unwrap());
+        pool.shutdown();
+    }
 }
@@ -168,6 +168,7 @@ mod test {
         let mut pool = SchedPool::new(PoolConfig {
             threads: 1,
             event_loop_factory: ::event_loop,
+            detect_deadlocks: false,
         });
 
         pool.spawn(TaskOpts::new(), proc() {
@@ -176,7 +177,7 @@ mod test {
         });
 
         let task = pool.task(TaskOpts::new(), proc() {
-            drop(rx.recv());
+            drop(rx.recv_unwrap());
         });
         pool.spawn_sched().send(sched::TaskFromFriend(task));
 
@@ -189,6 +190,7 @@ mod test {
         let mut pool = SchedPool::new(PoolConfig {
             threads: 1,
             event_loop_factory: ::event_loop,
+            detect_deadlocks: false,
         });
 
         pool.spawn(TaskOpts::new(), proc() {
@@ -201,7 +203,7 @@ mod test {
         });
 
         let task = pool.task(TaskOpts::new(), proc() {
-            let (mut watcher, addr) = rx.recv();
+            let (mut watcher, addr) = rx.recv_unwrap();
             let mut buf = [0, ..10];
             assert_eq!(watcher.recvfrom(buf).unwrap(), (4, addr));
         });
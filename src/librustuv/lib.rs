@@ -48,6 +48,7 @@ via `close` and `delete` methods.
 #[cfg(test)] extern crate realrustuv = "rustuv";
 extern crate libc;
 extern crate alloc;
+extern crate collections;
 
 use libc::{c_int, c_void};
 use std::fmt;
@@ -63,6 +64,8 @@ use std::str::raw::from_c_str;
 use std::str;
 use std::task;
 
+use wheel::TimerWheel;
+
 pub use self::async::AsyncWatcher;
 pub use self::file::{FsRequest, FileWatcher};
 pub use self::idle::IdleWatcher;
@@ -90,6 +93,8 @@ mod timeout;
 mod homing;
 mod queue;
 mod rc;
+mod wheel;
+mod poll;
 
 pub mod uvio;
 pub mod uvll;
@@ -310,6 +315,13 @@ impl Drop for Request {
     }
 }
 
+// The state which rust keeps for each loop
+struct LoopData {
+    // The number of tasks currently blocked waiting for I/O to complete
+    blockers: uint,
+    timers: Option<Box<TimerWheel>>,
+}
+
 /// FIXME: Loop(*handle) is buggy with destructors. Normal structs
 /// with dtors may not be destructured, but tuple structs can,
 /// but the results are not correct.
@@ -321,7 +333,8 @@ impl Loop {
     pub fn new() -> Loop {
         let handle = unsafe { uvll::loop_new() };
         assert!(handle.is_not_null());
-        unsafe { uvll::set_data_for_uv_loop(handle, 0 as *c_void) }
+        let data = box LoopData { blockers: 0, timers: None };
+        unsafe { uvll::set_data_for_uv_loop(handle, mem::transmute(data)) }
         Loop::wrap(handle)
     }
 
@@ -332,20 +345,64 @@ impl Loop {
     }
 
     pub fn close(&mut self) {
-        unsafe { uvll::uv_loop_delete(self.handle) };
+        unsafe {
+            let data: Box<LoopData> =
+                mem::transmute(uvll::get_data_for_uv_loop(self.handle));
+            assert!(data.timers.is_none());
+            uvll::uv_loop_delete(self.handle);
+        }
+    }
+
+    // The 'data' field of the uv_loop_t points at the loop's `LoopData`
+    fn data<'a>(&'a self) -> &'a mut LoopData {
+        unsafe { mem::transmute(uvll::get_data_for_uv_loop(self.handle)) }
     }
 
-    // The 'data' field of the uv_loop_t is used to count the number of tasks
-    // that are currently blocked waiting for I/O to complete.
     fn modify_blockers(&self, amt: uint) {
-        unsafe {
-            let cur = uvll::get_data_for_uv_loop(self.handle) as uint;
-            uvll::set_data_for_uv_loop(self.handle, (cur + amt) as *c_void)
-        }
+        self.data().blockers += amt;
     }
 
     fn get_blockers(&self) -> uint {
-        unsafe { uvll::get_data_for_uv_loop(self.handle) as uint }
+        self.data().blockers
+    }
+
+    /// The timer wheel which drives all of the timers of this loop, created
+    /// the first time that it's needed.
+    pub fn timers<'a>(&'a self) -> &'a mut TimerWheel {
+        let data = self.data();
+        if data.timers.is_none() {
+            data.timers = Some(TimerWheel::new(self));
+        }
+        &mut **data.timers.get_mut_ref()
+    }
+
+    // Closes the timer wheel, which has to be done before the loop is run
+    // for the last time.
+    fn close_timers(&mut self) {
+        match self.data().timers.take() {
+            Some(mut timers) => timers.close(),
+            None => {}
+        }
+    }
+
+    // Whether any handle on this loop other than an async handle is active.
+    // Async handles only fire when another thread pokes this loop, everything
+    // else (timers, listeners, signals, ...) may fire all on its own.
+    fn has_active_handles(&self) -> bool {
+        extern fn walk_cb(handle: *uvll::uv_handle_t, arg: *c_void) {
+            unsafe {
+                if uvll::get_handle_type(handle) != uvll::UV_ASYNC &&
+                   uvll::uv_is_active(handle) != 0 {
+                    *(arg as *mut bool) = true;
+                }
+            }
+        }
+        let mut active = false;
+        unsafe {
+            uvll::uv_walk(self.handle, walk_cb,
+                          &mut active as *mut bool as *c_void);
+        }
+        active
     }
 }
 
@@ -413,6 +470,7 @@ pub fn uv_error_to_io_error(uverr: UvError) -> IoError {
             uvll::ECONNABORTED => io::ConnectionAborted,
             uvll::EADDRNOTAVAIL => io::ConnectionRefused,
             uvll::ECANCELED => io::TimedOut,
+            uvll::EEXIST => io::PathAlreadyExists,
             err => {
                 uvdebug!("uverr.code {}", err as int);
                 // FIXME: Need to map remaining uv error types
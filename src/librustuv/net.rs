@@ -19,6 +19,7 @@ use std::rt::rtio;
 use std::rt::task::BlockedTask;
 
 use homing::{HomingIO, HomeHandle};
+use poll::Readiness;
 use rc::Refcount;
 use stream::StreamWatcher;
 use super::{Loop, Request, UvError, Buf, status_to_io_result,
@@ -146,6 +147,32 @@ fn socket_name(sk: SocketNameKind,
     }
 }
 
+#[cfg(unix)] pub type sock_t = c_int;
+#[cfg(windows)] pub type sock_t = libc::SOCKET;
+
+// The socket underlying a libuv handle, for the options libuv doesn't expose
+// and for polling
+pub fn raw_socket(handle: *uvll::uv_handle_t) -> Result<sock_t, IoError> {
+    let mut fd: uvll::uv_os_fd_t = unsafe { mem::zeroed() };
+    match unsafe { uvll::uv_fileno(handle, &mut fd) } {
+        0 => Ok(fd as sock_t),
+        n => Err(uv_error_to_io_error(UvError(n))),
+    }
+}
+
+fn setsockopt(fd: sock_t, level: c_int, opt: c_int,
+              val: c_int) -> Result<(), IoError> {
+    let ret = unsafe {
+        libc::setsockopt(fd, level, opt, &val as *c_int as *c_void,
+                         mem::size_of::<c_int>() as libc::socklen_t)
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(IoError::last_error())
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 /// TCP implementation
 ////////////////////////////////////////////////////////////////////////////////
@@ -170,6 +197,7 @@ pub struct TcpListener {
     closing_task: Option<BlockedTask>,
     outgoing: Sender<Result<Box<rtio::RtioTcpStream:Send>, IoError>>,
     incoming: Receiver<Result<Box<rtio::RtioTcpStream:Send>, IoError>>,
+    ready: Readiness,
 }
 
 pub struct TcpAcceptor {
@@ -277,6 +305,17 @@ impl rtio::RtioTcpStream for TcpWatcher {
         })
     }
 
+    fn time_to_live(&mut self, ttl: int) -> Result<(), IoError> {
+        // libuv only exposes the TTL of UDP sockets, so it's set on the socket
+        // itself, as libnative does
+        let _m = self.fire_homing_missile();
+        let (level, opt) = match try!(socket_name(Tcp, self.handle)).ip {
+            ip::Ipv4Addr(..) => (libc::IPPROTO_IP, libc::IP_TTL),
+            ip::Ipv6Addr(..) => (libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS),
+        };
+        setsockopt(try!(raw_socket(self.handle)), level, opt, ttl as c_int)
+    }
+
     fn clone(&self) -> Box<rtio::RtioTcpStream:Send> {
         box TcpWatcher {
             handle: self.handle,
@@ -332,6 +371,9 @@ impl rtio::RtioTcpStream for TcpWatcher {
             stream.cancel_write()
         }
     }
+
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<c_int> { raw_socket(self.handle).ok() }
 }
 
 impl UvHandle<uvll::uv_tcp_t> for TcpWatcher {
@@ -363,6 +405,7 @@ impl TcpListener {
             closing_task: None,
             outgoing: tx,
             incoming: rx,
+            ready: Readiness::new(),
         };
         let (addr, _len) = addr_to_sockaddr(address);
         let res = unsafe {
@@ -422,6 +465,7 @@ extern fn listen_cb(server: *uvll::uv_stream_t, status: c_int) {
         }
         n => Err(uv_error_to_io_error(UvError(n)))
     };
+    tcp.ready.push();
     tcp.outgoing.send(msg);
 }
 
@@ -447,7 +491,7 @@ impl rtio::RtioSocket for TcpAcceptor {
 
 impl rtio::RtioTcpAcceptor for TcpAcceptor {
     fn accept(&mut self) -> Result<Box<rtio::RtioTcpStream:Send>, IoError> {
-        self.timeout.accept(&self.listener.incoming)
+        self.timeout.accept(&self.listener.incoming, &self.listener.ready)
     }
 
     fn accept_simultaneously(&mut self) -> Result<(), IoError> {
@@ -471,6 +515,9 @@ impl rtio::RtioTcpAcceptor for TcpAcceptor {
             Some(ms) => self.timeout.set_timeout(ms, &mut *self.listener),
         }
     }
+
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<c_int> { Some(self.listener.ready.fd()) }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -907,7 +954,7 @@ mod test {
             }
         });
 
-        rx.recv();
+        rx.recv_unwrap();
         let mut w = match TcpWatcher::connect(local_loop(), addr, None) {
             Ok(w) => w, Err(e) => fail!("{:?}", e)
         };
@@ -943,7 +990,7 @@ mod test {
             }
         });
 
-        rx.recv();
+        rx.recv_unwrap();
         let mut w = match TcpWatcher::connect(local_loop(), addr, None) {
             Ok(w) => w, Err(e) => fail!("{:?}", e)
         };
@@ -975,7 +1022,7 @@ mod test {
             }
         });
 
-        rx.recv();
+        rx.recv_unwrap();
         let mut w = match UdpWatcher::bind(local_loop(), client) {
             Ok(w) => w, Err(e) => fail!("{:?}", e)
         };
@@ -1007,7 +1054,7 @@ mod test {
             }
         });
 
-        rx.recv();
+        rx.recv_unwrap();
         let mut w = match UdpWatcher::bind(local_loop(), client) {
             Ok(w) => w, Err(e) => fail!("{:?}", e)
         };
@@ -1036,7 +1083,7 @@ mod test {
             }
         });
 
-        rx.recv();
+        rx.recv_unwrap();
         let mut stream = TcpWatcher::connect(local_loop(), addr, None).unwrap();
         let mut buf = [0, .. 2048];
         let mut total_bytes_read = 0;
@@ -1059,7 +1106,7 @@ mod test {
 
         spawn(proc() {
             let mut client = UdpWatcher::bind(local_loop(), client_addr).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert!(client.sendto([1], server_addr).is_ok());
             assert!(client.sendto([2], server_addr).is_ok());
         });
@@ -1095,7 +1142,7 @@ mod test {
             let mut server_in = UdpWatcher::bind(l, server_in_addr).unwrap();
             let (tx, rx) = (tx2, rx1);
             tx.send(());
-            rx.recv();
+            rx.recv_unwrap();
             let msg = [1, .. 2048];
             let mut total_bytes_sent = 0;
             let mut buf = [1];
@@ -1117,7 +1164,7 @@ mod test {
         let mut client_out = UdpWatcher::bind(l, client_out_addr).unwrap();
         let mut client_in = UdpWatcher::bind(l, client_in_addr).unwrap();
         let (tx, rx) = (tx1, rx2);
-        rx.recv();
+        rx.recv_unwrap();
         tx.send(());
         let mut total_bytes_recv = 0;
         let mut buf = [0, .. 2048];
@@ -1144,14 +1191,14 @@ mod test {
         let (tx, rx) = channel::<Receiver<()>>();
 
         spawn(proc() {
-            let rx = rx.recv();
+            let rx = rx.recv_unwrap();
             let mut stream = TcpWatcher::connect(local_loop(), addr, None).unwrap();
             stream.write([0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
             stream.write([0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             stream.write([0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
             stream.write([0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
         });
 
         let listener = TcpListener::bind(local_loop(), addr).unwrap();
@@ -1223,7 +1270,7 @@ mod test {
             tx.send(());
             drop(w.accept().unwrap());
         });
-        rx.recv();
+        rx.recv_unwrap();
         let _w = TcpWatcher::connect(local_loop(), addr, None).unwrap();
         fail!();
     }
@@ -1248,7 +1295,7 @@ mod test {
             tx.send(w);
         });
 
-        let _w = rx.recv();
+        let _w = rx.recv_unwrap();
         fail!();
     }
 }
@@ -18,6 +18,7 @@ use std::rt::task::BlockedTask;
 
 use homing::{HomingIO, HomeHandle};
 use net;
+use poll::Readiness;
 use rc::Refcount;
 use stream::StreamWatcher;
 use super::{Loop, UvError, UvHandle, uv_error_to_io_error};
@@ -41,6 +42,7 @@ pub struct PipeListener {
     pipe: *uvll::uv_pipe_t,
     outgoing: Sender<Result<Box<RtioPipe:Send>, IoError>>,
     incoming: Receiver<Result<Box<RtioPipe:Send>, IoError>>,
+    ready: Readiness,
 }
 
 pub struct PipeAcceptor {
@@ -201,6 +203,11 @@ impl RtioPipe for PipeWatcher {
             stream.cancel_write()
         }
     }
+
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<libc::c_int> {
+        net::raw_socket(self.stream.handle).ok()
+    }
 }
 
 impl HomingIO for PipeWatcher {
@@ -240,6 +247,7 @@ impl PipeListener {
                     pipe: pipe.unwrap(),
                     incoming: rx,
                     outgoing: tx,
+                    ready: Readiness::new(),
                 };
                 Ok(p.install())
             }
@@ -288,6 +296,7 @@ extern fn listen_cb(server: *uvll::uv_stream_t, status: libc::c_int) {
         }
         n => Err(uv_error_to_io_error(UvError(n)))
     };
+    pipe.ready.push();
     pipe.outgoing.send(msg);
 }
 
@@ -302,7 +311,7 @@ impl Drop for PipeListener {
 
 impl RtioUnixAcceptor for PipeAcceptor {
     fn accept(&mut self) -> Result<Box<RtioPipe:Send>, IoError> {
-        self.timeout.accept(&self.listener.incoming)
+        self.timeout.accept(&self.listener.incoming, &self.listener.ready)
     }
 
     fn set_timeout(&mut self, timeout_ms: Option<u64>) {
@@ -311,6 +320,11 @@ impl RtioUnixAcceptor for PipeAcceptor {
             Some(ms) => self.timeout.set_timeout(ms, &mut *self.listener),
         }
     }
+
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<libc::c_int> {
+        Some(self.listener.ready.fd())
+    }
 }
 
 impl HomingIO for PipeAcceptor {
@@ -374,7 +388,7 @@ mod tests {
             assert_eq!(buf[0], 1);
             assert!(client.write([2]).is_ok());
         });
-        rx.recv();
+        rx.recv_unwrap();
         let mut c = PipeWatcher::connect(local_loop(), &path.to_c_str(), None).unwrap();
         assert!(c.write([1]).is_ok());
         let mut buf = [0];
@@ -394,7 +408,7 @@ mod tests {
             tx.send(());
             drop(p.accept().unwrap());
         });
-        rx.recv();
+        rx.recv_unwrap();
         let _c = PipeWatcher::connect(local_loop(), &path.to_c_str(), None).unwrap();
         fail!()
 
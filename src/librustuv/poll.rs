@@ -0,0 +1,209 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use libc::{c_int, c_void};
+use libc;
+use std::os;
+use std::sync::atomics::{AtomicUint, SeqCst};
+
+#[cfg(unix)] use std::rt::rtio;
+#[cfg(unix)] use std::rt::task::BlockedTask;
+#[cfg(unix)] use std::ptr;
+
+#[cfg(unix)] use super::{UvError, wait_until_woken_after, wakeup};
+#[cfg(unix)] use timer::TimerWatcher;
+#[cfg(unix)] use uvio::UvIoFactory;
+#[cfg(unix)] use uvll;
+
+/// A descriptor which stands in for a listener's socket when it's polled.
+///
+/// libuv watches a listening socket itself and accepts each connection as soon
+/// as it arrives, queueing it up for `accept`, so the socket is never readable
+/// as far as anyone else can tell. Instead the listener notes here each
+/// connection it queues and each one which is taken, and the read end of this
+/// pipe is readable for as long as any are queued.
+pub struct Readiness {
+    pending: AtomicUint,
+    pipe: os::Pipe,
+}
+
+impl Readiness {
+    pub fn new() -> Readiness {
+        Readiness { pending: AtomicUint::new(0), pipe: os::pipe() }
+    }
+
+    /// Notes that a connection has been queued. This must happen before the
+    /// connection is sent to the acceptor, so the byte written when the queue
+    /// becomes non-empty is always there to be read by `pop`.
+    pub fn push(&self) {
+        if self.pending.fetch_add(1, SeqCst) == 0 {
+            let buf = [0u8];
+            let n = unsafe {
+                libc::write(self.pipe.out, buf.as_ptr() as *c_void, 1)
+            };
+            assert_eq!(n, 1);
+        }
+    }
+
+    /// Notes that a queued connection has been taken by the acceptor.
+    ///
+    /// Unlike the rest of the listener, the queue is taken from without being
+    /// homed, hence the atomic count.
+    pub fn pop(&self) {
+        if self.pending.fetch_sub(1, SeqCst) == 1 {
+            let mut buf = [0u8];
+            let n = unsafe {
+                libc::read(self.pipe.input, buf.as_mut_ptr() as *mut c_void, 1)
+            };
+            assert_eq!(n, 1);
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn fd(&self) -> c_int { self.pipe.input }
+}
+
+impl Drop for Readiness {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = libc::close(self.pipe.input);
+            let _ = libc::close(self.pipe.out);
+        }
+    }
+}
+
+#[cfg(unix)]
+struct PollCtx {
+    task: Option<BlockedTask>,
+    ready: uint,
+}
+
+#[cfg(unix)]
+struct Watch {
+    handle: *uvll::uv_poll_t,
+    fd: *mut rtio::PollFd,
+    cx: *mut PollCtx,
+}
+
+/// Waits for any of `fds` to become ready, filling in their readiness and
+/// returning how many are ready. Returns 0 if the timeout expired first.
+///
+/// Each descriptor gets a `uv_poll_t` of its own for the duration of the call.
+/// libuv only lets one handle watch a descriptor at a time, which is why the
+/// listeners hand out a `Readiness` rather than their socket, and why a stream
+/// mustn't be read from or written to by another task while it's polled.
+#[cfg(unix)]
+pub fn poll(io: &mut UvIoFactory, fds: &mut [rtio::PollFd],
+            timeout_ms: Option<u64>) -> Result<uint, UvError> {
+    let mut cx = PollCtx { task: None, ready: 0 };
+    let mut watches = Vec::with_capacity(fds.len());
+
+    for fd in fds.mut_iter() {
+        fd.readable = false;
+        fd.writable = false;
+        let mut events = 0;
+        if fd.read { events |= uvll::UV_READABLE; }
+        if fd.write { events |= uvll::UV_WRITABLE; }
+
+        let handle = unsafe { uvll::malloc_handle(uvll::UV_POLL) };
+        match unsafe { uvll::uv_poll_init(io.loop_.handle, handle, fd.fd) } {
+            0 => {}
+            n => {
+                unsafe { uvll::free_handle(handle) }
+                close(watches.as_slice());
+                return Err(UvError(n))
+            }
+        }
+        watches.push(Watch { handle: handle, fd: fd as *mut rtio::PollFd,
+                             cx: &mut cx as *mut PollCtx });
+        // Start watching once the handle is on the list, so an error here
+        // still gets it closed.
+        match unsafe { uvll::uv_poll_start(handle, events, poll_cb) } {
+            0 => {}
+            n => {
+                close(watches.as_slice());
+                return Err(UvError(n))
+            }
+        }
+    }
+
+    let mut timer = timeout_ms.map(|ms| {
+        let mut timer = TimerWatcher::new(io);
+        timer.start(timer_cb, ms, 0);
+        timer
+    });
+
+    wait_until_woken_after(&mut cx.task, &io.loop_, || {
+        for watch in watches.iter() {
+            let data = watch as *Watch;
+            unsafe { uvll::set_data_for_uv_handle(watch.handle, data) }
+        }
+        match timer {
+            Some(ref mut t) => unsafe { t.set_data(&cx as *PollCtx) },
+            None => {}
+        }
+    });
+
+    match timer {
+        Some(ref mut t) => t.stop(),
+        None => {}
+    }
+    close(watches.as_slice());
+    return Ok(cx.ready);
+
+    extern fn poll_cb(handle: *uvll::uv_poll_t, status: c_int,
+                      events: c_int) {
+        let watch: &mut Watch = unsafe {
+            &mut *(uvll::get_data_for_uv_handle(handle) as *mut Watch)
+        };
+        let fd = unsafe { &mut *watch.fd };
+        let cx = unsafe { &mut *watch.cx };
+
+        // An error means that a read won't block (it'll return the error), so
+        // it's reported as the descriptor being readable.
+        if status < 0 {
+            fd.readable = true;
+        } else {
+            fd.readable = events & uvll::UV_READABLE != 0;
+            fd.writable = events & uvll::UV_WRITABLE != 0;
+        }
+        cx.ready += 1;
+
+        // Each descriptor is only counted once, and other descriptors may
+        // still become ready before the task gets to run.
+        unsafe { uvll::uv_poll_stop(handle); }
+        if cx.task.is_some() {
+            wakeup(&mut cx.task);
+        }
+    }
+
+    extern fn timer_cb(handle: *uvll::uv_timer_t) {
+        let cx: &mut PollCtx = unsafe {
+            &mut *(uvll::get_data_for_uv_handle(handle) as *mut PollCtx)
+        };
+        if cx.task.is_some() {
+            wakeup(&mut cx.task);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn close(watches: &[Watch]) {
+    for watch in watches.iter() {
+        unsafe {
+            uvll::set_data_for_uv_handle(watch.handle, ptr::null::<()>());
+            uvll::uv_close(watch.handle as *uvll::uv_handle_t, close_cb);
+        }
+    }
+
+    extern fn close_cb(handle: *uvll::uv_handle_t) {
+        unsafe { uvll::free_handle(handle) }
+    }
+}
@@ -12,6 +12,7 @@ use libc::c_int;
 use libc;
 use std::io::IoError;
 use std::io::process;
+use std::os;
 use std::ptr;
 use std::c_str::CString;
 use std::rt::rtio::{ProcessConfig, RtioProcess};
@@ -53,7 +54,26 @@ impl Process {
     /// occurred.
     pub fn spawn(io_loop: &mut UvIoFactory, cfg: ProcessConfig)
                 -> Result<(Box<Process>, Vec<Option<PipeWatcher>>), UvError> {
-        let mut io = vec![cfg.stdin, cfg.stdout, cfg.stderr];
+        // libuv only creates the pipe for a piped stdout while spawning the
+        // process, too late for stderr to inherit it. Instead a pipe is created
+        // up front for the two to share, and the child gets its write end as
+        // both of them.
+        let shared = if cfg.stderr_to_stdout && cfg.stdout == process::Piped {
+            Some(os::pipe())
+        } else {
+            None
+        };
+        let (stdout, stderr) = match shared {
+            Some(ref pipe) => (process::Fd(pipe.out), process::Fd(pipe.out)),
+            None if cfg.stderr_to_stdout => {
+                match cfg.stdout {
+                    process::Inherit => (process::Inherit, process::Fd(1)),
+                    other => (other, other),
+                }
+            }
+            None => (cfg.stdout, cfg.stderr),
+        };
+        let mut io = vec![cfg.stdin, stdout, stderr];
         for slot in cfg.extra_io.iter() {
             io.push(*slot);
         }
@@ -61,9 +81,9 @@ impl Process {
         let mut ret_io = Vec::with_capacity(io.len());
         unsafe {
             stdio.set_len(io.len());
-            for (slot, other) in stdio.iter().zip(io.iter()) {
+            for (i, (slot, other)) in stdio.iter().zip(io.iter()).enumerate() {
                 let io = set_stdio(slot as *uvll::uv_stdio_container_t, other,
-                                   io_loop);
+                                   i as libc::c_int, io_loop);
                 ret_io.push(io);
             }
         }
@@ -114,6 +134,19 @@ impl Process {
             })
         });
 
+        match shared {
+            Some(pipe) => {
+                unsafe { libc::close(pipe.out); }
+                if ret.is_err() {
+                    unsafe { libc::close(pipe.input); }
+                } else {
+                    *ret_io.get_mut(1) = Some(try!(PipeWatcher::open(io_loop,
+                                                                     pipe.input)));
+                }
+            }
+            None => {}
+        }
+
         match ret {
             Ok(p) => Ok((p, ret_io)),
             Err(e) => Err(e),
@@ -145,25 +178,34 @@ extern fn on_exit(handle: *uvll::uv_process_t,
     wakeup(&mut p.to_wake);
 }
 
+/// Configures `dst` as the child's file descriptor `fd` according to `io`.
 unsafe fn set_stdio(dst: *uvll::uv_stdio_container_t,
-                    io: &process::StdioContainer,
+                    io: &process::Stdio,
+                    fd: libc::c_int,
                     io_loop: &mut UvIoFactory) -> Option<PipeWatcher> {
     match *io {
-        process::Ignored => {
+        process::Inherit if fd < 3 => {
+            uvll::set_stdio_container_flags(dst, uvll::STDIO_INHERIT_FD);
+            uvll::set_stdio_container_fd(dst, fd);
+            None
+        }
+        process::Inherit | process::Null => {
             uvll::set_stdio_container_flags(dst, uvll::STDIO_IGNORE);
             None
         }
-        process::InheritFd(fd) => {
+        process::Fd(fd) => {
             uvll::set_stdio_container_flags(dst, uvll::STDIO_INHERIT_FD);
             uvll::set_stdio_container_fd(dst, fd);
             None
         }
-        process::CreatePipe(readable, writable) => {
+        process::Piped => {
+            // stdin is only read by the child, stdout and stderr are only
+            // written, and any extra descriptors are both
             let mut flags = uvll::STDIO_CREATE_PIPE as libc::c_int;
-            if readable {
+            if fd != 1 && fd != 2 {
                 flags |= uvll::STDIO_READABLE_PIPE as libc::c_int;
             }
-            if writable {
+            if fd != 0 {
                 flags |= uvll::STDIO_WRITABLE_PIPE as libc::c_int;
             }
             let pipe = PipeWatcher::new(io_loop, false);
@@ -23,23 +23,28 @@ pub struct SignalWatcher {
 
     channel: Sender<Signum>,
     signal: Signum,
+    masked: bool,
+    pending: bool,
 }
 
 impl SignalWatcher {
     pub fn new(io: &mut UvIoFactory, signum: Signum, channel: Sender<Signum>)
                -> Result<Box<SignalWatcher>, UvError> {
+        let raw = signum.os_signum().expect("signal not available");
         let s = box SignalWatcher {
             handle: UvHandle::alloc(None::<SignalWatcher>, uvll::UV_SIGNAL),
             home: io.make_handle(),
             channel: channel,
             signal: signum,
+            masked: false,
+            pending: false,
         };
         assert_eq!(unsafe {
             uvll::uv_signal_init(io.uv_loop(), s.handle)
         }, 0);
 
         match unsafe {
-            uvll::uv_signal_start(s.handle, signal_cb, signum as c_int)
+            uvll::uv_signal_start(s.handle, signal_cb, raw)
         } {
             0 => Ok(s.install()),
             n => Err(UvError(n)),
@@ -50,8 +55,12 @@ impl SignalWatcher {
 
 extern fn signal_cb(handle: *uvll::uv_signal_t, signum: c_int) {
     let s: &mut SignalWatcher = unsafe { UvHandle::from_uv_handle(&handle) };
-    assert_eq!(signum as int, s.signal as int);
-    let _ = s.channel.send_opt(s.signal);
+    assert_eq!(Some(signum), s.signal.os_signum());
+    if s.masked {
+        s.pending = true;
+    } else {
+        let _ = s.channel.send_opt(s.signal);
+    }
 }
 
 impl HomingIO for SignalWatcher {
@@ -62,7 +71,16 @@ impl UvHandle<uvll::uv_signal_t> for SignalWatcher {
     fn uv_handle(&self) -> *uvll::uv_signal_t { self.handle }
 }
 
-impl RtioSignal for SignalWatcher {}
+impl RtioSignal for SignalWatcher {
+    fn set_masked(&mut self, masked: bool) {
+        let _m = self.fire_homing_missile();
+        self.masked = masked;
+        if !masked && self.pending {
+            self.pending = false;
+            let _ = self.channel.send_opt(self.signal);
+        }
+    }
+}
 
 impl Drop for SignalWatcher {
     fn drop(&mut self) {
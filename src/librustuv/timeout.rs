@@ -15,6 +15,7 @@ use std::rt::task::BlockedTask;
 
 use access;
 use homing::{HomeHandle, HomingMissile, HomingIO};
+use poll::Readiness;
 use timer::TimerWatcher;
 use uvll;
 use uvio::UvIoFactory;
@@ -311,9 +312,16 @@ impl AcceptTimeout {
         AcceptTimeout { timer: None, timeout_tx: None, timeout_rx: None }
     }
 
-    pub fn accept<T: Send>(&mut self, c: &Receiver<IoResult<T>>) -> IoResult<T> {
+    /// Takes the next connection queued on `c`, noting in `ready` that it's
+    /// no longer pending.
+    pub fn accept<T: Send>(&mut self, c: &Receiver<IoResult<T>>,
+                           ready: &Readiness) -> IoResult<T> {
         match self.timeout_rx {
-            None => c.recv(),
+            None => {
+                let data = c.recv_unwrap();
+                ready.pop();
+                data
+            }
             Some(ref rx) => {
                 use std::comm::Select;
 
@@ -321,7 +329,7 @@ impl AcceptTimeout {
                 // select just yet). If someone's pending then we should return
                 // them immediately.
                 match c.try_recv() {
-                    Ok(data) => return data,
+                    Ok(data) => { ready.pop(); return data }
                     Err(..) => {}
                 }
 
@@ -339,7 +347,9 @@ impl AcceptTimeout {
                 if s.wait() == timeout.id() {
                     Err(uv_error_to_io_error(UvError(uvll::ECANCELED)))
                 } else {
-                    c.recv()
+                    let data = c.recv_unwrap();
+                    ready.pop();
+                    data
                 }
             }
         }
@@ -16,9 +16,15 @@ use homing::{HomeHandle, HomingIO};
 use super::{UvHandle, ForbidUnwind, ForbidSwitch, wait_until_woken_after, Loop};
 use uvio::UvIoFactory;
 use uvll;
+use wheel::Entry;
 
+/// A timer on a loop, which is run by the loop's timer wheel.
+///
+/// The libuv timer handle is never started, but it's still what the callbacks
+/// of the timer receive so that they can find their data.
 pub struct TimerWatcher {
     pub handle: *uvll::uv_timer_t,
+    entry: Box<Entry>,
     home: HomeHandle,
     action: Option<NextAction>,
     blocker: Option<BlockedTask>,
@@ -43,6 +49,7 @@ impl TimerWatcher {
         assert_eq!(unsafe { uvll::uv_timer_init(loop_.handle, handle) }, 0);
         TimerWatcher {
             handle: handle,
+            entry: box Entry::new(handle),
             action: None,
             blocker: None,
             home: home,
@@ -51,13 +58,13 @@ impl TimerWatcher {
     }
 
     pub fn start(&mut self, f: uvll::uv_timer_cb, msecs: u64, period: u64) {
-        assert_eq!(unsafe {
-            uvll::uv_timer_start(self.handle, f, msecs, period)
-        }, 0)
+        let entry = &mut *self.entry as *mut Entry;
+        self.uv_loop().timers().start(entry, f, msecs, period)
     }
 
     pub fn stop(&mut self) {
-        assert_eq!(unsafe { uvll::uv_timer_stop(self.handle) }, 0)
+        let entry = &mut *self.entry as *mut Entry;
+        self.uv_loop().timers().stop(entry)
     }
 
     pub unsafe fn set_data<T>(&mut self, data: *T) {
@@ -190,9 +197,9 @@ mod test {
     fn oneshot() {
         let mut timer = TimerWatcher::new(local_loop());
         let port = timer.oneshot(1);
-        port.recv();
+        port.recv_unwrap();
         let port = timer.oneshot(1);
-        port.recv();
+        port.recv_unwrap();
     }
 
     #[test]
@@ -203,18 +210,18 @@ mod test {
         timer.sleep(1);
         assert_eq!(oport.recv_opt(), Err(()));
         assert_eq!(pport.recv_opt(), Err(()));
-        timer.oneshot(1).recv();
+        timer.oneshot(1).recv_unwrap();
     }
 
     #[test]
     fn period() {
         let mut timer = TimerWatcher::new(local_loop());
         let port = timer.period(1);
-        port.recv();
-        port.recv();
+        port.recv_unwrap();
+        port.recv_unwrap();
         let port2 = timer.period(1);
-        port2.recv();
-        port2.recv();
+        port2.recv_unwrap();
+        port2.recv_unwrap();
     }
 
     #[test]
@@ -320,4 +327,27 @@ mod test {
         // callback do something terrible.
         timer2.sleep(2);
     }
+
+    #[test]
+    fn many_timers() {
+        let mut timers = Vec::from_fn(1000, |_| TimerWatcher::new(local_loop()));
+        let ports: Vec<Receiver<()>> = timers.mut_iter().enumerate().map(|(i, t)| {
+            t.oneshot((i % 20) as u64)
+        }).collect();
+        for port in ports.iter() {
+            port.recv_unwrap();
+        }
+    }
+
+    #[test]
+    fn far_timers() {
+        // a timer more than a revolution of the timer wheel away mustn't fire
+        // when the nearer ones do
+        let mut far = TimerWatcher::new(local_loop());
+        let far_port = far.oneshot(100000);
+        let mut near = TimerWatcher::new(local_loop());
+        near.sleep(1);
+        near.sleep(5);
+        assert!(far_port.try_recv().is_err());
+    }
 }
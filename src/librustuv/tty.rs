@@ -10,6 +10,11 @@
 
 use libc;
 use std::io::IoError;
+use std::io;
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::os;
 use std::ptr;
 use std::rt::rtio::RtioTTY;
 
@@ -99,12 +104,43 @@ impl RtioTTY for TtyWatcher {
         }
     }
 
+    // libuv only exposes the terminal's mode as a whole, so echo is toggled on
+    // the terminal's attributes directly, as libnative does.
+    #[cfg(unix)]
+    fn set_echo(&mut self, echo: bool) -> Result<(), IoError> {
+        let mut attrs: termios::termios = unsafe { mem::zeroed() };
+        if unsafe { termios::tcgetattr(self.fd, &mut attrs) } != 0 {
+            return Err(IoError::last_error())
+        }
+        if echo {
+            attrs.c_lflag |= termios::ECHO;
+        } else {
+            attrs.c_lflag &= !termios::ECHO;
+        }
+        loop {
+            match unsafe { termios::tcsetattr(self.fd, termios::TCSANOW, &attrs) } {
+                0 => return Ok(()),
+                _ if os::errno() as libc::c_int == libc::EINTR => {}
+                _ => return Err(IoError::last_error()),
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    fn set_echo(&mut self, _echo: bool) -> Result<(), IoError> {
+        Err(IoError {
+            kind: io::IoUnavailable,
+            desc: "controlling echo is not supported on windows",
+            detail: None,
+        })
+    }
+
     #[allow(unused_mut)]
     fn get_winsize(&mut self) -> Result<(int, int), IoError> {
         let mut width: libc::c_int = 0;
         let mut height: libc::c_int = 0;
         let widthptr: *libc::c_int = &width;
-        let heightptr: *libc::c_int = &width;
+        let heightptr: *libc::c_int = &height;
 
         let _m = self.fire_homing_missile();
         match unsafe { uvll::uv_tty_get_winsize(self.tty,
@@ -135,3 +171,98 @@ impl Drop for TtyWatcher {
         }
     }
 }
+
+// The parts of <termios.h> needed to toggle echo, see libnative's c_unix.rs.
+#[cfg(target_os = "linux")]
+#[cfg(target_os = "android")]
+mod termios {
+    use libc;
+
+    pub type tcflag_t = libc::c_uint;
+
+    pub static ECHO: tcflag_t = 0o10;
+
+    #[cfg(not(target_arch = "mips"))]
+    pub static TCSANOW: libc::c_int = 0;
+    #[cfg(target_arch = "mips")]
+    pub static TCSANOW: libc::c_int = 0x540e;
+
+    #[cfg(target_os = "linux")]
+    pub struct termios {
+        pub c_iflag: tcflag_t,
+        pub c_oflag: tcflag_t,
+        pub c_cflag: tcflag_t,
+        pub c_lflag: tcflag_t,
+        c_line: u8,
+        c_cc: [u8, ..32],
+        c_ispeed: libc::c_uint,
+        c_ospeed: libc::c_uint,
+    }
+
+    #[cfg(target_os = "linux")]
+    extern {
+        pub fn tcgetattr(fd: libc::c_int, termios: *mut termios) -> libc::c_int;
+        pub fn tcsetattr(fd: libc::c_int, actions: libc::c_int,
+                         termios: *termios) -> libc::c_int;
+    }
+
+    // bionic only provides these as inline functions, so call the ioctls they
+    // wrap directly. This is the kernel's definition of the structure.
+    #[cfg(target_os = "android")]
+    pub struct termios {
+        pub c_iflag: tcflag_t,
+        pub c_oflag: tcflag_t,
+        pub c_cflag: tcflag_t,
+        pub c_lflag: tcflag_t,
+        c_line: u8,
+        c_cc: [u8, ..19],
+    }
+
+    #[cfg(target_os = "android")]
+    extern {
+        fn ioctl(fd: libc::c_int, req: libc::c_ulong, ...) -> libc::c_int;
+    }
+
+    #[cfg(target_os = "android")]
+    pub unsafe fn tcgetattr(fd: libc::c_int,
+                            termios: *mut termios) -> libc::c_int {
+        ioctl(fd, 0x5401, termios)
+    }
+
+    #[cfg(target_os = "android")]
+    pub unsafe fn tcsetattr(fd: libc::c_int, _actions: libc::c_int,
+                            termios: *termios) -> libc::c_int {
+        ioctl(fd, 0x5402, termios)
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[cfg(target_os = "freebsd")]
+mod termios {
+    use libc;
+
+    #[cfg(target_os = "macos")]
+    pub type tcflag_t = libc::c_ulong;
+    #[cfg(target_os = "freebsd")]
+    pub type tcflag_t = libc::c_uint;
+
+    pub static ECHO: tcflag_t = 0x8;
+
+    pub static TCSANOW: libc::c_int = 0;
+
+    pub struct termios {
+        pub c_iflag: tcflag_t,
+        pub c_oflag: tcflag_t,
+        pub c_cflag: tcflag_t,
+        pub c_lflag: tcflag_t,
+        c_cc: [u8, ..20],
+        c_ispeed: tcflag_t,
+        c_ospeed: tcflag_t,
+    }
+
+    extern {
+        pub fn tcgetattr(fd: libc::c_int, termios: *mut termios) -> libc::c_int;
+        pub fn tcsetattr(fd: libc::c_int, actions: libc::c_int,
+                         termios: *termios) -> libc::c_int;
+    }
+}
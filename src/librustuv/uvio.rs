@@ -12,7 +12,7 @@
 
 use std::c_str::CString;
 use std::io::IoError;
-use std::io::net::ip::SocketAddr;
+use std::io::net::ip::{IpAddr, SocketAddr};
 use std::io::signal::Signum;
 use std::io::{FileMode, FileAccess, Open, Append, Truncate, Read, Write,
               ReadWrite, FileStat};
@@ -39,6 +39,7 @@ use homing::HomeHandle;
 use idle::IdleWatcher;
 use net::{TcpWatcher, TcpListener, UdpWatcher};
 use pipe::{PipeWatcher, PipeListener};
+#[cfg(unix)] use poll;
 use process::Process;
 use signal::SignalWatcher;
 use timer::TimerWatcher;
@@ -71,11 +72,12 @@ impl Drop for UvEventLoop {
         // after the loop has been closed because during the closing of the loop
         // the handle is required to be used apparently.
         //
-        // Lastly, after we've closed the pool of handles we pump the event loop
-        // one last time to run any closing callbacks to make sure the loop
-        // shuts down cleanly.
+        // Lastly, after we've closed the pool of handles and the timer wheel we
+        // pump the event loop one last time to run any closing callbacks to
+        // make sure the loop shuts down cleanly.
         let handle = self.uvio.handle_pool.get_ref().handle();
         drop(self.uvio.handle_pool.take());
+        self.uvio.loop_.close_timers();
         self.run();
 
         self.uvio.loop_.close();
@@ -112,6 +114,10 @@ impl EventLoop for UvEventLoop {
     fn has_active_io(&self) -> bool {
         self.uvio.loop_.get_blockers() > 0
     }
+
+    fn has_pending_events(&self) -> bool {
+        self.has_active_io() || self.uvio.loop_.has_active_handles()
+    }
 }
 
 #[test]
@@ -156,8 +162,16 @@ impl IoFactory for UvIoFactory {
         }
     }
 
-    fn tcp_bind(&mut self, addr: SocketAddr)
+    fn tcp_bind(&mut self, addr: SocketAddr, reuse_addr: bool)
                 -> Result<Box<rtio::RtioTcpListener:Send>, IoError> {
+        // libuv always sets SO_REUSEADDR on unix, and never on windows
+        if reuse_addr != cfg!(unix) {
+            return Err(IoError {
+                kind: io::IoUnavailable,
+                desc: "libuv does not allow configuring SO_REUSEADDR",
+                detail: None,
+            })
+        }
         match TcpListener::bind(self, addr) {
             Ok(t) => Ok(t as Box<rtio::RtioTcpListener:Send>),
             Err(e) => Err(uv_error_to_io_error(e)),
@@ -182,6 +196,15 @@ impl IoFactory for UvIoFactory {
         r.map_err(uv_error_to_io_error)
     }
 
+    fn get_address_name(&mut self, _addr: IpAddr) -> Result<String, IoError> {
+        // this version of libuv has no asynchronous getnameinfo
+        Err(IoError {
+            kind: io::IoUnavailable,
+            desc: "reverse name lookup is not supported by libuv",
+            detail: None,
+        })
+    }
+
     fn fs_from_raw_fd(&mut self, fd: c_int, close: rtio::CloseBehavior)
                       -> Box<rtio::RtioFileStream:Send> {
         box FileWatcher::new(self, fd, close) as
@@ -194,10 +217,13 @@ impl IoFactory for UvIoFactory {
             io::Open => 0,
             io::Append => libc::O_APPEND,
             io::Truncate => libc::O_TRUNC,
+            io::CreateNew => libc::O_CREAT | libc::O_EXCL,
         };
-        // Opening with a write permission must silently create the file.
+        // Opening with a write permission must silently create the file. The mode
+        // is only used when the file is created, which CreateNew also does.
         let (flags, mode) = match fa {
-            io::Read => (flags | libc::O_RDONLY, 0),
+            io::Read => (flags | libc::O_RDONLY,
+                         libc::S_IRUSR | libc::S_IWUSR),
             io::Write => (flags | libc::O_WRONLY | libc::O_CREAT,
                           libc::S_IRUSR | libc::S_IWUSR),
             io::ReadWrite => (flags | libc::O_RDWR | libc::O_CREAT,
@@ -262,6 +288,15 @@ impl IoFactory for UvIoFactory {
         let r = FsRequest::readlink(&self.loop_, path);
         r.map_err(uv_error_to_io_error)
     }
+    fn fs_realpath(&mut self, _path: &CString) -> Result<Path, IoError> {
+        // libuv has no realpath, so libstd resolves the path itself with
+        // lstat and readlink
+        Err(IoError {
+            kind: io::IoUnavailable,
+            desc: "realpath is not supported by libuv",
+            detail: None,
+        })
+    }
     fn fs_utime(&mut self, path: &CString, atime: u64, mtime: u64)
         -> Result<(), IoError>
     {
@@ -305,6 +340,16 @@ impl IoFactory for UvIoFactory {
         }
     }
 
+    fn unix_datagram(&mut self, _path: Option<&CString>)
+                     -> Result<Box<rtio::RtioUnixDatagram:Send>, IoError> {
+        // libuv's pipes are stream-oriented only
+        Err(IoError {
+            kind: io::IoUnavailable,
+            desc: "unix datagram sockets are not supported by libuv",
+            detail: None,
+        })
+    }
+
     fn tty_open(&mut self, fd: c_int, readable: bool)
             -> Result<Box<rtio::RtioTTY:Send>, IoError> {
         match TtyWatcher::new(self, fd, readable) {
@@ -323,9 +368,22 @@ impl IoFactory for UvIoFactory {
 
     fn signal(&mut self, signum: Signum, channel: Sender<Signum>)
         -> Result<Box<rtio::RtioSignal:Send>, IoError> {
+        if signum.os_signum().is_none() {
+            return Err(IoError {
+                kind: io::InvalidInput,
+                desc: "signal not available on this platform",
+                detail: None,
+            })
+        }
         match SignalWatcher::new(self, signum, channel) {
             Ok(s) => Ok(s as Box<rtio::RtioSignal:Send>),
             Err(e) => Err(uv_error_to_io_error(e)),
         }
     }
+
+    #[cfg(unix)]
+    fn poll(&mut self, fds: &mut [rtio::PollFd], timeout_ms: Option<u64>)
+            -> Result<uint, IoError> {
+        poll::poll(self, fds, timeout_ms).map_err(uv_error_to_io_error)
+    }
 }
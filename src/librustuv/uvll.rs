@@ -38,7 +38,8 @@ use std::rt::libc_heap::malloc_raw;
 use libc::uintptr_t;
 
 pub use self::errors::{EACCES, ECONNREFUSED, ECONNRESET, EPIPE, ECONNABORTED,
-                       ECANCELED, EBADF, ENOTCONN, ENOENT, EADDRNOTAVAIL};
+                       ECANCELED, EBADF, ENOTCONN, ENOENT, EADDRNOTAVAIL,
+                       EEXIST};
 
 pub static OK: c_int = 0;
 pub static EOF: c_int = -4095;
@@ -61,6 +62,7 @@ pub mod errors {
     pub static ECANCELED: c_int = -4081;
     pub static EBADF: c_int = -4083;
     pub static EADDRNOTAVAIL: c_int = -4090;
+    pub static EEXIST: c_int = -4075;
 }
 #[cfg(not(windows))]
 pub mod errors {
@@ -77,6 +79,7 @@ pub mod errors {
     pub static ECANCELED : c_int = -libc::ECANCELED;
     pub static EBADF : c_int = -libc::EBADF;
     pub static EADDRNOTAVAIL : c_int = -libc::EADDRNOTAVAIL;
+    pub static EEXIST : c_int = -libc::EEXIST;
 }
 
 pub static PROCESS_SETUID: c_int = 1 << 0;
@@ -92,6 +95,9 @@ pub static STDIO_INHERIT_STREAM: c_int = 0x04;
 pub static STDIO_READABLE_PIPE: c_int = 0x10;
 pub static STDIO_WRITABLE_PIPE: c_int = 0x20;
 
+pub static UV_READABLE: c_int = 1;
+pub static UV_WRITABLE: c_int = 2;
+
 #[cfg(unix)]
 pub type uv_buf_len_t = libc::size_t;
 #[cfg(windows)]
@@ -157,6 +163,7 @@ pub type uv_process_t = c_void;
 pub type uv_pipe_t = c_void;
 pub type uv_tty_t = c_void;
 pub type uv_signal_t = c_void;
+pub type uv_poll_t = c_void;
 pub type uv_shutdown_t = c_void;
 
 pub struct uv_timespec_t {
@@ -247,12 +254,17 @@ pub type uv_signal_cb = extern "C" fn(handle: *uv_signal_t,
                                       signum: c_int);
 pub type uv_fs_cb = extern "C" fn(req: *uv_fs_t);
 pub type uv_shutdown_cb = extern "C" fn(req: *uv_shutdown_t, status: c_int);
+pub type uv_poll_cb = extern "C" fn(handle: *uv_poll_t, status: c_int,
+                                    events: c_int);
 
 #[cfg(unix)] pub type uv_uid_t = libc::types::os::arch::posix88::uid_t;
 #[cfg(unix)] pub type uv_gid_t = libc::types::os::arch::posix88::gid_t;
 #[cfg(windows)] pub type uv_uid_t = libc::c_uchar;
 #[cfg(windows)] pub type uv_gid_t = libc::c_uchar;
 
+#[cfg(unix)] pub type uv_os_fd_t = c_int;
+#[cfg(windows)] pub type uv_os_fd_t = libc::HANDLE;
+
 #[repr(C)]
 #[deriving(Eq)]
 pub enum uv_handle_type {
@@ -441,6 +453,9 @@ pub unsafe fn get_loop_from_getaddrinfo_req(req: *uv_getaddrinfo_t) -> *uv_loop_
 pub unsafe fn get_loop_for_uv_handle<T>(handle: *T) -> *c_void {
     return rust_uv_get_loop_for_uv_handle(handle as *c_void);
 }
+pub unsafe fn get_handle_type<T>(handle: *T) -> uv_handle_type {
+    return rust_uv_get_handle_type(handle as *c_void);
+}
 pub unsafe fn get_stream_handle_from_connect_req(connect: *uv_connect_t) -> *uv_stream_t {
     return rust_uv_get_stream_handle_from_connect_req(connect);
 }
@@ -506,6 +521,7 @@ extern {
     fn rust_uv_get_stream_handle_from_connect_req(req: *uv_connect_t) -> *uv_stream_t;
     fn rust_uv_get_stream_handle_from_write_req(req: *uv_write_t) -> *uv_stream_t;
     fn rust_uv_get_loop_for_uv_handle(handle: *c_void) -> *c_void;
+    fn rust_uv_get_handle_type(handle: *c_void) -> uv_handle_type;
     fn rust_uv_get_data_for_uv_loop(loop_ptr: *c_void) -> *c_void;
     fn rust_uv_set_data_for_uv_loop(loop_ptr: *c_void, data: *c_void);
     fn rust_uv_get_data_for_uv_handle(handle: *c_void) -> *c_void;
@@ -526,7 +542,10 @@ extern {
     pub fn uv_handle_size(ty: uv_handle_type) -> size_t;
     pub fn uv_req_size(ty: uv_req_type) -> size_t;
     pub fn uv_run(l: *uv_loop_t, mode: uv_run_mode) -> c_int;
+    pub fn uv_now(l: *uv_loop_t) -> libc::uint64_t;
     pub fn uv_close(h: *uv_handle_t, cb: uv_close_cb);
+    pub fn uv_is_active(h: *uv_handle_t) -> c_int;
+    pub fn uv_fileno(h: *uv_handle_t, fd: *mut uv_os_fd_t) -> c_int;
     pub fn uv_walk(l: *uv_loop_t, cb: uv_walk_cb, arg: *c_void);
     pub fn uv_buf_init(base: *c_char, len: c_uint) -> uv_buf_t;
     pub fn uv_strerror(err: c_int) -> *c_char;
@@ -589,6 +608,12 @@ extern {
                           repeat: libc::uint64_t) -> c_int;
     pub fn uv_timer_stop(handle: *uv_timer_t) -> c_int;
 
+    // poll bindings
+    pub fn uv_poll_init(l: *uv_loop_t, h: *uv_poll_t, fd: c_int) -> c_int;
+    pub fn uv_poll_start(h: *uv_poll_t, events: c_int,
+                         cb: uv_poll_cb) -> c_int;
+    pub fn uv_poll_stop(h: *uv_poll_t) -> c_int;
+
     // fs operations
     pub fn uv_fs_open(loop_ptr: *uv_loop_t, req: *uv_fs_t, path: *c_char,
                       flags: c_int, mode: c_int, cb: uv_fs_cb) -> c_int;
@@ -0,0 +1,276 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A hashed timer wheel
+//!
+//! libuv keeps all of the timers of a loop ordered by deadline, so starting
+//! and stopping a timer gets slower as more timers are outstanding. Servers
+//! with many connections start and stop timers all the time (every read or
+//! write with a timeout does), so instead all of the timers of a loop are kept
+//! in a wheel which is driven by a single libuv timer.
+//!
+//! The wheel has a slot for each millisecond of a revolution, and a timer is
+//! kept in the slot its deadline hashes to. Starting a timer is then a push
+//! onto a slot and stopping it only has to look through the one slot. Every
+//! time the libuv timer fires, the slots for the milliseconds which have gone
+//! by are swept for the timers which are due.
+//!
+//! This is a single hashed wheel rather than a hierarchy of wheels, so timers
+//! more than a revolution away share slots with the nearer ones and are just
+//! skipped by the sweeps until they're due. To know when to fire next, the
+//! wheel also keeps a heap of the deadlines it has been given. Stopping a timer
+//! leaves its deadline in the heap, which is thrown away once it reaches the
+//! top of the heap (or when the heap is rebuilt for having too many of them).
+//!
+//! The entries of the wheel are owned by the timers themselves (see
+//! `TimerWatcher`), which stop their entry before going away. A timer's
+//! callback is not allowed to context switch, so no timer can be destroyed
+//! while the wheel is in the middle of running callbacks.
+
+use collections::PriorityQueue;
+use std::cmp;
+use std::u64;
+
+use super::{UvHandle, Loop};
+use uvll;
+
+// The number of slots, and the number of milliseconds per revolution
+static SLOTS: u64 = 4096;
+
+#[deriving(Eq)]
+enum State {
+    Idle,
+    Pending, // in the slot for its deadline
+    Due,     // taken out of its slot, waiting for its callback to be run
+}
+
+/// A timer registered with a wheel, which calls `cb` with the `timer` handle
+/// once it's due, just as libuv would.
+pub struct Entry {
+    timer: *uvll::uv_timer_t,
+    cb: Option<uvll::uv_timer_cb>,
+    deadline: u64,
+    period: u64,
+    state: State,
+}
+
+impl Entry {
+    pub fn new(timer: *uvll::uv_timer_t) -> Entry {
+        Entry {
+            timer: timer,
+            cb: None,
+            deadline: 0,
+            period: 0,
+            state: Idle,
+        }
+    }
+}
+
+pub struct TimerWheel {
+    handle: *uvll::uv_timer_t,
+    slots: Vec<Vec<*mut Entry>>,
+    // The next millisecond whose slot hasn't been swept yet
+    next_tick: u64,
+    // The number of pending entries
+    pending: uint,
+    // The deadlines of the pending entries, along with those of entries which
+    // have been stopped or run since. Deadlines are stored as
+    // `u64::MAX - deadline` so the top of the queue is the earliest one.
+    deadlines: PriorityQueue<u64>,
+    // The deadline which the libuv timer is currently set to fire at
+    armed: Option<u64>,
+}
+
+impl TimerWheel {
+    pub fn new(loop_: &Loop) -> Box<TimerWheel> {
+        let handle = UvHandle::alloc(None::<TimerWheel>, uvll::UV_TIMER);
+        assert_eq!(unsafe { uvll::uv_timer_init(loop_.handle, handle) }, 0);
+        let wheel = box TimerWheel {
+            handle: handle,
+            slots: Vec::from_fn(SLOTS as uint, |_| Vec::new()),
+            next_tick: unsafe { uvll::uv_now(loop_.handle) },
+            pending: 0,
+            deadlines: PriorityQueue::new(),
+            armed: None,
+        };
+        wheel.install()
+    }
+
+    /// Starts (or restarts) the timer of `entry` to fire after `msecs`, and
+    /// then every `period` milliseconds if `period` is nonzero.
+    pub fn start(&mut self, entry: *mut Entry, cb: uvll::uv_timer_cb,
+                 msecs: u64, period: u64) {
+        self.stop(entry);
+        unsafe {
+            (*entry).cb = Some(cb);
+            (*entry).period = period;
+            let deadline = self.now() + msecs;
+            self.insert(entry, deadline);
+        }
+    }
+
+    /// Stops the timer of `entry`, if it's running.
+    pub fn stop(&mut self, entry: *mut Entry) {
+        unsafe {
+            match (*entry).state {
+                Idle => return,
+                Due => {}
+                Pending => {
+                    let slot = self.slots.get_mut(slot_for((*entry).deadline));
+                    let i = slot.iter().position(|&e| e == entry).unwrap();
+                    slot.swap_remove(i);
+                    self.pending -= 1;
+                }
+            }
+            (*entry).state = Idle;
+        }
+        // Don't keep the loop alive once there's nothing left to wait for, but
+        // otherwise an early wakeup is harmless.
+        if self.pending == 0 {
+            self.deadlines.clear();
+            if self.armed.is_some() {
+                assert_eq!(unsafe { uvll::uv_timer_stop(self.handle) }, 0);
+                self.armed = None;
+            }
+        }
+    }
+
+    /// Closes the libuv timer driving this wheel, which must not have any
+    /// pending timers left.
+    pub fn close(&mut self) {
+        assert_eq!(self.pending, 0);
+        self.close_async_();
+    }
+
+    fn now(&self) -> u64 {
+        unsafe { uvll::uv_now(self.uv_loop().handle) }
+    }
+
+    unsafe fn insert(&mut self, entry: *mut Entry, deadline: u64) {
+        // The slot for the current millisecond may already have been swept
+        let deadline = cmp::max(deadline, self.next_tick);
+        (*entry).deadline = deadline;
+        (*entry).state = Pending;
+        self.slots.get_mut(slot_for(deadline)).push(entry);
+        self.pending += 1;
+        self.push_deadline(deadline);
+        match self.armed {
+            Some(d) if d <= deadline => {}
+            _ => self.arm(deadline),
+        }
+    }
+
+    fn arm(&mut self, deadline: u64) {
+        let timeout = deadline - cmp::min(deadline, self.now());
+        assert_eq!(unsafe {
+            uvll::uv_timer_start(self.handle, wheel_cb, timeout, 0)
+        }, 0);
+        self.armed = Some(deadline);
+    }
+
+    // Takes all of the timers which are due out of their slots
+    fn sweep(&mut self, now: u64) -> Vec<*mut Entry> {
+        let mut due = Vec::new();
+        let ticks = if now < self.next_tick {
+            0
+        } else {
+            cmp::min(now + 1 - self.next_tick, SLOTS)
+        };
+        for tick in range(self.next_tick, self.next_tick + ticks) {
+            let slot = self.slots.get_mut(slot_for(tick));
+            let mut i = 0;
+            while i < slot.len() {
+                let entry = *slot.get(i);
+                if unsafe { (*entry).deadline } <= now {
+                    unsafe { (*entry).state = Due; }
+                    due.push(entry);
+                    slot.swap_remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.next_tick = cmp::max(self.next_tick, now + 1);
+        self.pending -= due.len();
+        due.sort_by(|&a, &b| unsafe { (*a).deadline.cmp(&(*b).deadline) });
+        due
+    }
+
+    fn push_deadline(&mut self, deadline: u64) {
+        self.deadlines.push(u64::MAX - deadline);
+
+        // Timers which keep being restarted before they fire (like the timeout
+        // of a busy connection) leave a deadline behind every time, so once
+        // the stale ones outnumber the pending ones the heap is rebuilt from
+        // the slots, which costs about as much as the pushes since the last
+        // rebuild did.
+        if self.deadlines.len() > 2 * self.pending + SLOTS as uint {
+            let deadlines: Vec<u64> = self.slots.iter().flat_map(|s| {
+                s.iter()
+            }).map(|&e| {
+                u64::MAX - unsafe { (*e).deadline }
+            }).collect();
+            self.deadlines = PriorityQueue::from_vec(deadlines);
+        }
+    }
+
+    // Finds the earliest deadline among the pending timers
+    fn earliest(&mut self) -> Option<u64> {
+        if self.pending == 0 {
+            self.deadlines.clear();
+            return None
+        }
+        loop {
+            let deadline = u64::MAX - *self.deadlines.top().unwrap();
+
+            // Every pending entry is in the slot for its deadline, so this is
+            // only stale if that slot has no entry for it any more.
+            let slot = self.slots.get(slot_for(deadline));
+            if slot.iter().any(|&e| unsafe { (*e).deadline } == deadline) {
+                return Some(deadline)
+            }
+            self.deadlines.pop();
+        }
+    }
+}
+
+impl UvHandle<uvll::uv_timer_t> for TimerWheel {
+    fn uv_handle(&self) -> *uvll::uv_timer_t { self.handle }
+}
+
+fn slot_for(deadline: u64) -> uint { (deadline % SLOTS) as uint }
+
+extern fn wheel_cb(handle: *uvll::uv_timer_t) {
+    let wheel: &mut TimerWheel = unsafe { UvHandle::from_uv_handle(&handle) };
+    wheel.armed = None;
+    let now = wheel.now();
+
+    // Periodic timers are rescheduled before their callback is run, and any
+    // timer may be stopped or restarted by an earlier callback.
+    for &entry in wheel.sweep(now).iter() {
+        let cb = unsafe {
+            if (*entry).state != Due { continue }
+            let cb = (*entry).cb.unwrap();
+            (*entry).state = Idle;
+            if (*entry).period > 0 {
+                let deadline = now + (*entry).period;
+                wheel.insert(entry, deadline);
+            }
+            cb
+        };
+        cb(unsafe { (*entry).timer });
+    }
+
+    match (wheel.earliest(), wheel.armed) {
+        (Some(deadline), None) => wheel.arm(deadline),
+        (Some(deadline), Some(armed)) if deadline < armed => wheel.arm(deadline),
+        _ => {}
+    }
+}
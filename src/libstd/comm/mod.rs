@@ -16,11 +16,12 @@
 //! module are the building blocks for synchronization in rust.
 //!
 //! This module provides message-based communication over channels, concretely
-//! defined among three types:
+//! defined among four types:
 //!
 //! * `Sender`
 //! * `SyncSender`
 //! * `Receiver`
+//! * `SharedReceiver`
 //!
 //! A `Sender` or `SyncSender` is used to send data to a `Receiver`. Both
 //! senders are clone-able such that many tasks can send simultaneously to one
@@ -43,16 +44,23 @@
 //!    "rendezvous" channel where each sender atomically hands off a message to
 //!    a receiver.
 //!
+//! Additionally, the `shared_channel()` function returns a `(Sender,
+//! SharedReceiver)` tuple for an asynchronous channel which can also have many
+//! receivers, each message being received by only one of them. This is useful
+//! for distributing work among a pool of tasks.
+//!
 //! ## Failure Propagation
 //!
 //! In addition to being a core primitive for communicating in rust, channels
 //! are the points at which failure is propagated among tasks.  Whenever the one
-//! half of channel is closed, the other half will have its next operation
-//! `fail!`. The purpose of this is to allow propagation of failure among tasks
-//! that are linked to one another via channels.
+//! half of channel is closed, the other half will have its next `send` or
+//! `recv_unwrap` operation `fail!`. The purpose of this is to allow propagation
+//! of failure among tasks that are linked to one another via channels.
 //!
 //! There are methods on both of senders and receivers to perform their
-//! respective operations without failing, however.
+//! respective operations without failing, however. In particular, `recv`
+//! returns an error once the sending half has hung up, and `try_recv` and
+//! `recv_timeout` distinguish this from there being no data available yet.
 //!
 //! ## Runtime Requirements
 //!
@@ -79,7 +87,7 @@
 //! spawn(proc() {
 //!     tx.send(10);
 //! });
-//! assert_eq!(rx.recv(), 10);
+//! assert_eq!(rx.recv_unwrap(), 10);
 //! ```
 //!
 //! Shared usage:
@@ -95,7 +103,7 @@
 //! }
 //!
 //! for _ in range(0, 10) {
-//!     let j = rx.recv();
+//!     let j = rx.recv_unwrap();
 //!     assert!(0 <= j && j < 10);
 //! }
 //! ```
@@ -103,11 +111,11 @@
 //! Propagating failure:
 //!
 //! ```should_fail
-//! // The call to recv() will fail!() because the channel has already hung
+//! // The call to recv_unwrap() will fail!() because the channel has already hung
 //! // up (or been deallocated)
 //! let (tx, rx) = channel::<int>();
 //! drop(tx);
-//! rx.recv();
+//! rx.recv_unwrap();
 //! ```
 //!
 //! Synchronous channels:
@@ -118,7 +126,7 @@
 //!     // This will wait for the parent task to start receiving
 //!     tx.send(53);
 //! });
-//! rx.recv();
+//! rx.recv_unwrap();
 //! ```
 
 // A description of how Rust's channel implementation works
@@ -309,7 +317,7 @@ macro_rules! test (
                 use native;
                 let (tx, rx) = channel();
                 native::task::spawn(proc() { tx.send(f()) });
-                rx.recv();
+                rx.recv_unwrap();
             }
         }
     )
@@ -320,6 +328,7 @@ mod oneshot;
 mod stream;
 mod shared;
 mod sync;
+mod mpmc;
 
 // Use a power of 2 to allow LLVM to optimize to something that's not a
 // division, this is hit pretty regularly.
@@ -358,6 +367,23 @@ pub struct SyncSender<T> {
     marker: marker::NoShare,
 }
 
+/// The receiving-half of a channel created with `shared_channel`.
+///
+/// Unlike a `Receiver`, this half can be cloned to receive in many tasks at
+/// once. Each message sent is received by exactly one of the receivers.
+pub struct SharedReceiver<T> {
+    inner: Arc<Unsafe<mpmc::Packet<T>>>,
+    // can't share in an arc
+    marker: marker::NoShare,
+}
+
+/// An iterator over messages on a `SharedReceiver`, this iterator will block
+/// whenever `next` is called, waiting for a new message, and `None` will be
+/// returned when all of the senders have hung up.
+pub struct SharedMessages<'a, T> {
+    rx: &'a SharedReceiver<T>
+}
+
 /// This enumeration is the list of the possible reasons that try_recv could not
 /// return data when called.
 #[deriving(Eq, Clone, Show)]
@@ -370,6 +396,29 @@ pub enum TryRecvError {
     Disconnected,
 }
 
+/// The error returned by `recv` when all of the senders of a channel have hung
+/// up, so no data will ever be received on it again.
+#[deriving(Eq, Clone, Show)]
+pub struct RecvError;
+
+/// This enumeration is the list of the possible reasons that recv_timeout could
+/// not return data when called.
+///
+/// Along with the data itself, which is the `Ok` side of the `Result` returned
+/// by `recv_timeout`, these make up the three possible outcomes of a receive
+/// with a timeout. There is no separate variant for an empty channel as with
+/// `TryRecvError`: an empty channel is what makes a receive time out, so a
+/// `recv_timeout` of zero milliseconds on an empty channel returns `Timeout`.
+#[deriving(Eq, Clone, Show)]
+pub enum RecvTimeoutError {
+    /// No data arrived on this channel before the timeout elapsed, but the
+    /// sender(s) have not yet disconnected, so data may yet become available.
+    Timeout,
+    /// This channel's sending half has become disconnected, and there will
+    /// never be any more data received on this channel
+    SendDisconnected,
+}
+
 /// This enumeration is the list of the possible error outcomes for the
 /// `SyncSender::try_send` method.
 #[deriving(Eq, Clone, Show)]
@@ -391,6 +440,7 @@ enum Flavor<T> {
     Stream(Arc<Unsafe<stream::Packet<T>>>),
     Shared(Arc<Unsafe<shared::Packet<T>>>),
     Sync(Arc<Unsafe<sync::Packet<T>>>),
+    Mpmc(Arc<Unsafe<mpmc::Packet<T>>>),
 }
 
 #[doc(hidden)]
@@ -433,7 +483,7 @@ impl<T> UnsafeFlavor<T> for Receiver<T> {
 /// // Do some useful work for awhile
 ///
 /// // Let's see what that answer was
-/// println!("{}", rx.recv());
+/// println!("{}", rx.recv_unwrap());
 /// ```
 pub fn channel<T: Send>() -> (Sender<T>, Receiver<T>) {
     let a = Arc::new(Unsafe::new(oneshot::Packet::new()));
@@ -455,6 +505,11 @@ pub fn channel<T: Send>() -> (Sender<T>, Receiver<T>) {
 /// As with asynchronous channels, all senders will fail in `send` if the
 /// `Receiver` has been destroyed.
 ///
+/// Senders which shouldn't block indefinitely on a full channel can use
+/// `try_send`, which never blocks, or `send_timeout`, which blocks for at most
+/// a given number of milliseconds. Both hand the value back if it could not be
+/// sent.
+///
 /// # Example
 ///
 /// ```
@@ -468,14 +523,59 @@ pub fn channel<T: Send>() -> (Sender<T>, Receiver<T>) {
 ///     tx.send(2);
 /// });
 ///
-/// assert_eq!(rx.recv(), 1);
-/// assert_eq!(rx.recv(), 2);
+/// assert_eq!(rx.recv_unwrap(), 1);
+/// assert_eq!(rx.recv_unwrap(), 2);
 /// ```
 pub fn sync_channel<T: Send>(bound: uint) -> (SyncSender<T>, Receiver<T>) {
     let a = Arc::new(Unsafe::new(sync::Packet::new(bound)));
     (SyncSender::new(a.clone()), Receiver::new(Sync(a)))
 }
 
+/// Creates a new asynchronous channel with many receivers.
+///
+/// Sending on this channel works just like a channel created with `channel`,
+/// but the receiving half can be cloned and sent to other tasks, making it
+/// easy to distribute work among a pool of tasks. Each message is received by
+/// only one of the receivers.
+///
+/// Messages are received in the order they were sent. When several receivers
+/// are blocked waiting for a message they are woken up in the order that they
+/// blocked, but a receiver which isn't blocked may take a message first. There
+/// is no guarantee that the messages are spread evenly among the receivers.
+///
+/// The receivers of this channel can't be used with `select!`.
+///
+/// # Example
+///
+/// ```
+/// use std::comm::shared_channel;
+///
+/// let (tx, rx) = shared_channel();
+/// let (done_tx, done_rx) = channel();
+/// for _ in range(0, 4) {
+///     let rx = rx.clone();
+///     let done_tx = done_tx.clone();
+///     spawn(proc() {
+///         // each job is handled by one of the workers
+///         for job in rx.iter() {
+///             done_tx.send(job * 2);
+///         }
+///     });
+/// }
+///
+/// for i in range(0, 10) {
+///     tx.send(i);
+/// }
+/// drop(tx);
+///
+/// let total = range(0, 10).fold(0, |sum, _| sum + done_rx.recv_unwrap());
+/// assert_eq!(total, 90);
+/// ```
+pub fn shared_channel<T: Send>() -> (Sender<T>, SharedReceiver<T>) {
+    let a = Arc::new(Unsafe::new(mpmc::Packet::new()));
+    (Sender::new(Mpmc(a.clone())), SharedReceiver::new(a))
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Sender
 ////////////////////////////////////////////////////////////////////////////////
@@ -584,6 +684,7 @@ impl<T: Send> Sender<T> {
             }
             Stream(ref p) => return unsafe { (*p.get()).send(t) },
             Shared(ref p) => return unsafe { (*p.get()).send(t) },
+            Mpmc(ref p) => return unsafe { (*p.get()).send(t) },
             Sync(..) => unreachable!(),
         };
 
@@ -622,6 +723,10 @@ impl<T: Send> Clone for Sender<T> {
                 unsafe { (*p.get()).clone_chan(); }
                 return Sender::new(Shared(p.clone()));
             }
+            Mpmc(ref p) => {
+                unsafe { (*p.get()).clone_chan(); }
+                return Sender::new(Mpmc(p.clone()));
+            }
             Sync(..) => unreachable!(),
         };
 
@@ -642,6 +747,7 @@ impl<T: Send> Drop for Sender<T> {
             Oneshot(ref mut p) => unsafe { (*p.get()).drop_chan(); },
             Stream(ref mut p) => unsafe { (*p.get()).drop_chan(); },
             Shared(ref mut p) => unsafe { (*p.get()).drop_chan(); },
+            Mpmc(ref mut p) => unsafe { (*p.get()).drop_chan(); },
             Sync(..) => unreachable!(),
         }
     }
@@ -713,6 +819,37 @@ impl<T: Send> SyncSender<T> {
     pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
         unsafe { (*self.inner.get()).try_send(t) }
     }
+
+    /// Sends a value on this channel, blocking for at most `msecs`
+    /// milliseconds.
+    ///
+    /// This behaves like `send_opt`, except that if the value could not be
+    /// sent before the timeout elapsed then it is returned in a `Full` error.
+    /// For a channel with a buffer size of 0 this means that no receiver took
+    /// the value in time, and the value will never be seen by the receiver.
+    ///
+    /// See `SyncSender::send` for notes about guarantees of whether the
+    /// receiver has received the data or not if this function is successful.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::comm::Full;
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    /// assert_eq!(tx.send_timeout(1, 10), Ok(()));
+    ///
+    /// // the buffer is full, so this gives up after 10ms
+    /// assert_eq!(tx.send_timeout(2, 10), Err(Full(2)));
+    /// assert_eq!(rx.recv_unwrap(), 1);
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// This function fails if the local runtime is unable to create a timer.
+    pub fn send_timeout(&self, t: T, msecs: u64) -> Result<(), TrySendError<T>> {
+        unsafe { (*self.inner.get()).send_timeout(t, msecs) }
+    }
 }
 
 impl<T: Send> Clone for SyncSender<T> {
@@ -729,6 +866,77 @@ impl<T: Send> Drop for SyncSender<T> {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// SharedReceiver
+////////////////////////////////////////////////////////////////////////////////
+
+impl<T: Send> SharedReceiver<T> {
+    fn new(inner: Arc<Unsafe<mpmc::Packet<T>>>) -> SharedReceiver<T> {
+        SharedReceiver { inner: inner, marker: marker::NoShare }
+    }
+
+    /// Blocks waiting for a value on this receiver
+    ///
+    /// This function has the same semantics as `Receiver::recv`.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.recv_opt().map_err(|()| RecvError)
+    }
+
+    /// Blocks waiting for a value on this receiver, failing if all of the
+    /// senders have hung up.
+    ///
+    /// This function has the same semantics as `Receiver::recv_unwrap`.
+    pub fn recv_unwrap(&self) -> T {
+        match self.recv_opt() {
+            Ok(t) => t,
+            Err(()) => fail!("receiving on a closed channel"),
+        }
+    }
+
+    /// Attempts to return a pending value on this receiver without blocking
+    ///
+    /// This function has the same semantics as `Receiver::try_recv`.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match unsafe { (*self.inner.get()).try_recv() } {
+            Ok(t) => Ok(t),
+            Err(mpmc::Empty) => Err(Empty),
+            Err(mpmc::Disconnected) => Err(Disconnected),
+        }
+    }
+
+    /// Attempt to wait for a value on this receiver, but does not fail if all
+    /// of the senders have hung up.
+    ///
+    /// This function has the same semantics as `Receiver::recv_opt`.
+    pub fn recv_opt(&self) -> Result<T, ()> {
+        unsafe { (*self.inner.get()).recv() }
+    }
+
+    /// Returns an iterator which will block waiting for messages, but never
+    /// `fail!`. It will return `None` when all of the senders have hung up.
+    pub fn iter<'a>(&'a self) -> SharedMessages<'a, T> {
+        SharedMessages { rx: self }
+    }
+}
+
+impl<T: Send> Clone for SharedReceiver<T> {
+    fn clone(&self) -> SharedReceiver<T> {
+        unsafe { (*self.inner.get()).clone_port(); }
+        SharedReceiver::new(self.inner.clone())
+    }
+}
+
+impl<'a, T: Send> Iterator<T> for SharedMessages<'a, T> {
+    fn next(&mut self) -> Option<T> { self.rx.recv_opt().ok() }
+}
+
+#[unsafe_destructor]
+impl<T: Send> Drop for SharedReceiver<T> {
+    fn drop(&mut self) {
+        unsafe { (*self.inner.get()).drop_port(); }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Receiver
 ////////////////////////////////////////////////////////////////////////////////
@@ -744,26 +952,83 @@ impl<T: Send> Receiver<T> {
     /// on the channel from its paired `Sender` structure. This receiver will
     /// be woken up when data is ready, and the data will be returned.
     ///
+    /// If the other end of the channel has hung up (been deallocated), then
+    /// `Err(RecvError)` is returned once all of the data which was sent
+    /// before hanging up has been received.
+    ///
+    /// If blocking is not desired, then the `try_recv` method will attempt to
+    /// peek at a value on this receiver, and `recv_timeout` will only block
+    /// for a limited amount of time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::comm::RecvError;
+    ///
+    /// let (tx, rx) = channel();
+    /// tx.send(1);
+    /// drop(tx);
+    /// assert_eq!(rx.recv(), Ok(1));
+    /// assert_eq!(rx.recv(), Err(RecvError));
+    /// ```
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.recv_opt().map_err(|()| RecvError)
+    }
+
+    /// Blocks waiting for a value on this receiver, as with `recv`.
+    ///
     /// # Failure
     ///
     /// Similar to channels, this method will trigger a task failure if the
     /// other end of the channel has hung up (been deallocated). The purpose of
     /// this is to propagate failure among tasks.
-    ///
-    /// If failure is not desired, then there are two options:
-    ///
-    /// * If blocking is still desired, the `recv_opt` method will return `None`
-    ///   when the other end hangs up
-    ///
-    /// * If blocking is not desired, then the `try_recv` method will attempt to
-    ///   peek at a value on this receiver.
-    pub fn recv(&self) -> T {
+    pub fn recv_unwrap(&self) -> T {
         match self.recv_opt() {
             Ok(t) => t,
             Err(()) => fail!("receiving on a closed channel"),
         }
     }
 
+    /// Blocks waiting for a value on this receiver for at most `msecs`
+    /// milliseconds.
+    ///
+    /// If no value arrives in that time then `Err(Timeout)` is returned, and
+    /// if the other end of the channel has hung up then `Err(SendDisconnected)`
+    /// is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::comm::{Timeout, SendDisconnected};
+    ///
+    /// let (tx, rx) = channel();
+    /// assert_eq!(rx.recv_timeout(10), Err(Timeout));
+    /// tx.send(1);
+    /// drop(tx);
+    /// assert_eq!(rx.recv_timeout(10), Ok(1));
+    /// assert_eq!(rx.recv_timeout(10), Err(SendDisconnected));
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// This function fails if the local runtime is unable to create a timer.
+    pub fn recv_timeout(&self, msecs: u64) -> Result<T, RecvTimeoutError> {
+        match self.try_recv() {
+            Ok(t) => return Ok(t),
+            Err(Disconnected) => return Err(SendDisconnected),
+            Err(Empty) => {}
+        }
+        let sel = Select::new();
+        let mut handle = sel.handle(self);
+        unsafe { handle.add(); }
+        match sel.wait_timeout(msecs) {
+            // There's either data or a disconnection waiting for us now, so
+            // this won't block.
+            Some(..) => handle.recv_opt().map_err(|()| SendDisconnected),
+            None => Err(Timeout),
+        }
+    }
+
     /// Attempts to return a pending value on this receiver without blocking
     ///
     /// This method will never block the caller in order to wait for data to
@@ -818,6 +1083,7 @@ impl<T: Send> Receiver<T> {
                         Err(sync::Disconnected) => return Err(Disconnected),
                     }
                 }
+                Mpmc(..) => unreachable!(),
             };
             unsafe {
                 mem::swap(self.mut_inner(),
@@ -834,7 +1100,7 @@ impl<T: Send> Receiver<T> {
     /// that the channel has been deallocated.
     ///
     /// In other words, this function has the same semantics as the `recv`
-    /// method except for the failure aspect.
+    /// method except for the type of the error.
     ///
     /// If the channel has hung up, then `Err` is returned. Otherwise `Ok` of
     /// the value found on the receiver is returned.
@@ -864,7 +1130,8 @@ impl<T: Send> Receiver<T> {
                         Err(shared::Disconnected) => return Err(()),
                     }
                 }
-                Sync(ref p) => return unsafe { (*p.get()).recv() }
+                Sync(ref p) => return unsafe { (*p.get()).recv() },
+                Mpmc(..) => unreachable!(),
             };
             unsafe {
                 mem::swap(self.mut_inner(), new_port.mut_inner());
@@ -901,6 +1168,7 @@ impl<T: Send> select::Packet for Receiver<T> {
                 Sync(ref p) => {
                     return unsafe { (*p.get()).can_recv() };
                 }
+                Mpmc(..) => unreachable!(),
             };
             unsafe {
                 mem::swap(self.mut_inner(),
@@ -932,6 +1200,7 @@ impl<T: Send> select::Packet for Receiver<T> {
                 Sync(ref p) => {
                     return unsafe { (*p.get()).start_selection(task) };
                 }
+                Mpmc(..) => unreachable!(),
             };
             task = t;
             unsafe {
@@ -955,6 +1224,7 @@ impl<T: Send> select::Packet for Receiver<T> {
                 Sync(ref p) => return unsafe {
                     (*p.get()).abort_selection()
                 },
+                Mpmc(..) => unreachable!(),
             };
             let new_port = match result { Ok(b) => return b, Err(p) => p };
             was_upgrade = true;
@@ -978,6 +1248,7 @@ impl<T: Send> Drop for Receiver<T> {
             Stream(ref mut p) => unsafe { (*p.get()).drop_port(); },
             Shared(ref mut p) => unsafe { (*p.get()).drop_port(); },
             Sync(ref mut p) => unsafe { (*p.get()).drop_port(); },
+            Mpmc(..) => unreachable!(),
         }
     }
 }
@@ -1000,7 +1271,7 @@ mod test {
     test!(fn smoke() {
         let (tx, rx) = channel();
         tx.send(1);
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
     })
 
     test!(fn drop_full() {
@@ -1018,10 +1289,10 @@ mod test {
     test!(fn smoke_shared() {
         let (tx, rx) = channel();
         tx.send(1);
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
         let tx = tx.clone();
         tx.send(1);
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
     })
 
     test!(fn smoke_threads() {
@@ -1029,7 +1300,71 @@ mod test {
         spawn(proc() {
             tx.send(1);
         });
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
+    })
+
+    test!(fn recv_disconnected() {
+        let (tx, rx) = channel::<int>();
+        tx.send(1);
+        drop(tx);
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx.recv(), Err(RecvError));
+    })
+
+    test!(fn recv_disconnected_shared() {
+        let (tx, rx) = channel::<int>();
+        let tx2 = tx.clone();
+        tx.send(1);
+        tx2.send(2);
+        drop(tx);
+        drop(tx2);
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+        assert_eq!(rx.recv(), Err(RecvError));
+    })
+
+    test!(fn recv_disconnected_threads() {
+        let (tx, rx) = channel::<int>();
+        spawn(proc() {
+            drop(tx);
+        });
+        assert_eq!(rx.recv(), Err(RecvError));
+    })
+
+    test!(fn recv_timeout() {
+        let (tx, rx) = channel();
+        assert_eq!(rx.recv_timeout(0), Err(Timeout));
+        assert_eq!(rx.recv_timeout(10), Err(Timeout));
+        tx.send(1);
+        assert_eq!(rx.recv_timeout(10), Ok(1));
+        drop(tx);
+        assert_eq!(rx.recv_timeout(10), Err(SendDisconnected));
+    })
+
+    test!(fn recv_timeout_wakeup() {
+        let (tx, rx) = channel();
+        let (tx2, rx2) = channel();
+        spawn(proc() {
+            rx2.recv_unwrap();
+            tx.send(1);
+            drop(tx);
+        });
+        tx2.send(());
+        assert_eq!(rx.recv_timeout(100000), Ok(1));
+        assert_eq!(rx.recv_timeout(100000), Err(SendDisconnected));
+    })
+
+    test!(fn recv_timeout_shared() {
+        let (tx, rx) = channel();
+        let tx2 = tx.clone();
+        spawn(proc() {
+            tx2.send(2);
+        });
+        assert_eq!(rx.recv_timeout(100000), Ok(2));
+        assert_eq!(rx.recv_timeout(10), Err(Timeout));
+        tx.send(3);
+        assert_eq!(rx.recv_timeout(10), Ok(3));
     })
 
     test!(fn smoke_port_gone() {
@@ -1055,7 +1390,7 @@ mod test {
     test!(fn port_gone_concurrent() {
         let (tx, rx) = channel();
         spawn(proc() {
-            rx.recv();
+            rx.recv_unwrap();
         });
         loop { tx.send(1) }
     } #[should_fail])
@@ -1064,7 +1399,7 @@ mod test {
         let (tx, rx) = channel();
         let tx2 = tx.clone();
         spawn(proc() {
-            rx.recv();
+            rx.recv_unwrap();
         });
         loop {
             tx.send(1);
@@ -1075,7 +1410,7 @@ mod test {
     test!(fn smoke_chan_gone() {
         let (tx, rx) = channel::<int>();
         drop(tx);
-        rx.recv();
+        rx.recv_unwrap();
     } #[should_fail])
 
     test!(fn smoke_chan_gone_shared() {
@@ -1083,7 +1418,7 @@ mod test {
         let tx2 = tx.clone();
         drop(tx);
         drop(tx2);
-        rx.recv();
+        rx.recv_unwrap();
     } #[should_fail])
 
     test!(fn chan_gone_concurrent() {
@@ -1092,7 +1427,7 @@ mod test {
             tx.send(1);
             tx.send(1);
         });
-        loop { rx.recv(); }
+        loop { rx.recv_unwrap(); }
     } #[should_fail])
 
     test!(fn stress() {
@@ -1101,7 +1436,7 @@ mod test {
             for _ in range(0, 10000) { tx.send(1); }
         });
         for _ in range(0, 10000) {
-            assert_eq!(rx.recv(), 1);
+            assert_eq!(rx.recv_unwrap(), 1);
         }
     })
 
@@ -1113,7 +1448,7 @@ mod test {
 
         spawn(proc() {
             for _ in range(0, AMT * NTHREADS) {
-                assert_eq!(rx.recv(), 1);
+                assert_eq!(rx.recv_unwrap(), 1);
             }
             match rx.try_recv() {
                 Ok(..) => fail!(),
@@ -1129,7 +1464,7 @@ mod test {
             });
         }
         drop(tx);
-        drx.recv();
+        drx.recv_unwrap();
     })
 
     #[test]
@@ -1141,19 +1476,19 @@ mod test {
         spawn(proc() {
             tx1.send(());
             for _ in range(0, 40) {
-                assert_eq!(rx2.recv(), 1);
+                assert_eq!(rx2.recv_unwrap(), 1);
             }
             tx3.send(());
         });
-        rx1.recv();
+        rx1.recv_unwrap();
         native::task::spawn(proc() {
             for _ in range(0, 40) {
                 tx2.send(1);
             }
             tx4.send(());
         });
-        rx3.recv();
-        rx3.recv();
+        rx3.recv_unwrap();
+        rx3.recv_unwrap();
     }
 
     #[test]
@@ -1162,14 +1497,14 @@ mod test {
         let (dtx, drx) = channel();
         native::task::spawn(proc() {
             for _ in range(0, 40) {
-                assert_eq!(rx.recv(), 1);
+                assert_eq!(rx.recv_unwrap(), 1);
             }
             dtx.send(());
         });
         for _ in range(0, 40) {
             tx.send(1);
         }
-        drx.recv();
+        drx.recv_unwrap();
     }
 
     #[test]
@@ -1179,17 +1514,17 @@ mod test {
         let (tx3, rx3) = channel::<()>();
         let tx4 = tx3.clone();
         native::task::spawn(proc() {
-            assert_eq!(rx1.recv(), 1);
+            assert_eq!(rx1.recv_unwrap(), 1);
             tx2.send(2);
             tx4.send(());
         });
         native::task::spawn(proc() {
             tx1.send(1);
-            assert_eq!(rx2.recv(), 2);
+            assert_eq!(rx2.recv_unwrap(), 2);
             tx3.send(());
         });
-        rx3.recv();
-        rx3.recv();
+        rx3.recv_unwrap();
+        rx3.recv_unwrap();
     }
 
     test!(fn oneshot_single_thread_close_port_first() {
@@ -1216,7 +1551,7 @@ mod test {
         let res = task::try(proc() {
             let (tx, rx) = channel::<int>();
             drop(tx);
-            rx.recv();
+            rx.recv_unwrap();
         });
         // What is our res?
         assert!(res.is_err());
@@ -1225,13 +1560,13 @@ mod test {
     test!(fn oneshot_single_thread_send_then_recv() {
         let (tx, rx) = channel::<Box<int>>();
         tx.send(box 10);
-        assert!(rx.recv() == box 10);
+        assert!(rx.recv_unwrap() == box 10);
     })
 
     test!(fn oneshot_single_thread_try_send_open() {
         let (tx, rx) = channel::<int>();
         assert!(tx.send_opt(10).is_ok());
-        assert!(rx.recv() == 10);
+        assert!(rx.recv_unwrap() == 10);
     })
 
     test!(fn oneshot_single_thread_try_send_closed() {
@@ -1274,7 +1609,7 @@ mod test {
     test!(fn oneshot_multi_task_recv_then_send() {
         let (tx, rx) = channel::<Box<int>>();
         spawn(proc() {
-            assert!(rx.recv() == box 10);
+            assert!(rx.recv_unwrap() == box 10);
         });
 
         tx.send(box 10);
@@ -1286,7 +1621,7 @@ mod test {
             drop(tx);
         });
         let res = task::try(proc() {
-            assert!(rx.recv() == box 10);
+            assert!(rx.recv_unwrap() == box 10);
         });
         assert!(res.is_err());
     })
@@ -1318,7 +1653,7 @@ mod test {
             let (tx, rx) = channel::<int>();
             spawn(proc() {
                 let res = task::try(proc() {
-                    rx.recv();
+                    rx.recv_unwrap();
                 });
                 assert!(res.is_err());
             });
@@ -1337,7 +1672,7 @@ mod test {
                 tx.send(box 10);
             });
             spawn(proc() {
-                assert!(rx.recv() == box 10);
+                assert!(rx.recv_unwrap() == box 10);
             });
         }
     })
@@ -1362,7 +1697,7 @@ mod test {
                 if i == 10 { return }
 
                 spawn(proc() {
-                    assert!(rx.recv() == box i);
+                    assert!(rx.recv_unwrap() == box i);
                     recv(rx, i + 1);
                 });
             }
@@ -1373,7 +1708,7 @@ mod test {
         // Regression test that we don't run out of stack in scheduler context
         let (tx, rx) = channel();
         for _ in range(0, 10000) { tx.send(()); }
-        for _ in range(0, 10000) { rx.recv(); }
+        for _ in range(0, 10000) { rx.recv_unwrap(); }
     })
 
     test!(fn shared_chan_stress() {
@@ -1387,7 +1722,7 @@ mod test {
         }
 
         for _ in range(0, total) {
-            rx.recv();
+            rx.recv_unwrap();
         }
     })
 
@@ -1407,7 +1742,7 @@ mod test {
         tx.send(1);
         tx.send(2);
         drop(tx);
-        assert_eq!(total_rx.recv(), 6);
+        assert_eq!(total_rx.recv_unwrap(), 6);
     })
 
     test!(fn test_recv_iter_break() {
@@ -1431,7 +1766,7 @@ mod test {
         tx.send(2);
         let _ = tx.send_opt(2);
         drop(tx);
-        assert_eq!(count_rx.recv(), 4);
+        assert_eq!(count_rx.recv_unwrap(), 4);
     })
 
     test!(fn try_recv_states() {
@@ -1439,21 +1774,21 @@ mod test {
         let (tx2, rx2) = channel::<()>();
         let (tx3, rx3) = channel::<()>();
         spawn(proc() {
-            rx2.recv();
+            rx2.recv_unwrap();
             tx1.send(1);
             tx3.send(());
-            rx2.recv();
+            rx2.recv_unwrap();
             drop(tx1);
             tx3.send(());
         });
 
         assert_eq!(rx1.try_recv(), Err(Empty));
         tx2.send(());
-        rx3.recv();
+        rx3.recv_unwrap();
         assert_eq!(rx1.try_recv(), Ok(1));
         assert_eq!(rx1.try_recv(), Err(Empty));
         tx2.send(());
-        rx3.recv();
+        rx3.recv_unwrap();
         assert_eq!(rx1.try_recv(), Err(Disconnected));
     })
 
@@ -1463,7 +1798,7 @@ mod test {
         let (tx, rx) = channel();
         let (tx2, rx2) = channel();
         spawn(proc() {
-            rx.recv(); // wait on a oneshot
+            rx.recv_unwrap(); // wait on a oneshot
             drop(rx);  // destroy a shared
             tx2.send(());
         });
@@ -1476,7 +1811,7 @@ mod test {
         t.send(());
 
         // wait for the child task to exit before we exit
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     test!(fn sends_off_the_runtime() {
@@ -1489,7 +1824,7 @@ mod test {
             }
         });
         for _ in range(0, 1000) {
-            rx.recv();
+            rx.recv_unwrap();
         }
         t.join();
     })
@@ -1514,7 +1849,7 @@ mod test {
             tx.send(());
         }
         t.join();
-        pdone.recv();
+        pdone.recv_unwrap();
     })
 }
 
@@ -1533,7 +1868,7 @@ mod sync_tests {
     test!(fn smoke() {
         let (tx, rx) = sync_channel(1);
         tx.send(1);
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
     })
 
     test!(fn drop_full() {
@@ -1544,10 +1879,10 @@ mod sync_tests {
     test!(fn smoke_shared() {
         let (tx, rx) = sync_channel(1);
         tx.send(1);
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
         let tx = tx.clone();
         tx.send(1);
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
     })
 
     test!(fn smoke_threads() {
@@ -1555,7 +1890,7 @@ mod sync_tests {
         spawn(proc() {
             tx.send(1);
         });
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
     })
 
     test!(fn smoke_port_gone() {
@@ -1575,7 +1910,7 @@ mod sync_tests {
     test!(fn port_gone_concurrent() {
         let (tx, rx) = sync_channel(0);
         spawn(proc() {
-            rx.recv();
+            rx.recv_unwrap();
         });
         loop { tx.send(1) }
     } #[should_fail])
@@ -1584,7 +1919,7 @@ mod sync_tests {
         let (tx, rx) = sync_channel(0);
         let tx2 = tx.clone();
         spawn(proc() {
-            rx.recv();
+            rx.recv_unwrap();
         });
         loop {
             tx.send(1);
@@ -1595,7 +1930,7 @@ mod sync_tests {
     test!(fn smoke_chan_gone() {
         let (tx, rx) = sync_channel::<int>(0);
         drop(tx);
-        rx.recv();
+        rx.recv_unwrap();
     } #[should_fail])
 
     test!(fn smoke_chan_gone_shared() {
@@ -1603,7 +1938,7 @@ mod sync_tests {
         let tx2 = tx.clone();
         drop(tx);
         drop(tx2);
-        rx.recv();
+        rx.recv_unwrap();
     } #[should_fail])
 
     test!(fn chan_gone_concurrent() {
@@ -1612,7 +1947,7 @@ mod sync_tests {
             tx.send(1);
             tx.send(1);
         });
-        loop { rx.recv(); }
+        loop { rx.recv_unwrap(); }
     } #[should_fail])
 
     test!(fn stress() {
@@ -1621,7 +1956,7 @@ mod sync_tests {
             for _ in range(0, 10000) { tx.send(1); }
         });
         for _ in range(0, 10000) {
-            assert_eq!(rx.recv(), 1);
+            assert_eq!(rx.recv_unwrap(), 1);
         }
     })
 
@@ -1633,7 +1968,7 @@ mod sync_tests {
 
         spawn(proc() {
             for _ in range(0, AMT * NTHREADS) {
-                assert_eq!(rx.recv(), 1);
+                assert_eq!(rx.recv_unwrap(), 1);
             }
             match rx.try_recv() {
                 Ok(..) => fail!(),
@@ -1649,7 +1984,7 @@ mod sync_tests {
             });
         }
         drop(tx);
-        drx.recv();
+        drx.recv_unwrap();
     })
 
     test!(fn oneshot_single_thread_close_port_first() {
@@ -1676,7 +2011,7 @@ mod sync_tests {
         let res = task::try(proc() {
             let (tx, rx) = sync_channel::<int>(0);
             drop(tx);
-            rx.recv();
+            rx.recv_unwrap();
         });
         // What is our res?
         assert!(res.is_err());
@@ -1685,13 +2020,13 @@ mod sync_tests {
     test!(fn oneshot_single_thread_send_then_recv() {
         let (tx, rx) = sync_channel::<Box<int>>(1);
         tx.send(box 10);
-        assert!(rx.recv() == box 10);
+        assert!(rx.recv_unwrap() == box 10);
     })
 
     test!(fn oneshot_single_thread_try_send_open() {
         let (tx, rx) = sync_channel::<int>(1);
         assert_eq!(tx.try_send(10), Ok(()));
-        assert!(rx.recv() == 10);
+        assert!(rx.recv_unwrap() == 10);
     })
 
     test!(fn oneshot_single_thread_try_send_closed() {
@@ -1739,7 +2074,7 @@ mod sync_tests {
     test!(fn oneshot_multi_task_recv_then_send() {
         let (tx, rx) = sync_channel::<Box<int>>(0);
         spawn(proc() {
-            assert!(rx.recv() == box 10);
+            assert!(rx.recv_unwrap() == box 10);
         });
 
         tx.send(box 10);
@@ -1751,7 +2086,7 @@ mod sync_tests {
             drop(tx);
         });
         let res = task::try(proc() {
-            assert!(rx.recv() == box 10);
+            assert!(rx.recv_unwrap() == box 10);
         });
         assert!(res.is_err());
     })
@@ -1783,7 +2118,7 @@ mod sync_tests {
             let (tx, rx) = sync_channel::<int>(0);
             spawn(proc() {
                 let res = task::try(proc() {
-                    rx.recv();
+                    rx.recv_unwrap();
                 });
                 assert!(res.is_err());
             });
@@ -1802,7 +2137,7 @@ mod sync_tests {
                 tx.send(box 10);
             });
             spawn(proc() {
-                assert!(rx.recv() == box 10);
+                assert!(rx.recv_unwrap() == box 10);
             });
         }
     })
@@ -1827,7 +2162,7 @@ mod sync_tests {
                 if i == 10 { return }
 
                 spawn(proc() {
-                    assert!(rx.recv() == box i);
+                    assert!(rx.recv_unwrap() == box i);
                     recv(rx, i + 1);
                 });
             }
@@ -1838,7 +2173,7 @@ mod sync_tests {
         // Regression test that we don't run out of stack in scheduler context
         let (tx, rx) = sync_channel(10000);
         for _ in range(0, 10000) { tx.send(()); }
-        for _ in range(0, 10000) { rx.recv(); }
+        for _ in range(0, 10000) { rx.recv_unwrap(); }
     })
 
     test!(fn shared_chan_stress() {
@@ -1852,7 +2187,7 @@ mod sync_tests {
         }
 
         for _ in range(0, total) {
-            rx.recv();
+            rx.recv_unwrap();
         }
     })
 
@@ -1872,7 +2207,7 @@ mod sync_tests {
         tx.send(1);
         tx.send(2);
         drop(tx);
-        assert_eq!(total_rx.recv(), 6);
+        assert_eq!(total_rx.recv_unwrap(), 6);
     })
 
     test!(fn test_recv_iter_break() {
@@ -1896,7 +2231,7 @@ mod sync_tests {
         tx.send(2);
         let _ = tx.try_send(2);
         drop(tx);
-        assert_eq!(count_rx.recv(), 4);
+        assert_eq!(count_rx.recv_unwrap(), 4);
     })
 
     test!(fn try_recv_states() {
@@ -1904,21 +2239,21 @@ mod sync_tests {
         let (tx2, rx2) = sync_channel::<()>(1);
         let (tx3, rx3) = sync_channel::<()>(1);
         spawn(proc() {
-            rx2.recv();
+            rx2.recv_unwrap();
             tx1.send(1);
             tx3.send(());
-            rx2.recv();
+            rx2.recv_unwrap();
             drop(tx1);
             tx3.send(());
         });
 
         assert_eq!(rx1.try_recv(), Err(Empty));
         tx2.send(());
-        rx3.recv();
+        rx3.recv_unwrap();
         assert_eq!(rx1.try_recv(), Ok(1));
         assert_eq!(rx1.try_recv(), Err(Empty));
         tx2.send(());
-        rx3.recv();
+        rx3.recv_unwrap();
         assert_eq!(rx1.try_recv(), Err(Disconnected));
     })
 
@@ -1928,7 +2263,7 @@ mod sync_tests {
         let (tx, rx) = sync_channel(0);
         let (tx2, rx2) = sync_channel(0);
         spawn(proc() {
-            rx.recv(); // wait on a oneshot
+            rx.recv_unwrap(); // wait on a oneshot
             drop(rx);  // destroy a shared
             tx2.send(());
         });
@@ -1941,7 +2276,7 @@ mod sync_tests {
         t.send(());
 
         // wait for the child task to exit before we exit
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     test!(fn try_recvs_off_the_runtime() {
@@ -1964,12 +2299,12 @@ mod sync_tests {
             tx.send(());
         }
         t.join();
-        pdone.recv();
+        pdone.recv_unwrap();
     })
 
     test!(fn send_opt1() {
         let (tx, rx) = sync_channel(0);
-        spawn(proc() { rx.recv(); });
+        spawn(proc() { rx.recv_unwrap(); });
         assert_eq!(tx.send_opt(1), Ok(()));
     })
 
@@ -2000,8 +2335,8 @@ mod sync_tests {
             done2.send(());
         });
         drop(rx);
-        donerx.recv();
-        donerx.recv();
+        donerx.recv_unwrap();
+        donerx.recv_unwrap();
     })
 
     test!(fn try_send1() {
@@ -2028,6 +2363,204 @@ mod sync_tests {
             for _ in range(0, 1000) { task::deschedule(); }
             assert_eq!(tx.try_send(1), Ok(()));
         });
-        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv_unwrap(), 1);
     } #[ignore(reason = "flaky on libnative")])
+
+    test!(fn send_timeout1() {
+        let (tx, rx) = sync_channel(1);
+        assert_eq!(tx.send_timeout(1, 10), Ok(()));
+        assert_eq!(tx.send_timeout(2, 10), Err(Full(2)));
+        assert_eq!(rx.recv_unwrap(), 1);
+        assert_eq!(rx.try_recv(), Err(Empty));
+    })
+
+    test!(fn send_timeout2() {
+        // nobody takes the data on a rendezvous channel, so it's handed back
+        // and never received
+        let (tx, rx) = sync_channel(0);
+        assert_eq!(tx.send_timeout(1, 10), Err(Full(1)));
+        assert_eq!(rx.try_recv(), Err(Empty));
+        drop(rx);
+        assert_eq!(tx.send_timeout(2, 10), Err(RecvDisconnected(2)));
+    })
+
+    test!(fn send_timeout3() {
+        let (tx, rx) = sync_channel(0);
+        spawn(proc() {
+            assert_eq!(tx.send_timeout(1, 10000), Ok(()));
+        });
+        assert_eq!(rx.recv_unwrap(), 1);
+    })
+
+    test!(fn send_timeout4() {
+        // a sender queued behind a full buffer is woken up once there's space
+        let (tx, rx) = sync_channel(1);
+        let (done, donerx) = channel();
+        tx.send(1);
+        spawn(proc() {
+            assert_eq!(tx.send_timeout(2, 10000), Ok(()));
+            done.send(());
+        });
+        assert_eq!(rx.recv_unwrap(), 1);
+        donerx.recv_unwrap();
+        assert_eq!(rx.recv_unwrap(), 2);
+    })
+
+    test!(fn send_timeout5() {
+        // senders which time out leave the other queued senders alone
+        let (tx, rx) = sync_channel(1);
+        let (done, donerx) = channel();
+        tx.send(0);
+        for i in range(1, 4) {
+            let tx = tx.clone();
+            let done = done.clone();
+            spawn(proc() {
+                let ms = if i == 2 { 10000 } else { 10 };
+                done.send((i, tx.send_timeout(i, ms)));
+            });
+        }
+        for _ in range(0, 2) {
+            let (i, ret) = donerx.recv_unwrap();
+            assert!(i != 2);
+            assert_eq!(ret, Err(Full(i)));
+        }
+        assert_eq!(rx.recv_unwrap(), 0);
+        assert_eq!(donerx.recv_unwrap(), (2, Ok(())));
+        assert_eq!(rx.recv_unwrap(), 2);
+        assert_eq!(rx.try_recv(), Err(Empty));
+    })
+
+    test!(fn send_timeout_disconnect() {
+        let (tx, rx) = sync_channel(0);
+        let (done, donerx) = channel();
+        spawn(proc() {
+            done.send(tx.send_timeout(1, 10000));
+        });
+        for _ in range(0, 100) { task::deschedule(); }
+        drop(rx);
+        assert_eq!(donerx.recv_unwrap(), Err(RecvDisconnected(1)));
+    })
+}
+
+#[cfg(test)]
+mod mpmc_tests {
+    use prelude::*;
+    use os;
+
+    pub fn stress_factor() -> uint {
+        match os::getenv("RUST_TEST_STRESS") {
+            Some(val) => from_str::<uint>(val.as_slice()).unwrap(),
+            None => 1,
+        }
+    }
+
+    test!(fn smoke() {
+        let (tx, rx) = shared_channel();
+        tx.send(1);
+        assert_eq!(rx.recv_unwrap(), 1);
+        let rx2 = rx.clone();
+        tx.send(2);
+        assert_eq!(rx2.recv_unwrap(), 2);
+        assert_eq!(rx.try_recv(), Err(Empty));
+    })
+
+    test!(fn drop_full() {
+        let (tx, rx) = shared_channel();
+        let _rx2 = rx.clone();
+        tx.send(box 1);
+    })
+
+    test!(fn smoke_port_gone() {
+        let (tx, rx) = shared_channel::<int>();
+        let rx2 = rx.clone();
+        drop(rx);
+        assert_eq!(tx.send_opt(1), Ok(()));
+        drop(rx2);
+        assert_eq!(tx.send_opt(1), Err(1));
+    })
+
+    test!(fn recv_disconnected() {
+        let (tx, rx) = shared_channel::<int>();
+        let rx2 = rx.clone();
+        tx.send(1);
+        drop(tx);
+        assert_eq!(rx2.recv(), Ok(1));
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx2.recv(), Err(RecvError));
+    })
+
+    test!(fn smoke_chan_gone() {
+        let (tx, rx) = shared_channel::<int>();
+        let tx2 = tx.clone();
+        tx.send(1);
+        drop(tx);
+        drop(tx2);
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Err(Disconnected));
+        assert_eq!(rx.recv_opt(), Err(()));
+    })
+
+    test!(fn recv_on_closed() {
+        let (tx, rx) = shared_channel::<int>();
+        drop(tx);
+        rx.recv_unwrap();
+    } #[should_fail])
+
+    test!(fn disconnect_wakes_all_receivers() {
+        let (tx, rx) = shared_channel::<int>();
+        let (done, donerx) = channel();
+        for _ in range(0, 4) {
+            let rx = rx.clone();
+            let done = done.clone();
+            spawn(proc() {
+                done.send(rx.recv_opt());
+            });
+        }
+        for _ in range(0, 100) { task::deschedule(); }
+        drop(tx);
+        for _ in range(0, 4) {
+            assert_eq!(donerx.recv_unwrap(), Err(()));
+        }
+    })
+
+    test!(fn stress() {
+        static AMT: uint = 10000;
+        static NTHREADS: uint = 8;
+        let (tx, rx) = shared_channel::<uint>();
+        let (done, donerx) = channel();
+
+        for _ in range(0, NTHREADS) {
+            let rx = rx.clone();
+            let done = done.clone();
+            spawn(proc() {
+                let mut sum = 0;
+                let mut count = 0u;
+                for i in rx.iter() {
+                    sum += i;
+                    count += 1;
+                }
+                done.send((sum, count));
+            });
+        }
+        drop(rx);
+
+        let amt = AMT * stress_factor();
+        for _ in range(0, NTHREADS) {
+            let tx = tx.clone();
+            spawn(proc() {
+                for i in range(0, amt) { tx.send(i); }
+            });
+        }
+        drop(tx);
+
+        let mut sum = 0;
+        let mut count = 0;
+        for _ in range(0, NTHREADS) {
+            let (s, c) = donerx.recv_unwrap();
+            sum += s;
+            count += c;
+        }
+        assert_eq!(count, amt * NTHREADS);
+        assert_eq!(sum, NTHREADS * amt * (amt - 1) / 2);
+    })
 }
@@ -0,0 +1,236 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Multi-producer, multi-consumer channels
+///
+/// This is the flavor of channel behind `shared_channel`, where both halves of
+/// the channel can be cloned. Data is stored in the same lock-free mpsc queue
+/// as the shared flavor of channels, so senders never take a lock unless they
+/// have a receiver to wake up.
+///
+/// The queue only supports a single consumer at a time, so the receivers are
+/// serialized through a mutex. The mutex also protects the list of receivers
+/// which are blocked waiting for data. Senders find out whether there's anyone
+/// to wake up through the `waiting` count, which a receiver bumps with the
+/// lock held before checking the queue one last time and going to sleep. With
+/// both sides using sequentially consistent operations, either the sender sees
+/// the receiver waiting or the receiver sees the sender's data.
+///
+/// Blocked receivers are woken up in the order that they blocked, one per
+/// message sent. A woken receiver may find that its message was already taken
+/// by a receiver which came along in the meantime, in which case it simply
+/// goes back to sleep.
+
+use iter::Iterator;
+use kinds::Send;
+use mem;
+use ops::Drop;
+use option::{Some, None, Option};
+use owned::Box;
+use result::{Ok, Err, Result};
+use rt::local::Local;
+use rt::task::{Task, BlockedTask};
+use rt::thread::Thread;
+use sync::atomics;
+use ty::Unsafe;
+use unstable::mutex::NativeMutex;
+use vec::Vec;
+
+use mpsc = sync::mpsc_queue;
+
+pub struct Packet<T> {
+    queue: mpsc::Queue<T>,
+    channels: atomics::AtomicUint, // number of senders
+    ports: atomics::AtomicUint,    // number of receivers
+    disconnected: atomics::AtomicBool, // all senders are gone
+    port_dropped: atomics::AtomicBool, // all receivers are gone
+    waiting: atomics::AtomicUint,  // receivers blocked or about to block
+
+    /// Held by receivers while they pop from the queue, and protects the list
+    /// of blocked receivers
+    lock: NativeMutex,
+    waiters: Unsafe<Vec<BlockedTask>>,
+}
+
+pub enum Failure {
+    Empty,
+    Disconnected,
+}
+
+impl<T: Send> Packet<T> {
+    pub fn new() -> Packet<T> {
+        Packet {
+            queue: mpsc::Queue::new(),
+            channels: atomics::AtomicUint::new(1),
+            ports: atomics::AtomicUint::new(1),
+            disconnected: atomics::AtomicBool::new(false),
+            port_dropped: atomics::AtomicBool::new(false),
+            waiting: atomics::AtomicUint::new(0),
+            lock: unsafe { NativeMutex::new() },
+            waiters: Unsafe::new(Vec::new()),
+        }
+    }
+
+    pub fn send(&self, t: T) -> Result<(), T> {
+        // As with the other flavors, a send racing with the last receiver
+        // going away may still succeed, and the data is then destroyed along
+        // with the packet.
+        if self.port_dropped.load(atomics::SeqCst) { return Err(t) }
+        self.queue.push(t);
+
+        // Make sure our push is visible before looking for a receiver to wake
+        // up, see the module documentation.
+        atomics::fence(atomics::SeqCst);
+        if self.waiting.load(atomics::SeqCst) == 0 { return Ok(()) }
+
+        // A receiver may be in the middle of blocking. Once we have the lock
+        // it has either put itself in the list or found some data.
+        let task = unsafe {
+            let _g = self.lock.lock();
+            let waiters = &mut *self.waiters.get();
+            if waiters.len() > 0 {
+                self.waiting.fetch_sub(1, atomics::SeqCst);
+                waiters.remove(0)
+            } else {
+                None
+            }
+        };
+        task.map(|t| t.wake().map(|t| t.reawaken()));
+        Ok(())
+    }
+
+    pub fn recv(&self) -> Result<T, ()> {
+        unsafe {
+            let _g = self.lock.lock();
+            loop {
+                match self.pop() {
+                    Some(t) => return Ok(t),
+                    None => {}
+                }
+                // All data is pushed before the last sender disconnects, so
+                // after seeing the disconnect there's one last chance for data.
+                if self.disconnected.load(atomics::SeqCst) {
+                    return match self.pop() {
+                        Some(t) => Ok(t),
+                        None => Err(()),
+                    }
+                }
+
+                self.waiting.fetch_add(1, atomics::SeqCst);
+                match self.pop() {
+                    Some(t) => {
+                        self.waiting.fetch_sub(1, atomics::SeqCst);
+                        return Ok(t)
+                    }
+                    None => {}
+                }
+
+                let mut task: Box<Task> = Local::take();
+                task.blocked_on = Some("a shared channel");
+                task.deschedule(1, |task| {
+                    (*self.waiters.get()).push(task);
+                    self.lock.unlock_noguard();
+                    Ok(())
+                });
+                self.lock.lock_noguard();
+            }
+        }
+    }
+
+    pub fn try_recv(&self) -> Result<T, Failure> {
+        unsafe {
+            let _g = self.lock.lock();
+            match self.pop() {
+                Some(t) => Ok(t),
+                None if self.disconnected.load(atomics::SeqCst) => {
+                    match self.pop() {
+                        Some(t) => Ok(t),
+                        None => Err(Disconnected),
+                    }
+                }
+                None => Err(Empty),
+            }
+        }
+    }
+
+    // Pops some data off the queue. This must only be called with the lock
+    // held, as the queue only supports one consumer at a time. If a sender is
+    // in the middle of pushing, then we wait for it to finish.
+    unsafe fn pop(&self) -> Option<T> {
+        loop {
+            match self.queue.pop() {
+                mpsc::Data(t) => return Some(t),
+                mpsc::Empty => return None,
+                mpsc::Inconsistent => Thread::yield_now(),
+            }
+        }
+    }
+
+    pub fn clone_chan(&self) {
+        self.channels.fetch_add(1, atomics::SeqCst);
+    }
+
+    pub fn clone_port(&self) {
+        self.ports.fetch_add(1, atomics::SeqCst);
+    }
+
+    pub fn drop_chan(&self) {
+        match self.channels.fetch_sub(1, atomics::SeqCst) {
+            1 => {}
+            _ => return
+        }
+
+        // Wake up everyone who's blocked so they can see the disconnection.
+        // Receivers check the flag with the lock held, so none of them can go
+        // to sleep after we've emptied the list.
+        self.disconnected.store(true, atomics::SeqCst);
+        let waiters = unsafe {
+            let _g = self.lock.lock();
+            let waiters = mem::take(&mut *self.waiters.get());
+            self.waiting.fetch_sub(waiters.len(), atomics::SeqCst);
+            waiters
+        };
+        for task in waiters.move_iter() {
+            task.wake().map(|t| t.reawaken());
+        }
+    }
+
+    pub fn drop_port(&self) {
+        match self.ports.fetch_sub(1, atomics::SeqCst) {
+            1 => {}
+            _ => return
+        }
+
+        // Nobody will ever receive the data which has been sent, so destroy
+        // it now. The data is destroyed outside of the lock in case one of the
+        // destructors drops a sender for this channel.
+        self.port_dropped.store(true, atomics::SeqCst);
+        let mut data = Vec::new();
+        unsafe {
+            let _g = self.lock.lock();
+            loop {
+                match self.pop() {
+                    Some(t) => data.push(t),
+                    None => break,
+                }
+            }
+        }
+        mem::drop(data);
+    }
+}
+
+#[unsafe_destructor]
+impl<T: Send> Drop for Packet<T> {
+    fn drop(&mut self) {
+        assert_eq!(self.channels.load(atomics::SeqCst), 0);
+        assert_eq!(self.ports.load(atomics::SeqCst), 0);
+        assert_eq!(self.waiting.load(atomics::SeqCst), 0);
+    }
+}
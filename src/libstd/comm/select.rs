@@ -24,6 +24,12 @@
 //! received values of receivers in a much more natural syntax then usage of the
 //! `Select` structure directly.
 //!
+//! A selection can also be bounded in time, either with `Select::wait_timeout`
+//! or with a `timeout` arm in the `select!` macro. A single handle can also be
+//! given its own deadline with `Handle::add_timeout`. Timeouts are implemented
+//! with an `io::Timer`, so they work with both the native and the green
+//! runtime.
+//!
 //! # Example
 //!
 //! ```rust
@@ -34,18 +40,26 @@
 //! tx2.send(2);
 //!
 //! select! {
-//!     val = rx1.recv() => {
+//!     val = rx1.recv_unwrap() => {
 //!         assert_eq!(val, 1);
 //!     },
-//!     val = rx2.recv() => {
+//!     val = rx2.recv_unwrap() => {
 //!         assert_eq!(val, 2);
 //!     }
 //! }
+//!
+//! // nothing more will be sent, so this gives up after 10ms
+//! let (_tx3, rx3) = channel::<int>();
+//! select! {
+//!     timeout(10) => {},
+//!     _val = rx3.recv_unwrap() => fail!()
+//! }
 //! ```
 
 #![allow(dead_code)]
 
 use cell::Cell;
+use io::timer::Timer;
 use iter::Iterator;
 use kinds::Send;
 use kinds::marker;
@@ -57,7 +71,7 @@ use ptr::RawPtr;
 use result::{Ok, Err, Result};
 use rt::local::Local;
 use rt::task::{Task, BlockedTask};
-use super::Receiver;
+use super::{Receiver, RecvError};
 use uint;
 
 /// The "receiver set" of the select interface. This structure is used to manage
@@ -81,6 +95,7 @@ pub struct Handle<'rx, T> {
     prev: *mut Handle<'static, ()>,
     added: bool,
     packet: &'rx Packet,
+    timeout: Option<(Box<Timeout>, Box<Handle<'static, ()>>)>,
 
     // due to our fun transmutes, we be sure to place this at the end. (nothing
     // previous relies on T)
@@ -89,6 +104,13 @@ pub struct Handle<'rx, T> {
 
 struct Packets { cur: *mut Handle<'static, ()> }
 
+// The timer behind `Handle::add_timeout`. Its receiver is added to the set
+// through a second handle which has the same id as the handle it belongs to.
+struct Timeout {
+    timer: Timer,
+    rx: Receiver<()>,
+}
+
 #[doc(hidden)]
 pub trait Packet {
     fn can_recv(&self) -> bool;
@@ -125,6 +147,7 @@ impl Select {
             added: false,
             rx: rx,
             packet: rx,
+            timeout: None,
         }
     }
 
@@ -138,6 +161,30 @@ impl Select {
         self.wait2(false)
     }
 
+    /// Waits for an event on this receiver set for at most `msecs`
+    /// milliseconds.
+    ///
+    /// This returns `Some` with the id of a handle which has an event
+    /// available, just like `wait`, or `None` if the timeout elapsed without
+    /// any events. Receivers which are already ready when this is called are
+    /// always picked over the timeout, even if `msecs` is 0. Unlike `wait`,
+    /// this may be called on an empty set, in which case it simply sleeps.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the local runtime is unable to create a timer.
+    pub fn wait_timeout(&self, msecs: u64) -> Option<uint> {
+        // The timeout is just one more receiver in the set, which is added
+        // last so the receivers already in the set are given priority.
+        let mut timer = Timer::new().ok()
+                                    .expect("select: could not create a Timer");
+        let timeout = timer.oneshot(msecs);
+        let mut handle = self.handle(&timeout);
+        unsafe { handle.add(); }
+        let ret = self.wait();
+        if ret == handle.id() { None } else { Some(ret) }
+    }
+
     /// Helper method for skipping the preflight checks during testing
     fn wait2(&self, do_preflight_checks: bool) -> uint {
         // Note that this is currently an inefficient implementation. We in
@@ -177,7 +224,8 @@ impl Select {
             // Acquire a number of blocking contexts, and block on each one
             // sequentially until one fails. If one fails, then abort
             // immediately so we can go unblock on all the other receivers.
-            let task: Box<Task> = Local::take();
+            let mut task: Box<Task> = Local::take();
+            task.blocked_on = Some("a select over channels");
             task.deschedule(amt, |task| {
                 // Prepare for the block
                 let (i, handle) = iter.next().unwrap();
@@ -233,7 +281,10 @@ impl<'rx, T: Send> Handle<'rx, T> {
 
     /// Receive a value on the underlying receiver. Has the same semantics as
     /// `Receiver.recv`
-    pub fn recv(&mut self) -> T { self.rx.recv() }
+    pub fn recv(&mut self) -> Result<T, RecvError> { self.rx.recv() }
+    /// Receive a value on the underlying receiver, failing if the channel
+    /// disconnects. Has the same semantics as `Receiver.recv_unwrap`
+    pub fn recv_unwrap(&mut self) -> T { self.rx.recv_unwrap() }
     /// Block to receive a value on the underlying receiver, returning `Some` on
     /// success or `None` if the channel disconnects. This function has the same
     /// semantics as `Receiver.recv_opt`
@@ -262,10 +313,69 @@ impl<'rx, T: Send> Handle<'rx, T> {
         self.added = true;
     }
 
-    /// Removes this handle from the `Select` set. This method is unsafe because
-    /// it has no guarantee that the `Handle` was not moved since `add` was
-    /// called.
+    /// Adds this handle to the receiver set like `add`, but with a timeout of
+    /// `msecs` milliseconds.
+    ///
+    /// If the receiver has no event available by the time the timeout
+    /// elapses, waiting on the set returns the id of this handle anyway, and
+    /// `timed_out` can be used to tell the two cases apart. Once elapsed, the
+    /// timeout keeps being reported by every wait until the handle is removed
+    /// or the timeout is re-armed by calling this method again.
+    ///
+    /// This method is unsafe for the same reason that `add` is.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the local runtime is unable to create a timer.
+    pub unsafe fn add_timeout(&mut self, msecs: u64) {
+        self.add();
+        self.cancel_timeout();
+
+        let mut timer = Timer::new().ok()
+                                    .expect("select: could not create a Timer");
+        let rx = timer.oneshot(msecs);
+        let timeout = box Timeout { timer: timer, rx: rx };
+
+        // Both boxes are owned by this handle and removed from the set before
+        // they are freed, so their contents outlive their use in the set.
+        let selector: &'static Select = mem::transmute(&*self.selector);
+        let timeout_rx: &'static Receiver<()> = mem::transmute(&timeout.rx);
+        let mut handle = box selector.handle(timeout_rx);
+        handle.id = self.id;
+        handle.add();
+        self.timeout = Some((timeout, handle));
+    }
+
+    /// Returns whether the timeout given to `add_timeout` has elapsed without
+    /// any event becoming available on the receiver. This is always false if
+    /// the handle has no timeout.
+    pub fn timed_out(&self) -> bool {
+        match self.timeout {
+            Some((ref timeout, _)) => {
+                let timeout: &Packet = &timeout.rx;
+                timeout.can_recv() && !self.packet.can_recv()
+            }
+            None => false,
+        }
+    }
+
+    // Takes the timer behind `add_timeout` out of the set, if there is one.
+    unsafe fn cancel_timeout(&mut self) {
+        match self.timeout.take() {
+            Some((timeout, mut handle)) => {
+                handle.remove();
+                drop(handle);
+                drop(timeout);
+            }
+            None => {}
+        }
+    }
+
+    /// Removes this handle from the `Select` set, cancelling any timeout it was
+    /// added with. This method is unsafe because it has no guarantee that the
+    /// `Handle` was not moved since `add` was called.
     pub unsafe fn remove(&mut self) {
+        self.cancel_timeout();
         if !self.added { return }
 
         let selector: &mut Select = mem::transmute(&*self.selector);
@@ -329,18 +439,18 @@ mod test {
         let (tx2, rx2) = channel::<int>();
         tx1.send(1);
         select! (
-            foo = rx1.recv() => { assert_eq!(foo, 1); },
-            _bar = rx2.recv() => { fail!() }
+            foo = rx1.recv_unwrap() => { assert_eq!(foo, 1); },
+            _bar = rx2.recv_unwrap() => { fail!() }
         )
         tx2.send(2);
         select! (
-            _foo = rx1.recv() => { fail!() },
-            bar = rx2.recv() => { assert_eq!(bar, 2) }
+            _foo = rx1.recv_unwrap() => { fail!() },
+            bar = rx2.recv_unwrap() => { assert_eq!(bar, 2) }
         )
         drop(tx1);
         select! (
             foo = rx1.recv_opt() => { assert_eq!(foo, Err(())); },
-            _bar = rx2.recv() => { fail!() }
+            _bar = rx2.recv_unwrap() => { fail!() }
         )
         drop(tx2);
         select! (
@@ -356,11 +466,11 @@ mod test {
         let (tx5, rx5) = channel::<int>();
         tx5.send(4);
         select! (
-            _foo = rx1.recv() => { fail!("1") },
-            _foo = rx2.recv() => { fail!("2") },
-            _foo = rx3.recv() => { fail!("3") },
-            _foo = rx4.recv() => { fail!("4") },
-            foo = rx5.recv() => { assert_eq!(foo, 4); }
+            _foo = rx1.recv_unwrap() => { fail!("1") },
+            _foo = rx2.recv_unwrap() => { fail!("2") },
+            _foo = rx3.recv_unwrap() => { fail!("3") },
+            _foo = rx4.recv_unwrap() => { fail!("4") },
+            foo = rx5.recv_unwrap() => { assert_eq!(foo, 4); }
         )
     })
 
@@ -383,18 +493,18 @@ mod test {
         spawn(proc() {
             for _ in range(0, 20) { task::deschedule(); }
             tx1.send(1);
-            rx3.recv();
+            rx3.recv_unwrap();
             for _ in range(0, 20) { task::deschedule(); }
         });
 
         select! (
-            a = rx1.recv() => { assert_eq!(a, 1); },
-            _b = rx2.recv() => { fail!() }
+            a = rx1.recv_unwrap() => { assert_eq!(a, 1); },
+            _b = rx2.recv_unwrap() => { fail!() }
         )
         tx3.send(1);
         select! (
             a = rx1.recv_opt() => { assert_eq!(a, Err(())); },
-            _b = rx2.recv() => { fail!() }
+            _b = rx2.recv_unwrap() => { fail!() }
         )
     })
 
@@ -407,16 +517,16 @@ mod test {
             for _ in range(0, 20) { task::deschedule(); }
             tx1.send(1);
             tx2.send(2);
-            rx3.recv();
+            rx3.recv_unwrap();
         });
 
         select! (
-            a = rx1.recv() => { assert_eq!(a, 1); },
-            a = rx2.recv() => { assert_eq!(a, 2); }
+            a = rx1.recv_unwrap() => { assert_eq!(a, 1); },
+            a = rx2.recv_unwrap() => { assert_eq!(a, 2); }
         )
         select! (
-            a = rx1.recv() => { assert_eq!(a, 1); },
-            a = rx2.recv() => { assert_eq!(a, 2); }
+            a = rx1.recv_unwrap() => { assert_eq!(a, 1); },
+            a = rx2.recv_unwrap() => { assert_eq!(a, 2); }
         )
         assert_eq!(rx1.try_recv(), Err(Empty));
         assert_eq!(rx2.try_recv(), Err(Empty));
@@ -436,36 +546,209 @@ mod test {
                 } else {
                     tx2.send(i);
                 }
-                rx3.recv();
+                rx3.recv_unwrap();
             }
         });
 
         for i in range(0, AMT) {
             select! (
-                i1 = rx1.recv() => { assert!(i % 2 == 0 && i == i1); },
-                i2 = rx2.recv() => { assert!(i % 2 == 1 && i == i2); }
+                i1 = rx1.recv_unwrap() => { assert!(i % 2 == 0 && i == i1); },
+                i2 = rx2.recv_unwrap() => { assert!(i % 2 == 1 && i == i2); }
             )
             tx3.send(());
         }
     })
 
+    test!(fn timeout() {
+        let (_tx, rx) = channel::<int>();
+        select! (
+            timeout(10) => {},
+            _foo = rx.recv_unwrap() => { fail!() }
+        )
+
+        let sel = Select::new();
+        let mut handle = sel.handle(&rx);
+        unsafe { handle.add(); }
+        assert_eq!(sel.wait_timeout(0), None);
+    })
+
+    test!(fn handle_timeout() {
+        let (tx1, rx1) = channel::<int>();
+        let (_tx2, rx2) = channel::<int>();
+        let sel = Select::new();
+        let mut h1 = sel.handle(&rx1);
+        let mut h2 = sel.handle(&rx2);
+
+        // a receiver which is ready is picked over an elapsed timeout
+        tx1.send(1);
+        unsafe { h1.add_timeout(0); h2.add(); }
+        ::io::timer::sleep(10);
+        assert_eq!(sel.wait(), h1.id());
+        assert!(!h1.timed_out());
+        assert_eq!(h1.recv_unwrap(), 1);
+
+        unsafe { h1.add_timeout(10); }
+        assert!(!h1.timed_out());
+        assert_eq!(sel.wait(), h1.id());
+        assert!(h1.timed_out());
+        assert!(!h2.timed_out());
+
+        // an elapsed timeout is reported until it's re-armed or removed
+        assert_eq!(sel.wait(), h1.id());
+        unsafe { h1.add_timeout(10000); }
+        assert!(!h1.timed_out());
+        assert_eq!(sel.wait_timeout(0), None);
+        unsafe { h1.remove(); }
+        assert_eq!(sel.wait_timeout(10), None);
+    })
+
+    test!(fn handle_ready_beats_timeout() {
+        let (tx1, rx1) = channel::<int>();
+        let (tx2, rx2) = channel::<()>();
+        spawn(proc() {
+            rx2.recv_unwrap();
+            tx1.send(1);
+        });
+
+        let sel = Select::new();
+        let mut h1 = sel.handle(&rx1);
+        unsafe { h1.add_timeout(10000); }
+        tx2.send(());
+        assert_eq!(sel.wait(), h1.id());
+        assert!(!h1.timed_out());
+        assert_eq!(h1.recv_unwrap(), 1);
+    })
+
+    test!(fn stress_handle_timeout() {
+        static AMT: int = 1000;
+        let (tx1, rx1) = channel::<int>();
+        let (tx2, rx2) = channel::<()>();
+
+        spawn(proc() {
+            for i in range(0, AMT) {
+                tx1.send(i);
+                if i % 100 == 0 {
+                    ::io::timer::sleep(1);
+                }
+            }
+            rx2.recv_unwrap();
+        });
+
+        let sel = Select::new();
+        let mut h1 = sel.handle(&rx1);
+        let mut next = 0;
+        while next < AMT {
+            unsafe { h1.add_timeout(1); }
+            assert_eq!(sel.wait(), h1.id());
+            if !h1.timed_out() {
+                assert_eq!(h1.recv_unwrap(), next);
+                next += 1;
+            }
+        }
+        unsafe { h1.remove(); }
+        assert_eq!(rx1.try_recv(), Err(Empty));
+        tx2.send(());
+    })
+
+    test!(fn timeout_empty_set() {
+        let sel = Select::new();
+        assert_eq!(sel.wait_timeout(10), None);
+    })
+
+    test!(fn ready_beats_timeout() {
+        let (tx1, rx1) = channel::<int>();
+        let (_tx2, rx2) = channel::<int>();
+        tx1.send(1);
+        select! (
+            timeout(0) => { fail!() },
+            _foo = rx2.recv_unwrap() => { fail!() },
+            foo = rx1.recv_unwrap() => { assert_eq!(foo, 1); }
+        )
+
+        drop(tx1);
+        let sel = Select::new();
+        let mut h1 = sel.handle(&rx1);
+        let mut h2 = sel.handle(&rx2);
+        unsafe { h1.add(); h2.add(); }
+        assert_eq!(sel.wait_timeout(0), Some(h1.id()));
+        assert_eq!(h1.recv_opt(), Err(()));
+    })
+
+    test!(fn timeout_wakes_up() {
+        let (tx1, rx1) = channel::<int>();
+        let (tx2, rx2) = channel::<()>();
+        spawn(proc() {
+            rx2.recv_unwrap();
+            tx1.send(1);
+        });
+
+        select! (
+            timeout(10) => {},
+            _foo = rx1.recv_unwrap() => { fail!() }
+        )
+        tx2.send(());
+        select! (
+            timeout(10000) => { fail!() },
+            foo = rx1.recv_unwrap() => { assert_eq!(foo, 1); }
+        )
+    })
+
+    test!(fn stress_timeout() {
+        static AMT: int = 1000;
+        let (tx1, rx1) = channel::<int>();
+        let (tx2, rx2) = channel::<int>();
+        let (tx3, rx3) = channel::<()>();
+
+        // every value sent is received exactly once, no matter how the
+        // timeouts interleave with the sends
+        spawn(proc() {
+            for i in range(0, AMT) {
+                if i % 2 == 0 {
+                    tx1.send(i);
+                } else {
+                    tx2.send(i);
+                }
+                if i % 100 == 0 {
+                    ::io::timer::sleep(1);
+                }
+            }
+            // keep the channels open until everything has been received
+            rx3.recv_unwrap();
+        });
+
+        let mut received = Vec::new();
+        while received.len() < AMT as uint {
+            select! (
+                timeout(1) => {},
+                i1 = rx1.recv_unwrap() => { assert!(i1 % 2 == 0); received.push(i1) },
+                i2 = rx2.recv_unwrap() => { assert!(i2 % 2 == 1); received.push(i2) }
+            )
+        }
+        received.sort();
+        let expected: Vec<int> = range(0, AMT).collect();
+        assert!(received == expected);
+        assert_eq!(rx1.try_recv(), Err(Empty));
+        assert_eq!(rx2.try_recv(), Err(Empty));
+        tx3.send(());
+    })
+
     test!(fn cloning() {
         let (tx1, rx1) = channel::<int>();
         let (_tx2, rx2) = channel::<int>();
         let (tx3, rx3) = channel::<()>();
 
         spawn(proc() {
-            rx3.recv();
+            rx3.recv_unwrap();
             tx1.clone();
             assert_eq!(rx3.try_recv(), Err(Empty));
             tx1.send(2);
-            rx3.recv();
+            rx3.recv_unwrap();
         });
 
         tx3.send(());
         select!(
-            _i1 = rx1.recv() => {},
-            _i2 = rx2.recv() => fail!()
+            _i1 = rx1.recv_unwrap() => {},
+            _i2 = rx2.recv_unwrap() => fail!()
         )
         tx3.send(());
     })
@@ -476,17 +759,17 @@ mod test {
         let (tx3, rx3) = channel::<()>();
 
         spawn(proc() {
-            rx3.recv();
+            rx3.recv_unwrap();
             tx1.clone();
             assert_eq!(rx3.try_recv(), Err(Empty));
             tx1.send(2);
-            rx3.recv();
+            rx3.recv_unwrap();
         });
 
         tx3.send(());
         select!(
-            _i1 = rx1.recv() => {},
-            _i2 = rx2.recv() => fail!()
+            _i1 = rx1.recv_unwrap() => {},
+            _i2 = rx2.recv_unwrap() => fail!()
         )
         tx3.send(());
     })
@@ -508,14 +791,14 @@ mod test {
         for _ in range(0, 1000) { task::deschedule(); }
         drop(tx1.clone());
         tx2.send(());
-        rx3.recv();
+        rx3.recv_unwrap();
     })
 
     test!(fn preflight1() {
         let (tx, rx) = channel();
         tx.send(());
         select!(
-            () = rx.recv() => {}
+            () = rx.recv_unwrap() => {}
         )
     })
 
@@ -524,7 +807,7 @@ mod test {
         tx.send(());
         tx.send(());
         select!(
-            () = rx.recv() => {}
+            () = rx.recv_unwrap() => {}
         )
     })
 
@@ -533,7 +816,7 @@ mod test {
         drop(tx.clone());
         tx.send(());
         select!(
-            () = rx.recv() => {}
+            () = rx.recv_unwrap() => {}
         )
     })
 
@@ -579,7 +862,7 @@ mod test {
         let (tx, rx) = channel();
         tx.send(());
         drop(tx);
-        rx.recv();
+        rx.recv_unwrap();
         let s = Select::new();
         let mut h = s.handle(&rx);
         unsafe { h.add(); }
@@ -591,7 +874,7 @@ mod test {
         drop(tx.clone());
         tx.send(());
         drop(tx);
-        rx.recv();
+        rx.recv_unwrap();
         let s = Select::new();
         let mut h = s.handle(&rx);
         unsafe { h.add(); }
@@ -603,14 +886,14 @@ mod test {
         let (tx2, rx2) = channel();
         spawn(proc() {
             select! {
-                () = rx1.recv() => {}
+                () = rx1.recv_unwrap() => {}
             }
             tx2.send(());
         });
 
         for _ in range(0, 100) { task::deschedule() }
         tx1.send(());
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     test!(fn stream_data_waiting() {
@@ -618,18 +901,18 @@ mod test {
         let (tx2, rx2) = channel();
         tx1.send(());
         tx1.send(());
-        rx1.recv();
-        rx1.recv();
+        rx1.recv_unwrap();
+        rx1.recv_unwrap();
         spawn(proc() {
             select! {
-                () = rx1.recv() => {}
+                () = rx1.recv_unwrap() => {}
             }
             tx2.send(());
         });
 
         for _ in range(0, 100) { task::deschedule() }
         tx1.send(());
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     test!(fn shared_data_waiting() {
@@ -637,24 +920,24 @@ mod test {
         let (tx2, rx2) = channel();
         drop(tx1.clone());
         tx1.send(());
-        rx1.recv();
+        rx1.recv_unwrap();
         spawn(proc() {
             select! {
-                () = rx1.recv() => {}
+                () = rx1.recv_unwrap() => {}
             }
             tx2.send(());
         });
 
         for _ in range(0, 100) { task::deschedule() }
         tx1.send(());
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     test!(fn sync1() {
         let (tx, rx) = sync_channel(1);
         tx.send(1);
         select! {
-            n = rx.recv() => { assert_eq!(n, 1); }
+            n = rx.recv_unwrap() => { assert_eq!(n, 1); }
         }
     })
 
@@ -665,7 +948,7 @@ mod test {
             tx.send(1);
         });
         select! {
-            n = rx.recv() => { assert_eq!(n, 1); }
+            n = rx.recv_unwrap() => { assert_eq!(n, 1); }
         }
     })
 
@@ -675,13 +958,13 @@ mod test {
         spawn(proc() { tx1.send(1); });
         spawn(proc() { tx2.send(2); });
         select! {
-            n = rx1.recv() => {
+            n = rx1.recv_unwrap() => {
                 assert_eq!(n, 1);
-                assert_eq!(rx2.recv(), 2);
+                assert_eq!(rx2.recv_unwrap(), 2);
             },
-            n = rx2.recv() => {
+            n = rx2.recv_unwrap() => {
                 assert_eq!(n, 2);
-                assert_eq!(rx1.recv(), 1);
+                assert_eq!(rx1.recv_unwrap(), 1);
             }
         }
     })
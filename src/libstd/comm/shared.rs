@@ -232,7 +232,8 @@ impl<T: Send> Packet<T> {
             data => return data,
         }
 
-        let task: Box<Task> = Local::take();
+        let mut task: Box<Task> = Local::take();
+        task.blocked_on = Some("a channel");
         task.deschedule(1, |task| {
             self.decrement(task)
         });
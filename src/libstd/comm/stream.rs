@@ -182,7 +182,8 @@ impl<T: Send> Packet<T> {
 
         // Welp, our channel has no data. Deschedule the current task and
         // initiate the blocking protocol.
-        let task: Box<Task> = Local::take();
+        let mut task: Box<Task> = Local::take();
+        task.blocked_on = Some("a channel");
         task.deschedule(1, |task| {
             self.decrement(task)
         });
@@ -34,6 +34,7 @@
 /// but they're mostly just relevant to blocking senders.
 
 use container::Container;
+use io::timer::Timer;
 use iter::Iterator;
 use kinds::Send;
 use mem;
@@ -49,6 +50,9 @@ use ty::Unsafe;
 use unstable::mutex::{NativeMutex, LockGuard};
 use vec::Vec;
 
+use super::Receiver;
+use Selectable = super::select::Packet;
+
 pub struct Packet<T> {
     /// Only field outside of the mutex. Just done for kicks, but mainly because
     /// the other shared channel already had the code implemented
@@ -111,7 +115,8 @@ pub enum Failure {
 /// in the meantime. This re-locks the mutex upon returning.
 fn wait(slot: &mut Blocker, f: fn(BlockedTask) -> Blocker,
         lock: &NativeMutex) {
-    let me: Box<Task> = Local::take();
+    let mut me: Box<Task> = Local::take();
+    me.blocked_on = Some("a synchronous channel");
     me.deschedule(1, |task| {
         match mem::replace(slot, f(task)) {
             NoneBlocked => {}
@@ -123,6 +128,40 @@ fn wait(slot: &mut Blocker, f: fn(BlockedTask) -> Blocker,
     unsafe { lock.lock_noguard(); }
 }
 
+/// Blocks the current task just like `wait`, except that the task is also woken
+/// up once `timeout` fires. The closure `register` is given the blocked task to
+/// store wherever it will be woken up from, and `lock` is unlocked while
+/// blocked.
+///
+/// Returns whether the task actually blocked. If the timeout had already fired
+/// then `register` is not invoked and the lock is held the whole time.
+/// Otherwise the caller needs to find out why it was woken up, and if it was
+/// the timeout then it is responsible for removing the task registered.
+fn wait_timeout(timeout: &Receiver<()>, lock: &NativeMutex,
+                register: |BlockedTask|) -> bool {
+    let me: Box<Task> = Local::take();
+    let mut timer_registered = false;
+    let mut blocked = false;
+    me.deschedule(2, |task| {
+        if !timer_registered {
+            timer_registered = true;
+            timeout.start_selection(task)
+        } else {
+            register(task);
+            blocked = true;
+            unsafe { lock.unlock_noguard(); }
+            Ok(())
+        }
+    });
+    if blocked {
+        // Make sure the timer is done with our task before re-acquiring the
+        // lock, this may need to wait for a timer which is just firing.
+        timeout.abort_selection();
+        unsafe { lock.lock_noguard(); }
+    }
+    blocked
+}
+
 /// Wakes up a task, dropping the lock at the correct time
 fn wakeup(task: BlockedTask, guard: LockGuard) {
     // We need to be careful to wake up the waiting task *outside* of the mutex
@@ -228,6 +267,69 @@ impl<T: Send> Packet<T> {
         }
     }
 
+    // Like `send`, but gives up once `msecs` milliseconds have passed. This
+    // blocks on a oneshot timer in addition to the channel itself, whichever
+    // wakes us up first.
+    pub fn send_timeout(&self, t: T,
+                        msecs: u64) -> Result<(), super::TrySendError<T>> {
+        // don't bother with a timer if we can send right away
+        let t = match self.try_send(t) {
+            Err(super::Full(t)) => t,
+            ret => return ret,
+        };
+        let mut timer = Timer::new().ok()
+                                    .expect("send_timeout: could not create a Timer");
+        let timeout = timer.oneshot(msecs);
+        let (guard, state) = self.lock();
+
+        // wait for a slot to become available. If the timer fires while we're
+        // queued we still retry once, in case we were also dequeued.
+        while !state.disconnected && state.buf.size() == state.buf.cap() {
+            if !state.queue.enqueue_timeout(&self.lock, &timeout) {
+                return Err(super::Full(t))
+            }
+        }
+        if state.disconnected { return Err(super::RecvDisconnected(t)) }
+        state.buf.enqueue(t);
+
+        match mem::replace(&mut state.blocker, NoneBlocked) {
+            // As with `send`, with a capacity of 0 we wait for a receiver to
+            // take our data. If nobody did before the timeout we need to take
+            // the data back out of the buffer.
+            NoneBlocked if state.cap == 0 => {
+                let mut canceled = false;
+                assert!(state.canceled.is_none());
+                state.canceled = Some(unsafe { mem::transmute(&mut canceled) });
+                let blocked = wait_timeout(&timeout, &self.lock, |task| {
+                    state.blocker = BlockedSender(task);
+                });
+                if canceled {
+                    return Err(super::RecvDisconnected(state.buf.dequeue()))
+                }
+                let timed_out = !blocked || match mem::replace(&mut state.blocker,
+                                                               NoneBlocked) {
+                    NoneBlocked => false,
+                    BlockedSender(task) => { task.trash(); true }
+                    BlockedReceiver(..) => unreachable!(),
+                };
+                if timed_out {
+                    state.canceled.take();
+                    Err(super::Full(state.buf.dequeue()))
+                } else {
+                    Ok(())
+                }
+            }
+
+            // success, we buffered some data
+            NoneBlocked => Ok(()),
+
+            // success, someone's about to receive our buffered data.
+            BlockedReceiver(task) => { wakeup(task, guard); Ok(()) }
+
+            BlockedSender(..) => fail!("lolwut"),
+        }
+    }
+
     // Receives a message from this channel
     //
     // When reading this, remember that there can only ever be one receiver at
@@ -333,14 +435,19 @@ impl<T: Send> Packet<T> {
         // needs to be careful to destroy the data *outside* of the lock to
         // prevent deadlock.
         let _data = if state.cap != 0 {
-            mem::replace(&mut state.buf.buf, Vec::new())
+            mem::take(&mut state.buf.buf)
         } else {
             Vec::new()
         };
-        let mut queue = mem::replace(&mut state.queue, Queue {
-            head: 0 as *mut Node,
-            tail: 0 as *mut Node,
-        });
+        // The queued senders are dequeued while the lock is still held, as a
+        // sender which timed out removes itself from the queue under the lock.
+        let mut queued = Vec::new();
+        loop {
+            match state.queue.dequeue() {
+                Some(task) => queued.push(task),
+                None => break,
+            }
+        }
 
         let waiter = match mem::replace(&mut state.blocker, NoneBlocked) {
             NoneBlocked => None,
@@ -352,11 +459,8 @@ impl<T: Send> Packet<T> {
         };
         mem::drop((state, guard));
 
-        loop {
-            match queue.dequeue() {
-                Some(task) => { task.wake().map(|t| t.reawaken()); }
-                None => break,
-            }
+        for task in queued.move_iter() {
+            task.wake().map(|t| t.reawaken());
         }
         waiter.map(|t| t.wake().map(|t| t.reawaken()));
     }
@@ -445,22 +549,15 @@ impl<T> Buffer<T> {
 
 impl Queue {
     fn enqueue(&mut self, lock: &NativeMutex) {
-        let task: Box<Task> = Local::take();
+        let mut task: Box<Task> = Local::take();
+        task.blocked_on = Some("a synchronous channel");
         let mut node = Node {
             task: None,
             next: 0 as *mut Node,
         };
         task.deschedule(1, |task| {
             node.task = Some(task);
-            if self.tail.is_null() {
-                self.head = &mut node as *mut Node;
-                self.tail = &mut node as *mut Node;
-            } else {
-                unsafe {
-                    (*self.tail).next = &mut node as *mut Node;
-                    self.tail = &mut node as *mut Node;
-                }
-            }
+            self.push(&mut node);
             unsafe { lock.unlock_noguard(); }
             Ok(())
         });
@@ -468,6 +565,67 @@ impl Queue {
         assert!(node.next.is_null());
     }
 
+    // Like `enqueue`, but also wakes up once `timeout` fires. Returns false if
+    // the task was woken up by the timeout rather than by being dequeued.
+    fn enqueue_timeout(&mut self, lock: &NativeMutex,
+                       timeout: &Receiver<()>) -> bool {
+        let mut node = Node {
+            task: None,
+            next: 0 as *mut Node,
+        };
+        let blocked = wait_timeout(timeout, lock, |task| {
+            node.task = Some(task);
+            self.push(&mut node);
+        });
+        if !blocked { return false }
+
+        // If our task is still in the node then nobody dequeued us, so we need
+        // to get out of the queue before the node goes away.
+        match node.task.take() {
+            None => {}
+            Some(task) => {
+                self.remove(&mut node);
+                task.trash();
+                return false
+            }
+        }
+        assert!(node.next.is_null());
+        true
+    }
+
+    fn push(&mut self, node: *mut Node) {
+        if self.tail.is_null() {
+            self.head = node;
+            self.tail = node;
+        } else {
+            unsafe {
+                (*self.tail).next = node;
+                self.tail = node;
+            }
+        }
+    }
+
+    // Unlinks a node which is currently in the queue
+    fn remove(&mut self, node: *mut Node) {
+        let mut prev = 0 as *mut Node;
+        let mut cur = self.head;
+        while cur != node {
+            assert!(!cur.is_null());
+            prev = cur;
+            cur = unsafe { (*cur).next };
+        }
+        let next = unsafe { (*node).next };
+        if prev.is_null() {
+            self.head = next;
+        } else {
+            unsafe { (*prev).next = next; }
+        }
+        if self.tail == node {
+            self.tail = prev;
+        }
+        unsafe { (*node).next = 0 as *mut Node; }
+    }
+
     fn dequeue(&mut self) -> Option<BlockedTask> {
         if self.head.is_null() {
             return None
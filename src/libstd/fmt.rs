@@ -180,9 +180,11 @@ fn fmt(&self, f: &mut std::fmt::Formatter) -> fmt::Result;
 Your type will be passed as `self` by-reference, and then the function should
 emit output into the `f.buf` stream. It is up to each format trait
 implementation to correctly adhere to the requested formatting parameters. The
-values of these parameters will be listed in the fields of the `Formatter`
-struct. In order to help with this, the `Formatter` struct also provides some
-helper methods.
+values of these parameters can be queried with methods of the `Formatter`
+struct such as `width()`, `precision()` and `sign_plus()`. In order to help
+with this, the `Formatter` struct also provides the `pad` and `pad_integral`
+helper methods, which apply the width, fill, alignment and sign flags the same
+way the built-in types do.
 
 Additionally, the return value of this function is `fmt::Result` which is a
 typedef to `Result<(), IoError>` (also known as `IoError<()>`). Formatting
@@ -220,7 +222,7 @@ impl fmt::Binary for Vector2D {
         // Respect the formatting flags by using the helper method
         // `pad_integral` on the Formatter object. See the method documentation
         // for details, and the function `pad` can be used to pad strings.
-        let decimals = f.precision.unwrap_or(3);
+        let decimals = f.precision().unwrap_or(3);
         let string = f64::to_str_exact(magnitude, decimals);
         f.pad_integral(true, "", string.as_bytes())
     }
@@ -442,7 +444,12 @@ These can all be interpreted as flags for a particular formatter.
         always be printed. Positive signs are never printed by default, and the
         negative sign is only printed by default for the `Signed` trait. This
         flag indicates that the correct sign (+ or -) should always be printed.
-* '-' - Currently not used
+        For signed integers formatted with `t`, `o`, `x` or `X`, whose bit
+        pattern is printed by default, it also switches to printing the sign
+        followed by the magnitude, so `{:+x}` yields `-1` for the `i8` `-1`.
+* '-' - Like '+', this selects sign and magnitude output for signed integers
+        formatted with `t`, `o`, `x` or `X`, but only negative values are
+        given a sign: `{:-#x}` yields `-0xff` for `-255` and `0xff` for `255`.
 * '#' - This flag is indicates that the "alternate" form of printing should be
         used. By default, this only applies to the integer formatting traits and
         performs like:
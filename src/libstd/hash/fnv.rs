@@ -0,0 +1,174 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+ * Implementation of the 64-bit FNV-1a hash
+ *
+ * See: http://www.isthe.com/chongo/tech/comp/fnv/
+ *
+ * FNV is much faster than SipHash for small keys such as integers, but it is
+ * not keyed, so anyone who can choose the keys of a hashtable using it can
+ * make them all collide. Only use it for tables whose keys can't be chosen by
+ * an attacker, like the interners and node maps of a compiler; `SipHasher`
+ * remains the default for `HashMap`.
+ */
+
+use clone::Clone;
+use default::Default;
+use io::{IoResult, Writer};
+use iter::Iterator;
+use result::Ok;
+use slice::ImmutableVector;
+
+use super::{Hash, Hasher};
+
+static INITIAL_STATE: u64 = 0xcbf29ce484222325;
+static PRIME: u64 = 0x100000001b3;
+
+/// `FnvState` computes an FNV-1a hash over a stream of bytes.
+pub struct FnvState {
+    hash: u64,
+}
+
+impl FnvState {
+    /// Create an `FnvState`.
+    #[inline]
+    pub fn new() -> FnvState {
+        FnvState { hash: INITIAL_STATE }
+    }
+
+    /// Reset the state back to its initial value.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.hash = INITIAL_STATE;
+    }
+
+    /// Return the computed hash.
+    #[inline]
+    pub fn result(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl Writer for FnvState {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> IoResult<()> {
+        let mut hash = self.hash;
+        for byte in bytes.iter() {
+            hash = hash ^ (*byte as u64);
+            hash = hash * PRIME;
+        }
+        self.hash = hash;
+        Ok(())
+    }
+}
+
+impl Clone for FnvState {
+    #[inline]
+    fn clone(&self) -> FnvState {
+        FnvState { hash: self.hash }
+    }
+}
+
+impl Default for FnvState {
+    #[inline]
+    fn default() -> FnvState {
+        FnvState::new()
+    }
+}
+
+/// `FnvHasher` computes the FNV-1a algorithm from a stream of bytes.
+#[deriving(Clone)]
+pub struct FnvHasher;
+
+impl Hasher<FnvState> for FnvHasher {
+    #[inline]
+    fn hash<T: Hash<FnvState>>(&self, value: &T) -> u64 {
+        hash(value)
+    }
+}
+
+impl Default for FnvHasher {
+    #[inline]
+    fn default() -> FnvHasher {
+        FnvHasher
+    }
+}
+
+/// Hash a value using the FNV-1a algorithm.
+#[inline]
+pub fn hash<T: Hash<FnvState>>(value: &T) -> u64 {
+    let mut state = FnvState::new();
+    value.hash(&mut state);
+    state.result()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+    use prelude::*;
+    use self::test::Bencher;
+
+    use super::super::Hash;
+    use super::{FnvState, hash};
+
+    // Hash just the bytes of the slice, without length prefix
+    struct Bytes<'a>(&'a [u8]);
+
+    impl<'a, S: Writer> Hash<S> for Bytes<'a> {
+        #[allow(unused_must_use)]
+        fn hash(&self, state: &mut S) {
+            let Bytes(v) = *self;
+            state.write(v);
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        assert_eq!(hash(&Bytes([])), 0xcbf29ce484222325);
+        assert_eq!(hash(&Bytes("a".as_bytes())), 0xaf63dc4c8601ec8c);
+        assert_eq!(hash(&Bytes("foobar".as_bytes())), 0x85944171f73967e8);
+    }
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn test_incremental() {
+        let mut state = FnvState::new();
+        state.write("foo".as_bytes());
+        state.write("bar".as_bytes());
+        assert_eq!(state.result(), hash(&Bytes("foobar".as_bytes())));
+
+        state.reset();
+        assert_eq!(state.result(), hash(&Bytes([])));
+    }
+
+    #[test]
+    fn test_ints_differ() {
+        assert!(hash(&1u) != hash(&2u));
+        assert!(hash(&0u64) != hash(&(1u64 << 32)));
+    }
+
+    #[bench]
+    fn bench_uint(b: &mut Bencher) {
+        let mut i = 0u;
+        b.iter(|| {
+            i += 1;
+            hash(&i)
+        })
+    }
+
+    #[bench]
+    fn bench_str_under_8_bytes(b: &mut Bencher) {
+        let s = "foo";
+        b.iter(|| {
+            hash(&s)
+        })
+    }
+}
@@ -59,6 +59,11 @@
  *
  * assert!(hash::hash(&person1) == hash::hash(&person2));
  * ```
+ *
+ * Values are hashed with SipHash (see the `sip` module) by default, but the
+ * `Hash` trait is generic over the state being hashed into, so the same types
+ * can also be hashed with other algorithms such as FNV (see the `fnv`
+ * module). Hashtables pick their algorithm with a `Hasher`.
  */
 
 #![allow(unused_must_use)]
@@ -79,6 +84,7 @@ pub use hash = self::sip::hash;
 
 pub use Writer = io::Writer;
 
+pub mod fnv;
 pub mod sip;
 
 /// A trait that represents a hashable type. The `S` type parameter is an
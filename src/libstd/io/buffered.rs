@@ -13,6 +13,7 @@
 use cmp;
 use container::Container;
 use io::{Reader, Writer, Stream, Buffer, DEFAULT_BUF_SIZE, IoResult};
+use io::{Seek, SeekStyle, SeekSet, SeekCur};
 use iter::ExactSize;
 use ops::Drop;
 use option::{Some, None, Option};
@@ -113,6 +114,44 @@ impl<R: Reader> Reader for BufferedReader<R> {
     }
 }
 
+/// Seeking a `BufferedReader` accounts for the data which has been read from
+/// the underlying reader but not yet consumed. A seek which lands within the
+/// buffered data only moves the position in the buffer, any other seek
+/// discards the buffer and seeks the underlying reader.
+impl<R: Reader + Seek> Seek for BufferedReader<R> {
+    fn tell(&self) -> IoResult<u64> {
+        let pos = try!(self.inner.tell());
+        Ok(pos - (self.cap - self.pos) as u64)
+    }
+
+    fn seek(&mut self, pos: i64, style: SeekStyle) -> IoResult<()> {
+        let offset = match style {
+            SeekCur => Some(pos),
+            SeekSet => Some(pos - try!(self.tell()) as i64),
+            _ => None,
+        };
+        match offset {
+            Some(off) if off >= -(self.pos as i64) &&
+                         off <= (self.cap - self.pos) as i64 => {
+                self.pos = (self.pos as i64 + off) as uint;
+                return Ok(())
+            }
+            _ => {}
+        }
+
+        // The underlying reader is ahead of us by the unconsumed part of the
+        // buffer, which relative seeks need to take into account.
+        let pos = match style {
+            SeekCur => pos - (self.cap - self.pos) as i64,
+            _ => pos,
+        };
+        try!(self.inner.seek(pos, style));
+        self.pos = 0;
+        self.cap = 0;
+        Ok(())
+    }
+}
+
 /// Wraps a Writer and buffers output to it
 ///
 /// It can be excessively inefficient to work directly with a `Writer`. For
@@ -205,6 +244,20 @@ impl<W: Writer> Writer for BufferedWriter<W> {
     }
 }
 
+/// Seeking a `BufferedWriter` flushes the buffer before seeking the underlying
+/// writer.
+impl<W: Writer + Seek> Seek for BufferedWriter<W> {
+    fn tell(&self) -> IoResult<u64> {
+        let pos = try!(self.inner.get_ref().tell());
+        Ok(pos + self.pos as u64)
+    }
+
+    fn seek(&mut self, pos: i64, style: SeekStyle) -> IoResult<()> {
+        try!(self.flush_buf());
+        self.inner.get_mut_ref().seek(pos, style)
+    }
+}
+
 #[unsafe_destructor]
 impl<W: Writer> Drop for BufferedWriter<W> {
     fn drop(&mut self) {
@@ -432,6 +485,53 @@ mod test {
         assert!(reader.read(buf).is_err());
     }
 
+    #[test]
+    fn test_buffered_reader_seek() {
+        let inner = MemReader::new(vec!(0, 1, 2, 3, 4, 5, 6, 7));
+        let mut reader = BufferedReader::with_capacity(4, inner);
+
+        assert_eq!(reader.read_byte(), Ok(0));
+        assert_eq!(reader.stream_position(), Ok(1));
+        assert_eq!(reader.get_ref().tell(), Ok(4));
+
+        // seeks within the buffer don't touch the underlying reader
+        assert_eq!(reader.seek_from(io::Current(2)), Ok(3));
+        assert_eq!(reader.get_ref().tell(), Ok(4));
+        assert_eq!(reader.read_byte(), Ok(3));
+        assert_eq!(reader.seek_from(io::Start(0)), Ok(0));
+        assert_eq!(reader.get_ref().tell(), Ok(4));
+        assert_eq!(reader.read_byte(), Ok(0));
+
+        // seeks outside of it discard the buffer
+        assert_eq!(reader.seek_from(io::Current(5)), Ok(6));
+        assert_eq!(reader.read_byte(), Ok(6));
+        assert_eq!(reader.seek_from(io::End(-6)), Ok(2));
+        assert_eq!(reader.read_byte(), Ok(2));
+        assert_eq!(reader.seek_from(io::Current(-2)), Ok(1));
+        assert_eq!(reader.read_byte(), Ok(1));
+
+        // a failed seek leaves the position alone
+        assert!(reader.seek_from(io::Current(-10)).is_err());
+        assert_eq!(reader.stream_position(), Ok(2));
+        assert_eq!(reader.read_byte(), Ok(2));
+        assert!(reader.seek_from(io::Start(-1i64 as u64)).is_err());
+    }
+
+    #[test]
+    fn test_buffered_writer_seek() {
+        let mut writer = BufferedWriter::with_capacity(4, MemWriter::new());
+        writer.write([0, 1, 2, 3, 4, 5]).unwrap();
+        writer.write([6]).unwrap();
+        assert_eq!(writer.stream_position(), Ok(7));
+        assert_eq!(writer.get_ref().tell(), Ok(6));
+
+        assert_eq!(writer.seek_from(io::Start(2)), Ok(2));
+        assert_eq!(writer.get_ref().get_ref(), &[0, 1, 2, 3, 4, 5, 6]);
+        writer.write([9, 9]).unwrap();
+        assert_eq!(writer.stream_position(), Ok(4));
+        assert_eq!(writer.unwrap().unwrap(), vec!(0, 1, 9, 9, 4, 5, 6));
+    }
+
     #[test]
     fn test_buffered_writer() {
         let inner = MemWriter::new();
@@ -513,6 +613,22 @@ mod test {
         assert!(reader.read_until(9).is_err());
     }
 
+    #[test]
+    fn test_read_until_limit() {
+        let inner = MemReader::new(vec!(0, 1, 2, 3, 1, 0));
+        let mut reader = BufferedReader::with_capacity(2, inner);
+        assert_eq!(reader.read_until_limit(1, 3), Ok(vec!(0, 1)));
+        assert_eq!(reader.read_until_limit(0, 3), Ok(vec!(2, 3, 1)));
+        assert_eq!(reader.read_until_limit(0, 0), Ok(vec!()));
+        assert_eq!(reader.read_until_limit(0, 1), Ok(vec!(0)));
+        assert!(reader.read_until_limit(0, 1).is_err());
+
+        let inner = MemReader::new(vec!(5, 5, 5, 5, 5));
+        let mut reader = BufferedReader::with_capacity(2, inner);
+        assert_eq!(reader.read_until_limit(0, 4), Ok(vec!(5, 5, 5, 5)));
+        assert_eq!(reader.read_until_limit(0, 4), Ok(vec!(5)));
+    }
+
     #[test]
     fn test_line_buffer() {
         let mut writer = LineBufferedWriter::new(MemWriter::new());
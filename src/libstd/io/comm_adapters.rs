@@ -182,7 +182,7 @@ mod test {
         writer.write_be_u32(42).unwrap();
 
         let wanted = box [0u8, 0u8, 0u8, 42u8];
-        let got = task::try(proc() { rx.recv() }).unwrap();
+        let got = task::try(proc() { rx.recv_unwrap() }).unwrap();
         assert_eq!(wanted, got);
 
         match writer.write_u8(1) {
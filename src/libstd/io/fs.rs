@@ -52,8 +52,12 @@ fs::unlink(&path);
 use c_str::ToCStr;
 use clone::Clone;
 use container::Container;
-use iter::Iterator;
+use iter::{Iterator, DoubleEndedIterator};
 use kinds::Send;
+use libc::c_int;
+use libc;
+use ops::{Deref, DerefMut, Drop};
+use os;
 use super::{Reader, Writer, Seek};
 use super::{SeekStyle, Read, Write, Open, IoError, Truncate};
 use super::{FileMode, FileAccess, FileStat, IoResult, FilePermission};
@@ -64,7 +68,7 @@ use owned::Box;
 use result::{Ok, Err};
 use path;
 use path::{Path, GenericPath};
-use slice::{OwnedVector, ImmutableVector};
+use slice::{Vector, OwnedVector, ImmutableVector};
 use vec::Vec;
 
 /// Unconstrained file access type that exposes read and write operations
@@ -180,6 +184,14 @@ impl File {
         &self.path
     }
 
+    /// Returns the raw file descriptor of the underlying file.
+    ///
+    /// The descriptor is still owned by this `File`: it is closed when the
+    /// `File` is dropped, and must not be closed by anyone else.
+    pub fn fd(&self) -> c_int {
+        self.fd.fd()
+    }
+
     /// Synchronizes all modifications to this file to its permanent storage
     /// device. This will flush any internal buffers necessary to perform this
     /// operation.
@@ -219,6 +231,149 @@ impl File {
     pub fn stat(&mut self) -> IoResult<FileStat> {
         self.fd.fstat()
     }
+
+    /// Acquires an exclusive advisory lock on this file, blocking until any
+    /// other lock held on it has been released. The lock is released when the
+    /// returned guard falls out of scope.
+    ///
+    /// Advisory locks only coordinate processes which all use them; they do
+    /// not prevent anyone from reading or writing the file. Note that this
+    /// always blocks the calling OS thread, even in a green task.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #![allow(unused_must_use)]
+    /// use std::io::File;
+    ///
+    /// let mut file = File::create(&Path::new("app.pid")).unwrap();
+    /// let mut lock = file.lock_exclusive().unwrap();
+    /// lock.write_str("1234\n");
+    /// ```
+    pub fn lock_exclusive<'a>(&'a mut self) -> IoResult<FileLock<'a>> {
+        try!(lock(self.fd(), true, true));
+        Ok(FileLock { file: self })
+    }
+
+    /// Acquires a shared advisory lock on this file, blocking until any
+    /// exclusive lock held on it has been released. Any number of shared
+    /// locks may be held on a file at once.
+    ///
+    /// See `lock_exclusive` for more information.
+    pub fn lock_shared<'a>(&'a mut self) -> IoResult<FileLock<'a>> {
+        try!(lock(self.fd(), false, true));
+        Ok(FileLock { file: self })
+    }
+
+    /// Attempts to acquire an exclusive advisory lock on this file without
+    /// blocking, returning `None` if another lock is already held on it.
+    pub fn try_lock_exclusive<'a>(&'a mut self)
+                                  -> IoResult<Option<FileLock<'a>>> {
+        if try!(lock(self.fd(), true, false)) {
+            Ok(Some(FileLock { file: self }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Attempts to acquire a shared advisory lock on this file without
+    /// blocking, returning `None` if an exclusive lock is already held on it.
+    pub fn try_lock_shared<'a>(&'a mut self) -> IoResult<Option<FileLock<'a>>> {
+        if try!(lock(self.fd(), false, false)) {
+            Ok(Some(FileLock { file: self }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Releases any advisory lock held on this file through this handle.
+    ///
+    /// Locks are normally released by dropping the guard they were acquired
+    /// with, but this allows any error from the release to be inspected.
+    pub fn unlock(&mut self) -> IoResult<()> {
+        unlock(self.fd())
+    }
+}
+
+/// An advisory lock held on a `File`, which is released when this guard falls
+/// out of scope. The locked file may be used through the guard.
+pub struct FileLock<'a> {
+    file: &'a mut File,
+}
+
+impl<'a> Deref<File> for FileLock<'a> {
+    fn deref<'b>(&'b self) -> &'b File { &*self.file }
+}
+
+impl<'a> DerefMut<File> for FileLock<'a> {
+    fn deref_mut<'b>(&'b mut self) -> &'b mut File { &mut *self.file }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for FileLock<'a> {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+// Returns whether the lock was acquired, which is always the case when
+// `block` is true.
+#[cfg(unix)]
+fn lock(fd: c_int, exclusive: bool, block: bool) -> IoResult<bool> {
+    let mut op = if exclusive {libc::LOCK_EX} else {libc::LOCK_SH};
+    if !block { op |= libc::LOCK_NB; }
+    loop {
+        match unsafe { libc::flock(fd, op) } {
+            0 => return Ok(true),
+            _ if os::errno() as int == libc::EINTR as int => {}
+            _ if !block && os::errno() as int == libc::EWOULDBLOCK as int => {
+                return Ok(false)
+            }
+            _ => return Err(IoError::last_error()),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn unlock(fd: c_int) -> IoResult<()> {
+    match unsafe { libc::flock(fd, libc::LOCK_UN) } {
+        0 => Ok(()),
+        _ => Err(IoError::last_error()),
+    }
+}
+
+#[cfg(windows)]
+fn lock(fd: c_int, exclusive: bool, block: bool) -> IoResult<bool> {
+    use mem;
+    let mut flags = if exclusive {libc::LOCKFILE_EXCLUSIVE_LOCK} else {0};
+    if !block { flags |= libc::LOCKFILE_FAIL_IMMEDIATELY; }
+    unsafe {
+        let handle = libc::get_osfhandle(fd) as libc::HANDLE;
+        let mut overlapped: libc::OVERLAPPED = mem::zeroed();
+        match libc::LockFileEx(handle, flags, 0, 0xffffffff, 0xffffffff,
+                               &mut overlapped) {
+            0 if !block &&
+                 os::errno() as int == libc::ERROR_LOCK_VIOLATION as int => {
+                Ok(false)
+            }
+            0 => Err(IoError::last_error()),
+            _ => Ok(true),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn unlock(fd: c_int) -> IoResult<()> {
+    use mem;
+    unsafe {
+        let handle = libc::get_osfhandle(fd) as libc::HANDLE;
+        let mut overlapped: libc::OVERLAPPED = mem::zeroed();
+        match libc::UnlockFileEx(handle, 0, 0xffffffff, 0xffffffff,
+                                 &mut overlapped) {
+            0 => Err(IoError::last_error()),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Unlink a file from the underlying filesystem.
@@ -413,6 +568,85 @@ pub fn readlink(path: &Path) -> IoResult<Path> {
     LocalIo::maybe_raise(|io| io.fs_readlink(&path.to_c_str()))
 }
 
+/// Returns the canonical, absolute form of `path`, with all symlinks in it
+/// resolved.
+///
+/// Relative paths are resolved against the current working directory. Note
+/// that `Path` already removes `.` and `..` components lexically when it is
+/// created, so a `..` following a symlink in `path` itself goes back up the
+/// path as written rather than the one the symlink leads to.
+///
+/// # Example
+///
+/// ```rust
+/// # #![allow(unused_must_use)]
+/// use std::io::fs;
+///
+/// let p = fs::canonicalize(&Path::new(".")).unwrap();
+/// assert!(p.is_absolute());
+/// ```
+///
+/// # Error
+///
+/// This function will return an error if the path does not exist, or if
+/// resolving it follows too many symlinks (which may indicate a cycle).
+pub fn canonicalize(path: &Path) -> IoResult<Path> {
+    let path = os::make_absolute(path);
+    match LocalIo::maybe_raise(|io| io.fs_realpath(&path.to_c_str())) {
+        Err(IoError { kind: io::IoUnavailable, .. }) => resolve_links(&path),
+        result => result,
+    }
+}
+
+// Resolves the symlinks in an absolute path one component at a time, for the
+// runtimes which have no realpath. Link targets are `Path`s as well, so a `..`
+// following a symlink inside of a target is resolved lexically.
+fn resolve_links(path: &Path) -> IoResult<Path> {
+    static MAX_LINKS_FOLLOWED: uint = 256;
+
+    let mut result = path.root_path().expect("make_absolute has no root");
+    // The components which are left to resolve, the next one last
+    let mut pending: Vec<Vec<u8>> = Vec::new();
+    push_components(&mut pending, path);
+    let mut followed = 0;
+    loop {
+        let part = match pending.pop() {
+            Some(part) => part,
+            None => return Ok(result),
+        };
+        // Everything in `result` has been resolved already, so this is only
+        // ever a leading `..` of a relative link target
+        if part.as_slice() == bytes!("..") {
+            result.pop();
+            continue
+        }
+        result.push(part.as_slice());
+        if try!(lstat(&result)).kind != io::TypeSymlink { continue }
+
+        if followed == MAX_LINKS_FOLLOWED {
+            return Err(IoError {
+                kind: io::InvalidInput,
+                desc: "too many levels of symbolic links",
+                detail: None,
+            })
+        }
+        followed += 1;
+        let target = try!(readlink(&result));
+        result.pop();
+        if target.is_absolute() {
+            result = target.root_path().expect("absolute path has no root");
+        }
+        push_components(&mut pending, &target);
+    }
+
+    fn push_components(pending: &mut Vec<Vec<u8>>, path: &Path) {
+        let parts: Vec<&[u8]> = path.components().collect();
+        for part in parts.iter().rev() {
+            pending.push(Vec::from_slice(*part));
+        }
+    }
+}
+
 /// Create a new, empty directory at the provided path
 ///
 /// # Example
@@ -496,19 +730,34 @@ pub fn readdir(path: &Path) -> IoResult<Vec<Path>> {
 /// perform iteration in some top-down order.  The contents of unreadable
 /// subdirectories are ignored.
 pub fn walk_dir(path: &Path) -> IoResult<Directories> {
-    Ok(Directories { stack: try!(readdir(path)) })
+    Ok(Directories { stack: try!(readdir(path)), follow_links: true })
 }
 
 /// An iterator which walks over a directory
 pub struct Directories {
     stack: Vec<Path>,
+    follow_links: bool,
+}
+
+impl Directories {
+    fn should_descend(&self, path: &Path) -> bool {
+        // FIXME(#12795) we should use lstat in all cases
+        if self.follow_links || cfg!(windows) {
+            path.is_dir()
+        } else {
+            match lstat(path) {
+                Ok(stat) => stat.kind == io::TypeDirectory,
+                Err(..) => false,
+            }
+        }
+    }
 }
 
 impl Iterator<Path> for Directories {
     fn next(&mut self) -> Option<Path> {
         match self.stack.pop() {
             Some(path) => {
-                if path.is_dir() {
+                if self.should_descend(&path) {
                     match readdir(&path) {
                         Ok(dirs) => { self.stack.push_all_move(dirs); }
                         Err(..) => {}
@@ -521,6 +770,96 @@ impl Iterator<Path> for Directories {
     }
 }
 
+/// Describes how `copy_recursive` treats the symbolic links it encounters.
+#[deriving(Eq, Clone, Show)]
+pub enum SymlinkPolicy {
+    /// Copy whatever each link points to, as if the link were that file or
+    /// directory itself. Note that a link pointing back up the tree will cause
+    /// the copy to never terminate.
+    FollowSymlinks,
+    /// Create a link at the destination with the same target as the original
+    /// link. The target itself is not copied (unless it is also in the tree).
+    CopySymlinks,
+    /// Leave links out of the copy entirely.
+    SkipSymlinks,
+}
+
+/// Recursively copies the directory at `from`, and all of its contents, to a
+/// new directory at `to`. Links in the tree are treated as specified by
+/// `symlinks`.
+///
+/// The copied files and directories have the same permission bits as the
+/// originals.
+///
+/// # Example
+///
+/// ```rust
+/// # #![allow(unused_must_use)]
+/// use std::io::fs;
+///
+/// fs::copy_recursive(&Path::new("src"), &Path::new("backup"),
+///                    fs::CopySymlinks);
+/// ```
+///
+/// # Error
+///
+/// This function will return an error if `from` is not a directory, if `to`
+/// already exists or is inside of `from`, or if any file in the tree cannot be
+/// copied (see `fs::copy`). The contents of unreadable subdirectories are not copied.
+/// No attempt is made to remove a partially completed copy on error.
+pub fn copy_recursive(from: &Path, to: &Path,
+                      symlinks: SymlinkPolicy) -> IoResult<()> {
+    if !from.is_dir() {
+        return Err(IoError {
+            kind: io::MismatchedFileTypeForOperation,
+            desc: "the source path is not an existing directory",
+            detail: None,
+        })
+    }
+
+    // A copy inside of the tree being copied would keep copying itself. `to`
+    // doesn't exist yet, so only its parent can be canonicalized.
+    let to_real = match to.filename() {
+        Some(name) => try!(canonicalize(&to.dir_path())).join(name),
+        None => try!(canonicalize(to)),
+    };
+    if try!(canonicalize(from)).is_ancestor_of(&to_real) {
+        return Err(IoError {
+            kind: io::InvalidInput,
+            desc: "the destination path is inside of the source directory",
+            detail: None,
+        })
+    }
+
+    // Directories are created writable, and only given their final
+    // permissions once everything inside of them has been copied.
+    let follow = symlinks == FollowSymlinks;
+    let mut dirs = vec!((try!(from.stat()).perm, to.clone()));
+    try!(mkdir(to, io::UserRWX));
+
+    let walk = Directories { stack: try!(readdir(from)), follow_links: follow };
+    for src in walk {
+        let dst = to.join(src.path_relative_from(from).unwrap());
+        let stat = if follow { try!(src.stat()) } else { try!(src.lstat()) };
+        match stat.kind {
+            io::TypeDirectory => {
+                try!(mkdir(&dst, io::UserRWX));
+                dirs.push((stat.perm, dst));
+            }
+            io::TypeSymlink if symlinks == CopySymlinks => {
+                try!(symlink(&try!(readlink(&src)), &dst));
+            }
+            io::TypeSymlink => {}
+            _ => try!(copy(&src, &dst)),
+        }
+    }
+
+    for &(perm, ref dir) in dirs.iter().rev() {
+        try!(chmod(dir, perm));
+    }
+    Ok(())
+}
+
 /// Recursively create a directory and all of its parent components if they
 /// are missing.
 ///
@@ -628,10 +967,28 @@ impl Reader for File {
             Err(e) => Err(e),
         }
     }
+
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        match self.fd.readv(bufs) {
+            Ok(read) => {
+                self.last_nread = read;
+                match read {
+                    0 if bufs.iter().any(|buf| !buf.is_empty()) => {
+                        Err(io::standard_error(io::EndOfFile))
+                    }
+                    _ => Ok(read as uint)
+                }
+            },
+            Err(e) => Err(e),
+        }
+    }
 }
 
 impl Writer for File {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.fd.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.fd.writev(bufs)
+    }
 }
 
 impl Seek for File {
@@ -770,6 +1127,136 @@ mod test {
         check!(unlink(filename));
     })
 
+    iotest!(fn file_test_io_vectored() {
+        let message = "header: value\nbody";
+        let tmpdir = tmpdir();
+        let filename = &tmpdir.join("file_rt_io_vectored.txt");
+        {
+            let mut write_stream = File::open_mode(filename, Open, ReadWrite);
+            check!(write_stream.write_vectored(["header: ".as_bytes(),
+                                                "".as_bytes(),
+                                                "value\n".as_bytes(),
+                                                "body".as_bytes()]));
+        }
+        {
+            let mut read_stream = File::open_mode(filename, Open, Read);
+            let mut a = [0u8, .. 8];
+            let mut b = [];
+            let mut c = [0u8, .. 32];
+            let n = check!(read_stream.read_vectored([a.as_mut_slice(),
+                                                      b.as_mut_slice(),
+                                                      c.as_mut_slice()]));
+            let mut read = Vec::from_slice(a.as_slice());
+            read.push_all(c.as_slice());
+            assert!(n > 0);
+            assert!(read.slice_to(n) == message.as_bytes().slice_to(n));
+        }
+        assert_eq!(check!(File::open(filename).read_to_end()).as_slice(),
+                   message.as_bytes());
+        check!(unlink(filename));
+    })
+
+    iotest!(fn file_test_locking() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let mut a = check!(File::create(&path));
+        let mut b = check!(File::open(&path));
+        {
+            let mut lock = check!(a.lock_exclusive());
+            assert!(check!(b.try_lock_shared()).is_none());
+            assert!(check!(b.try_lock_exclusive()).is_none());
+            check!(lock.write(bytes!("locked")));
+        }
+        {
+            let _lock = check!(a.lock_shared());
+            assert!(check!(b.try_lock_shared()).is_some());
+            assert!(check!(b.try_lock_exclusive()).is_none());
+        }
+        {
+            let lock = check!(b.try_lock_exclusive());
+            assert!(lock.is_some());
+            assert!(check!(a.try_lock_shared()).is_none());
+        }
+        assert!(check!(a.try_lock_exclusive()).is_some());
+        check!(a.unlock());
+        assert_eq!(check!(File::open(&path).read_to_end()),
+                   Vec::from_slice(bytes!("locked")));
+    })
+
+    iotest!(fn canonicalize_resolves_links() {
+        let tmpdir = tmpdir();
+        let tmp = check!(canonicalize(tmpdir.path()));
+        assert!(tmp.is_absolute());
+        assert_eq!(check!(canonicalize(&tmp)), tmp);
+
+        let file = tmp.join("file");
+        let dir = tmp.join("dir");
+        check!(File::create(&file));
+        check!(mkdir(&dir, io::UserRWX));
+        assert_eq!(check!(canonicalize(&dir.join("..").join("file"))), file);
+        assert!(canonicalize(&tmp.join("missing")).is_err());
+
+        if cfg!(windows) { return }
+        check!(symlink(&file, &dir.join("link")));
+        check!(symlink(&Path::new("dir"), &tmp.join("dirlink")));
+        check!(symlink(&Path::new("../file"), &dir.join("rel")));
+        assert_eq!(check!(canonicalize(&dir.join("link"))), file);
+        assert_eq!(check!(canonicalize(&tmp.join("dirlink"))), dir);
+        assert_eq!(check!(canonicalize(&tmp.join("dirlink").join("rel"))), file);
+
+        check!(symlink(&Path::new("loop"), &tmp.join("loop")));
+        assert!(canonicalize(&tmp.join("loop")).is_err());
+    })
+
+    iotest!(fn canonicalize_resolves_nested_links() {
+        if cfg!(windows) { return }
+        let tmpdir = tmpdir();
+        let tmp = check!(canonicalize(tmpdir.path()));
+        let sub = tmp.join("dir").join("sub");
+        let file = sub.join("file");
+        check!(mkdir_recursive(&sub, io::UserRWX));
+        check!(File::create(&file));
+        check!(symlink(&Path::new("dir"), &tmp.join("dirlink")));
+
+        // Symlinks in the middle of a link's target are resolved as well
+        check!(symlink(&Path::new("dirlink/sub"), &tmp.join("sublink")));
+        assert_eq!(check!(canonicalize(&tmp.join("sublink"))), sub);
+        assert_eq!(check!(canonicalize(&tmp.join("sublink").join("file"))), file);
+
+        // And so are chains of links through other links
+        check!(symlink(&Path::new("sublink/file"), &tmp.join("chain")));
+        check!(symlink(&tmp.join("chain"), &tmp.join("dirlink").join("abs")));
+        assert_eq!(check!(canonicalize(&tmp.join("chain"))), file);
+        assert_eq!(check!(canonicalize(&tmp.join("dirlink").join("abs"))), file);
+
+        // A relative target is relative to the directory the link is really in
+        check!(symlink(&Path::new("../../dirlink"), &sub.join("up")));
+        assert_eq!(check!(canonicalize(&tmp.join("sublink").join("up"))),
+                   tmp.join("dir"));
+    })
+
+    iotest!(fn canonicalize_windows_forms() {
+        if !cfg!(windows) { return }
+        let tmpdir = tmpdir();
+        let tmp = check!(canonicalize(tmpdir.path()));
+        let repr = tmp.as_str().unwrap().to_string();
+        assert!(!repr.as_slice().starts_with(r"\\?\"));
+
+        // The verbatim form of a path names the same file
+        let verbatim = Path::new(format!(r"\\?\{}", repr));
+        assert_eq!(check!(canonicalize(&verbatim)), tmp);
+
+        // Reaching the file through the administrative share of its drive
+        // gives a UNC path, if the share is available at all
+        let unc = Path::new(format!(r"\\localhost\{}$\{}",
+                                    repr.as_slice().slice_to(1),
+                                    repr.as_slice().slice_from(3)));
+        if stat(&unc).is_err() { return }
+        let canonical = check!(canonicalize(&unc));
+        assert!(canonical.as_str().unwrap().starts_with(r"\\localhost\"));
+        assert!(!canonical.as_str().unwrap().starts_with(r"\\?\"));
+    })
+
     iotest!(fn invalid_path_raises() {
         let tmpdir = tmpdir();
         let filename = &tmpdir.join("file_that_does_not_exist.txt");
@@ -1032,6 +1519,67 @@ mod test {
         assert!(canary.exists());
     })
 
+    iotest!(fn recursive_copy() {
+        let tmpdir = tmpdir();
+        let src = tmpdir.join("src");
+        let sub = src.join("sub");
+        check!(mkdir_recursive(&sub.join("empty"), io::UserRWX));
+        check!(File::create(&src.join("a.txt")).write(bytes!("a")));
+        check!(File::create(&sub.join("b.txt")).write(bytes!("b")));
+        check!(chmod(&sub.join("b.txt"), io::UserRWX));
+
+        let dst = tmpdir.join("dst");
+        check!(copy_recursive(&src, &dst, SkipSymlinks));
+        assert!(dst.join("sub").join("empty").is_dir());
+        assert_eq!(check!(File::open(&dst.join("a.txt")).read_to_end()),
+                   Vec::from_slice(bytes!("a")));
+        assert_eq!(check!(File::open(&dst.join("sub").join("b.txt"))
+                                .read_to_end()),
+                   Vec::from_slice(bytes!("b")));
+        assert_eq!(check!(dst.join("sub").join("b.txt").stat()).perm,
+                   check!(sub.join("b.txt").stat()).perm);
+
+        // the destination must not already exist
+        assert!(copy_recursive(&src, &dst, SkipSymlinks).is_err());
+        // and the source must be a directory
+        assert!(copy_recursive(&src.join("a.txt"), &tmpdir.join("f"),
+                               SkipSymlinks).is_err());
+        // nor can the copy go inside of the source
+        match copy_recursive(&src, &sub.join("copy"), SkipSymlinks) {
+            Err(e) => assert_eq!(e.kind, io::InvalidInput),
+            Ok(()) => fail!("copied a directory into itself"),
+        }
+        assert!(!sub.join("copy").exists());
+    })
+
+    #[cfg(not(windows))]
+    iotest!(fn recursive_copy_symlinks() {
+        let tmpdir = tmpdir();
+        let src = tmpdir.join("src");
+        let outside = tmpdir.join("outside");
+        check!(mkdir(&src, io::UserRWX));
+        check!(mkdir(&outside, io::UserRWX));
+        check!(File::create(&outside.join("c.txt")).write(bytes!("c")));
+        check!(symlink(&outside, &src.join("link")));
+
+        let skipped = tmpdir.join("skipped");
+        check!(copy_recursive(&src, &skipped, SkipSymlinks));
+        assert!(!skipped.join("link").exists());
+
+        let copied = tmpdir.join("copied");
+        check!(copy_recursive(&src, &copied, CopySymlinks));
+        assert_eq!(check!(copied.join("link").lstat()).kind, io::TypeSymlink);
+        assert!(check!(readlink(&copied.join("link"))) == outside);
+
+        let followed = tmpdir.join("followed");
+        check!(copy_recursive(&src, &followed, FollowSymlinks));
+        assert_eq!(check!(followed.join("link").lstat()).kind,
+                   io::TypeDirectory);
+        assert_eq!(check!(File::open(&followed.join("link").join("c.txt"))
+                                .read_to_end()),
+                   Vec::from_slice(bytes!("c")));
+    })
+
     iotest!(fn unicode_path_is_dir() {
         assert!(Path::new(".").is_dir());
         assert!(!Path::new("test/stdtest/fs.rs").is_dir());
@@ -1291,6 +1839,13 @@ mod test {
         assert!(tmpdir.join("g").exists());
         check!(File::open_mode(&tmpdir.join("g"), io::Truncate, io::ReadWrite));
 
+        check!(File::open_mode(&tmpdir.join("i"), io::CreateNew, io::Write));
+        assert!(tmpdir.join("i").exists());
+        match File::open_mode(&tmpdir.join("i"), io::CreateNew, io::Write) {
+            Ok(..) => fail!("created a file which already exists"),
+            Err(e) => assert_eq!(e.kind, io::PathAlreadyExists),
+        }
+
         check!(File::create(&tmpdir.join("h")).write("foo".as_bytes()));
         check!(File::open_mode(&tmpdir.join("h"), io::Open, io::Read));
         {
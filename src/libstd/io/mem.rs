@@ -75,7 +75,8 @@ impl MemWriter {
     /// because it could corrupt the state of this `MemWriter`.
     pub fn get_ref<'a>(&'a self) -> &'a [u8] { self.buf.as_slice() }
 
-    /// Unwraps this `MemWriter`, returning the underlying buffer
+    /// Unwraps this `MemWriter`, returning the underlying buffer. The buffer
+    /// is moved out rather than copied.
     pub fn unwrap(self) -> Vec<u8> { self.buf }
 }
 
@@ -333,6 +334,101 @@ impl<'a> Buffer for BufReader<'a> {
     fn consume(&mut self, amt: uint) { self.pos += amt; }
 }
 
+/// Reads from and writes to a fixed-size, caller-provided byte slice. This
+/// allows serializing into a buffer on the stack and reading the result back
+/// without copying it into a separate vector.
+///
+/// # Example
+///
+/// ```rust
+/// # #![allow(unused_must_use)]
+/// use std::io::{Cursor, SeekSet};
+///
+/// let mut buf = [0, ..4];
+/// let mut c = Cursor::new(buf);
+/// c.write([0, 1, 2]);
+/// c.seek(1, SeekSet);
+/// assert_eq!(c.read_to_end().unwrap(), vec!(1, 2, 0));
+/// ```
+pub struct Cursor<'a> {
+    buf: &'a mut [u8],
+    pos: uint
+}
+
+impl<'a> Cursor<'a> {
+    /// Creates a new `Cursor` over the specified buffer. The cursor initially
+    /// starts at position 0.
+    pub fn new<'a>(buf: &'a mut [u8]) -> Cursor<'a> {
+        Cursor {
+            buf: buf,
+            pos: 0
+        }
+    }
+
+    /// Acquires an immutable reference to the whole underlying buffer,
+    /// regardless of the current position.
+    pub fn get_ref<'b>(&'b self) -> &'b [u8] { self.buf.as_slice() }
+
+    /// Tests whether this cursor is positioned at or past the end of its
+    /// buffer.
+    ///
+    /// If `true`, then this will no longer return bytes from `read`.
+    pub fn eof(&self) -> bool { self.pos >= self.buf.len() }
+}
+
+impl<'a> Reader for Cursor<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> {
+        if self.eof() { return Err(io::standard_error(io::EndOfFile)) }
+
+        let write_len = min(buf.len(), self.buf.len() - self.pos);
+        {
+            let input = self.buf.slice(self.pos, self.pos + write_len);
+            let output = buf.mut_slice(0, write_len);
+            slice::bytes::copy_memory(output, input);
+        }
+        self.pos += write_len;
+        Ok(write_len)
+    }
+}
+
+impl<'a> Writer for Cursor<'a> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<()> {
+        // return an error if the entire write does not fit in the buffer
+        let max_size = self.buf.len();
+        if self.pos >= max_size || (self.pos + buf.len()) > max_size {
+            return Err(IoError {
+                kind: io::OtherIoError,
+                desc: "Trying to write past end of buffer",
+                detail: None
+            })
+        }
+
+        slice::bytes::copy_memory(self.buf.mut_slice_from(self.pos), buf);
+        self.pos += buf.len();
+        Ok(())
+    }
+}
+
+impl<'a> Seek for Cursor<'a> {
+    fn tell(&self) -> IoResult<u64> { Ok(self.pos as u64) }
+    fn seek(&mut self, pos: i64, style: SeekStyle) -> IoResult<()> {
+        let new = try!(combine(style, self.pos, self.buf.len(), pos));
+        self.pos = new as uint;
+        Ok(())
+    }
+}
+
+impl<'a> Buffer for Cursor<'a> {
+    fn fill_buf<'b>(&'b mut self) -> IoResult<&'b [u8]> {
+        if self.pos < self.buf.len() {
+            Ok(self.buf.slice_from(self.pos))
+        } else {
+            Err(io::standard_error(io::EndOfFile))
+        }
+    }
+    fn consume(&mut self, amt: uint) { self.pos += amt; }
+}
+
 #[cfg(test)]
 mod test {
     use prelude::*;
@@ -425,6 +521,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_cursor() {
+        let mut buf = [0 as u8, ..8];
+        {
+            let mut c = Cursor::new(buf);
+            c.write([0, 1, 2, 3]).unwrap();
+            assert_eq!(c.tell(), Ok(4));
+            c.seek(-2, SeekCur).unwrap();
+            let mut out = [0, ..4];
+            assert_eq!(c.read(out), Ok(4));
+            assert_eq!(out.as_slice(), &[2, 3, 0, 0]);
+            assert!(c.eof());
+            assert!(c.read(out).is_err());
+            assert!(c.write([4]).is_err());
+
+            c.seek(1, SeekSet).unwrap();
+            assert_eq!(c.read_until(3).unwrap(), vec!(1, 2, 3));
+            c.seek(-1, SeekEnd).unwrap();
+            c.write([7]).unwrap();
+            assert_eq!(c.get_ref(), &[0, 1, 2, 3, 0, 0, 0, 7]);
+        }
+        assert_eq!(buf.as_slice(), &[0, 1, 2, 3, 0, 0, 0, 7]);
+    }
+
     #[test]
     fn test_mem_reader() {
         let mut reader = MemReader::new(vec!(0, 1, 2, 3, 4, 5, 6, 7));
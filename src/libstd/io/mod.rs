@@ -214,9 +214,11 @@ responding to errors that may occur while attempting to read the numbers.
 #![deny(unused_must_use)]
 
 use char::Char;
+use cmp;
 use container::Container;
 use fmt;
 use int;
+use i64;
 use iter::Iterator;
 use libc;
 use mem::transmute;
@@ -247,9 +249,9 @@ pub use self::net::tcp::TcpStream;
 pub use self::net::udp::UdpStream;
 pub use self::pipe::PipeStream;
 pub use self::process::{Process, Command};
-pub use self::tempfile::TempDir;
+pub use self::tempfile::{TempDir, TempFile};
 
-pub use self::mem::{MemReader, BufReader, MemWriter, BufWriter};
+pub use self::mem::{MemReader, BufReader, MemWriter, BufWriter, Cursor};
 pub use self::buffered::{BufferedReader, BufferedWriter, BufferedStream,
                          LineBufferedWriter};
 pub use self::comm_adapters::{ChanReader, ChanWriter};
@@ -266,6 +268,7 @@ pub mod extensions;
 pub mod fs;
 pub mod net;
 pub mod pipe;
+#[cfg(unix)] pub mod poll;
 pub mod process;
 pub mod signal;
 pub mod stdio;
@@ -310,6 +313,8 @@ impl IoError {
                 libc::ERROR_NO_DATA => (BrokenPipe, "the pipe is being closed"),
                 libc::ERROR_FILE_NOT_FOUND => (FileNotFound, "file not found"),
                 libc::ERROR_INVALID_NAME => (InvalidInput, "invalid file name"),
+                libc::ERROR_FILE_EXISTS | libc::ERROR_ALREADY_EXISTS =>
+                    (PathAlreadyExists, "file exists"),
                 libc::WSAECONNREFUSED => (ConnectionRefused, "connection refused"),
                 libc::WSAECONNRESET => (ConnectionReset, "connection reset"),
                 libc::WSAEACCES => (PermissionDenied, "permission denied"),
@@ -351,6 +356,7 @@ impl IoError {
                 libc::EADDRINUSE => (ConnectionRefused, "address in use"),
                 libc::ENOENT => (FileNotFound, "no such file or directory"),
                 libc::EISDIR => (InvalidInput, "illegal operation on a directory"),
+                libc::EEXIST => (PathAlreadyExists, "file exists"),
 
                 // These two constants can have the same value on some systems, but
                 // different values on others, so we can't use a match clause
@@ -480,6 +486,21 @@ pub trait Reader {
     /// not to return 0 if you can avoid it.
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint>;
 
+    /// Read bytes into the buffers of `bufs`, filling each one in turn before
+    /// moving on to the next. Returns the total number of bytes read, which
+    /// (as with `read`) may be less than the number requested. Returns `Err`
+    /// on EOF.
+    ///
+    /// The default implementation only reads into the first non-empty buffer.
+    /// Readers which are backed by a file descriptor can instead fill several
+    /// buffers at once with a single `readv` system call.
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        match bufs.mut_iter().find(|buf| !buf.is_empty()) {
+            Some(buf) => self.read(&mut **buf),
+            None => Ok(0),
+        }
+    }
+
     // Convenient helper methods based on the above methods
 
     /// Reads at least `min` bytes and places them in `buf`.
@@ -873,10 +894,16 @@ pub trait Reader {
 
 impl Reader for Box<Reader> {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> { self.read(buf) }
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        self.read_vectored(bufs)
+    }
 }
 
 impl<'a> Reader for &'a mut Reader {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> { self.read(buf) }
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        self.read_vectored(bufs)
+    }
 }
 
 /// Returns a slice of `v` between `start` and `end`.
@@ -933,6 +960,9 @@ pub struct RefReader<'a, R> {
 
 impl<'a, R: Reader> Reader for RefReader<'a, R> {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> { self.inner.read(buf) }
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        self.inner.read_vectored(bufs)
+    }
 }
 
 impl<'a, R: Buffer> Buffer for RefReader<'a, R> {
@@ -966,6 +996,24 @@ pub trait Writer {
     /// data (if any) was actually written.
     fn write(&mut self, buf: &[u8]) -> IoResult<()>;
 
+    /// Write the entirety of each of the buffers of `bufs`, in order.
+    ///
+    /// The default implementation writes each buffer with a separate call to
+    /// `write`. Writers which are backed by a file descriptor can instead
+    /// gather all of the buffers with `writev`, which avoids both copying them
+    /// into one buffer and making a system call per buffer.
+    ///
+    /// # Errors
+    ///
+    /// As with `write`, it is unknown how much data (if any) was written if an
+    /// error is returned.
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        for buf in bufs.iter() {
+            try!(self.write(*buf));
+        }
+        Ok(())
+    }
+
     /// Flush this output stream, ensuring that all intermediately buffered
     /// contents reach their destination.
     ///
@@ -1178,11 +1226,17 @@ pub trait Writer {
 
 impl Writer for Box<Writer> {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.write_vectored(bufs)
+    }
     fn flush(&mut self) -> IoResult<()> { self.flush() }
 }
 
 impl<'a> Writer for &'a mut Writer {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.write_vectored(bufs)
+    }
     fn flush(&mut self) -> IoResult<()> { self.flush() }
 }
 
@@ -1217,6 +1271,9 @@ pub struct RefWriter<'a, W> {
 
 impl<'a, W: Writer> Writer for RefWriter<'a, W> {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.inner.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> IoResult<()> { self.inner.flush() }
 }
 
@@ -1365,7 +1422,25 @@ pub trait Buffer: Reader {
     /// is the reason that the byte buffer returned may not always contain the
     /// delimiter.
     fn read_until(&mut self, byte: u8) -> IoResult<Vec<u8>> {
+        self.read_until_limit(byte, uint::MAX)
+    }
+
+    /// Reads a sequence of bytes leading up to a specified delimiter, as with
+    /// `read_until`, but reads no more than `limit` bytes.
+    ///
+    /// If `limit` bytes have been read and the delimiter has not yet been
+    /// encountered, reading ceases and those bytes are returned, leaving the
+    /// remainder of the stream unread. The returned buffer ends with the
+    /// delimiter only if the delimiter was actually found. This makes it
+    /// possible to read delimited data from untrusted sources without
+    /// allocating an unbounded amount of memory.
+    ///
+    /// # Error
+    ///
+    /// Errors are reported in the same way as for `read_until`.
+    fn read_until_limit(&mut self, byte: u8, limit: uint) -> IoResult<Vec<u8>> {
         let mut res = Vec::new();
+        if limit == 0 { return Ok(res) }
 
         let mut used;
         loop {
@@ -1378,6 +1453,8 @@ pub trait Buffer: Reader {
                     }
                     Err(e) => return Err(e)
                 };
+                let left = cmp::min(available.len(), limit - res.len());
+                let available = available.slice_to(left);
                 match available.iter().position(|&b| b == byte) {
                     Some(i) => {
                         res.push_all(available.slice_to(i + 1));
@@ -1387,6 +1464,7 @@ pub trait Buffer: Reader {
                     None => {
                         res.push_all(available);
                         used = available.len();
+                        if res.len() == limit { break }
                     }
                 }
             }
@@ -1448,6 +1526,20 @@ pub enum SeekStyle {
     SeekCur,
 }
 
+/// A position to seek to, for use with `Seek::seek_from`.
+///
+/// Unlike a `SeekStyle` paired with an offset, this can't express seeking to a
+/// negative absolute position.
+#[deriving(Eq, Clone, Show)]
+pub enum SeekFrom {
+    /// Seek to the given number of bytes from the beginning of the stream
+    Start(u64),
+    /// Seek to the given offset from the end of the stream
+    End(i64),
+    /// Seek to the given offset from the current position
+    Current(i64),
+}
+
 /// An object implementing `Seek` internally has some form of cursor which can
 /// be moved within a stream of bytes. The stream typically has a fixed size,
 /// allowing seeking relative to either end.
@@ -1467,6 +1559,39 @@ pub trait Seek {
     ///   stream, but the next write may cause the previous data to be filled in
     ///   with a bit pattern.
     fn seek(&mut self, pos: i64, style: SeekStyle) -> IoResult<()>;
+
+    /// Seek to a position in the stream, returning the new position measured
+    /// from the beginning of the stream.
+    ///
+    /// # Errors
+    ///
+    /// The same as `seek`, and additionally an `InvalidInput` error for a
+    /// `Start` position which can't be represented as an `i64`.
+    fn seek_from(&mut self, pos: SeekFrom) -> IoResult<u64> {
+        let (pos, style) = match pos {
+            Start(n) if n > i64::MAX as u64 => {
+                return Err(IoError {
+                    kind: InvalidInput,
+                    desc: "seek position is too large",
+                    detail: None,
+                })
+            }
+            Start(n) => (n as i64, SeekSet),
+            End(n) => (n, SeekEnd),
+            Current(n) => (n, SeekCur),
+        };
+        try!(self.seek(pos, style));
+        self.tell()
+    }
+
+    /// Return the current position in the stream, measured from the beginning
+    /// of the stream.
+    ///
+    /// This is the same as `tell`, and for buffered streams it accounts for the
+    /// data still held in the buffer.
+    fn stream_position(&self) -> IoResult<u64> {
+        self.tell()
+    }
 }
 
 /// A listener is a value that can consume itself to start listening for
@@ -1568,6 +1693,9 @@ pub enum FileMode {
     Append,
     /// Opens a file, truncating it if it already exists.
     Truncate,
+    /// Creates a new file, failing if it already exists. The check for the
+    /// existence of the file and its creation are a single atomic operation.
+    CreateNew,
 }
 
 /// Access permissions with which the file should be opened. `File`s
@@ -24,9 +24,11 @@ use io::IoResult;
 use io::net::ip::{SocketAddr, IpAddr};
 use option::{Option, Some, None};
 use rt::rtio::{IoFactory, LocalIo};
+use string::String;
 use vec::Vec;
 
 /// Hints to the types of sockets that are desired when looking up hosts
+#[deriving(Eq, Clone, Show)]
 pub enum SocketType {
     Stream, Datagram, Raw
 }
@@ -35,6 +37,7 @@ pub enum SocketType {
 /// to manipulate how a query is performed.
 ///
 /// The meaning of each of these flags can be found with `man -s 3 getaddrinfo`
+#[deriving(Eq, Clone, Show)]
 pub enum Flag {
     AddrConfig,
     All,
@@ -47,6 +50,7 @@ pub enum Flag {
 
 /// A transport protocol associated with either a hint or a return value of
 /// `lookup`
+#[deriving(Eq, Clone, Show)]
 pub enum Protocol {
     TCP, UDP
 }
@@ -56,6 +60,7 @@ pub enum Protocol {
 ///
 /// For details on these fields, see their corresponding definitions via
 /// `man -s 3 getaddrinfo`
+#[deriving(Eq, Clone, Show)]
 pub struct Hint {
     pub family: uint,
     pub socktype: Option<SocketType>,
@@ -63,6 +68,7 @@ pub struct Hint {
     pub flags: uint,
 }
 
+#[deriving(Eq, Clone, Show)]
 pub struct Info {
     pub address: SocketAddr,
     pub family: uint,
@@ -83,17 +89,41 @@ pub fn get_host_addresses(host: &str) -> IoResult<Vec<IpAddr>> {
 /// # Arguments
 ///
 /// * hostname - an optional hostname to lookup against
-/// * servname - an optional service name, listed in the system services
+/// * servname - an optional service name or port number, either of which is
+///              resolved into the port of the returned addresses
 /// * hint - see the hint structure, and "man -s 3 getaddrinfo", for how this
 ///          controls lookup
 ///
-/// FIXME: this is not public because the `Hint` structure is not ready for public
-///      consumption just yet.
-fn lookup(hostname: Option<&str>, servname: Option<&str>, hint: Option<Hint>)
-          -> IoResult<Vec<Info>> {
+/// At least one of `hostname` and `servname` must be given.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::io::net::addrinfo::{lookup, Hint, Stream, TCP};
+/// use std::io::net::ip::Ipv6Addr;
+///
+/// let hint = Hint { family: 0, socktype: Some(Stream), protocol: Some(TCP),
+///                   flags: 0 };
+/// let addrs = lookup(Some("example.com"), Some("http"), Some(hint)).unwrap();
+/// // prefer IPv6 addresses, if there are any
+/// let (v6, v4) = addrs.partition(|info| {
+///     match info.address.ip { Ipv6Addr(..) => true, _ => false }
+/// });
+/// ```
+pub fn lookup(hostname: Option<&str>, servname: Option<&str>, hint: Option<Hint>)
+              -> IoResult<Vec<Info>> {
     LocalIo::maybe_raise(|io| io.get_host_addresses(hostname, servname, hint))
 }
 
+/// Reverse name resolution. Performs a synchronous call to getnameinfo to find
+/// the hostname of `addr`.
+///
+/// If no name is registered for the address, then the numeric form of the
+/// address is returned instead.
+pub fn reverse_lookup(addr: IpAddr) -> IoResult<String> {
+    LocalIo::maybe_raise(|io| io.get_address_name(addr))
+}
+
 // Ignored on android since we cannot give tcp/ip
 // permission without help of apk
 #[cfg(test, not(target_os = "android"))]
@@ -108,6 +138,26 @@ mod test {
         assert!(found_local);
     })
 
+    iotest!(fn lookup_service() {
+        let hint = Hint { family: 0, socktype: Some(Stream), protocol: None,
+                          flags: 0 };
+        let infos = lookup(Some("localhost"), Some("80"), Some(hint)).unwrap();
+        assert!(infos.len() > 0);
+        for info in infos.iter() {
+            assert_eq!(info.address.port, 80);
+            assert_eq!(info.socktype, Some(Stream));
+        }
+    })
+
+    iotest!(fn reverse_lookup_localhost() {
+        // Not every system has a name registered for the loopback address, but
+        // the numeric form is used when it doesn't.
+        match reverse_lookup(Ipv4Addr(127, 0, 0, 1)) {
+            Ok(name) => assert!(name.len() > 0),
+            Err(e) => assert_eq!(e.kind, IoUnavailable),
+        }
+    })
+
     iotest!(fn issue_10663() {
         // Something should happen here, but this certainly shouldn't cause
         // everything to die. The actual outcome we don't care too much about.
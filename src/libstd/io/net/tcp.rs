@@ -26,7 +26,9 @@ use io::net::addrinfo::get_host_addresses;
 use io::net::ip::SocketAddr;
 use io::{IoError, ConnectionFailed, InvalidInput};
 use io::{Reader, Writer, Listener, Acceptor};
+#[cfg(unix)] use io::poll::Pollable;
 use from_str::FromStr;
+#[cfg(unix)] use libc;
 use kinds::Send;
 use option::{None, Some, Option};
 use owned::Box;
@@ -139,6 +141,13 @@ impl TcpStream {
         }
     }
 
+    /// Sets the time-to-live (or, for IPv6, the hop limit) of packets sent
+    /// on this connection.
+    #[experimental]
+    pub fn set_ttl(&mut self, ttl: int) -> IoResult<()> {
+        self.obj.time_to_live(ttl)
+    }
+
     /// Closes the reading half of this connection.
     ///
     /// This method will close the reading portion of this connection, causing
@@ -262,10 +271,21 @@ impl Clone for TcpStream {
 
 impl Reader for TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> { self.obj.read(buf) }
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        self.obj.readv(bufs)
+    }
 }
 
 impl Writer for TcpStream {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.obj.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.obj.writev(bufs)
+    }
+}
+
+#[cfg(unix)]
+impl Pollable for TcpStream {
+    fn poll_fd(&self) -> Option<libc::c_int> { self.obj.poll_fd() }
 }
 
 /// A structure representing a socket server. This listener is used to create a
@@ -317,11 +337,21 @@ impl TcpListener {
     /// to this listener. The port allocated can be queried via the
     /// `socket_name` function.
     pub fn bind(addr: &str, port: u16) -> IoResult<TcpListener> {
+        TcpListener::bind_with_options(addr, port, BindOptions::new())
+    }
+
+    /// Creates a new `TcpListener` like `bind`, with the given options applied
+    /// to the socket before it is bound.
+    #[experimental = "the set of options is likely to grow"]
+    pub fn bind_with_options(addr: &str, port: u16,
+                             opts: BindOptions) -> IoResult<TcpListener> {
         match FromStr::from_str(addr) {
             Some(ip) => {
                 let socket_addr = SocketAddr{ip: ip, port: port};
                 LocalIo::maybe_raise(|io| {
-                    io.tcp_bind(socket_addr).map(|l| TcpListener { obj: l })
+                    io.tcp_bind(socket_addr, opts.reuse_addr).map(|l| {
+                        TcpListener { obj: l }
+                    })
                 })
             }
             None => {
@@ -340,6 +370,26 @@ impl TcpListener {
     }
 }
 
+/// Options for binding a `TcpListener`, used by
+/// `TcpListener::bind_with_options`.
+#[experimental = "the set of options is likely to grow"]
+#[deriving(Clone, Eq, Show)]
+pub struct BindOptions {
+    /// Whether to set `SO_REUSEADDR` on the socket, which allows a server to
+    /// rebind its address while connections from a previous instance of it
+    /// are still lingering. This is the default on unix. It is not the default
+    /// on windows, where the option also allows binding an address which
+    /// another socket is actively listening on.
+    pub reuse_addr: bool,
+}
+
+impl BindOptions {
+    /// Returns the options used by `TcpListener::bind`.
+    pub fn new() -> BindOptions {
+        BindOptions { reuse_addr: cfg!(unix) }
+    }
+}
+
 impl Listener<TcpStream, TcpAcceptor> for TcpListener {
     fn listen(self) -> IoResult<TcpAcceptor> {
         self.obj.listen().map(|acceptor| TcpAcceptor { obj: acceptor })
@@ -407,6 +457,11 @@ impl Acceptor<TcpStream> for TcpAcceptor {
     }
 }
 
+#[cfg(unix)]
+impl Pollable for TcpAcceptor {
+    fn poll_fd(&self) -> Option<libc::c_int> { self.obj.poll_fd() }
+}
+
 #[cfg(test)]
 #[allow(experimental)]
 mod test {
@@ -516,6 +571,32 @@ mod test {
         assert!(buf[0] == 99);
     })
 
+    iotest!(fn vectored_io() {
+        let addr = next_test_ip4();
+        let ip_str = addr.ip.to_str();
+        let port = addr.port;
+        let mut acceptor = TcpListener::bind(ip_str.as_slice(), port).listen();
+
+        spawn(proc() {
+            let mut stream = TcpStream::connect(ip_str.as_slice(), port);
+            let (a, b, c) = ([1u8, 2], [], [3u8]);
+            stream.write_vectored([a.as_slice(), b.as_slice(),
+                                   c.as_slice()]).unwrap();
+            let mut a = [0, 0];
+            let mut b = [0, 0];
+            let n = stream.read_vectored([a.as_mut_slice(),
+                                          b.as_mut_slice()]).unwrap();
+            assert!(n > 0);
+            assert_eq!(a[0], 4);
+        });
+
+        let mut stream = acceptor.accept();
+        let mut buf = [0, 0, 0];
+        assert_eq!(stream.read_at_least(3, buf), Ok(3));
+        assert!(buf.as_slice() == &[1, 2, 3]);
+        stream.write([4, 5, 6]).unwrap();
+    })
+
     iotest!(fn smoke_test_ip6() {
         let addr = next_test_ip6();
         let ip_str = addr.ip.to_str();
@@ -929,13 +1010,13 @@ mod test {
             tx.send(());
         });
 
-        rx.recv();
+        rx.recv_unwrap();
         let ip_str = addr.ip.to_str();
         let mut c = TcpStream::connect(ip_str.as_slice(), port).unwrap();
         let mut b = [0, ..10];
         assert_eq!(c.read(b), Ok(1));
         c.write([1]).unwrap();
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn double_bind() {
@@ -959,10 +1040,10 @@ mod test {
 
         spawn(proc() {
             let ip_str = addr.ip.to_str();
-            rx.recv();
+            rx.recv_unwrap();
             let _stream = TcpStream::connect(ip_str.as_slice(), port).unwrap();
             // Close
-            rx.recv();
+            rx.recv_unwrap();
         });
 
         {
@@ -1000,14 +1081,14 @@ mod test {
         let (tx2, rx2) = channel();
         spawn(proc() {
             let mut s2 = s2;
-            rx1.recv();
+            rx1.recv_unwrap();
             s2.write([1]).unwrap();
             tx2.send(());
         });
         tx1.send(());
         let mut buf = [0, 0];
         assert_eq!(s1.read(buf), Ok(1));
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn tcp_clone_two_read() {
@@ -1021,9 +1102,9 @@ mod test {
         spawn(proc() {
             let mut s = TcpStream::connect(ip_str.as_slice(), port);
             s.write([1]).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             s.write([2]).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
         });
 
         let mut s1 = acceptor.accept().unwrap();
@@ -1041,7 +1122,7 @@ mod test {
         s1.read(buf).unwrap();
         tx1.send(());
 
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn tcp_clone_two_write() {
@@ -1068,7 +1149,7 @@ mod test {
         });
         s1.write([2]).unwrap();
 
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn shutdown_smoke() {
@@ -1117,7 +1198,7 @@ mod test {
                 tx.send(TcpStream::connect(addr.ip.to_str().as_slice(),
                                            port).unwrap());
             });
-            let _l = rx.recv();
+            let _l = rx.recv_unwrap();
             for i in range(0, 1001) {
                 match a.accept() {
                     Ok(..) => break,
@@ -1200,7 +1281,7 @@ mod test {
         s.close_read().unwrap();
 
         // this test will never finish if the child doesn't wake up
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn readwrite_timeouts() {
@@ -1211,7 +1292,7 @@ mod test {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = TcpStream::connect(ip_str.as_slice(), port).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert!(s.write([0]).is_ok());
             let _ = rx.recv_opt();
         });
@@ -1245,7 +1326,7 @@ mod test {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = TcpStream::connect(ip_str.as_slice(), port).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             let mut amt = 0;
             while amt < 100 * 128 * 1024 {
                 match s.read([0, ..128 * 1024]) {
@@ -1275,7 +1356,7 @@ mod test {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = TcpStream::connect(ip_str.as_slice(), port).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert!(s.write([0]).is_ok());
             let _ = rx.recv_opt();
         });
@@ -1304,7 +1385,7 @@ mod test {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = TcpStream::connect(ip_str.as_slice(), port).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert_eq!(s.write([0]), Ok(()));
             let _ = rx.recv_opt();
         });
@@ -1322,6 +1403,45 @@ mod test {
         assert_eq!(s.read([0]).err().unwrap().kind, TimedOut);
         tx.send(());
 
-        rx2.recv();
+        rx2.recv_unwrap();
+    })
+
+    iotest!(fn bind_with_reuse_addr() {
+        let addr = next_test_ip4();
+        let ip_str = addr.ip.to_str();
+        let port = addr.port;
+        let opts = BindOptions { reuse_addr: true };
+        let mut acceptor = TcpListener::bind_with_options(ip_str.as_slice(),
+                                                          port, opts).listen();
+
+        let (tx, rx) = channel();
+        spawn(proc() {
+            let _stream = TcpStream::connect(ip_str.as_slice(), port).unwrap();
+            rx.recv_unwrap();
+        });
+        // closing the server side first leaves the connection lingering in
+        // TIME_WAIT on this address
+        drop(acceptor.accept().unwrap());
+        drop(acceptor);
+        tx.send(());
+
+        let ip_str = addr.ip.to_str();
+        let listener = TcpListener::bind_with_options(ip_str.as_slice(),
+                                                      port, opts);
+        assert!(listener.listen().is_ok());
+    } #[ignore(cfg(windows))])
+
+    iotest!(fn set_ttl() {
+        for &addr in [next_test_ip4(), next_test_ip6()].iter() {
+            let ip_str = addr.ip.to_str();
+            let port = addr.port;
+            let mut acceptor = TcpListener::bind(ip_str.as_slice(), port).listen();
+
+            spawn(proc() {
+                let _stream = TcpStream::connect(ip_str.as_slice(), port);
+            });
+            let mut stream = acceptor.accept().unwrap();
+            assert!(stream.set_ttl(16).is_ok());
+        }
     })
 }
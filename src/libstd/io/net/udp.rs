@@ -136,7 +136,7 @@ impl UdpSocket {
 
     /// Sets the broadcast flag on or off
     #[experimental]
-    pub fn set_broadast(&mut self, broadcast: bool) -> IoResult<()> {
+    pub fn set_broadcast(&mut self, broadcast: bool) -> IoResult<()> {
         if broadcast {
             self.obj.hear_broadcasts()
         } else {
@@ -144,6 +144,12 @@ impl UdpSocket {
         }
     }
 
+    /// Sets the broadcast flag on or off
+    #[deprecated = "renamed to `set_broadcast`"]
+    pub fn set_broadast(&mut self, broadcast: bool) -> IoResult<()> {
+        self.set_broadcast(broadcast)
+    }
+
     /// Sets the read/write timeout for this socket.
     ///
     /// For more information, see `TcpStream::set_timeout`
@@ -249,7 +255,7 @@ mod test {
         spawn(proc() {
             match UdpSocket::bind(client_ip) {
                 Ok(ref mut client) => {
-                    rx1.recv();
+                    rx1.recv_unwrap();
                     client.sendto([99], server_ip).unwrap()
                 }
                 Err(..) => fail!()
@@ -272,7 +278,7 @@ mod test {
             }
             Err(..) => fail!()
         }
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn socket_smoke_test_ip6() {
@@ -283,7 +289,7 @@ mod test {
         spawn(proc() {
             match UdpSocket::bind(client_ip) {
                 Ok(ref mut client) => {
-                    rx.recv();
+                    rx.recv_unwrap();
                     client.sendto([99], server_ip).unwrap()
                 }
                 Err(..) => fail!()
@@ -318,7 +324,7 @@ mod test {
                 Ok(client) => {
                     let client = box client;
                     let mut stream = client.connect(server_ip);
-                    rx1.recv();
+                    rx1.recv_unwrap();
                     stream.write([99]).unwrap();
                 }
                 Err(..) => fail!()
@@ -342,7 +348,7 @@ mod test {
             }
             Err(..) => fail!()
         }
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn stream_smoke_test_ip6() {
@@ -356,7 +362,7 @@ mod test {
                 Ok(client) => {
                     let client = box client;
                     let mut stream = client.connect(server_ip);
-                    rx1.recv();
+                    rx1.recv_unwrap();
                     stream.write([99]).unwrap();
                 }
                 Err(..) => fail!()
@@ -380,7 +386,7 @@ mod test {
             }
             Err(..) => fail!()
         }
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     pub fn socket_name(addr: SocketAddr) {
@@ -404,6 +410,26 @@ mod test {
         socket_name(next_test_ip6());
     })
 
+    iotest!(fn socket_options_ip4() {
+        let mut sock = UdpSocket::bind(next_test_ip4()).unwrap();
+        assert!(sock.set_broadcast(true).is_ok());
+        assert!(sock.set_broadcast(false).is_ok());
+        assert!(sock.set_multicast_loop(true).is_ok());
+        assert!(sock.set_multicast_loop(false).is_ok());
+        assert!(sock.set_multicast_ttl(4).is_ok());
+        assert!(sock.set_ttl(64).is_ok());
+    })
+
+    iotest!(fn socket_options_ip6() {
+        // broadcasts are an IPv4 notion, so only the options which have IPv6
+        // counterparts are set here
+        let mut sock = UdpSocket::bind(next_test_ip6()).unwrap();
+        assert!(sock.set_multicast_loop(true).is_ok());
+        assert!(sock.set_multicast_loop(false).is_ok());
+        assert!(sock.set_multicast_ttl(4).is_ok());
+        assert!(sock.set_ttl(64).is_ok());
+    })
+
     iotest!(fn udp_clone_smoke() {
         let addr1 = next_test_ip4();
         let addr2 = next_test_ip4();
@@ -424,14 +450,14 @@ mod test {
         let (tx2, rx2) = channel();
         spawn(proc() {
             let mut sock3 = sock3;
-            rx1.recv();
+            rx1.recv_unwrap();
             sock3.sendto([1], addr2).unwrap();
             tx2.send(());
         });
         tx1.send(());
         let mut buf = [0, 0];
         assert_eq!(sock1.recvfrom(buf), Ok((1, addr2)));
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn udp_clone_two_read() {
@@ -445,9 +471,9 @@ mod test {
         spawn(proc() {
             let mut sock2 = sock2;
             sock2.sendto([1], addr1).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             sock2.sendto([2], addr1).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
         });
 
         let sock3 = sock1.clone();
@@ -464,7 +490,7 @@ mod test {
         sock1.recvfrom(buf).unwrap();
         tx1.send(());
 
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn udp_clone_two_write() {
@@ -480,7 +506,7 @@ mod test {
             let mut sock2 = sock2;
             let mut buf = [0, 1];
 
-            rx.recv();
+            rx.recv_unwrap();
             match sock2.recvfrom(buf) {
                 Ok(..) => {}
                 Err(e) => fail!("failed receive: {}", e),
@@ -506,8 +532,8 @@ mod test {
         }
         drop(tx);
 
-        rx.recv();
-        serv_rx.recv();
+        rx.recv_unwrap();
+        serv_rx.recv_unwrap();
     })
 
     iotest!(fn recvfrom_timeout() {
@@ -521,7 +547,7 @@ mod test {
             let mut a = UdpSocket::bind(addr2).unwrap();
             assert_eq!(a.recvfrom([0]), Ok((1, addr1)));
             assert_eq!(a.sendto([0], addr1), Ok(()));
-            rx.recv();
+            rx.recv_unwrap();
             assert_eq!(a.sendto([0], addr1), Ok(()));
 
             tx2.send(());
@@ -543,7 +569,7 @@ mod test {
         assert_eq!(a2.recvfrom([0]), Ok((1, addr2)));
 
         // Make sure the child didn't die
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn sendto_timeout() {
@@ -20,6 +20,26 @@ These pipes are similar to TCP in the sense that you can have both a stream to a
 server and a server itself. The server provided accepts other `UnixStream`
 instances as clients.
 
+On windows, the path given to `UnixListener::bind` and `UnixStream::connect`
+is the name of the pipe, which must be of the form `\\.\pipe\<name>` (or
+`\\<server>\pipe\<name>` to connect to a pipe on another machine). This
+allows communicating with existing services which expose named pipes. The
+pipes are opened for overlapped I/O, so reads, writes and timeouts on the
+same stream from different tasks don't block one another, just as with UNIX
+domain sockets.
+
+On Unix, connectionless communication is also available through
+`UnixDatagram`, which sends and receives individual datagrams over a UNIX
+domain socket. Datagram sockets are currently only supported by the native
+runtime.
+
+When using the native runtime on Linux, a path beginning with `@` names a
+socket in the abstract namespace rather than a file in the filesystem. The `@`
+stands in for the leading null byte of the socket's address, so the path
+`@/tmp/.X11-unix/X0` corresponds to the address shown as such by `ss -x`.
+As with `ss -x`, any other null bytes in an abstract address received from
+the system, such as the source of a datagram, are also shown as `@`.
+
 */
 
 #![allow(missing_doc)]
@@ -29,10 +49,12 @@ use prelude::*;
 use c_str::ToCStr;
 use clone::Clone;
 use io::{Listener, Acceptor, Reader, Writer, IoResult};
+#[cfg(unix)] use io::poll::Pollable;
 use kinds::Send;
+#[cfg(unix)] use libc;
 use owned::Box;
 use rt::rtio::{IoFactory, LocalIo, RtioUnixListener};
-use rt::rtio::{RtioUnixAcceptor, RtioPipe};
+use rt::rtio::{RtioUnixAcceptor, RtioPipe, RtioUnixDatagram};
 
 /// A stream which communicates over a named pipe.
 pub struct UnixStream {
@@ -127,10 +149,21 @@ impl Clone for UnixStream {
 
 impl Reader for UnixStream {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> { self.obj.read(buf) }
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        self.obj.readv(bufs)
+    }
 }
 
 impl Writer for UnixStream {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.obj.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.obj.writev(bufs)
+    }
+}
+
+#[cfg(unix)]
+impl Pollable for UnixStream {
+    fn poll_fd(&self) -> Option<libc::c_int> { self.obj.poll_fd() }
 }
 
 /// A value that can listen for incoming named pipe connection requests.
@@ -206,6 +239,106 @@ impl Acceptor<UnixStream> for UnixAcceptor {
     }
 }
 
+#[cfg(unix)]
+impl Pollable for UnixAcceptor {
+    fn poll_fd(&self) -> Option<libc::c_int> { self.obj.poll_fd() }
+}
+
+/// A datagram socket which communicates over a UNIX domain socket.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![allow(unused_must_use)]
+/// use std::io::net::unix::UnixDatagram;
+///
+/// let mut socket = match UnixDatagram::bind(&Path::new("/tmp/my-socket")) {
+///     Ok(s) => s,
+///     Err(e) => fail!("couldn't bind socket: {}", e),
+/// };
+///
+/// let mut buf = [0, ..10];
+/// match socket.recvfrom(buf) {
+///     Ok((amt, Some(src))) => {
+///         // Send a reply to the socket we received data from
+///         socket.sendto(buf.slice_to(amt), &src);
+///     }
+///     Ok((_, None)) => println!("received a datagram from an unbound socket"),
+///     Err(e) => println!("couldn't receive a datagram: {}", e)
+/// }
+/// ```
+pub struct UnixDatagram {
+    obj: Box<RtioUnixDatagram:Send>,
+}
+
+impl UnixDatagram {
+    /// Creates a datagram socket bound to `path`, which other sockets may
+    /// then send datagrams to.
+    ///
+    /// The path is unlinked when the last handle to the socket is dropped.
+    pub fn bind<P: ToCStr>(path: &P) -> IoResult<UnixDatagram> {
+        LocalIo::maybe_raise(|io| {
+            io.unix_datagram(Some(&path.to_c_str()))
+              .map(|s| UnixDatagram { obj: s })
+        })
+    }
+
+    /// Creates a datagram socket which is not bound to any path.
+    ///
+    /// An unbound socket can send datagrams, but it can only receive replies
+    /// on systems that automatically bind it to an address (such as Linux).
+    pub fn unbound() -> IoResult<UnixDatagram> {
+        LocalIo::maybe_raise(|io| {
+            io.unix_datagram(None).map(|s| UnixDatagram { obj: s })
+        })
+    }
+
+    /// Receives data from the socket. On success, returns the number of bytes
+    /// read and the path of the socket which sent the data, if it has one.
+    pub fn recvfrom(&mut self, buf: &mut [u8])
+                    -> IoResult<(uint, Option<Path>)> {
+        self.obj.recvfrom(buf).map(|(n, src)| {
+            (n, src.map(|src| Path::new(src.as_bytes_no_nul())))
+        })
+    }
+
+    /// Sends data on the socket to the socket bound to `dst`. Returns nothing
+    /// on success.
+    pub fn sendto<P: ToCStr>(&mut self, buf: &[u8], dst: &P) -> IoResult<()> {
+        self.obj.sendto(buf, &dst.to_c_str())
+    }
+
+    /// Sets the read/write timeout for this socket.
+    ///
+    /// For more information, see `TcpStream::set_timeout`
+    #[experimental = "the timeout argument may change in type and value"]
+    pub fn set_timeout(&mut self, timeout_ms: Option<u64>) {
+        self.obj.set_timeout(timeout_ms)
+    }
+
+    /// Sets the read timeout for this socket.
+    ///
+    /// For more information, see `TcpStream::set_timeout`
+    #[experimental = "the timeout argument may change in type and value"]
+    pub fn set_read_timeout(&mut self, timeout_ms: Option<u64>) {
+        self.obj.set_read_timeout(timeout_ms)
+    }
+
+    /// Sets the write timeout for this socket.
+    ///
+    /// For more information, see `TcpStream::set_timeout`
+    #[experimental = "the timeout argument may change in type and value"]
+    pub fn set_write_timeout(&mut self, timeout_ms: Option<u64>) {
+        self.obj.set_write_timeout(timeout_ms)
+    }
+}
+
+impl Clone for UnixDatagram {
+    fn clone(&self) -> UnixDatagram {
+        UnixDatagram { obj: self.obj.clone() }
+    }
+}
+
 #[cfg(test)]
 #[allow(experimental)]
 mod tests {
@@ -358,7 +491,7 @@ mod tests {
         let (tx2, rx2) = channel();
         spawn(proc() {
             let mut s2 = s2;
-            rx1.recv();
+            rx1.recv_unwrap();
             debug!("writer writing");
             s2.write([1]).unwrap();
             debug!("writer done");
@@ -369,7 +502,7 @@ mod tests {
         debug!("reader reading");
         assert_eq!(s1.read(buf), Ok(1));
         debug!("reader done");
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn unix_clone_two_read() {
@@ -381,9 +514,9 @@ mod tests {
         spawn(proc() {
             let mut s = UnixStream::connect(&addr);
             s.write([1]).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             s.write([2]).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
         });
 
         let mut s1 = acceptor.accept().unwrap();
@@ -401,7 +534,7 @@ mod tests {
         s1.read(buf).unwrap();
         tx1.send(());
 
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn unix_clone_two_write() {
@@ -426,7 +559,7 @@ mod tests {
         });
         s1.write([2]).unwrap();
 
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn drop_removes_listener_path() {
@@ -464,7 +597,7 @@ mod tests {
         spawn(proc() {
             tx.send(UnixStream::connect(&addr2).unwrap());
         });
-        let l = rx.recv();
+        let l = rx.recv_unwrap();
         for i in range(0, 1001) {
             match a.accept() {
                 Ok(..) => break,
@@ -554,7 +687,7 @@ mod tests {
         s.close_read().unwrap();
 
         // this test will never finish if the child doesn't wake up
-        rx.recv();
+        rx.recv_unwrap();
     })
 
     iotest!(fn readwrite_timeouts() {
@@ -563,7 +696,7 @@ mod tests {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = UnixStream::connect(&addr).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert!(s.write([0]).is_ok());
             let _ = rx.recv_opt();
         });
@@ -600,7 +733,7 @@ mod tests {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = UnixStream::connect(&addr).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             let mut amt = 0;
             while amt < 100 * 128 * 1024 {
                 match s.read([0, ..128 * 1024]) {
@@ -628,7 +761,7 @@ mod tests {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = UnixStream::connect(&addr).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert!(s.write([0]).is_ok());
             let _ = rx.recv_opt();
         });
@@ -654,7 +787,7 @@ mod tests {
         let (tx, rx) = channel::<()>();
         spawn(proc() {
             let mut s = UnixStream::connect(&addr).unwrap();
-            rx.recv();
+            rx.recv_unwrap();
             assert!(s.write([0]).is_ok());
             let _ = rx.recv_opt();
         });
@@ -672,6 +805,6 @@ mod tests {
         assert_eq!(s.read([0]).err().unwrap().kind, TimedOut);
         tx.send(());
 
-        rx2.recv();
+        rx2.recv_unwrap();
     })
 }
@@ -17,6 +17,7 @@
 
 use prelude::*;
 use io::IoResult;
+#[cfg(unix)] use io::poll::Pollable;
 use libc;
 use owned::Box;
 use rt::rtio::{RtioPipe, LocalIo};
@@ -68,10 +69,21 @@ impl Clone for PipeStream {
 
 impl Reader for PipeStream {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> { self.obj.read(buf) }
+    fn read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        self.obj.readv(bufs)
+    }
 }
 
 impl Writer for PipeStream {
     fn write(&mut self, buf: &[u8]) -> IoResult<()> { self.obj.write(buf) }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        self.obj.writev(bufs)
+    }
+}
+
+#[cfg(unix)]
+impl Pollable for PipeStream {
+    fn poll_fd(&self) -> Option<libc::c_int> { self.obj.poll_fd() }
 }
 
 #[cfg(test)]
@@ -87,7 +99,7 @@ mod test {
         spawn(proc() {
             let mut out = out;
             out.write([10]).unwrap();
-            rx.recv(); // don't close the pipe until the other read has finished
+            rx.recv_unwrap(); // don't close the pipe until the other read has finished
         });
 
         let mut buf = [0, ..10];
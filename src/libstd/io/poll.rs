@@ -0,0 +1,254 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+
+Readiness polling over multiple I/O handles
+
+This module allows a single task to wait on a number of streams and acceptors
+at once, rather than dedicating a task to each of them. Handles are registered
+with a `Poll` along with the events they are interested in, and `wait` will
+block until at least one of them is ready.
+
+A `Poll` borrows the handles registered with it for as long as it lives, so a
+handle can't be closed behind its back. As reading from or accepting on a
+handle needs it mutably, a `Poll` is usually set up anew for each wait.
+
+Both runtimes can poll their handles. Under the green runtime a polled stream
+mustn't be read from or written to by another task (through a clone, say)
+while the wait is in progress, as the event loop only lets one party watch a
+descriptor at a time.
+
+Polling is only available on unix. There is no Windows backend yet, so this
+module doesn't exist there: sockets could be waited on with `WSAPoll`, but
+pipes only support overlapped I/O on Windows, so there is nothing to wait on
+for their readiness.
+
+# Example
+
+```rust,no_run
+# #![allow(unused_must_use)]
+use std::io::{TcpListener, Listener, Acceptor};
+use std::io::poll::{Poll, Readable};
+
+let mut acceptor = TcpListener::bind("127.0.0.1", 8080).listen().unwrap();
+let (token, events) = {
+    let mut poll = Poll::new();
+    let token = poll.register(&acceptor, Readable).unwrap();
+    (token, poll.wait(Some(1000)).unwrap())
+};
+
+for event in events.iter() {
+    if event.token == token && event.readable {
+        let stream = acceptor.accept();
+        // handle the new connection
+    }
+}
+```
+
+*/
+
+use container::Container;
+use io::{IoResult, IoError, IoUnavailable};
+use iter::Iterator;
+use libc::c_int;
+use option::{Option, Some, None};
+use result::{Ok, Err};
+use rt::rtio::{IoFactory, LocalIo, PollFd};
+use vec::Vec;
+
+/// An I/O handle which can be registered with a `Poll`.
+pub trait Pollable {
+    /// Returns the raw descriptor to wait on, or `None` if the runtime which
+    /// created this handle cannot poll it.
+    fn poll_fd(&self) -> Option<c_int>;
+}
+
+/// The events a registered handle is waited on for.
+#[deriving(Eq, Clone, Show)]
+pub enum Interest {
+    /// Wait for the handle to become readable (or, for an acceptor, to have
+    /// a pending connection).
+    Readable,
+    /// Wait for the handle to become writable.
+    Writable,
+    /// Wait for the handle to become either readable or writable.
+    ReadWrite,
+}
+
+/// Readiness of one registered handle, as returned by `Poll::wait`.
+#[deriving(Eq, Clone, Show)]
+pub struct Event {
+    /// The token returned when the handle was registered.
+    pub token: uint,
+    /// The handle can be read from without blocking. This is also set if the
+    /// handle has been closed or hit an error, in which case a read will
+    /// return the error.
+    pub readable: bool,
+    /// The handle can be written to without blocking.
+    pub writable: bool,
+}
+
+/// A set of handles which can be waited on together.
+///
+/// Each registered handle stays borrowed until the `Poll` is dropped, even
+/// once deregistered.
+pub struct Poll<'a> {
+    handles: Vec<&'a Pollable>,
+    fds: Vec<PollFd>,
+    tokens: Vec<uint>,
+    next_token: uint,
+}
+
+impl<'a> Poll<'a> {
+    /// Creates a new, empty set of handles.
+    pub fn new() -> Poll<'a> {
+        Poll {
+            handles: Vec::new(),
+            fds: Vec::new(),
+            tokens: Vec::new(),
+            next_token: 0,
+        }
+    }
+
+    /// Registers `handle` to be waited on for `interest`, returning a token
+    /// which identifies it in the events returned by `wait`.
+    ///
+    /// # Error
+    ///
+    /// Returns an `IoUnavailable` error if the handle cannot be polled by its
+    /// runtime.
+    pub fn register<P: Pollable>(&mut self, handle: &'a P,
+                                 interest: Interest) -> IoResult<uint> {
+        let fd = match handle.poll_fd() {
+            Some(fd) => fd,
+            None => return Err(unavailable()),
+        };
+        let (read, write) = match interest {
+            Readable => (true, false),
+            Writable => (false, true),
+            ReadWrite => (true, true),
+        };
+        let token = self.next_token;
+        self.next_token += 1;
+        self.fds.push(PollFd {
+            fd: fd, read: read, write: write, readable: false, writable: false,
+        });
+        self.tokens.push(token);
+        self.handles.push(handle as &'a Pollable);
+        Ok(token)
+    }
+
+    /// Removes the handle registered as `token`. Returns whether the token was
+    /// registered.
+    pub fn deregister(&mut self, token: uint) -> bool {
+        match self.tokens.iter().position(|t| *t == token) {
+            Some(i) => {
+                self.handles.remove(i);
+                self.fds.remove(i);
+                self.tokens.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of registered handles.
+    pub fn len(&self) -> uint { self.fds.len() }
+
+    /// Blocks until at least one of the registered handles is ready, returning
+    /// the events for each handle which is.
+    ///
+    /// If `timeout_ms` is `Some`, this waits for at most that many
+    /// milliseconds, and returns an empty vector if nothing became ready in
+    /// that time.
+    pub fn wait(&mut self, timeout_ms: Option<u64>) -> IoResult<Vec<Event>> {
+        let n = try!(LocalIo::maybe_raise(|io| {
+            io.poll(self.fds.as_mut_slice(), timeout_ms)
+        }));
+        let mut events = Vec::with_capacity(n);
+        for (fd, token) in self.fds.iter().zip(self.tokens.iter()) {
+            if fd.readable || fd.writable {
+                events.push(Event {
+                    token: *token,
+                    readable: fd.readable,
+                    writable: fd.writable,
+                });
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn unavailable() -> IoError {
+    IoError {
+        kind: IoUnavailable,
+        desc: "handle cannot be polled by this runtime",
+        detail: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use prelude::*;
+    use io::net::tcp::{TcpListener, TcpStream};
+    use io::test::next_test_ip4;
+    use io::{Listener, Acceptor};
+    use super::{Poll, Readable, Writable};
+
+    iotest!(fn poll_tcp() {
+        let addr = next_test_ip4();
+        let ip_str = addr.ip.to_str();
+        let mut acceptor = TcpListener::bind(ip_str.as_slice(), addr.port)
+                                       .listen().unwrap();
+        {
+            let mut poll = Poll::new();
+            poll.register(&acceptor, Readable).unwrap();
+            assert_eq!(poll.wait(Some(10)).unwrap().len(), 0);
+        }
+
+        let mut client = TcpStream::connect(ip_str.as_slice(),
+                                            addr.port).unwrap();
+        {
+            let mut poll = Poll::new();
+            let listener = poll.register(&acceptor, Readable).unwrap();
+            let events = poll.wait(None).unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events.get(0).token, listener);
+            assert!(events.get(0).readable);
+        }
+
+        let mut server = acceptor.accept().unwrap();
+        {
+            let mut poll = Poll::new();
+            let stream = poll.register(&server, Readable).unwrap();
+            let out = poll.register(&client, Writable).unwrap();
+            let events = poll.wait(None).unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events.get(0).token, out);
+            assert!(events.get(0).writable);
+
+            assert!(poll.deregister(out));
+            assert!(!poll.deregister(out));
+            assert!(poll.deregister(stream));
+            assert_eq!(poll.len(), 0);
+        }
+
+        client.write([1]).unwrap();
+        {
+            let mut poll = Poll::new();
+            let stream = poll.register(&server, Readable).unwrap();
+            let events = poll.wait(None).unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events.get(0).token, stream);
+        }
+        assert_eq!(server.read_byte().unwrap(), 1);
+    })
+}
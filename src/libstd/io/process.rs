@@ -59,16 +59,16 @@ use c_str::CString;
 pub struct Process {
     handle: Box<RtioProcess:Send>,
 
-    /// Handle to the child's stdin, if the `stdin` field of this process's
-    /// `ProcessConfig` was `CreatePipe`. By default, this handle is `Some`.
+    /// Handle to the child's stdin, if it was configured as `Piped` when the
+    /// process was spawned. By default, this handle is `Some`.
     pub stdin: Option<io::PipeStream>,
 
-    /// Handle to the child's stdout, if the `stdout` field of this process's
-    /// `ProcessConfig` was `CreatePipe`. By default, this handle is `Some`.
+    /// Handle to the child's stdout, if it was configured as `Piped` when the
+    /// process was spawned. By default, this handle is `Some`.
     pub stdout: Option<io::PipeStream>,
 
-    /// Handle to the child's stderr, if the `stderr` field of this process's
-    /// `ProcessConfig` was `CreatePipe`. By default, this handle is `Some`.
+    /// Handle to the child's stderr, if it was configured as `Piped` when the
+    /// process was spawned. By default, this handle is `Some`.
     pub stderr: Option<io::PipeStream>,
 
     /// Extra I/O handles as configured by the original `ProcessConfig` when
@@ -99,10 +99,11 @@ pub struct Command {
     args: Vec<CString>,
     env: Option<Vec<(CString, CString)>>,
     cwd: Option<CString>,
-    stdin: StdioContainer,
-    stdout: StdioContainer,
-    stderr: StdioContainer,
-    extra_io: Vec<StdioContainer>,
+    stdin: Stdio,
+    stdout: Stdio,
+    stderr: Stdio,
+    stderr_to_stdout: bool,
+    extra_io: Vec<Stdio>,
     uid: Option<uint>,
     gid: Option<uint>,
     detach: bool,
@@ -133,9 +134,10 @@ impl Command {
             args: Vec::new(),
             env: None,
             cwd: None,
-            stdin: CreatePipe(true, false),
-            stdout: CreatePipe(false, true),
-            stderr: CreatePipe(false, true),
+            stdin: Piped,
+            stdout: Piped,
+            stderr: Piped,
+            stderr_to_stdout: false,
             extra_io: Vec::new(),
             uid: None,
             gid: None,
@@ -172,6 +174,14 @@ impl Command {
         self
     }
 
+    /// Clears the environment for the child process, so that it starts with no
+    /// environment variables at all (rather than inheriting those of the
+    /// current process).
+    pub fn env_clear<'a>(&'a mut self) -> &'a mut Command {
+        self.env = Some(Vec::new());
+        self
+    }
+
     /// Set the working directory for the child process.
     pub fn cwd<'a>(&'a mut self, dir: &Path) -> &'a mut Command {
         self.cwd = Some(dir.to_c_str());
@@ -179,30 +189,46 @@ impl Command {
     }
 
     /// Configuration for the child process's stdin handle (file descriptor 0).
-    /// Defaults to `CreatePipe(true, false)` so the input can be written to.
-    pub fn stdin<'a>(&'a mut self, cfg: StdioContainer) -> &'a mut Command {
+    /// Defaults to `Piped` so the input can be written to.
+    pub fn stdin<'a>(&'a mut self, cfg: Stdio) -> &'a mut Command {
         self.stdin = cfg;
         self
     }
 
     /// Configuration for the child process's stdout handle (file descriptor 1).
-    /// Defaults to `CreatePipe(false, true)` so the output can be collected.
-    pub fn stdout<'a>(&'a mut self, cfg: StdioContainer) -> &'a mut Command {
+    /// Defaults to `Piped` so the output can be collected.
+    pub fn stdout<'a>(&'a mut self, cfg: Stdio) -> &'a mut Command {
         self.stdout = cfg;
         self
     }
 
     /// Configuration for the child process's stderr handle (file descriptor 2).
-    /// Defaults to `CreatePipe(false, true)` so the output can be collected.
-    pub fn stderr<'a>(&'a mut self, cfg: StdioContainer) -> &'a mut Command {
+    /// Defaults to `Piped` so the output can be collected.
+    ///
+    /// This undoes any previous call to `stderr_to_stdout`.
+    pub fn stderr<'a>(&'a mut self, cfg: Stdio) -> &'a mut Command {
         self.stderr = cfg;
+        self.stderr_to_stdout = false;
         self
     }
+
+    /// Sends the child process's stderr to the same place as its stdout, in
+    /// the same way as `2>&1` in a shell. If stdout is `Piped`, both streams
+    /// are read from the `stdout` handle of the spawned process, and its
+    /// `stderr` handle is `None`.
+    pub fn stderr_to_stdout<'a>(&'a mut self) -> &'a mut Command {
+        self.stderr_to_stdout = true;
+        self
+    }
+
     /// Attaches a stream/file descriptor/pipe to the child process. Inherited
     /// file descriptors are numbered consecutively, starting at 3; the first
     /// three file descriptors (stdin/stdout/stderr) are configured with the
     /// `stdin`, `stdout`, and `stderr` methods.
-    pub fn extra_io<'a>(&'a mut self, cfg: StdioContainer) -> &'a mut Command {
+    ///
+    /// `Inherit` is equivalent to `Null` for these descriptors, and a `Piped`
+    /// descriptor can be both read from and written to by the child.
+    pub fn extra_io<'a>(&'a mut self, cfg: Stdio) -> &'a mut Command {
         self.extra_io.push(cfg);
         self
     }
@@ -224,7 +250,8 @@ impl Command {
     }
 
     /// Sets the child process to be spawned in a detached state. On unix, this
-    /// means that the child is the leader of a new process group.
+    /// means that the child is the leader of a new session (and so of a new
+    /// process group), with no controlling terminal.
     pub fn detached<'a>(&'a mut self) -> &'a mut Command {
         self.detach = true;
         self
@@ -241,6 +268,7 @@ impl Command {
                 stdin: self.stdin,
                 stdout: self.stdout,
                 stderr: self.stderr,
+                stderr_to_stdout: self.stderr_to_stdout,
                 extra_io: self.extra_io.as_slice(),
                 uid: self.uid,
                 gid: self.gid,
@@ -328,22 +356,28 @@ pub struct ProcessOutput {
 }
 
 /// Describes what to do with a standard io stream for a child process.
-pub enum StdioContainer {
+#[deriving(Eq, Clone, Show)]
+pub enum Stdio {
+    /// The child shares the corresponding stream of the current process, so
+    /// for example a child's stdout is written to wherever the stdout of the
+    /// current process goes.
+    Inherit,
+
     /// This stream will be ignored. This is the equivalent of attaching the
     /// stream to `/dev/null`
-    Ignored,
-
-    /// The specified file descriptor is inherited for the stream which it is
-    /// specified for.
-    InheritFd(libc::c_int),
+    Null,
 
-    /// Creates a pipe for the specified file descriptor which will be created
-    /// when the process is spawned.
+    /// Creates a new pipe connected to the stream when the process is spawned.
+    /// The other end of the pipe is available from the `stdin`, `stdout`,
+    /// `stderr` or `extra_io` fields of the spawned `Process`.
     ///
-    /// The first boolean argument is whether the pipe is readable, and the
-    /// second is whether it is writable. These properties are from the view of
-    /// the *child* process, not the parent process.
-    CreatePipe(bool /* readable */, bool /* writable */),
+    /// The pipe is readable by the child for stdin, and writable by the child
+    /// for stdout and stderr.
+    Piped,
+
+    /// The stream is redirected to the specified file descriptor of the
+    /// current process, which the child inherits.
+    Fd(libc::c_int),
 }
 
 /// Describes the result of a process after it has terminated.
@@ -379,6 +413,24 @@ impl ProcessExit {
     pub fn matches_exit_status(&self, wanted: int) -> bool {
         *self == ExitStatus(wanted)
     }
+
+    /// Returns the exit status of the process, or `None` if it was terminated
+    /// by a signal.
+    pub fn code(&self) -> Option<int> {
+        match *self {
+            ExitStatus(code) => Some(code),
+            ExitSignal(..) => None,
+        }
+    }
+
+    /// Returns the number of the signal which terminated the process, or
+    /// `None` if it exited normally. This is always `None` on windows.
+    pub fn signal(&self) -> Option<int> {
+        match *self {
+            ExitStatus(..) => None,
+            ExitSignal(signal) => Some(signal),
+        }
+    }
 }
 
 impl Process {
@@ -490,6 +542,45 @@ impl Process {
         self.handle.set_timeout(timeout_ms)
     }
 
+    /// Wait for the child to exit for at most `timeout_ms` milliseconds,
+    /// returning `None` if it is still running once the timeout elapses.
+    ///
+    /// This replaces any timeout previously specified via `set_timeout`, and
+    /// leaves future calls to `wait()` with no timeout at all.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #![allow(experimental)]
+    /// use std::io::process::{Command, ProcessExit};
+    /// use std::io::IoResult;
+    ///
+    /// fn stop(p: &mut std::io::Process) -> IoResult<ProcessExit> {
+    ///     try!(p.signal_exit());
+    ///     match try!(p.wait_timeout(1_000)) {
+    ///         Some(status) => Ok(status),
+    ///         None => { try!(p.signal_kill()); p.wait() }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails for any of the reasons that `wait()` fails, with
+    /// the exception of the timeout expiring.
+    #[experimental = "the type of the timeout is likely to change"]
+    pub fn wait_timeout(&mut self, timeout_ms: u64)
+                        -> IoResult<Option<ProcessExit>> {
+        self.set_timeout(Some(timeout_ms));
+        let ret = self.wait();
+        self.set_timeout(None);
+        match ret {
+            Ok(status) => Ok(Some(status)),
+            Err(ref e) if e.kind == io::TimedOut => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Simultaneously wait for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning a `ProcessOutput`
     /// instance.
@@ -520,8 +611,8 @@ impl Process {
 
         Ok(ProcessOutput {
             status: status,
-            output: stdout.recv().ok().unwrap_or(Vec::new()),
-            error:  stderr.recv().ok().unwrap_or(Vec::new()),
+            output: stdout.recv_unwrap().ok().unwrap_or(Vec::new()),
+            error:  stderr.recv_unwrap().ok().unwrap_or(Vec::new()),
         })
     }
 }
@@ -533,7 +624,7 @@ impl Drop for Process {
         drop(self.stdin.take());
         drop(self.stdout.take());
         drop(self.stderr.take());
-        drop(mem::replace(&mut self.extra_io, Vec::new()));
+        drop(mem::take(&mut self.extra_io));
 
         self.set_timeout(None);
         let _ = self.wait().unwrap();
@@ -600,7 +691,7 @@ mod tests {
     #[cfg(not(target_os="android"))]
     iotest!(fn stdout_works() {
         let mut cmd = Command::new("echo");
-        cmd.arg("foobar").stdout(CreatePipe(false, true));
+        cmd.arg("foobar").stdout(Piped);
         assert_eq!(run_output(cmd), "foobar\n".to_string());
     })
 
@@ -609,7 +700,7 @@ mod tests {
         let mut cmd = Command::new("/bin/sh");
         cmd.arg("-c").arg("pwd")
            .cwd(&Path::new("/"))
-           .stdout(CreatePipe(false, true));
+           .stdout(Piped);
         assert_eq!(run_output(cmd), "/\n".to_string());
     })
 
@@ -617,8 +708,8 @@ mod tests {
     iotest!(fn stdin_works() {
         let mut p = Command::new("/bin/sh")
                             .arg("-c").arg("read line; echo $line")
-                            .stdin(CreatePipe(true, false))
-                            .stdout(CreatePipe(false, true))
+                            .stdin(Piped)
+                            .stdout(Piped)
                             .spawn().unwrap();
         p.stdin.get_mut_ref().write("foobar".as_bytes()).unwrap();
         drop(p.stdin.take());
@@ -627,6 +718,45 @@ mod tests {
         assert_eq!(out, "foobar\n".to_string());
     })
 
+    #[cfg(unix, not(target_os="android"))]
+    iotest!(fn stderr_to_stdout_works() {
+        let mut cmd = Command::new("/bin/sh");
+        cmd.arg("-c").arg("echo foo; echo bar 1>&2").stderr_to_stdout();
+        let mut p = match cmd.spawn() {
+            Ok(p) => p,
+            Err(e) => fail!("failed to spawn: {}", e),
+        };
+        assert!(p.stderr.is_none());
+        let out = read_all(p.stdout.get_mut_ref() as &mut Reader);
+        assert!(p.wait().unwrap().success());
+        assert_eq!(out, "foo\nbar\n".to_string());
+
+        // output() pipes stdout as well
+        let out = cmd.output().unwrap();
+        assert_eq!(out.output.as_slice(), bytes!("foo\nbar\n"));
+        assert_eq!(out.error.as_slice(), bytes!(""));
+
+        // setting stderr again undoes the redirection
+        let out = cmd.stderr(Null).output().unwrap();
+        assert_eq!(out.output.as_slice(), bytes!("foo\n"));
+        assert_eq!(out.error.as_slice(), bytes!(""));
+    })
+
+    #[cfg(unix, not(target_os="android"))]
+    iotest!(fn stdio_null_and_inherit() {
+        let out = Command::new("/bin/sh").arg("-c").arg("echo foo")
+                          .stdout(Null).output().unwrap();
+        assert!(out.status.success());
+        assert_eq!(out.output.as_slice(), bytes!(""));
+
+        let mut p = Command::new("/bin/sh").arg("-c").arg("true")
+                            .stdin(Inherit).stdout(Inherit).stderr(Inherit)
+                            .spawn().unwrap();
+        assert!(p.stdin.is_none() && p.stdout.is_none() &&
+                p.stderr.is_none());
+        assert!(p.wait().unwrap().success());
+    })
+
     #[cfg(not(target_os="android"))]
     iotest!(fn detach_works() {
         let mut p = Command::new("true").detached().spawn().unwrap();
@@ -849,6 +979,17 @@ mod tests {
                 "didn't find RUN_TEST_NEW_ENV inside of:\n\n{}", output);
     })
 
+    #[cfg(not(windows), not(target_os="android"))]
+    iotest!(fn test_env_clear() {
+        let prog = env_cmd().env_clear().spawn().unwrap();
+        let result = prog.wait_with_output().unwrap();
+        let output = str::from_utf8_lossy(result.output.as_slice()).into_string();
+
+        assert!(result.status.success());
+        assert!(output.as_slice().trim().is_empty(),
+                "expected an empty environment, found:\n\n{}", output);
+    })
+
     #[cfg(unix)]
     pub fn sleeper() -> Process {
         Command::new("sleep").arg("1000").spawn().unwrap()
@@ -897,6 +1038,30 @@ mod tests {
         assert!(p.wait().is_ok());
     })
 
+    iotest!(fn wait_timeout_option() {
+        let mut p = sleeper();
+        assert_eq!(p.wait_timeout(10), Ok(None));
+        assert_eq!(p.wait_timeout(10), Ok(None));
+        p.signal_kill().unwrap();
+        let status = p.wait_timeout(10_000).unwrap().unwrap();
+        assert!(!status.success());
+        assert_eq!(p.wait(), Ok(status));
+    })
+
+    #[cfg(unix, not(target_os="android"))]
+    iotest!(fn exit_signal() {
+        use libc;
+        let mut p = sleeper();
+        p.signal(libc::SIGTERM as int).unwrap();
+        let status = p.wait().unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGTERM as int));
+        assert_eq!(status.code(), None);
+
+        let status = Command::new("false").status().unwrap();
+        assert_eq!(status.signal(), None);
+        assert_eq!(status.code(), Some(1));
+    })
+
     iotest!(fn wait_timeout2() {
         let (tx, rx) = channel();
         let tx2 = tx.clone();
@@ -914,7 +1079,7 @@ mod tests {
             p.signal_kill().unwrap();
             tx2.send(());
         });
-        rx.recv();
-        rx.recv();
+        rx.recv_unwrap();
+        rx.recv_unwrap();
     })
 }
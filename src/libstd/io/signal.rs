@@ -17,6 +17,32 @@ local I/O factory. There are a number of defined signals which can be caught,
 but not all signals will work across all platforms (windows doesn't have
 definitions for a number of signals.
 
+Registered signals are delivered over a channel rather than by running a
+handler, so a task can simply block on the receiver of a `Listener` and act on
+each signal as it arrives.
+
+# Example
+
+A daemon which reloads its configuration on `HangUp` and shuts down cleanly on
+`Terminate`:
+
+```rust,no_run
+# #![allow(unused_must_use)]
+use std::io::signal::{Listener, HangUp, Terminate};
+
+let mut listener = Listener::new();
+listener.register(HangUp);
+listener.register(Terminate);
+
+loop {
+    match listener.rx.recv_unwrap() {
+        HangUp => { /* reload the configuration */ }
+        Terminate => break,
+        _ => {}
+    }
+}
+```
+
 */
 
 use clone::Clone;
@@ -24,8 +50,9 @@ use comm::{Sender, Receiver, channel};
 use io;
 use iter::Iterator;
 use kinds::Send;
+use libc;
 use mem::drop;
-use option::{Some, None};
+use option::{Option, Some, None};
 use owned::Box;
 use result::{Ok, Err};
 use rt::rtio::{IoFactory, LocalIo, RtioSignal};
@@ -33,11 +60,21 @@ use slice::ImmutableVector;
 use vec::Vec;
 
 /// Signals that can be sent and received
+///
+/// The discriminant of each signal is its number on linux; use `os_signum` to
+/// find the number of a signal on the current platform.
 #[repr(int)]
 #[deriving(Eq, Hash, Show)]
 pub enum Signum {
+    /// Equivalent to SIGALRM, delivered when a timer set by `alarm` expires.
+    Alarm = 14i,
     /// Equivalent to SIGBREAK, delivered when the user presses Ctrl-Break.
     Break = 21i,
+    /// Equivalent to SIGCHLD, delivered when a child process exits or stops.
+    Child = 17i,
+    /// Equivalent to SIGCONT, delivered when the process is resumed after
+    /// having been stopped.
+    Continue = 18i,
     /// Equivalent to SIGHUP, delivered when the user closes the terminal
     /// window. On delivery of HangUp, the program is given approximately
     /// 10 seconds to perform any cleanup. After that, Windows will
@@ -45,10 +82,15 @@ pub enum Signum {
     HangUp = 1i,
     /// Equivalent to SIGINT, delivered when the user presses Ctrl-c.
     Interrupt = 2i,
+    /// Equivalent to SIGPIPE, delivered when writing to a pipe or socket
+    /// whose reading end has been closed.
+    Pipe = 13i,
     /// Equivalent to SIGQUIT, delivered when the user presses Ctrl-\.
     Quit = 3i,
     /// Equivalent to SIGTSTP, delivered when the user presses Ctrl-z.
     StopTemporarily = 20i,
+    /// Equivalent to SIGTERM, the polite request for the process to exit.
+    Terminate = 15i,
     /// Equivalent to SIGUSR1.
     User1 = 10i,
     /// Equivalent to SIGUSR2.
@@ -59,6 +101,62 @@ pub enum Signum {
     WindowSizeChange = 28i,
 }
 
+impl Signum {
+    /// Returns the number of this signal on the current platform, or `None` if
+    /// the platform has no equivalent signal.
+    #[cfg(target_os = "linux", not(target_arch = "mips"))]
+    #[cfg(target_os = "android")]
+    pub fn os_signum(&self) -> Option<libc::c_int> {
+        match *self {
+            Break => None,
+            sig => Some(sig as libc::c_int),
+        }
+    }
+
+    /// Returns the number of this signal on the current platform, or `None` if
+    /// the platform has no equivalent signal.
+    #[cfg(target_os = "linux", target_arch = "mips")]
+    pub fn os_signum(&self) -> Option<libc::c_int> {
+        match *self {
+            Break => None,
+            Child => Some(18),
+            Continue => Some(25),
+            StopTemporarily => Some(24),
+            User1 => Some(16),
+            User2 => Some(17),
+            WindowSizeChange => Some(20),
+            sig => Some(sig as libc::c_int),
+        }
+    }
+
+    /// Returns the number of this signal on the current platform, or `None` if
+    /// the platform has no equivalent signal.
+    #[cfg(target_os = "macos")]
+    #[cfg(target_os = "freebsd")]
+    pub fn os_signum(&self) -> Option<libc::c_int> {
+        match *self {
+            Break => None,
+            Child => Some(20),
+            Continue => Some(19),
+            StopTemporarily => Some(18),
+            User1 => Some(30),
+            User2 => Some(31),
+            sig => Some(sig as libc::c_int),
+        }
+    }
+
+    /// Returns the number of this signal on the current platform, or `None` if
+    /// the platform has no equivalent signal.
+    #[cfg(windows)]
+    pub fn os_signum(&self) -> Option<libc::c_int> {
+        match *self {
+            sig @ Break | sig @ HangUp | sig @ Interrupt |
+            sig @ WindowSizeChange => Some(sig as libc::c_int),
+            _ => None,
+        }
+    }
+}
+
 /// Listener provides a receiver to listen for registered signals.
 ///
 /// Listener automatically unregisters its handles once it is out of scope.
@@ -74,7 +172,7 @@ pub enum Signum {
 /// listener.register(Interrupt);
 ///
 /// loop {
-///     match listener.rx.recv() {
+///     match listener.rx.recv_unwrap() {
 ///         Interrupt => println!("Got Interrupt'ed"),
 ///         _ => (),
 ///     }
@@ -146,6 +244,35 @@ impl Listener {
             None => {}
         }
     }
+
+    /// Masks a registered signal, returning whether this listener is
+    /// registered for it.
+    ///
+    /// While a signal is masked it is still caught (so the default action,
+    /// such as terminating the process, is not taken), but it is held back
+    /// rather than being sent on `rx`. If the signal arrives while masked, it
+    /// is sent once when the signal is unmasked.
+    pub fn mask(&mut self, signum: Signum) -> bool {
+        self.set_masked(signum, true)
+    }
+
+    /// Unmasks a signal previously masked with `mask`, returning whether this
+    /// listener is registered for it.
+    pub fn unmask(&mut self, signum: Signum) -> bool {
+        self.set_masked(signum, false)
+    }
+
+    fn set_masked(&mut self, signum: Signum, masked: bool) -> bool {
+        match self.handles.iter().position(|&(i, _)| i == signum) {
+            Some(i) => {
+                match *self.handles.get_mut(i) {
+                    (_, ref mut handle) => handle.set_masked(masked),
+                }
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 #[cfg(test, unix)]
@@ -153,8 +280,9 @@ mod test_unix {
     use prelude::*;
     use libc;
     use comm::Empty;
+    use io::process::Command;
     use io::timer;
-    use super::{Listener, Interrupt};
+    use super::{Listener, Interrupt, User1, Child};
 
     fn sigint() {
         unsafe {
@@ -168,7 +296,7 @@ mod test_unix {
         signal.register(Interrupt).unwrap();
         sigint();
         timer::sleep(10);
-        match signal.rx.recv() {
+        match signal.rx.recv_unwrap() {
             Interrupt => (),
             s => fail!("Expected Interrupt, got {:?}", s),
         }
@@ -182,11 +310,11 @@ mod test_unix {
         s2.register(Interrupt).unwrap();
         sigint();
         timer::sleep(10);
-        match s1.rx.recv() {
+        match s1.rx.recv_unwrap() {
             Interrupt => (),
             s => fail!("Expected Interrupt, got {:?}", s),
         }
-        match s2.rx.recv() {
+        match s2.rx.recv_unwrap() {
             Interrupt => (),
             s => fail!("Expected Interrupt, got {:?}", s),
         }
@@ -203,6 +331,47 @@ mod test_unix {
         timer::sleep(10);
         assert_eq!(s2.rx.try_recv(), Err(Empty));
     }
+
+    #[test] #[cfg(not(target_os="android"))] // FIXME(#10378)
+    fn test_io_signal_mask() {
+        let mut s = Listener::new();
+        assert!(!s.mask(User1));
+        s.register(User1).unwrap();
+        assert!(s.mask(User1));
+        unsafe {
+            let signum = User1.os_signum().unwrap();
+            libc::funcs::posix88::signal::kill(libc::getpid(), signum);
+        }
+        timer::sleep(10);
+        assert_eq!(s.rx.try_recv(), Err(Empty));
+        assert!(s.unmask(User1));
+        match s.rx.recv_unwrap() {
+            User1 => (),
+            s => fail!("Expected User1, got {:?}", s),
+        }
+    }
+
+    #[test] #[cfg(not(target_os="android"))] // FIXME(#10378)
+    fn test_io_signal_child_with_process_timeouts() {
+        // Listening for SIGCHLD, and then not listening any more, mustn't take
+        // SIGCHLD away from waiting on children with a timeout.
+        fn wait_for_true() {
+            let mut p = Command::new("true").spawn().unwrap();
+            p.set_timeout(Some(10000));
+            assert!(p.wait().unwrap().success());
+        }
+
+        wait_for_true();
+        let mut s = Listener::new();
+        s.register(Child).unwrap();
+        wait_for_true();
+        match s.rx.recv_unwrap() {
+            Child => (),
+            s => fail!("Expected Child, got {:?}", s),
+        }
+        s.unregister(Child);
+        wait_for_true();
+    }
 }
 
 #[cfg(test, windows)]
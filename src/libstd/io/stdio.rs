@@ -33,6 +33,7 @@ use io::{Reader, Writer, IoResult, IoError, OtherIoError,
 use libc;
 use kinds::Send;
 use mem::replace;
+use ops::{Deref, DerefMut, Drop};
 use option::{Option, Some, None};
 use owned::Box;
 use prelude::drop;
@@ -298,6 +299,78 @@ impl StdReader {
             File(..) => false,
         }
     }
+
+    /// Controls whether this input stream is a "raw stream" or simply a normal
+    /// stream. In raw mode input is not line buffered or echoed, and control
+    /// characters such as ^C are read rather than interpreted.
+    ///
+    /// Leaving raw mode restores the terminal to the state it had before it
+    /// was first put into raw mode, once no other stream on it is still in raw
+    /// mode. A stream which is dropped while in raw mode leaves it as well.
+    /// See also `raw_mode`, which leaves raw mode automatically.
+    ///
+    /// # Error
+    ///
+    /// This function will return an error if the input stream is not actually
+    /// connected to a TTY instance, or if configuring the TTY instance fails.
+    pub fn set_raw(&mut self, raw: bool) -> IoResult<()> {
+        match self.inner {
+            TTY(ref mut tty) => tty.set_raw(raw),
+            File(..) => Err(not_a_tty()),
+        }
+    }
+
+    /// Puts this input stream into raw mode, returning a guard which puts it
+    /// back into its original mode when dropped. The guard is also dropped if
+    /// the task fails, so the terminal is not left in raw mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #![allow(unused_must_use)]
+    /// use std::io;
+    ///
+    /// let mut stdin = io::stdin_raw();
+    /// let mut raw = stdin.raw_mode().unwrap();
+    /// // read a single keypress without waiting for enter
+    /// let key = raw.read_byte();
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns the same errors as `set_raw`.
+    pub fn raw_mode<'a>(&'a mut self) -> IoResult<RawMode<'a>> {
+        try!(self.set_raw(true));
+        Ok(RawMode { reader: self })
+    }
+
+    /// Controls whether characters typed into the terminal this stream is
+    /// reading from are echoed back to it, for example to read a password.
+    ///
+    /// # Error
+    ///
+    /// This function will return an error if the input stream is not actually
+    /// connected to a TTY instance, or if configuring the TTY instance fails.
+    pub fn set_echo(&mut self, echo: bool) -> IoResult<()> {
+        match self.inner {
+            TTY(ref mut tty) => tty.set_echo(echo),
+            File(..) => Err(not_a_tty()),
+        }
+    }
+
+    /// Gets the size of the terminal this stream is reading from, as
+    /// `Ok((width, height))`.
+    ///
+    /// # Error
+    ///
+    /// This function will return an error if the input stream is not actually
+    /// connected to a TTY instance, or if querying the TTY instance fails.
+    pub fn winsize(&mut self) -> IoResult<(int, int)> {
+        match self.inner {
+            TTY(ref mut tty) => tty.get_winsize(),
+            File(..) => Err(not_a_tty()),
+        }
+    }
 }
 
 impl Reader for StdReader {
@@ -324,6 +397,40 @@ impl Reader for StdReader {
     }
 }
 
+/// A guard which keeps a `StdReader` in raw mode, created by
+/// `StdReader::raw_mode`.
+///
+/// The stream can be read through the guard, and leaves raw mode when the
+/// guard is dropped.
+pub struct RawMode<'a> {
+    reader: &'a mut StdReader,
+}
+
+impl<'a> Deref<StdReader> for RawMode<'a> {
+    fn deref<'b>(&'b self) -> &'b StdReader { &*self.reader }
+}
+
+impl<'a> DerefMut<StdReader> for RawMode<'a> {
+    fn deref_mut<'b>(&'b mut self) -> &'b mut StdReader { &mut *self.reader }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for RawMode<'a> {
+    fn drop(&mut self) {
+        // There's nobody to report an error to here, and the stream is still
+        // usable (if oddly configured) even if this fails.
+        let _ = self.reader.set_raw(false);
+    }
+}
+
+fn not_a_tty() -> IoError {
+    IoError {
+        kind: OtherIoError,
+        desc: "stream is not a tty",
+        detail: None,
+    }
+}
+
 /// Representation of a writer to a standard output stream
 pub struct StdWriter {
     inner: StdSource
@@ -343,13 +450,7 @@ impl StdWriter {
     pub fn winsize(&mut self) -> IoResult<(int, int)> {
         match self.inner {
             TTY(ref mut tty) => tty.get_winsize(),
-            File(..) => {
-                Err(IoError {
-                    kind: OtherIoError,
-                    desc: "stream is not a tty",
-                    detail: None,
-                })
-            }
+            File(..) => Err(not_a_tty()),
         }
     }
 
@@ -363,13 +464,7 @@ impl StdWriter {
     pub fn set_raw(&mut self, raw: bool) -> IoResult<()> {
         match self.inner {
             TTY(ref mut tty) => tty.set_raw(raw),
-            File(..) => {
-                Err(IoError {
-                    kind: OtherIoError,
-                    desc: "stream is not a tty",
-                    detail: None,
-                })
-            }
+            File(..) => Err(not_a_tty()),
         }
     }
 
@@ -400,6 +495,18 @@ mod tests {
         stderr();
     })
 
+    iotest!(fn tty_control_requires_tty() {
+        // The test harness may or may not be run from a terminal, so only check
+        // the behavior of a redirected stdin.
+        let mut stdin = stdin_raw();
+        if !stdin.isatty() {
+            assert!(stdin.set_raw(true).is_err());
+            assert!(stdin.raw_mode().is_err());
+            assert!(stdin.set_echo(false).is_err());
+            assert!(stdin.winsize().is_err());
+        }
+    })
+
     iotest!(fn capture_stdout() {
         use io::{ChanReader, ChanWriter};
 
@@ -10,15 +10,19 @@
 
 //! Temporary files and directories
 
-use io::{fs, IoResult};
+use container::Container;
+use io::{fs, IoResult, IoError, File, Reader, Writer, Seek, SeekStyle};
 use io;
 use iter::{Iterator, range};
 use libc;
+use mem::drop;
 use ops::Drop;
 use option::{Option, None, Some};
 use os;
 use path::{Path, GenericPath};
 use result::{Ok, Err};
+use slice::ImmutableVector;
+use string::String;
 use sync::atomics;
 
 /// A wrapper for a path to temporary directory implementing automatic
@@ -107,6 +111,165 @@ impl Drop for TempDir {
     }
 }
 
+/// A temporary file which is deleted when it goes out of scope.
+///
+/// The file is created with a name which can't be predicted from outside of
+/// the process, and its creation fails (rather than opening an existing file)
+/// if a file with that name already exists. On unix the file is only readable
+/// and writable by its owner.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![allow(unused_must_use)]
+/// use std::io::TempFile;
+///
+/// let mut tmp = TempFile::new("scratch").unwrap();
+/// tmp.write(bytes!("intermediate results"));
+/// ```
+pub struct TempFile {
+    file: Option<File>,
+    path: Option<Path>,
+}
+
+impl TempFile {
+    /// Attempts to create a new temporary file inside of `tmpdir` whose name
+    /// starts with `prefix`, opened for both reading and writing. The file
+    /// will be deleted once the returned wrapper is destroyed, unless it is
+    /// persisted first.
+    pub fn new_in(tmpdir: &Path, prefix: &str) -> IoResult<TempFile> {
+        if !tmpdir.is_absolute() {
+            return TempFile::new_in(&os::make_absolute(tmpdir), prefix);
+        }
+
+        for _ in range(0u, 1000) {
+            let filename = format!("{}{}", prefix, try!(random_name()));
+            let p = tmpdir.join(filename);
+            match File::open_mode(&p, io::CreateNew, io::ReadWrite) {
+                Ok(file) => {
+                    return Ok(TempFile { file: Some(file), path: Some(p) })
+                }
+                Err(ref e) if e.kind == io::PathAlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Err(IoError {
+            kind: io::PathAlreadyExists,
+            desc: "could not find an unused name for a temporary file",
+            detail: None,
+        })
+    }
+
+    /// Attempts to create a new temporary file inside of `os::tmpdir()` whose
+    /// name starts with `prefix`. See `new_in` for details.
+    pub fn new(prefix: &str) -> IoResult<TempFile> {
+        TempFile::new_in(&os::tmpdir(), prefix)
+    }
+
+    /// Access the path to the temporary file.
+    pub fn path<'a>(&'a self) -> &'a Path {
+        self.path.get_ref()
+    }
+
+    /// Access the open handle to the temporary file.
+    pub fn file<'a>(&'a mut self) -> &'a mut File {
+        self.file.get_mut_ref()
+    }
+
+    /// Keep the temporary file rather than deleting it, returning the open
+    /// handle to it along with its path.
+    pub fn persist(self) -> (File, Path) {
+        let mut tmp = self;
+        (tmp.file.take_unwrap(), tmp.path.take_unwrap())
+    }
+
+    /// Close and remove the temporary file
+    ///
+    /// Although `TempFile` removes the file on drop, in the destructor any
+    /// errors are ignored. To detect errors cleaning up the temporary file,
+    /// call `close` instead.
+    pub fn close(mut self) -> IoResult<()> {
+        self.cleanup_file()
+    }
+
+    fn cleanup_file(&mut self) -> IoResult<()> {
+        // the handle is closed first so the file can be removed on windows
+        drop(self.file.take());
+        match self.path.take() {
+            Some(ref p) => fs::unlink(p),
+            None => Ok(())
+        }
+    }
+}
+
+impl Reader for TempFile {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<uint> {
+        self.file.get_mut_ref().read(buf)
+    }
+}
+
+impl Writer for TempFile {
+    fn write(&mut self, buf: &[u8]) -> IoResult<()> {
+        self.file.get_mut_ref().write(buf)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.file.get_mut_ref().flush()
+    }
+}
+
+impl Seek for TempFile {
+    fn tell(&self) -> IoResult<u64> {
+        self.file.get_ref().tell()
+    }
+
+    fn seek(&mut self, pos: i64, style: SeekStyle) -> IoResult<()> {
+        self.file.get_mut_ref().seek(pos, style)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = self.cleanup_file();
+    }
+}
+
+// Generates a random file name from the operating system's source of
+// randomness, so that the names of temporary files can't be guessed.
+fn random_name() -> IoResult<String> {
+    static CHARSET: &'static [u8] =
+        bytes!("abcdefghijklmnopqrstuvwxyz0123456789");
+
+    let mut bytes = [0u8, ..12];
+    try!(random_bytes(bytes));
+    Ok(bytes.iter().map(|b| {
+        CHARSET[*b as uint % CHARSET.len()] as char
+    }).collect())
+}
+
+#[cfg(unix)]
+fn random_bytes(buf: &mut [u8]) -> IoResult<()> {
+    let mut urandom = try!(File::open(&Path::new("/dev/urandom")));
+    urandom.read_at_least(buf.len(), buf).map(|_| ())
+}
+
+#[cfg(windows)]
+fn random_bytes(buf: &mut [u8]) -> IoResult<()> {
+    #[link(name = "advapi32")]
+    extern "system" {
+        // This is the documented name of RtlGenRandom
+        fn SystemFunction036(RandomBuffer: *mut u8,
+                             RandomBufferLength: libc::c_ulong) -> u8;
+    }
+
+    match unsafe {
+        SystemFunction036(buf.as_mut_ptr(), buf.len() as libc::c_ulong)
+    } {
+        0 => Err(IoError::last_error()),
+        _ => Ok(()),
+    }
+}
+
 // the tests for this module need to change the path using change_dir,
 // and this doesn't play nicely with other tests so these unit tests are located
 // in src/test/run-pass/tempfile.rs
@@ -47,7 +47,7 @@ macro_rules! iotest (
                 use native;
                 let (tx, rx) = channel();
                 native::task::spawn(proc() { tx.send(f()) });
-                rx.recv();
+                rx.recv_unwrap();
             }
         }
     )
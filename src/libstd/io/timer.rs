@@ -42,11 +42,11 @@ use rt::rtio::{IoFactory, LocalIo, RtioTimer};
 ///
 /// let timeout = timer.oneshot(10);
 /// // do some work
-/// timeout.recv(); // wait for the timeout to expire
+/// timeout.recv_unwrap(); // wait for the timeout to expire
 ///
 /// let periodic = timer.periodic(10);
 /// loop {
-///     periodic.recv();
+///     periodic.recv_unwrap();
 ///     // this loop is only executed once every 10ms
 /// }
 /// # }
@@ -126,7 +126,7 @@ mod test {
 
     iotest!(fn test_io_timer_sleep_oneshot() {
         let mut timer = Timer::new().unwrap();
-        timer.oneshot(1).recv();
+        timer.oneshot(1).recv_unwrap();
     })
 
     iotest!(fn test_io_timer_sleep_oneshot_forget() {
@@ -138,7 +138,7 @@ mod test {
         let mut timer = Timer::new().unwrap();
         let rx1 = timer.oneshot(10000);
         let rx = timer.oneshot(1);
-        rx.recv();
+        rx.recv_unwrap();
         assert_eq!(rx1.recv_opt(), Err(()));
     })
 
@@ -153,9 +153,9 @@ mod test {
     iotest!(fn test_io_timer_sleep_periodic() {
         let mut timer = Timer::new().unwrap();
         let rx = timer.periodic(1);
-        rx.recv();
-        rx.recv();
-        rx.recv();
+        rx.recv_unwrap();
+        rx.recv_unwrap();
+        rx.recv_unwrap();
     })
 
     iotest!(fn test_io_timer_sleep_periodic_forget() {
@@ -171,11 +171,11 @@ mod test {
         let mut timer = Timer::new().unwrap();
 
         let rx = timer.oneshot(1);
-        rx.recv();
+        rx.recv_unwrap();
         assert!(rx.recv_opt().is_err());
 
         let rx = timer.oneshot(1);
-        rx.recv();
+        rx.recv_unwrap();
         assert!(rx.recv_opt().is_err());
     })
 
@@ -186,17 +186,17 @@ mod test {
         timer.sleep(1);
         assert_eq!(orx.recv_opt(), Err(()));
         assert_eq!(prx.recv_opt(), Err(()));
-        timer.oneshot(1).recv();
+        timer.oneshot(1).recv_unwrap();
     })
 
     iotest!(fn period() {
         let mut timer = Timer::new().unwrap();
         let rx = timer.periodic(1);
-        rx.recv();
-        rx.recv();
+        rx.recv_unwrap();
+        rx.recv_unwrap();
         let rx2 = timer.periodic(1);
-        rx2.recv();
-        rx2.recv();
+        rx2.recv_unwrap();
+        rx2.recv_unwrap();
     })
 
     iotest!(fn sleep() {
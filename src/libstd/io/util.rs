@@ -118,6 +118,9 @@ impl Buffer for NullReader {
 }
 
 /// A `Writer` which multiplexes writes to a set of `Writers`.
+///
+/// Each write and flush is attempted on every writer, even if an earlier one
+/// fails, and the first error encountered is returned.
 pub struct MultiWriter {
     writers: Vec<Box<Writer>>
 }
@@ -187,8 +190,45 @@ impl<R: Reader, I: Iterator<R>> Reader for ChainedReader<I, R> {
     }
 }
 
+impl<R: Buffer, I: Iterator<R>> Buffer for ChainedReader<I, R> {
+    fn fill_buf<'a>(&'a mut self) -> io::IoResult<&'a [u8]> {
+        // Move on to the first reader which still has data, so that the buffer
+        // of the current reader can be returned.
+        loop {
+            let err = match self.cur_reader {
+                Some(ref mut r) => {
+                    match r.fill_buf() {
+                        Ok(..) => break,
+                        Err(ref e) if e.kind == io::EndOfFile => None,
+                        Err(e) => Some(e),
+                    }
+                }
+                None => return Err(io::standard_error(io::EndOfFile))
+            };
+            self.cur_reader = self.readers.next();
+            match err {
+                Some(e) => return Err(e),
+                None => {}
+            }
+        }
+        self.cur_reader.get_mut_ref().fill_buf()
+    }
+
+    fn consume(&mut self, amt: uint) {
+        match self.cur_reader {
+            Some(ref mut r) => r.consume(amt),
+            None => {}
+        }
+    }
+}
+
 /// A `Reader` which forwards input from another `Reader`, passing it along to
 /// a `Writer` as well. Similar to the `tee(1)` command.
+///
+/// Only the bytes actually returned by each read are written, so short reads
+/// from the underlying reader are passed along as-is. `TeeReader` does not
+/// implement `Buffer`, as a failure to write the bytes passed to `consume`
+/// could not be reported.
 pub struct TeeReader<R, W> {
     reader: R,
     writer: W,
@@ -231,7 +271,7 @@ pub fn copy<R: Reader, W: Writer>(r: &mut R, w: &mut W) -> io::IoResult<()> {
 
 #[cfg(test)]
 mod test {
-    use io::{MemReader, MemWriter, BufReader};
+    use io::{MemReader, MemWriter, BufReader, ChanWriter};
     use io;
     use owned::Box;
     use super::*;
@@ -326,6 +366,68 @@ mod test {
         assert_eq!(vec!(0, 1, 2, 3), r.read_to_end().unwrap());
     }
 
+    #[test]
+    fn test_chained_reader_buffer() {
+        let rs = vec!(BufReader::new(bytes!("ab")), BufReader::new(bytes!("")),
+                      BufReader::new(bytes!("c\nd")));
+        let mut r = ChainedReader::new(rs.move_iter());
+        assert_eq!(r.read_line(), Ok("abc\n".to_str()));
+        assert_eq!(r.read_char(), Ok('d'));
+        assert_eq!(r.fill_buf().err().unwrap().kind, io::EndOfFile);
+    }
+
+    // A reader which returns at most one byte per read
+    struct ShortReader { data: Vec<u8> }
+
+    impl Reader for ShortReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::IoResult<uint> {
+            match self.data.shift() {
+                Some(b) => { buf[0] = b; Ok(1) }
+                None => Err(io::standard_error(io::EndOfFile)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_chained_reader_short_reads() {
+        let rs = vec!(ShortReader { data: vec!(0, 1) },
+                      ShortReader { data: vec!(2) });
+        let mut r = ChainedReader::new(rs.move_iter());
+        let mut buf = [0, ..4];
+        assert_eq!(r.read(buf), Ok(1));
+        assert_eq!(buf[0], 0);
+        assert_eq!(vec!(1, 2), r.read_to_end().unwrap());
+    }
+
+    #[test]
+    fn test_tee_reader_short_reads() {
+        let mut r = TeeReader::new(ShortReader { data: vec!(0, 1, 2) },
+                                   MemWriter::new());
+        let mut buf = [9, ..4];
+        assert_eq!(r.read(buf), Ok(1));
+        assert_eq!(vec!(1, 2), r.read_to_end().unwrap());
+        let (_, w) = r.unwrap();
+        assert_eq!(vec!(0, 1, 2), w.unwrap());
+    }
+
+    #[test]
+    fn test_multi_writer_partial_failure() {
+        struct FailWriter;
+        impl Writer for FailWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::IoResult<()> {
+                Err(io::standard_error(io::OtherIoError))
+            }
+        }
+
+        let (tx, rx) = channel();
+        let mut w = MultiWriter::new(vec!(box FailWriter as Box<Writer>,
+                                          box ChanWriter::new(tx)
+                                                as Box<Writer>));
+        // the write still reaches the second writer
+        assert_eq!(w.write([1, 2]).err().unwrap().kind, io::OtherIoError);
+        assert_eq!(rx.recv_unwrap().as_slice(), &[1, 2]);
+    }
+
     #[test]
     fn test_tee_reader() {
         let mut r = TeeReader::new(MemReader::new(vec!(0, 1, 2)),
@@ -133,6 +133,7 @@ extern crate libc;
 #[cfg(test)] pub use realstd::ops;
 #[cfg(test)] pub use realstd::cmp;
 #[cfg(test)] pub use realstd::ty;
+#[cfg(test)] pub use realstd::nonzero;
 
 
 // NB: These reexports are in the order they should be listed in rustdoc
@@ -156,6 +157,7 @@ pub use core::raw;
 pub use core::simd;
 pub use core::tuple;
 #[cfg(not(test))] pub use core::ty;
+#[cfg(not(test))] pub use core::nonzero;
 pub use core::result;
 
 pub use alloc::owned;
@@ -33,11 +33,15 @@ key_vector.replace(Some(~[4]));
 assert_eq!(*key_vector.get().unwrap(), ~[4]);
 ```
 
+Keys which can't be declared statically, for example those belonging to code
+which is loaded at runtime, can instead be created with `DynamicKey::new`.
+
 */
 
 // Casting 'Arcane Sight' reveals an overwhelming aura of Transmutation
 // magic.
 
+use clone::Clone;
 use iter::{Iterator};
 use kinds::Send;
 use kinds::marker;
@@ -49,6 +53,8 @@ use owned::Box;
 use raw;
 use rt::task::{Task, LocalStorage};
 use slice::{ImmutableVector, MutableVector};
+use str::Str;
+use string::String;
 use vec::Vec;
 
 /**
@@ -91,8 +97,11 @@ impl<T: 'static> LocalData for T {}
 //
 // n.b. If TLS is used heavily in future, this could be made more efficient with
 //      a proper map.
+//
+// Each entry is the key, the value, the number of outstanding loans on the
+// value, and whether the key is a `DynamicKey`.
 #[doc(hidden)]
-pub type Map = Vec<Option<(*u8, TLSValue, uint)>>;
+pub type Map = Vec<Option<(*u8, TLSValue, uint, bool)>>;
 type TLSValue = Box<LocalData:Send>;
 
 // Gets the map from the runtime. Lazily initialises if not done so already.
@@ -130,7 +139,6 @@ pub struct Ref<T> {
     // FIXME #12808: strange names to try to avoid interfering with
     // field accesses of the contained type via Deref
     _ptr: &'static T,
-    _key: Key<T>,
     _index: uint,
     _nosend: marker::NoSend,
 }
@@ -156,53 +164,7 @@ impl<T: 'static> KeyValue<T> {
     /// assert_eq!(foo.replace(None), Some(4));
     /// ```
     pub fn replace(&'static self, data: Option<T>) -> Option<T> {
-        let map = unsafe { get_local_map() };
-        let keyval = key_to_key_value(self);
-
-        // When the task-local map is destroyed, all the data needs to be
-        // cleaned up. For this reason we can't do some clever tricks to store
-        // '~T' as a '*c_void' or something like that. To solve the problem, we
-        // cast everything to a trait (LocalData) which is then stored inside
-        // the map.  Upon destruction of the map, all the objects will be
-        // destroyed and the traits have enough information about them to
-        // destroy themselves.
-        //
-        // Additionally, the type of the local data map must ascribe to Send, so
-        // we do the transmute here to add the Send bound back on. This doesn't
-        // actually matter because TLS will always own the data (until its moved
-        // out) and we're not actually sending it to other schedulers or
-        // anything.
-        let newval = data.map(|d| {
-            let d = box d as Box<LocalData>;
-            let d: Box<LocalData:Send> = unsafe { mem::transmute(d) };
-            (keyval, d, 0)
-        });
-
-        let pos = match self.find(map) {
-            Some((i, _, &0)) => Some(i),
-            Some((_, _, _)) => fail!("TLS value cannot be replaced because it \
-                                      is already borrowed"),
-            None => map.iter().position(|entry| entry.is_none()),
-        };
-
-        match pos {
-            Some(i) => {
-                replace(map.get_mut(i), newval).map(|(_, data, _)| {
-                    // Move `data` into transmute to get out the memory that it
-                    // owns, we must free it manually later.
-                    let t: raw::TraitObject = unsafe { mem::transmute(data) };
-                    let alloc: Box<T> = unsafe { mem::transmute(t.data) };
-
-                    // Now that we own `alloc`, we can just move out of it as we
-                    // would with any other data.
-                    *alloc
-                })
-            }
-            None => {
-                map.push(newval);
-                None
-            }
-        }
+        replace_value(key_to_key_value(self), false, data)
     }
 
     /// Borrows a value from TLS.
@@ -223,36 +185,227 @@ impl<T: 'static> KeyValue<T> {
     /// assert_eq!(*key.get().unwrap(), 3);
     /// ```
     pub fn get(&'static self) -> Option<Ref<T>> {
-        let map = unsafe { get_local_map() };
+        get_value(key_to_key_value(self)).map(|(pos, ptr)| {
+            Ref { _ptr: ptr, _index: pos, _nosend: marker::NoSend }
+        })
+    }
+}
+
+/// A task-local data key which is created at runtime.
+///
+/// Each call to `DynamicKey::new` creates a new key, distinct from all other
+/// keys, which can then be used in the same way as a key declared with
+/// `local_data_key!`. Keys can be cloned and sent to other tasks, and each
+/// task has its own value for the key.
+///
+/// A key may be given a destructor, which is called with the value stored for
+/// the key when a task exits (or when the value is otherwise destroyed without
+/// having been taken out with `replace`).
+///
+/// Every key has a name, which is used to identify it in diagnostics such as
+/// `each_dynamic`. A key takes up a small amount of memory which is never
+/// freed, so that it stays distinct from any keys created later on.
+///
+/// # Example
+///
+/// ```
+/// use std::local_data::DynamicKey;
+///
+/// fn close(fd: int) { println!("closing {}", fd); }
+///
+/// let key = DynamicKey::with_destructor("plugin-fd", close);
+/// key.replace(Some(3));
+/// assert_eq!(*key.get().unwrap(), 3);
+/// // `close(3)` is called when this task exits
+/// ```
+pub struct DynamicKey<T> {
+    info: &'static DynamicKeyInfo,
+    dtor: Option<fn(T)>,
+}
+
+struct DynamicKeyInfo {
+    name: String,
+}
+
+// The value stored in TLS for a dynamic key, which is passed to the key's
+// destructor when it is dropped.
+struct DynamicValue<T> {
+    value: Option<T>,
+    dtor: Option<fn(T)>,
+}
+
+#[unsafe_destructor]
+impl<T: 'static> Drop for DynamicValue<T> {
+    fn drop(&mut self) {
+        match (self.value.take(), self.dtor) {
+            (Some(value), Some(dtor)) => dtor(value),
+            _ => {}
+        }
+    }
+}
+
+impl<T: 'static> DynamicKey<T> {
+    /// Creates a new key named `name`, whose values are dropped normally.
+    pub fn new(name: &str) -> DynamicKey<T> {
+        DynamicKey::create(name, None)
+    }
+
+    /// Creates a new key named `name`, whose values are passed to `dtor` when
+    /// they are destroyed.
+    pub fn with_destructor(name: &str, dtor: fn(T)) -> DynamicKey<T> {
+        DynamicKey::create(name, Some(dtor))
+    }
+
+    fn create(name: &str, dtor: Option<fn(T)>) -> DynamicKey<T> {
+        let info = box DynamicKeyInfo { name: String::from_str(name) };
+        DynamicKey { info: unsafe { mem::transmute(info) }, dtor: dtor }
+    }
+
+    /// Returns the name this key was created with.
+    pub fn name<'a>(&'a self) -> &'a str { self.info.name.as_slice() }
+
+    /// Replaces a value in task local storage, with the same semantics as
+    /// `KeyValue::replace`.
+    ///
+    /// The previous value is returned without being passed to the key's
+    /// destructor.
+    ///
+    /// # Failure
+    ///
+    /// This function will fail if this key is present in TLS and currently on
+    /// loan with the `get` method.
+    pub fn replace(&self, data: Option<T>) -> Option<T> {
+        let data = data.map(|d| DynamicValue { value: Some(d), dtor: self.dtor });
+        replace_value(self.key_value(), true, data).map(|mut old| {
+            old.value.take().unwrap()
+        })
+    }
 
-        self.find(map).map(|(pos, data, loan)| {
-            *loan += 1;
-
-            // data was created with `~T as ~LocalData`, so we extract
-            // pointer part of the trait, (as ~T), and then use
-            // compiler coercions to achieve a '&' pointer.
-            let ptr = unsafe {
-                let data = data as *Box<LocalData:Send> as *raw::TraitObject;
-                &mut *((*data).data as *mut T)
-            };
-            Ref { _ptr: ptr, _index: pos, _nosend: marker::NoSend, _key: self }
+    /// Borrows a value from TLS, with the same semantics as `KeyValue::get`.
+    pub fn get(&self) -> Option<Ref<T>> {
+        get_value::<DynamicValue<T>>(self.key_value()).map(|(pos, ptr)| {
+            Ref { _ptr: ptr.value.get_ref(), _index: pos,
+                  _nosend: marker::NoSend }
         })
     }
 
-    fn find<'a>(&'static self,
-                map: &'a mut Map) -> Option<(uint, &'a TLSValue, &'a mut uint)>{
-        let key_value = key_to_key_value(self);
-        map.mut_iter().enumerate().filter_map(|(i, entry)| {
+    fn key_value(&self) -> *u8 {
+        self.info as *DynamicKeyInfo as *u8
+    }
+}
+
+impl<T: 'static> Clone for DynamicKey<T> {
+    fn clone(&self) -> DynamicKey<T> {
+        DynamicKey { info: self.info, dtor: self.dtor }
+    }
+}
+
+/// Calls `f` with the name of each dynamic key which has a value in the
+/// current task's local storage, along with whether that value is currently
+/// on loan with `get`. Iteration stops early if `f` returns `false`, in which
+/// case `false` is returned.
+///
+/// This is meant for diagnostics, such as finding out which plugins have data
+/// stored in a task. Keys declared with `local_data_key!` are not included, as
+/// they have no name.
+pub fn each_dynamic(f: |&str, bool| -> bool) -> bool {
+    // Collect the keys up front, as `f` may itself use TLS and move the map
+    // out from under us.
+    let keys: Vec<(&'static str, bool)> = unsafe {
+        get_local_map().iter().filter_map(|entry| {
             match *entry {
-                Some((k, ref data, ref mut loan)) if k == key_value => {
-                    Some((i, data, loan))
+                Some((k, _, loan, true)) => {
+                    let info = &*(k as *DynamicKeyInfo);
+                    Some((info.name.as_slice(), loan > 0))
                 }
-                _ => None
+                _ => None,
             }
-        }).next()
+        }).collect()
+    };
+    keys.move_iter().all(|(name, borrowed)| f(name, borrowed))
+}
+
+fn replace_value<T: 'static>(keyval: *u8, dynamic: bool,
+                             data: Option<T>) -> Option<T> {
+    let map = unsafe { get_local_map() };
+
+    // When the task-local map is destroyed, all the data needs to be
+    // cleaned up. For this reason we can't do some clever tricks to store
+    // '~T' as a '*c_void' or something like that. To solve the problem, we
+    // cast everything to a trait (LocalData) which is then stored inside
+    // the map.  Upon destruction of the map, all the objects will be
+    // destroyed and the traits have enough information about them to
+    // destroy themselves.
+    //
+    // Additionally, the type of the local data map must ascribe to Send, so
+    // we do the transmute here to add the Send bound back on. This doesn't
+    // actually matter because TLS will always own the data (until its moved
+    // out) and we're not actually sending it to other schedulers or
+    // anything.
+    let newval = data.map(|d| {
+        let d = box d as Box<LocalData>;
+        let d: Box<LocalData:Send> = unsafe { mem::transmute(d) };
+        (keyval, d, 0, dynamic)
+    });
+
+    let pos = match find(keyval, map) {
+        Some((i, _, &0)) => Some(i),
+        Some((_, _, _)) => fail!("TLS value cannot be replaced because it \
+                                  is already borrowed"),
+        None => map.iter().position(|entry| entry.is_none()),
+    };
+
+    match pos {
+        Some(i) => {
+            replace(map.get_mut(i), newval).map(|(_, data, _, _)| {
+                // Move `data` into transmute to get out the memory that it
+                // owns, we must free it manually later.
+                let t: raw::TraitObject = unsafe { mem::transmute(data) };
+                let alloc: Box<T> = unsafe { mem::transmute(t.data) };
+
+                // Now that we own `alloc`, we can just move out of it as we
+                // would with any other data.
+                *alloc
+            })
+        }
+        None => {
+            map.push(newval);
+            None
+        }
     }
 }
 
+// Takes out a new loan on the value for `keyval`, returning its position in
+// the map along with the value.
+fn get_value<T: 'static>(keyval: *u8) -> Option<(uint, &'static T)> {
+    let map = unsafe { get_local_map() };
+
+    find(keyval, map).map(|(pos, data, loan)| {
+        *loan += 1;
+
+        // data was created with `~T as ~LocalData`, so we extract
+        // pointer part of the trait, (as ~T), and then use
+        // compiler coercions to achieve a '&' pointer.
+        let ptr = unsafe {
+            let data = data as *Box<LocalData:Send> as *raw::TraitObject;
+            &*((*data).data as *T)
+        };
+        (pos, ptr)
+    })
+}
+
+fn find<'a>(keyval: *u8,
+            map: &'a mut Map) -> Option<(uint, &'a TLSValue, &'a mut uint)> {
+    map.mut_iter().enumerate().filter_map(|(i, entry)| {
+        match *entry {
+            Some((k, ref data, ref mut loan, _)) if k == keyval => {
+                Some((i, data, loan))
+            }
+            _ => None
+        }
+    }).next()
+}
+
 impl<T: 'static> Deref<T> for Ref<T> {
     fn deref<'a>(&'a self) -> &'a T { self._ptr }
 }
@@ -262,7 +415,7 @@ impl<T: 'static> Drop for Ref<T> {
     fn drop(&mut self) {
         let map = unsafe { get_local_map() };
 
-        let (_, _, ref mut loan) = *map.get_mut(self._index).get_mut_ref();
+        let (_, _, ref mut loan, _) = *map.get_mut(self._index).get_mut_ref();
         *loan -= 1;
     }
 }
@@ -271,6 +424,7 @@ impl<T: 'static> Drop for Ref<T> {
 mod tests {
     use prelude::*;
     use super::*;
+    use native;
     use owned::Box;
     use task;
 
@@ -425,4 +579,95 @@ mod tests {
         let _k = key.get();
         key.replace(Some(4));
     }
+
+    #[test]
+    fn test_dynamic_key() {
+        let key1 = DynamicKey::new("key1");
+        let key2 = DynamicKey::new("key2");
+        assert!(key1.get().is_none());
+        key1.replace(Some(1));
+        key2.replace(Some(2));
+        assert_eq!(*key1.get().unwrap(), 1);
+        assert_eq!(*key2.get().unwrap(), 2);
+        assert_eq!(key1.clone().replace(None), Some(1));
+        assert!(key1.get().is_none());
+        assert_eq!(key2.name(), "key2");
+    }
+
+    #[test]
+    fn test_dynamic_key_multitask() {
+        let key = DynamicKey::new("multitask");
+        key.replace(Some("parent data".to_string()));
+        let key2 = key.clone();
+        task::try(proc() {
+            assert!(key2.get().is_none());
+            key2.replace(Some("child data".to_string()));
+        }).unwrap();
+        assert!(key.get().unwrap().as_slice() == "parent data");
+    }
+
+    fn notify(tx: Sender<()>) { tx.send(()); }
+
+    #[test]
+    fn test_dynamic_key_destructor() {
+        let key = DynamicKey::with_destructor("dtor", notify);
+        let (tx, rx) = channel();
+        let key2 = key.clone();
+        task::spawn(proc() {
+            key2.replace(Some(tx));
+        });
+        rx.recv_unwrap();
+
+        let (tx, rx) = channel();
+        let key2 = key.clone();
+        native::task::spawn(proc() {
+            key2.replace(Some(tx));
+        });
+        rx.recv_unwrap();
+
+        // values which are taken out again aren't destroyed
+        let (tx, rx) = channel();
+        key.replace(Some(tx));
+        let tx = key.replace(None).unwrap();
+        assert!(rx.try_recv().is_err());
+        drop(tx);
+    }
+
+    #[test]
+    fn test_dynamic_key_destructor_on_failure() {
+        let key = DynamicKey::with_destructor("fail", notify);
+        let (tx, rx) = channel();
+        assert!(task::try(proc() {
+            key.replace(Some(tx));
+            fail!();
+        }).is_err());
+        rx.recv_unwrap();
+    }
+
+    #[test]
+    fn test_each_dynamic() {
+        static static_key: Key<int> = &Key;
+        let key1 = DynamicKey::new("each1");
+        let key2 = DynamicKey::new("each2");
+        let key3: DynamicKey<int> = DynamicKey::new("each3");
+        task::try(proc() {
+            static_key.replace(Some(0));
+            key1.replace(Some(1));
+            key2.replace(Some(2));
+            let _r = key2.get();
+
+            let mut seen = Vec::new();
+            assert!(each_dynamic(|name, borrowed| {
+                seen.push((name.to_string(), borrowed));
+                true
+            }));
+            assert_eq!(seen, vec!(("each1".to_string(), false),
+                                  ("each2".to_string(), true)));
+
+            let mut count = 0;
+            assert!(!each_dynamic(|_, _| { count += 1; false }));
+            assert_eq!(count, 1);
+            drop(key3);
+        }).unwrap();
+    }
 }
@@ -41,7 +41,7 @@ macro_rules! fail(
         fail!("explicit failure")
     );
     ($msg:expr) => (
-        ::std::rt::begin_unwind($msg, file!(), line!())
+        ::std::rt::begin_unwind_col($msg, file!(), line!(), col!() + 1)
     );
     ($fmt:expr, $($arg:tt)*) => ({
         // a closure can't have return type !, so we need a full
@@ -57,12 +57,36 @@ macro_rules! fail(
         // up with the number of calls to fail!()
         #[inline(always)]
         fn run_fmt(fmt: &::std::fmt::Arguments) -> ! {
-            ::std::rt::begin_unwind_fmt(fmt, file!(), line!())
+            ::std::rt::begin_unwind_fmt(fmt, file!(), line!(), col!() + 1)
         }
         format_args!(run_fmt, $fmt, $($arg)*)
     });
 )
 
+/// Fails the current task like `fail!`, additionally requesting that the
+/// process exit with `status` if this is the root task of the program.
+///
+/// This is useful for programs hosted by foreign code, where the exit status
+/// is the only way to communicate failure back to the host.
+///
+/// # Example
+///
+/// ```should_fail
+/// # #![allow(unreachable_code)]
+/// fail_with_status!(3, "configuration file is missing");
+/// fail_with_status!(3, "missing key: {}", "port");
+/// ```
+#[macro_export]
+macro_rules! fail_with_status(
+    ($status:expr, $msg:expr) => (
+        ::std::rt::begin_unwind_col(::std::task::FailureStatus::new($status, $msg),
+                                    file!(), line!(), col!() + 1)
+    );
+    ($status:expr, $fmt:expr, $($arg:tt)*) => (
+        fail_with_status!($status, format!($fmt, $($arg)*))
+    );
+)
+
 /// Ensure that a boolean expression is `true` at runtime.
 ///
 /// This will invoke the `fail!` macro if the provided expression cannot be
@@ -341,17 +365,47 @@ macro_rules! vec(
 /// spawn(proc() { tx2.send(calculate_the_answer()) });
 ///
 /// select! (
-///     () = rx1.recv() => println!("the long running task finished first"),
-///     answer = rx2.recv() => {
+///     () = rx1.recv_unwrap() => println!("the long running task finished first"),
+///     answer = rx2.recv_unwrap() => {
 ///         println!("the answer was: {}", answer);
 ///     }
 /// )
 /// ```
 ///
+/// A `timeout` arm may be given first, in which case its code is run if none of
+/// the receivers have an event within that many milliseconds.
+///
+/// ```
+/// let (tx, rx) = channel::<int>();
+///
+/// select! (
+///     timeout(100) => println!("nothing was received in 100ms"),
+///     val = rx.recv_unwrap() => println!("received {}", val)
+/// )
+/// # drop(tx);
+/// ```
+///
 /// For more information about select, see the `std::comm::Select` structure.
 #[macro_export]
-#[experimental]
 macro_rules! select {
+    (
+        timeout($ms:expr) => $timeout:expr,
+        $($name:pat = $rx:ident.$meth:ident() => $code:expr),+
+    ) => ({
+        use std::comm::Select;
+        let sel = Select::new();
+        $( let mut $rx = sel.handle(&$rx); )+
+        unsafe {
+            $( $rx.add(); )+
+        }
+        match sel.wait_timeout($ms) {
+            None => $timeout,
+            Some(ret) => {
+                $( if ret == $rx.id() { let $name = $rx.$meth(); $code } else )+
+                { unreachable!() }
+            }
+        }
+    });
     (
         $($name:pat = $rx:ident.$meth:ident() => $code:expr),+
     ) => ({
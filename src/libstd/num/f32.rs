@@ -15,7 +15,6 @@
 
 use prelude::*;
 
-use from_str::FromStr;
 use intrinsics;
 use libc::c_int;
 use num::strconv;
@@ -386,70 +385,49 @@ pub fn from_str_hex(num: &str) -> Option<f32> {
                              strconv::ExpBin, false, false)
 }
 
-impl FromStr for f32 {
-    /// Convert a string in base 10 to a float.
-    /// Accepts an optional decimal exponent.
-    ///
-    /// This function accepts strings such as
-    ///
-    /// * '3.14'
-    /// * '+3.14', equivalent to '3.14'
-    /// * '-3.14'
-    /// * '2.5E10', or equivalently, '2.5e10'
-    /// * '2.5E-10'
-    /// * '.' (understood as 0)
-    /// * '5.'
-    /// * '.5', or, equivalently,  '0.5'
-    /// * '+inf', 'inf', '-inf', 'NaN'
-    ///
-    /// Leading and trailing whitespace represent an error.
-    ///
-    /// # Arguments
-    ///
-    /// * num - A string
-    ///
-    /// # Return value
-    ///
-    /// `None` if the string did not represent a valid number.  Otherwise,
-    /// `Some(n)` where `n` is the floating-point number represented by `num`.
-    #[inline]
-    fn from_str(val: &str) -> Option<f32> {
-        strconv::from_str_common(val, 10u, true, true, true,
-                                 strconv::ExpDec, false, false)
-    }
-}
-
-impl num::FromStrRadix for f32 {
-    /// Convert a string in a given base to a float.
-    ///
-    /// Due to possible conflicts, this function does **not** accept
-    /// the special values `inf`, `-inf`, `+inf` and `NaN`, **nor**
-    /// does it recognize exponents of any kind.
-    ///
-    /// Leading and trailing whitespace represent an error.
-    ///
-    /// # Arguments
-    ///
-    /// * num - A string
-    /// * radix - The base to use. Must lie in the range [2 .. 36]
-    ///
-    /// # Return value
-    ///
-    /// `None` if the string did not represent a valid number. Otherwise,
-    /// `Some(n)` where `n` is the floating-point number represented by `num`.
-    #[inline]
-    fn from_str_radix(val: &str, rdx: uint) -> Option<f32> {
-        strconv::from_str_common(val, rdx, true, true, false,
-                                 strconv::ExpNone, false, false)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use f32::*;
     use num::*;
     use num;
 
+    #[test]
+    fn test_total_cmp() {
+        use prelude::*;
+        use cmp::{Less, Equal, Greater};
+        use mem;
+        // the sign of NAN itself is unspecified, so build both by hand
+        let nan: f32 = unsafe { mem::transmute(0x7fc00000u32) };
+        let neg_nan: f32 = unsafe { mem::transmute(0xffc00000u32) };
+        let ordered = [neg_nan, NEG_INFINITY, -1.0, -0.0, 0.0, MIN_POS_VALUE,
+                       1.0, INFINITY, nan];
+        for (i, &a) in ordered.iter().enumerate() {
+            for (j, &b) in ordered.iter().enumerate() {
+                let expected = if i < j { Less } else if i > j { Greater } else { Equal };
+                assert_eq!(a.total_cmp(b), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_wrapper() {
+        use prelude::*;
+        use cmp::TotalF32;
+        use mem;
+        let nan: f32 = unsafe { mem::transmute(0x7fc00000u32) };
+        let mut v = vec![TotalF32(nan), TotalF32(1.0), TotalF32(-0.0),
+                         TotalF32(NEG_INFINITY), TotalF32(0.0)];
+        v.sort();
+        let sorted: Vec<f32> = v.iter().map(|&TotalF32(x)| x).collect();
+        assert_eq!(sorted.get(0), &NEG_INFINITY);
+        assert!(sorted.get(1).is_negative() && *sorted.get(1) == 0.0);
+        assert!(sorted.get(2).is_positive() && *sorted.get(2) == 0.0);
+        assert_eq!(sorted.get(3), &1.0);
+        assert!(sorted.get(4).is_nan());
+        assert!(TotalF32(NAN) == TotalF32(NAN));
+        assert!(TotalF32(-0.0) != TotalF32(0.0));
+    }
+
     #[test]
     fn test_min_nan() {
         assert_eq!(NAN.min(2.0), 2.0);
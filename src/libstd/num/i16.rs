@@ -10,13 +10,11 @@
 
 //! Operations and constants for signed 16-bits integers (`i16` type)
 
-use from_str::FromStr;
-use num::{ToStrRadix, FromStrRadix};
-use num::strconv;
-use option::Option;
+use num::ToStrRadix;
 use slice::ImmutableVector;
 use string::String;
 
 pub use core::i16::{BITS, BYTES, MIN, MAX};
+pub use core::i16::parse_bytes;
 
 int_module!(i16)
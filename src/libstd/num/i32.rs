@@ -10,13 +10,11 @@
 
 //! Operations and constants for signed 32-bits integers (`i32` type)
 
-use from_str::FromStr;
-use num::{ToStrRadix, FromStrRadix};
-use num::strconv;
-use option::Option;
+use num::ToStrRadix;
 use slice::ImmutableVector;
 use string::String;
 
 pub use core::i32::{BITS, BYTES, MIN, MAX};
+pub use core::i32::parse_bytes;
 
 int_module!(i32)
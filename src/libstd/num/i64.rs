@@ -10,13 +10,11 @@
 
 //! Operations and constants for signed 64-bits integers (`i64` type)
 
-use from_str::FromStr;
-use num::{ToStrRadix, FromStrRadix};
-use num::strconv;
-use option::Option;
+use num::ToStrRadix;
 use slice::ImmutableVector;
 use string::String;
 
 pub use core::i64::{BITS, BYTES, MIN, MAX};
+pub use core::i64::parse_bytes;
 
 int_module!(i64)
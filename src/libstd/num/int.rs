@@ -10,13 +10,11 @@
 
 //! Operations and constants for architecture-sized signed integers (`int` type)
 
-use from_str::FromStr;
-use num::{ToStrRadix, FromStrRadix};
-use num::strconv;
-use option::Option;
+use num::ToStrRadix;
 use slice::ImmutableVector;
 use string::String;
 
 pub use core::int::{BITS, BYTES, MIN, MAX};
+pub use core::int::parse_bytes;
 
 int_module!(int)
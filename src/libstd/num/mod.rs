@@ -15,7 +15,6 @@
 
 #![allow(missing_doc)]
 
-use option::Option;
 use string::String;
 
 #[cfg(test)] use fmt::Show;
@@ -24,7 +23,9 @@ pub use core::num::{Num, div_rem, Zero, zero, One, one};
 pub use core::num::{Signed, abs, abs_sub, signum};
 pub use core::num::{Unsigned, pow, Bounded, Bitwise};
 pub use core::num::{Primitive, Int, Saturating};
-pub use core::num::{CheckedAdd, CheckedSub, CheckedMul, CheckedDiv};
+pub use core::num::{CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, CheckedRem};
+pub use core::num::{OverflowingAdd, OverflowingSub, OverflowingMul};
+pub use core::num::Wrapping;
 pub use core::num::{cast, FromPrimitive, NumCast, ToPrimitive};
 pub use core::num::{next_power_of_two, is_power_of_two};
 pub use core::num::{checked_next_power_of_two};
@@ -33,6 +34,7 @@ pub use core::num::{from_uint, from_u8, from_u16, from_u32, from_u64};
 pub use core::num::{from_f32, from_f64};
 pub use core::num::{FPCategory, FPNaN, FPInfinite, FPZero, FPSubnormal};
 pub use core::num::{FPNormal, Float};
+pub use core::num::{FromStrRadix, from_str_radix};
 
 pub mod strconv;
 
@@ -115,16 +117,6 @@ pub trait ToStrRadix {
     fn to_str_radix(&self, radix: uint) -> String;
 }
 
-/// A generic trait for converting a string with a radix (base) to a value
-pub trait FromStrRadix {
-    fn from_str_radix(str: &str, radix: uint) -> Option<Self>;
-}
-
-/// A utility function that just calls FromStrRadix::from_str_radix.
-pub fn from_str_radix<T: FromStrRadix>(str: &str, radix: uint) -> Option<T> {
-    FromStrRadix::from_str_radix(str, radix)
-}
-
 /// Helper function for testing numeric operations
 #[cfg(test)]
 pub fn test_num<T:Num + NumCast + Show>(ten: T, two: T) {
@@ -659,6 +651,83 @@ mod tests {
         assert_eq!(third.checked_mul(&4), None);
     }
 
+    #[test]
+    fn test_saturating_mul_uint() {
+        use uint::MAX;
+        assert_eq!(3u.saturating_mul(5u), 15u);
+        assert_eq!(3u.saturating_mul(MAX), MAX);
+        assert_eq!(MAX.saturating_mul(0u), 0u);
+    }
+
+    #[test]
+    fn test_saturating_mul_int() {
+        use int::{MIN,MAX};
+        assert_eq!(3i.saturating_mul(-5i), -15i);
+        assert_eq!(MAX.saturating_mul(2i), MAX);
+        assert_eq!(MIN.saturating_mul(-2i), MAX);
+        assert_eq!(MAX.saturating_mul(-2i), MIN);
+        assert_eq!((-2i).saturating_mul(MAX), MIN);
+    }
+
+    #[test]
+    fn test_checked_rem() {
+        assert_eq!(7u.checked_rem(&3), Some(1));
+        assert_eq!(7u.checked_rem(&0), None);
+        assert_eq!((-7i).checked_rem(&3), Some(-1));
+        assert_eq!(int::MIN.checked_rem(&-1), None);
+    }
+
+    #[test]
+    fn test_overflowing_add() {
+        assert_eq!(250u8.overflowing_add(&5), (255, false));
+        assert_eq!(250u8.overflowing_add(&10), (4, true));
+        assert_eq!(i8::MAX.overflowing_add(&1), (i8::MIN, true));
+        assert_eq!(uint::MAX.overflowing_add(&1), (0, true));
+    }
+
+    #[test]
+    fn test_overflowing_sub() {
+        assert_eq!(5u8.overflowing_sub(&5), (0, false));
+        assert_eq!(0u8.overflowing_sub(&1), (255, true));
+        assert_eq!(i8::MIN.overflowing_sub(&1), (i8::MAX, true));
+        assert_eq!(0u.overflowing_sub(&1), (uint::MAX, true));
+    }
+
+    #[test]
+    fn test_overflowing_mul() {
+        assert_eq!(16u8.overflowing_mul(&15), (240, false));
+        assert_eq!(16u8.overflowing_mul(&16), (0, true));
+        assert_eq!(i8::MIN.overflowing_mul(&-1), (i8::MIN, true));
+        assert_eq!(int::MAX.overflowing_mul(&2), (-2, true));
+    }
+
+    #[test]
+    fn test_wrapping_arith() {
+        assert_eq!(Wrapping(250u8) + Wrapping(10), Wrapping(4));
+        assert_eq!(Wrapping(0u8) - Wrapping(1), Wrapping(255));
+        assert_eq!(Wrapping(16u8) * Wrapping(16), Wrapping(0));
+        assert_eq!(Wrapping(i8::MAX) + Wrapping(1), Wrapping(i8::MIN));
+        assert_eq!(Wrapping(uint::MAX) + Wrapping(1), Wrapping(0));
+    }
+
+    #[test]
+    fn test_wrapping_bitwise() {
+        assert_eq!(!Wrapping(0u8), Wrapping(255));
+        assert_eq!(Wrapping(0b1100u8) & Wrapping(0b1010), Wrapping(0b1000));
+        assert_eq!(Wrapping(0b1100u8) | Wrapping(0b1010), Wrapping(0b1110));
+        assert_eq!(Wrapping(0b1100u8) ^ Wrapping(0b1010), Wrapping(0b0110));
+        assert_eq!(Wrapping(1u8) << 9, Wrapping(2));
+        assert_eq!(Wrapping(128u8) >> 8, Wrapping(128));
+    }
+
+    #[test]
+    fn test_wrapping_conversions() {
+        let x: Option<Wrapping<u8>> = FromPrimitive::from_u64(200);
+        assert_eq!(x, Some(Wrapping(200)));
+        assert_eq!(Wrapping(200u8).to_i8(), None);
+        assert_eq!(Wrapping(-1i).to_uint(), None);
+    }
+
     macro_rules! test_next_power_of_two(
         ($test_name:ident, $T:ident) => (
             fn $test_name() {
@@ -10,13 +10,11 @@
 
 //! Operations and constants for unsigned 8-bits integers (`u8` type)
 
-use from_str::FromStr;
-use num::{ToStrRadix, FromStrRadix};
-use num::strconv;
-use option::Option;
+use num::ToStrRadix;
 use slice::ImmutableVector;
 use string::String;
 
 pub use core::u8::{BITS, BYTES, MIN, MAX};
+pub use core::u8::parse_bytes;
 
 uint_module!(u8)
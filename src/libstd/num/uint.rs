@@ -10,13 +10,11 @@
 
 //! Operations and constants for architecture-sized unsigned integers (`uint` type)
 
-use from_str::FromStr;
-use num::{ToStrRadix, FromStrRadix};
-use num::strconv;
-use option::Option;
+use num::ToStrRadix;
 use slice::ImmutableVector;
 use string::String;
 
 pub use core::uint::{BITS, BYTES, MIN, MAX};
+pub use core::uint::parse_bytes;
 
 uint_module!(uint)
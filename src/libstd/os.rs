@@ -31,15 +31,18 @@
 use clone::Clone;
 use container::Container;
 use fmt;
+use io::fs::File;
 use iter::Iterator;
 use libc::{c_void, c_int};
 use libc;
+use mem;
 use ops::Drop;
 use option::{Some, None, Option};
 use os;
 use path::{Path, GenericPath};
 use ptr::RawPtr;
 use ptr;
+use raw;
 use result::{Err, Ok, Result};
 use slice::{Vector, ImmutableVector, MutableVector, OwnedVector};
 use str::{Str, StrSlice, StrAllocating};
@@ -335,15 +338,7 @@ pub fn getenv_as_bytes(n: &str) -> Option<Vec<u8>> {
 ///
 /// Fails if `n` or `v` have any interior NULs.
 pub fn setenv(n: &str, v: &str) {
-    unsafe {
-        with_env_lock(|| {
-            n.with_c_str(|nbuf| {
-                v.with_c_str(|vbuf| {
-                    libc::funcs::posix01::unistd::setenv(nbuf, vbuf, 1);
-                })
-            })
-        })
-    }
+    setenv_as_bytes(n, v.as_bytes())
 }
 
 
@@ -363,6 +358,33 @@ pub fn setenv(n: &str, v: &str) {
     }
 }
 
+#[cfg(unix)]
+/// Sets the environment variable `n` to the byte vector `v` for the currently
+/// running process. Unlike `setenv`, the value need not be valid UTF-8.
+///
+/// # Failure
+///
+/// Fails if `n` or `v` have any interior NULs.
+pub fn setenv_as_bytes(n: &str, v: &[u8]) {
+    unsafe {
+        with_env_lock(|| {
+            n.with_c_str(|nbuf| {
+                v.with_c_str(|vbuf| {
+                    libc::funcs::posix01::unistd::setenv(nbuf, vbuf, 1);
+                })
+            })
+        })
+    }
+}
+
+#[cfg(windows)]
+/// Sets the environment variable `n` to the byte vector `v` for the currently
+/// running process. Environment variables are UTF-16 on windows, so invalid
+/// UTF-8 bytes in `v` are replaced with \uFFFD.
+pub fn setenv_as_bytes(n: &str, v: &[u8]) {
+    setenv(n, str::from_utf8_lossy(v).as_slice())
+}
+
 /// Remove a variable from the environment entirely
 ///
 /// # Failure
@@ -960,7 +982,6 @@ pub fn page_size() -> uint {
 /// Returns the page size of the current architecture in bytes.
 #[cfg(windows)]
 pub fn page_size() -> uint {
-    use mem;
     unsafe {
         let mut info = mem::zeroed();
         libc::GetSystemInfo(&mut info);
@@ -1013,6 +1034,11 @@ pub enum MapOption {
     /// When using `MapFd`, the start of the map is `uint` bytes from the start
     /// of the file.
     MapOffset(uint),
+    /// When using `MapFd`, changes to the memory are carried through to the
+    /// file, and are visible to other mappings of it. Corresponds to
+    /// `MAP_SHARED` on POSIX, which uses `MAP_PRIVATE` otherwise. File mappings
+    /// are always shared on Windows.
+    MapShared,
     /// On POSIX, this can be used to specify the default flags passed to
     /// `mmap`. By default it uses `MAP_PRIVATE` and, if not using `MapFd`,
     /// `MAP_ANON`. This will override both of those. This is platform-specific
@@ -1049,8 +1075,7 @@ pub enum MapError {
     /// Unsupported combination of protection flags
     /// (`MapReadable`/`MapWritable`/`MapExecutable`).
     ErrUnsupProt,
-    /// When using `MapFd`, `MapOffset` was given (Windows does not support this
-    /// at all)
+    /// `MapOffset` was given without `MapFd`.
     ErrUnsupOffset,
     /// When using `MapFd`, there was already a mapping to the file.
     ErrAlreadyExists,
@@ -1062,7 +1087,10 @@ pub enum MapError {
     ErrCreateFileMappingW(uint),
     /// Unrecognized error from `MapViewOfFile`. The inner value is the return
     /// value of `GetLastError`.
-    ErrMapViewOfFile(uint)
+    ErrMapViewOfFile(uint),
+    /// Unrecognized error from `FlushViewOfFile`. The inner value is the
+    /// return value of `GetLastError`.
+    ErrFlushViewOfFile(uint)
 }
 
 impl fmt::Show for MapError {
@@ -1091,6 +1119,9 @@ impl fmt::Show for MapError {
             },
             ErrMapViewOfFile(code) => {
                 return write!(out, "MapViewOfFile failure = {}", code)
+            },
+            ErrFlushViewOfFile(code) => {
+                return write!(out, "FlushViewOfFile failure = {}", code)
             }
         };
         write!(out, "{}", str)
@@ -1114,6 +1145,7 @@ impl MemoryMap {
         let mut flags = libc::MAP_PRIVATE;
         let mut fd = -1;
         let mut offset = 0;
+        let mut shared = false;
         let mut custom_flags = false;
         let len = round_up(min_len, page_size());
 
@@ -1131,10 +1163,14 @@ impl MemoryMap {
                     fd = fd_;
                 },
                 MapOffset(offset_) => { offset = offset_ as off_t; },
+                MapShared => { shared = true; },
                 MapNonStandardFlags(f) => { custom_flags = true; flags = f },
             }
         }
-        if fd == -1 && !custom_flags { flags |= libc::MAP_ANON; }
+        if !custom_flags {
+            if fd == -1 { flags |= libc::MAP_ANON; }
+            if shared { flags = (flags & !libc::MAP_PRIVATE) | libc::MAP_SHARED; }
+        }
 
         let r = unsafe {
             libc::mmap(addr as *c_void, len as libc::size_t, prot, flags, fd,
@@ -1167,6 +1203,24 @@ impl MemoryMap {
     pub fn granularity() -> uint {
         page_size()
     }
+
+    /// Synchronously writes the changes made to a `MapShared` file mapping
+    /// back to the file. Corresponds to `msync` with `MS_SYNC`.
+    pub fn flush(&self) -> Result<(), MapError> {
+        let r = unsafe {
+            libc::msync(self.data as *c_void, self.len as libc::size_t,
+                        libc::MS_SYNC)
+        };
+        if r == 0 {
+            Ok(())
+        } else {
+            Err(match errno() as c_int {
+                libc::EINVAL => ErrUnaligned,
+                libc::ENOMEM => ErrNoMem,
+                code => ErrUnknown(code as int)
+            })
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -1204,7 +1258,7 @@ impl MemoryMap {
                 MapAddr(addr_) => { lpAddress = addr_ as LPVOID; },
                 MapFd(fd_) => { fd = fd_; },
                 MapOffset(offset_) => { offset = offset_; },
-                MapNonStandardFlags(..) => {}
+                MapShared | MapNonStandardFlags(..) => {}
             }
         }
 
@@ -1261,7 +1315,7 @@ impl MemoryMap {
                 }
                 let r = libc::MapViewOfFile(mapping,
                                             dwDesiredAccess,
-                                            ((len as u64) >> 32) as DWORD,
+                                            ((offset as u64) >> 32) as DWORD,
                                             (offset & 0xffff_ffff) as DWORD,
                                             0);
                 match r as uint {
@@ -1279,7 +1333,6 @@ impl MemoryMap {
     /// Granularity of MapAddr() and MapOffset() parameter values.
     /// This may be greater than the value returned by page_size().
     pub fn granularity() -> uint {
-        use mem;
         unsafe {
             let mut info = mem::zeroed();
             libc::GetSystemInfo(&mut info);
@@ -1287,6 +1340,24 @@ impl MemoryMap {
             return info.dwAllocationGranularity as uint;
         }
     }
+
+    /// Writes the changes made to a file mapping back to the file.
+    /// Corresponds to `FlushViewOfFile`.
+    pub fn flush(&self) -> Result<(), MapError> {
+        use libc::types::os::arch::extra::LPCVOID;
+        use libc::consts::os::extra::FALSE;
+
+        match self.kind {
+            MapVirtual => Ok(()),
+            MapFile(..) => unsafe {
+                if libc::FlushViewOfFile(self.data as LPCVOID, 0) == FALSE {
+                    Err(ErrFlushViewOfFile(errno()))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
 }
 
 #[cfg(windows)]
@@ -1319,6 +1390,101 @@ impl Drop for MemoryMap {
     }
 }
 
+/// A memory map of part of a `File`, which can't outlive the file it maps.
+///
+/// Unlike `MemoryMap`, the mapped bytes can be accessed safely, and neither
+/// the offset nor the length of the map need to be aligned to
+/// `MemoryMap::granularity`.
+pub struct Mmap<'a> {
+    map: MemoryMap,
+    // The mapping has to start at a multiple of the granularity, so the bytes
+    // that were asked for start `start` bytes into it.
+    start: uint,
+    len: uint,
+    writable: bool,
+    file: &'a File,
+}
+
+impl<'a> Mmap<'a> {
+    /// Maps the `len` bytes of `file` starting `offset` bytes into it.
+    ///
+    /// `options` sets how the memory may be accessed, and can be any
+    /// combination of `MapReadable`, `MapWritable`, `MapExecutable` and
+    /// `MapShared`. The file has to be open for reading, and also for writing
+    /// if the map is both writable and shared.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `options` contains any other `MapOption`.
+    pub fn new(file: &'a File, offset: uint, len: uint,
+               options: &[MapOption]) -> Result<Mmap<'a>, MapError> {
+        if len == 0 {
+            return Err(ErrZeroLength)
+        }
+        let start = offset % MemoryMap::granularity();
+        let mut writable = false;
+        let mut opts = vec!(MapFd(file.fd()), MapOffset(offset - start));
+        for &o in options.iter() {
+            match o {
+                MapReadable | MapExecutable | MapShared => {}
+                MapWritable => { writable = true; }
+                _ => fail!("Mmap::new: only MapReadable, MapWritable, \
+                            MapExecutable and MapShared are allowed")
+            }
+            opts.push(o);
+        }
+
+        let map = try!(MemoryMap::new(start + len, opts.as_slice()));
+        Ok(Mmap {
+            map: map,
+            start: start,
+            len: len,
+            writable: writable,
+            file: file,
+        })
+    }
+
+    /// Returns the `File` this is a map of.
+    pub fn file(&self) -> &'a File {
+        self.file
+    }
+
+    /// Returns the mapped bytes.
+    pub fn as_slice<'b>(&'b self) -> &'b [u8] {
+        unsafe {
+            mem::transmute(raw::Slice {
+                data: self.map.data.offset(self.start as int) as *u8,
+                len: self.len
+            })
+        }
+    }
+
+    /// Returns the mapped bytes, which may be modified.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the map was not created with `MapWritable`.
+    pub fn as_mut_slice<'b>(&'b mut self) -> &'b mut [u8] {
+        assert!(self.writable, "Mmap::as_mut_slice: the map is not writable");
+        unsafe {
+            mem::transmute(raw::Slice {
+                data: self.map.data.offset(self.start as int) as *u8,
+                len: self.len
+            })
+        }
+    }
+
+    /// Writes the changes made to a `MapShared` map back to the file. See
+    /// `MemoryMap::flush`.
+    pub fn flush(&self) -> Result<(), MapError> {
+        self.map.flush()
+    }
+}
+
+impl<'a> Container for Mmap<'a> {
+    fn len(&self) -> uint { self.len }
+}
+
 #[cfg(target_os = "linux")]
 pub mod consts {
     pub use os::arch_consts::ARCH;
@@ -1502,6 +1668,8 @@ mod tests {
     use option;
     use os::{env, getcwd, getenv, make_absolute};
     use os::{setenv, unsetenv};
+    #[cfg(unix)]
+    use os::{env_as_bytes, getenv_as_bytes, setenv_as_bytes};
     use os;
     use rand::Rng;
     use rand;
@@ -1525,6 +1693,20 @@ mod tests {
         assert_eq!(getenv(n.as_slice()), option::Some("VALUE".to_string()));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_setenv_as_bytes() {
+        let n = make_rand_name();
+        let v = [0x61, 0xff, 0x62];
+        setenv_as_bytes(n.as_slice(), v);
+        assert_eq!(getenv_as_bytes(n.as_slice()),
+                   option::Some(Vec::from_slice(v)));
+        assert_eq!(getenv(n.as_slice()), option::Some("a\uFFFDb".to_string()));
+        assert!(env_as_bytes().iter().any(|&(ref k, ref val)| {
+            k.as_slice() == n.as_bytes() && val.as_slice() == v
+        }));
+    }
+
     #[test]
     fn test_unsetenv() {
         let n = make_rand_name();
@@ -1752,5 +1934,51 @@ mod tests {
         fs::unlink(&path).unwrap();
     }
 
+    #[test]
+    fn mmap_file() {
+        use io::{File, Truncate, ReadWrite};
+        use io::fs;
+        use os::{tmpdir, Mmap, MemoryMap, MapReadable, MapWritable, MapShared};
+
+        let mut path = tmpdir();
+        path.push("mmap_wrapper.tmp");
+        // deliberately not aligned to the granularity
+        let offset = MemoryMap::granularity() + 3;
+
+        let mut file = File::open_mode(&path, Truncate, ReadWrite).unwrap();
+        file.write(Vec::from_elem(offset + 16, 0u8).as_slice()).unwrap();
+        {
+            let mut map = Mmap::new(&file, offset, 4,
+                                    [MapReadable, MapWritable, MapShared]).unwrap();
+            assert_eq!(map.len(), 4);
+            assert_eq!(map.as_slice(), &[0u8, 0, 0, 0]);
+            for (i, b) in map.as_mut_slice().mut_iter().enumerate() {
+                *b = i as u8 + 1;
+            }
+            map.flush().unwrap();
+        }
+        drop(file);
+
+        let contents = File::open(&path).read_to_end().unwrap();
+        assert_eq!(contents.slice(offset - 1, offset + 5), &[0u8, 1, 2, 3, 4, 0]);
+
+        fs::unlink(&path).unwrap();
+    }
+
+    #[test]
+    #[should_fail]
+    fn mmap_read_only() {
+        use io::{File, Truncate, ReadWrite};
+        use os::{tmpdir, Mmap, MapReadable};
+
+        let mut path = tmpdir();
+        path.push("mmap_read_only.tmp");
+        let mut file = File::open_mode(&path, Truncate, ReadWrite).unwrap();
+        file.write([0u8, ..16]).unwrap();
+
+        let mut map = Mmap::new(&file, 0, 16, [MapReadable]).unwrap();
+        map.as_mut_slice();
+    }
+
     // More recursive_mkdir tests are in extra::tempfile
 }
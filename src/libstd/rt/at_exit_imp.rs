@@ -12,13 +12,11 @@
 //!
 //! Documentation can be found on the `rt::at_exit` function.
 
-use iter::Iterator;
 use kinds::Send;
 use mem;
 use option::{Some, None};
 use owned::Box;
 use ptr::RawPtr;
-use slice::OwnedVector;
 use unstable::sync::Exclusive;
 use vec::Vec;
 
@@ -28,7 +26,8 @@ type Queue = Exclusive<Vec<proc():Send>>;
 // purpose. This module is designed to have init() called *once* in a
 // single-task context, and then run() is called only once in another
 // single-task context. As a result of this, only the `push` function is
-// thread-safe, and it assumes that the `init` function has run previously.
+// thread-safe, and it assumes that the `init` function has run previously and
+// that `run` has yet to finish.
 static mut QUEUE: *mut Queue = 0 as *mut Queue;
 static mut RUNNING: bool = false;
 
@@ -43,7 +42,6 @@ pub fn init() {
 
 pub fn push(f: proc():Send) {
     unsafe {
-        rtassert!(!RUNNING);
         rtassert!(!QUEUE.is_null());
         let state: &mut Queue = mem::transmute(QUEUE);
         let mut f = Some(f);
@@ -54,21 +52,28 @@ pub fn push(f: proc():Send) {
 }
 
 pub fn run() {
-    let vec = unsafe {
+    unsafe {
         rtassert!(!RUNNING);
         rtassert!(!QUEUE.is_null());
         RUNNING = true;
-        let state: Box<Queue> = mem::transmute(QUEUE);
-        QUEUE = 0 as *mut Queue;
-        let mut vec = None;
-        state.with(|arr| {
-            vec = Some(mem::replace(arr, vec!()));
-        });
-        vec.take_unwrap()
-    };
+    }
 
+    // Handlers are popped off the queue one at a time rather than all being
+    // taken at once, so those registered by a running handler are run as soon
+    // as it returns.
+    loop {
+        let f = unsafe {
+            let state: &mut Queue = mem::transmute(QUEUE);
+            state.with(|arr| arr.pop())
+        };
+        match f {
+            Some(f) => f(),
+            None => break,
+        }
+    }
 
-    for f in vec.move_iter() {
-        f();
+    unsafe {
+        let _state: Box<Queue> = mem::transmute(QUEUE);
+        QUEUE = 0 as *mut Queue;
     }
 }
@@ -20,29 +20,48 @@ use iter::Iterator;
 use option::{Some, None};
 use os;
 use result::{Ok, Err};
-use str::StrSlice;
+use str::{Str, StrSlice};
 use sync::atomics;
 
 pub use self::imp::write;
 
+static OFF: int = 1;
+static ON: int = 2;
+static FULL: int = 3;
+
 // For now logging is turned off by default, and this function checks to see
 // whether the magical environment variable is present to see if it's turned on.
-pub fn log_enabled() -> bool {
-    static mut ENABLED: atomics::AtomicInt = atomics::INIT_ATOMIC_INT;
+// Setting `RUST_BACKTRACE=0` explicitly turns it back off, and
+// `RUST_BACKTRACE=full` additionally asks for inlined frames and source
+// locations to be printed.
+fn mode() -> int {
+    static mut MODE: atomics::AtomicInt = atomics::INIT_ATOMIC_INT;
     unsafe {
-        match ENABLED.load(atomics::SeqCst) {
-            1 => return false,
-            2 => return true,
-            _ => {}
+        match MODE.load(atomics::SeqCst) {
+            0 => {}
+            n => return n,
         }
     }
 
     let val = match os::getenv("RUST_BACKTRACE") {
-        Some(..) => 2,
-        None => 1,
+        Some(ref s) if s.as_slice() == "0" => OFF,
+        Some(ref s) if s.as_slice() == "full" => FULL,
+        Some(..) => ON,
+        None => OFF,
     };
-    unsafe { ENABLED.store(val, atomics::SeqCst); }
-    val == 2
+    unsafe { MODE.store(val, atomics::SeqCst); }
+    val
+}
+
+/// Returns whether a backtrace should be printed when a task fails.
+pub fn log_enabled() -> bool {
+    mode() != OFF
+}
+
+/// Returns whether backtraces should expand inlined frames and show the
+/// source location of each frame, using the debug info of the program.
+pub fn full_enabled() -> bool {
+    mode() == FULL
 }
 
 #[cfg(target_word_size = "64")] static HEX_WIDTH: uint = 18;
@@ -292,8 +311,14 @@ mod imp {
             // instructions after it. This means that the return instruction
             // pointer points *outside* of the calling function, and by
             // unwinding it we go back to the original function.
+            //
+            // In full mode we need the precise address to get the right source
+            // line out of the debug info, so the return address is instead
+            // backed up by one byte to land inside of the call instruction.
             let ip = if cfg!(target_os = "macos") {
                 ip
+            } else if super::full_enabled() {
+                (ip as uint - 1) as *libc::c_void
             } else {
                 unsafe { uw::_Unwind_FindEnclosingFunction(ip) }
             };
@@ -356,6 +381,7 @@ mod imp {
         use ptr::RawPtr;
         use ptr;
         use slice::{ImmutableVector, MutableVector};
+        use vec::Vec;
 
         ////////////////////////////////////////////////////////////////////////
         // libbacktrace.h API
@@ -366,6 +392,12 @@ mod imp {
                           symname: *libc::c_char,
                           symval: libc::uintptr_t,
                           symsize: libc::uintptr_t);
+        type backtrace_full_callback =
+            extern "C" fn(data: *mut libc::c_void,
+                          pc: libc::uintptr_t,
+                          filename: *libc::c_char,
+                          lineno: libc::c_int,
+                          function: *libc::c_char) -> libc::c_int;
         type backtrace_error_callback =
             extern "C" fn(data: *mut libc::c_void,
                           msg: *libc::c_char,
@@ -383,6 +415,11 @@ mod imp {
                                  cb: backtrace_syminfo_callback,
                                  error: backtrace_error_callback,
                                  data: *mut libc::c_void) -> libc::c_int;
+            fn backtrace_pcinfo(state: *mut backtrace_state,
+                                addr: libc::uintptr_t,
+                                cb: backtrace_full_callback,
+                                error: backtrace_error_callback,
+                                data: *mut libc::c_void) -> libc::c_int;
         }
 
         ////////////////////////////////////////////////////////////////////////
@@ -401,6 +438,21 @@ mod imp {
             let slot = data as *mut *libc::c_char;
             unsafe { *slot = symname; }
         }
+        extern fn pcinfo_cb(data: *mut libc::c_void,
+                            _pc: libc::uintptr_t,
+                            filename: *libc::c_char,
+                            lineno: libc::c_int,
+                            function: *libc::c_char) -> libc::c_int {
+            let frames: &mut Vec<Frame> = unsafe { mem::transmute(data) };
+            if !filename.is_null() {
+                frames.push(Frame {
+                    filename: filename,
+                    lineno: lineno,
+                    function: function,
+                });
+            }
+            0 // keep going
+        }
 
         // The libbacktrace API supports creating a state, but it does not
         // support destroying a state. I personally take this to mean that a
@@ -468,10 +520,53 @@ mod imp {
                               data_addr as *mut libc::c_void)
         };
         if ret == 0 || data.is_null() {
-            output(w, idx, addr, None)
+            try!(output(w, idx, addr, None));
         } else {
-            output(w, idx, addr, Some(unsafe { CString::new(data, false) }))
+            try!(output(w, idx, addr, Some(unsafe { CString::new(data, false) })));
         }
+
+        // In full mode, ask the debug info which source lines this address
+        // corresponds to. There is one entry per level of inlining, the
+        // innermost first, and the last entry is the function which the
+        // symbol above names.
+        if !super::full_enabled() { return Ok(()) }
+        let mut frames: Vec<Frame> = Vec::new();
+        unsafe {
+            backtrace_pcinfo(state, addr as libc::uintptr_t,
+                             pcinfo_cb, error_cb,
+                             &mut frames as *mut Vec<Frame> as *mut libc::c_void);
+        }
+        let n = frames.len();
+        for (i, frame) in frames.iter().enumerate() {
+            try!(write!(w, "{:1$}", "", super::HEX_WIDTH + 9));
+            if i + 1 < n {
+                try!(w.write_str("inlined "));
+                let function = if frame.function.is_null() {
+                    None
+                } else {
+                    Some(unsafe { CString::new(frame.function, false) })
+                };
+                match function.as_ref().and_then(|c| c.as_str()) {
+                    Some(s) => try!(super::demangle(w, s)),
+                    None => try!(w.write_str("<unknown>")),
+                }
+                try!(w.write_str(" "));
+            }
+            let filename = unsafe { CString::new(frame.filename, false) };
+            try!(writeln!(w, "at {}:{}",
+                          filename.as_str().unwrap_or("<unknown>"),
+                          frame.lineno));
+        }
+        Ok(())
+    }
+
+    // A source location reported by libbacktrace. The strings are owned by the
+    // backtrace state, which lives forever.
+    #[cfg(not(target_os = "macos"))]
+    struct Frame {
+        filename: *libc::c_char,
+        lineno: libc::c_int,
+        function: *libc::c_char,
     }
 
     // Finally, after all that work above, we can emit a symbol.
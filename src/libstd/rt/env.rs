@@ -19,6 +19,7 @@ use str::Str;
 // They are expected to be initialized once then left alone.
 
 static mut MIN_STACK: uint = 2 * 1024 * 1024;
+static mut MIN_STACK_FROM_ENV: bool = false;
 /// This default corresponds to 20M of cache per scheduler (at the default size).
 static mut MAX_CACHED_STACKS: uint = 10;
 static mut DEBUG_BORROW: bool = false;
@@ -27,7 +28,10 @@ pub fn init() {
     unsafe {
         match os::getenv("RUST_MIN_STACK") {
             Some(s) => match from_str(s.as_slice()) {
-                Some(i) => MIN_STACK = i,
+                Some(i) => {
+                    MIN_STACK = i;
+                    MIN_STACK_FROM_ENV = true;
+                }
                 None => ()
             },
             None => ()
@@ -52,6 +56,12 @@ pub fn min_stack() -> uint {
     unsafe { MIN_STACK }
 }
 
+/// Whether the minimum stack size was set through `RUST_MIN_STACK`, which
+/// takes precedence over any default stack size set by a runtime.
+pub fn min_stack_from_env() -> bool {
+    unsafe { MIN_STACK_FROM_ENV }
+}
+
 pub fn max_cached_stacks() -> uint {
     unsafe { MAX_CACHED_STACKS }
 }
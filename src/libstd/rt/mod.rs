@@ -70,10 +70,20 @@ use self::task::{Task, BlockedTask};
 pub use self::util::default_sched_threads;
 
 // Export unwinding facilities used by the failure macros
-pub use self::unwind::{begin_unwind, begin_unwind_fmt};
+pub use self::unwind::{begin_unwind, begin_unwind_col, begin_unwind_fmt};
+
+// Counters of the failures which have occurred in this process
+pub use self::unwind::failure_stats;
+pub use core::failure::FailureStats;
 
 pub use self::util::{Stdio, Stdout, Stderr};
 
+// Redirection of failure messages
+pub use self::util::{set_stderr_sink, take_stderr_sink};
+
+// Hooks for profilers and tracing tools
+pub use self::observer::{TaskObserver, set_task_observer};
+
 pub use alloc::{heap, libc_heap};
 
 // Used by I/O tests
@@ -141,6 +151,12 @@ pub mod bookkeeping;
 // Stack overflow protection
 pub mod stack;
 
+// Guard page based stack overflow reporting
+pub mod stack_overflow;
+
+// Notifications of task transitions
+pub mod observer;
+
 /// The default error code of the rust runtime if the main task fails instead
 /// of exiting cleanly.
 pub static DEFAULT_ERROR_CODE: int = 101;
@@ -170,6 +186,9 @@ pub trait Runtime {
     fn local_io<'a>(&'a mut self) -> Option<rtio::LocalIo<'a>>;
     /// The (low, high) edges of the current stack.
     fn stack_bounds(&self) -> (uint, uint); // (lo, hi)
+    /// The (low, high) edges of the guard area below the current stack, if
+    /// known.
+    fn stack_guard(&self) -> Option<(uint, uint)>;
     fn can_block(&self) -> bool;
 
     // FIXME: This is a serious code smell and this should not exist at all.
@@ -189,6 +208,7 @@ pub fn init(argc: int, argv: **u8) {
         env::init();
         local_ptr::init();
         at_exit_imp::init();
+        stack_overflow::init();
     }
 }
 
@@ -196,15 +216,25 @@ pub fn init(argc: int, argv: **u8) {
 ///
 /// The procedure passed to this function will be executed as part of the
 /// runtime cleanup phase. For normal rust programs, this means that it will run
-/// after all other tasks have exited.
+/// after all other tasks have exited, whether or not the main task failed.
+/// This makes it the place for libraries to flush any state they've buffered
+/// up, such as log records or metrics.
+///
+/// Procedures are run in the reverse order of their registration, so a
+/// library's handler runs before those of the libraries it was built on top of
+/// (as long as they registered theirs first). A running procedure may register
+/// more procedures, which are run as soon as it returns and before any which
+/// were registered earlier.
 ///
 /// The procedure is *not* executed with a local `Task` available to it, so
-/// primitives like logging, I/O, channels, spawning, etc, are *not* available.
-/// This is meant for "bare bones" usage to clean up runtime details, this is
-/// not meant as a general-purpose "let's clean everything up" function.
+/// primitives like logging, I/O, channels, spawning, etc, are *not* available,
+/// and a procedure which fails will abort the process. Output has to be
+/// written without going through the runtime, by calling into libc for
+/// example.
 ///
-/// It is forbidden for procedures to register more `at_exit` handlers when they
-/// are running, and doing so will lead to a process abort.
+/// Registering a procedure before the runtime has been initialized (see
+/// `init`), or once every procedure has been run, will lead to a process
+/// abort.
 pub fn at_exit(f: proc():Send) {
     at_exit_imp::push(f);
 }
@@ -222,5 +252,6 @@ pub unsafe fn cleanup() {
     bookkeeping::wait_for_other_tasks();
     at_exit_imp::run();
     args::cleanup();
+    stack_overflow::cleanup();
     local_ptr::cleanup();
 }
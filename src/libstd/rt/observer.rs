@@ -0,0 +1,103 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hooks for observing the lifecycle of tasks
+//!
+//! Profilers and tracing tools can register a process-wide `TaskObserver`,
+//! which both runtimes notify whenever a task is spawned, blocks, is woken up
+//! or dies. Each notification carries the id of the task (see `Task::id`) and
+//! its name, which is enough to build up a timeline of each task.
+//!
+//! Observers are invoked on the hot paths of the runtimes, from whichever
+//! thread the transition happens on, and possibly while runtime locks are
+//! held. They should record what they need and return quickly, and must not
+//! block or spawn tasks themselves.
+
+use kinds::{Send, Share};
+use mem;
+use option::Option;
+use owned::Box;
+use rt::task::Task;
+use str::Str;
+use sync::atomics;
+
+/// The interface for being notified of task transitions. All methods do
+/// nothing by default.
+pub trait TaskObserver: Send + Share {
+    /// Called once a new task has been created, before it starts running.
+    fn spawned(&self, _id: uint, _name: Option<&str>) {}
+
+    /// Called when a task blocks, along with what it's blocking on if this is
+    /// known.
+    fn blocked(&self, _id: uint, _name: Option<&str>,
+               _on: Option<&'static str>) {}
+
+    /// Called when a blocked task is woken up. This may be called from the
+    /// waking task rather than the woken one.
+    fn unblocked(&self, _id: uint, _name: Option<&str>) {}
+
+    /// Called once a task has finished running, along with whether it failed.
+    fn died(&self, _id: uint, _name: Option<&str>, _failed: bool) {}
+}
+
+// A leaked `Box<Box<TaskObserver>>`, or 0 if no observer has been set
+static mut OBSERVER: atomics::AtomicUint = atomics::INIT_ATOMIC_UINT;
+
+/// Registers the observer which is notified of the task transitions of every
+/// runtime in this process, replacing any previous observer.
+///
+/// Observers are never destroyed, as another thread may still be notifying a
+/// replaced observer.
+pub fn set_task_observer(observer: Box<TaskObserver:Send+Share>) {
+    unsafe {
+        let observer: uint = mem::transmute(box observer);
+        OBSERVER.store(observer, atomics::SeqCst);
+    }
+}
+
+fn with_observer(f: |&TaskObserver:Send+Share|) {
+    unsafe {
+        match OBSERVER.load(atomics::SeqCst) {
+            0 => {}
+            n => {
+                let observer = n as *Box<TaskObserver:Send+Share>;
+                f(&**observer)
+            }
+        }
+    }
+}
+
+fn name<'a>(task: &'a Task) -> Option<&'a str> {
+    task.name.as_ref().map(|n| n.as_slice())
+}
+
+/// Notifies the observer that `task` has just been spawned. Called by the
+/// runtimes.
+pub fn spawned(task: &Task) {
+    with_observer(|o| o.spawned(task.id(), name(task)))
+}
+
+/// Notifies the observer that `task` is about to block. Called by the
+/// runtimes.
+pub fn blocked(task: &Task) {
+    with_observer(|o| o.blocked(task.id(), name(task), task.blocked_on))
+}
+
+/// Notifies the observer that `task` is being woken up. Called by the
+/// runtimes.
+pub fn unblocked(task: &Task) {
+    with_observer(|o| o.unblocked(task.id(), name(task)))
+}
+
+/// Notifies the observer that `task` has finished running. Called by the
+/// runtimes.
+pub fn died(task: &Task) {
+    with_observer(|o| o.died(task.id(), name(task), task.unwinder.unwinding()))
+}
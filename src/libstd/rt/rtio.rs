@@ -12,24 +12,29 @@
 
 use c_str::CString;
 use comm::{Sender, Receiver};
+use container::Container;
+use iter::Iterator;
 use kinds::Send;
 use libc::c_int;
 use libc;
 use mem;
+use num::Zero;
 use ops::Drop;
 use option::{Option, Some, None};
 use owned::Box;
 use path::Path;
-use result::Err;
+use result::{Ok, Err};
 use rt::local::Local;
 use rt::task::Task;
+use slice::{ImmutableVector, MutableVector};
+use string::String;
 use vec::Vec;
 
 use ai = io::net::addrinfo;
 use io;
 use io::IoResult;
 use io::net::ip::{IpAddr, SocketAddr};
-use io::process::{StdioContainer, ProcessExit};
+use io::process::{Stdio, ProcessExit};
 use io::signal::Signum;
 use io::{FileMode, FileAccess, FileStat, FilePermission};
 use io::{SeekStyle};
@@ -38,6 +43,23 @@ pub trait Callback {
     fn call(&mut self);
 }
 
+/// The fallback for `readv` on streams which can't read into several buffers
+/// at once: reads into the first non-empty buffer of `bufs` with `read`.
+pub fn readv_first<T: Zero>(bufs: &mut [&mut [u8]],
+                            read: |&mut [u8]| -> IoResult<T>) -> IoResult<T> {
+    match bufs.mut_iter().find(|buf| !buf.is_empty()) {
+        Some(buf) => read(&mut **buf),
+        None => Ok(Zero::zero()),
+    }
+}
+
+/// The fallback for `writev` on streams which can't write several buffers at
+/// once: writes each buffer of `bufs` in turn with `write`.
+pub fn writev_each(bufs: &[&[u8]], write: |&[u8]| -> IoResult<()>) -> IoResult<()> {
+    for buf in bufs.iter() { try!(write(*buf)); }
+    Ok(())
+}
+
 pub trait EventLoop {
     fn run(&mut self);
     fn callback(&mut self, arg: proc():Send);
@@ -49,6 +71,11 @@ pub trait EventLoop {
     /// The asynchronous I/O services. Not all event loops may provide one.
     fn io<'a>(&'a mut self) -> Option<&'a mut IoFactory>;
     fn has_active_io(&self) -> bool;
+
+    /// Whether anything on this event loop may still wake up a task, such as
+    /// an armed timer or a listening socket. This is stricter than
+    /// `has_active_io`, which only covers tasks blocked in I/O calls.
+    fn has_pending_events(&self) -> bool { self.has_active_io() }
 }
 
 pub trait RemoteCallback {
@@ -87,6 +114,26 @@ pub enum CloseBehavior {
     CloseAsynchronously,
 }
 
+/// A descriptor to wait on with `IoFactory::poll`.
+///
+/// The `read` and `write` fields select which events are of interest, and the
+/// `readable` and `writable` fields are filled in with the events which were
+/// ready when the poll returned.
+#[cfg(unix)]
+pub struct PollFd {
+    /// The raw descriptor, as returned by one of the `poll_fd` methods.
+    pub fd: c_int,
+    /// Whether to wait for the descriptor to become readable.
+    pub read: bool,
+    /// Whether to wait for the descriptor to become writable.
+    pub write: bool,
+    /// Set if the descriptor is ready to be read from (or has been closed or
+    /// hit an error, in which case a read will not block).
+    pub readable: bool,
+    /// Set if the descriptor is ready to be written to.
+    pub writable: bool,
+}
+
 /// Data needed to spawn a process. Serializes the `std::io::process::Command`
 /// builder.
 pub struct ProcessConfig<'a> {
@@ -105,19 +152,20 @@ pub struct ProcessConfig<'a> {
     pub cwd: Option<&'a CString>,
 
     /// Configuration for the child process's stdin handle (file descriptor 0).
-    /// This field defaults to `CreatePipe(true, false)` so the input can be
-    /// written to.
-    pub stdin: StdioContainer,
+    /// This field defaults to `Piped` so the input can be written to.
+    pub stdin: Stdio,
 
     /// Configuration for the child process's stdout handle (file descriptor 1).
-    /// This field defaults to `CreatePipe(false, true)` so the output can be
-    /// collected.
-    pub stdout: StdioContainer,
+    /// This field defaults to `Piped` so the output can be collected.
+    pub stdout: Stdio,
 
     /// Configuration for the child process's stdout handle (file descriptor 2).
-    /// This field defaults to `CreatePipe(false, true)` so the output can be
-    /// collected.
-    pub stderr: StdioContainer,
+    /// This field defaults to `Piped` so the output can be collected.
+    pub stderr: Stdio,
+
+    /// If true, `stderr` is ignored and the child's stderr is sent to the same
+    /// place as its stdout. Any pipe created for stdout is shared by both.
+    pub stderr_to_stdout: bool,
 
     /// Any number of streams/file descriptors/pipes may be attached to this
     /// process. This list enumerates the file descriptors and such for the
@@ -125,7 +173,7 @@ pub struct ProcessConfig<'a> {
     /// 3 and go to the length of this array. The first three file descriptors
     /// (stdin/stdout/stderr) are configured with the `stdin`, `stdout`, and
     /// `stderr` fields.
-    pub extra_io: &'a [StdioContainer],
+    pub extra_io: &'a [Stdio],
 
     /// Sets the child process's user id. This translates to a `setuid` call in
     /// the child process. Setting this value on windows will cause the spawn to
@@ -207,7 +255,7 @@ pub trait IoFactory {
     // networking
     fn tcp_connect(&mut self, addr: SocketAddr,
                    timeout: Option<u64>) -> IoResult<Box<RtioTcpStream:Send>>;
-    fn tcp_bind(&mut self, addr: SocketAddr)
+    fn tcp_bind(&mut self, addr: SocketAddr, reuse_addr: bool)
                 -> IoResult<Box<RtioTcpListener:Send>>;
     fn udp_bind(&mut self, addr: SocketAddr)
                 -> IoResult<Box<RtioUdpSocket:Send>>;
@@ -215,8 +263,11 @@ pub trait IoFactory {
                  -> IoResult<Box<RtioUnixListener:Send>>;
     fn unix_connect(&mut self, path: &CString,
                     timeout: Option<u64>) -> IoResult<Box<RtioPipe:Send>>;
+    fn unix_datagram(&mut self, path: Option<&CString>)
+                     -> IoResult<Box<RtioUnixDatagram:Send>>;
     fn get_host_addresses(&mut self, host: Option<&str>, servname: Option<&str>,
                           hint: Option<ai::Hint>) -> IoResult<Vec<ai::Info>>;
+    fn get_address_name(&mut self, addr: IpAddr) -> IoResult<String>;
 
     // filesystem operations
     fn fs_from_raw_fd(&mut self, fd: c_int, close: CloseBehavior)
@@ -237,6 +288,7 @@ pub trait IoFactory {
     fn fs_chown(&mut self, path: &CString, uid: int, gid: int) ->
         IoResult<()>;
     fn fs_readlink(&mut self, path: &CString) -> IoResult<Path>;
+    fn fs_realpath(&mut self, path: &CString) -> IoResult<Path>;
     fn fs_symlink(&mut self, src: &CString, dst: &CString) -> IoResult<()>;
     fn fs_link(&mut self, src: &CString, dst: &CString) -> IoResult<()>;
     fn fs_utime(&mut self, src: &CString, atime: u64, mtime: u64) ->
@@ -253,6 +305,9 @@ pub trait IoFactory {
             -> IoResult<Box<RtioTTY:Send>>;
     fn signal(&mut self, signal: Signum, channel: Sender<Signum>)
         -> IoResult<Box<RtioSignal:Send>>;
+    #[cfg(unix)]
+    fn poll(&mut self, fds: &mut [PollFd], timeout_ms: Option<u64>)
+            -> IoResult<uint>;
 }
 
 pub trait RtioTcpListener : RtioSocket {
@@ -264,22 +319,33 @@ pub trait RtioTcpAcceptor : RtioSocket {
     fn accept_simultaneously(&mut self) -> IoResult<()>;
     fn dont_accept_simultaneously(&mut self) -> IoResult<()>;
     fn set_timeout(&mut self, timeout: Option<u64>);
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<c_int> { None }
 }
 
 pub trait RtioTcpStream : RtioSocket {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint>;
     fn write(&mut self, buf: &[u8]) -> IoResult<()>;
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        readv_first(bufs, |buf| self.read(buf))
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        writev_each(bufs, |buf| self.write(buf))
+    }
     fn peer_name(&mut self) -> IoResult<SocketAddr>;
     fn control_congestion(&mut self) -> IoResult<()>;
     fn nodelay(&mut self) -> IoResult<()>;
     fn keepalive(&mut self, delay_in_seconds: uint) -> IoResult<()>;
     fn letdie(&mut self) -> IoResult<()>;
+    fn time_to_live(&mut self, ttl: int) -> IoResult<()>;
     fn clone(&self) -> Box<RtioTcpStream:Send>;
     fn close_write(&mut self) -> IoResult<()>;
     fn close_read(&mut self) -> IoResult<()>;
     fn set_timeout(&mut self, timeout_ms: Option<u64>);
     fn set_read_timeout(&mut self, timeout_ms: Option<u64>);
     fn set_write_timeout(&mut self, timeout_ms: Option<u64>);
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<c_int> { None }
 }
 
 pub trait RtioSocket {
@@ -317,6 +383,12 @@ pub trait RtioTimer {
 pub trait RtioFileStream {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<int>;
     fn write(&mut self, buf: &[u8]) -> IoResult<()>;
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<int> {
+        readv_first(bufs, |buf| self.read(buf))
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        writev_each(bufs, |buf| self.write(buf))
+    }
     fn pread(&mut self, buf: &mut [u8], offset: u64) -> IoResult<int>;
     fn pwrite(&mut self, buf: &[u8], offset: u64) -> IoResult<()>;
     fn seek(&mut self, pos: i64, whence: SeekStyle) -> IoResult<u64>;
@@ -325,6 +397,7 @@ pub trait RtioFileStream {
     fn datasync(&mut self) -> IoResult<()>;
     fn truncate(&mut self, offset: i64) -> IoResult<()>;
     fn fstat(&mut self) -> IoResult<FileStat>;
+    fn fd(&self) -> c_int;
 }
 
 pub trait RtioProcess {
@@ -337,6 +410,12 @@ pub trait RtioProcess {
 pub trait RtioPipe {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint>;
     fn write(&mut self, buf: &[u8]) -> IoResult<()>;
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<uint> {
+        readv_first(bufs, |buf| self.read(buf))
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> IoResult<()> {
+        writev_each(bufs, |buf| self.write(buf))
+    }
     fn clone(&self) -> Box<RtioPipe:Send>;
 
     fn close_write(&mut self) -> IoResult<()>;
@@ -344,6 +423,8 @@ pub trait RtioPipe {
     fn set_timeout(&mut self, timeout_ms: Option<u64>);
     fn set_read_timeout(&mut self, timeout_ms: Option<u64>);
     fn set_write_timeout(&mut self, timeout_ms: Option<u64>);
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<c_int> { None }
 }
 
 pub trait RtioUnixListener {
@@ -353,12 +434,24 @@ pub trait RtioUnixListener {
 pub trait RtioUnixAcceptor {
     fn accept(&mut self) -> IoResult<Box<RtioPipe:Send>>;
     fn set_timeout(&mut self, timeout: Option<u64>);
+    #[cfg(unix)]
+    fn poll_fd(&self) -> Option<c_int> { None }
+}
+
+pub trait RtioUnixDatagram {
+    fn recvfrom(&mut self, buf: &mut [u8]) -> IoResult<(uint, Option<CString>)>;
+    fn sendto(&mut self, buf: &[u8], dst: &CString) -> IoResult<()>;
+    fn clone(&self) -> Box<RtioUnixDatagram:Send>;
+    fn set_timeout(&mut self, timeout_ms: Option<u64>);
+    fn set_read_timeout(&mut self, timeout_ms: Option<u64>);
+    fn set_write_timeout(&mut self, timeout_ms: Option<u64>);
 }
 
 pub trait RtioTTY {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<uint>;
     fn write(&mut self, buf: &[u8]) -> IoResult<()>;
     fn set_raw(&mut self, raw: bool) -> IoResult<()>;
+    fn set_echo(&mut self, echo: bool) -> IoResult<()>;
     fn get_winsize(&mut self) -> IoResult<(int, int)>;
     fn isatty(&self) -> bool;
 }
@@ -368,4 +461,6 @@ pub trait PausableIdleCallback {
     fn resume(&mut self);
 }
 
-pub trait RtioSignal {}
+pub trait RtioSignal {
+    fn set_masked(&mut self, masked: bool);
+}
@@ -0,0 +1,463 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Guard page based stack overflow detection
+//!
+//! The stack limit checks emitted in function prologues (see `rt::stack`)
+//! catch the vast majority of stack overflows, but not all of them. Frames
+//! larger than the red zone, C code called over FFI, and functions compiled
+//! without stack checks can all run straight off the end of the stack and
+//! into the guard page placed below it, which the OS reports as a
+//! segmentation violation.
+//!
+//! This module installs a process-wide handler for these faults. If the
+//! faulting address lies within the guard page of the current task's stack
+//! then the usual "task '...' has overflowed its stack" message is printed
+//! before the process is aborted. Any other fault is handed back to the
+//! default signal disposition.
+//!
+//! Every OS thread which may run rust tasks needs a `Handler` for the
+//! duration of its life; on unix this provides the alternate stack that the
+//! signal handler runs on (the thread's own stack having just run out).
+
+#![allow(non_camel_case_types)]
+
+use option::{Option, None, Some};
+use rt::local::Local;
+use rt::task::Task;
+use str::Str;
+
+pub use self::imp::{Handler, init, cleanup, current_guard_page};
+
+/// Prints the stack overflow message for the task currently running.
+///
+/// This is called from within a signal handler, so it avoids allocating and
+/// does not touch the task's stderr.
+#[no_split_stack]
+fn report_overflow() {
+    let task: Option<*mut Task> = unsafe { Local::try_unsafe_borrow() };
+    let name = task.and_then(|task| unsafe {
+        (*task).name.as_ref().map(|n| n.as_slice())
+    });
+    rterrln!("\ntask '{}' has overflowed its stack", name.unwrap_or("<unknown>"));
+}
+
+/// Returns the (low, high) edges of the guard area of the current task's
+/// stack, if they are known.
+#[no_split_stack]
+fn task_guard_page() -> Option<(uint, uint)> {
+    let task: Option<*mut Task> = unsafe { Local::try_unsafe_borrow() };
+    task.and_then(|task| unsafe { (*task).stack_guard() })
+}
+
+#[cfg(target_os = "linux")]
+#[cfg(target_os = "freebsd")]
+mod imp {
+    use cmp;
+    use intrinsics;
+    use libc;
+    use mem;
+    use ops::Drop;
+    use option::{Option, None, Some};
+    use os;
+    use ptr;
+    use ptr::RawPtr;
+    use rt::stack;
+
+    use self::signal::{siginfo, sigaction, sigaltstack, SIGBUS, SIG_DFL,
+                       SA_SIGINFO, SA_ONSTACK, SIGSTKSZ};
+
+    /// A per-thread registration for stack overflow reporting. While alive,
+    /// the guard page handler can run on the thread which created it.
+    pub struct Handler {
+        altstack: *mut libc::c_void,
+    }
+
+    // The handler for the main thread, created by init() and destroyed by
+    // cleanup().
+    static mut MAIN_ALTSTACK: *mut libc::c_void = 0 as *mut libc::c_void;
+
+    #[no_split_stack]
+    extern fn signal_handler(signum: libc::c_int,
+                             info: *mut siginfo,
+                             _data: *mut libc::c_void) {
+        unsafe {
+            // We're running on the alternate signal stack, which is nowhere
+            // near the stack limit recorded for the task. Clear the limit so
+            // that the functions called below don't think that they've run
+            // out of stack.
+            stack::record_sp_limit(0);
+
+            let addr = (*info).si_addr as uint;
+            let overflow = match super::task_guard_page() {
+                Some((lo, hi)) => lo <= addr && addr < hi,
+                None => false,
+            };
+
+            if !overflow {
+                // Not a stack overflow that we know about. Restore the default
+                // disposition and return, the faulting instruction will be
+                // executed again and the fault delivered normally.
+                let mut action: sigaction = mem::zeroed();
+                action.sa_sigaction = SIG_DFL;
+                sigaction(signum, &action, ptr::mut_null());
+                return
+            }
+
+            super::report_overflow();
+            intrinsics::abort()
+        }
+    }
+
+    /// Installs the fault handler for the whole process and registers the
+    /// current (main) thread.
+    pub unsafe fn init() {
+        let mut action: sigaction = mem::zeroed();
+        action.sa_flags = SA_SIGINFO | SA_ONSTACK;
+        action.sa_sigaction = signal_handler as signal::sighandler_t;
+        sigaction(libc::SIGSEGV, &action, ptr::mut_null());
+        sigaction(SIGBUS, &action, ptr::mut_null());
+
+        let handler = Handler::new();
+        MAIN_ALTSTACK = handler.altstack;
+        mem::forget(handler);
+    }
+
+    /// Releases the resources allocated for the main thread by `init`.
+    pub unsafe fn cleanup() {
+        mem::drop(Handler { altstack: MAIN_ALTSTACK });
+        MAIN_ALTSTACK = ptr::mut_null();
+    }
+
+    impl Handler {
+        /// Registers the current thread, allocating the alternate stack that
+        /// the fault handler will run on.
+        pub unsafe fn new() -> Handler {
+            let altstack = libc::mmap(ptr::null(), SIGSTKSZ,
+                                      libc::PROT_READ | libc::PROT_WRITE,
+                                      libc::MAP_PRIVATE | libc::MAP_ANON,
+                                      -1, 0);
+            if altstack as *libc::c_void == libc::MAP_FAILED {
+                fail!("failed to allocate an alternate signal stack: {}",
+                      os::last_os_error());
+            }
+
+            let mut stack: sigaltstack = mem::zeroed();
+            stack.ss_sp = altstack;
+            stack.ss_flags = 0;
+            stack.ss_size = SIGSTKSZ;
+            sigaltstack(&stack, ptr::mut_null());
+
+            Handler { altstack: altstack }
+        }
+    }
+
+    impl Drop for Handler {
+        fn drop(&mut self) {
+            if self.altstack.is_null() { return }
+            unsafe {
+                // Disable the alternate stack before unmapping it.
+                let mut stack: sigaltstack = mem::zeroed();
+                stack.ss_flags = signal::SS_DISABLE;
+                sigaltstack(&stack, ptr::mut_null());
+                libc::munmap(self.altstack as *libc::c_void, SIGSTKSZ);
+            }
+        }
+    }
+
+    /// Returns the (low, high) edges of the guard area at the bottom of the
+    /// current OS thread's stack.
+    #[cfg(target_os = "linux")]
+    pub unsafe fn current_guard_page() -> Option<(uint, uint)> {
+        let mut attr: libc::pthread_attr_t = mem::zeroed();
+        if pthread_getattr_np(pthread_self(), &mut attr) != 0 {
+            return None
+        }
+        let ret = guard_range(&attr);
+        assert_eq!(pthread_attr_destroy(&mut attr), 0);
+        ret
+    }
+
+    /// Returns the (low, high) edges of the guard area at the bottom of the
+    /// current OS thread's stack.
+    #[cfg(target_os = "freebsd")]
+    pub unsafe fn current_guard_page() -> Option<(uint, uint)> {
+        let mut attr: libc::pthread_attr_t = mem::zeroed();
+        assert_eq!(pthread_attr_init(&mut attr), 0);
+        let ret = if pthread_attr_get_np(pthread_self(), &mut attr) == 0 {
+            guard_range(&attr)
+        } else {
+            None
+        };
+        assert_eq!(pthread_attr_destroy(&mut attr), 0);
+        ret
+    }
+
+    // The guard area of the thread described by `attr`.
+    //
+    // glibc before 2.27 reports the guard area as part of the stack, so that
+    // it lies just above the reported stack address, while later versions
+    // (and the other libcs) only report the usable stack above the guard. The
+    // layout can't be told apart from here, so both possible areas are
+    // covered: the usable stack is mapped, so no fault is ever reported for
+    // the wrong half. The main thread reports no guard size at all, but the
+    // kernel leaves at least a page below its stack unmapped.
+    unsafe fn guard_range(attr: *libc::pthread_attr_t) -> Option<(uint, uint)> {
+        let mut stackaddr = ptr::mut_null();
+        let mut size = 0;
+        if pthread_attr_getstack(attr, &mut stackaddr, &mut size) != 0 {
+            return None
+        }
+        let mut guardsize = 0;
+        if pthread_attr_getguardsize(attr, &mut guardsize) != 0 {
+            return None
+        }
+        let guard = cmp::max(guardsize as uint, os::page_size());
+        let stackaddr = stackaddr as uint;
+        Some((stackaddr - guard, stackaddr + guard))
+    }
+
+    #[cfg(target_os = "linux")]
+    mod signal {
+        use libc;
+
+        pub type sighandler_t = *mut libc::c_void;
+
+        pub static SA_ONSTACK: libc::c_int = 0x08000000;
+        pub static SA_SIGINFO: libc::c_int = 0x00000004;
+        pub static SS_DISABLE: libc::c_int = 2;
+        pub static SIGBUS: libc::c_int = 7;
+        pub static SIGSTKSZ: libc::size_t = 8192;
+        pub static SIG_DFL: sighandler_t = 0 as sighandler_t;
+
+        // This definition is not as accurate as it could be, {si_addr} is
+        // actually a field of a giant union. It's the only one that's needed
+        // here, however.
+        pub struct siginfo {
+            pub si_signo: libc::c_int,
+            pub si_errno: libc::c_int,
+            pub si_code: libc::c_int,
+            pub si_addr: *mut libc::c_void,
+        }
+
+        pub struct sigaction {
+            pub sa_sigaction: sighandler_t,
+            pub sa_mask: sigset_t,
+            pub sa_flags: libc::c_int,
+            pub sa_restorer: *mut libc::c_void,
+        }
+
+        #[cfg(target_word_size = "32")]
+        pub struct sigset_t {
+            __val: [libc::c_ulong, ..32],
+        }
+        #[cfg(target_word_size = "64")]
+        pub struct sigset_t {
+            __val: [libc::c_ulong, ..16],
+        }
+
+        pub struct sigaltstack {
+            pub ss_sp: *mut libc::c_void,
+            pub ss_flags: libc::c_int,
+            pub ss_size: libc::size_t,
+        }
+    }
+
+    #[cfg(target_os = "freebsd")]
+    mod signal {
+        use libc;
+
+        pub type sighandler_t = *mut libc::c_void;
+
+        pub static SA_ONSTACK: libc::c_int = 0x0001;
+        pub static SA_SIGINFO: libc::c_int = 0x0040;
+        pub static SS_DISABLE: libc::c_int = 4;
+        pub static SIGBUS: libc::c_int = 10;
+        pub static SIGSTKSZ: libc::size_t = 34816;
+        pub static SIG_DFL: sighandler_t = 0 as sighandler_t;
+
+        pub struct siginfo {
+            pub si_signo: libc::c_int,
+            pub si_errno: libc::c_int,
+            pub si_code: libc::c_int,
+            pub si_pid: libc::pid_t,
+            pub si_uid: libc::uid_t,
+            pub si_status: libc::c_int,
+            pub si_addr: *mut libc::c_void,
+            _reserved: [libc::c_long, ..20],
+        }
+
+        pub struct sigaction {
+            pub sa_sigaction: sighandler_t,
+            pub sa_flags: libc::c_int,
+            pub sa_mask: sigset_t,
+        }
+
+        pub struct sigset_t {
+            bits: [u32, ..4],
+        }
+
+        pub struct sigaltstack {
+            pub ss_sp: *mut libc::c_void,
+            pub ss_size: libc::size_t,
+            pub ss_flags: libc::c_int,
+        }
+    }
+
+    extern {
+        fn sigaction(signum: libc::c_int,
+                     act: *sigaction,
+                     oldact: *mut sigaction) -> libc::c_int;
+        fn sigaltstack(ss: *sigaltstack,
+                       oss: *mut sigaltstack) -> libc::c_int;
+
+        fn pthread_self() -> libc::pthread_t;
+        fn pthread_attr_destroy(attr: *mut libc::pthread_attr_t) -> libc::c_int;
+        fn pthread_attr_getstack(attr: *libc::pthread_attr_t,
+                                 stackaddr: *mut *mut libc::c_void,
+                                 stacksize: *mut libc::size_t) -> libc::c_int;
+        fn pthread_attr_getguardsize(attr: *libc::pthread_attr_t,
+                                     guardsize: *mut libc::size_t) -> libc::c_int;
+    }
+
+    #[cfg(target_os = "linux")]
+    extern {
+        fn pthread_getattr_np(native: libc::pthread_t,
+                              attr: *mut libc::pthread_attr_t) -> libc::c_int;
+    }
+
+    #[cfg(target_os = "freebsd")]
+    extern {
+        fn pthread_attr_init(attr: *mut libc::pthread_attr_t) -> libc::c_int;
+        fn pthread_attr_get_np(native: libc::pthread_t,
+                               attr: *mut libc::pthread_attr_t) -> libc::c_int;
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use libc;
+    use libc::types::os::arch::extra::{BOOL, DWORD, LONG, LPVOID};
+    use mem;
+    use option::{Option, None};
+    use ptr::RawPtr;
+    use rt::stack;
+
+    type ULONG = libc::c_ulong;
+
+    /// A per-thread registration for stack overflow reporting. On windows
+    /// this reserves enough stack for the exception handler to run once the
+    /// thread's stack has been exhausted.
+    pub struct Handler {
+        _priv: (),
+    }
+
+    #[no_split_stack]
+    extern "system" fn vectored_handler(info: *mut EXCEPTION_POINTERS) -> LONG {
+        unsafe {
+            let rec = &*(*info).ExceptionRecord;
+            if rec.ExceptionCode != EXCEPTION_STACK_OVERFLOW {
+                return EXCEPTION_CONTINUE_SEARCH
+            }
+
+            // The stack limit is meaningless at this point, and we're about to
+            // call functions which check it.
+            stack::record_sp_limit(0);
+
+            // Windows tells us exactly what happened, so there's no need to
+            // look for the guard page. The exception continues on to the
+            // default handler afterwards, which terminates the process.
+            super::report_overflow();
+            EXCEPTION_CONTINUE_SEARCH
+        }
+    }
+
+    /// Installs the exception handler for the whole process and registers
+    /// the current (main) thread.
+    pub unsafe fn init() {
+        if AddVectoredExceptionHandler(0, vectored_handler).is_null() {
+            fail!("failed to install the stack overflow exception handler");
+        }
+        mem::forget(Handler::new());
+    }
+
+    pub unsafe fn cleanup() {}
+
+    impl Handler {
+        /// Registers the current thread, reserving the stack space needed to
+        /// report an overflow.
+        pub unsafe fn new() -> Handler {
+            let mut reserve: ULONG = 0x5000;
+            if SetThreadStackGuarantee(&mut reserve) == 0 {
+                fail!("failed to reserve stack space for exception handling");
+            }
+            Handler { _priv: () }
+        }
+    }
+
+    /// Windows reports stack overflows with a dedicated exception code, so
+    /// the location of the guard page is never needed.
+    pub unsafe fn current_guard_page() -> Option<(uint, uint)> {
+        None
+    }
+
+    static EXCEPTION_STACK_OVERFLOW: DWORD = 0xc00000fd;
+    static EXCEPTION_MAXIMUM_PARAMETERS: uint = 15;
+    static EXCEPTION_CONTINUE_SEARCH: LONG = 0;
+
+    struct EXCEPTION_RECORD {
+        ExceptionCode: DWORD,
+        ExceptionFlags: DWORD,
+        ExceptionRecord: *mut EXCEPTION_RECORD,
+        ExceptionAddress: LPVOID,
+        NumberParameters: DWORD,
+        ExceptionInformation: [LPVOID, ..EXCEPTION_MAXIMUM_PARAMETERS],
+    }
+
+    struct EXCEPTION_POINTERS {
+        ExceptionRecord: *mut EXCEPTION_RECORD,
+        ContextRecord: LPVOID,
+    }
+
+    type PVECTORED_EXCEPTION_HANDLER =
+        extern "system" fn(info: *mut EXCEPTION_POINTERS) -> LONG;
+
+    extern "system" {
+        fn AddVectoredExceptionHandler(FirstHandler: ULONG,
+                                       VectoredHandler: PVECTORED_EXCEPTION_HANDLER)
+                                       -> LPVOID;
+        fn SetThreadStackGuarantee(StackSizeInBytes: *mut ULONG) -> BOOL;
+    }
+}
+
+#[cfg(not(target_os = "linux"), not(target_os = "freebsd"), not(windows))]
+mod imp {
+    use option::{Option, None};
+
+    /// A per-thread registration for stack overflow reporting. Guard page
+    /// detection is not implemented on this platform, so this does nothing.
+    pub struct Handler {
+        _priv: (),
+    }
+
+    pub unsafe fn init() {}
+
+    pub unsafe fn cleanup() {}
+
+    impl Handler {
+        pub unsafe fn new() -> Handler {
+            Handler { _priv: () }
+        }
+    }
+
+    pub unsafe fn current_guard_page() -> Option<(uint, uint)> {
+        None
+    }
+}
@@ -34,8 +34,8 @@ use rt::local_heap::LocalHeap;
 use rt::rtio::LocalIo;
 use rt::unwind::Unwinder;
 use str::SendStr;
-use sync::atomics::{AtomicUint, SeqCst};
-use task::{TaskResult, TaskOpts};
+use sync::atomics::{AtomicUint, SeqCst, INIT_ATOMIC_UINT};
+use task::{TaskResult, TaskOpts, FailureHandlerRef};
 use finally::Finally;
 
 /// The Task struct represents all state associated with a rust
@@ -55,9 +55,20 @@ pub struct Task {
     pub stdout: Option<Box<Writer:Send>>,
     pub stderr: Option<Box<Writer:Send>>,
 
+    pub on_fail: Option<FailureHandlerRef>,
+
+    // What this task is about to block on, set by blocking primitives right
+    // before descheduling. Runtimes which detect deadlocks use this to report
+    // what each task was waiting for.
+    pub blocked_on: Option<&'static str>,
+
+    id: uint,
     imp: Option<Box<Runtime:Send>>,
 }
 
+// The id which the next task to be created is given
+static mut TASK_IDS: AtomicUint = INIT_ATOMIC_UINT;
+
 pub struct GarbageCollector;
 pub struct LocalStorage(pub Option<local_data::Map>);
 
@@ -98,10 +109,17 @@ impl Task {
             name: None,
             stdout: None,
             stderr: None,
+            on_fail: None,
+            blocked_on: None,
+            id: unsafe { TASK_IDS.fetch_add(1, SeqCst) },
             imp: None,
         }
     }
 
+    /// Returns the id of this task, which is unique among all of the tasks
+    /// created by this process.
+    pub fn id(&self) -> uint { self.id }
+
     /// Executes the given closure as if it's running inside this task. The task
     /// is consumed upon entry, and the destroyed task is returned from this
     /// function in order for the caller to free. This function is guaranteed to
@@ -289,6 +307,14 @@ impl Task {
         self.imp.get_ref().stack_bounds()
     }
 
+    /// Returns the (low, high) edges of the guard area at the bottom of this
+    /// task's stack, if they are known. Unlike most methods on `Task`, this
+    /// returns `None` rather than failing if the task's runtime has been
+    /// taken, as it is used when reporting stack overflows.
+    pub fn stack_guard(&self) -> Option<(uint, uint)> {
+        self.imp.as_ref().and_then(|rt| rt.stack_guard())
+    }
+
     /// Returns whether it is legal for this task to block the OS thread that it
     /// is running on.
     pub fn can_block(&self) -> bool {
@@ -454,14 +480,14 @@ mod test {
     fn comm_stream() {
         let (tx, rx) = channel();
         tx.send(10);
-        assert!(rx.recv() == 10);
+        assert!(rx.recv_unwrap() == 10);
     }
 
     #[test]
     fn comm_shared_chan() {
         let (tx, rx) = channel();
         tx.send(10);
-        assert!(rx.recv() == 10);
+        assert!(rx.recv_unwrap() == 10);
     }
 
     #[test]
@@ -44,8 +44,10 @@ static DEFAULT_STACK_SIZE: uint = 1024 * 1024;
 #[no_split_stack]
 extern fn thread_start(main: *libc::c_void) -> imp::rust_thread_return {
     use rt::stack;
+    use rt::stack_overflow::Handler;
     unsafe {
         stack::record_stack_bounds(0, uint::MAX);
+        let _handler = Handler::new();
         let f: Box<proc()> = mem::transmute(main);
         (*f)();
         mem::transmute(0 as imp::rust_thread_return)
@@ -86,7 +88,7 @@ impl Thread<()> {
             *mem::transmute::<&Box<Option<T>>, **mut Option<T>>(&packet)
         };
         let main = proc() unsafe { *packet2 = Some(main()); };
-        let native = unsafe { imp::create(stack, box main) };
+        let native = unsafe { imp::create(stack, 0, box main) };
 
         Thread {
             native: native,
@@ -108,8 +110,16 @@ impl Thread<()> {
     /// Performs the same functionality as `spawn`, but explicitly specifies a
     /// stack size for the new thread.
     pub fn spawn_stack(stack: uint, main: proc():Send) {
+        Thread::spawn_stack_guard(stack, 0, main)
+    }
+
+    /// Performs the same functionality as `spawn_stack`, but also specifies
+    /// the size of the guard area placed below the new thread's stack. A
+    /// guard size of zero leaves the platform's default in place, and the
+    /// guard size is ignored on platforms which don't support setting it.
+    pub fn spawn_stack_guard(stack: uint, guard: uint, main: proc():Send) {
         unsafe {
-            let handle = imp::create(stack, box main);
+            let handle = imp::create(stack, guard, box main);
             imp::detach(handle);
         }
     }
@@ -119,6 +129,31 @@ impl Thread<()> {
     pub fn yield_now() {
         unsafe { imp::yield_now(); }
     }
+
+    /// Sets the name of the current OS thread, which is shown by debuggers and
+    /// tools such as `top -H`.
+    ///
+    /// Platforms limit the length of thread names (to 15 bytes on linux), so
+    /// the name may be truncated. It is also cut off at an interior nul. On
+    /// platforms which can't name threads this does nothing.
+    pub fn set_name(name: &str) {
+        unsafe { imp::set_name(name); }
+    }
+}
+
+// Truncates `name` to `max` bytes (stopping at the first nul) and passes it
+// to `f` as a C string.
+#[cfg(unix)]
+fn with_name<T>(name: &str, max: uint, f: |*libc::c_char| -> T) -> T {
+    use c_str::ToCStr;
+    use cmp;
+    use iter::Iterator;
+    use slice::ImmutableVector;
+    use str::StrSlice;
+
+    let name = name.as_bytes();
+    let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+    name.slice_to(cmp::min(len, max)).with_c_str(f)
 }
 
 impl<T: Send> Thread<T> {
@@ -146,6 +181,7 @@ impl<T: Send> Drop for Thread<T> {
 
 #[cfg(windows)]
 mod imp {
+    use c_str::ToCStr;
     use mem;
     use cmp;
     use kinds::Send;
@@ -156,11 +192,14 @@ mod imp {
     use owned::Box;
     use ptr;
     use rt::stack::RED_ZONE;
+    use ptr::RawPtr;
+    use str::StrSlice;
 
     pub type rust_thread = HANDLE;
     pub type rust_thread_return = DWORD;
 
-    pub unsafe fn create(stack: uint, p: Box<proc():Send>) -> rust_thread {
+    pub unsafe fn create(stack: uint, _guard: uint,
+                         p: Box<proc():Send>) -> rust_thread {
         let arg: *mut libc::c_void = mem::transmute(p);
         // FIXME On UNIX, we guard against stack sizes that are too small but
         // that's because pthreads enforces that stacks are at least
@@ -199,6 +238,22 @@ mod imp {
         SwitchToThread();
     }
 
+    pub unsafe fn set_name(name: &str) {
+        // SetThreadDescription is only available on newer versions of
+        // windows, so it has to be looked up at runtime.
+        type F = extern "system" fn(HANDLE, *u16) -> libc::c_long;
+        let kernel32 = "kernel32.dll".to_utf16().append_one(0);
+        let kernel32 = GetModuleHandleW(kernel32.as_ptr());
+        if kernel32.is_null() { return }
+        let f = "SetThreadDescription".with_c_str(|s| {
+            GetProcAddress(kernel32, s)
+        });
+        if f.is_null() { return }
+        let name = name.to_utf16().append_one(0);
+        mem::transmute::<*libc::c_void, F>(f)(GetCurrentThread(),
+                                               name.as_ptr());
+    }
+
     extern "system" {
         fn CreateThread(lpThreadAttributes: LPSECURITY_ATTRIBUTES,
                         dwStackSize: SIZE_T,
@@ -208,6 +263,10 @@ mod imp {
                         lpThreadId: LPDWORD) -> HANDLE;
         fn WaitForSingleObject(hHandle: HANDLE, dwMilliseconds: DWORD) -> DWORD;
         fn SwitchToThread() -> BOOL;
+        fn GetCurrentThread() -> HANDLE;
+        fn GetModuleHandleW(lpModuleName: *u16) -> *libc::c_void;
+        fn GetProcAddress(hModule: *libc::c_void,
+                          lpProcName: *libc::c_char) -> *libc::c_void;
     }
 }
 
@@ -226,13 +285,25 @@ mod imp {
     pub type rust_thread = libc::pthread_t;
     pub type rust_thread_return = *u8;
 
-    pub unsafe fn create(stack: uint, p: Box<proc():Send>) -> rust_thread {
+    pub unsafe fn create(stack: uint, guard: uint,
+                         p: Box<proc():Send>) -> rust_thread {
         let mut native: libc::pthread_t = mem::zeroed();
         let mut attr: libc::pthread_attr_t = mem::zeroed();
         assert_eq!(pthread_attr_init(&mut attr), 0);
         assert_eq!(pthread_attr_setdetachstate(&mut attr,
                                                PTHREAD_CREATE_JOINABLE), 0);
 
+        // Some implementations carve the guard area out of the stack, so make
+        // room for it on top of the requested size.
+        let mut stack = stack;
+        if guard > 0 {
+            let page_size = os::page_size();
+            let guard = (guard + page_size - 1) & (-(page_size - 1) - 1);
+            assert_eq!(pthread_attr_setguardsize(&mut attr,
+                                                 guard as libc::size_t), 0);
+            stack += guard;
+        }
+
         // Reserve room for the red zone, the runtime's stack of last resort.
         let stack_size = cmp::max(stack, RED_ZONE + min_stack_size(&attr) as uint);
         match pthread_attr_setstacksize(&mut attr, stack_size as libc::size_t) {
@@ -275,6 +346,46 @@ mod imp {
 
     pub unsafe fn yield_now() { assert_eq!(sched_yield(), 0); }
 
+    #[cfg(target_os = "linux")]
+    #[cfg(target_os = "android")]
+    pub unsafe fn set_name(name: &str) {
+        static PR_SET_NAME: libc::c_int = 15;
+        // the kernel truncates the name to fit by itself
+        super::with_name(name, ::uint::MAX, |name| {
+            prctl(PR_SET_NAME, name as libc::c_ulong);
+        });
+    }
+
+    #[cfg(target_os = "macos")]
+    pub unsafe fn set_name(name: &str) {
+        // names which don't fit into MAXTHREADNAMESIZE are rejected
+        super::with_name(name, 63, |name| { pthread_setname_np(name); });
+    }
+
+    #[cfg(target_os = "freebsd")]
+    pub unsafe fn set_name(name: &str) {
+        super::with_name(name, ::uint::MAX, |name| {
+            pthread_set_name_np(pthread_self(), name);
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    #[cfg(target_os = "android")]
+    extern {
+        fn prctl(option: libc::c_int, arg2: libc::c_ulong, ...) -> libc::c_int;
+    }
+
+    #[cfg(target_os = "macos")]
+    extern {
+        fn pthread_setname_np(name: *libc::c_char) -> libc::c_int;
+    }
+
+    #[cfg(target_os = "freebsd")]
+    extern {
+        fn pthread_self() -> libc::pthread_t;
+        fn pthread_set_name_np(tid: libc::pthread_t, name: *libc::c_char);
+    }
+
     // glibc >= 2.15 has a __pthread_get_minstack() function that returns
     // PTHREAD_STACK_MIN plus however many bytes are needed for thread-local
     // storage.  We need that information to avoid blowing up when a small stack
@@ -318,6 +429,8 @@ mod imp {
         fn pthread_attr_destroy(attr: *mut libc::pthread_attr_t) -> libc::c_int;
         fn pthread_attr_setstacksize(attr: *mut libc::pthread_attr_t,
                                      stack_size: libc::size_t) -> libc::c_int;
+        fn pthread_attr_setguardsize(attr: *mut libc::pthread_attr_t,
+                                     guard_size: libc::size_t) -> libc::c_int;
         fn pthread_attr_setdetachstate(attr: *mut libc::pthread_attr_t,
                                        state: libc::c_int) -> libc::c_int;
         fn pthread_detach(thread: libc::pthread_t) -> libc::c_int;
@@ -338,10 +451,26 @@ mod tests {
     #[test]
     fn detached() { Thread::spawn(proc () {}) }
 
+    #[test]
+    fn set_name() {
+        Thread::start(proc() {
+            Thread::set_name("rust-thread-name-which-is-long");
+            Thread::set_name("with\0nul");
+            Thread::set_name("");
+        }).join();
+    }
+
     #[test]
     fn small_stacks() {
         assert_eq!(42, Thread::start_stack(0, proc () 42).join());
         assert_eq!(42, Thread::start_stack(1, proc () 42).join());
     }
+
+    #[test]
+    fn guarded() {
+        let (tx, rx) = channel();
+        Thread::spawn_stack_guard(1 << 20, 1 << 16, proc() tx.send(42));
+        assert_eq!(rx.recv_unwrap(), 42);
+    }
 }
 
@@ -58,8 +58,12 @@
 // Currently Rust uses unwind runtime provided by libgcc.
 
 use any::{Any, AnyRefExt};
+use core::failure;
+#[cfg(not(test))] use core::failure::FailureInfo;
+use core::failure::FailureStats;
 use fmt;
 use intrinsics;
+use io::{IoResult, Writer};
 use kinds::Send;
 use mem;
 use option::{Some, None, Option};
@@ -70,9 +74,10 @@ use result::{Err, Ok};
 use rt::backtrace;
 use rt::local::Local;
 use rt::task::Task;
-use str::Str;
+use str::{Str, StrAllocating};
 use string::String;
-use task::TaskResult;
+use sync::atomics;
+use task::{TaskResult, CausedBy, CoreFailure, FailureStatus};
 
 use uw = rt::libunwind;
 
@@ -161,6 +166,16 @@ impl Unwinder {
         }
     }
 
+    /// Clears the unwinding state of this unwinder, returning the cause of the
+    /// failure which was being unwound (if any).
+    ///
+    /// This is used when unwinding has been caught somewhere other than the
+    /// base of the task's stack.
+    pub fn reset(&mut self) -> Option<Box<Any:Send>> {
+        self.unwinding = false;
+        self.cause.take()
+    }
+
     pub fn result(&mut self) -> TaskResult {
         if self.unwinding {
             Err(self.cause.take().unwrap())
@@ -329,16 +344,56 @@ pub mod eabi {
 // Entry point of failure from the libcore crate
 #[cfg(not(test), not(stage0))]
 #[lang = "begin_unwind"]
-pub extern fn rust_begin_unwind(msg: &fmt::Arguments,
-                                file: &'static str, line: uint) -> ! {
-    begin_unwind_fmt(msg, file, line)
+pub extern fn rust_begin_unwind(info: &FailureInfo) -> ! {
+    begin_unwind_core(info)
+}
+
+#[no_mangle]
+#[cfg(not(test), stage0)]
+pub extern fn rust_begin_unwind(info: &FailureInfo) -> ! {
+    begin_unwind_core(info)
+}
+
+// Failures from libcore which carry a class are wrapped up in a `CoreFailure`
+// so the class survives to the other end of `task::try`.
+#[cfg(not(test))]
+fn begin_unwind_core(info: &FailureInfo) -> ! {
+    match info.payload {
+        Some(class) => {
+            let failure = CoreFailure { msg: fmt::format(info.fmt), class: class };
+            begin_unwind_inner(box failure, info.file, info.line, info.col)
+        }
+        None => begin_unwind_fmt(info.fmt, info.file, info.line, info.col),
+    }
+}
+
+// Entry point of out-of-memory handling from the liballoc crate
+#[cfg(not(test), not(stage0))]
+#[lang = "oom"]
+pub extern fn rust_oom(size: uint) -> ! {
+    oom(size)
 }
 
 #[no_mangle]
 #[cfg(not(test), stage0)]
-pub extern fn rust_begin_unwind(msg: &fmt::Arguments,
-                                file: &'static str, line: uint) -> ! {
-    begin_unwind_fmt(msg, file, line)
+pub extern fn rust_oom(size: uint) -> ! {
+    oom(size)
+}
+
+/// The default out-of-memory handler, printing the size of the failed
+/// allocation (and a backtrace, if enabled) before aborting the process.
+///
+/// Unwinding isn't an option here, as it requires allocating the failure
+/// payload, so care is taken to not allocate at all.
+#[inline(never)] #[cold]
+pub fn oom(size: uint) -> ! {
+    rterrln!("fatal runtime error: out of memory (failed to allocate {} bytes)",
+             size);
+    if backtrace::log_enabled() {
+        let mut err = ::rt::util::Stderr;
+        let _err = backtrace::write(&mut err);
+    }
+    unsafe { intrinsics::abort() }
 }
 
 /// The entry point for unwinding with a formatted message.
@@ -347,19 +402,31 @@ pub extern fn rust_begin_unwind(msg: &fmt::Arguments,
 /// site as much as possible (so that `fail!()` has as low an impact
 /// on (e.g.) the inlining of other functions as possible), by moving
 /// the actual formatting into this shared place.
-#[inline(never)] #[cold]
+#[inline(never)] #[cold] #[rustc_begin_unwind]
 pub fn begin_unwind_fmt(msg: &fmt::Arguments, file: &'static str,
-                        line: uint) -> ! {
+                        line: uint, col: uint) -> ! {
     // We do two allocations here, unfortunately. But (a) they're
     // required with the current scheme, and (b) we don't handle
     // failure + OOM properly anyway (see comment in begin_unwind
     // below).
-    begin_unwind_inner(box fmt::format(msg), file, line)
+    begin_unwind_inner(box fmt::format(msg), file, line, col)
 }
 
 /// This is the entry point of unwinding for fail!() and assert!().
-#[inline(never)] #[cold] // avoid code bloat at the call sites as much as possible
+///
+/// The column of the failure is not known when failing through this function;
+/// see `begin_unwind_col`.
+#[inline(never)] #[cold] #[rustc_begin_unwind] // avoid code bloat at the call sites
 pub fn begin_unwind<M: Any + Send>(msg: M, file: &'static str, line: uint) -> ! {
+    // see below for why we do the `Any` coercion here.
+    begin_unwind_inner(box msg, file, line, 0)
+}
+
+/// The same as `begin_unwind`, but additionally records the (1-based) column
+/// at which failure was invoked.
+#[inline(never)] #[cold] #[rustc_begin_unwind] // avoid code bloat at the call sites
+pub fn begin_unwind_col<M: Any + Send>(msg: M, file: &'static str, line: uint,
+                                       col: uint) -> ! {
     // Note that this should be the only allocation performed in this code path.
     // Currently this means that fail!() on OOM will invoke this code path,
     // but then again we're not really ready for failing on OOM anyway. If
@@ -368,10 +435,85 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file: &'static str, line: uint) -> !
     // failing.
 
     // see below for why we do the `Any` coercion here.
-    begin_unwind_inner(box msg, file, line)
+    begin_unwind_inner(box msg, file, line, col)
+}
+
+/// Returns the number of failures of each class which have begun in this
+/// process so far, for example to export crash statistics from a
+/// long-running service.
+pub fn failure_stats() -> FailureStats {
+    failure::stats()
+}
+
+/// The location of a failure, displayed as `file:line` or `file:line:col`.
+struct Location {
+    file: &'static str,
+    line: uint,
+    col: uint,
+}
+
+impl fmt::Show for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.col == 0 {
+            write!(f, "{}:{}", self.file, self.line)
+        } else {
+            write!(f, "{}:{}:{}", self.file, self.line, self.col)
+        }
+    }
 }
 
 
+/// Extracts a printable message from a failure payload.
+fn payload_str<'a>(msg: &'a Any) -> &'a str {
+    match msg.downcast_ref::<&'static str>() {
+        Some(s) => *s,
+        None => match msg.downcast_ref::<String>() {
+            Some(s) => s.as_slice(),
+            None => match msg.downcast_ref::<CausedBy>() {
+                Some(c) => payload_str(&*c.msg),
+                None => match msg.downcast_ref::<FailureStatus>() {
+                    Some(s) => payload_str(&*s.msg),
+                    None => match msg.downcast_ref::<CoreFailure>() {
+                        Some(c) => c.msg.as_slice(),
+                        None => "Box<Any>",
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Writes out the chain of failures which led to the failure `msg`, if any,
+/// from the most recent to the original.
+fn write_causes(w: &mut Writer, msg: &Any) -> IoResult<()> {
+    let mut msg = msg;
+    loop {
+        match msg.downcast_ref::<CausedBy>() {
+            Some(c) => {
+                try!(writeln!(w, "  caused by: '{}'", payload_str(&*c.cause)));
+                msg = &*c.cause;
+            }
+            None => return Ok(()),
+        }
+    }
+}
+
+// Whether the hint on how to enable backtraces has been printed yet
+static mut BACKTRACE_HINTED: atomics::AtomicBool = atomics::INIT_ATOMIC_BOOL;
+
+/// Writes a backtrace of the current task if backtraces are enabled, or a
+/// hint on how to enable them otherwise. The hint is only written for the
+/// first failure of the process, as it's the same every time.
+fn write_backtrace(w: &mut Writer) -> IoResult<()> {
+    if backtrace::log_enabled() {
+        backtrace::write(w)
+    } else if unsafe { BACKTRACE_HINTED.swap(true, atomics::SeqCst) } {
+        Ok(())
+    } else {
+        w.write_str("run with `RUST_BACKTRACE=1` to see a backtrace\n")
+    }
+}
+
 /// The core of the unwinding.
 ///
 /// This is non-generic to avoid instantiation bloat in other crates
@@ -384,16 +526,17 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file: &'static str, line: uint) -> !
 #[inline(never)] #[cold] // this is the slow path, please never inline this
 fn begin_unwind_inner(msg: Box<Any:Send>,
                       file: &'static str,
-                      line: uint) -> ! {
+                      line: uint,
+                      col: uint) -> ! {
+    // Failures raised by libcore's runtime checks are counted by libcore.
+    if !msg.is::<CoreFailure>() {
+        failure::record_explicit_failure();
+    }
+
+    let loc = Location { file: file, line: line, col: col };
     let mut task;
     {
-        let msg_s = match msg.as_ref::<&'static str>() {
-            Some(s) => *s,
-            None => match msg.as_ref::<String>() {
-                Some(s) => s.as_slice(),
-                None => "Box<Any>",
-            }
-        };
+        let msg_s = payload_str(&*msg);
 
         // It is assumed that all reasonable rust code will have a local task at
         // all times. This means that this `try_take` will succeed almost all of
@@ -406,17 +549,67 @@ fn begin_unwind_inner(msg: Box<Any:Send>,
         task = match opt_task {
             Some(t) => t,
             None => {
-                rterrln!("failed at '{}', {}:{}", msg_s, file, line);
-                if backtrace::log_enabled() {
-                    let mut err = ::rt::util::Stderr;
-                    let _err = backtrace::write(&mut err);
-                } else {
-                    rterrln!("run with `RUST_BACKTRACE=1` to see a backtrace");
-                }
+                ::rt::util::with_stderr_sink(|err| {
+                    let _err = writeln!(err, "failed at '{}', {}", msg_s, loc);
+                    let _err = write_causes(err, &*msg);
+                    let _err = write_backtrace(err);
+                });
                 unsafe { intrinsics::abort() }
             }
         };
 
+        if task.unwinder.unwinding {
+            // If a task fails while it's already unwinding then we
+            // have limited options. Currently our preference is to
+            // just abort. In the future we may consider resuming
+            // unwinding or otherwise exiting the task cleanly.
+            //
+            // This is checked before running any user code (such as the
+            // task's stderr) so that the diagnostic is printed the same way
+            // no matter what state the task is in.
+            let (n, first) = {
+                let n = task.name.as_ref().map(|n| n.as_slice())
+                            .unwrap_or("<unnamed>");
+                let first = match task.unwinder.cause {
+                    Some(ref cause) => payload_str(&**cause),
+                    None => "<unknown>",
+                };
+                (n.to_string(), first.to_string())
+            };
+
+            // The sink may want to wake up other tasks, so the task has to be
+            // back in TLS while writing to it.
+            Local::put(task);
+            ::rt::util::with_stderr_sink(|err| {
+                let _err = writeln!(err, "task '{}' failed during unwinding \
+                                          (double failure)", n);
+                let _err = writeln!(err, "  first failure: '{}'", first);
+                let _err = writeln!(err, "  second failure: '{}', {}", msg_s, loc);
+                let _err = writeln!(err, "rust must abort now. so sorry.");
+                let _err = backtrace::write(err);
+            });
+            unsafe { intrinsics::abort() }
+        }
+
+        // Give the task's failure handler a chance to observe the failure
+        // before anything is unwound. The handler is taken out of the task so
+        // a failure inside of it is unwound normally instead of invoking the
+        // handler again, and put back afterwards (unless it set a new one) so
+        // it also hears about the failures after one which was caught. As
+        // with stderr below, the task must be in TLS while running the user's
+        // code.
+        match task.on_fail.take() {
+            Some(handler) => {
+                Local::put(task);
+                handler.on_fail(msg_s, file, line, col);
+                task = Local::take();
+                if task.on_fail.is_none() {
+                    task.on_fail = Some(handler);
+                }
+            }
+            None => {}
+        }
+
         // See comments in io::stdio::with_task_stdout as to why we have to be
         // careful when using an arbitrary I/O handle from the task. We
         // essentially need to dance to make sure when a task is in TLS when
@@ -430,11 +623,10 @@ fn begin_unwind_inner(msg: Box<Any:Send>,
                     Local::put(task);
                     // FIXME: what to do when the task printing fails?
                     let _err = write!(stderr,
-                                      "task '{}' failed at '{}', {}:{}\n",
-                                      n, msg_s, file, line);
-                    if backtrace::log_enabled() {
-                        let _err = backtrace::write(stderr);
-                    }
+                                      "task '{}' failed at '{}', {}\n",
+                                      n, msg_s, loc);
+                    let _err = write_causes(stderr, &*msg);
+                    let _err = write_backtrace(stderr);
                     task = Local::take();
 
                     match mem::replace(&mut task.stderr, Some(stderr)) {
@@ -447,33 +639,18 @@ fn begin_unwind_inner(msg: Box<Any:Send>,
                     }
                 }
                 None => {
-                    rterrln!("task '{}' failed at '{}', {}:{}", n, msg_s,
-                             file, line);
-                    if backtrace::log_enabled() {
-                        let mut err = ::rt::util::Stderr;
-                        let _err = backtrace::write(&mut err);
-                    }
+                    Local::put(task);
+                    ::rt::util::with_stderr_sink(|err| {
+                        let _err = writeln!(err, "task '{}' failed at '{}', {}",
+                                            n, msg_s, loc);
+                        let _err = write_causes(err, &*msg);
+                        let _err = write_backtrace(err);
+                    });
+                    task = Local::take();
                 }
             }
         }
         task.name = name;
-
-        if task.unwinder.unwinding {
-            // If a task fails while it's already unwinding then we
-            // have limited options. Currently our preference is to
-            // just abort. In the future we may consider resuming
-            // unwinding or otherwise exiting the task cleanly.
-            rterrln!("task failed during unwinding (double-failure - total drag!)")
-            rterrln!("rust must abort now. so sorry.");
-
-            // Don't print the backtrace twice (it would have already been
-            // printed if logging was enabled).
-            if !backtrace::log_enabled() {
-                let mut err = ::rt::util::Stderr;
-                let _err = backtrace::write(&mut err);
-            }
-            unsafe { intrinsics::abort() }
-        }
     }
 
     // The unwinder won't actually use the task at all, so we put the task back
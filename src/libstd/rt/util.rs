@@ -16,11 +16,18 @@ use io;
 use iter::Iterator;
 use libc;
 use libc::uintptr_t;
+use mem;
+use ops::Drop;
 use option::{Some, None, Option};
 use os;
+use owned::Box;
+use ptr::RawPtr;
 use result::Ok;
+use rt::local::Local;
+use rt::task::Task;
 use str::{Str, StrSlice};
 use slice::ImmutableVector;
+use unstable::mutex::{StaticNativeMutex, NATIVE_MUTEX_INIT};
 
 // Indicates whether we should perform expensive sanity checks, including rtassert!
 // FIXME: Once the runtime matures remove the `true` below to turn off rtassert, etc.
@@ -98,6 +105,140 @@ pub fn dumb_println(args: &fmt::Arguments) {
     let _ = writeln!(&mut w, "{}", args);
 }
 
+// The process-wide destination for failure messages, protected by SINK_LOCK.
+// A null pointer means that messages go straight to the stderr file
+// descriptor.
+//
+// The sink isn't written to with the lock held. Instead it's taken out of SINK
+// for the duration of the write, while SINK_USER records who's writing to it
+// (see `current_user`) and other writers wait for it to be put back. SINK_GEN
+// counts the times the sink was replaced, so that a sink which was replaced
+// while in use is dropped instead of being put back.
+static mut SINK_LOCK: StaticNativeMutex = NATIVE_MUTEX_INIT;
+static mut SINK: *mut Box<io::Writer:Send> = 0 as *mut Box<io::Writer:Send>;
+static mut SINK_USER: uint = 0;
+static mut SINK_GEN: uint = 0;
+
+/// Redirects the messages printed by the default failure reporter (and any
+/// backtraces printed along with them) to `sink`, returning the previously
+/// configured sink, if any.
+///
+/// This affects all tasks which do not have a task-local stderr configured,
+/// as well as messages printed when no task is available. Writes to the sink
+/// are serialized, so the output of tasks failing concurrently will not be
+/// interleaved.
+///
+/// Tasks wait for each other to finish writing to the sink, so it must not
+/// block waiting on other Rust tasks. Messages printed by a task while it's
+/// writing to the sink, such as the sink's own failure, go straight to stderr.
+pub fn set_stderr_sink(sink: Box<io::Writer:Send>) -> Option<Box<io::Writer:Send>> {
+    let new: *mut Box<io::Writer:Send> = unsafe { mem::transmute(box sink) };
+    unsafe { replace_sink(new) }
+}
+
+/// Removes the sink configured by `set_stderr_sink`, returning it. Failure
+/// messages are written directly to stderr again afterwards.
+pub fn take_stderr_sink() -> Option<Box<io::Writer:Send>> {
+    unsafe { replace_sink(0 as *mut Box<io::Writer:Send>) }
+}
+
+// Installs `new` as the sink once nobody else is writing to the current one.
+// If the calling task is itself writing to it, the current sink is dropped
+// once it's done and None is returned.
+unsafe fn replace_sink(new: *mut Box<io::Writer:Send>) -> Option<Box<io::Writer:Send>> {
+    let me = current_user();
+    let old = {
+        let g = SINK_LOCK.lock();
+        while SINK_USER != 0 && SINK_USER != me {
+            g.wait();
+        }
+        if SINK_USER == 0 {
+            g.signal();
+        }
+        SINK_GEN += 1;
+        mem::replace(&mut SINK, new)
+    };
+    sink_from_raw(old)
+}
+
+unsafe fn sink_from_raw(sink: *mut Box<io::Writer:Send>) -> Option<Box<io::Writer:Send>> {
+    if sink.is_null() {
+        None
+    } else {
+        let sink: Box<Box<io::Writer:Send>> = mem::transmute(sink);
+        Some(*sink)
+    }
+}
+
+// Identifies whoever is writing to the sink: the local task, or the thread if
+// there's no task.
+unsafe fn current_user() -> uint {
+    let task: Option<*mut Task> = Local::try_unsafe_borrow();
+    match task {
+        Some(task) => task as uint,
+        None => 1,
+    }
+}
+
+// A sink taken out of SINK to be written to, which is put back when dropped
+// (even if writing to it failed).
+struct SinkInUse {
+    sink: *mut Box<io::Writer:Send>,
+    gen: uint,
+}
+
+impl Drop for SinkInUse {
+    fn drop(&mut self) {
+        unsafe {
+            let stale = {
+                let g = SINK_LOCK.lock();
+                SINK_USER = 0;
+                g.signal();
+                if SINK_GEN == self.gen {
+                    SINK = self.sink;
+                    0 as *mut Box<io::Writer:Send>
+                } else {
+                    self.sink
+                }
+            };
+            mem::drop(sink_from_raw(stale));
+        }
+    }
+}
+
+/// Invokes `f` with exclusive access to the writer that failure messages
+/// should currently be written to.
+pub fn with_stderr_sink<T>(f: |&mut io::Writer| -> T) -> T {
+    let in_use = unsafe {
+        let me = current_user();
+        let g = SINK_LOCK.lock();
+        while SINK_USER != 0 && SINK_USER != me {
+            g.wait();
+        }
+        if SINK_USER == me || SINK.is_null() {
+            // Either there's no sink, or this task is already writing to it,
+            // and waiting for itself to finish would never return.
+            if SINK_USER == 0 {
+                g.signal();
+            }
+            None
+        } else {
+            SINK_USER = me;
+            Some(SinkInUse {
+                sink: mem::replace(&mut SINK, 0 as *mut Box<io::Writer:Send>),
+                gen: SINK_GEN,
+            })
+        }
+    };
+    match in_use {
+        Some(in_use) => unsafe { f(&mut **in_use.sink) },
+        None => {
+            let mut err = Stderr;
+            f(&mut err)
+        }
+    }
+}
+
 pub fn abort(msg: &str) -> ! {
     let msg = if !msg.is_empty() { msg } else { "aborted" };
     let hash = msg.chars().fold(0, |accum, val| accum + (val as uint) );
@@ -1238,6 +1238,21 @@ mod tests {
         assert!(v1.position_elem(&4).is_none());
     }
 
+    #[test]
+    fn test_binary_search_by() {
+        let v = [2,4,6,8,10];
+        assert_eq!(v.binary_search_by(|x| x.cmp(&2)), Ok(0));
+        assert_eq!(v.binary_search_by(|x| x.cmp(&8)), Ok(3));
+        assert_eq!(v.binary_search_by(|x| x.cmp(&10)), Ok(4));
+        for i in range(0u, 6) {
+            let target = i as int * 2 + 1;
+            assert_eq!(v.binary_search_by(|x| x.cmp(&target)), Err(i));
+        }
+
+        let empty: [int, ..0] = [];
+        assert_eq!(empty.binary_search_by(|x| x.cmp(&1)), Err(0));
+    }
+
     #[test]
     fn test_bsearch_elem() {
         assert_eq!([1,2,3,4,5].bsearch_elem(&5), Some(4));
@@ -1896,6 +1911,29 @@ mod tests {
         v.push(Rc::new(2));
     }
 
+    #[test]
+    fn test_split_at() {
+        let values = [1u8,2,3,4,5];
+        let (left, right) = values.split_at(2);
+        assert!(left == [1, 2]);
+        assert!(right == [3, 4, 5]);
+
+        let (left, right) = values.split_at(0);
+        assert!(left.is_empty());
+        assert!(right == values);
+
+        let (left, right) = values.split_at(5);
+        assert!(left == values);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_split_at_fail() {
+        let values = [1u8,2,3];
+        values.split_at(4);
+    }
+
     #[test]
     fn test_mut_split_at() {
         let mut values = [1u8,2,3,4,5];
@@ -2114,6 +2152,32 @@ mod tests {
         let y: &mut [int] = [];
         assert!(y.mut_last().is_none());
     }
+
+    #[test]
+    fn test_slice_syntax() {
+        let x: &[int] = [1, 2, 3, 4, 5];
+        assert_eq!(x[], x);
+        assert_eq!(x[2..], &[3, 4, 5]);
+        assert_eq!(x[..3], &[1, 2, 3]);
+        assert_eq!(x[1..4], &[2, 3, 4]);
+
+        let mut a = [1, 2, 3, 4, 5];
+        {
+            let mut y: &mut [int] = a;
+            y[mut 1..3][0] = 6;
+            y[mut ..2][0] = 7;
+            y[mut 4..][0] = 8;
+            y[mut][2] = 9;
+        }
+        assert!(a == [7, 6, 9, 4, 8]);
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_slice_syntax_out_of_bounds() {
+        let x: &[int] = [1, 2, 3];
+        x[2..1];
+    }
 }
 
 #[cfg(test)]
@@ -73,7 +73,7 @@ use container::Container;
 use default::Default;
 use fmt;
 use io::Writer;
-use iter::{Iterator, range, AdditiveIterator};
+use iter::{Iterator, Extendable, range, AdditiveIterator};
 use mem::transmute;
 use mem;
 use option::{None, Option, Some};
@@ -757,6 +757,40 @@ pub trait StrAllocating: Str {
         out
     }
 
+    /// Returns the uppercase equivalent of this string, converting each char
+    /// with `char::to_uppercase`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// assert_eq!("Straße".to_uppercase(), "STRASSE".to_string());
+    /// ```
+    fn to_uppercase(&self) -> String {
+        let me = self.as_slice();
+        let mut out = String::with_capacity(me.len());
+        for c in me.chars() {
+            out.extend(c.to_uppercase());
+        }
+        out
+    }
+
+    /// Returns the lowercase equivalent of this string, converting each char
+    /// with `char::to_lowercase`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// assert_eq!("HELLO, Wörld".to_lowercase(), "hello, wörld".to_string());
+    /// ```
+    fn to_lowercase(&self) -> String {
+        let me = self.as_slice();
+        let mut out = String::with_capacity(me.len());
+        for c in me.chars() {
+            out.extend(c.to_lowercase());
+        }
+        out
+    }
+
     /// Replace all occurrences of one string with another.
     ///
     /// # Arguments
@@ -1403,6 +1437,38 @@ mod tests {
         assert!(is_utf8([0xF4, 0x8F, 0xBF, 0xBF]));
     }
 
+    #[test]
+    fn test_is_utf8_boundaries() {
+        // Place each sequence at every position in a run of ASCII, viewing the
+        // run from several starting offsets so that the sequence lands at
+        // every alignment relative to the word-at-a-time ASCII checks.
+        let valid: &[&[u8]] = &[&[0xC2, 0x80], &[0xE0, 0xA0, 0x80],
+                                &[0xEF, 0xBF, 0xBF], &[0xF0, 0x90, 0x80, 0x80]];
+        let invalid: &[&[u8]] = &[&[0x80], &[0xBF], &[0xFF], &[0xC0, 0x80],
+                                  &[0xE0, 0xA0], &[0xED, 0xA0, 0x80],
+                                  &[0xF4, 0x90, 0x80, 0x80]];
+        let len = 48u;
+        for offset in range(0u, 17) {
+            for &(seqs, ok) in [(valid, true), (invalid, false)].iter() {
+                for seq in seqs.iter() {
+                    for pos in range(offset, len - seq.len() + 1) {
+                        let mut v = Vec::from_elem(len, 'a' as u8);
+                        for (i, &b) in seq.iter().enumerate() {
+                            *v.get_mut(pos + i) = b;
+                        }
+                        assert_eq!(is_utf8(v.slice(offset, len)), ok);
+
+                        // Cutting a multibyte sequence short is an error.
+                        if ok {
+                            let end = pos + seq.len() - 1;
+                            assert!(!is_utf8(v.slice(offset, end)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_is_utf16() {
         macro_rules! pos ( ($($e:expr),*) => { { $(assert!(is_utf16($e));)* } });
@@ -1727,6 +1793,23 @@ mod tests {
         assert_eq!("\U0001d4ea\r".escape_default(), "\\U0001d4ea\\r".to_string());
     }
 
+    #[test]
+    fn test_to_uppercase() {
+        assert_eq!("".to_uppercase(), "".to_string());
+        assert_eq!("hello, world!".to_uppercase(), "HELLO, WORLD!".to_string());
+        assert_eq!("straße".to_uppercase(), "STRASSE".to_string());
+        assert_eq!("\ufb03n".to_uppercase(), "FFIN".to_string());
+        assert_eq!("ὀδυσσεύς".to_uppercase(), "ὈΔΥΣΣΕΎΣ".to_string());
+    }
+
+    #[test]
+    fn test_to_lowercase() {
+        assert_eq!("".to_lowercase(), "".to_string());
+        assert_eq!("HELLO, WORLD!".to_lowercase(), "hello, world!".to_string());
+        assert_eq!("GRÜSSE".to_lowercase(), "grüsse".to_string());
+        assert_eq!("\u0130".to_lowercase(), "i\u0307".to_string());
+    }
+
     #[test]
     fn test_total_ord() {
         "1234".cmp(&("123")) == Greater;
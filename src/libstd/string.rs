@@ -12,17 +12,20 @@
 
 use c_vec::CVec;
 use char::Char;
+use clone::Clone;
 use cmp::Equiv;
 use container::{Container, Mutable};
 use default::Default;
 use fmt;
 use from_str::FromStr;
 use io::Writer;
-use iter::{Extendable, FromIterator, Iterator, range};
+use iter::{DoubleEndedIterator, Extendable, FromIterator, Iterator, range};
 use mem;
+use ops::Drop;
 use option::{None, Option, Some};
 use ptr::RawPtr;
 use ptr;
+use raw::Slice;
 use result::{Result, Ok, Err};
 use slice::Vector;
 use str::{CharRange, Str, StrSlice, StrAllocating};
@@ -30,7 +33,7 @@ use str;
 use vec::Vec;
 
 /// A growable string stored as a UTF-8 encoded buffer.
-#[deriving(Clone, Eq, Ord, TotalEq, TotalOrd)]
+#[deriving(Eq, Ord, TotalEq, TotalOrd)]
 pub struct String {
     vec: Vec<u8>,
 }
@@ -274,6 +277,70 @@ impl String {
         Some(ch)
     }
 
+    /// Retains only the characters for which `f` returns true, removing the
+    /// others in place without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut s = String::from_str("f_o_o_b_a_r");
+    /// s.retain(|c| c != '_');
+    /// assert_eq!(s.as_slice(), "foobar");
+    /// ```
+    pub fn retain(&mut self, f: |char| -> bool) {
+        let len = self.len();
+        let mut del = 0u;
+        let mut idx = 0u;
+        unsafe {
+            // The characters kept are moved down over the ones removed, which
+            // leaves broken UTF-8 in between. The string is kept empty until
+            // it's done, in case `f` fails.
+            self.vec.set_len(0);
+            let p = self.vec.as_mut_ptr();
+            let bytes: &[u8] = mem::transmute(Slice { data: p as *u8, len: len });
+            while idx < len {
+                let CharRange {ch, next} = str::raw::from_utf8(bytes).char_range_at(idx);
+                if !f(ch) {
+                    del += next - idx;
+                } else if del > 0 {
+                    ptr::copy_memory(p.offset((idx - del) as int),
+                                     p.offset(idx as int) as *u8,
+                                     next - idx);
+                }
+                idx = next;
+            }
+            self.vec.set_len(len - del);
+        }
+    }
+
+    /// Removes the bytes from `start` up to (but not including) `end` from
+    /// the string, returning an iterator over the characters removed.
+    ///
+    /// The bytes are removed once the iterator is dropped, even if not all of
+    /// the characters were iterated over.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `start` is greater than `end`, if `end` is greater than the
+    /// length of the string, or if either of them is not on a character
+    /// boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut s = String::from_str("αβγδ");
+    /// let removed: String = s.drain(2, 6).collect();
+    /// assert_eq!(removed.as_slice(), "βγ");
+    /// assert_eq!(s.as_slice(), "αδ");
+    /// ```
+    pub fn drain<'a>(&'a mut self, start: uint, end: uint) -> Drain<'a> {
+        assert!(start <= end);
+        assert!(end <= self.len());
+        assert!(self.as_slice().is_char_boundary(start));
+        assert!(self.as_slice().is_char_boundary(end));
+        Drain { string: self, start: start, end: end, front: start, back: end }
+    }
+
     /// Views the string buffer as a mutable sequence of bytes.
     ///
     /// Callers must preserve the valid UTF-8 property.
@@ -282,6 +349,62 @@ impl String {
     }
 }
 
+/// An iterator over the characters removed from a `String` by `drain`.
+pub struct Drain<'a> {
+    string: &'a mut String,
+    // The bytes being removed
+    start: uint,
+    end: uint,
+    // The bytes which haven't been iterated over yet
+    front: uint,
+    back: uint,
+}
+
+impl<'a> Iterator<char> for Drain<'a> {
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        if self.front == self.back {
+            return None
+        }
+        let CharRange {ch, next} = self.string.as_slice().char_range_at(self.front);
+        self.front = next;
+        Some(ch)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let bytes = self.back - self.front;
+        ((bytes + 3) / 4, Some(bytes))
+    }
+}
+
+impl<'a> DoubleEndedIterator<char> for Drain<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        if self.front == self.back {
+            return None
+        }
+        let CharRange {ch, next} = self.string.as_slice().char_range_at_reverse(self.back);
+        self.back = next;
+        Some(ch)
+    }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for Drain<'a> {
+    fn drop(&mut self) {
+        let len = self.string.len();
+        let tail = len - self.end;
+        unsafe {
+            let p = self.string.vec.as_mut_ptr();
+            ptr::copy_memory(p.offset(self.start as int),
+                             p.offset(self.end as int) as *u8,
+                             tail);
+            self.string.vec.set_len(self.start + tail);
+        }
+    }
+}
+
 impl Container for String {
     #[inline]
     fn len(&self) -> uint {
@@ -296,6 +419,18 @@ impl Mutable for String {
     }
 }
 
+impl Clone for String {
+    #[inline]
+    fn clone(&self) -> String {
+        String { vec: self.vec.clone() }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &String) {
+        self.vec.clone_from(&source.vec)
+    }
+}
+
 impl FromIterator<char> for String {
     fn from_iter<I:Iterator<char>>(iterator: I) -> String {
         let mut buf = String::new();
@@ -369,7 +504,12 @@ impl FromStr for String {
 #[cfg(test)]
 mod tests {
     extern crate test;
+    use char::Char;
+    use clone::Clone;
     use container::{Container, Mutable};
+    use iter::{Iterator, DoubleEndedIterator};
+    use option::{Some, None};
+    use prelude::drop;
     use self::test::Bencher;
     use str::{Str, StrSlice};
     use super::String;
@@ -475,6 +615,65 @@ mod tests {
         s.truncate(1);
     }
 
+    #[test]
+    fn test_retain() {
+        let mut s = String::from_str("α_β_γ");
+        s.retain(|c| c != '_');
+        assert_eq!(s.as_slice(), "αβγ");
+
+        let mut s = String::from_str("ประเทศไทย中华b¢€𤭢");
+        s.retain(|c| c.len_utf8_bytes() != 3);
+        assert_eq!(s.as_slice(), "b¢𤭢");
+
+        let mut s = String::from_str("abc");
+        s.retain(|_| true);
+        assert_eq!(s.as_slice(), "abc");
+        s.retain(|_| false);
+        assert_eq!(s.as_slice(), "");
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut s = String::from_str("αβγδε");
+        let removed: String = s.drain(2, 6).collect();
+        assert_eq!(removed.as_slice(), "βγ");
+        assert_eq!(s.as_slice(), "αδε");
+
+        let removed: String = s.drain(2, 6).rev().collect();
+        assert_eq!(removed.as_slice(), "εδ");
+        assert_eq!(s.as_slice(), "α");
+
+        {
+            let mut it = s.drain(0, 2);
+            assert_eq!(it.size_hint(), (1, Some(2)));
+            assert_eq!(it.next(), Some('α'));
+            assert_eq!(it.next(), None);
+            assert_eq!(it.next_back(), None);
+        }
+        assert_eq!(s.as_slice(), "");
+
+        let mut s = String::from_str("hello world");
+        assert_eq!(s.drain(5, 5).next(), None);
+        drop(s.drain(0, 6));
+        assert_eq!(s.as_slice(), "world");
+        drop(s.drain(3, 5));
+        assert_eq!(s.as_slice(), "wor");
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_drain_split_codepoint() {
+        let mut s = String::from_str("αβ");
+        s.drain(1, 4);
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_drain_out_of_bounds() {
+        let mut s = String::from_str("abc");
+        s.drain(1, 4);
+    }
+
     #[test]
     fn test_str_clear() {
         let mut s = String::from_str("12345");
@@ -482,4 +681,14 @@ mod tests {
         assert_eq!(s.len(), 0);
         assert_eq!(s.as_slice(), "");
     }
+
+    #[test]
+    fn test_clone_from() {
+        let mut s = String::with_capacity(64);
+        s.push_str("hello");
+        let cap = s.vec.capacity();
+        s.clone_from(&String::from_str("world"));
+        assert_eq!(s.as_slice(), "world");
+        assert_eq!(s.vec.capacity(), cap);
+    }
 }
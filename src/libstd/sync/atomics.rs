@@ -15,7 +15,8 @@
 //! types.
 //!
 //! This module defines atomic versions of a select number of primitive
-//! types, including `AtomicBool`, `AtomicInt`, `AtomicUint`, and `AtomicOption`.
+//! types, including `AtomicBool`, `AtomicInt`, `AtomicUint`, `AtomicI64`,
+//! `AtomicU64` and `AtomicOption`.
 //! Atomic types present operations that, when used correctly, synchronize
 //! updates between threads.
 //!
@@ -111,8 +112,10 @@ use option::{Option,Some,None};
 use owned::Box;
 
 pub use core::atomics::{AtomicBool, AtomicInt, AtomicUint, AtomicPtr};
+pub use core::atomics::{AtomicI64, AtomicU64};
 pub use core::atomics::{Ordering, Relaxed, Release, Acquire, AcqRel, SeqCst};
 pub use core::atomics::{INIT_ATOMIC_BOOL, INIT_ATOMIC_INT, INIT_ATOMIC_UINT};
+pub use core::atomics::{INIT_ATOMIC_I64, INIT_ATOMIC_U64};
 pub use core::atomics::fence;
 
 /// An atomic, nullable unique pointer
@@ -189,8 +192,37 @@ impl<T> Drop for AtomicOption<T> {
 #[cfg(test)]
 mod test {
     use option::*;
+    use result::{Ok, Err};
     use super::*;
 
+    use alloc::arc::Arc;
+    use comm::channel;
+    use iter::range;
+    use kinds::{Send, Share};
+    use native;
+
+    static NTHREADS: uint = 8;
+    static NITERS: uint = 1000;
+
+    // Runs `f(thread, iteration)` on NTHREADS native threads, NITERS times
+    // each, and waits for all of them to finish.
+    fn run_threads<T: Send + Share>(shared: Arc<T>, f: fn(&T, uint, uint)) {
+        let (tx, rx) = channel();
+        for thread in range(0, NTHREADS) {
+            let tx = tx.clone();
+            let shared = shared.clone();
+            native::task::spawn(proc() {
+                for i in range(0, NITERS) {
+                    f(&*shared, thread, i);
+                }
+                tx.send(());
+            });
+        }
+        for _ in range(0, NTHREADS) {
+            rx.recv_unwrap();
+        }
+    }
+
     #[test]
     fn option_empty() {
         let option: AtomicOption<()> = AtomicOption::empty();
@@ -230,5 +262,64 @@ mod test {
         assert!(p.fill(box 2, SeqCst).is_none()); // shouldn't fail
         assert!(p.take(SeqCst) == Some(box 2));
     }
-}
 
+    #[test]
+    fn uint_fetch_max_contended() {
+        fn bump(x: &AtomicUint, thread: uint, i: uint) {
+            x.fetch_max(i * NTHREADS + thread, SeqCst);
+        }
+        let x = Arc::new(AtomicUint::new(0));
+        run_threads(x.clone(), bump);
+        assert_eq!(x.load(SeqCst), NITERS * NTHREADS - 1);
+    }
+
+    #[test]
+    fn int_fetch_min_contended() {
+        fn lower(x: &AtomicInt, thread: uint, i: uint) {
+            x.fetch_min(-((i * NTHREADS + thread) as int), SeqCst);
+        }
+        let x = Arc::new(AtomicInt::new(0));
+        run_threads(x.clone(), lower);
+        assert_eq!(x.load(SeqCst), -((NITERS * NTHREADS - 1) as int));
+    }
+
+    #[test]
+    fn u64_fetch_max_contended() {
+        fn bump(x: &AtomicU64, thread: uint, i: uint) {
+            x.fetch_max(((i * NTHREADS + thread) as u64) << 32, SeqCst);
+        }
+        let x = Arc::new(AtomicU64::new(0));
+        run_threads(x.clone(), bump);
+        assert_eq!(x.load(SeqCst), ((NITERS * NTHREADS - 1) as u64) << 32);
+    }
+
+    #[test]
+    fn uint_fetch_update_contended() {
+        // Every update must be applied exactly once, even when the
+        // compare-and-swap loses a race and `f` has to be retried.
+        fn incr(x: &AtomicUint, _thread: uint, _i: uint) {
+            assert!(x.fetch_update(SeqCst, SeqCst, |v| Some(v + 1)).is_ok());
+        }
+        let x = Arc::new(AtomicUint::new(0));
+        run_threads(x.clone(), incr);
+        assert_eq!(x.load(SeqCst), NITERS * NTHREADS);
+    }
+
+    #[test]
+    fn i64_fetch_update_contended() {
+        // Saturate at a bound which is crossed part way through; updates
+        // beyond it must be rejected with the current value.
+        fn incr(x: &AtomicI64, _thread: uint, _i: uint) {
+            let bound = (NITERS * NTHREADS / 2) as i64 - 0x1_0000_0000;
+            match x.fetch_update(SeqCst, SeqCst, |v| {
+                if v < bound { Some(v + 1) } else { None }
+            }) {
+                Ok(prev) => assert!(prev < bound),
+                Err(cur) => assert_eq!(cur, bound),
+            }
+        }
+        let x = Arc::new(AtomicI64::new(-0x1_0000_0000));
+        run_threads(x.clone(), incr);
+        assert_eq!(x.load(SeqCst), (NITERS * NTHREADS / 2) as i64 - 0x1_0000_0000);
+    }
+}
@@ -200,6 +200,12 @@ impl<T: Send> Worker<T> {
         unsafe { self.deque.pop() }
     }
 
+    /// Returns the number of elements in the work queue. Stealers may be
+    /// taking elements at the same time, so this is only a snapshot.
+    pub fn len(&self) -> uint {
+        self.deque.len()
+    }
+
     /// Gets access to the buffer pool that this worker is attached to. This can
     /// be used to create more deques which share the same buffer pool as this
     /// deque.
@@ -214,6 +220,13 @@ impl<T: Send> Stealer<T> {
         unsafe { self.deque.steal() }
     }
 
+    /// Returns the number of elements in the work queue. The worker and other
+    /// stealers may be using the queue at the same time, so this is only a
+    /// snapshot.
+    pub fn len(&self) -> uint {
+        self.deque.len()
+    }
+
     /// Gets access to the buffer pool that this stealer is attached to. This
     /// can be used to create more deques which share the same buffer pool as
     /// this deque.
@@ -242,6 +255,13 @@ impl<T: Send> Deque<T> {
         }
     }
 
+    fn len(&self) -> uint {
+        // A pop in progress may briefly move the bottom past the top
+        let t = self.top.load(SeqCst);
+        let b = self.bottom.load(SeqCst);
+        if b > t { (b - t) as uint } else { 0 }
+    }
+
     unsafe fn push(&self, data: T) {
         let mut b = self.bottom.load(SeqCst);
         let t = self.top.load(SeqCst);
@@ -435,6 +455,22 @@ mod tests {
         assert_eq!(s.clone().steal(), Data(1));
     }
 
+    #[test]
+    fn len() {
+        let pool = BufferPool::new();
+        let (w, s) = pool.deque();
+        assert_eq!(w.len(), 0);
+        for i in range(0, 200) { w.push(i); }
+        assert_eq!(w.len(), 200);
+        assert_eq!(s.len(), 200);
+        assert_eq!(s.steal(), Data(0));
+        assert_eq!(w.pop(), Some(199));
+        assert_eq!(s.len(), 198);
+        while w.pop().is_some() {}
+        assert_eq!(w.len(), 0);
+        assert_eq!(s.len(), 0);
+    }
+
     #[test]
     fn stealpush() {
         static AMT: int = 100000;
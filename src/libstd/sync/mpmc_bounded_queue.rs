@@ -211,10 +211,10 @@ mod tests {
         }
 
         for rx in completion_rxs.mut_iter() {
-            assert_eq!(nmsgs, rx.recv());
+            assert_eq!(nmsgs, rx.recv_unwrap());
         }
         for _ in range(0, nthreads) {
-            rx.recv();
+            rx.recv_unwrap();
         }
     }
 }
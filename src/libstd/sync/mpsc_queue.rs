@@ -202,7 +202,7 @@ mod tests {
         }
         drop(tx);
         for _ in range(0, nthreads) {
-            rx.recv();
+            rx.recv_unwrap();
         }
     }
 }
@@ -294,7 +294,7 @@ mod test {
             for _ in range(0, 100000) {
                 a.push(1);
             }
-            rx.recv();
+            rx.recv_unwrap();
         }
     }
 }
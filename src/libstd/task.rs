@@ -18,7 +18,8 @@
  *
  * Failure in one task does not propagate to any others (not to parent, not to
  * child).  Failure propagation is instead handled by using the channel send()
- * and recv() methods which will fail if the other end has hung up already.
+ * and recv_unwrap() methods which will fail if the other end has hung up
+ * already.
  *
  * Task Scheduling:
  *
@@ -36,22 +37,32 @@
  * ```
  */
 
+use alloc::arc::Arc;
 use any::Any;
+use cell::RefCell;
+use clone::Clone;
 use comm::{Sender, Receiver, channel};
-use io::Writer;
-use kinds::{Send, marker};
+use finally::Finally;
+use io::{Writer, IoResult};
+use iter::Iterator;
+use kinds::{Send, Share, marker};
+use mem;
 use option::{None, Some, Option};
 use owned::Box;
 use result::{Result, Ok, Err};
+use rt;
 use rt::local::Local;
 use rt::task::Task;
 use str::{Str, SendStr, IntoMaybeOwned};
+use string::String;
+use vec::Vec;
 
-#[cfg(test)] use any::AnyRefExt;
+use any::AnyRefExt;
+
+pub use core::failure::{BoundsCheckFailure, DivideByZeroFailure, OverflowFailure};
 #[cfg(test)] use owned::AnyOwnExt;
 #[cfg(test)] use result;
 #[cfg(test)] use str::StrAllocating;
-#[cfg(test)] use string::String;
 
 /// Indicates the manner in which a task exited.
 ///
@@ -61,6 +72,123 @@ use str::{Str, SendStr, IntoMaybeOwned};
 /// children tasks complete, recommend using a result future.
 pub type TaskResult = Result<(), Box<Any:Send>>;
 
+/// A failure payload which records the failure that caused it.
+///
+/// When a task fails because of the failure of another task (for example, the
+/// result of `try` being unwrapped), the original failure would usually be
+/// lost. Failing with a `CausedBy` preserves it: the default failure reporter
+/// prints the whole chain of causes, and the chain is returned intact to any
+/// task which collects the failure with `try`.
+pub struct CausedBy {
+    /// The payload describing this failure
+    pub msg: Box<Any:Send>,
+    /// The payload of the failure which caused this one. This may itself be
+    /// a `CausedBy`.
+    pub cause: Box<Any:Send>,
+}
+
+impl CausedBy {
+    /// Returns the payload of the original failure at the end of the chain.
+    pub fn root_cause<'a>(&'a self) -> &'a Any {
+        let mut cause: &'a Any = &*self.cause;
+        loop {
+            match cause.downcast_ref::<CausedBy>() {
+                Some(c) => cause = &*c.cause,
+                None => return cause,
+            }
+        }
+    }
+}
+
+/// Fails the current task with the message `msg`, recording `cause` (usually
+/// the payload of another task's failure) as the reason for the failure.
+pub fn fail_with_cause<M: Any + Send>(msg: M, cause: Box<Any:Send>) -> ! {
+    fail!(CausedBy { msg: box msg as Box<Any:Send>, cause: cause })
+}
+
+/// The payload of a failure triggered with `fail_with_status!`.
+///
+/// If the root task of a program fails with a `FailureStatus`, the process
+/// exits with `status` rather than the default failure status. This holds
+/// even if a different status was requested earlier with
+/// `os::set_exit_status`, so that embedders see the same exit code for the
+/// same failure.
+pub struct FailureStatus {
+    /// The exit status the process should report
+    pub status: int,
+    /// The payload describing this failure
+    pub msg: Box<Any:Send>,
+}
+
+impl FailureStatus {
+    /// Creates a new payload failing with `msg` and exiting with `status`.
+    pub fn new<M: Any + Send>(status: int, msg: M) -> FailureStatus {
+        FailureStatus { status: status, msg: box msg as Box<Any:Send> }
+    }
+}
+
+/// Returns the exit status that a process should report when its root task
+/// fails with the payload `msg`.
+pub fn failure_exit_status(msg: &Any) -> int {
+    match msg.downcast_ref::<FailureStatus>() {
+        Some(s) => s.status,
+        None => rt::DEFAULT_ERROR_CODE,
+    }
+}
+
+/// The payload of a failure raised by the compiler's runtime checks, such as
+/// an index being out of bounds or a division by zero.
+///
+/// The `class` is one of `BoundsCheckFailure`, `DivideByZeroFailure` or
+/// `OverflowFailure`, which allows callers of `try` to tell these failures
+/// apart without inspecting the message.
+pub struct CoreFailure {
+    /// The failure message
+    pub msg: String,
+    /// The class of the failure
+    pub class: &'static Any:Send,
+}
+
+impl CoreFailure {
+    /// Returns true if the class of this failure is `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        let class: &Any = self.class;
+        class.is::<T>()
+    }
+}
+
+/// A handler which is notified when a task fails.
+///
+/// The handler is invoked from within the failing task, after the failure has
+/// been triggered but before any of the task's stack has been unwound. This
+/// makes it a good place to record information about the failure (such as the
+/// task's name or the request it was servicing) which would otherwise be lost.
+///
+/// The handler stays in place for any later failures of the task, such as
+/// after a failure caught with `try_catch`. If the handler itself fails, then
+/// that failure is unwound normally and the handler is removed instead.
+pub trait FailureHandler: Send + Share {
+    /// Called with the failure message and the location where failure was
+    /// initiated. `col` is zero if the column isn't known.
+    fn on_fail(&self, msg: &str, file: &'static str, line: uint, col: uint);
+}
+
+/// A shared reference to a failure handler, as stored by each task.
+pub type FailureHandlerRef = Arc<Box<FailureHandler:Send+Share>>;
+
+/// The scheduling priority of a task, relative to the other threads of the
+/// process.
+#[deriving(Eq, Clone, Show)]
+pub enum Priority {
+    /// The task is scheduled less often than others
+    LowPriority,
+    /// The default priority of a task
+    NormalPriority,
+    /// The task is scheduled more often than others. Raising the priority of
+    /// a task usually requires special privileges.
+    HighPriority,
+}
+
 /// Task configuration options
 pub struct TaskOpts {
     /// Enable lifecycle notifications on the given channel
@@ -69,10 +197,22 @@ pub struct TaskOpts {
     pub name: Option<SendStr>,
     /// The size of the stack for the spawned task
     pub stack_size: Option<uint>,
+    /// The scheduling priority of the spawned task
+    pub priority: Option<Priority>,
+    /// The indices of the CPUs which the spawned task may run on
+    pub affinity: Option<Vec<uint>>,
+    /// Told whether the priority and affinity could be applied to the spawned
+    /// task. If they couldn't, the task exits without running its body once
+    /// the error has been sent here, whereas without this channel it fails.
+    pub scheduled: Option<Sender<IoResult<()>>>,
+    /// Keep the spawned task on the thread it starts running on
+    pub pinned: bool,
     /// Task-local stdout
     pub stdout: Option<Box<Writer:Send>>,
     /// Task-local stderr
     pub stderr: Option<Box<Writer:Send>>,
+    /// The handler to invoke if the task fails
+    pub on_fail: Option<FailureHandlerRef>,
 }
 
 /**
@@ -140,6 +280,59 @@ impl TaskBuilder {
         self
     }
 
+    /// Set the size of the stack for the task-to-be, in bytes, rather than
+    /// using the default size.
+    pub fn stack_size(mut self, size: uint) -> TaskBuilder {
+        self.opts.stack_size = Some(size);
+        self
+    }
+
+    /// Set the scheduling priority of the task-to-be.
+    ///
+    /// Priorities are currently only supported by native tasks on linux and
+    /// windows. Use `try_spawn` to find out whether the priority could be set,
+    /// as `spawn` fails if it couldn't.
+    pub fn priority(mut self, priority: Priority) -> TaskBuilder {
+        self.opts.priority = Some(priority);
+        self
+    }
+
+    /// Restrict the task-to-be to running on the CPUs with the given indices
+    /// (starting at 0).
+    ///
+    /// As with `priority`, this is currently only supported by native tasks on
+    /// linux and windows, and `try_spawn` returns an error if the affinity
+    /// can't be set.
+    pub fn affinity(mut self, cpus: &[uint]) -> TaskBuilder {
+        self.opts.affinity = Some(Vec::from_slice(cpus));
+        self
+    }
+
+    /// Keep the task-to-be on the OS thread which it starts running on.
+    ///
+    /// Green tasks are otherwise free to migrate among the scheduler threads
+    /// of their pool, which breaks tasks owning thread-affine resources. A
+    /// pinned green task is pinned to the scheduler which first runs it. Native
+    /// tasks always stay on their own thread, so this has no effect on them.
+    pub fn pinned(mut self) -> TaskBuilder {
+        self.opts.pinned = true;
+        self
+    }
+
+    /// Give the task-to-be the same failure handler as the current task, if
+    /// the current task has one.
+    pub fn inherit_on_fail(mut self) -> TaskBuilder {
+        self.opts.on_fail = Local::borrow(None::<Task>).on_fail.clone();
+        self
+    }
+
+    /// Set the handler to be invoked if the task-to-be fails.
+    pub fn on_fail<H: FailureHandler>(mut self, handler: H) -> TaskBuilder {
+        self.opts.on_fail = Some(Arc::new(box handler as
+                                          Box<FailureHandler:Send+Share>));
+        self
+    }
+
     /**
      * Add a wrapper to the body of the spawned task.
      *
@@ -169,8 +362,40 @@ impl TaskBuilder {
      * Sets up a new task with its own call stack and schedules it to run
      * the provided unique closure. The task has the properties and behavior
      * specified by the task_builder.
+     *
+     * # Failure
+     *
+     * Fails if the priority or affinity given to the builder can't be
+     * applied to the new task, see `try_spawn`.
      */
-    pub fn spawn(mut self, f: proc():Send) {
+    pub fn spawn(self, f: proc():Send) {
+        match self.try_spawn(f) {
+            Ok(()) => {}
+            Err(e) => fail!("failed to schedule the new task: {}", e),
+        }
+    }
+
+    /**
+     * Creates and executes a new child task, like `spawn`, returning an error
+     * if the priority or affinity given to the builder can't be applied to
+     * it.
+     *
+     * This waits for the new task to have been scheduled. If that fails, the
+     * task exits without running any of its body.
+     */
+    pub fn try_spawn(mut self, f: proc():Send) -> IoResult<()> {
+        if self.opts.priority.is_none() && self.opts.affinity.is_none() {
+            self.spawn_unscheduled(f);
+            return Ok(())
+        }
+        let (tx, rx) = channel();
+        self.opts.scheduled = Some(tx);
+        self.spawn_unscheduled(f);
+        // a runtime which isn't aware of the options has nothing to report
+        rx.recv().unwrap_or(Ok(()))
+    }
+
+    fn spawn_unscheduled(mut self, f: proc():Send) {
         let gen_body = self.gen_body.take();
         let f = match gen_body {
             Some(gen) => gen(f),
@@ -199,12 +424,15 @@ impl TaskBuilder {
 
         let result = self.future_result();
 
-        self.spawn(proc() {
+        match self.try_spawn(proc() {
             tx.send(f());
-        });
+        }) {
+            Ok(()) => {}
+            Err(e) => return Err(box e as Box<Any:Send>),
+        }
 
-        match result.recv() {
-            Ok(())     => Ok(rx.recv()),
+        match result.recv_unwrap() {
+            Ok(())     => Ok(rx.recv_unwrap()),
             Err(cause) => Err(cause)
         }
     }
@@ -222,8 +450,13 @@ impl TaskOpts {
             notify_chan: None,
             name: None,
             stack_size: None,
+            priority: None,
+            affinity: None,
+            scheduled: None,
+            pinned: false,
             stdout: None,
             stderr: None,
+            on_fail: None,
         }
     }
 }
@@ -248,6 +481,128 @@ pub fn try<T:Send>(f: proc():Send -> T) -> Result<T, Box<Any:Send>> {
     TaskBuilder::new().try(f)
 }
 
+/// Execute a function on the current task, catching any failure which occurs
+/// while it is running.
+///
+/// If the closure returns normally then its return value is returned in `Ok`.
+/// If the closure fails, then the task's stack is unwound back to this call,
+/// running destructors along the way, and the cause of the failure is
+/// returned in `Err`. The current task continues running as if it had not
+/// failed.
+///
+/// Unlike `try`, no new task is spawned, so the closure may borrow data from
+/// the caller's stack and need not be `Send`. Care should be taken, however,
+/// that any state the closure modifies is not observed in an inconsistent
+/// state after a failure has been caught.
+///
+/// # Failure
+///
+/// If this is called while the current task is already unwinding (for example
+/// from a destructor), a failure inside the closure is a double failure, and
+/// the process will abort.
+///
+/// # Example
+///
+/// ```rust
+/// use std::task;
+///
+/// let r = task::try_catch(|| -> int { fail!("oops") });
+/// assert!(r.is_err());
+/// assert!(!task::failing());
+/// ```
+pub fn try_catch<T>(f: || -> T) -> Result<T, Box<Any:Send>> {
+    let mut result = None;
+    unsafe {
+        let task: *mut Task = Local::unsafe_borrow();
+        (*task).unwinder.try(|| result = Some(f()));
+    }
+
+    match result {
+        Some(t) => Ok(t),
+        None => unsafe {
+            // The task may have migrated while unwinding, so borrow it anew
+            let task: *mut Task = Local::unsafe_borrow();
+            Err((*task).unwinder.reset().unwrap())
+        }
+    }
+}
+
+/// A scope in which tasks can be spawned that borrow data from the stack of
+/// the current task, see `scoped`.
+pub struct Scope<'a> {
+    results: RefCell<Vec<Receiver<TaskResult>>>,
+    failure: RefCell<Option<Box<Any:Send>>>,
+    marker: marker::InvariantLifetime<'a>,
+}
+
+impl<'a> Scope<'a> {
+    /// Spawns a new task running `f`, which is joined before the enclosing
+    /// call to `scoped` returns. Unlike with `spawn`, the closure may borrow
+    /// anything which outlives that call.
+    pub fn spawn(&self, f: proc():'a + Send) {
+        let mut builder = TaskBuilder::new();
+        self.results.borrow_mut().push(builder.future_result());
+        // This is safe because `scoped` joins this task before anything which
+        // the closure borrows can go out of scope, even when failing.
+        let f: proc():Send = unsafe { mem::transmute(f) };
+        builder.spawn(f);
+    }
+
+    // Waits for all of the spawned tasks, remembering the first failure
+    fn join(&self) {
+        let results = mem::take(&mut *self.results.borrow_mut());
+        for result in results.move_iter() {
+            match result.recv_unwrap() {
+                Err(cause) if self.failure.borrow().is_none() => {
+                    *self.failure.borrow_mut() = Some(cause);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Runs `f` with a scope in which tasks can be spawned, waiting for all of
+/// them to exit before returning.
+///
+/// Because the spawned tasks are joined on the way out, they can borrow data
+/// from the stack of the current task rather than having to own it, which is
+/// handy for fork-join parallelism.
+///
+/// # Failure
+///
+/// If `f` returns normally but one of the spawned tasks failed, the current
+/// task fails as well once all tasks have exited, with a `CausedBy` payload
+/// holding the first of the failures. If `f` itself fails, the spawned tasks
+/// are still waited for, and their failures are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use std::task;
+///
+/// let mut v = vec!(1, 2, 3, 4, 5, 6);
+/// task::scoped(|scope| {
+///     for chunk in v.as_mut_slice().mut_chunks(2) {
+///         scope.spawn(proc() {
+///             for x in chunk.mut_iter() { *x *= 2; }
+///         });
+///     }
+/// });
+/// assert_eq!(v.as_slice(), &[2, 4, 6, 8, 10, 12]);
+/// ```
+pub fn scoped<'a, T>(f: |&Scope<'a>| -> T) -> T {
+    let scope = Scope {
+        results: RefCell::new(Vec::new()),
+        failure: RefCell::new(None),
+        marker: marker::InvariantLifetime,
+    };
+    let ret = (|| f(&scope)).finally(|| scope.join());
+    match scope.failure.borrow_mut().take() {
+        Some(cause) => fail_with_cause("a scoped task failed", cause),
+        None => ret,
+    }
+}
 
 /* Lifecycle functions */
 
@@ -262,6 +617,16 @@ pub fn with_task_name<U>(blk: |Option<&str>| -> U) -> U {
     }
 }
 
+/// Set the handler which is invoked if the current task fails, replacing any
+/// previous handler.
+///
+/// Tasks spawned by the current task do not inherit the handler unless they
+/// are spawned with `TaskBuilder::inherit_on_fail`.
+pub fn on_fail<H: FailureHandler>(handler: H) {
+    let handler = Arc::new(box handler as Box<FailureHandler:Send+Share>);
+    Local::borrow(None::<Task>).on_fail = Some(handler);
+}
+
 pub fn deschedule() {
     //! Yield control to the task scheduler
 
@@ -326,7 +691,7 @@ fn test_run_basic() {
     TaskBuilder::new().spawn(proc() {
         tx.send(());
     });
-    rx.recv();
+    rx.recv_unwrap();
 }
 
 #[test]
@@ -339,7 +704,31 @@ fn test_with_wrapper() {
         };
         result
     }).spawn(proc() { });
-    rx.recv();
+    rx.recv_unwrap();
+}
+
+#[test]
+fn test_builder_stack_size() {
+    let r = TaskBuilder::new().stack_size(1 << 20).try(proc() {
+        // use up a good part of the stack
+        let buf = [1u8, ..100000];
+        buf[99999]
+    });
+    assert_eq!(r.ok(), Some(1));
+}
+
+#[test]
+fn test_builder_bad_affinity() {
+    // no runtime can pin a task to this many CPUs
+    let (tx, rx) = channel();
+    let r = TaskBuilder::new().affinity([1 << 20]).try_spawn(proc() {
+        tx.send(());
+    });
+    assert!(r.is_err());
+    assert!(rx.recv().is_err());
+
+    let r = TaskBuilder::new().affinity([1 << 20]).try(proc() {});
+    assert!(r.is_err());
 }
 
 #[test]
@@ -347,14 +736,14 @@ fn test_future_result() {
     let mut builder = TaskBuilder::new();
     let result = builder.future_result();
     builder.spawn(proc() {});
-    assert!(result.recv().is_ok());
+    assert!(result.recv_unwrap().is_ok());
 
     let mut builder = TaskBuilder::new();
     let result = builder.future_result();
     builder.spawn(proc() {
         fail!();
     });
-    assert!(result.recv().is_err());
+    assert!(result.recv_unwrap().is_err());
 }
 
 #[test] #[should_fail]
@@ -384,6 +773,161 @@ fn test_try_fail() {
     }
 }
 
+#[test]
+fn test_try_catch_success() {
+    let mut x = 1;
+    match try_catch(|| { x += 1; x }) {
+        Ok(2) => {}
+        _ => fail!()
+    }
+    assert_eq!(x, 2);
+}
+
+#[test]
+fn test_try_catch_fail() {
+    match try_catch(|| -> () { fail!("static string") }) {
+        Err(e) => {
+            type T = &'static str;
+            assert!(e.is::<T>());
+            assert_eq!(*e.move::<T>().unwrap(), "static string");
+        }
+        Ok(()) => fail!()
+    }
+    assert!(!failing());
+}
+
+#[test]
+fn test_try_catch_runs_destructors() {
+    use cell::Cell;
+    use ops::Drop;
+
+    struct Foo<'a> { dropped: &'a Cell<bool> }
+    #[unsafe_destructor]
+    impl<'a> Drop for Foo<'a> {
+        fn drop(&mut self) { self.dropped.set(true); }
+    }
+
+    let dropped = Cell::new(false);
+    let r = try_catch(|| -> () {
+        let _f = Foo { dropped: &dropped };
+        fail!()
+    });
+    assert!(r.is_err());
+    assert!(dropped.get());
+}
+
+#[test]
+fn test_try_catch_nested() {
+    let r = try_catch(|| {
+        let inner = try_catch(|| -> () { fail!("inner") });
+        assert!(inner.is_err());
+        3
+    });
+    assert_eq!(r.ok(), Some(3));
+}
+
+#[cfg(test)]
+struct CountFailures(Arc<::sync::atomics::AtomicUint>);
+
+#[cfg(test)]
+impl FailureHandler for CountFailures {
+    fn on_fail(&self, msg: &str, _file: &'static str, _line: uint,
+               _col: uint) {
+        let CountFailures(ref count) = *self;
+        assert_eq!(msg, "counted");
+        count.fetch_add(1, ::sync::atomics::SeqCst);
+    }
+}
+
+#[test]
+fn test_on_fail() {
+    use sync::atomics::{AtomicUint, SeqCst};
+
+    let count = Arc::new(AtomicUint::new(0));
+    let count2 = count.clone();
+    let r = try(proc() {
+        on_fail(CountFailures(count2));
+        fail!("counted");
+    });
+    assert!(r.is_err());
+    assert_eq!(count.load(SeqCst), 1);
+}
+
+#[test]
+fn test_on_fail_twice() {
+    use sync::atomics::{AtomicUint, SeqCst};
+
+    let count = Arc::new(AtomicUint::new(0));
+    let count2 = count.clone();
+    let r = try(proc() {
+        on_fail(CountFailures(count2));
+        assert!(try_catch(|| -> () { fail!("counted") }).is_err());
+        assert!(try_catch(|| -> () { fail!("counted") }).is_err());
+        fail!("counted");
+    });
+    assert!(r.is_err());
+    assert_eq!(count.load(SeqCst), 3);
+}
+
+#[test]
+fn test_on_fail_inherit() {
+    use sync::atomics::{AtomicUint, SeqCst};
+
+    let count = Arc::new(AtomicUint::new(0));
+    let count2 = count.clone();
+    let r = try(proc() {
+        on_fail(CountFailures(count2));
+        let r = TaskBuilder::new().inherit_on_fail().try(proc() {
+            fail!("counted");
+        });
+        assert!(r.is_err());
+        let r = TaskBuilder::new().try(proc() {
+            fail!("not counted");
+        });
+        assert!(r.is_err());
+    });
+    assert!(r.is_ok());
+    assert_eq!(count.load(SeqCst), 1);
+}
+
+#[test]
+fn test_on_fail_builder() {
+    use sync::atomics::{AtomicUint, SeqCst};
+
+    let count = Arc::new(AtomicUint::new(0));
+    let r = TaskBuilder::new().on_fail(CountFailures(count.clone())).try(proc() {
+        fail!("counted");
+    });
+    assert!(r.is_err());
+    assert_eq!(count.load(SeqCst), 1);
+}
+
+#[test]
+fn test_fail_with_cause() {
+    let r = try(proc() {
+        match try(proc() { fail!("root") }) {
+            Ok(()) => {}
+            Err(e) => fail_with_cause("middle", e),
+        }
+    });
+    let r = try(proc() {
+        match r {
+            Ok(()) => {}
+            Err(e) => fail_with_cause("top", e),
+        }
+    });
+    match r {
+        Err(e) => {
+            let c = e.downcast_ref::<CausedBy>().unwrap();
+            assert_eq!(*c.msg.downcast_ref::<&'static str>().unwrap(), "top");
+            let middle = c.cause.downcast_ref::<CausedBy>().unwrap();
+            assert_eq!(*middle.msg.downcast_ref::<&'static str>().unwrap(), "middle");
+            assert_eq!(*c.root_cause().downcast_ref::<&'static str>().unwrap(), "root");
+        }
+        Ok(()) => fail!()
+    }
+}
+
 #[test]
 fn test_spawn_sched() {
     use clone::Clone;
@@ -402,7 +946,7 @@ fn test_spawn_sched() {
 
     }
     f(10, tx);
-    rx.recv();
+    rx.recv_unwrap();
 }
 
 #[test]
@@ -415,7 +959,7 @@ fn test_spawn_sched_childs_on_default_sched() {
         });
     });
 
-    rx.recv();
+    rx.recv_unwrap();
 }
 
 #[cfg(test)]
@@ -430,7 +974,7 @@ fn avoid_copying_the_body(spawnfn: |v: proc():Send|) {
         tx.send(x_in_child);
     });
 
-    let x_in_child = rx.recv();
+    let x_in_child = rx.recv_unwrap();
     assert_eq!(x_in_parent, x_in_child);
 }
 
@@ -535,3 +1079,113 @@ fn test_try_fail_message_unit_struct() {
         Err(_) | Ok(()) => fail!()
     }
 }
+
+#[test]
+fn test_try_fail_bounds_check() {
+    let v = vec![1i, 2, 3];
+    match try(proc() {
+        let i = v.len();
+        v.get(i);
+    }) {
+        Err(e) => {
+            let failure = e.move::<CoreFailure>().unwrap();
+            assert!(failure.is::<BoundsCheckFailure>());
+            assert!(!failure.is::<DivideByZeroFailure>());
+        }
+        Ok(()) => fail!()
+    }
+}
+
+#[test]
+fn test_try_fail_is_not_core_failure() {
+    match try(proc() {
+        fail!("index out of bounds");
+    }) {
+        Err(e) => assert!(!e.is::<CoreFailure>()),
+        Ok(()) => fail!()
+    }
+}
+
+#[cfg(test)]
+mod scoped_test {
+    use prelude::*;
+    use super::{scoped, try, deschedule, CausedBy};
+    use any::AnyRefExt;
+    use owned::AnyOwnExt;
+
+    #[test]
+    fn borrow() {
+        let mut v = Vec::from_elem(10, 0u);
+        let total = 3u;
+        {
+            let total = &total;
+            scoped(|scope| {
+                for chunk in v.as_mut_slice().mut_chunks(3) {
+                    scope.spawn(proc() {
+                        for x in chunk.mut_iter() { *x = *total; }
+                    });
+                }
+            });
+        }
+        assert!(v.iter().all(|&x| x == 3));
+    }
+
+    #[test]
+    fn return_value() {
+        let v = vec!(1i, 2, 3);
+        let (tx, rx) = channel();
+        let n = scoped(|scope| {
+            let tx = tx.clone();
+            let v = &v;
+            scope.spawn(proc() { tx.send(v.len()); });
+            v.len() + 1
+        });
+        assert_eq!(n, 4);
+        assert_eq!(rx.try_recv(), Ok(3));
+    }
+
+    #[test]
+    fn child_fails() {
+        let (tx, rx) = channel();
+        let r = try(proc() {
+            scoped(|scope| {
+                scope.spawn(proc() { fail!("child") });
+                let tx = tx.clone();
+                scope.spawn(proc() {
+                    for _ in range(0, 10) { deschedule() }
+                    tx.send(());
+                });
+            });
+        });
+        // the other task was still waited for
+        assert_eq!(rx.try_recv(), Ok(()));
+        match r {
+            Err(e) => {
+                let c = e.downcast_ref::<CausedBy>().unwrap();
+                let root = c.root_cause().downcast_ref::<&'static str>();
+                assert_eq!(*root.unwrap(), "child");
+            }
+            Ok(()) => fail!()
+        }
+    }
+
+    #[test]
+    fn parent_fails() {
+        let (tx, rx) = channel();
+        let r = try(proc() {
+            scoped(|scope| {
+                scope.spawn(proc() { fail!("child") });
+                scope.spawn(proc() {
+                    for _ in range(0, 10) { deschedule() }
+                    tx.send(());
+                });
+                fail!("parent");
+            });
+        });
+        assert_eq!(rx.try_recv(), Ok(()));
+        match r {
+            Err(e) => assert_eq!(*e.move::<&'static str>().unwrap(), "parent"),
+            Ok(()) => fail!()
+        }
+    }
+}
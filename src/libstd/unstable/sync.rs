@@ -137,7 +137,7 @@ mod tests {
                 });
             };
 
-            for f in futures.mut_iter() { f.recv() }
+            for f in futures.mut_iter() { f.recv_unwrap() }
 
             total.with(|total| assert!(**total == num_tasks * count));
         }
@@ -20,7 +20,8 @@ use iter::{DoubleEndedIterator, FromIterator, Extendable, Iterator, range};
 use mem;
 use num::{CheckedMul, CheckedAdd};
 use num;
-use ops::{Add, Drop};
+use ops::{Add, Drop, IndexMut};
+use ops;
 use option::{None, Option, Some, Expect};
 use ptr::RawPtr;
 use ptr;
@@ -1243,79 +1244,23 @@ impl<T> Vec<T> {
         }
     }
 
-    /// Expands a vector in place, initializing the new elements to the result of a function.
-    ///
-    /// The vector is grown by `n` elements. The i-th new element are initialized to the value
-    /// returned by `f(i)` where `i` is in the range [0, n).
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let mut vec = vec!(0u, 1);
-    /// vec.grow_fn(3, |i| i);
-    /// assert_eq!(vec, vec!(0, 1, 0, 1, 2));
-    /// ```
-    pub fn grow_fn(&mut self, n: uint, f: |uint| -> T) {
-        self.reserve_additional(n);
-        for i in range(0u, n) {
-            self.push(f(i));
-        }
-    }
-}
-
-impl<T:TotalOrd> Vec<T> {
-    /// Sorts the vector in place.
-    ///
-    /// This sort is `O(n log n)` worst-case and stable, but allocates
-    /// approximately `2 * n`, where `n` is the length of `self`.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let mut vec = vec!(3i, 1, 2);
-    /// vec.sort();
-    /// assert_eq!(vec, vec!(1, 2, 3));
-    /// ```
-    pub fn sort(&mut self) {
-        self.as_mut_slice().sort()
-    }
-}
-
-impl<T> Mutable for Vec<T> {
-    #[inline]
-    fn clear(&mut self) {
-        self.truncate(0)
-    }
-}
-
-impl<T:Eq> Vec<T> {
-    /// Return true if a vector contains an element with the given value
+    /// Remove consecutive elements which `same` considers to be duplicates,
+    /// keeping the first of each run.
     ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let vec = vec!(1, 2, 3);
-    /// assert!(vec.contains(&1));
-    /// ```
-    pub fn contains(&self, x: &T) -> bool {
-        self.as_slice().contains(x)
-    }
-
-    /// Remove consecutive repeated elements in the vector.
-    ///
-    /// If the vector is sorted, this removes all duplicates.
+    /// `same` is called with each element and the last element kept before
+    /// it.
     ///
     /// # Example
     ///
     /// ```rust
-    /// let mut vec = vec!(1, 2, 2, 3, 2);
-    /// vec.dedup();
-    /// assert_eq!(vec, vec!(1, 2, 3, 2));
+    /// let mut vec = vec!(1i, 3, 10, 21, 20, 5);
+    /// vec.dedup_by(|a, b| *a / 10 == *b / 10);
+    /// assert_eq!(vec, vec!(1, 10, 21, 5));
     /// ```
-    pub fn dedup(&mut self) {
+    pub fn dedup_by(&mut self, same: |&T, &T| -> bool) {
         unsafe {
             // Although we have a mutable reference to `self`, we cannot make
-            // *arbitrary* changes. The `Eq` comparisons could fail, so we
+            // *arbitrary* changes. The comparisons could fail, so we
             // must ensure that the vector is in a valid state at all time.
             //
             // The way that we handle this is by using swaps; we iterate
@@ -1382,7 +1327,7 @@ impl<T:Eq> Vec<T> {
             while r < ln {
                 let p_r = p.offset(r as int);
                 let p_wm1 = p.offset((w - 1) as int);
-                if *p_r != *p_wm1 {
+                if !same(&*p_r, &*p_wm1) {
                     if r != w {
                         let p_w = p_wm1.offset(1);
                         mem::swap(&mut *p_r, &mut *p_w);
@@ -1395,6 +1340,137 @@ impl<T:Eq> Vec<T> {
             self.truncate(w);
         }
     }
+
+    /// Remove consecutive elements which have the same key, keeping the
+    /// first of each run.
+    ///
+    /// If the vector is sorted by the key, this removes all duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut vec = vec!(10i, 20, 21, 30, 20);
+    /// vec.dedup_by_key(|x| *x / 10);
+    /// assert_eq!(vec, vec!(10, 20, 30, 20));
+    /// ```
+    pub fn dedup_by_key<K: Eq>(&mut self, key: |&T| -> K) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes the elements from `start` up to (but not including) `end`
+    /// from the vector, returning an iterator which moves them out.
+    ///
+    /// The elements after the range are moved back into place once the
+    /// iterator is dropped, and any elements of the range which weren't
+    /// iterated over are dropped along with it. If the iterator is leaked
+    /// instead, the vector is left holding just the elements before `start`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `start` is greater than `end` or if `end` is greater than the
+    /// length of the vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut vec = vec!(1i, 2, 3, 4, 5);
+    /// let removed: Vec<int> = vec.drain(1, 3).collect();
+    /// assert_eq!(removed, vec!(2, 3));
+    /// assert_eq!(vec, vec!(1, 4, 5));
+    /// ```
+    pub fn drain<'a>(&'a mut self, start: uint, end: uint) -> Drain<'a, T> {
+        assert!(start <= end);
+        assert!(end <= self.len);
+        unsafe {
+            let len = self.len;
+            // Until the iterator is done, the vector only owns the elements
+            // before the range
+            self.set_len(start);
+            let range: &'a [T] = mem::transmute(Slice {
+                data: self.as_ptr().offset(start as int),
+                len: end - start,
+            });
+            Drain {
+                vec: self,
+                iter: range.iter(),
+                tail: end,
+                tail_len: len - end,
+            }
+        }
+    }
+
+    /// Expands a vector in place, initializing the new elements to the result of a function.
+    ///
+    /// The vector is grown by `n` elements. The i-th new element are initialized to the value
+    /// returned by `f(i)` where `i` is in the range [0, n).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut vec = vec!(0u, 1);
+    /// vec.grow_fn(3, |i| i);
+    /// assert_eq!(vec, vec!(0, 1, 0, 1, 2));
+    /// ```
+    pub fn grow_fn(&mut self, n: uint, f: |uint| -> T) {
+        self.reserve_additional(n);
+        for i in range(0u, n) {
+            self.push(f(i));
+        }
+    }
+}
+
+impl<T:TotalOrd> Vec<T> {
+    /// Sorts the vector in place.
+    ///
+    /// This sort is `O(n log n)` worst-case and stable, but allocates
+    /// approximately `2 * n`, where `n` is the length of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut vec = vec!(3i, 1, 2);
+    /// vec.sort();
+    /// assert_eq!(vec, vec!(1, 2, 3));
+    /// ```
+    pub fn sort(&mut self) {
+        self.as_mut_slice().sort()
+    }
+}
+
+impl<T> Mutable for Vec<T> {
+    #[inline]
+    fn clear(&mut self) {
+        self.truncate(0)
+    }
+}
+
+impl<T:Eq> Vec<T> {
+    /// Return true if a vector contains an element with the given value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let vec = vec!(1, 2, 3);
+    /// assert!(vec.contains(&1));
+    /// ```
+    pub fn contains(&self, x: &T) -> bool {
+        self.as_slice().contains(x)
+    }
+
+    /// Remove consecutive repeated elements in the vector.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut vec = vec!(1, 2, 2, 3, 2);
+    /// vec.dedup();
+    /// assert_eq!(vec, vec!(1, 2, 3, 2));
+    /// ```
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| *a == *b)
+    }
 }
 
 impl<T> Vector<T> for Vec<T> {
@@ -1414,6 +1490,61 @@ impl<T> Vector<T> for Vec<T> {
     }
 }
 
+impl<T> IndexMut<uint, T> for Vec<T> {
+    /// Returns a mutable reference to the element at `index`, allowing
+    /// `v[index] = x`.
+    ///
+    /// Fails if `index` is out of bounds.
+    #[inline]
+    fn index_mut<'a>(&'a mut self, index: &uint) -> &'a mut T {
+        self.get_mut(*index)
+    }
+}
+
+impl<'a, T> ops::Slice<'a, uint, &'a [T]> for Vec<T> {
+    #[inline]
+    fn as_slice_(&'a self) -> &'a [T] {
+        self.as_slice()
+    }
+
+    #[inline]
+    fn slice_from_or_fail(&'a self, from: &uint) -> &'a [T] {
+        self.slice_from(*from)
+    }
+
+    #[inline]
+    fn slice_to_or_fail(&'a self, to: &uint) -> &'a [T] {
+        self.slice_to(*to)
+    }
+
+    #[inline]
+    fn slice_or_fail(&'a self, from: &uint, to: &uint) -> &'a [T] {
+        self.slice(*from, *to)
+    }
+}
+
+impl<'a, T> ops::SliceMut<'a, uint, &'a mut [T]> for Vec<T> {
+    #[inline]
+    fn as_mut_slice_(&'a mut self) -> &'a mut [T] {
+        self.as_mut_slice()
+    }
+
+    #[inline]
+    fn slice_from_or_fail_mut(&'a mut self, from: &uint) -> &'a mut [T] {
+        self.mut_slice_from(*from)
+    }
+
+    #[inline]
+    fn slice_to_or_fail_mut(&'a mut self, to: &uint) -> &'a mut [T] {
+        self.mut_slice_to(*to)
+    }
+
+    #[inline]
+    fn slice_or_fail_mut(&'a mut self, from: &uint, to: &uint) -> &'a mut [T] {
+        self.mut_slice(*from, *to)
+    }
+}
+
 impl<T: Clone, V: Vector<T>> Add<V, Vec<T>> for Vec<T> {
     #[inline]
     fn add(&self, rhs: &V) -> Vec<T> {
@@ -1495,6 +1626,55 @@ impl<T> Drop for MoveItems<T> {
     }
 }
 
+/// An iterator that moves a range of elements out of a vector.
+///
+/// This is created by the `drain` method on `Vec`.
+pub struct Drain<'a, T> {
+    vec: &'a mut Vec<T>,
+    iter: Items<'a, T>, // the elements of the range not yet moved out
+    tail: uint, // the index of the first element after the range
+    tail_len: uint, // the number of elements after the range
+}
+
+impl<'a, T> Iterator<T> for Drain<'a, T> {
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            self.iter.next().map(|x| ptr::read(x))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator<T> for Drain<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        unsafe {
+            self.iter.next_back().map(|x| ptr::read(x))
+        }
+    }
+}
+
+#[unsafe_destructor]
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        // destroy the remaining elements of the range
+        for _x in *self {}
+        unsafe {
+            let start = self.vec.len();
+            let p = self.vec.as_mut_ptr();
+            ptr::copy_memory(p.offset(start as int),
+                             p.offset(self.tail as int) as *T,
+                             self.tail_len);
+            self.vec.set_len(start + self.tail_len);
+        }
+    }
+}
+
 /**
  * Convert an iterator of pairs into a pair of vectors.
  *
@@ -1582,6 +1762,7 @@ pub mod raw {
 #[cfg(test)]
 mod tests {
     use prelude::*;
+    use mem;
     use mem::size_of;
     use kinds::marker;
     use super::{unzip, raw, FromVec};
@@ -1764,6 +1945,89 @@ mod tests {
         assert!(vec == Vec::from_slice([2u, 4]));
     }
 
+    #[test]
+    fn test_dedup_by() {
+        let mut vec = vec!(1i, 3, 10, 21, 20, 5);
+        vec.dedup_by(|a, b| *a / 10 == *b / 10);
+        assert_eq!(vec, vec!(1, 10, 21, 5));
+
+        // Each element is compared with the last one kept
+        let mut vec = vec!(1i, 2, 3, 4, 5, 9);
+        vec.dedup_by(|a, b| *a - *b < 3);
+        assert_eq!(vec, vec!(1, 4, 9));
+
+        let mut vec: Vec<int> = vec!();
+        vec.dedup_by(|_, _| fail!());
+        assert_eq!(vec, vec!());
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let mut vec = vec!((1u, "a"), (1, "b"), (2, "c"), (1, "d"), (1, "e"));
+        vec.dedup_by_key(|&(k, _)| k);
+        assert_eq!(vec, vec!((1, "a"), (2, "c"), (1, "d")));
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut vec = vec!(box 1i, box 2, box 3, box 4, box 5);
+        let removed: Vec<Box<int>> = vec.drain(1, 3).collect();
+        assert_eq!(removed, vec!(box 2, box 3));
+        assert_eq!(vec, vec!(box 1, box 4, box 5));
+
+        let removed: Vec<Box<int>> = vec.drain(1, 3).rev().collect();
+        assert_eq!(removed, vec!(box 5, box 4));
+        assert_eq!(vec, vec!(box 1));
+
+        assert_eq!(vec.drain(1, 1).next(), None);
+        {
+            let mut it = vec.drain(0, 1);
+            assert_eq!(it.size_hint(), (1, Some(1)));
+            assert_eq!(it.next(), Some(box 1));
+            assert_eq!(it.next(), None);
+        }
+        assert!(vec.is_empty());
+
+        let mut vec = vec!((), (), ());
+        assert_eq!(vec.drain(0, 2).count(), 2);
+        assert_eq!(vec.len(), 1);
+    }
+
+    #[test]
+    fn test_drain_drop() {
+        static mut drops: uint = 0;
+        struct Elem(int);
+        impl Drop for Elem {
+            fn drop(&mut self) {
+                unsafe { drops += 1; }
+            }
+        }
+
+        let mut v = vec![Elem(1), Elem(2), Elem(3), Elem(4), Elem(5)];
+        {
+            let mut it = v.drain(1, 4);
+            let Elem(x) = it.next().unwrap();
+            assert_eq!(x, 2);
+        }
+        assert_eq!(unsafe { drops }, 3);
+        assert_eq!(v.len(), 2);
+        let Elem(x) = *v.get(1);
+        assert_eq!(x, 5);
+
+        // A leaked iterator leaves the elements before the range
+        unsafe { mem::forget(v.drain(1, 2)); }
+        assert_eq!(v.len(), 1);
+        drop(v);
+        assert_eq!(unsafe { drops }, 4);
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_drain_out_of_bounds() {
+        let mut v = vec!(1i, 2, 3);
+        v.drain(1, 4);
+    }
+
     #[test]
     fn zero_sized_values() {
         let mut v = Vec::new();
@@ -1900,4 +2164,50 @@ mod tests {
         let mut v = vec![BadElem(1), BadElem(2), BadElem(0xbadbeef), BadElem(4)];
         v.truncate(0);
     }
+
+    #[test]
+    fn test_index_mut() {
+        let mut v = vec!(1, 2, 3);
+        v[1] = 5;
+        v[2] += 1;
+        assert_eq!(v, vec!(1, 5, 4));
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_index_mut_out_of_bounds() {
+        let mut v = vec!(1, 2, 3);
+        v[3] = 4;
+    }
+
+    #[test]
+    fn test_slice_syntax() {
+        let v = vec!(1, 2, 3, 4, 5);
+        assert_eq!(v[], &[1, 2, 3, 4, 5]);
+        assert_eq!(v[2..], &[3, 4, 5]);
+        assert_eq!(v[..3], &[1, 2, 3]);
+        assert_eq!(v[1..4], &[2, 3, 4]);
+        assert!(v[2..2].is_empty());
+    }
+
+    #[test]
+    fn test_slice_mut_syntax() {
+        let mut v = vec!(1, 2, 3, 4, 5);
+        {
+            let s = v[mut 1..3];
+            s[0] = 6;
+            s[1] = 7;
+        }
+        for x in v[mut 3..].mut_iter() {
+            *x += 10;
+        }
+        assert_eq!(v, vec!(1, 6, 7, 14, 15));
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_slice_out_of_bounds() {
+        let v = vec!(1, 2, 3);
+        v[1..4];
+    }
 }
@@ -40,9 +40,12 @@ impl<S:Send,R:Send> DuplexStream<S, R> {
     pub fn send_opt(&self, x: S) -> Result<(), S> {
         self.tx.send_opt(x)
     }
-    pub fn recv(&self) -> R {
+    pub fn recv(&self) -> Result<R, comm::RecvError> {
         self.rx.recv()
     }
+    pub fn recv_unwrap(&self) -> R {
+        self.rx.recv_unwrap()
+    }
     pub fn try_recv(&self) -> Result<R, comm::TryRecvError> {
         self.rx.try_recv()
     }
@@ -63,7 +66,7 @@ mod test {
         left.send("abc".to_string());
         right.send(123);
 
-        assert!(left.recv() == 123);
-        assert!(right.recv() == "abc".to_string());
+        assert!(left.recv_unwrap() == 123);
+        assert!(right.recv_unwrap() == "abc".to_string());
     }
 }
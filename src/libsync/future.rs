@@ -26,6 +26,7 @@
 
 #![allow(missing_doc)]
 
+use std::comm::{Select, Empty, Disconnected, TryRecvError};
 use std::mem::replace;
 
 /// A type encapsulating the result of a computation which may not be complete
@@ -35,10 +36,31 @@ pub struct Future<A> {
 
 enum FutureState<A> {
     Pending(proc():Send -> A),
+    Receiving(Box<Source<A>:Send>),
     Evaluating,
     Forced(A)
 }
 
+// The value of a future which is being computed in another task. This is only
+// ever a `Receiver`, but hiding it behind a trait object means that the methods
+// which don't care where the value comes from don't need `A` to be `Send`.
+trait Source<A> {
+    fn recv(&mut self) -> A;
+    fn try_recv(&mut self) -> Result<A, TryRecvError>;
+    fn take(&mut self) -> Receiver<A>;
+}
+
+impl<A: Send> Source<A> for Receiver<A> {
+    fn recv(&mut self) -> A { self.recv_unwrap() }
+    fn try_recv(&mut self) -> Result<A, TryRecvError> {
+        Receiver::try_recv(self)
+    }
+    fn take(&mut self) -> Receiver<A> {
+        let (_, rx) = channel();
+        replace(self, rx)
+    }
+}
+
 /// Methods on the `future` type
 impl<A:Clone> Future<A> {
     pub fn get(&mut self) -> A {
@@ -67,18 +89,39 @@ impl<A> Future<A> {
         match self.state {
             Forced(ref v) => return v,
             Evaluating => fail!("Recursive forcing of future!"),
-            Pending(_) => {
-                match replace(&mut self.state, Evaluating) {
+            Pending(_) | Receiving(_) => {
+                let val = match replace(&mut self.state, Evaluating) {
                     Forced(_) | Evaluating => fail!("Logic error."),
-                    Pending(f) => {
-                        self.state = Forced(f());
-                        self.get_ref()
-                    }
-                }
+                    Pending(f) => f(),
+                    Receiving(mut src) => src.recv(),
+                };
+                self.state = Forced(val);
+                self.get_ref()
             }
         }
     }
 
+    /// Returns whether the value of this future is available, in which case
+    /// `get` will not block.
+    ///
+    /// A future created with `from_fn` is only ready once it has been forced,
+    /// as its function runs in the task which forces it. A future whose value
+    /// is computed by another task is also considered ready if that task
+    /// failed, as `get` will then fail immediately.
+    pub fn is_ready(&mut self) -> bool {
+        let val = match self.state {
+            Forced(_) => return true,
+            Pending(_) | Evaluating => return false,
+            Receiving(ref mut src) => match src.try_recv() {
+                Ok(val) => val,
+                Err(Empty) => return false,
+                Err(Disconnected) => return true,
+            },
+        };
+        self.state = Forced(val);
+        true
+    }
+
     pub fn from_value(val: A) -> Future<A> {
         /*!
          * Create a future from a value.
@@ -112,9 +155,7 @@ impl<A:Send> Future<A> {
          * waiting for the result to be received on the port.
          */
 
-        Future::from_fn(proc() {
-            rx.recv()
-        })
+        Future {state: Receiving(box rx as Box<Source<A>:Send>)}
     }
 
     pub fn spawn(blk: proc():Send -> A) -> Future<A> {
@@ -133,6 +174,104 @@ impl<A:Send> Future<A> {
 
         Future::from_receiver(rx)
     }
+
+    /// Creates a future whose value is the result of calling `f` on the value
+    /// of this future.
+    ///
+    /// Like `from_fn`, `f` is not spawned into another task. It runs in the
+    /// task which forces the new future, once the value of this future is
+    /// available.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sync::Future;
+    ///
+    /// let mut f = Future::spawn(proc() 21u).then(proc(n) n * 2);
+    /// assert_eq!(f.get(), 42);
+    /// ```
+    pub fn then<B>(self, f: proc(A):Send -> B) -> Future<B> {
+        Future::from_fn(proc() {
+            f(self.unwrap())
+        })
+    }
+
+    /// Converts this future into a receiver which the value will be sent on,
+    /// so that it can be waited on along with other receivers with `select!`.
+    ///
+    /// If this future was created with `from_fn` and hasn't been forced, its
+    /// function is spawned into a new task to compute the value. If the task
+    /// computing the value fails, the receiver is disconnected without any
+    /// value being sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sync::Future;
+    ///
+    /// let (tx, rx) = channel::<uint>();
+    /// let f = Future::spawn(proc() 3u).into_receiver();
+    /// select! {
+    ///     n = f.recv_unwrap() => assert_eq!(n, 3),
+    ///     _ = rx.recv_unwrap() => fail!()
+    /// }
+    /// # drop(tx);
+    /// ```
+    pub fn into_receiver(mut self) -> Receiver<A> {
+        match replace(&mut self.state, Evaluating) {
+            Forced(val) => {
+                let (tx, rx) = channel();
+                tx.send(val);
+                rx
+            }
+            Pending(f) => Future::spawn(f).into_receiver(),
+            Receiving(mut src) => src.take(),
+            Evaluating => fail!("Logic error."),
+        }
+    }
+
+    /// Creates a future whose value is the values of all of `futures`, in the
+    /// same order.
+    ///
+    /// Forcing the new future forces each of `futures` in turn, and fails if
+    /// any of them fails.
+    pub fn all(futures: Vec<Future<A>>) -> Future<Vec<A>> {
+        Future::from_fn(proc() {
+            futures.move_iter().map(|f| f.unwrap()).collect()
+        })
+    }
+
+    /// Creates a future whose value is the value of whichever of `futures`
+    /// becomes available first, along with its index in `futures`.
+    ///
+    /// Forcing the new future converts each of `futures` into a receiver with
+    /// `into_receiver` and waits for the first value to arrive. If the task
+    /// computing one of the values fails before any value arrives, then
+    /// forcing the new future fails as well.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `futures` is empty, as there would never be a value.
+    pub fn any(futures: Vec<Future<A>>) -> Future<(uint, A)> {
+        assert!(futures.len() > 0, "Future::any called with no futures");
+        Future::from_fn(proc() {
+            let rxs: Vec<Receiver<A>> = futures.move_iter().map(|f| {
+                f.into_receiver()
+            }).collect();
+            let sel = Select::new();
+            // The handles can't be moved once they've been added, so they're
+            // all created before any of them are added.
+            let mut handles: Vec<_> = rxs.iter().map(|rx| {
+                sel.handle(rx)
+            }).collect();
+            for handle in handles.mut_iter() {
+                unsafe { handle.add(); }
+            }
+            let id = sel.wait();
+            let i = handles.iter().position(|h| h.id() == id).unwrap();
+            (i, handles.get_mut(i).recv_unwrap())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -192,6 +331,112 @@ mod test {
         let _x: String = f.get();
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut f = Future::from_value(1);
+        assert!(f.is_ready());
+
+        let mut f = Future::from_fn(proc() 2);
+        assert!(!f.is_ready());
+        assert_eq!(f.get(), 2);
+        assert!(f.is_ready());
+
+        let (tx, rx) = channel();
+        let mut f = Future::from_receiver(rx);
+        assert!(!f.is_ready());
+        tx.send(3);
+        assert!(f.is_ready());
+        assert_eq!(f.get(), 3);
+    }
+
+    #[test]
+    fn test_is_ready_failed() {
+        let (tx, rx) = channel::<int>();
+        let mut f = Future::from_receiver(rx);
+        drop(tx);
+        assert!(f.is_ready());
+        assert!(task::try(proc() { let _ = f.get(); }).is_err());
+    }
+
+    #[test]
+    fn test_then() {
+        let mut f = Future::from_value(2).then(proc(n) n * 3)
+                                         .then(proc(n) n.to_str());
+        assert_eq!(f.get(), "6".to_string());
+
+        let mut f = Future::spawn(proc() "whale").then(proc(s) s.len());
+        assert_eq!(f.get(), 5);
+    }
+
+    #[test]
+    fn test_into_receiver() {
+        assert_eq!(Future::from_value(1).into_receiver().recv_unwrap(), 1);
+        assert_eq!(Future::from_fn(proc() 2).into_receiver().recv_unwrap(), 2);
+        assert_eq!(Future::spawn(proc() 3).into_receiver().recv_unwrap(), 3);
+
+        let mut f = Future::from_fn(proc() 4);
+        assert_eq!(f.get(), 4);
+        assert_eq!(f.into_receiver().recv_unwrap(), 4);
+    }
+
+    #[test]
+    fn test_into_receiver_select() {
+        let (tx, rx) = channel::<int>();
+        let (tx2, rx2) = channel();
+        let never = Future::from_receiver(rx).into_receiver();
+        let f = Future::from_receiver(rx2).into_receiver();
+        tx2.send(5);
+        select! {
+            _ = never.recv_unwrap() => fail!(),
+            n = f.recv_unwrap() => assert_eq!(n, 5)
+        }
+        drop(tx);
+    }
+
+    #[test]
+    fn test_into_receiver_failed() {
+        let rx = Future::spawn(proc() -> int { fail!() }).into_receiver();
+        assert!(rx.recv_opt().is_err());
+    }
+
+    #[test]
+    fn test_all() {
+        let futures = vec!(Future::from_value(1), Future::from_fn(proc() 2),
+                           Future::spawn(proc() 3));
+        assert_eq!(Future::all(futures).unwrap(), vec!(1, 2, 3));
+        let empty: Vec<Future<int>> = Vec::new();
+        assert_eq!(Future::all(empty).unwrap(), Vec::new());
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_all_fail() {
+        let futures = vec!(Future::from_value(1),
+                           Future::spawn(proc() fail!()));
+        Future::all(futures).unwrap();
+    }
+
+    #[test]
+    fn test_any() {
+        let (tx, rx) = channel::<int>();
+        let (tx2, rx2) = channel();
+        let futures = vec!(Future::from_receiver(rx),
+                           Future::from_receiver(rx2));
+        tx2.send(7);
+        assert_eq!(Future::any(futures).unwrap(), (1, 7));
+        drop(tx);
+
+        let futures = vec!(Future::from_fn(proc() 8));
+        assert_eq!(Future::any(futures).unwrap(), (0, 8));
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_any_empty() {
+        let empty: Vec<Future<int>> = Vec::new();
+        Future::any(empty);
+    }
+
     #[test]
     fn test_sendable_future() {
         let expected = "schlorf";
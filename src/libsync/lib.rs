@@ -34,7 +34,8 @@ pub use task_pool::TaskPool;
 pub use future::Future;
 pub use alloc::arc::{Arc, Weak};
 pub use lock::{Mutex, MutexGuard, Condvar, Barrier,
-               RWLock, RWLockReadGuard, RWLockWriteGuard};
+               RWLock, RWLockReadGuard, RWLockWriteGuard,
+               RWLockUpgradableGuard};
 
 // The mutex/rwlock in this module are not meant for reexport
 pub use raw::{Semaphore, SemaphoreGuard};
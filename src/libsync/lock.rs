@@ -113,6 +113,35 @@ impl<'a> Condvar<'a> {
         PoisonOnFail::check(*self.poison.flag, self.name);
     }
 
+    /// Atomically exit the associated lock and block until a signal is sent,
+    /// or until `msecs` milliseconds have passed. The lock is reacquired
+    /// before returning either way.
+    ///
+    /// Returns whether the task was woken by a signal rather than by the
+    /// timeout. Note that a signal doesn't guarantee that the condition the
+    /// task was waiting for still holds (another task may have taken the lock
+    /// first), so the condition should be checked again in a loop.
+    ///
+    /// wait_timeout() is equivalent to wait_timeout_on(0, msecs).
+    ///
+    /// # Failure
+    ///
+    /// As with wait(), and also fails if the local runtime is unable to create
+    /// a timer.
+    #[inline]
+    pub fn wait_timeout(&self, msecs: u64) -> bool {
+        self.wait_timeout_on(0, msecs)
+    }
+
+    /// As wait_timeout(), but waits on a specified condvar, as wait_on().
+    #[inline]
+    pub fn wait_timeout_on(&self, condvar_id: uint, msecs: u64) -> bool {
+        assert!(!*self.poison.flag);
+        let signalled = self.inner.cond().wait_timeout_on(condvar_id, msecs);
+        PoisonOnFail::check(*self.poison.flag, self.name);
+        signalled
+    }
+
     /// Wake up a blocked task. Returns false if there was no blocked task.
     #[inline]
     pub fn signal(&self) -> bool { self.signal_on(0) }
@@ -214,8 +243,23 @@ impl<T: Send> Mutex<T> {
     /// blocked on the mutex) will also fail immediately.
     #[inline]
     pub fn lock<'a>(&'a self) -> MutexGuard<'a, T> {
-        let guard = self.lock.lock();
+        self.guard(self.lock.lock())
+    }
 
+    /// Attempts to lock the mutex like `lock`, waiting for at most `msecs`
+    /// milliseconds for it to become available.
+    ///
+    /// Returns `None` if the mutex could not be locked in time.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the mutex is poisoned, as `lock` does, or if the mutex has to
+    /// be waited on and the local runtime is unable to create a timer.
+    pub fn try_lock_for<'a>(&'a self, msecs: u64) -> Option<MutexGuard<'a, T>> {
+        self.lock.try_lock_for(msecs).map(|guard| self.guard(guard))
+    }
+
+    fn guard<'a>(&'a self, guard: raw::MutexGuard<'a>) -> MutexGuard<'a, T> {
         // These two accesses are safe because we're guranteed at this point
         // that we have exclusive access to this mutex. We are indeed able to
         // promote ourselves from &Mutex to `&mut T`
@@ -291,6 +335,17 @@ pub struct RWLockReadGuard<'a, T> {
     _guard: raw::RWLockReadGuard<'a>,
 }
 
+/// A guard which is created by locking an rwlock in upgradable read mode.
+/// Through this guard the underlying data can be accessed immutably, and the
+/// guard can later be upgraded to a write guard.
+pub struct RWLockUpgradableGuard<'a, T> {
+    // FIXME #12808: strange names to try to avoid interfering with
+    // field accesses of the contained type via Deref
+    _data: &'a T,
+    _lock: &'a RWLock<T>,
+    _guard: raw::RWLockUpgradableGuard<'a>,
+}
+
 impl<T: Send + Share> RWLock<T> {
     /// Create a reader/writer lock with the supplied data.
     pub fn new(user_data: T) -> RWLock<T> {
@@ -317,8 +372,11 @@ impl<T: Send + Share> RWLock<T> {
     /// the lock, so subsequent readers and writers will both also fail.
     #[inline]
     pub fn write<'a>(&'a self) -> RWLockWriteGuard<'a, T> {
-        let guard = self.lock.write();
+        self.write_guard(self.lock.write())
+    }
 
+    fn write_guard<'a>(&'a self,
+                       guard: raw::RWLockWriteGuard<'a>) -> RWLockWriteGuard<'a, T> {
         // These two accesses are safe because we're guranteed at this point
         // that we have exclusive access to this rwlock. We are indeed able to
         // promote ourselves from &RWLock to `&mut T`
@@ -343,13 +401,80 @@ impl<T: Send + Share> RWLock<T> {
     /// Failing will unlock the lock while unwinding. However, unlike all other
     /// access modes, this will not poison the lock.
     pub fn read<'a>(&'a self) -> RWLockReadGuard<'a, T> {
-        let guard = self.lock.read();
+        self.read_guard(self.lock.read())
+    }
+
+    /// Attempts to lock the rwlock in write mode like `write`, waiting for at
+    /// most `msecs` milliseconds for it to become available.
+    ///
+    /// Returns `None` if the rwlock could not be locked in time.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the rwlock is poisoned, as `write` does, or if the rwlock has
+    /// to be waited on and the local runtime is unable to create a timer.
+    pub fn try_write_for<'a>(&'a self,
+                             msecs: u64) -> Option<RWLockWriteGuard<'a, T>> {
+        self.lock.try_write_for(msecs).map(|guard| self.write_guard(guard))
+    }
+
+    /// Attempts to lock the rwlock in read mode like `read`, waiting for at
+    /// most `msecs` milliseconds for it to become available.
+    ///
+    /// Returns `None` if the rwlock could not be locked in time.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the rwlock is poisoned, as `read` does, or if the rwlock has
+    /// to be waited on and the local runtime is unable to create a timer.
+    pub fn try_read_for<'a>(&'a self,
+                            msecs: u64) -> Option<RWLockReadGuard<'a, T>> {
+        self.lock.try_read_for(msecs).map(|guard| self.read_guard(guard))
+    }
+
+    fn read_guard<'a>(&'a self,
+                      guard: raw::RWLockReadGuard<'a>) -> RWLockReadGuard<'a, T> {
         PoisonOnFail::check(unsafe { *self.failed.get() }, "RWLock");
         RWLockReadGuard {
             _guard: guard,
             _data: unsafe { &*self.data.get() },
         }
     }
+
+    /// Access the underlying data immutably, with the option of upgrading to
+    /// mutable access later on. May run concurrently with other reading
+    /// tasks, but only one upgradable reader can hold the lock at a time.
+    ///
+    /// Unlike downgrading a write lock, no writer can get into the lock
+    /// between taking an upgradable read lock and upgrading it, so the data
+    /// seen while reading is still the same once the write guard is returned.
+    ///
+    /// # Failure
+    ///
+    /// Failing will unlock the lock while unwinding. As with `read`, this will
+    /// not poison the lock unless the guard has been upgraded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sync::RWLock;
+    ///
+    /// let lock = RWLock::new(vec!(1, 2));
+    /// let val = lock.read_upgradable();
+    /// if !val.contains(&3) {
+    ///     let mut val = val.upgrade();
+    ///     val.push(3);
+    /// }
+    /// ```
+    pub fn read_upgradable<'a>(&'a self) -> RWLockUpgradableGuard<'a, T> {
+        let guard = self.lock.read_upgradable();
+        PoisonOnFail::check(unsafe { *self.failed.get() }, "RWLock");
+        RWLockUpgradableGuard {
+            _guard: guard,
+            _lock: self,
+            _data: unsafe { &*self.data.get() },
+        }
+    }
 }
 
 impl<'a, T: Send + Share> RWLockWriteGuard<'a, T> {
@@ -368,9 +493,34 @@ impl<'a, T: Send + Share> RWLockWriteGuard<'a, T> {
     }
 }
 
+impl<'a, T: Send + Share> RWLockUpgradableGuard<'a, T> {
+    /// Consumes this upgradable read lock token, returning a new write lock
+    /// token.
+    ///
+    /// This waits for all other readers to leave the lock. New readers are
+    /// kept out in the meantime, so the upgrade can't be starved by them.
+    pub fn upgrade(self) -> RWLockWriteGuard<'a, T> {
+        let RWLockUpgradableGuard { _lock, _guard, .. } = self;
+        _lock.write_guard(_guard.upgrade())
+    }
+
+    /// Consumes this upgradable read lock token, returning a plain read lock
+    /// token.
+    ///
+    /// This allows another upgradable reader (or a writer) to queue up for
+    /// the lock.
+    pub fn downgrade(self) -> RWLockReadGuard<'a, T> {
+        let RWLockUpgradableGuard { _data, _guard, .. } = self;
+        RWLockReadGuard { _guard: _guard.downgrade(), _data: _data }
+    }
+}
+
 impl<'a, T: Send + Share> Deref<T> for RWLockReadGuard<'a, T> {
     fn deref<'a>(&'a self) -> &'a T { self._data }
 }
+impl<'a, T: Send + Share> Deref<T> for RWLockUpgradableGuard<'a, T> {
+    fn deref<'a>(&'a self) -> &'a T { self._data }
+}
 impl<'a, T: Send + Share> Deref<T> for RWLockWriteGuard<'a, T> {
     fn deref<'a>(&'a self) -> &'a T { &*self._data }
 }
@@ -463,7 +613,7 @@ mod tests {
         let (tx, rx) = channel();
         task::spawn(proc() {
             // wait until parent gets in
-            rx.recv();
+            rx.recv_unwrap();
             let mut lock = arc2.lock();
             *lock = true;
             lock.cond.signal();
@@ -477,6 +627,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mutex_arc_condvar_timeout() {
+        let arc = Arc::new(Mutex::new(false));
+        let arc2 = arc.clone();
+        let (tx, rx) = channel();
+
+        let mut lock = arc.lock();
+        assert!(!lock.cond.wait_timeout(10));
+        // nobody is left waiting once the wait has timed out
+        assert!(!lock.cond.signal());
+
+        task::spawn(proc() {
+            rx.recv_unwrap();
+            let mut lock = arc2.lock();
+            *lock = true;
+            lock.cond.signal();
+        });
+        tx.send(());
+        while !*lock {
+            assert!(lock.cond.wait_timeout(10000));
+        }
+        *lock = false;
+    }
+    #[test]
+    fn test_rw_arc_condvar_timeout() {
+        let arc = Arc::new(RWLock::new(0));
+        let lock = arc.write();
+        assert!(!lock.cond.wait_timeout(10));
+        assert!(!lock.cond.wait_timeout_on(0, 0));
+        drop(lock);
+        // the lock is released again after the timed out wait
+        drop(arc.read());
+    }
+
     #[test] #[should_fail]
     fn test_arc_condvar_poison() {
         let arc = Arc::new(Mutex::new(1));
@@ -484,7 +668,7 @@ mod tests {
         let (tx, rx) = channel();
 
         spawn(proc() {
-            rx.recv();
+            rx.recv_unwrap();
             let lock = arc2.lock();
             lock.cond.signal();
             // Parent should fail when it wakes up.
@@ -510,6 +694,24 @@ mod tests {
         assert_eq!(*lock, 1);
     }
 
+    #[test]
+    fn test_mutex_arc_try_lock_for() {
+        let arc = Arc::new(Mutex::new(1));
+        let arc2 = arc.clone();
+        let (tx, rx) = channel();
+        let mut lock = arc.lock();
+        task::spawn(proc() {
+            assert!(arc2.try_lock_for(10).is_none());
+            tx.send(());
+            let lock = arc2.try_lock_for(10000).unwrap();
+            assert_eq!(*lock, 2);
+            tx.send(());
+        });
+        rx.recv_unwrap();
+        *lock = 2;
+        drop(lock);
+        rx.recv_unwrap();
+    }
     #[test]
     fn test_mutex_arc_nested() {
         // Tests nested mutexes and access
@@ -600,6 +802,90 @@ mod tests {
         assert_eq!(*lock, 1);
     }
 
+    #[test]
+    fn test_rw_arc_no_poison_ur() {
+        let arc = Arc::new(RWLock::new(1));
+        let arc2 = arc.clone();
+        let _ = task::try(proc() {
+            let lock = arc2.read_upgradable();
+            assert_eq!(*lock, 2);
+        });
+        let lock = arc.write();
+        assert_eq!(*lock, 1);
+    }
+    #[test]
+    fn test_rw_arc_poison_upgrade() {
+        let arc = Arc::new(RWLock::new(1));
+        let arc2 = arc.clone();
+        let _ = task::try(proc() {
+            let lock = arc2.read_upgradable().upgrade();
+            assert_eq!(*lock, 2);
+        });
+        let r = task::try(proc() {
+            let lock = arc.read_upgradable();
+            assert_eq!(*lock, 1);
+        });
+        assert!(r.is_err());
+    }
+    #[test]
+    fn test_rw_upgrade() {
+        let arc = Arc::new(RWLock::new(0));
+        let arc2 = arc.clone();
+        let (tx, rx) = channel();
+
+        let lock = arc.read_upgradable();
+        task::spawn(proc() {
+            // Readers can share the lock with an upgradable reader, but the
+            // writer has to wait until the upgraded guard is dropped.
+            assert_eq!(*arc2.read(), 0);
+            tx.send(());
+            let mut lock = arc2.write();
+            assert_eq!(*lock, 1);
+            *lock = 2;
+            tx.send(());
+        });
+        rx.recv_unwrap();
+        let mut lock = lock.upgrade();
+        *lock = 1;
+        let lock = lock.downgrade();
+        assert_eq!(*lock, 1);
+        drop(lock);
+        rx.recv_unwrap();
+        let lock = arc.read_upgradable().downgrade();
+        assert_eq!(*lock, 2);
+    }
+    #[test]
+    fn test_rw_arc_try_for() {
+        let arc = Arc::new(RWLock::new(1));
+        let arc2 = arc.clone();
+        let (tx, rx) = channel();
+        let lock = arc.read();
+        task::spawn(proc() {
+            assert!(arc2.try_write_for(10).is_none());
+            assert_eq!(*arc2.try_read_for(0).unwrap(), 1);
+            tx.send(());
+            let mut lock = arc2.try_write_for(10000).unwrap();
+            *lock = 2;
+            tx.send(());
+        });
+        rx.recv_unwrap();
+        drop(lock);
+        rx.recv_unwrap();
+        assert_eq!(*arc.try_read_for(10).unwrap(), 2);
+    }
+    #[test]
+    fn test_rw_arc_try_for_poison() {
+        let arc = Arc::new(RWLock::new(1));
+        let arc2 = arc.clone();
+        let _ = task::try(proc() {
+            let lock = arc2.write();
+            assert_eq!(*lock, 2);
+        });
+        let r = task::try(proc() {
+            let _ = arc.try_read_for(10);
+        });
+        assert!(r.is_err());
+    }
     #[test]
     fn test_rw_arc() {
         let arc = Arc::new(RWLock::new(0));
@@ -631,11 +917,11 @@ mod tests {
 
         // Wait for children to pass their asserts
         for r in children.mut_iter() {
-            assert!(r.recv().is_ok());
+            assert!(r.recv_unwrap().is_ok());
         }
 
         // Wait for writer to finish
-        rx.recv();
+        rx.recv_unwrap();
         let lock = arc.read();
         assert_eq!(*lock, 10);
     }
@@ -678,7 +964,7 @@ mod tests {
             reader_convos.push((tx1, rx2));
             let arcn = arc.clone();
             task::spawn(proc() {
-                rx1.recv(); // wait for downgrader to give go-ahead
+                rx1.recv_unwrap(); // wait for downgrader to give go-ahead
                 let lock = arcn.read();
                 assert_eq!(*lock, 31337);
                 tx2.send(());
@@ -689,14 +975,14 @@ mod tests {
         let arc2 = arc.clone();
         let ((tx1, rx1), (tx2, rx2)) = (channel(), channel());
         task::spawn(proc() {
-            rx1.recv();
+            rx1.recv_unwrap();
             {
                 let mut lock = arc2.write();
                 assert_eq!(*lock, 0);
                 *lock = 42;
                 lock.cond.signal();
             }
-            rx1.recv();
+            rx1.recv_unwrap();
             {
                 let mut lock = arc2.write();
                 // This shouldn't happen until after the downgrade read
@@ -722,13 +1008,13 @@ mod tests {
         let lock = lock.downgrade();
         // complete handshake with other readers
         for &(_, ref mut rp) in reader_convos.mut_iter() {
-            rp.recv()
+            rp.recv_unwrap()
         }
         tx1.send(()); // tell writer to try again
         assert_eq!(*lock, 31337);
         drop(lock);
 
-        rx2.recv(); // complete handshake with writer
+        rx2.recv_unwrap(); // complete handshake with writer
     }
 
     #[cfg(test)]
@@ -753,7 +1039,7 @@ mod tests {
             *lock = false;
         });
 
-        rx.recv(); // wait for writer to get in
+        rx.recv_unwrap(); // wait for writer to get in
 
         let lock = x.write();
         assert!(*lock);
@@ -766,7 +1052,7 @@ mod tests {
             tx.send(());
             drop(xr.read());
         });
-        rx.recv(); // wait for reader task to exist
+        rx.recv_unwrap(); // wait for reader task to exist
 
         let lock = lock.downgrade();
         // if writer mistakenly got in, make sure it mutates state
@@ -814,7 +1100,7 @@ mod tests {
         barrier.wait();
         // Now, the barrier is cleared and we should get data.
         for _ in range(0, 9) {
-            rx.recv();
+            rx.recv_unwrap();
         }
     }
 }
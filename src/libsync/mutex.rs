@@ -56,7 +56,20 @@
 // The crux of this implementation is an atomic work which is CAS'd on many
 // times in order to manage a few flags about who's blocking where and whether
 // it's locked or not.
-
+//
+// ## Timed acquisitions
+//
+// Neither the OS mutex nor the queue of green tasks have a way of giving up on
+// a blocked acquisition (see the fixme in green_lock), so tasks in
+// `try_lock_for` wait on the side instead. Each of them registers a channel
+// with the mutex and flags itself in the atomic word, which it may only do
+// while the mutex is locked. The unlocker takes all of the registered channels
+// *before* flagging the mutex as unlocked (as the mutex may be freed right
+// after that), and notifies them afterwards so they can try to grab the lock.
+// A notified task which loses the race for the lock just registers again.
+
+use std::comm::Select;
+use std::io::timer::Timer;
 use std::kinds::marker;
 use std::mem;
 use std::rt::local::Local;
@@ -71,6 +84,11 @@ use q = mpsc_intrusive;
 pub static LOCKED: uint = 1 << 0;
 pub static GREEN_BLOCKED: uint = 1 << 1;
 pub static NATIVE_BLOCKED: uint = 1 << 2;
+pub static TIMED_BLOCKED: uint = 1 << 3;
+
+// The channels of the tasks waiting in `try_lock_for`, tagged with an id so a
+// task which timed out can take its own channel back out.
+type TimedWaiters = Vec<(uint, Sender<()>)>;
 
 /// A mutual exclusion primitive useful for protecting shared data
 ///
@@ -143,6 +161,11 @@ pub struct StaticMutex {
     /// to figure out when to dequeue and enqueue.
     q: q::Queue<uint>,
     green_cnt: atomics::AtomicUint,
+
+    /// an OS mutex protecting the list of tasks in `try_lock_for`, and the
+    /// uint-cast of that list (allocated when it's first needed)
+    timed_lock: mutex::StaticNativeMutex,
+    timed: Unsafe<uint>,
 }
 
 /// An RAII implementation of a "scoped lock" of a mutex. When this structure is
@@ -170,7 +193,9 @@ pub static MUTEX_INIT: StaticMutex = StaticMutex {
         stub: q::DummyNode {
             next: atomics::INIT_ATOMIC_UINT,
         }
-    }
+    },
+    timed_lock: mutex::NATIVE_MUTEX_INIT,
+    timed: Unsafe { value: 0, marker1: marker::InvariantType },
 };
 
 impl StaticMutex {
@@ -193,6 +218,99 @@ impl StaticMutex {
         }
     }
 
+    /// Attempts to grab this lock within a timeout, see `Mutex::try_lock_for`
+    pub fn try_lock_for<'a>(&'a self, msecs: u64) -> Option<Guard<'a>> {
+        match self.try_lock() {
+            Some(guard) => return Some(guard),
+            None => {}
+        }
+
+        if msecs == 0 { return None }
+
+        // See the "Timed acquisitions" section at the top of this module.
+        let mut timer = Timer::new().ok()
+                                    .expect("mutex: could not create a Timer");
+        let timeout = timer.oneshot(msecs);
+        loop {
+            let (tx, rx) = channel();
+            let id = &rx as *Receiver<()> as uint;
+            if self.add_timed(id, tx) {
+                let sel = Select::new();
+                let mut notified = sel.handle(&rx);
+                let mut timed_out = sel.handle(&timeout);
+                unsafe {
+                    notified.add();
+                    timed_out.add();
+                }
+                if sel.wait() == timed_out.id() {
+                    self.remove_timed(id);
+                    return self.try_lock()
+                }
+            }
+            match self.try_lock() {
+                Some(guard) => return Some(guard),
+                None => {}
+            }
+        }
+    }
+
+    // Registers a task in `try_lock_for` to be notified through `tx` when the
+    // mutex is unlocked. Returns false without registering if the mutex isn't
+    // locked any more.
+    fn add_timed(&self, id: uint, tx: Sender<()>) -> bool {
+        unsafe {
+            let _guard = self.timed_lock.lock();
+            let mut old = self.state.load(atomics::SeqCst);
+            loop {
+                if old & LOCKED == 0 { return false }
+                match self.state.compare_and_swap(old, old | TIMED_BLOCKED,
+                                                  atomics::SeqCst) {
+                    n if n == old => break,
+                    n => { old = n; }
+                }
+            }
+            if *self.timed.get() == 0 {
+                let list: Box<TimedWaiters> = box Vec::new();
+                *self.timed.get() = mem::transmute(list);
+            }
+            let list: &mut TimedWaiters = mem::transmute(*self.timed.get());
+            list.push((id, tx));
+            true
+        }
+    }
+
+    // Takes a task which timed out back off the list, if an unlock hasn't
+    // taken it already.
+    fn remove_timed(&self, id: uint) {
+        unsafe {
+            let _guard = self.timed_lock.lock();
+            let list: &mut TimedWaiters = mem::transmute(*self.timed.get());
+            match list.iter().position(|&(i, _)| i == id) {
+                Some(i) => { list.swap_remove(i); }
+                None => return,
+            }
+            let state = self.state.load(atomics::SeqCst);
+            if list.len() == 0 && state & TIMED_BLOCKED != 0 {
+                self.unset(state, TIMED_BLOCKED);
+            }
+        }
+    }
+
+    // Takes all of the tasks in `try_lock_for` off the list, for the unlocker
+    // to notify once the mutex is unlocked. Must only be called with the mutex
+    // locked. The last waiter may have timed out since `TIMED_BLOCKED` was
+    // seen, in which case the list is empty and the bit is already unset.
+    fn take_timed(&self) -> TimedWaiters {
+        unsafe {
+            let _guard = self.timed_lock.lock();
+            let state = self.state.load(atomics::SeqCst);
+            if state & TIMED_BLOCKED == 0 { return Vec::new() }
+            self.unset(state, TIMED_BLOCKED);
+            let list: &mut TimedWaiters = mem::transmute(*self.timed.get());
+            mem::take(list)
+        }
+    }
+
     /// Acquires this lock, see `Mutex::lock`
     pub fn lock<'a>(&'a self) -> Guard<'a> {
         // First, attempt to steal the mutex from an unlocked state. The "fast
@@ -244,7 +362,8 @@ impl StaticMutex {
         // regularly in native/green contention. Due to try_lock and the header
         // of lock stealing the lock, it's also possible for native/native
         // contention to hit this location, but as less common.
-        let t: Box<Task> = Local::take();
+        let mut t: Box<Task> = Local::take();
+        t.blocked_on = Some("a mutex");
         t.deschedule(1, |task| {
             let task = unsafe { task.cast_to_uint() };
 
@@ -317,7 +436,7 @@ impl StaticMutex {
         unsafe { self.lock.unlock_noguard(); }
     }
 
-    fn green_lock(&self, t: Box<Task>) {
+    fn green_lock(&self, mut t: Box<Task>) {
         // Green threads flag their presence with an atomic counter, and if they
         // fail to be the first to the mutex, they enqueue themselves on a
         // concurrent internal queue with a stack-allocated node.
@@ -330,6 +449,7 @@ impl StaticMutex {
         }
 
         let mut node = q::Node::new(0);
+        t.blocked_on = Some("a mutex");
         t.deschedule(1, |task| {
             unsafe {
                 node.data = task.cast_to_uint();
@@ -376,6 +496,7 @@ impl StaticMutex {
 
         let mut state = self.state.load(atomics::SeqCst);
         let mut unlocked = false;
+        let mut timed = Vec::new();
         let task;
         loop {
             assert!(state & LOCKED != 0);
@@ -395,6 +516,9 @@ impl StaticMutex {
                     BlockedTask::cast_from_uint(task)
                 };
                 break;
+            } else if state & TIMED_BLOCKED != 0 {
+                timed.push_all_move(self.take_timed());
+                state = self.state.load(atomics::SeqCst);
             } else {
                 assert_eq!(state, LOCKED);
                 if !unlocked {
@@ -407,7 +531,7 @@ impl StaticMutex {
                     unlocked = true;
                 }
                 match self.state.compare_and_swap(LOCKED, 0, atomics::SeqCst) {
-                    LOCKED => return,
+                    LOCKED => return notify_timed(timed),
                     n => { state = n; }
                 }
             }
@@ -421,7 +545,11 @@ impl StaticMutex {
             }
         }
 
+        // The task we're handing the lock to may have been claimed by a green
+        // pool's deadlock monitor in the meantime, in which case it dies still
+        // holding the lock.
         task.wake().map(|t| t.reawaken());
+        notify_timed(timed);
     }
 
     /// Loops around a CAS to unset the `bit` in `state`
@@ -447,7 +575,21 @@ impl StaticMutex {
     /// memory if this method is not called, but this is not guaranteed to be
     /// true on all platforms.
     pub unsafe fn destroy(&self) {
-        self.lock.destroy()
+        self.lock.destroy();
+        self.timed_lock.destroy();
+        if *self.timed.get() != 0 {
+            let _list: Box<TimedWaiters> = mem::transmute(*self.timed.get());
+            *self.timed.get() = 0;
+        }
+    }
+}
+
+// Lets the tasks in `try_lock_for` taken off a mutex's list know that the mutex
+// has been unlocked. This must not touch the mutex itself, which may be gone by
+// now.
+fn notify_timed(timed: TimedWaiters) {
+    for (_, tx) in timed.move_iter() {
+        let _ = tx.send_opt(());
     }
 }
 
@@ -463,6 +605,8 @@ impl Mutex {
                 green_cnt: atomics::AtomicUint::new(0),
                 q: q::Queue::new(),
                 lock: unsafe { mutex::StaticNativeMutex::new() },
+                timed_lock: unsafe { mutex::StaticNativeMutex::new() },
+                timed: Unsafe::new(0),
             }
         }
     }
@@ -478,6 +622,24 @@ impl Mutex {
         self.lock.try_lock()
     }
 
+    /// Attempts to acquire this lock, waiting for at most `msecs` milliseconds
+    /// for it to become available.
+    ///
+    /// If the lock could not be acquired in time, then `None` is returned.
+    /// Otherwise, an RAII guard is returned just like with `lock`.
+    ///
+    /// A task waiting here is woken up every time the mutex is unlocked, but
+    /// the lock is handed to tasks blocked in `lock` first, so this is not fair
+    /// with respect to them.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the lock is not immediately available and the local runtime is
+    /// unable to create a timer.
+    pub fn try_lock_for<'a>(&'a self, msecs: u64) -> Option<Guard<'a>> {
+        self.lock.try_lock_for(msecs)
+    }
+
     /// Acquires a mutex, blocking the current task until it is able to do so.
     ///
     /// This function will block the local task until it is available to acquire
@@ -563,7 +725,7 @@ mod test {
 
         drop(tx);
         for _ in range(0, 2 * N) {
-            rx.recv();
+            rx.recv_unwrap();
         }
         assert_eq!(unsafe {CNT}, M * N * 2);
         unsafe {
@@ -576,4 +738,45 @@ mod test {
         let m = Mutex::new();
         assert!(m.try_lock().is_some());
     }
+
+    #[test]
+    fn trylock_for() {
+        static mut m: StaticMutex = MUTEX_INIT;
+        unsafe {
+            drop(m.try_lock_for(0).unwrap());
+            let g = m.lock();
+            assert!(m.try_lock_for(10).is_none());
+
+            let (tx, rx) = channel();
+            native::task::spawn(proc() {
+                assert!(m.try_lock_for(10000).is_some());
+                tx.send(());
+            });
+            drop(g);
+            rx.recv_unwrap();
+            m.destroy();
+        }
+    }
+
+    #[test]
+    fn trylock_for_contended() {
+        static mut m: StaticMutex = MUTEX_INIT;
+        static N: uint = 4;
+        unsafe {
+            let g = m.lock();
+            let (tx, rx) = channel();
+            for _ in range(0, N) {
+                let tx = tx.clone();
+                native::task::spawn(proc() {
+                    for _ in range(0, 100) {
+                        assert!(m.try_lock_for(10000).is_some());
+                    }
+                    tx.send(());
+                });
+            }
+            drop(g);
+            for _ in range(0, N) { rx.recv_unwrap(); }
+            m.destroy();
+        }
+    }
 }
@@ -13,8 +13,10 @@
 //! This primitive is meant to be used to run one-time initialization. An
 //! example use case would be for initializing an FFI library.
 
+use std::finally::Finally;
 use std::int;
 use std::sync::atomics;
+use std::task;
 
 use mutex::{StaticMutex, MUTEX_INIT};
 
@@ -28,6 +30,10 @@ use mutex::{StaticMutex, MUTEX_INIT};
 /// routine run for FFI bindings or related external functionality. This type
 /// can only be statically constructed with the `ONCE_INIT` value.
 ///
+/// If the initialization routine fails, the `Once` is poisoned and all later
+/// calls to `doit` or `try_doit` will fail as well, rather than carrying on as
+/// if the initialization had completed.
+///
 /// # Example
 ///
 /// ```rust
@@ -44,6 +50,7 @@ pub struct Once {
     mutex: StaticMutex,
     cnt: atomics::AtomicInt,
     lock_cnt: atomics::AtomicInt,
+    poisoned: atomics::AtomicBool,
 }
 
 /// Initialization value for static `Once` values.
@@ -51,6 +58,7 @@ pub static ONCE_INIT: Once = Once {
     mutex: MUTEX_INIT,
     cnt: atomics::INIT_ATOMIC_INT,
     lock_cnt: atomics::INIT_ATOMIC_INT,
+    poisoned: atomics::INIT_ATOMIC_BOOL,
 };
 
 impl Once {
@@ -63,10 +71,54 @@ impl Once {
     ///
     /// When this function returns, it is guaranteed that some initialization
     /// has run and completed (it may not be the closure specified).
+    ///
+    /// # Failure
+    ///
+    /// Fails if an initialization routine has failed, in this call or any
+    /// other.
     pub fn doit(&self, f: ||) {
+        let _: Result<(), ()> = self.try_doit(|| { f(); Ok(()) });
+    }
+
+    /// Perform a fallible initialization routine once and only once.
+    ///
+    /// This is like `doit`, except that the initialization is only considered
+    /// complete once the closure returns `Ok`. If it returns an error, the
+    /// error is returned from this call and the next call to `doit` or
+    /// `try_doit` will attempt the initialization again.
+    ///
+    /// When this function returns `Ok`, it is guaranteed that some
+    /// initialization has run and completed successfully.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sync::one::{Once, ONCE_INIT};
+    ///
+    /// static mut INIT: Once = ONCE_INIT;
+    ///
+    /// fn load_config() -> Result<(), String> {
+    ///     // read the configuration here
+    ///     Ok(())
+    /// }
+    ///
+    /// unsafe {
+    ///     match INIT.try_doit(load_config) {
+    ///         Ok(()) => {}
+    ///         Err(e) => println!("failed to load the configuration: {}", e),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Fails if an initialization routine has failed, in this call or any
+    /// other.
+    pub fn try_doit<E>(&self, f: || -> Result<(), E>) -> Result<(), E> {
         // Optimize common path: load is much cheaper than fetch_add.
         if self.cnt.load(atomics::SeqCst) < 0 {
-            return
+            self.check_poison();
+            return Ok(())
         }
 
         // Implementation-wise, this would seem like a fairly trivial primitive.
@@ -87,37 +139,66 @@ impl Once {
         // This atomicity is achieved by swapping a very negative value into the
         // shared count when the initialization routine has completed. This will
         // read the number of threads which will at some point attempt to
-        // acquire the mutex. This count is then squirreled away in a separate
-        // variable, and the last person on the way out of the mutex is then
-        // responsible for destroying the mutex.
+        // acquire the mutex. This count is then added to a separate variable,
+        // which everyone decrements on the way out of the mutex, and the last
+        // person out is then responsible for destroying the mutex. Threads
+        // which are on their way out before the initialization has completed
+        // (because it returned an error) take the separate count below zero,
+        // so none of them can be mistaken for the last one.
         //
         // It is crucial that the negative value is swapped in *after* the
         // initialization routine has completed because otherwise new threads
         // calling `doit` will return immediately before the initialization has
-        // completed.
+        // completed. A failing initialization routine also completes the
+        // initialization, so that the mutex still gets cleaned up, but it
+        // poisons the `Once` before doing so.
 
         let prev = self.cnt.fetch_add(1, atomics::SeqCst);
         if prev < 0 {
             // Make sure we never overflow, we'll never have int::MIN
             // simultaneous calls to `doit` to make this value go back to 0
             self.cnt.store(int::MIN, atomics::SeqCst);
-            return
+            self.check_poison();
+            return Ok(())
         }
 
         // If the count is negative, then someone else finished the job,
-        // otherwise we run the job and record how many people will try to grab
-        // this lock
-        let guard = self.mutex.lock();
-        if self.cnt.load(atomics::SeqCst) > 0 {
-            f();
-            let prev = self.cnt.swap(int::MIN, atomics::SeqCst);
-            self.lock_cnt.store(prev, atomics::SeqCst);
-        }
-        drop(guard);
+        // otherwise we run the job and, if it succeeded, record how many
+        // people will try to grab this lock
+        let ret = (|| {
+            let _guard = self.mutex.lock();
+            if self.cnt.load(atomics::SeqCst) > 0 {
+                let ret = (|| f()).finally(|| {
+                    if task::failing() {
+                        self.poisoned.store(true, atomics::SeqCst);
+                        self.complete();
+                    }
+                });
+                if ret.is_ok() { self.complete() }
+                ret
+            } else {
+                Ok(())
+            }
+        }).finally(|| {
+            // Last one out cleans up after everyone else, no leaks!
+            if self.lock_cnt.fetch_add(-1, atomics::SeqCst) == 1 {
+                unsafe { self.mutex.destroy() }
+            }
+        });
+
+        if ret.is_ok() { self.check_poison() }
+        ret
+    }
+
+    // Gates all future threads, recording how many threads went for the mutex
+    fn complete(&self) {
+        let prev = self.cnt.swap(int::MIN, atomics::SeqCst);
+        self.lock_cnt.fetch_add(prev, atomics::SeqCst);
+    }
 
-        // Last one out cleans up after everyone else, no leaks!
-        if self.lock_cnt.fetch_add(-1, atomics::SeqCst) == 1 {
-            unsafe { self.mutex.destroy() }
+    fn check_poison(&self) {
+        if self.poisoned.load(atomics::SeqCst) {
+            fail!("Poisoned Once - the initialization routine failed!");
         }
     }
 }
@@ -167,7 +248,71 @@ mod test {
         }
 
         for _ in range(0, 10) {
-            rx.recv();
+            rx.recv_unwrap();
+        }
+    }
+
+    #[test]
+    fn poison_once() {
+        static mut o: Once = ONCE_INIT;
+        let r = task::try(proc() {
+            unsafe { o.doit(|| fail!()); }
+        });
+        assert!(r.is_err());
+
+        // later callers fail rather than running without an initialization
+        let r = task::try(proc() {
+            unsafe { o.doit(|| {}); }
+        });
+        assert!(r.is_err());
+        let r = task::try(proc() {
+            let _: Result<(), ()> = unsafe { o.try_doit(|| Ok(())) };
+        });
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn try_doit_once() {
+        static mut o: Once = ONCE_INIT;
+        let mut a = 0;
+        unsafe {
+            assert_eq!(o.try_doit(|| { a += 1; Err(a) }), Err(1));
+            assert_eq!(o.try_doit(|| { a += 1; Err(a) }), Err(2));
+            assert_eq!(o.try_doit(|| { a += 1; Ok(()) }), Ok(()));
+            assert_eq!(o.try_doit(|| { a += 1; Err(a) }), Ok(()));
+            o.doit(|| a += 1);
+        }
+        assert_eq!(a, 3);
+    }
+
+    #[test]
+    fn try_doit_stampede() {
+        static mut o: Once = ONCE_INIT;
+        static mut runs: uint = 0;
+
+        // the first few attempts fail, everyone must agree on the one which
+        // succeeded
+        let (tx, rx) = channel();
+        for _ in range(0, 10) {
+            let tx = tx.clone();
+            spawn(proc() {
+                for _ in range(0, 4) { task::deschedule() }
+                loop {
+                    let r = unsafe {
+                        o.try_doit(|| {
+                            runs += 1;
+                            if runs < 3 { Err(()) } else { Ok(()) }
+                        })
+                    };
+                    if r.is_ok() { break }
+                }
+                assert_eq!(unsafe { runs }, 3);
+                tx.send(());
+            });
+        }
+
+        for _ in range(0, 10) {
+            rx.recv_unwrap();
         }
     }
 }
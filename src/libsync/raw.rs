@@ -15,8 +15,11 @@
 //! `sync` crate which wrap values directly and provide safer abstractions for
 //! containing data.
 
+use std::cmp;
+use std::comm::Select;
 use std::kinds::marker;
 use std::mem;
+use std::io::timer::Timer;
 use std::sync::atomics;
 use std::finally::Finally;
 
@@ -79,6 +82,49 @@ impl WaitQueue {
     }
 }
 
+// The point in time at which a timed acquisition gives up. This may span the
+// acquisition of a few semaphores, and the timer is only started once one of
+// them actually has to block.
+struct Deadline {
+    msecs: u64,
+    timer: Option<(Timer, Receiver<()>)>,
+}
+
+impl Deadline {
+    fn new(msecs: u64) -> Deadline {
+        Deadline { msecs: msecs, timer: None }
+    }
+
+    // Returns a receiver which is ready once the deadline has passed. It is
+    // never received from, so it stays ready from then on.
+    fn timeout<'a>(&'a mut self) -> &'a Receiver<()> {
+        if self.timer.is_none() {
+            let mut timer = Timer::new().ok()
+                                        .expect("lock: could not create a Timer");
+            let timeout = timer.oneshot(self.msecs);
+            self.timer = Some((timer, timeout));
+        }
+        match self.timer {
+            Some((_, ref timeout)) => timeout,
+            None => unreachable!(),
+        }
+    }
+}
+
+// Blocks until either a signal arrives on `wait_end` or the deadline passes,
+// returning whether it was the signal. The signal is left in the receiver.
+fn wait_until(wait_end: &WaitEnd, deadline: &mut Deadline) -> bool {
+    let timeout = deadline.timeout();
+    let sel = Select::new();
+    let mut signal = sel.handle(wait_end);
+    let mut timeout = sel.handle(timeout);
+    unsafe {
+        signal.add();
+        timeout.add();
+    }
+    sel.wait() == signal.id()
+}
+
 // The building-block used to make semaphores, mutexes, and rwlocks.
 struct Sem<Q> {
     lock: mutex::Mutex,
@@ -125,33 +171,94 @@ impl<Q: Send> Sem<Q> {
         f(&mut *(self.inner as *mut SemInner<Q>))
     }
 
-    pub fn acquire(&self) {
+    pub fn acquire(&self) { self.acquire_many(1) }
+
+    // Takes `n` from the count, returning a wait end for each unit which
+    // wasn't available. The wait ends are enqueued together, so a task waiting
+    // for several units isn't starved by tasks waiting for one.
+    unsafe fn take(&self, n: uint) -> Vec<WaitEnd> {
+        let mut wait_ends = Vec::new();
+        self.with(|state| {
+            state.count -= n as int;
+            for _ in range(0, cmp::min(n as int, -state.count)) {
+                wait_ends.push(state.waiters.wait_end());
+            }
+        });
+        wait_ends
+    }
+
+    fn acquire_many(&self, n: uint) {
         unsafe {
-            let mut waiter_nobe = None;
-            self.with(|state| {
-                state.count -= 1;
-                if state.count < 0 {
-                    // Create waiter nobe, enqueue ourself, and tell
-                    // outer scope we need to block.
-                    waiter_nobe = Some(state.waiters.wait_end());
-                }
-            });
+            let wait_ends = self.take(n);
             // Uncomment if you wish to test for sem races. Not
             // valgrind-friendly.
             /* for _ in range(0, 1000) { task::deschedule(); } */
             // Need to wait outside the exclusive.
-            if waiter_nobe.is_some() {
-                let _ = waiter_nobe.unwrap().recv();
+            for wait_end in wait_ends.iter() {
+                let _ = wait_end.recv_unwrap();
             }
         }
     }
 
-    pub fn release(&self) {
+    fn try_acquire_many(&self, n: uint) -> bool {
+        let mut acquired = false;
+        unsafe {
+            self.with(|state| {
+                if state.count >= n as int {
+                    state.count -= n as int;
+                    acquired = true;
+                }
+            });
+        }
+        acquired
+    }
+
+    // Like `acquire`, but gives up once the deadline has passed. Returns
+    // whether the semaphore was acquired.
+    fn acquire_timeout(&self, deadline: &mut Deadline) -> bool {
+        self.acquire_many_timeout(1, deadline)
+    }
+
+    fn acquire_many_timeout(&self, n: uint, deadline: &mut Deadline) -> bool {
         unsafe {
+            let wait_ends = self.take(n);
+            if wait_ends.iter().all(|wait_end| wait_until(wait_end, deadline)) {
+                return true
+            }
+
+            // We may have been signalled just as the deadline passed. For each
+            // unit we weren't signalled for, we give back our place in the
+            // count and hang up, so that a later `signal` will skip over us.
+            // Signals are only sent with the lock held, so none of them can be
+            // lost in between.
+            let mut wait_ends = Some(wait_ends);
+            let mut missing = 0;
             self.with(|state| {
-                state.count += 1;
-                if state.count <= 0 {
-                    state.waiters.signal();
+                for wait_end in wait_ends.take_unwrap().move_iter() {
+                    if wait_end.try_recv().is_err() {
+                        state.count += 1;
+                        missing += 1;
+                    }
+                }
+            });
+            if missing == 0 { return true }
+
+            // The units which we did get are of no use on their own.
+            self.release_many(n - missing);
+            false
+        }
+    }
+
+    pub fn release(&self) { self.release_many(1) }
+
+    fn release_many(&self, n: uint) {
+        unsafe {
+            self.with(|state| {
+                for _ in range(0, n) {
+                    state.count += 1;
+                    if state.count <= 0 {
+                        state.waiters.signal();
+                    }
                 }
             })
         }
@@ -161,6 +268,15 @@ impl<Q: Send> Sem<Q> {
         self.acquire();
         SemGuard { sem: self }
     }
+
+    fn access_timeout<'a>(&'a self,
+                          deadline: &mut Deadline) -> Option<SemGuard<'a, Q>> {
+        if self.acquire_timeout(deadline) {
+            Some(SemGuard { sem: self })
+        } else {
+            None
+        }
+    }
 }
 
 #[unsafe_destructor]
@@ -202,7 +318,7 @@ impl Sem<Vec<WaitQueue>> {
 // inside of type parameters.
 enum ReacquireOrderLock<'a> {
     Nothing, // c.c
-    Just(&'a Semaphore),
+    Just(&'a Semaphore, &'a Semaphore),
 }
 
 /// A mechanism for atomic-unlock-and-deschedule blocking and signalling.
@@ -210,12 +326,14 @@ pub struct Condvar<'a> {
     // The 'Sem' object associated with this condvar. This is the one that's
     // atomically-unlocked-and-descheduled upon and reacquired during wakeup.
     sem: &'a Sem<Vec<WaitQueue> >,
-    // This is (can be) an extra semaphore which is held around the reacquire
+    // These are (can be) extra semaphores which are held around the reacquire
     // operation on the first one. This is only used in cvars associated with
     // rwlocks, and is needed to ensure that, when a downgrader is trying to
     // hand off the access lock (which would be the first field, here), a 2nd
     // writer waking up from a cvar wait can't race with a reader to steal it,
-    // See the comment in write_cond for more detail.
+    // See the comment in write_cond for more detail. The first semaphore is the
+    // upgrade lock, which keeps waking writers out while an upgradable reader
+    // holds the rwlock.
     order: ReacquireOrderLock<'a>,
     // Make sure condvars are non-copyable.
     nocopy: marker::NoCopy,
@@ -240,6 +358,34 @@ impl<'a> Condvar<'a> {
     ///
     /// wait() is equivalent to wait_on(0).
     pub fn wait_on(&self, condvar_id: uint) {
+        self.wait_for(condvar_id, None);
+    }
+
+    /// As wait(), but gives up once `msecs` milliseconds have passed without a
+    /// signal. Either way the associated lock is reacquired before returning.
+    ///
+    /// Returns whether the task was woken up by a signal rather than the
+    /// timeout. Being signalled doesn't mean that the condition being waited
+    /// for still holds, as another task may have gotten into the lock first,
+    /// so callers should check it again and wait for the remaining time if
+    /// necessary.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the local runtime is unable to create a timer.
+    pub fn wait_timeout(&self, msecs: u64) -> bool {
+        self.wait_timeout_on(0, msecs)
+    }
+
+    /// As wait_timeout(), but can specify which of multiple condition
+    /// variables to wait on, as with wait_on().
+    pub fn wait_timeout_on(&self, condvar_id: uint, msecs: u64) -> bool {
+        self.wait_for(condvar_id, Some(Deadline::new(msecs)))
+    }
+
+    // Waits on a condvar, optionally until a deadline. Returns whether we were
+    // signalled.
+    fn wait_for(&self, condvar_id: uint, mut deadline: Option<Deadline>) -> bool {
         let mut wait_end = None;
         let mut out_of_bounds = None;
         // Release lock, 'atomically' enqueuing ourselves in so doing.
@@ -267,12 +413,33 @@ impl<'a> Condvar<'a> {
             // signaller already sent -- I mean 'unconditionally' in contrast
             // with acquire().)
             (|| {
-                let _ = wait_end.take_unwrap().recv();
+                let wait_end = wait_end.take_unwrap();
+                match deadline {
+                    None => { let _ = wait_end.recv_unwrap(); true }
+                    Some(ref mut deadline) => {
+                        if wait_until(&wait_end, deadline) { return true }
+
+                        // As with a timed out acquire, we may have been
+                        // signalled just as the deadline passed. If not, we
+                        // hang up with the lock held so that whoever signals
+                        // next will skip over us.
+                        let mut wait_end = Some(wait_end);
+                        let mut signalled = false;
+                        unsafe {
+                            self.sem.with(|_| {
+                                let wait_end = wait_end.take_unwrap();
+                                signalled = wait_end.try_recv().is_ok();
+                            })
+                        }
+                        signalled
+                    }
+                }
             }).finally(|| {
                 // Reacquire the condvar.
                 match self.order {
-                    Just(lock) => {
-                        let _g = lock.access();
+                    Just(upgrade, order) => {
+                        let _u = upgrade.access();
+                        let _g = order.access();
                         self.sem.acquire();
                     }
                     Nothing => self.sem.acquire(),
@@ -369,7 +536,8 @@ pub struct Semaphore {
 /// dropped, this value will release the resource back to the semaphore.
 #[must_use]
 pub struct SemaphoreGuard<'a> {
-    guard: SemGuard<'a, ()>,
+    sem: &'a Sem<()>,
+    units: uint,
 }
 
 impl Semaphore {
@@ -382,14 +550,81 @@ impl Semaphore {
     /// until resource(s) become available.
     pub fn acquire(&self) { self.sem.acquire() }
 
+    /// Acquire `n` resources represented by the semaphore, blocking until all
+    /// of them are available.
+    ///
+    /// The resources are waited for in the same order as any other tasks
+    /// acquiring this semaphore, so a task acquiring many resources won't be
+    /// starved by tasks acquiring fewer of them.
+    pub fn acquire_many(&self, n: uint) { self.sem.acquire_many(n) }
+
+    /// Attempt to acquire a resource represented by the semaphore without
+    /// blocking. Returns whether the resource was acquired.
+    pub fn try_acquire(&self) -> bool { self.sem.try_acquire_many(1) }
+
+    /// Acquire a resource represented by the semaphore, blocking for at most
+    /// `msecs` milliseconds. Returns whether the resource was acquired.
+    ///
+    /// # Failure
+    ///
+    /// Fails if a timer can't be created to wait for the timeout.
+    pub fn acquire_timeout(&self, msecs: u64) -> bool {
+        self.sem.acquire_timeout(&mut Deadline::new(msecs))
+    }
+
     /// Release a held resource represented by the semaphore. Wakes a blocked
     /// contending task, if any exist. Won't block the caller.
     pub fn release(&self) { self.sem.release() }
 
+    /// Release `n` held resources represented by the semaphore, waking up
+    /// blocked contending tasks as appropriate. Won't block the caller.
+    pub fn release_many(&self, n: uint) { self.sem.release_many(n) }
+
     /// Acquire a resource of this semaphore, returning an RAII guard which will
     /// release the resource when dropped.
     pub fn access<'a>(&'a self) -> SemaphoreGuard<'a> {
-        SemaphoreGuard { guard: self.sem.access() }
+        self.access_many(1)
+    }
+
+    /// Acquire `n` resources of this semaphore as with `acquire_many`,
+    /// returning an RAII guard which will release all of them when dropped.
+    pub fn access_many<'a>(&'a self, n: uint) -> SemaphoreGuard<'a> {
+        self.sem.acquire_many(n);
+        SemaphoreGuard { sem: &self.sem, units: n }
+    }
+
+    /// Acquire `n` resources of this semaphore, blocking for at most `msecs`
+    /// milliseconds in total. Returns an RAII guard which will release all of
+    /// them when dropped, or `None` if they couldn't all be acquired in time,
+    /// in which case none of them are held.
+    ///
+    /// # Failure
+    ///
+    /// Fails if a timer can't be created to wait for the timeout.
+    pub fn access_many_timeout<'a>(&'a self, n: uint,
+                                   msecs: u64) -> Option<SemaphoreGuard<'a>> {
+        self.access_many_until(n, &mut Deadline::new(msecs))
+    }
+
+    fn access_timeout<'a>(&'a self,
+                          deadline: &mut Deadline) -> Option<SemaphoreGuard<'a>> {
+        self.access_many_until(1, deadline)
+    }
+
+    fn access_many_until<'a>(&'a self, n: uint,
+                             deadline: &mut Deadline) -> Option<SemaphoreGuard<'a>> {
+        if self.sem.acquire_many_timeout(n, deadline) {
+            Some(SemaphoreGuard { sem: &self.sem, units: n })
+        } else {
+            None
+        }
+    }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for SemaphoreGuard<'a> {
+    fn drop(&mut self) {
+        self.sem.release_many(self.units);
     }
 }
 
@@ -437,6 +672,30 @@ impl Mutex {
         let SemCondGuard { guard, cvar } = self.sem.access_cond();
         MutexGuard { guard: guard, cond: cvar }
     }
+
+    /// Attempts to acquire ownership of this mutex, waiting for at most
+    /// `msecs` milliseconds for it to become available.
+    ///
+    /// Returns `None` if the mutex could not be acquired in time, otherwise
+    /// this behaves like `lock`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the mutex has to be waited on and the local runtime is unable
+    /// to create a timer.
+    pub fn try_lock_for<'a>(&'a self, msecs: u64) -> Option<MutexGuard<'a>> {
+        let mut deadline = Deadline::new(msecs);
+        self.sem.access_timeout(&mut deadline).map(|guard| {
+            MutexGuard {
+                guard: guard,
+                cond: Condvar {
+                    sem: &self.sem,
+                    order: Nothing,
+                    nocopy: marker::NoCopy,
+                },
+            }
+        })
+    }
 }
 
 /****************************************************************************
@@ -452,6 +711,10 @@ impl Mutex {
 /// A task which fails while holding an rwlock will unlock the rwlock as it
 /// unwinds.
 pub struct RWLock {
+    // Held by upgradable readers for as long as they hold the lock, and by
+    // writers while they acquire it. Semaphores are always acquired in the
+    // order upgrade_lock, order_lock, access_lock.
+    upgrade_lock: Semaphore,
     order_lock:  Semaphore,
     access_lock: Sem<Vec<WaitQueue>>,
 
@@ -472,6 +735,16 @@ pub struct RWLockReadGuard<'a> {
     lock: &'a RWLock,
 }
 
+/// An RAII helper which is created by acquiring an upgradable read lock on an
+/// RWLock. When dropped, this will unlock the RWLock.
+///
+/// A value of this type can be consumed to upgrade to a write lock, or to
+/// downgrade to a plain read lock.
+#[must_use]
+pub struct RWLockUpgradableGuard<'a> {
+    lock: &'a RWLock,
+}
+
 /// An RAII helper which is created by acquiring a write lock on an RWLock. When
 /// dropped, this will unlock the RWLock.
 ///
@@ -492,6 +765,7 @@ impl RWLock {
     /// Similar to mutex_with_condvars.
     pub fn new_with_condvars(num_condvars: uint) -> RWLock {
         RWLock {
+            upgrade_lock: Semaphore::new(1),
             order_lock: Semaphore::new(1),
             access_lock: Sem::new_and_signal(1, num_condvars),
             read_count: atomics::AtomicUint::new(0),
@@ -502,12 +776,98 @@ impl RWLock {
     /// when dropped. Calls to 'read' from other tasks may run concurrently with
     /// this one.
     pub fn read<'a>(&'a self) -> RWLockReadGuard<'a> {
+        self.read_lock();
+        RWLockReadGuard { lock: self }
+    }
+
+    /// Acquires an upgradable read-lock, returning an RAII guard that will
+    /// unlock the lock when dropped.
+    ///
+    /// Calls to `read` from other tasks may run concurrently with this one,
+    /// but calls to `write` and `read_upgradable` will block. This means that
+    /// the guard can later be upgraded to a write-lock without any other writer
+    /// getting in first, so anything observed while reading still holds when
+    /// writing.
+    ///
+    /// Note that this will deadlock if the current task already holds a read
+    /// lock on this rwlock, as would `write`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sync::raw::RWLock;
+    ///
+    /// let lock = RWLock::new();
+    /// let read = lock.read_upgradable();
+    /// // ... shared access, but no other writers ...
+    /// let write = read.upgrade();
+    /// // ... exclusive access ...
+    /// drop(write);
+    /// ```
+    pub fn read_upgradable<'a>(&'a self) -> RWLockUpgradableGuard<'a> {
+        self.upgrade_lock.acquire();
+        self.read_lock();
+        RWLockUpgradableGuard { lock: self }
+    }
+
+    fn read_lock(&self) {
         let _guard = self.order_lock.access();
         let old_count = self.read_count.fetch_add(1, atomics::Acquire);
         if old_count == 0 {
             self.access_lock.acquire();
         }
-        RWLockReadGuard { lock: self }
+    }
+
+    /// Attempts to acquire a read-lock, waiting for at most `msecs`
+    /// milliseconds for it to become available.
+    ///
+    /// Returns `None` if the lock could not be acquired in time, otherwise
+    /// this behaves like `read`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the lock has to be waited on and the local runtime is unable
+    /// to create a timer.
+    pub fn try_read_for<'a>(&'a self, msecs: u64) -> Option<RWLockReadGuard<'a>> {
+        if self.read_lock_timeout(&mut Deadline::new(msecs)) {
+            Some(RWLockReadGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    fn read_lock_timeout(&self, deadline: &mut Deadline) -> bool {
+        let _guard = match self.order_lock.access_timeout(deadline) {
+            Some(guard) => guard,
+            None => return false,
+        };
+        let old_count = self.read_count.fetch_add(1, atomics::Acquire);
+        if old_count != 0 || self.access_lock.acquire_timeout(deadline) {
+            return true
+        }
+
+        // While we hold the order lock, the only way for someone else to join
+        // the reader cloud is a downgrading writer. If one did, it is about to
+        // hand the access lock over to us, so we need to take it on behalf of
+        // the downgraded reader before bailing out.
+        let old_count = self.read_count.fetch_sub(1, atomics::Release);
+        assert!(old_count > 0);
+        if old_count != 1 {
+            self.access_lock.acquire();
+        }
+        false
+    }
+
+    fn read_unlock(&self) {
+        let old_count = self.read_count.fetch_sub(1, atomics::Release);
+        assert!(old_count > 0);
+        if old_count == 1 {
+            // Note: this release used to be outside of a locked access
+            // to exclusive-protected state. If this code is ever
+            // converted back to such (instead of using atomic ops),
+            // this access MUST NOT go inside the exclusive access.
+            self.access_lock.release();
+        }
     }
 
     /// Acquire a write-lock, returning an RAII guard that will unlock the lock
@@ -531,6 +891,7 @@ impl RWLock {
     /// drop(read);
     /// ```
     pub fn write<'a>(&'a self) -> RWLockWriteGuard<'a> {
+        let _u = self.upgrade_lock.access();
         let _g = self.order_lock.access();
         self.access_lock.acquire();
 
@@ -560,17 +921,89 @@ impl RWLock {
         // which can't happen until T2 finishes the downgrade-read entirely.
         // The astute reader will also note that making waking writers use the
         // order_lock is better for not starving readers.
+        self.write_guard()
+    }
+
+    /// Attempts to acquire a write-lock, waiting for at most `msecs`
+    /// milliseconds for it to become available.
+    ///
+    /// Returns `None` if the lock could not be acquired in time, otherwise
+    /// this behaves like `write`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the lock has to be waited on and the local runtime is unable
+    /// to create a timer.
+    pub fn try_write_for<'a>(&'a self,
+                             msecs: u64) -> Option<RWLockWriteGuard<'a>> {
+        let mut deadline = Deadline::new(msecs);
+        let _u = match self.upgrade_lock.access_timeout(&mut deadline) {
+            Some(guard) => guard,
+            None => return None,
+        };
+        let _g = match self.order_lock.access_timeout(&mut deadline) {
+            Some(guard) => guard,
+            None => return None,
+        };
+        if self.access_lock.acquire_timeout(&mut deadline) {
+            Some(self.write_guard())
+        } else {
+            None
+        }
+    }
+
+    fn write_guard<'a>(&'a self) -> RWLockWriteGuard<'a> {
         RWLockWriteGuard {
             lock: self,
             cond: Condvar {
                 sem: &self.access_lock,
-                order: Just(&self.order_lock),
+                order: Just(&self.upgrade_lock, &self.order_lock),
                 nocopy: marker::NoCopy,
             }
         }
     }
 }
 
+impl<'a> RWLockUpgradableGuard<'a> {
+    /// Consumes this upgradable read lock and converts it into a write lock.
+    ///
+    /// This blocks until all other readers have released the lock. No writer
+    /// can acquire the lock in the meantime.
+    pub fn upgrade(self) -> RWLockWriteGuard<'a> {
+        let lock = self.lock;
+        // Don't run the destructor of the upgradable guard, we're in charge
+        // of things from now on
+        unsafe { mem::forget(self) }
+
+        {
+            // Holding the order lock keeps new readers out while we wait for
+            // the current ones to leave. Writers only take the order lock
+            // while holding the upgrade lock, so none of them can be holding
+            // it right now.
+            let _g = lock.order_lock.access();
+            let old_count = lock.read_count.fetch_sub(1, atomics::Release);
+            assert!(old_count > 0);
+            // If we were the last reader, then the access lock of the
+            // "reader cloud" is now ours. Otherwise the last of the other
+            // readers will release it to us.
+            if old_count != 1 {
+                lock.access_lock.acquire();
+            }
+        }
+        lock.upgrade_lock.release();
+        lock.write_guard()
+    }
+
+    /// Consumes this upgradable read lock and converts it into a plain read
+    /// lock, allowing writers to acquire the lock once all readers are done.
+    pub fn downgrade(self) -> RWLockReadGuard<'a> {
+        let lock = self.lock;
+        unsafe { mem::forget(self) }
+        lock.upgrade_lock.release();
+        RWLockReadGuard { lock: lock }
+    }
+}
+
 impl<'a> RWLockWriteGuard<'a> {
     /// Consumes this write lock and converts it into a read lock.
     pub fn downgrade(self) -> RWLockReadGuard<'a> {
@@ -604,15 +1037,15 @@ impl<'a> Drop for RWLockWriteGuard<'a> {
 #[unsafe_destructor]
 impl<'a> Drop for RWLockReadGuard<'a> {
     fn drop(&mut self) {
-        let old_count = self.lock.read_count.fetch_sub(1, atomics::Release);
-        assert!(old_count > 0);
-        if old_count == 1 {
-            // Note: this release used to be outside of a locked access
-            // to exclusive-protected state. If this code is ever
-            // converted back to such (instead of using atomic ops),
-            // this access MUST NOT go inside the exclusive access.
-            self.lock.access_lock.release();
-        }
+        self.lock.read_unlock();
+    }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for RWLockUpgradableGuard<'a> {
+    fn drop(&mut self) {
+        self.lock.read_unlock();
+        self.lock.upgrade_lock.release();
     }
 }
 
@@ -667,7 +1100,7 @@ mod tests {
         });
         for _ in range(0, 5) { task::deschedule(); }
         s.release();
-        let _ = rx.recv();
+        let _ = rx.recv_unwrap();
 
         /* Parent waits and child signals */
         let (tx, rx) = channel();
@@ -676,7 +1109,7 @@ mod tests {
         task::spawn(proc() {
             for _ in range(0, 5) { task::deschedule(); }
             s2.release();
-            let _ = rx.recv();
+            let _ = rx.recv_unwrap();
         });
         s.acquire();
         tx.send(());
@@ -691,12 +1124,71 @@ mod tests {
         let (tx2, rx2) = channel();
         task::spawn(proc() {
             let _g = s2.access();
-            let _ = rx2.recv();
+            let _ = rx2.recv_unwrap();
             tx1.send(());
         });
         let _g = s.access();
         tx2.send(());
-        let _ = rx1.recv();
+        let _ = rx1.recv_unwrap();
+    }
+    #[test]
+    fn test_sem_try_acquire() {
+        let s = Semaphore::new(1);
+        assert!(s.try_acquire());
+        assert!(!s.try_acquire());
+        s.release();
+        assert!(s.try_acquire());
+    }
+    #[test]
+    fn test_sem_acquire_many() {
+        let s = Arc::new(Semaphore::new(1));
+        let s2 = s.clone();
+        let (tx, rx) = channel();
+        task::spawn(proc() {
+            s2.acquire_many(3);
+            tx.send(());
+        });
+        s.release();
+        for _ in range(0, 5) { task::deschedule(); }
+        assert!(rx.try_recv().is_err());
+        s.release_many(2);
+        rx.recv_unwrap();
+        assert!(!s.try_acquire());
+    }
+    #[test]
+    fn test_sem_access_many() {
+        let s = Semaphore::new(3);
+        {
+            let _g = s.access_many(2);
+            assert!(s.try_acquire());
+            assert!(!s.try_acquire());
+            s.release();
+        }
+        s.acquire_many(3);
+        assert!(!s.try_acquire());
+    }
+    #[test]
+    fn test_sem_acquire_timeout() {
+        let s = Arc::new(Semaphore::new(0));
+        assert!(!s.acquire_timeout(10));
+        let s2 = s.clone();
+        task::spawn(proc() { s2.release(); });
+        assert!(s.acquire_timeout(100000));
+        assert!(!s.try_acquire());
+    }
+    #[test]
+    fn test_sem_access_many_timeout() {
+        let s = Semaphore::new(2);
+        assert!(s.access_many_timeout(3, 10).is_none());
+        // the units which were available were given back, and nobody is left
+        // waiting for the missing unit
+        {
+            let _g = s.access_many_timeout(2, 10).unwrap();
+            assert!(!s.try_acquire());
+        }
+        s.release();
+        assert!(s.access_many_timeout(3, 10).is_some());
+        assert!(s.try_acquire());
     }
     #[test]
     fn test_sem_runtime_friendly_blocking() {
@@ -712,10 +1204,10 @@ mod tests {
                 drop(s2.access());
                 tx.send(());
             });
-            rx.recv(); // wait for child to come alive
+            rx.recv_unwrap(); // wait for child to come alive
             for _ in range(0, 5) { task::deschedule(); } // let the child contend
         }
-        rx.recv(); // wait for child to be done
+        rx.recv_unwrap(); // wait for child to be done
     }
     /************************************************************************
      * Mutex tests
@@ -737,7 +1229,7 @@ mod tests {
         }
         {
             access_shared(&mut *sharedstate, &m, 10);
-            let _ = rx.recv();
+            let _ = rx.recv_unwrap();
 
             assert_eq!(*sharedstate, 20);
         }
@@ -752,6 +1244,49 @@ mod tests {
         }
     }
     #[test]
+    fn test_mutex_try_lock_for() {
+        let m = Mutex::new();
+        drop(m.try_lock_for(0).unwrap());
+        let g = m.lock();
+        assert!(m.try_lock_for(0).is_none());
+        assert!(m.try_lock_for(10).is_none());
+        drop(g);
+        // giving up must not have left the mutex in a bad state
+        drop(m.try_lock_for(10).unwrap());
+        drop(m.lock());
+    }
+    #[test]
+    fn test_mutex_try_lock_for_released() {
+        let m = Arc::new(Mutex::new());
+        let m2 = m.clone();
+        let (tx, rx) = channel();
+        let g = m.lock();
+        task::spawn(proc() {
+            tx.send(m2.try_lock_for(10000).is_some());
+        });
+        for _ in range(0, 10) { task::deschedule(); }
+        drop(g);
+        assert!(rx.recv_unwrap());
+    }
+    #[test]
+    fn test_mutex_try_lock_for_queued() {
+        // A waiter which gave up is skipped over when the lock is released.
+        let m = Arc::new(Mutex::new());
+        let m2 = m.clone();
+        let (tx, rx) = channel();
+        let g = m.lock();
+        assert!(m.try_lock_for(10).is_none());
+        task::spawn(proc() {
+            let _g = m2.lock();
+            tx.send(());
+        });
+        for _ in range(0, 10) { task::deschedule(); }
+        assert!(m.try_lock_for(10).is_none());
+        drop(g);
+        rx.recv_unwrap();
+        drop(m.lock());
+    }
+    #[test]
     fn test_mutex_cond_wait() {
         let m = Arc::new(Mutex::new());
 
@@ -775,13 +1310,13 @@ mod tests {
             lock.cond.wait();
             tx.send(());
         });
-        rx.recv(); // Wait until child gets in the mutex
+        rx.recv_unwrap(); // Wait until child gets in the mutex
         {
             let lock = m.lock();
             let woken = lock.cond.signal();
             assert!(woken);
         }
-        rx.recv(); // Wait until child wakes up
+        rx.recv_unwrap(); // Wait until child wakes up
     }
 
     fn test_mutex_cond_broadcast_helper(num_waiters: uint) {
@@ -801,14 +1336,14 @@ mod tests {
         }
 
         // wait until all children get in the mutex
-        for rx in rxs.mut_iter() { rx.recv(); }
+        for rx in rxs.mut_iter() { rx.recv_unwrap(); }
         {
             let lock = m.lock();
             let num_woken = lock.cond.broadcast();
             assert_eq!(num_woken, num_waiters);
         }
         // wait until all children wake up
-        for rx in rxs.mut_iter() { rx.recv(); }
+        for rx in rxs.mut_iter() { rx.recv_unwrap(); }
     }
     #[test]
     fn test_mutex_cond_broadcast() {
@@ -829,6 +1364,58 @@ mod tests {
         assert!(!lock.cond.signal());
     }
     #[test]
+    fn test_mutex_cond_wait_timeout() {
+        let m = Arc::new(Mutex::new());
+        let m2 = m.clone();
+        let (tx, rx) = channel();
+
+        let lock = m.lock();
+        assert!(!lock.cond.wait_timeout(10));
+        assert!(!lock.cond.signal());
+
+        task::spawn(proc() {
+            rx.recv_unwrap();
+            let lock = m2.lock();
+            assert!(lock.cond.signal());
+        });
+        tx.send(());
+        assert!(lock.cond.wait_timeout(10000));
+    }
+    #[test]
+    fn test_mutex_cond_wait_timeout_broadcast() {
+        // Tasks which gave up waiting aren't counted by a broadcast, while
+        // the ones still waiting are woken up.
+        let m = Arc::new(Mutex::new());
+        let (ready_tx, ready_rx) = channel();
+        let (tx, rx) = channel();
+        for i in range(0, 4) {
+            let m = m.clone();
+            let ready_tx = ready_tx.clone();
+            let tx = tx.clone();
+            task::spawn(proc() {
+                let lock = m.lock();
+                ready_tx.send(());
+                let timeout = if i % 2 == 0 { 1 } else { 100000 };
+                tx.send(lock.cond.wait_timeout(timeout));
+            });
+        }
+        for _ in range(0, 4) { ready_rx.recv_unwrap(); }
+        for _ in range(0, 2) { assert!(!rx.recv_unwrap()); }
+        {
+            let lock = m.lock();
+            assert_eq!(lock.cond.broadcast(), 2);
+        }
+        for _ in range(0, 2) { assert!(rx.recv_unwrap()); }
+    }
+    #[test]
+    fn test_mutex_cond_wait_timeout_on_out_of_bounds() {
+        let result = task::try(proc() {
+            let m = Mutex::new_with_condvars(1);
+            m.lock().cond.wait_timeout_on(1, 10);
+        });
+        assert!(result.is_err());
+    }
+    #[test]
     fn test_mutex_killed_simple() {
         use std::any::Any;
 
@@ -878,7 +1465,9 @@ mod tests {
      * Reader/writer lock tests
      ************************************************************************/
     #[cfg(test)]
-    pub enum RWLockMode { Read, Write, Downgrade, DowngradeRead }
+    pub enum RWLockMode {
+        Read, Write, Downgrade, DowngradeRead, Upgradable, Upgrade
+    }
     #[cfg(test)]
     fn lock_rwlock_in_mode(x: &Arc<RWLock>, mode: RWLockMode, blk: ||) {
         match mode {
@@ -886,6 +1475,8 @@ mod tests {
             Write => { let _g = x.write(); blk() }
             Downgrade => { let _g = x.write(); blk() }
             DowngradeRead => { let _g = x.write().downgrade(); blk() }
+            Upgradable => { let _g = x.read_upgradable(); blk() }
+            Upgrade => { let _g = x.read_upgradable().upgrade(); blk() }
         }
     }
     #[cfg(test)]
@@ -908,7 +1499,7 @@ mod tests {
         }
         {
             access_shared(sharedstate, &x, mode2, 10);
-            let _ = rx.recv();
+            let _ = rx.recv_unwrap();
 
             assert_eq!(*sharedstate, 20);
         }
@@ -951,25 +1542,25 @@ mod tests {
         let (tx2, rx2) = channel();
         task::spawn(proc() {
             if !make_mode2_go_first {
-                rx2.recv(); // parent sends to us once it locks, or ...
+                rx2.recv_unwrap(); // parent sends to us once it locks, or ...
             }
             lock_rwlock_in_mode(&x2, mode2, || {
                 if make_mode2_go_first {
                     tx1.send(()); // ... we send to it once we lock
                 }
-                rx2.recv();
+                rx2.recv_unwrap();
                 tx1.send(());
             })
         });
         if make_mode2_go_first {
-            rx1.recv(); // child sends to us once it locks, or ...
+            rx1.recv_unwrap(); // child sends to us once it locks, or ...
         }
         lock_rwlock_in_mode(&x, mode1, || {
             if !make_mode2_go_first {
                 tx2.send(()); // ... we send to it once we lock
             }
             tx2.send(());
-            rx1.recv();
+            rx1.recv_unwrap();
         })
     }
     #[test]
@@ -992,12 +1583,201 @@ mod tests {
         test_rwlock_exclusion(y, Write, Write);
     }
     #[test]
+    fn test_rwlock_upgradable_exclusion() {
+        // Only one upgradable reader is allowed in at a time, and never
+        // together with a writer.
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Upgradable, Write);
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Write, Upgradable);
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Upgradable, Upgradable);
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Upgrade, Read);
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Read, Upgrade);
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Upgrade, Write);
+        test_rwlock_exclusion(Arc::new(RWLock::new()), Upgrade, Upgradable);
+    }
+    #[test]
+    fn test_rwlock_upgradable_and_readers() {
+        test_rwlock_handshake(Arc::new(RWLock::new()), Upgradable, Read, false);
+        test_rwlock_handshake(Arc::new(RWLock::new()), Read, Upgradable, true);
+    }
+    #[test]
+    fn test_rwlock_upgradable_downgrade() {
+        let x = Arc::new(RWLock::new());
+        let read = x.read_upgradable().downgrade();
+        // another upgradable reader can get in now
+        drop(x.read_upgradable());
+        drop(read);
+        test_rwlock_exclusion(x, Write, Write);
+    }
+    #[test]
+    fn test_rwlock_upgrade_before_waiting_writer() {
+        // A writer which is waiting while the lock is held upgradably can't
+        // get in before the upgrade.
+        let x = Arc::new(RWLock::new());
+        let x2 = x.clone();
+        let (tx, rx) = channel();
+        let (log_tx, log_rx) = channel();
+        let log_tx2 = log_tx.clone();
+
+        let read = x.read_upgradable();
+        task::spawn(proc() {
+            tx.send(());
+            let _g = x2.write();
+            log_tx2.send(2);
+        });
+        rx.recv_unwrap();
+        for _ in range(0, 100) { task::deschedule(); }
+        {
+            let _write = read.upgrade();
+            log_tx.send(1);
+        }
+        assert_eq!(log_rx.recv_unwrap(), 1);
+        assert_eq!(log_rx.recv_unwrap(), 2);
+    }
+    #[test]
+    fn test_rwlock_upgrade_not_starved_by_readers() {
+        // The upgrade keeps new readers out, so it gets the lock even while
+        // readers keep coming in.
+        let x = Arc::new(RWLock::new());
+        let mut stops = Vec::new();
+        let (done_tx, done_rx) = channel();
+        for _ in range(0, 4) {
+            let x = x.clone();
+            let done_tx = done_tx.clone();
+            let (stop_tx, stop_rx) = channel();
+            stops.push(stop_tx);
+            task::spawn(proc() {
+                while stop_rx.try_recv().is_err() {
+                    let _g = x.read();
+                    task::deschedule();
+                }
+                done_tx.send(());
+            });
+        }
+        for _ in range(0, 10) {
+            drop(x.read_upgradable().upgrade());
+            task::deschedule();
+        }
+        for stop in stops.iter() { stop.send(()); }
+        for _ in range(0, 4) { done_rx.recv_unwrap(); }
+    }
+    #[test]
+    fn test_rwlock_writer_not_starved_by_upgradable() {
+        // Writers queue up with upgradable readers, so a stream of upgradable
+        // readers can't keep a writer out.
+        let x = Arc::new(RWLock::new());
+        let (stop_tx, stop_rx) = channel();
+        let (done_tx, done_rx) = channel();
+        let x2 = x.clone();
+        task::spawn(proc() {
+            while stop_rx.try_recv().is_err() {
+                let _g = x2.read_upgradable();
+                task::deschedule();
+            }
+            done_tx.send(());
+        });
+        for _ in range(0, 10) {
+            drop(x.write());
+            task::deschedule();
+        }
+        stop_tx.send(());
+        done_rx.recv_unwrap();
+    }
+    #[test]
+    fn test_rwlock_upgrade_cond_wait() {
+        // The write guard of an upgrade has a working condvar.
+        let x = Arc::new(RWLock::new());
+        let x2 = x.clone();
+        let write = x.read_upgradable().upgrade();
+        task::spawn(proc() {
+            let write = x2.write();
+            assert!(write.cond.signal());
+        });
+        write.cond.wait();
+        drop(write);
+        drop(x.read_upgradable().upgrade());
+    }
+    #[test]
+    fn test_rwlock_try_write_for() {
+        let x = RWLock::new();
+        drop(x.try_write_for(0).unwrap());
+        {
+            let _r = x.read();
+            assert!(x.try_write_for(10).is_none());
+            drop(x.try_read_for(0).unwrap());
+        }
+        {
+            let _u = x.read_upgradable();
+            assert!(x.try_write_for(10).is_none());
+        }
+        {
+            let _w = x.write();
+            assert!(x.try_write_for(10).is_none());
+            assert!(x.try_read_for(10).is_none());
+        }
+        // the lock is still usable in every mode after giving up
+        test_rwlock_exclusion(Arc::new(x), Write, Read);
+    }
+    #[test]
+    fn test_rwlock_try_read_for_released() {
+        let x = Arc::new(RWLock::new());
+        let x2 = x.clone();
+        let (tx, rx) = channel();
+        let w = x.write();
+        task::spawn(proc() {
+            tx.send(x2.try_read_for(10000).is_some());
+        });
+        for _ in range(0, 10) { task::deschedule(); }
+        drop(w);
+        assert!(rx.recv_unwrap());
+        drop(x.write());
+    }
+    #[test]
+    fn test_rwlock_try_read_for_downgrade_race() {
+        // A reader which gives up while a writer downgrades must leave the
+        // downgraded reader holding the lock.
+        for _ in range(0, 10) {
+            let x = Arc::new(RWLock::new());
+            let x2 = x.clone();
+            let (tx, rx) = channel();
+            let w = x.write();
+            task::spawn(proc() {
+                let _ = x2.try_read_for(1);
+                tx.send(());
+            });
+            for _ in range(0, 10) { task::deschedule(); }
+            let r = w.downgrade();
+            rx.recv_unwrap();
+            assert!(x.try_write_for(0).is_none());
+            drop(r);
+            drop(x.try_write_for(0).unwrap());
+        }
+    }
+    #[test]
     fn test_rwlock_read_recursive() {
         let x = RWLock::new();
         let _g1 = x.read();
         let _g2 = x.read();
     }
     #[test]
+    fn test_rwlock_cond_wait_timeout() {
+        let x = Arc::new(RWLock::new());
+        let x2 = x.clone();
+        let (tx, rx) = channel();
+
+        let write = x.write();
+        assert!(!write.cond.wait_timeout(10));
+        task::spawn(proc() {
+            rx.recv_unwrap();
+            let write = x2.write();
+            assert!(write.cond.signal());
+        });
+        tx.send(());
+        assert!(write.cond.wait_timeout(10000));
+        let read = write.downgrade();
+        drop(read);
+        drop(x.write());
+    }
+    #[test]
     fn test_rwlock_cond_wait() {
         // As test_mutex_cond_wait above.
         let x = Arc::new(RWLock::new());
@@ -1021,13 +1801,13 @@ mod tests {
             lock.cond.wait();
             tx.send(());
         });
-        rx.recv(); // Wait until child gets in the rwlock
+        rx.recv_unwrap(); // Wait until child gets in the rwlock
         drop(x.read()); // Must be able to get in as a reader
         {
             let x = x.write();
             assert!(x.cond.signal());
         }
-        rx.recv(); // Wait until child wakes up
+        rx.recv_unwrap(); // Wait until child wakes up
         drop(x.read()); // Just for good measure
     }
     #[cfg(test)]
@@ -1055,13 +1835,13 @@ mod tests {
         }
 
         // wait until all children get in the mutex
-        for rx in rxs.mut_iter() { let _ = rx.recv(); }
+        for rx in rxs.mut_iter() { let _ = rx.recv_unwrap(); }
         lock_cond(&x, |cond| {
             let num_woken = cond.broadcast();
             assert_eq!(num_woken, num_waiters);
         });
         // wait until all children wake up
-        for rx in rxs.mut_iter() { let _ = rx.recv(); }
+        for rx in rxs.mut_iter() { let _ = rx.recv_unwrap(); }
     }
     #[test]
     fn test_rwlock_cond_broadcast() {
@@ -52,7 +52,7 @@ impl<T> TaskPool<T> {
             let task_body = proc() {
                 let local_data = init_fn(i);
                 loop {
-                    match rx.recv() {
+                    match rx.recv_unwrap() {
                         Execute(f) => f(&local_data),
                         Quit => break
                     }
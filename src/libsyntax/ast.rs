@@ -514,6 +514,9 @@ pub enum Expr_ {
     ExprAssignOp(BinOp, @Expr, @Expr),
     ExprField(@Expr, Ident, Vec<P<Ty>>),
     ExprIndex(@Expr, @Expr),
+    /// A slicing expression, `base[start..end]`. Either bound may be
+    /// omitted; `base[mut ..]` requests a mutable slice.
+    ExprSlice(@Expr, Option<@Expr>, Option<@Expr>, Mutability),
 
     /// Expression that looks like a "name". For example,
     /// `std::slice::from_elem::<uint>` is an ExprPath that's the "name" part
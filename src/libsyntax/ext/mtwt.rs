@@ -65,7 +65,7 @@ fn new_mark_internal(m: Mrk, tail: SyntaxContext, table: &SCTable) -> SyntaxCont
     let new_ctxt = |_: &(SyntaxContext, Mrk)|
                    idx_push(&mut *table.table.borrow_mut(), Mark(m, tail));
 
-    *table.mark_memo.borrow_mut().find_or_insert_with(key, new_ctxt)
+    *table.mark_memo.borrow_mut().entry(key).or_insert_with(new_ctxt)
 }
 
 /// Extend a syntax context with a given rename
@@ -83,7 +83,7 @@ fn new_rename_internal(id: Ident,
     let new_ctxt = |_: &(SyntaxContext, Ident, Mrk)|
                    idx_push(&mut *table.table.borrow_mut(), Rename(id, to, tail));
 
-    *table.rename_memo.borrow_mut().find_or_insert_with(key, new_ctxt)
+    *table.rename_memo.borrow_mut().entry(key).or_insert_with(new_ctxt)
 }
 
 /// Fetch the SCTable from TLS, create one if it doesn't yet exist.
@@ -864,6 +864,12 @@ pub fn noop_fold_expr<T: Folder>(e: @Expr, folder: &mut T) -> @Expr {
         ExprIndex(el, er) => {
             ExprIndex(folder.fold_expr(el), folder.fold_expr(er))
         }
+        ExprSlice(e, e1, e2, m) => {
+            ExprSlice(folder.fold_expr(e),
+                      e1.map(|x| folder.fold_expr(x)),
+                      e2.map(|x| folder.fold_expr(x)),
+                      m)
+        }
         ExprPath(ref pth) => ExprPath(folder.fold_path(pth)),
         ExprBreak(opt_ident) => ExprBreak(opt_ident.map(|x| folder.fold_ident(x))),
         ExprAgain(opt_ident) => ExprAgain(opt_ident.map(|x| folder.fold_ident(x))),
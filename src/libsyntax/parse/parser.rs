@@ -25,7 +25,7 @@ use ast::{ExprBreak, ExprCall, ExprCast};
 use ast::{ExprField, ExprFnBlock, ExprIf, ExprIndex};
 use ast::{ExprLit, ExprLoop, ExprMac};
 use ast::{ExprMethodCall, ExprParen, ExprPath, ExprProc};
-use ast::{ExprRepeat, ExprRet, ExprStruct, ExprTup, ExprUnary};
+use ast::{ExprRepeat, ExprRet, ExprSlice, ExprStruct, ExprTup, ExprUnary};
 use ast::{ExprVec, ExprVstore, ExprVstoreSlice};
 use ast::{ExprVstoreMutSlice, ExprWhile, ExprForLoop, Field, FnDecl};
 use ast::{ExprVstoreUniq, Once, Many};
@@ -1741,6 +1741,14 @@ impl<'a> Parser<'a> {
         ExprIndex(expr, idx)
     }
 
+    pub fn mk_slice(&mut self, expr: @Expr,
+                    start: Option<@Expr>,
+                    end: Option<@Expr>,
+                    mutbl: Mutability)
+                    -> ast::Expr_ {
+        ExprSlice(expr, start, end, mutbl)
+    }
+
     pub fn mk_field(&mut self, expr: @Expr, ident: Ident, tys: Vec<P<Ty>> ) -> ast::Expr_ {
         ExprField(expr, ident, tys)
     }
@@ -2059,13 +2067,66 @@ impl<'a> Parser<'a> {
               }
 
               // expr[...]
+              // Could be either an index expression or a slicing expression.
+              // Any slicing expression may be made mutable by writing `mut`
+              // straight after the opening bracket.
               token::LBRACKET => {
                 self.bump();
-                let ix = self.parse_expr();
-                hi = self.span.hi;
-                self.commit_expr_expecting(ix, token::RBRACKET);
-                let index = self.mk_index(e, ix);
-                e = self.mk_expr(lo, hi, index)
+                let mutbl = if self.eat_keyword(keywords::Mut) {
+                    MutMutable
+                } else {
+                    MutImmutable
+                };
+                let full_slice = self.token == token::RBRACKET ||
+                    (self.token == token::DOTDOT &&
+                     self.look_ahead(1, |t| *t == token::RBRACKET));
+                match self.token {
+                  // e[] or e[..]
+                  _ if full_slice => {
+                    self.eat(&token::DOTDOT);
+                    hi = self.span.hi;
+                    self.bump();
+                    let slice = self.mk_slice(e, None, None, mutbl);
+                    e = self.mk_expr(lo, hi, slice)
+                  }
+                  // e[..e]
+                  token::DOTDOT => {
+                    self.bump();
+                    let e2 = self.parse_expr();
+                    hi = self.span.hi;
+                    self.commit_expr_expecting(e2, token::RBRACKET);
+                    let slice = self.mk_slice(e, None, Some(e2), mutbl);
+                    e = self.mk_expr(lo, hi, slice)
+                  }
+                  // e[e], e[e..] or e[e..e]
+                  _ => {
+                    let ix = self.parse_expr();
+                    if self.token == token::DOTDOT {
+                        self.bump();
+                        let e2 = match self.token {
+                            token::RBRACKET => None,
+                            _ => Some(self.parse_expr())
+                        };
+                        hi = self.span.hi;
+                        match e2 {
+                            Some(e2) => self.commit_expr_expecting(e2, token::RBRACKET),
+                            None => self.expect(&token::RBRACKET)
+                        }
+                        let slice = self.mk_slice(e, Some(ix), e2, mutbl);
+                        e = self.mk_expr(lo, hi, slice)
+                    } else {
+                        if mutbl == MutMutable {
+                            self.span_err(self.span,
+                                          "`mut` keyword is only valid in \
+                                           slicing expressions");
+                        }
+                        hi = self.span.hi;
+                        self.commit_expr_expecting(ix, token::RBRACKET);
+                        let index = self.mk_index(e, ix);
+                        e = self.mk_expr(lo, hi, index)
+                    }
+                  }
+                }
               }
 
               _ => return e
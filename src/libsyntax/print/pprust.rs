@@ -1459,6 +1459,28 @@ impl<'a> State<'a> {
                 try!(self.print_expr(index));
                 try!(word(&mut self.s, "]"));
             }
+            ast::ExprSlice(expr, start, end, mutbl) => {
+                try!(self.print_expr(expr));
+                try!(word(&mut self.s, "["));
+                if mutbl == ast::MutMutable {
+                    try!(word(&mut self.s, "mut"));
+                    if start.is_some() || end.is_some() {
+                        try!(space(&mut self.s));
+                    }
+                }
+                match start {
+                    Some(start) => try!(self.print_expr(start)),
+                    None => {}
+                }
+                if start.is_some() || end.is_some() {
+                    try!(word(&mut self.s, ".."));
+                }
+                match end {
+                    Some(end) => try!(self.print_expr(end)),
+                    None => {}
+                }
+                try!(word(&mut self.s, "]"));
+            }
             ast::ExprPath(ref path) => try!(self.print_path(path, true)),
             ast::ExprBreak(opt_ident) => {
                 try!(word(&mut self.s, "break"));
@@ -755,6 +755,11 @@ pub fn walk_expr<E: Clone, V: Visitor<E>>(visitor: &mut V, expression: &Expr, en
             visitor.visit_expr(main_expression, env.clone());
             visitor.visit_expr(index_expression, env.clone())
         }
+        ExprSlice(main_expression, start, end, _) => {
+            visitor.visit_expr(main_expression, env.clone());
+            walk_expr_opt(visitor, start, env.clone());
+            walk_expr_opt(visitor, end, env.clone())
+        }
         ExprPath(ref path) => {
             visitor.visit_path(path, expression.id, env.clone())
         }
@@ -920,7 +920,7 @@ fn run_tests(opts: &TestOpts,
             pending += 1;
         }
 
-        let (desc, result, stdout) = rx.recv();
+        let (desc, result, stdout) = rx.recv_unwrap();
         if concurrency != 1 {
             try!(callback(TeWait(desc.clone(), PadNone)));
         }
@@ -933,7 +933,7 @@ fn run_tests(opts: &TestOpts,
     for b in filtered_benchs_and_metrics.move_iter() {
         try!(callback(TeWait(b.desc.clone(), b.testfn.padding())));
         run_test(opts, !opts.run_benchmarks, b, tx.clone());
-        let (test, result, stdout) = rx.recv();
+        let (test, result, stdout) = rx.recv_unwrap();
         try!(callback(TeResult(test, result, stdout)));
     }
     Ok(())
@@ -1037,7 +1037,7 @@ pub fn run_test(opts: &TestOpts,
             task.spawn(testfn);
 
             let stdout = reader.read_to_end().unwrap().move_iter().collect();
-            let task_result = result_future.recv();
+            let task_result = result_future.recv_unwrap();
             let test_result = calc_result(&desc, task_result.is_ok());
             monitor_ch.send((desc.clone(), test_result, stdout));
         })
@@ -1240,11 +1240,23 @@ impl MetricMap {
 
 // Benchmarking
 
+/// A function that is opaque to the optimizer, to allow benchmarks to
+/// pretend to use outputs to assist in avoiding dead-code
+/// elimination.
+///
+/// This function is a no-op; it is lowered by the compiler to a barrier
+/// the optimizer cannot see through.
+#[cfg(not(stage0))]
+pub fn black_box<T>(dummy: T) {
+    unsafe { std::intrinsics::black_box(dummy); }
+}
+
 /// A function that is opaque to the optimizer, to allow benchmarks to
 /// pretend to use outputs to assist in avoiding dead-code
 /// elimination.
 ///
 /// This function is a no-op, and does not even read from `dummy`.
+#[cfg(stage0)]
 pub fn black_box<T>(dummy: T) {
     // we need to "use" the argument in some way LLVM can't
     // introspect.
@@ -1395,7 +1407,7 @@ mod tests {
         };
         let (tx, rx) = channel();
         run_test(&TestOpts::new(), false, desc, tx);
-        let (_, res, _) = rx.recv();
+        let (_, res, _) = rx.recv_unwrap();
         assert!(res != TrOk);
     }
 
@@ -1412,7 +1424,7 @@ mod tests {
         };
         let (tx, rx) = channel();
         run_test(&TestOpts::new(), false, desc, tx);
-        let (_, res, _) = rx.recv();
+        let (_, res, _) = rx.recv_unwrap();
         assert!(res == TrIgnored);
     }
 
@@ -1429,7 +1441,7 @@ mod tests {
         };
         let (tx, rx) = channel();
         run_test(&TestOpts::new(), false, desc, tx);
-        let (_, res, _) = rx.recv();
+        let (_, res, _) = rx.recv_unwrap();
         assert!(res == TrOk);
     }
 
@@ -1446,7 +1458,7 @@ mod tests {
         };
         let (tx, rx) = channel();
         run_test(&TestOpts::new(), false, desc, tx);
-        let (_, res, _) = rx.recv();
+        let (_, res, _) = rx.recv_unwrap();
         assert!(res == TrFailed);
     }
 
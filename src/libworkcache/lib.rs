@@ -465,7 +465,7 @@ impl<'a, T:Send +
         match self {
             WorkValue(v) => v,
             WorkFromTask(prep, port) => {
-                let (exe, v) = port.recv();
+                let (exe, v) = port.recv_unwrap();
                 let s = json_encode(&v);
                 let mut db = prep.ctxt.db.deref().write();
                 db.deref_mut().cache(prep.fn_name,
@@ -56,6 +56,11 @@ rust_uv_get_loop_for_uv_handle(uv_handle_t* handle) {
     return handle->loop;
 }
 
+uv_handle_type
+rust_uv_get_handle_type(uv_handle_t* handle) {
+    return handle->type;
+}
+
 void*
 rust_uv_get_data_for_uv_loop(uv_loop_t* loop) {
     return loop->data;
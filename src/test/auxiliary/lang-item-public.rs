@@ -11,7 +11,10 @@
 #![no_std]
 
 #[lang="fail_"]
-fn fail(_: &'static str, _: &'static str, _: uint) -> ! { loop {} }
+fn fail(_: &'static str, _: &'static str, _: uint, _: uint) -> ! { loop {} }
+
+#[lang="fail_rem_by_zero"]
+fn fail_rem_by_zero(_: &'static str, _: uint, _: uint) -> ! { loop {} }
 
 #[lang = "stack_exhausted"]
 extern fn stack_exhausted() {}
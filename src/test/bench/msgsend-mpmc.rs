@@ -0,0 +1,97 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Distributes messages from a number of senders to a pool of workers, once
+// through a `shared_channel` and once through a `Receiver` behind a mutex,
+// which is what had to be done before there were channels with many
+// receivers.
+
+extern crate sync;
+extern crate time;
+
+use std::comm::shared_channel;
+use std::os;
+use sync::{Arc, Mutex};
+
+fn report(name: &str, msgs: uint, start: f64) {
+    let elapsed = time::precise_time_s() - start;
+    println!("{}: {} messages in {} seconds, {} per sec",
+             name, msgs, elapsed, (msgs as f64) / elapsed);
+}
+
+fn send_all(msgs: uint, senders: uint, tx: Sender<uint>) {
+    for _ in range(0, senders) {
+        let tx = tx.clone();
+        spawn(proc() {
+            for i in range(0, msgs / senders) { tx.send(i); }
+        });
+    }
+}
+
+fn run_shared(msgs: uint, senders: uint, workers: uint) {
+    let (tx, rx) = shared_channel();
+    let (done, donerx) = channel();
+    let start = time::precise_time_s();
+    for _ in range(0, workers) {
+        let rx = rx.clone();
+        let done = done.clone();
+        spawn(proc() {
+            done.send(rx.iter().count(|_| true));
+        });
+    }
+    send_all(msgs, senders, tx);
+    let total = range(0, workers).fold(0, |sum, _| sum + donerx.recv_unwrap());
+    assert_eq!(total, msgs / senders * senders);
+    report("shared_channel", total, start);
+}
+
+fn run_mutex(msgs: uint, senders: uint, workers: uint) {
+    let (tx, rx) = channel();
+    let rx = Arc::new(Mutex::new(rx));
+    let (done, donerx) = channel();
+    let start = time::precise_time_s();
+    for _ in range(0, workers) {
+        let rx = rx.clone();
+        let done = done.clone();
+        spawn(proc() {
+            let mut count = 0u;
+            loop {
+                match rx.lock().recv_opt() {
+                    Ok(..) => count += 1,
+                    Err(..) => break,
+                }
+            }
+            done.send(count);
+        });
+    }
+    send_all(msgs, senders, tx);
+    let total = range(0, workers).fold(0, |sum, _| sum + donerx.recv_unwrap());
+    assert_eq!(total, msgs / senders * senders);
+    report("mutex around a Receiver", total, start);
+}
+
+fn main() {
+    let args = os::args();
+    let args = if os::getenv("RUST_BENCH").is_some() {
+        vec!("".to_string(), "1000000".to_string(), "4".to_string(),
+             "8".to_string())
+    } else if args.len() <= 1u {
+        vec!("".to_string(), "10000".to_string(), "2".to_string(),
+             "4".to_string())
+    } else {
+        args.move_iter().collect()
+    };
+
+    let msgs = from_str::<uint>(args.get(1).as_slice()).unwrap();
+    let senders = from_str::<uint>(args.get(2).as_slice()).unwrap();
+    let workers = from_str::<uint>(args.get(3).as_slice()).unwrap();
+    run_shared(msgs, senders, workers);
+    run_mutex(msgs, senders, workers);
+}
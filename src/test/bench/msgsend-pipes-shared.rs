@@ -79,13 +79,13 @@ fn run(args: &[String]) {
     });
 
     for r in worker_results.iter() {
-        r.recv();
+        r.recv_unwrap();
     }
 
     //println!("sending stop message");
     to_child.send(stop);
     move_out(to_child);
-    let result = from_child.recv();
+    let result = from_child.recv_unwrap();
     let end = time::precise_time_s();
     let elapsed = end - start;
     print!("Count is {:?}\n", result);
@@ -38,14 +38,14 @@ fn ping_pong_bench(n: uint, m: uint) {
             let (tx, rx) = (atx, brx);
             for _ in range(0, n) {
                 tx.send(());
-                rx.recv();
+                rx.recv_unwrap();
             }
         });
 
         spawn(proc() {
             let (tx, rx) = (btx, arx);
             for _ in range(0, n) {
-                rx.recv();
+                rx.recv_unwrap();
                 tx.send(());
             }
         });
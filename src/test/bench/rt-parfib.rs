@@ -25,7 +25,7 @@ fn parfib(n: uint) -> uint {
         tx.send(parfib(n-1));
     });
     let m2 = parfib(n-2);
-    return (rx.recv() + m2);
+    return (rx.recv_unwrap() + m2);
 }
 
 fn main() {
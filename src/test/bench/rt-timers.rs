@@ -0,0 +1,55 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![no_start]
+
+extern crate green;
+extern crate rustuv;
+extern crate time;
+
+use std::io::timer::Timer;
+use std::os;
+
+// Keeps N timers outstanding on one event loop. Every timer is restarted a few
+// times before it's left to fire, the way a server keeps pushing back the
+// timeout of a busy connection.
+
+#[start]
+fn start(argc: int, argv: **u8) -> int {
+    green::start(argc, argv, rustuv::event_loop, main)
+}
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    let n = if os::getenv("RUST_BENCH").is_some() {
+        100000
+    } else if args.len() == 2 {
+        from_str::<uint>(args[1].as_slice()).unwrap()
+    } else {
+        10000
+    };
+
+    let start = time::precise_time_s();
+    let mut timers = Vec::from_fn(n, |_| Timer::new().unwrap());
+    for _ in range(0, 3) {
+        for (i, timer) in timers.mut_iter().enumerate() {
+            drop(timer.oneshot(60000 + i as u64));
+        }
+    }
+    let ports: Vec<Receiver<()>> = timers.mut_iter().enumerate().map(|(i, t)| {
+        t.oneshot((i % 1000) as u64)
+    }).collect();
+    for port in ports.iter() {
+        port.recv_unwrap();
+    }
+    let elapsed = time::precise_time_s() - start;
+    println!("{} timers in {} seconds", n, elapsed);
+}
@@ -167,8 +167,8 @@ fn rendezvous(nn: uint, set: Vec<Color>) {
 
     // set up meetings...
     for _ in range(0, nn) {
-        let fst_creature = from_creatures.recv();
-        let snd_creature = from_creatures.recv();
+        let fst_creature = from_creatures.recv_unwrap();
+        let snd_creature = from_creatures.recv_unwrap();
 
         creatures_met += 2;
 
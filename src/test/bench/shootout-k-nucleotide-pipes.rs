@@ -115,7 +115,7 @@ fn make_sequence_processor(sz: uint,
 
    loop {
 
-      line = from_parent.recv();
+      line = from_parent.recv_unwrap();
       if line == Vec::new() { break; }
 
        carry = windows_with_carry(carry.append(line.as_slice()).as_slice(),
@@ -218,6 +218,6 @@ fn main() {
 
    // now fetch and print result messages
    for (ii, _sz) in sizes.iter().enumerate() {
-       println!("{}", from_child.get(ii).recv());
+       println!("{}", from_child.get(ii).recv_unwrap());
    }
 }
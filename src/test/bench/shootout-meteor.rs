@@ -292,7 +292,7 @@ fn par_search(masks: Vec<Vec<Vec<u64>>>) -> Data {
 
     // collecting the results
     drop(tx);
-    let mut data = rx.recv();
+    let mut data = rx.recv_unwrap();
     for d in rx.iter() { data.reduce_from(d); }
     data
 }
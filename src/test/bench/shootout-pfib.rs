@@ -39,13 +39,13 @@ fn fib(n: int) -> int {
             task::spawn(proc() pfib(&tx2, n - 1));
             let tx2 = tx1.clone();
             task::spawn(proc() pfib(&tx2, n - 2));
-            tx.send(rx.recv() + rx.recv());
+            tx.send(rx.recv_unwrap() + rx.recv_unwrap());
         }
     }
 
     let (tx, rx) = channel();
     spawn(proc() pfib(&tx, n) );
-    rx.recv()
+    rx.recv_unwrap()
 }
 
 struct Config {
@@ -86,7 +86,7 @@ fn stress(num_tasks: int) {
         });
     }
     for r in results.iter() {
-        r.recv();
+        r.recv_unwrap();
     }
 }
 
@@ -28,11 +28,11 @@ fn calc(children: uint, parent_wait_chan: &Sender<Sender<Sender<int>>>) {
     });
 
     let child_start_chans: Vec<Sender<Sender<int>>> =
-        wait_ports.move_iter().map(|port| port.recv()).collect();
+        wait_ports.move_iter().map(|port| port.recv_unwrap()).collect();
 
     let (start_port, start_chan) = stream::<Sender<int>>();
     parent_wait_chan.send(start_chan);
-    let parent_result_chan: Sender<int> = start_port.recv();
+    let parent_result_chan: Sender<int> = start_port.recv_unwrap();
 
     let child_sum_ports: Vec<Reciever<int>> =
         child_start_chans.move_iter().map(|child_start_chan| {
@@ -41,7 +41,7 @@ fn calc(children: uint, parent_wait_chan: &Sender<Sender<Sender<int>>>) {
             child_sum_port
     }).collect();
 
-    let sum = child_sum_ports.move_iter().fold(0, |sum, sum_port| sum + sum_port.recv() );
+    let sum = child_sum_ports.move_iter().fold(0, |sum, sum_port| sum + sum_port.recv_unwrap() );
 
     parent_result_chan.send(sum + 1);
 }
@@ -62,9 +62,9 @@ fn main() {
         calc(children, &wait_chan);
     });
 
-    let start_chan = wait_port.recv();
+    let start_chan = wait_port.recv_unwrap();
     let (sum_port, sum_chan) = stream::<int>();
     start_chan.send(sum_chan);
-    let sum = sum_port.recv();
+    let sum = sum_port.recv_unwrap();
     println!("How many tasks? {} tasks.", sum);
 }
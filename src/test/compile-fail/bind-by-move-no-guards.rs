@@ -13,8 +13,8 @@ fn main() {
     let x = Some(rx);
     tx.send(false);
     match x {
-        Some(z) if z.recv() => { fail!() }, //~ ERROR cannot bind by-move into a pattern guard
-        Some(z) => { assert!(!z.recv()); },
+        Some(z) if z.recv_unwrap() => { fail!() }, //~ ERROR cannot bind by-move into a pattern guard
+        Some(z) => { assert!(!z.recv_unwrap()); },
         None => fail!()
     }
 }
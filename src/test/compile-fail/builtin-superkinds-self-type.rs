@@ -22,5 +22,5 @@ impl <T: Share> Foo for T { }
 fn main() {
     let (tx, rx) = channel();
     1193182.foo(tx);
-    assert!(rx.recv() == 1193182);
+    assert!(rx.recv_unwrap() == 1193182);
 }
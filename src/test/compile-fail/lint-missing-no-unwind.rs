@@ -0,0 +1,27 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(missing_no_unwind)]
+#![allow(dead_code)]
+
+extern "C" fn callback() {} //~ ERROR: extern function is not marked #[rustc_no_unwind]
+
+extern "system" fn system_callback() {} //~ ERROR: extern function is not marked
+
+#[rustc_no_unwind]
+extern "C" fn safe_callback() {}
+
+fn rust_fn() {}
+
+extern {
+    fn foreign();
+}
+
+fn main() {}
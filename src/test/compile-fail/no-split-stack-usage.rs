@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(attribute_usage)]
+#![allow(dead_code)]
+#![no_split_stack] //~ ERROR has no effect on a crate
+
+#[no_split_stack] //~ ERROR only applies to functions
+static X: int = 0;
+
+#[no_split_stack] //~ ERROR only applies to functions
+struct Foo;
+
+extern {
+    #[no_split_stack] //~ ERROR only applies to functions
+    fn foo();
+}
+
+#[no_split_stack]
+fn bar() {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// error-pattern:fail-column.rs:19:19
+
+fn main() {
+    let v = [[1i, 2], [3, 4]];
+    let i = 1u;
+    let j = 5u;
+    // Only the second index is out of bounds, so the reported column must
+    // point at it rather than at the start of the expression.
+    let _x = v[i][j];
+}
@@ -0,0 +1,61 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+// ignore-win32
+// error-pattern:custom failure handler: oh no
+
+// Checks that a #![no_std] executable can supply its own definition of the
+// `begin_unwind` lang item and that libcore's failure is routed through it.
+
+#![no_std]
+#![feature(phase)]
+
+#[phase(syntax, link)]
+extern crate core;
+extern crate libc;
+
+use core::prelude::*;
+use core::failure::FailureInfo;
+use core::fmt;
+
+struct Stderr;
+
+impl fmt::FormatWriter for Stderr {
+    fn write(&mut self, bytes: &[u8]) -> fmt::Result {
+        unsafe {
+            libc::write(libc::STDERR_FILENO,
+                        bytes.as_ptr() as *libc::c_void,
+                        bytes.len() as libc::size_t);
+        }
+        Ok(())
+    }
+}
+
+#[lang = "begin_unwind"]
+extern fn begin_unwind(info: &FailureInfo) -> ! {
+    let mut err = Stderr;
+    let _ = fmt::FormatWriter::write(&mut err, "custom failure handler: ".as_bytes());
+    let _ = fmt::write(&mut err, info.fmt);
+    let _ = fmt::FormatWriter::write(&mut err, "\n".as_bytes());
+    unsafe { libc::exit(101) }
+}
+
+#[lang = "stack_exhausted"] extern fn stack_exhausted() {}
+#[lang = "eh_personality"] extern fn eh_personality() {}
+
+#[start]
+fn start(_: int, _: **u8) -> int {
+    fail!("oh no");
+}
+
+mod std {
+    pub use core::{option, fmt};
+}
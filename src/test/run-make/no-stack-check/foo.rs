@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn checked(n: uint) -> uint {
+    if n == 0 { 0 } else { n + checked(n - 1) }
+}
+
+#[no_split_stack]
+fn unchecked(n: uint) -> uint {
+    n * 2
+}
+
+fn main() {
+    assert_eq!(checked(10), 55);
+    assert_eq!(unchecked(10), 20);
+}
@@ -0,0 +1,68 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+// ignore-win32
+// compile-flags: -Z abort-on-fail
+
+// Checks that with -Z abort-on-fail both the failures emitted by the compiler
+// (such as bounds checks) and explicit failure trap immediately instead of
+// unwinding through the runtime.
+//
+// This can't be a run-fail test, as those expect the exit status of a task
+// failure rather than a trap.
+
+use std::os;
+use std::io::process::{Command, ExitStatus};
+use std::str;
+
+#[inline(never)]
+fn index(v: &[int], i: uint) -> int {
+    v[i]
+}
+
+#[inline(never)]
+fn unwrap(x: Option<int>) -> int {
+    x.unwrap()
+}
+
+fn check(arg: &str, message: &str) {
+    let me = os::args();
+    let out = Command::new(me.get(0).as_slice()).arg(arg).output().unwrap();
+    match out.status {
+        ExitStatus(101) => fail!("child unwound instead of aborting"),
+        ExitStatus(0) => fail!("child did not fail"),
+        _ => {}
+    }
+    let s = str::from_utf8(out.error.as_slice()).unwrap();
+    assert!(!s.contains(message), "bad output: {}", s);
+}
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() >= 2 {
+        match args[1].as_slice() {
+            "bounds" => { let v = [1, 2, 3]; index(v, 10); }
+            "fail" => fail!("explicit failure in the child"),
+            "fail-fmt" => fail!("formatted failure in the child: {}", 3),
+            "assert" => assert!(args.len() == 0, "assertion in the child"),
+            "unwrap" => { unwrap(None); }
+            _ => {}
+        }
+        return
+    }
+
+    check("bounds", "index out of bounds");
+    check("fail", "explicit failure in the child");
+    check("fail-fmt", "formatted failure in the child");
+    check("assert", "assertion in the child");
+    check("unwrap", "called `Option::unwrap()` on a `None` value");
+}
@@ -0,0 +1,56 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-win32
+
+// at_exit handlers are run last-in first-out, including those registered by
+// other handlers, and they're run even if the main task fails.
+
+extern crate libc;
+
+use std::io::process::Command;
+use std::os;
+use std::rt;
+use std::str;
+
+// There's no task around while the handlers are running, so go straight to
+// stdout.
+fn say(s: &'static str) {
+    unsafe {
+        libc::write(1, s.as_ptr() as *libc::c_void, s.len() as libc::size_t);
+    }
+}
+
+fn child(fail: bool) {
+    rt::at_exit(proc() say("1"));
+    rt::at_exit(proc() {
+        say("2");
+        rt::at_exit(proc() say("4"));
+    });
+    rt::at_exit(proc() say("3"));
+    if fail { fail!() }
+}
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() > 1 && args[1].as_slice() == "child" {
+        return child(args.len() > 2);
+    }
+
+    let out = Command::new(args[0].as_slice()).arg("child").output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(str::from_utf8(out.output.as_slice()), Some("3241"));
+
+    let out = Command::new(args[0].as_slice()).arg("child").arg("fail")
+                                              .output().unwrap();
+    assert!(!out.status.success());
+    assert_eq!(str::from_utf8(out.output.as_slice()), Some("3241"));
+}
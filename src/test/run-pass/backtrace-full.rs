@@ -0,0 +1,57 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+// ignore-macos
+// ignore-win32 FIXME #13259
+// compile-flags:-g
+
+// Checks that RUST_BACKTRACE=full prints the source location of frames,
+// including frames which have been inlined into their caller.
+
+use std::io::process::Command;
+use std::os;
+use std::str;
+
+#[inline(always)]
+fn inner() {
+    fail!("oops")
+}
+
+#[inline(never)]
+fn outer() {
+    inner()
+}
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() >= 2 && args[1].as_slice() == "fail" {
+        outer();
+        return
+    }
+
+    let mut env = os::env().move_iter()
+                           .filter(|&(ref k, _)| k.as_slice() != "RUST_BACKTRACE")
+                           .map(|(ref k, ref v)| {
+                               (k.to_string(), v.to_string())
+                           }).collect::<Vec<(String,String)>>();
+    env.push(("RUST_BACKTRACE".to_string(), "full".to_string()));
+
+    let p = Command::new(args[0].as_slice()).arg("fail").env(env.as_slice()).spawn();
+    let out = p.unwrap().wait_with_output().unwrap();
+    assert!(!out.status.success());
+    let s = str::from_utf8(out.error.as_slice()).unwrap();
+    assert!(s.contains("stack backtrace") && s.contains("outer::h"),
+            "bad output: {}", s);
+    assert!(s.contains("at ") && s.contains("backtrace-full.rs:"),
+            "no source locations: {}", s);
+    assert!(s.contains("inlined inner"), "no inlined frames: {}", s);
+}
@@ -15,6 +15,7 @@ use std::os;
 use std::io::process::Command;
 use std::finally::Finally;
 use std::str;
+use std::task;
 
 #[start]
 fn start(argc: int, argv: **u8) -> int { native::start(argc, argv, main) }
@@ -60,6 +61,18 @@ fn runtest(me: &str) {
     let s = str::from_utf8(out.error.as_slice()).unwrap();
     assert!(!s.contains("stack backtrace") && !s.contains("foo::h"),
             "bad output2: {}", s);
+    assert!(s.contains("RUST_BACKTRACE=1"), "bad output2: {}", s);
+
+    // Make sure RUST_BACKTRACE=0 turns the stack trace back off
+    let last = env.len() - 1;
+    *env.get_mut(last) = ("RUST_BACKTRACE".to_string(), "0".to_string());
+    let mut p = Command::new(me).arg("fail").env(env.as_slice()).spawn().unwrap();
+    let out = p.wait_with_output().unwrap();
+    assert!(!out.status.success());
+    let s = str::from_utf8(out.error.as_slice()).unwrap();
+    assert!(!s.contains("stack backtrace") && !s.contains("foo::h"),
+            "bad output2.5: {}", s);
+    *env.get_mut(last) = ("RUST_BACKTRACE".to_string(), "1".to_string());
 
     // Make sure a stack trace is printed
     let mut p = Command::new(me).arg("double-fail").spawn().unwrap();
@@ -68,6 +81,10 @@ fn runtest(me: &str) {
     let s = str::from_utf8(out.error.as_slice()).unwrap();
     assert!(s.contains("stack backtrace") && s.contains("double::h"),
             "bad output3: {}", s);
+    assert!(s.contains("failed during unwinding") &&
+            s.contains("first failure: 'once'") &&
+            s.contains("second failure: 'twice'"),
+            "bad output3: {}", s);
 
     // Make sure a stack trace isn't printed too many times
     let mut p = Command::new(me).arg("double-fail").env(env.as_slice()).spawn().unwrap();
@@ -80,6 +97,15 @@ fn runtest(me: &str) {
     }
     assert!(s.slice_from(i + 10).find_str("stack backtrace").is_none(),
             "bad output4: {}", s);
+
+    // Make sure the hint to set RUST_BACKTRACE is only printed once
+    let mut p = Command::new(me).arg("fail-twice").spawn().unwrap();
+    let out = p.wait_with_output().unwrap();
+    assert!(!out.status.success());
+    let s = str::from_utf8(out.error.as_slice()).unwrap();
+    let first = s.find_str("RUST_BACKTRACE=1").expect(s);
+    assert!(s.slice_from(first + 1).find_str("RUST_BACKTRACE=1").is_none(),
+            "bad output5: {}", s);
 }
 
 fn main() {
@@ -89,6 +115,9 @@ fn main() {
         foo();
     } else if args.len() >= 2 && args[1].as_slice() == "double-fail" {
         double();
+    } else if args.len() >= 2 && args[1].as_slice() == "fail-twice" {
+        assert!(task::try(proc() foo()).is_err());
+        foo();
     } else {
         runtest(args[0].as_slice());
     }
@@ -30,5 +30,5 @@ fn foo<T: RequiresRequiresShareAndSend>(val: T, chan: Sender<T>) {
 pub fn main() {
     let (tx, rx) = channel();
     foo(X(31337), tx);
-    assert!(rx.recv() == X(31337));
+    assert!(rx.recv_unwrap() == X(31337));
 }
@@ -23,5 +23,5 @@ fn foo<T: Foo>(val: T, chan: Sender<T>) {
 pub fn main() {
     let (tx, rx) = channel();
     foo(31337, tx);
-    assert!(rx.recv() == 31337);
+    assert!(rx.recv_unwrap() == 31337);
 }
@@ -35,5 +35,5 @@ fn foo(x: ()) -> Receiver<()> {
 }
 
 pub fn main() {
-    foo(()).recv()
+    foo(()).recv_unwrap()
 }
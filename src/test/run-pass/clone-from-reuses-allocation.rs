@@ -0,0 +1,36 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `clone_from` on `Option` and `Result` forwards to the contained
+// value, so that its allocation is kept when the variants match.
+
+fn main() {
+    let mut a: Option<Vec<int>> = Some(Vec::with_capacity(64));
+    a.clone_from(&Some(vec!(1, 2, 3)));
+    assert_eq!(a.get_ref().as_slice(), &[1, 2, 3]);
+    assert_eq!(a.get_ref().capacity(), 64);
+
+    a.clone_from(&None);
+    assert!(a.is_none());
+    a.clone_from(&Some(vec!(4)));
+    assert_eq!(a, Some(vec!(4)));
+
+    let mut r: Result<Vec<int>, Vec<int>> = Ok(Vec::with_capacity(64));
+    r.clone_from(&Ok(vec!(1, 2)));
+    assert_eq!(r.as_ref().unwrap().as_slice(), &[1, 2]);
+    assert_eq!(r.as_ref().unwrap().capacity(), 64);
+
+    let mut e: Result<Vec<int>, Vec<int>> = Err(Vec::with_capacity(32));
+    e.clone_from(&Err(vec!(5)));
+    assert_eq!(e.as_ref().unwrap_err().capacity(), 32);
+
+    e.clone_from(&Ok(vec!(6)));
+    assert_eq!(e, Ok(vec!(6)));
+}
@@ -19,5 +19,5 @@ pub fn main() {
     foo(proc() {
         tx.send(());
     });
-    rx.recv();
+    rx.recv_unwrap();
 }
@@ -15,7 +15,7 @@ use std::task;
 pub fn main() {
     let (tx, rx) = channel();
     let _t = task::spawn(proc() { child(&tx) });
-    let y = rx.recv();
+    let y = rx.recv_unwrap();
     println!("received");
     println!("{:?}", y);
     assert_eq!(y, 10);
@@ -47,7 +47,7 @@ macro_rules! iotest (
                 use native;
                 let (tx, rx) = channel();
                 native::task::spawn(proc() { tx.send(f()) });
-                rx.recv();
+                rx.recv_unwrap();
             }
         }
     )
@@ -116,8 +116,8 @@ pub fn test_destroy_actually_kills(force: bool) {
     let rx2 = t.oneshot(1000);
     spawn(proc() {
         select! {
-            () = rx2.recv() => unsafe { libc::exit(1) },
-            () = rx1.recv() => {}
+            () = rx2.recv_unwrap() => unsafe { libc::exit(1) },
+            () = rx1.recv_unwrap() => {}
         }
     });
     match p.wait().unwrap() {
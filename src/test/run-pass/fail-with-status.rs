@@ -0,0 +1,45 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+
+// Checks that a failure of the root task through `fail_with_status!` sets the
+// exit status of the process, overriding `os::set_exit_status`, while other
+// failures keep using the default failure status.
+
+use std::any::AnyRefExt;
+use std::io::process::{Command, ExitStatus};
+use std::os;
+use std::str;
+use std::task;
+use std::task::FailureStatus;
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() >= 2 && args[1].as_slice() == "status" {
+        os::set_exit_status(50);
+        fail_with_status!(7, "bad config: {}", "port");
+    } else if args.len() >= 2 && args[1].as_slice() == "child" {
+        // A child task's status is only a payload, not the process's status
+        let r = task::try(proc() { fail_with_status!(7, "child") });
+        let cause = r.unwrap_err();
+        assert_eq!((*cause).downcast_ref::<FailureStatus>().unwrap().status, 7);
+        fail!("rethrown");
+    }
+
+    let out = Command::new(args[0].as_slice()).arg("status").output().unwrap();
+    assert_eq!(out.status, ExitStatus(7));
+    let s = str::from_utf8(out.error.as_slice()).unwrap();
+    assert!(s.contains("failed at 'bad config: port'"), "bad output: {}", s);
+
+    let out = Command::new(args[0].as_slice()).arg("child").output().unwrap();
+    assert_eq!(out.status, ExitStatus(101));
+}
@@ -0,0 +1,34 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that a failure sink which fails itself doesn't deadlock the task
+// writing to it, and that it's still in place for the failures after that.
+
+use std::io::IoResult;
+use std::rt;
+use std::task;
+
+struct FailingWriter;
+
+impl Writer for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> IoResult<()> {
+        fail!("the sink failed")
+    }
+}
+
+fn main() {
+    assert!(rt::set_stderr_sink(box FailingWriter).is_none());
+
+    for _ in range(0, 2) {
+        assert!(task::try::<()>(proc() fail!("oops")).is_err());
+    }
+
+    assert!(rt::take_stderr_sink().is_some());
+}
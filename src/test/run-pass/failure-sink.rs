@@ -0,0 +1,46 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that the messages of tasks failing concurrently are all delivered
+// to the process-wide failure sink.
+
+use std::io::{ChanReader, ChanWriter};
+use std::rt;
+use std::task::TaskBuilder;
+
+static N: uint = 8;
+
+fn main() {
+    let (tx, rx) = channel();
+    assert!(rt::set_stderr_sink(box ChanWriter::new(tx)).is_none());
+
+    let results = range(0, N).map(|i| {
+        let mut t = TaskBuilder::new().named(format!("failing-{}", i));
+        let result = t.future_result();
+        t.spawn(proc() {
+            fail!("oops {}", i);
+        });
+        result
+    }).collect::<Vec<Receiver<_>>>();
+    for result in results.iter() {
+        assert!(result.recv_unwrap().is_err());
+    }
+
+    // Dropping the sink closes the channel, so the reader sees EOF.
+    assert!(rt::take_stderr_sink().is_some());
+    assert!(rt::take_stderr_sink().is_none());
+
+    let out = ChanReader::new(rx).read_to_str().unwrap();
+    for i in range(0, N) {
+        let msg = format!("task 'failing-{}' failed at 'oops {}'", i, i);
+        assert!(out.as_slice().contains(msg.as_slice()),
+                "missing `{}` in:\n{}", msg, out);
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that each class of failure is counted by `rt::failure_stats`.
+
+use std::os;
+use std::rt;
+use std::task;
+
+fn main() {
+    let before = rt::failure_stats();
+
+    assert!(task::try(proc() { fail!("explicit") }).is_err());
+    assert!(task::try(proc() { assert!(1i == 2) }).is_err());
+
+    let v = vec![1i, 2, 3];
+    assert!(task::try(proc() {
+        let i = v.len();
+        v.get(i);
+    }).is_err());
+
+    // The divisor is only known at runtime.
+    let zero = os::args().len() - 1;
+    assert!(task::try(proc() { 1u / zero; }).is_err());
+
+    let after = rt::failure_stats();
+    assert_eq!(after.explicit - before.explicit, 2);
+    assert_eq!(after.bounds_check - before.bounds_check, 1);
+    assert_eq!(after.arithmetic - before.arithmetic, 1);
+}
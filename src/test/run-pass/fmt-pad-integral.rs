@@ -0,0 +1,64 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that numeric types outside of the standard library can honor the
+// sign, alternate and zero-padding flags through `pad_integral`.
+
+use std::fmt;
+
+// A fixed-point number with two decimal places
+struct Fixed(i64);
+
+impl fmt::Show for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Fixed(n) = *self;
+        let abs = if n < 0 { -n } else { n };
+        let s = format!("{}.{:02u}", abs / 100, abs % 100);
+        f.pad_integral(n >= 0, "", s.as_bytes())
+    }
+}
+
+impl fmt::LowerHex for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Fixed(n) = *self;
+        let abs = if n < 0 { -n } else { n };
+        let s = format!("{:x}", abs);
+        f.pad_integral(n >= 0, "0x", s.as_bytes())
+    }
+}
+
+struct Flags;
+
+impl fmt::Show for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = format!("{}{}{}{} {} {}",
+                        if f.sign_plus() { "+" } else { "" },
+                        if f.sign_minus() { "-" } else { "" },
+                        if f.alternate() { "#" } else { "" },
+                        if f.sign_aware_zero_pad() { "0" } else { "" },
+                        f.width(), f.precision());
+        f.write(s.as_bytes())
+    }
+}
+
+pub fn main() {
+    assert_eq!(format!("{}", Fixed(314)).as_slice(), "3.14");
+    assert_eq!(format!("{}", Fixed(-5)).as_slice(), "-0.05");
+    assert_eq!(format!("{:+}", Fixed(314)).as_slice(), "+3.14");
+    assert_eq!(format!("{:8}", Fixed(-314)).as_slice(), "   -3.14");
+    assert_eq!(format!("{:<8}", Fixed(314)).as_slice(), "3.14    ");
+    assert_eq!(format!("{:+08}", Fixed(314)).as_slice(), "+0003.14");
+    assert_eq!(format!("{:#x}", Fixed(255)).as_slice(), "0xff");
+    assert_eq!(format!("{:+#08x}", Fixed(255)).as_slice(), "+0x000ff");
+
+    assert_eq!(format!("{}", Flags).as_slice(), " None None");
+    assert_eq!(format!("{:+#010.3}", Flags).as_slice(), "+#0 Some(10) Some(3)");
+    assert_eq!(format!("{:-}", Flags).as_slice(), "- None None");
+}
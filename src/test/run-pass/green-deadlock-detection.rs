@@ -0,0 +1,113 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-fast
+
+// A pool of green schedulers which detects deadlocks should fail all of its
+// tasks once they're all waiting on each other, saying what each one was
+// deadlocked on, and leave everything else alone.
+
+extern crate green;
+extern crate sync;
+
+use std::any::Any;
+use std::owned::AnyOwnExt;
+use std::task::{TaskOpts, TaskResult};
+use sync::Arc;
+use sync::mutex::Mutex;
+
+fn pool() -> green::SchedPool {
+    let mut config = green::PoolConfig::new();
+    config.threads = 2;
+    config.detect_deadlocks = true;
+    green::SchedPool::new(config)
+}
+
+// Spawns a named task into the pool, returning where its result shows up.
+fn spawn_named(pool: &mut green::SchedPool, name: &'static str,
+               f: proc():Send) -> Receiver<TaskResult> {
+    let (tx, rx) = channel();
+    let mut opts = TaskOpts::new();
+    opts.name = Some(name.into_maybe_owned());
+    opts.notify_chan = Some(tx);
+    pool.spawn(opts, f);
+    rx
+}
+
+fn failure(rx: Receiver<TaskResult>) -> String {
+    let err: Box<Any:Send> = rx.recv().unwrap_err();
+    match err.move::<String>() {
+        Ok(msg) => *msg,
+        Err(..) => fail!("the failure should carry a message"),
+    }
+}
+
+fn deadlock() {
+    let mut pool = pool();
+    let (tx1, rx1) = channel::<()>();
+    let (tx2, rx2) = channel::<()>();
+    let first = spawn_named(&mut pool, "first", proc() {
+        let _ = rx1.recv();
+        drop(tx2);
+    });
+    let second = spawn_named(&mut pool, "second", proc() {
+        let _ = rx2.recv();
+        drop(tx1);
+    });
+    assert_eq!(failure(first).as_slice(), "deadlocked on a channel");
+    assert_eq!(failure(second).as_slice(), "deadlocked on a channel");
+    pool.shutdown();
+}
+
+fn mutex_deadlock() {
+    let mut pool = pool();
+    let m = Arc::new(Mutex::new());
+    let m2 = m.clone();
+    let (tx, rx) = channel::<()>();
+    let (locked_tx, locked_rx) = channel();
+    let locker = spawn_named(&mut pool, "locker", proc() {
+        let _g = m2.lock();
+        locked_tx.send(());
+        let _ = rx.recv();
+    });
+    let waiter = spawn_named(&mut pool, "waiter", proc() {
+        locked_rx.recv_unwrap();
+        let _g = m.lock();
+        drop(tx);
+    });
+    assert_eq!(failure(locker).as_slice(), "deadlocked on a channel");
+    assert_eq!(failure(waiter).as_slice(), "deadlocked on a mutex");
+    pool.shutdown();
+}
+
+fn no_deadlock() {
+    let mut pool = pool();
+    let done = spawn_named(&mut pool, "main", proc() {
+        let (tx, rx) = channel();
+        for i in range(0, 10) {
+            let tx = tx.clone();
+            spawn(proc() {
+                let (tx2, rx2) = channel();
+                spawn(proc() { tx2.send(i) });
+                tx.send(rx2.recv_unwrap());
+            });
+        }
+        drop(tx);
+        assert_eq!(rx.iter().fold(0, |a, b| a + b), 45);
+    });
+    assert!(done.recv().is_ok());
+    pool.shutdown();
+}
+
+fn main() {
+    deadlock();
+    mutex_deadlock();
+    no_deadlock();
+}
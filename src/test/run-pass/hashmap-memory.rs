@@ -56,7 +56,7 @@ mod map_reduce {
             println!("sending find_reducer");
             ctrl.send(find_reducer(Vec::from_slice(key.as_bytes()), tx));
             println!("receiving");
-            let c = rx.recv();
+            let c = rx.recv_unwrap();
             println!("{:?}", c);
             im.insert(key, c);
         }
@@ -81,7 +81,7 @@ mod map_reduce {
         let mut num_mappers = inputs.len() as int;
 
         while num_mappers > 0 {
-            match rx.recv() {
+            match rx.recv_unwrap() {
               mapper_done => { num_mappers -= 1; }
               find_reducer(k, cc) => {
                 let mut c;
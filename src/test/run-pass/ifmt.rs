@@ -126,6 +126,12 @@ pub fn main() {
     t!(format!("{:10.3f}", 1.0f64),   "     1.000");
     t!(format!("{:+10.3f}", 1.0f64),  "    +1.000");
     t!(format!("{:+10.3f}", -1.0f64), "    -1.000");
+    t!(format!("{:.*f}", 3, 1.0f64), "1.000");
+    t!(format!("{:1$.2$f}", 1.0f64, 10, 3), "     1.000");
+    t!(format!("{:+w$.p$f}", -1.0f64, w=10, p=3), "    -1.000");
+    t!(format!("{:1$d}", 7, 3), "  7");
+    t!(format!("{:<1$d}|", 7, 3), "7  |");
+    t!(format!("{:0>1$u}", 7u, 3), "007");
 
     t!(format!("{:e}", 1.2345e6f32), "1.2345e6");
     t!(format!("{:e}", 1.2345e6f64), "1.2345e6");
@@ -135,6 +141,22 @@ pub fn main() {
     t!(format!("{:+10.3e}", 1.2345e6f64),  "  +1.234e6");
     t!(format!("{:+10.3e}", -1.2345e6f64), "  -1.234e6");
 
+    // Without a precision floats use the shortest representation that
+    // round-trips
+    t!(format!("{}", 0.1f32), "0.1");
+    t!(format!("{}", 0.1f64), "0.1");
+    t!(format!("{}", 0.1f64 + 0.2f64), "0.30000000000000004");
+    t!(format!("{}", 3.14159274f32), "3.1415927");
+    t!(format!("{}", -2.5f64), "-2.5");
+    t!(format!("{}", 1.5e-7f64), "0.00000015");
+    t!(format!("{}", 1e21f64), "1000000000000000000000");
+    t!(format!("{:8}", 123.456f64), " 123.456");
+    t!(format!("{:e}", 0.0f64), "0e0");
+    t!(format!("{:e}", 5e-324f64), "5e-324");
+    t!(format!("{:E}", 1.5e-10f64), "1.5E-10");
+    t!(format!("{:e}", 1.7976931348623157e308f64), "1.7976931348623157e308");
+    t!(format!("{:e}", 3.4028235e38f32), "3.4028235e38");
+
     // Escaping
     t!(format!("\\{"), "{");
     t!(format!("\\}"), "}");
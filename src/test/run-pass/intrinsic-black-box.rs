@@ -0,0 +1,48 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+
+use std::intrinsics::black_box;
+
+#[deriving(Eq, Show)]
+struct Big {
+    a: u64,
+    b: [u8, ..32],
+    c: String,
+}
+
+pub fn main() {
+    unsafe {
+        // Immediate values
+        assert_eq!(black_box(3u), 3u);
+        assert_eq!(black_box(1.5f64), 1.5f64);
+        assert_eq!(black_box(Some('x')), Some('x'));
+
+        // Values returned through an out pointer, including ones with
+        // destructors, which must be moved rather than copied
+        let big = Big { a: 7, b: [1, ..32], c: "hello".to_string() };
+        let big2 = black_box(big);
+        assert_eq!(big2, Big { a: 7, b: [1, ..32], c: "hello".to_string() });
+        let b = black_box(box 5);
+        assert_eq!(*b, 5);
+
+        // Zero-sized values
+        black_box(());
+
+        // The barrier keeps the loop from being folded away, but does not
+        // change what it computes
+        let mut sum = 0u;
+        for i in range(0u, 100) {
+            sum += black_box(i);
+        }
+        assert_eq!(sum, 4950);
+    }
+}
@@ -29,6 +29,6 @@ pub fn main () {
     }
 
     let mut p = process::Command::new(args[0].as_slice());
-    p.arg("child").stdout(process::Ignored).stderr(process::Ignored);
+    p.arg("child").stdout(process::Null).stderr(process::Null);
     println!("{}", p.spawn().unwrap().wait());
 }
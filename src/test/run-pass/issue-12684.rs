@@ -25,5 +25,5 @@ fn main() {
 fn customtask() {
     let mut timer = std::io::timer::Timer::new().unwrap();
     let periodic = timer.periodic(10);
-    periodic.recv();
+    periodic.recv_unwrap();
 }
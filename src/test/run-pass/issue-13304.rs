@@ -32,7 +32,7 @@ fn main() {
         } else {
             let (tx, rx) = channel();
             native::task::spawn(proc() { tx.send(child()); });
-            rx.recv();
+            rx.recv_unwrap();
         }
     } else {
         parent("green".to_string());
@@ -43,7 +43,7 @@ fn main() {
             parent("native".to_string());
             tx.send(());
         });
-        rx.recv();
+        rx.recv_unwrap();
     }
 }
 
@@ -35,8 +35,8 @@ fn test() {
         let (tx2, rx2) = channel();
         tx.send(tx2);
         select! {
-            () = rx2.recv() => (),
-            _ = rcv.recv() => ()
+            () = rx2.recv_unwrap() => (),
+            _ = rcv.recv_unwrap() => ()
         }
     }
 }
@@ -46,11 +46,11 @@ fn main() {
     spawn(proc() {
         tx.send(test());
     });
-    rx.recv();
+    rx.recv_unwrap();
 
     let (tx, rx) = channel();
     native::task::spawn(proc() {
         tx.send(test());
     });
-    rx.recv();
+    rx.recv_unwrap();
 }
@@ -33,7 +33,7 @@ fn main() {
     native::task::spawn(proc() {
         tx.send(test());
     });
-    rx.recv();
+    rx.recv_unwrap();
 
 }
 
@@ -46,9 +46,9 @@ fn child() {
 fn test() {
     let args = os::args();
     let mut p = Command::new(args.get(0).as_slice()).arg("child")
-                                     .stdin(process::Ignored)
-                                     .stdout(process::Ignored)
-                                     .stderr(process::Ignored)
+                                     .stdin(process::Null)
+                                     .stdout(process::Null)
+                                     .stderr(process::Null)
                                      .spawn().unwrap();
     assert!(p.wait().unwrap().success());
 }
@@ -14,7 +14,7 @@ pub fn main() {
     let (tx, rx) = channel();
 
     spawn(proc() {
-        println(rx.recv());
+        println(rx.recv_unwrap());
     });
 
     tx.send("hello, world");
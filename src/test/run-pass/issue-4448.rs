@@ -14,7 +14,7 @@ pub fn main() {
     let (tx, rx) = channel::<&'static str>();
 
     task::spawn(proc() {
-        assert_eq!(rx.recv(), "hello, world");
+        assert_eq!(rx.recv_unwrap(), "hello, world");
     });
 
     tx.send("hello, world");
@@ -47,7 +47,7 @@ fn main() {
     let threes = periodical(3);
     let fives = periodical(5);
     for _ in range(1, 100) {
-        match (ints.recv(), threes.recv(), fives.recv()) {
+        match (ints.recv_unwrap(), threes.recv_unwrap(), fives.recv_unwrap()) {
             (_, true, true) => println!("FizzBuzz"),
             (_, true, false) => println!("Fizz"),
             (_, false, true) => println!("Buzz"),
@@ -34,5 +34,5 @@ pub fn main() {
         assert!(f.get() == 1);
         tx.send(());
     });
-    rx.recv();
+    rx.recv_unwrap();
 }
@@ -0,0 +1,51 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// exec-env:RUST_MIN_STACK=4194304
+
+// A stack size given in RUST_MIN_STACK takes precedence over the default set
+// by the program.
+
+extern crate native;
+
+use std::rt::local::Local;
+use std::rt::task::Task;
+use std::task::TaskOpts;
+
+static ENV_SIZE: uint = 4 * 1024 * 1024;
+
+#[start]
+fn start(argc: int, argv: **u8) -> int { native::start(argc, argv, main) }
+
+fn stack_size() -> uint {
+    let task: Box<Task> = Local::take();
+    let (lo, hi) = task.stack_bounds();
+    Local::put(task);
+    hi - lo
+}
+
+fn main() {
+    assert_eq!(native::task::default_stack_size(), ENV_SIZE);
+    native::task::set_default_stack_size(16 * 1024 * 1024);
+    assert_eq!(native::task::default_stack_size(), ENV_SIZE);
+
+    let (tx, rx) = channel();
+    native::task::spawn(proc() tx.send(stack_size()));
+    let size = rx.recv_unwrap();
+    assert!(size >= ENV_SIZE - 4096 && size <= ENV_SIZE,
+            "stack of {} bytes", size);
+
+    // An explicit stack size still beats the environment
+    let (tx, rx) = channel();
+    let mut opts = TaskOpts::new();
+    opts.stack_size = Some(8 * 1024 * 1024);
+    native::task::spawn_opts(opts, proc() tx.send(stack_size()));
+    assert!(rx.recv_unwrap() >= 8 * 1024 * 1024 - 4096);
+}
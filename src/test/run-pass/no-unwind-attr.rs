@@ -0,0 +1,74 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+// ignore-win32
+
+// Checks that a failure escaping a `#[rustc_no_unwind]` function aborts the
+// process, after running the destructors inside of the function, instead of
+// unwinding into the caller.
+
+use std::io::process::{Command, ExitStatus};
+use std::os;
+use std::str;
+
+struct Noisy;
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("dropped");
+    }
+}
+
+#[rustc_no_unwind]
+fn double(x: int) -> int {
+    let _n = Noisy;
+    if x < 0 { fail!("negative") }
+    x * 2
+}
+
+#[rustc_no_unwind]
+extern "C" fn callback(x: int) -> int {
+    double(x)
+}
+
+struct Caller;
+
+impl Drop for Caller {
+    fn drop(&mut self) {
+        println!("unwound into caller");
+    }
+}
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() >= 2 && args[1].as_slice() == "fail" {
+        let _c = Caller;
+        callback(-1);
+        return
+    }
+
+    // Functions which don't fail behave as usual
+    assert_eq!(double(2), 4);
+    assert_eq!(callback(3), 6);
+
+    let out = Command::new(args[0].as_slice()).arg("fail").output().unwrap();
+    match out.status {
+        ExitStatus(101) => fail!("child unwound instead of aborting"),
+        ExitStatus(0) => fail!("child did not fail"),
+        _ => {}
+    }
+    let stdout = str::from_utf8(out.output.as_slice()).unwrap();
+    assert!(stdout.contains("dropped"), "bad output: {}", stdout);
+    assert!(!stdout.contains("unwound into caller"), "bad output: {}", stdout);
+    let stderr = str::from_utf8(out.error.as_slice()).unwrap();
+    assert!(stderr.contains("negative"), "bad output: {}", stderr);
+}
@@ -0,0 +1,55 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that enums use a `NonZero` field, even one nested within other
+// structs, to encode their discriminant.
+
+use std::mem;
+use std::nonzero::NonZero;
+
+struct MyPtr<T> {
+    ptr: NonZero<*mut T>,
+}
+
+struct Counted<T> {
+    count: uint,
+    inner: MyPtr<T>,
+}
+
+struct Id(NonZero<u32>);
+
+static NONE: Option<MyPtr<int>> = None;
+
+pub fn main() {
+    assert_eq!(mem::size_of::<Option<NonZero<uint>>>(), mem::size_of::<uint>());
+    assert_eq!(mem::size_of::<Option<MyPtr<int>>>(), mem::size_of::<MyPtr<int>>());
+    assert_eq!(mem::size_of::<Option<Counted<int>>>(), mem::size_of::<Counted<int>>());
+    assert_eq!(mem::size_of::<Option<Id>>(), mem::size_of::<u32>());
+
+    let mut x = 5;
+    let some = Some(Counted {
+        count: 0,
+        inner: MyPtr { ptr: unsafe { NonZero::new(&mut x as *mut int) } },
+    });
+    match some {
+        Some(ref c) => unsafe { assert_eq!(**c.inner.ptr, 5) },
+        None => fail!(),
+    }
+
+    let none: Option<Counted<int>> = None;
+    assert!(none.is_none());
+    assert!(NONE.is_none());
+
+    let id = Some(Id(unsafe { NonZero::new(7u32) }));
+    match id {
+        Some(Id(n)) => assert_eq!(*n, 7),
+        None => fail!(),
+    }
+}
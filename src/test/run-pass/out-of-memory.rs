@@ -0,0 +1,36 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+// ignore-win32
+
+// Checks that a failed allocation is reported through the default `oom`
+// handler in libstd rather than silently aborting.
+
+use std::os;
+use std::io::process::Command;
+use std::rt::heap;
+use std::str;
+use std::uint;
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() >= 2 && args[1].as_slice() == "oom" {
+        // Larger than any object is allowed to be, so this can never succeed
+        unsafe { heap::allocate(uint::MAX / 2 + 1, 8); }
+        return
+    }
+
+    let out = Command::new(args[0].as_slice()).arg("oom").output().unwrap();
+    assert!(!out.status.success());
+    let s = str::from_utf8(out.error.as_slice()).unwrap();
+    assert!(s.contains("out of memory"), "bad output: {}", s);
+}
@@ -0,0 +1,60 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+// ignore-macos guard page detection is not implemented there
+
+// Checks that running into the guard page at the bottom of a task's stack,
+// bypassing the stack limit checks, is still reported as a stack overflow.
+
+#![feature(asm)]
+
+use std::io::process::Command;
+use std::os;
+use std::str;
+use std::task::TaskBuilder;
+
+// Inlining to avoid llvm turning the recursive functions into tail calls,
+// which doesn't consume stack.
+#[inline(always)]
+pub fn black_box<T>(dummy: T) { unsafe { asm!("" : : "r"(&dummy)) } }
+
+// Without a stack check in the prologue this runs straight into the guard
+// page, rather than stopping at the red zone.
+#[no_split_stack]
+fn unchecked_recurse() {
+    let buf = [0, ..1000];
+    black_box(buf);
+    unchecked_recurse();
+}
+
+fn main() {
+    let args = os::args();
+    let args = args.as_slice();
+    if args.len() > 1 && args[1].as_slice() == "main" {
+        unchecked_recurse();
+    } else if args.len() > 1 && args[1].as_slice() == "task" {
+        let _ = TaskBuilder::new().named("overflower").try(proc() {
+            unchecked_recurse();
+        });
+    } else {
+        let out = Command::new(args[0].as_slice()).arg("main").output().unwrap();
+        assert!(!out.status.success());
+        let error = str::from_utf8_lossy(out.error.as_slice());
+        assert!(error.as_slice().contains("task '<main>' has overflowed its stack"),
+                "bad output: {}", error);
+
+        let out = Command::new(args[0].as_slice()).arg("task").output().unwrap();
+        assert!(!out.status.success());
+        let error = str::from_utf8_lossy(out.error.as_slice());
+        assert!(error.as_slice().contains("task 'overflower' has overflowed its stack"),
+                "bad output: {}", error);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test overloading of `[]` in mutable contexts via `IndexMut`, while
+// reads still go through `Index`.
+
+struct Matrix {
+    cols: uint,
+    data: Vec<int>
+}
+
+impl Index<(uint, uint), int> for Matrix {
+    fn index(&self, &(row, col): &(uint, uint)) -> int {
+        *self.data.get(row * self.cols + col)
+    }
+}
+
+impl IndexMut<(uint, uint), int> for Matrix {
+    fn index_mut<'a>(&'a mut self, &(row, col): &(uint, uint)) -> &'a mut int {
+        self.data.get_mut(row * self.cols + col)
+    }
+}
+
+fn bump(x: &mut int) {
+    *x += 100;
+}
+
+pub fn main() {
+    let mut m = Matrix { cols: 2, data: vec!(0, 0, 0, 0) };
+
+    m[(0, 1)] = 1;
+    m[(1, 0)] = 2;
+    m[(1, 1)] += 3;
+    bump(&mut m[(0, 0)]);
+
+    assert_eq!(m[(0, 0)], 100);
+    assert_eq!(m[(0, 1)], 1);
+    assert_eq!(m[(1, 0)], 2);
+    assert_eq!(m[(1, 1)], 3);
+    assert_eq!(m.data, vec!(100, 1, 2, 3));
+
+    // Vec only implements `IndexMut`.
+    let mut v = vec!(1u, 2, 3);
+    v[0] = 4;
+    v[2] *= 2;
+    assert_eq!(v, vec!(4u, 2, 6));
+}
@@ -16,7 +16,7 @@ use std::comm;
 
 fn starve_main(alive: Receiver<int>) {
     println!("signalling main");
-    alive.recv();
+    alive.recv_unwrap();
     println!("starving main");
     let mut i: int = 0;
     loop { i += 1; }
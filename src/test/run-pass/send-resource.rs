@@ -31,8 +31,8 @@ pub fn main() {
         let (tx2, rx2) = channel();
         tx.send(tx2);
 
-        let _r = rx2.recv();
+        let _r = rx2.recv_unwrap();
     });
 
-    rx.recv().send(test(42));
+    rx.recv_unwrap().send(test(42));
 }
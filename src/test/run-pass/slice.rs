@@ -0,0 +1,80 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test slicing expressions on a user type and on the built-in impls.
+
+struct Foo;
+
+static mut COUNT: uint = 0;
+
+impl<'a> Slice<'a, Foo, &'a Foo> for Foo {
+    fn as_slice_(&'a self) -> &'a Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+    fn slice_from_or_fail(&'a self, _from: &Foo) -> &'a Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+    fn slice_to_or_fail(&'a self, _to: &Foo) -> &'a Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+    fn slice_or_fail(&'a self, _from: &Foo, _to: &Foo) -> &'a Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+}
+
+impl<'a> SliceMut<'a, Foo, &'a mut Foo> for Foo {
+    fn as_mut_slice_(&'a mut self) -> &'a mut Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+    fn slice_from_or_fail_mut(&'a mut self, _from: &Foo) -> &'a mut Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+    fn slice_to_or_fail_mut(&'a mut self, _to: &Foo) -> &'a mut Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+    fn slice_or_fail_mut(&'a mut self, _from: &Foo, _to: &Foo) -> &'a mut Foo {
+        unsafe { COUNT += 1; }
+        self
+    }
+}
+
+pub fn main() {
+    let mut x = Foo;
+    x[];
+    x[Foo..];
+    x[..Foo];
+    x[Foo..Foo];
+    x[mut];
+    x[mut Foo..];
+    x[mut ..Foo];
+    x[mut Foo..Foo];
+    unsafe {
+        assert!(COUNT == 8);
+    }
+
+    let v = vec!(1, 2, 3, 4);
+    let s: &[int] = v[1..3];
+    assert_eq!(s, &[2, 3]);
+    assert_eq!(s[1..], &[3]);
+    assert_eq!(v[..], v.as_slice());
+
+    let mut v = v;
+    for x in v[mut ..2].mut_iter() {
+        *x *= 10;
+    }
+    assert_eq!(v, vec!(10, 20, 3, 4));
+}
@@ -25,11 +25,11 @@ fn test05_start(tx : &Sender<int>) {
 fn test05() {
     let (tx, rx) = channel();
     task::spawn(proc() { test05_start(&tx) });
-    let mut value: int = rx.recv();
+    let mut value: int = rx.recv_unwrap();
     println!("{}", value);
-    value = rx.recv();
+    value = rx.recv_unwrap();
     println!("{}", value);
-    value = rx.recv();
+    value = rx.recv_unwrap();
     println!("{}", value);
     assert_eq!(value, 30);
 }
@@ -17,10 +17,10 @@ fn start(tx: &Sender<Sender<String>>) {
 
     let mut a;
     let mut b;
-    a = rx.recv();
+    a = rx.recv_unwrap();
     assert!(a == "A".to_string());
     println!("{}", a);
-    b = rx.recv();
+    b = rx.recv_unwrap();
     assert!(b == "B".to_string());
     println!("{}", b);
 }
@@ -29,7 +29,7 @@ pub fn main() {
     let (tx, rx) = channel();
     let _child = task::spawn(proc() { start(&tx) });
 
-    let mut c = rx.recv();
+    let mut c = rx.recv_unwrap();
     c.send("A".to_string());
     c.send("B".to_string());
     task::deschedule();
@@ -21,5 +21,5 @@ pub fn main() {
     let _child = task::spawn(proc() {
         start(&tx)
     });
-    let _tx = rx.recv();
+    let _tx = rx.recv_unwrap();
 }
@@ -31,7 +31,7 @@ fn test00() {
     }
 
     // Try joining tasks that have already finished.
-    result.recv();
+    result.recv_unwrap();
 
     println!("Joined task.");
 }
@@ -28,5 +28,5 @@ pub fn main() {
     task::spawn(proc() {
         start(&tx, 10)
     });
-    rx.recv();
+    rx.recv_unwrap();
 }
@@ -18,7 +18,7 @@ fn test_rec() {
     let r0: R = R {val0: 0, val1: 1u8, val2: '2'};
     tx.send(r0);
     let mut r1: R;
-    r1 = rx.recv();
+    r1 = rx.recv_unwrap();
     assert_eq!(r1.val0, 0);
     assert_eq!(r1.val1, 1u8);
     assert_eq!(r1.val2, '2');
@@ -28,7 +28,7 @@ fn test_vec() {
     let (tx, rx) = channel();
     let v0: Vec<int> = vec!(0, 1, 2);
     tx.send(v0);
-    let v1 = rx.recv();
+    let v1 = rx.recv_unwrap();
     assert_eq!(*v1.get(0), 0);
     assert_eq!(*v1.get(1), 1);
     assert_eq!(*v1.get(2), 2);
@@ -38,7 +38,7 @@ fn test_str() {
     let (tx, rx) = channel();
     let s0 = "test".to_string();
     tx.send(s0);
-    let s1 = rx.recv();
+    let s1 = rx.recv_unwrap();
     assert_eq!(s1.as_slice()[0], 't' as u8);
     assert_eq!(s1.as_slice()[1], 'e' as u8);
     assert_eq!(s1.as_slice()[2], 's' as u8);
@@ -85,11 +85,11 @@ fn test_tag() {
     tx.send(tag2(10));
     tx.send(tag3(10, 11u8, 'A'));
     let mut t1: t;
-    t1 = rx.recv();
+    t1 = rx.recv_unwrap();
     assert_eq!(t1, tag1);
-    t1 = rx.recv();
+    t1 = rx.recv_unwrap();
     assert_eq!(t1, tag2(10));
-    t1 = rx.recv();
+    t1 = rx.recv_unwrap();
     assert_eq!(t1, tag3(10, 11u8, 'A'));
 }
 
@@ -97,12 +97,12 @@ fn test_chan() {
     let (tx1, rx1) = channel();
     let (tx2, rx2) = channel();
     tx1.send(tx2);
-    let tx2 = rx1.recv();
+    let tx2 = rx1.recv_unwrap();
     // Does the transmitted channel still work?
 
     tx2.send(10);
     let mut i: int;
-    i = rx2.recv();
+    i = rx2.recv_unwrap();
     assert_eq!(i, 10);
 }
 
@@ -55,14 +55,14 @@ fn test00() {
     for _r in results.iter() {
         i = 0;
         while i < number_of_messages {
-            let value = rx.recv();
+            let value = rx.recv_unwrap();
             sum += value;
             i = i + 1;
         }
     }
 
     // Join spawned tasks...
-    for r in results.iter() { r.recv(); }
+    for r in results.iter() { r.recv_unwrap(); }
 
     println!("Completed: Final number is: ");
     println!("{:?}", sum);
@@ -20,32 +20,32 @@ fn test00() {
     tx.send(2);
     tx.send(3);
     tx.send(4);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
     tx.send(5);
     tx.send(6);
     tx.send(7);
     tx.send(8);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
-    r = rx.recv();
+    r = rx.recv_unwrap();
     sum += r;
     println!("{}", r);
     assert_eq!(sum, 1 + 2 + 3 + 4 + 5 + 6 + 7 + 8);
@@ -18,6 +18,6 @@ fn test00() {
     let mut i: int = 0;
     while i < number_of_messages { tx.send(i + 0); i += 1; }
     i = 0;
-    while i < number_of_messages { sum += rx.recv(); i += 1; }
+    while i < number_of_messages { sum += rx.recv_unwrap(); i += 1; }
     assert_eq!(sum, number_of_messages * (number_of_messages - 1) / 2);
 }
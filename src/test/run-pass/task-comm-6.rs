@@ -31,13 +31,13 @@ fn test00() {
     }
     i = 0;
     while i < number_of_messages {
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
         i += 1;
     }
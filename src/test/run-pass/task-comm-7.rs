@@ -46,13 +46,13 @@ fn test00() {
 
     let mut i: int = 0;
     while i < number_of_messages {
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
-        r = rx.recv();
+        r = rx.recv_unwrap();
         sum += r;
         i += 1;
     }
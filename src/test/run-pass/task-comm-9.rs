@@ -33,12 +33,12 @@ fn test00() {
 
     let mut i: int = 0;
     while i < number_of_messages {
-        sum += rx.recv();
+        sum += rx.recv_unwrap();
         println!("{:?}", r);
         i += 1;
     }
 
-    result.recv();
+    result.recv_unwrap();
 
     assert_eq!(sum, number_of_messages * (number_of_messages - 1) / 2);
 }
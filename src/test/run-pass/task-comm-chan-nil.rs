@@ -14,6 +14,6 @@
 pub fn main() {
     let (tx, rx) = channel();
     tx.send(());
-    let n: () = rx.recv();
+    let n: () = rx.recv_unwrap();
     assert_eq!(n, ());
 }
@@ -0,0 +1,83 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A task observer hears about every task spawned, blocked, woken up and dead.
+
+use std::rt;
+use std::sync::atomics::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+use std::task;
+use std::task::TaskBuilder;
+
+static mut SPAWNED: AtomicUint = INIT_ATOMIC_UINT;
+static mut BLOCKED: AtomicUint = INIT_ATOMIC_UINT;
+static mut UNBLOCKED: AtomicUint = INIT_ATOMIC_UINT;
+static mut DIED: AtomicUint = INIT_ATOMIC_UINT;
+static mut FAILED: AtomicUint = INIT_ATOMIC_UINT;
+
+struct Counter;
+
+impl rt::TaskObserver for Counter {
+    fn spawned(&self, _id: uint, name: Option<&str>) {
+        if name != Some("observed") { return }
+        unsafe { SPAWNED.fetch_add(1, SeqCst); }
+    }
+
+    fn blocked(&self, _id: uint, _name: Option<&str>,
+               on: Option<&'static str>) {
+        if on == Some("a channel") {
+            unsafe { BLOCKED.fetch_add(1, SeqCst); }
+        }
+    }
+
+    fn unblocked(&self, _id: uint, _name: Option<&str>) {
+        unsafe { UNBLOCKED.fetch_add(1, SeqCst); }
+    }
+
+    fn died(&self, _id: uint, name: Option<&str>, failed: bool) {
+        if name != Some("observed") { return }
+        unsafe {
+            DIED.fetch_add(1, SeqCst);
+            if failed { FAILED.fetch_add(1, SeqCst); }
+        }
+    }
+}
+
+fn wait_for(counter: &AtomicUint, n: uint) {
+    while counter.load(SeqCst) < n {
+        task::deschedule();
+    }
+}
+
+fn main() {
+    rt::set_task_observer(box Counter);
+
+    let (tx, rx) = channel();
+    let (done, donerx) = channel();
+    TaskBuilder::new().named("observed").spawn(proc() {
+        rx.recv_unwrap();
+        done.send(());
+    });
+    unsafe { wait_for(&BLOCKED, 1); }
+    tx.send(());
+    donerx.recv_unwrap();
+    assert!(TaskBuilder::new().named("observed").try(proc() {
+        fail!()
+    }).is_err());
+
+    // Tasks notify the observer of their death after their result has been
+    // sent, so they may not be quite dead yet.
+    unsafe {
+        wait_for(&DIED, 2);
+        assert_eq!(SPAWNED.load(SeqCst), 2);
+        assert_eq!(FAILED.load(SeqCst), 1);
+        assert!(BLOCKED.load(SeqCst) >= 1);
+        assert!(UNBLOCKED.load(SeqCst) >= BLOCKED.load(SeqCst));
+    }
+}
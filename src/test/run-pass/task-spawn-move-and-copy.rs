@@ -21,6 +21,6 @@ pub fn main() {
         tx.send(x_in_child);
     });
 
-    let x_in_child = rx.recv();
+    let x_in_child = rx.recv_unwrap();
     assert_eq!(x_in_parent, x_in_child);
 }
@@ -45,7 +45,7 @@ macro_rules! iotest (
                 use native;
                 let (tx, rx) = channel();
                 native::task::spawn(proc() { tx.send(f()) });
-                rx.recv();
+                rx.recv_unwrap();
             }
         }
     )
@@ -67,7 +67,7 @@ iotest!(fn eventual_timeout() {
         tx1.send(());
         let _ = rx2.recv_opt();
     });
-    rx1.recv();
+    rx1.recv_unwrap();
 
     let mut v = Vec::new();
     for _ in range(0, 10000) {
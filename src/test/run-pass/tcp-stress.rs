@@ -55,7 +55,7 @@ fn main() {
             stream.write([2]);
         }
     });
-    let addr = rx.recv();
+    let addr = rx.recv_unwrap();
 
     let (tx, rx) = channel();
     for _ in range(0, 1000) {
@@ -82,7 +82,7 @@ fn main() {
     // server just runs infinitely.
     drop(tx);
     for _ in range(0, 1000) {
-        rx.recv();
+        rx.recv_unwrap();
     }
     unsafe { libc::exit(0) }
 }
@@ -20,7 +20,7 @@
 
 extern crate debug;
 
-use std::io::{fs, TempDir};
+use std::io::{fs, File, TempDir, TempFile};
 use std::io;
 use std::os;
 use std::task;
@@ -43,7 +43,7 @@ fn test_rm_tempdir() {
         fail!("fail to unwind past `tmp`");
     };
     task::try(f);
-    let path = rx.recv();
+    let path = rx.recv_unwrap();
     assert!(!path.exists());
 
     let tmp = TempDir::new("test_rm_tempdir").unwrap();
@@ -85,7 +85,7 @@ fn test_rm_tempdir_close() {
         fail!("fail to unwind past `tmp`");
     };
     task::try(f);
-    let path = rx.recv();
+    let path = rx.recv_unwrap();
     assert!(!path.exists());
 
     let tmp = TempDir::new("test_rm_tempdir").unwrap();
@@ -120,6 +120,35 @@ fn test_rm_tempdir_close() {
     assert!(!path.exists());
 }
 
+fn test_tempfile() {
+    let path = {
+        let mut tmp = TempFile::new_in(&Path::new("."), "foobar").unwrap();
+        let path = tmp.path().clone();
+        assert!(path.is_absolute());
+        assert!(path.filename_str().unwrap().starts_with("foobar"));
+        assert!(path.exists());
+        tmp.write(bytes!("hello")).unwrap();
+        tmp.seek(0, io::SeekSet).unwrap();
+        assert_eq!(tmp.read_to_end().unwrap().as_slice(), bytes!("hello"));
+        path
+    };
+    assert!(!path.exists());
+
+    let a = TempFile::new("test_tempfile").unwrap();
+    let b = TempFile::new("test_tempfile").unwrap();
+    assert!(a.path() != b.path());
+    let path = a.path().clone();
+    a.close().unwrap();
+    assert!(!path.exists());
+
+    let (mut file, path) = b.persist();
+    file.write(bytes!("kept")).unwrap();
+    drop(file);
+    assert_eq!(File::open(&path).read_to_end().unwrap().as_slice(),
+               bytes!("kept"));
+    fs::unlink(&path).unwrap();
+}
+
 // Ideally these would be in std::os but then core would need
 // to depend on std
 fn recursive_mkdir_rel() {
@@ -200,6 +229,7 @@ pub fn main() {
     in_tmpdir(test_tempdir);
     in_tmpdir(test_rm_tempdir);
     in_tmpdir(test_rm_tempdir_close);
+    in_tmpdir(test_tempfile);
     in_tmpdir(recursive_mkdir_rel);
     in_tmpdir(recursive_mkdir_dot);
     in_tmpdir(recursive_mkdir_rel_2);
@@ -86,9 +86,9 @@ pub fn main() {
     let (tx3, rx3) = channel();
     let arc3 = arc.clone();
     task::spawn(proc() { check_pedigree(arc3); tx3.send(()); });
-    rx1.recv();
-    rx2.recv();
-    rx3.recv();
+    rx1.recv_unwrap();
+    rx2.recv_unwrap();
+    rx3.recv_unwrap();
 }
 
 fn check_legs(arc: Arc<Vec<Box<Pet:Share+Send>>>) {
@@ -18,6 +18,6 @@ extern crate debug;
 pub fn main() {
     let (tx, rx) = channel();
     tx.send(42);
-    let r = rx.recv();
+    let r = rx.recv_unwrap();
     println!("{:?}", r);
 }
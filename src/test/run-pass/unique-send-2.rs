@@ -28,7 +28,7 @@ pub fn main() {
 
     let mut actual = 0u;
     for _ in range(0u, n) {
-        let j = rx.recv();
+        let j = rx.recv_unwrap();
         actual += *j;
     }
 
@@ -11,6 +11,6 @@
 pub fn main() {
     let (tx, rx) = channel();
     tx.send(box 100);
-    let v = rx.recv();
+    let v = rx.recv_unwrap();
     assert_eq!(v, box 100);
 }
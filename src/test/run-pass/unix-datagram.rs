@@ -0,0 +1,112 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-win32 UNIX domain sockets are not available on windows
+
+// Exercise datagram and abstract namespace UNIX domain sockets, which are only
+// provided by the native runtime (and so can't be `iotest!`s in libstd).
+
+extern crate libc;
+
+use std::io::TempDir;
+use std::io::TimedOut;
+use std::io::net::unix::UnixDatagram;
+
+fn datagram_smoke() {
+    let dir = TempDir::new("unix-datagram").unwrap();
+    let server_path = dir.path().join("server");
+    let client_path = dir.path().join("client");
+
+    let mut server = UnixDatagram::bind(&server_path).unwrap();
+    let mut client = UnixDatagram::bind(&client_path).unwrap();
+    assert!(server_path.exists());
+
+    client.sendto([1, 2, 3], &server_path).unwrap();
+    let mut buf = [0, ..10];
+    let (n, src) = server.recvfrom(buf).unwrap();
+    assert_eq!(buf.slice_to(n), &[1, 2, 3]);
+    assert_eq!(src, Some(client_path.clone()));
+
+    // reply to the sender, and make sure datagram boundaries are kept
+    server.sendto([4], &client_path).unwrap();
+    server.sendto([5, 6], &client_path).unwrap();
+    assert_eq!(client.recvfrom(buf).unwrap(), (1, Some(server_path.clone())));
+    assert_eq!(buf[0], 4);
+    assert_eq!(client.recvfrom(buf).unwrap(), (2, Some(server_path.clone())));
+    assert_eq!(buf.slice_to(2), &[5, 6]);
+
+    // the path lives as long as any clone of the socket
+    let server2 = server.clone();
+    drop(server);
+    assert!(server_path.exists());
+    drop(server2);
+    assert!(!server_path.exists());
+}
+
+fn datagram_unbound() {
+    let dir = TempDir::new("unix-datagram").unwrap();
+    let path = dir.path().join("sock");
+
+    let mut server = UnixDatagram::bind(&path).unwrap();
+    let mut client = UnixDatagram::unbound().unwrap();
+    client.sendto([7], &path).unwrap();
+
+    let mut buf = [0];
+    let (n, _) = server.recvfrom(buf).unwrap();
+    assert_eq!(n, 1);
+    assert_eq!(buf[0], 7);
+}
+
+fn datagram_timeout() {
+    let dir = TempDir::new("unix-datagram").unwrap();
+    let mut sock = UnixDatagram::bind(&dir.path().join("sock")).unwrap();
+    sock.set_read_timeout(Some(20));
+    assert_eq!(sock.recvfrom([0]).err().unwrap().kind, TimedOut);
+}
+
+#[cfg(target_os = "linux")]
+fn abstract_namespace() {
+    use std::io::net::unix::{UnixListener, UnixStream};
+    use std::io::{Listener, Acceptor};
+
+    let name = format!("@rust-unix-abstract-{}", unsafe { libc::getpid() });
+
+    let mut acceptor = UnixListener::bind(&name).listen().unwrap();
+    assert!(!Path::new(name.as_slice()).exists());
+    let name2 = name.clone();
+    spawn(proc() {
+        let mut s = UnixStream::connect(&name2).unwrap();
+        s.write([8]).unwrap();
+    });
+    let mut s = acceptor.accept().unwrap();
+    assert_eq!(s.read_byte().unwrap(), 8);
+    drop(s);
+    drop(acceptor);
+
+    let server_name = format!("{}-server", name);
+    let client_name = format!("{}-client", name);
+    let mut server = UnixDatagram::bind(&server_name).unwrap();
+    let mut client = UnixDatagram::bind(&client_name).unwrap();
+    client.sendto([9], &server_name).unwrap();
+    let mut buf = [0];
+    let (_, src) = server.recvfrom(buf).unwrap();
+    assert_eq!(buf[0], 9);
+    assert_eq!(src, Some(Path::new(client_name.as_slice())));
+}
+
+#[cfg(not(target_os = "linux"))]
+fn abstract_namespace() {}
+
+pub fn main() {
+    datagram_smoke();
+    datagram_unbound();
+    datagram_timeout();
+    abstract_namespace();
+}
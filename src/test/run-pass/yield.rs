@@ -20,7 +20,7 @@ pub fn main() {
     println!("2");
     task::deschedule();
     println!("3");
-    result.recv();
+    result.recv_unwrap();
 }
 
 fn child() {
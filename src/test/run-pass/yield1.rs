@@ -17,7 +17,7 @@ pub fn main() {
     builder.spawn(child);
     println!("1");
     task::deschedule();
-    result.recv();
+    result.recv_unwrap();
 }
 
 fn child() { println!("2"); }